@@ -2,7 +2,9 @@
 fn main() -> anyhow::Result<()> {
     use std::{fs, path::PathBuf};
 
-    let schemas_dir = PathBuf::from("dist/schemas/v1");
+    let schemas_dir = std::env::args_os()
+        .nth(1)
+        .map_or_else(|| PathBuf::from("dist/schemas/v1"), PathBuf::from);
     greentic_types::write_all_schemas(&schemas_dir)?;
 
     let dist_root = PathBuf::from("dist");
@@ -0,0 +1,67 @@
+//! Embeds the committed JSON Schemas from `schemas/v1` for `schema::embedded()`.
+//!
+//! Each schema is pulled in with `include_str!` and its Blake3 digest is computed here from the
+//! same bytes, so nothing is regenerated or hashed at runtime.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+const SCHEMA_DIR: &str = "schemas/v1";
+const SCHEMA_SUFFIX: &str = ".schema.json";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SCHEMA_DIR}");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"));
+    let schema_dir = manifest_dir.join(SCHEMA_DIR);
+    let mut schemas = Vec::new();
+    for entry in fs::read_dir(&schema_dir).expect("read schemas/v1") {
+        let path = entry.expect("schema dir entry").path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !file_name.ends_with(SCHEMA_SUFFIX) {
+            continue;
+        }
+        println!("cargo:rerun-if-changed={}", path.display());
+        let json = fs::read_to_string(&path).expect("read schema");
+        let id =
+            schema_id(&json).unwrap_or_else(|| panic!("{} has no top-level `$id`", path.display()));
+        let digest = blake3::hash(json.as_bytes()).to_hex();
+        schemas.push((id.to_owned(), file_name.to_owned(), digest.to_string()));
+    }
+    schemas.sort();
+
+    let mut out = String::from("&[\n");
+    for (id, file_name, digest) in &schemas {
+        writeln!(
+            out,
+            "    EmbeddedSchema {{ id: {id:?}, file_name: {file_name:?}, json: include_str!({path:?}), digest_hex: {digest:?} }},",
+            path = schema_dir.join(file_name).display().to_string(),
+        )
+        .expect("write to string");
+    }
+    out.push(']');
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR"));
+    write_if_changed(&out_dir.join("embedded_schemas.rs"), &out);
+}
+
+/// Reads the `"$id"` member that `write_all_schemas` places first in every document.
+fn schema_id(json: &str) -> Option<&str> {
+    let rest = json.trim_start().strip_prefix('{')?.trim_start();
+    let rest = rest
+        .strip_prefix("\"$id\"")?
+        .trim_start()
+        .strip_prefix(':')?;
+    let rest = rest.trim_start().strip_prefix('"')?;
+    rest.split_once('"').map(|(id, _)| id)
+}
+
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+        return;
+    }
+    fs::write(path, contents).expect("write embedded_schemas.rs");
+}
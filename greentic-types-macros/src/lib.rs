@@ -1,6 +1,9 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Attribute, ItemFn, LitStr, ReturnType, Type, meta, parse::Parser, spanned::Spanned};
+use syn::{
+    Attribute, FnArg, GenericArgument, ItemFn, LitStr, PathArguments, ReturnType, Type, meta,
+    parse::Parser, spanned::Spanned,
+};
 
 /// Automatically installs Greentic telemetry at runtime entry-points.
 ///
@@ -61,6 +64,115 @@ fn expand_main(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream>
     Ok(expanded.into())
 }
 
+/// Generates `ComponentOperation` metadata from a typed operation handler.
+///
+/// The handler takes at most one input argument besides `self`; its type (behind any reference)
+/// is the operation input. The return type is the output, unwrapped from a `Result`-like type.
+/// A sibling `<handler>_operation()` function returns the `ComponentOperation` with both JSON
+/// Schemas generated through `schemars`, so the contract cannot drift from the code.
+///
+/// ```ignore
+/// #[greentic_types::greentic_operation(name = "handle_message")]
+/// fn handle(input: MessageIn) -> Result<MessageOut, NodeError> {
+///     // ...
+/// }
+///
+/// let operation = handle_operation();
+/// assert_eq!(operation.name, "handle_message");
+/// ```
+#[proc_macro_attribute]
+pub fn greentic_operation(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand_operation(args, item).unwrap_or_else(|err| err.to_compile_error().into())
+}
+
+fn expand_operation(args: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let item_fn: ItemFn = syn::parse(item)?;
+    let ident = &item_fn.sig.ident;
+    let name = parse_operation_name(args)?
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+
+    let mut inputs = item_fn.sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Typed(pat) => Some(&pat.ty),
+        FnArg::Receiver(_) => None,
+    });
+    let input = match (inputs.next(), inputs.next()) {
+        (None, _) => quote! { () },
+        (Some(ty), None) => {
+            let ty = strip_reference(ty);
+            quote! { #ty }
+        }
+        (Some(_), Some(extra)) => {
+            return Err(syn::Error::new(
+                extra.span(),
+                "`#[greentic_operation]` handlers take a single input argument",
+            ));
+        }
+    };
+    let output = match &item_fn.sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => {
+            let ty = result_ok_type(ty).unwrap_or(ty);
+            quote! { #ty }
+        }
+    };
+
+    let vis = &item_fn.vis;
+    let operation_ident = format_ident!("{}_operation", ident);
+    let doc = format!("Contract of the `{}` operation.", name.value());
+    let expanded = quote! {
+        #item_fn
+
+        #[doc = #doc]
+        #vis fn #operation_ident() -> ::greentic_types::ComponentOperation {
+            ::greentic_types::ComponentOperation::from_types::<#input, #output>(#name)
+        }
+    };
+
+    Ok(expanded.into())
+}
+
+fn parse_operation_name(args: TokenStream) -> syn::Result<Option<LitStr>> {
+    let mut name = None;
+    let parser = meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            if name.is_some() {
+                return Err(meta.error("name specified more than once"));
+            }
+            name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `name = \"...\"`"))
+        }
+    });
+
+    parser.parse2(proc_macro2::TokenStream::from(args))?;
+    Ok(name)
+}
+
+fn strip_reference(ty: &Type) -> &Type {
+    match ty {
+        Type::Reference(reference) => strip_reference(&reference.elem),
+        _ => ty,
+    }
+}
+
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if !segment.ident.to_string().ends_with("Result") {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
 fn parse_service_name(args: TokenStream) -> syn::Result<LitStr> {
     let mut service_name = None;
     let parser = meta::parser(|meta| {
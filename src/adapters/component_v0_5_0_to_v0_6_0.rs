@@ -32,7 +32,10 @@ pub fn adapt_component_qa_spec(
     Ok(CborBytes::new(bytes))
 }
 
-fn map_component_qa_spec(mode: QaMode, legacy: &LegacyComponentQaSpec) -> ComponentQaSpec {
+pub(crate) fn map_component_qa_spec(
+    mode: QaMode,
+    legacy: &LegacyComponentQaSpec,
+) -> ComponentQaSpec {
     let title_key = "legacy.component.v0_5_0.title".to_string();
     let description_key = "legacy.component.v0_5_0.description".to_string();
 
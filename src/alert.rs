@@ -0,0 +1,274 @@
+//! Operational alert records raised by runtimes, distributors, and supply-chain tooling.
+//!
+//! An [`Alert`] links back to the records that triggered it through [`AlertSubject`] references
+//! and moves through the [`AlertState`] lifecycle `firing → acknowledged → resolved`.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    AlertId, EnvironmentRef, ErrorCode, FindingSeverity, FlowId, GResult, GreenticError, PackId,
+    RolloutStatus, RunResult, ScanRef, ScanResult, SessionKey, TenantCtx, UnixMillis, UserId,
+};
+
+/// Alert severity, ordered from least to most severe.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum AlertSeverity {
+    /// Informational; no action required.
+    #[default]
+    Info,
+    /// Degradation that needs attention.
+    Warning,
+    /// Failure affecting a tenant workload.
+    Error,
+    /// Outage or security issue requiring immediate action.
+    Critical,
+}
+
+impl From<FindingSeverity> for AlertSeverity {
+    fn from(value: FindingSeverity) -> Self {
+        match value {
+            FindingSeverity::Unknown | FindingSeverity::Info => Self::Info,
+            FindingSeverity::Low | FindingSeverity::Medium => Self::Warning,
+            FindingSeverity::High => Self::Error,
+            FindingSeverity::Critical => Self::Critical,
+        }
+    }
+}
+
+/// Lifecycle state of an alert.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum AlertState {
+    /// Condition is active and nobody has taken ownership.
+    #[default]
+    Firing,
+    /// Someone is handling the alert.
+    Acknowledged,
+    /// Condition cleared.
+    Resolved,
+}
+
+/// Record an alert refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum AlertSubject {
+    /// Flow run reported through a [`RunResult`](crate::RunResult).
+    Run {
+        /// Session the run executed in.
+        session_id: SessionKey,
+        /// Pack that was executed.
+        pack_id: PackId,
+        /// Flow that was executed.
+        flow_id: FlowId,
+    },
+    /// Environment rollout reported through a [`RolloutStatus`].
+    Rollout {
+        /// Target environment.
+        environment_ref: EnvironmentRef,
+        /// Desired state version being rolled out.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        desired_state_version: Option<u64>,
+    },
+    /// Scan reported through a [`ScanResult`].
+    Scan {
+        /// Scan identifier.
+        scan_id: ScanRef,
+    },
+}
+
+impl From<&RunResult> for AlertSubject {
+    fn from(run: &RunResult) -> Self {
+        Self::Run {
+            session_id: run.session_id.clone(),
+            pack_id: run.pack_id.clone(),
+            flow_id: run.flow_id.clone(),
+        }
+    }
+}
+
+impl From<&RolloutStatus> for AlertSubject {
+    fn from(rollout: &RolloutStatus) -> Self {
+        Self::Rollout {
+            environment_ref: rollout.environment_ref.clone(),
+            desired_state_version: rollout.desired_state_version,
+        }
+    }
+}
+
+impl From<&ScanResult> for AlertSubject {
+    fn from(scan: &ScanResult) -> Self {
+        Self::Scan {
+            scan_id: scan.scan_id.clone(),
+        }
+    }
+}
+
+/// Operational alert shared across runtimes and distributors.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct Alert {
+    /// Alert identifier.
+    pub id: AlertId,
+    /// Tenant affected by the alert.
+    pub tenant: TenantCtx,
+    /// Component that raised the alert (for example `runner` or `distributor`).
+    pub source: String,
+    /// Severity.
+    pub severity: AlertSeverity,
+    /// Short summary.
+    pub title: String,
+    /// Optional detailed description.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub message: Option<String>,
+    /// Records that triggered the alert.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub related: Vec<AlertSubject>,
+    /// Lifecycle state.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub state: AlertState,
+    /// Deduplication key; alerts sharing a fingerprint describe the same condition.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub fingerprint: Option<String>,
+    /// User who acknowledged the alert.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub acknowledged_by: Option<UserId>,
+    /// Time the alert started firing (UTC).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub raised_at_utc: Option<UnixMillis>,
+    /// Time the alert was resolved (UTC).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub resolved_at_utc: Option<UnixMillis>,
+    /// Routing labels (for example `team=payments`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub labels: BTreeMap<String, String>,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl Alert {
+    /// Creates a firing alert.
+    pub fn new(
+        id: AlertId,
+        tenant: TenantCtx,
+        source: impl Into<String>,
+        severity: AlertSeverity,
+        title: impl Into<String>,
+    ) -> Self {
+        Self {
+            id,
+            tenant,
+            source: source.into(),
+            severity,
+            title: title.into(),
+            message: None,
+            related: Vec::new(),
+            state: AlertState::Firing,
+            fingerprint: None,
+            acknowledged_by: None,
+            raised_at_utc: None,
+            resolved_at_utc: None,
+            labels: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a related record.
+    pub fn with_subject(mut self, subject: impl Into<AlertSubject>) -> Self {
+        self.related.push(subject.into());
+        self
+    }
+
+    /// Returns `true` while the alert is not resolved.
+    pub fn is_open(&self) -> bool {
+        self.state != AlertState::Resolved
+    }
+
+    /// Marks the alert as acknowledged by `user`.
+    ///
+    /// Acknowledging twice is a no-op that keeps the first acknowledger; resolved alerts are
+    /// rejected with [`ErrorCode::Conflict`].
+    pub fn acknowledge(&mut self, user: Option<UserId>) -> GResult<()> {
+        match self.state {
+            AlertState::Firing => {
+                self.state = AlertState::Acknowledged;
+                self.acknowledged_by = user;
+                Ok(())
+            }
+            AlertState::Acknowledged => Ok(()),
+            AlertState::Resolved => Err(GreenticError::new(
+                ErrorCode::Conflict,
+                format!("alert {} is already resolved", self.id),
+            )),
+        }
+    }
+
+    /// Marks the alert as resolved.
+    pub fn resolve(&mut self) {
+        self.state = AlertState::Resolved;
+    }
+
+    /// Moves a resolved or acknowledged alert back to firing when its condition recurs.
+    pub fn refire(&mut self) {
+        self.state = AlertState::Firing;
+        self.acknowledged_by = None;
+        self.resolved_at_utc = None;
+    }
+}
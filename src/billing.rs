@@ -0,0 +1,355 @@
+//! Usage metering shared by runners and billing services.
+//!
+//! Runners emit one [`UsageRecord`] per measurement window; billing services deduplicate them by
+//! idempotency key and roll them up into [`UsageAggregate`]s that can be priced against a metered
+//! [`PriceModel`]. A [`SubscriptionUsage`] snapshot collects a subscription's aggregates for one
+//! billing window, which is what invoicing, plan-limit enforcement, and the tenant console read.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    ErrorCode, GResult, GreenticError, MoneyError, MoneyMicro, PriceModel, SubscriptionId,
+    UnixMillis,
+};
+
+/// Units consumed by a subscription during a window.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct UsageRecord {
+    /// Subscription the usage is billed to.
+    pub subscription_id: SubscriptionId,
+    /// Metered unit label, as declared by [`PriceModel::Metered`].
+    pub unit_label: String,
+    /// Units consumed.
+    pub quantity: u64,
+    /// Start of the measurement window (inclusive).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub window_start: UnixMillis,
+    /// End of the measurement window (exclusive).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub window_end: UnixMillis,
+    /// Key identifying the measurement; repeated deliveries carry the same key.
+    pub idempotency_key: String,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl UsageRecord {
+    /// Creates a record without metadata.
+    pub fn new(
+        subscription_id: SubscriptionId,
+        unit_label: impl Into<String>,
+        quantity: u64,
+        window_start: UnixMillis,
+        window_end: UnixMillis,
+        idempotency_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            subscription_id,
+            unit_label: unit_label.into(),
+            quantity,
+            window_start,
+            window_end,
+            idempotency_key: idempotency_key.into(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Ensures the unit label and idempotency key are set and the window is not inverted.
+    pub fn validate(&self) -> GResult<()> {
+        if self.unit_label.is_empty() {
+            return Err(self.invalid("unit_label must not be empty"));
+        }
+        if self.idempotency_key.is_empty() {
+            return Err(self.invalid("idempotency_key must not be empty"));
+        }
+        if self.window_end < self.window_start {
+            return Err(self.invalid("window_end precedes window_start"));
+        }
+        Ok(())
+    }
+
+    fn invalid(&self, reason: &str) -> GreenticError {
+        GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "usage record for subscription {}: {reason}",
+                self.subscription_id.as_str()
+            ),
+        )
+    }
+}
+
+/// Total usage of one unit by one subscription.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct UsageAggregate {
+    /// Subscription the usage is billed to.
+    pub subscription_id: SubscriptionId,
+    /// Metered unit label.
+    pub unit_label: String,
+    /// Sum of the record quantities, saturating on overflow.
+    pub quantity: u64,
+    /// Earliest record window start.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub window_start: UnixMillis,
+    /// Latest record window end.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub window_end: UnixMillis,
+    /// Number of distinct records included.
+    pub record_count: u64,
+}
+
+impl UsageAggregate {
+    /// Rolls `records` up per subscription and unit label.
+    ///
+    /// Records repeating an idempotency key already seen for the same subscription are skipped,
+    /// so redelivered measurements are counted once. Aggregates are ordered by subscription and
+    /// unit label.
+    pub fn from_records<'a>(records: impl IntoIterator<Item = &'a UsageRecord>) -> Vec<Self> {
+        let mut seen = BTreeSet::new();
+        let mut aggregates: BTreeMap<(SubscriptionId, String), Self> = BTreeMap::new();
+        for record in records {
+            if !seen.insert((&record.subscription_id, record.idempotency_key.as_str())) {
+                continue;
+            }
+            aggregates
+                .entry((record.subscription_id.clone(), record.unit_label.clone()))
+                .and_modify(|aggregate| aggregate.add(record))
+                .or_insert_with(|| Self {
+                    subscription_id: record.subscription_id.clone(),
+                    unit_label: record.unit_label.clone(),
+                    quantity: record.quantity,
+                    window_start: record.window_start,
+                    window_end: record.window_end,
+                    record_count: 1,
+                });
+        }
+        aggregates.into_values().collect()
+    }
+
+    /// Units billed at the overage rate of `price`.
+    ///
+    /// Returns `None` unless `price` is metered on this aggregate's unit label.
+    pub fn overage_units(&self, price: &PriceModel) -> Option<u64> {
+        self.metered_terms(price)
+            .map(|(included_units, _)| self.quantity.saturating_sub(included_units))
+    }
+
+    /// Overage charge under `price`, saturating on overflow.
+    pub fn overage_cost(&self, price: &PriceModel) -> Option<MoneyMicro> {
+        self.metered_terms(price).map(|(included_units, rate)| {
+            rate.saturating_mul(self.quantity.saturating_sub(included_units))
+        })
+    }
+
+    /// Included units and overage rate when `price` meters this unit label.
+    fn metered_terms<'p>(&self, price: &'p PriceModel) -> Option<(u64, &'p MoneyMicro)> {
+        match price {
+            PriceModel::Metered {
+                included_units,
+                overage_rate,
+                unit_label,
+            } if *unit_label == self.unit_label => Some((*included_units, overage_rate)),
+            _ => None,
+        }
+    }
+
+    fn add(&mut self, record: &UsageRecord) {
+        self.quantity = self.quantity.saturating_add(record.quantity);
+        self.window_start = self.window_start.min(record.window_start);
+        self.window_end = self.window_end.max(record.window_end);
+        self.record_count += 1;
+    }
+}
+
+/// Consumption of one metered unit within a [`SubscriptionUsage`] window.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct UnitUsage {
+    /// Units consumed.
+    pub consumed: u64,
+    /// Units included in the plan, when the plan meters this unit.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub included: Option<u64>,
+    /// Units consumed beyond `included`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub overage: u64,
+}
+
+/// Usage of one subscription over a billing window, keyed by unit label.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SubscriptionUsage {
+    /// Subscription the usage belongs to.
+    pub subscription_id: SubscriptionId,
+    /// Start of the billing window (inclusive).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub window_start: UnixMillis,
+    /// End of the billing window (exclusive).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub window_end: UnixMillis,
+    /// Consumption per metered unit label.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub units: BTreeMap<String, UnitUsage>,
+    /// Overage charge across all units; `None` until a metered price has been applied.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub overage_cost: Option<MoneyMicro>,
+}
+
+impl SubscriptionUsage {
+    /// Creates an empty snapshot for the window.
+    pub fn new(
+        subscription_id: SubscriptionId,
+        window_start: UnixMillis,
+        window_end: UnixMillis,
+    ) -> Self {
+        Self {
+            subscription_id,
+            window_start,
+            window_end,
+            units: BTreeMap::new(),
+            overage_cost: None,
+        }
+    }
+
+    /// Builds a snapshot from the aggregates belonging to `subscription_id`, priced against
+    /// `price`.
+    ///
+    /// Aggregates for other subscriptions are ignored. Overage is only computed for the unit
+    /// label metered by `price`; other units are reported as consumed without an allowance.
+    pub fn from_aggregates<'a>(
+        subscription_id: SubscriptionId,
+        window_start: UnixMillis,
+        window_end: UnixMillis,
+        aggregates: impl IntoIterator<Item = &'a UsageAggregate>,
+        price: &PriceModel,
+    ) -> Result<Self, MoneyError> {
+        let mut usage = Self::new(subscription_id, window_start, window_end);
+        for aggregate in aggregates {
+            if aggregate.subscription_id == usage.subscription_id {
+                usage.add(&aggregate.unit_label, aggregate.quantity, price)?;
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Adds `quantity` consumed units of `unit_label`, recomputing overage under `price`.
+    ///
+    /// Only overage beyond what earlier calls already charged adds to the overage cost, so a price
+    /// with a larger allowance never lowers it. The overage cost is kept in the currency of the
+    /// first metered price applied. A metered price in another currency fails with
+    /// [`MoneyError::CurrencyMismatch`] and leaves the snapshot unchanged; an overage cost beyond
+    /// `u64::MAX` micro-units is clamped.
+    pub fn add(
+        &mut self,
+        unit_label: &str,
+        quantity: u64,
+        price: &PriceModel,
+    ) -> Result<(), MoneyError> {
+        if let (
+            Some(total),
+            PriceModel::Metered {
+                overage_rate,
+                unit_label: metered,
+                ..
+            },
+        ) = (&self.overage_cost, price)
+            && metered == unit_label
+            && total.currency != overage_rate.currency
+        {
+            return Err(MoneyError::CurrencyMismatch {
+                expected: total.currency.clone(),
+                found: overage_rate.currency.clone(),
+            });
+        }
+        let unit = self.units.entry(unit_label.into()).or_default();
+        unit.consumed = unit.consumed.saturating_add(quantity);
+        match price {
+            PriceModel::Metered {
+                included_units,
+                overage_rate,
+                unit_label: metered,
+            } if metered == unit_label => {
+                let previous = unit.overage;
+                unit.included = Some(*included_units);
+                unit.overage = unit.consumed.saturating_sub(*included_units);
+                // A later price with a larger allowance can shrink the overage; units already
+                // charged stay charged.
+                let charge = overage_rate.saturating_mul(unit.overage.saturating_sub(previous));
+                self.overage_cost = Some(match self.overage_cost.take() {
+                    None => charge,
+                    Some(total) => match total.checked_add(&charge) {
+                        Ok(sum) => sum,
+                        Err(_) => MoneyMicro::new(u64::MAX, total.currency),
+                    },
+                });
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Units of `unit_label` consumed in the window.
+    pub fn consumed(&self, unit_label: &str) -> u64 {
+        self.units.get(unit_label).map_or(0, |unit| unit.consumed)
+    }
+
+    /// Units still available before `unit_label` goes into overage.
+    ///
+    /// Returns `None` when the plan does not include an allowance for the unit.
+    pub fn remaining(&self, unit_label: &str) -> Option<u64> {
+        let unit = self.units.get(unit_label)?;
+        unit.included
+            .map(|included| included.saturating_sub(unit.consumed))
+    }
+
+    /// Returns `true` when any unit consumed beyond its allowance.
+    pub fn has_overage(&self) -> bool {
+        self.units.values().any(|unit| unit.overage > 0)
+    }
+}
@@ -0,0 +1,120 @@
+//! References to payload bytes held in an external content store.
+//!
+//! Attachments, run artifacts, and worker payloads that are too large to inline carry a
+//! [`BlobRef`] instead; receivers fetch the bytes from `store` and check them with
+//! [`BlobRef::verify`].
+
+use alloc::format;
+use alloc::string::String;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorCode, GResult, GreenticError, HashAlgorithm, HashDigest, StoreRef, UnixMillis};
+
+/// Bytes stored outside the message that references them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BlobRef {
+    /// Content store holding the blob.
+    pub store: StoreRef,
+    /// Key of the blob within the store.
+    pub key: String,
+    /// Size of the blob in bytes.
+    pub size_bytes: u64,
+    /// MIME type of the blob, when known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub content_type: Option<String>,
+    /// Digest of the blob contents.
+    pub digest: HashDigest,
+    /// Instant after which the store may delete the blob.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub expires_at: Option<UnixMillis>,
+}
+
+impl BlobRef {
+    /// Creates a non-expiring reference without a content type.
+    pub fn new(
+        store: StoreRef,
+        key: impl Into<String>,
+        size_bytes: u64,
+        digest: HashDigest,
+    ) -> Self {
+        Self {
+            store,
+            key: key.into(),
+            size_bytes,
+            content_type: None,
+            digest,
+            expires_at: None,
+        }
+    }
+
+    /// Sets the MIME type.
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Sets the expiry instant.
+    pub fn with_expires_at(mut self, expires_at: UnixMillis) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Returns `true` when the blob may already have been deleted at `now`.
+    pub fn is_expired(&self, now: UnixMillis) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Checks fetched `bytes` against the recorded size and digest.
+    ///
+    /// Only Blake3 digests can be recomputed; other algorithms fail with
+    /// [`ErrorCode::Unavailable`].
+    pub fn verify(&self, bytes: &[u8]) -> GResult<()> {
+        if bytes.len() as u64 != self.size_bytes {
+            return Err(self.invalid(&format!(
+                "has {} bytes, expected {}",
+                bytes.len(),
+                self.size_bytes
+            )));
+        }
+        match &self.digest.algo {
+            HashAlgorithm::Blake3 => {
+                let actual = blake3::hash(bytes).to_hex();
+                if !actual.as_str().eq_ignore_ascii_case(&self.digest.hex) {
+                    return Err(self.invalid("does not match its digest"));
+                }
+                Ok(())
+            }
+            HashAlgorithm::Other(algo) => Err(GreenticError::new(
+                ErrorCode::Unavailable,
+                format!("blob {}: cannot verify {algo} digests", self.key),
+            )),
+        }
+    }
+
+    fn invalid(&self, reason: &str) -> GreenticError {
+        GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("blob {}: {reason}", self.key),
+        )
+    }
+}
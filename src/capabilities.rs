@@ -2,7 +2,9 @@
 
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
-use crate::{AllowList, NetworkPolicy, SecretRequirement};
+use crate::{
+    AllowList, ByteSize, DurationMs, ErrorCode, GreenticError, NetworkPolicy, SecretRequirement,
+};
 
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
@@ -87,7 +89,7 @@ pub struct HttpCaps {
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub max_body_bytes: Option<u64>,
+    pub max_body_bytes: Option<ByteSize>,
 }
 
 impl HttpCaps {
@@ -224,10 +226,22 @@ impl ToolsCaps {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub struct Limits {
-    /// Memory ceiling per flow instance (in megabytes).
-    pub memory_mb: u32,
-    /// Wall-clock budget per invocation (milliseconds).
-    pub wall_time_ms: u64,
+    /// Memory ceiling per flow instance.
+    ///
+    /// Serialized as `memory_mb`; bare numbers are mebibytes and strings such as `"512MiB"` are
+    /// accepted.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "memory_mb", with = "crate::units::mebibytes")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::units::MebibyteSize"))]
+    pub memory: ByteSize,
+    /// Wall-clock budget per invocation.
+    ///
+    /// Serialized as `wall_time_ms`; bare numbers are milliseconds and strings such as `"30s"`
+    /// are accepted.
+    #[cfg_attr(feature = "serde", serde(rename = "wall_time_ms"))]
+    pub wall_time: DurationMs,
     /// Optional fuel/step counter for deterministic engines.
     #[cfg_attr(
         feature = "serde",
@@ -240,23 +254,70 @@ pub struct Limits {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub files: Option<u32>,
+    /// Optional ceiling for a single envelope payload, enforced at ingress.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_payload_bytes: Option<ByteSize>,
 }
 
 impl Limits {
     /// Creates a new limit declaration.
-    pub fn new(memory_mb: u32, wall_time_ms: u64) -> Self {
+    pub fn new(memory: ByteSize, wall_time: DurationMs) -> Self {
         Self {
-            memory_mb,
-            wall_time_ms,
+            memory,
+            wall_time,
             fuel: None,
             files: None,
+            max_payload_bytes: None,
+        }
+    }
+
+    /// Sets the payload size ceiling.
+    pub fn with_max_payload_bytes(mut self, max: ByteSize) -> Self {
+        self.max_payload_bytes = Some(max);
+        self
+    }
+
+    /// Checks a payload of `size` against [`Limits::max_payload_bytes`].
+    pub fn check_payload_size(&self, size: ByteSize) -> Result<(), EnvelopeLimitError> {
+        match self.max_payload_bytes {
+            Some(limit) if size > limit => Err(EnvelopeLimitError::PayloadTooLarge { size, limit }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Envelope rejected by [`Limits`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum EnvelopeLimitError {
+    /// Payload exceeds [`Limits::max_payload_bytes`].
+    #[error("payload of {size} exceeds the {limit} limit")]
+    PayloadTooLarge {
+        /// Measured payload size.
+        size: ByteSize,
+        /// Configured ceiling.
+        limit: ByteSize,
+    },
+}
+
+impl From<EnvelopeLimitError> for GreenticError {
+    fn from(err: EnvelopeLimitError) -> Self {
+        let message = err.to_string();
+        match err {
+            EnvelopeLimitError::PayloadTooLarge { size, limit } => {
+                GreenticError::new(ErrorCode::InvalidInput, message)
+                    .with_field("size_bytes", size.as_bytes())
+                    .with_field("limit_bytes", limit.as_bytes())
+            }
         }
     }
 }
 
 impl Default for Limits {
     fn default() -> Self {
-        Self::new(0, 0)
+        Self::new(ByteSize::ZERO, DurationMs::ZERO)
     }
 }
 
@@ -1,5 +1,5 @@
 //! Helpers for deterministic canonical CBOR, hashing, and Base32 IDs.
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use core::cmp::Ordering;
 
 use blake3;
@@ -7,6 +7,8 @@ use ciborium::{de::from_reader, ser::into_writer, value::Value};
 use serde::{Serialize, de::DeserializeOwned};
 use thiserror::Error;
 
+use crate::{HashAlgorithm, HashDigest};
+
 /// Errors emitted while canonicalizing or hashing CBOR payloads.
 #[derive(Debug, Error)]
 pub enum CanonicalError {
@@ -60,13 +62,23 @@ pub fn ensure_canonical(bytes: &[u8]) -> Result<()> {
 /// Parse CBOR and re-encode in canonical form.
 pub fn canonicalize(bytes: &[u8]) -> Result<Vec<u8>> {
     let value: Value = from_reader(bytes).map_err(|err| CanonicalError::Decode(err.to_string()))?;
-    let canonical = canonicalize_value(value)?;
+    let canonical = canonicalize_value(value, FloatPolicy::Reject)?;
     let mut buf = Vec::new();
     into_writer(&canonical, &mut buf).map_err(|err| CanonicalError::Encode(err.to_string()))?;
     Ok(buf)
 }
 
-fn canonicalize_value(value: Value) -> Result<Value> {
+/// How canonicalisation treats floating-point values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Floats fail with [`CanonicalError::FloatNotAllowed`].
+    Reject,
+    /// Finite floats are kept and encoded in the shortest IEEE 754 width that preserves their
+    /// value, as in RFC 8949 deterministic encoding; NaN and infinities are still rejected.
+    Shortest,
+}
+
+fn canonicalize_value(value: Value, floats: FloatPolicy) -> Result<Value> {
     match value {
         Value::Integer(_) | Value::Bytes(_) | Value::Text(_) | Value::Bool(_) | Value::Null => {
             Ok(value)
@@ -74,7 +86,7 @@ fn canonicalize_value(value: Value) -> Result<Value> {
         Value::Array(elements) => {
             let canonical_elements = elements
                 .into_iter()
-                .map(canonicalize_value)
+                .map(|element| canonicalize_value(element, floats))
                 .collect::<Result<Vec<_>>>()?;
             Ok(Value::Array(canonical_elements))
         }
@@ -86,13 +98,14 @@ fn canonicalize_value(value: Value) -> Result<Value> {
                         Value::Text(text) => Value::Text(text),
                         _ => return Err(CanonicalError::NonStringMapKey),
                     };
-                    let canonical_val = canonicalize_value(val)?;
+                    let canonical_val = canonicalize_value(val, floats)?;
                     Ok((canonical_key, canonical_val))
                 })
                 .collect::<Result<Vec<_>>>()?;
             canonical_entries.sort_unstable_by(|(a, _), (b, _)| compare_map_keys(a, b));
             Ok(Value::Map(canonical_entries))
         }
+        Value::Float(float) if floats == FloatPolicy::Shortest && float.is_finite() => Ok(value),
         Value::Float(_) => Err(CanonicalError::FloatNotAllowed),
         Value::Tag(_, _) => Err(CanonicalError::TagNotAllowed),
         _ => Err(CanonicalError::TagNotAllowed),
@@ -165,6 +178,100 @@ pub fn blake3_128(bytes: &[u8]) -> [u8; 16] {
     out
 }
 
+/// Content digest shared by caches and change detection across services.
+///
+/// The digest is the Blake3 hash of the canonical CBOR encoding of [`StableHash::stable_value`],
+/// so two services agree on it regardless of map insertion order or serialization format used in
+/// transit. Values containing floats cannot be hashed unless the type opts into
+/// [`FloatPolicy::Shortest`].
+pub trait StableHash: Serialize + Sized {
+    /// Treatment of floating-point values while hashing.
+    const FLOATS: FloatPolicy = FloatPolicy::Reject;
+
+    /// Returns the CBOR value that is canonicalised and hashed.
+    ///
+    /// Defaults to the serialized form. Types holding maps whose entry order is meaningful
+    /// override it and pass those maps through [`ordered_entries`], since canonicalisation sorts
+    /// every map by key.
+    fn stable_value(&self) -> Result<Value> {
+        Value::serialized(self).map_err(|err| CanonicalError::Encode(err.to_string()))
+    }
+
+    /// Returns the stable digest of this value.
+    fn stable_hash(&self) -> Result<HashDigest> {
+        let canonical = canonicalize_value(self.stable_value()?, Self::FLOATS)?;
+        let mut bytes = Vec::new();
+        into_writer(&canonical, &mut bytes)
+            .map_err(|err| CanonicalError::Encode(err.to_string()))?;
+        Ok(HashDigest {
+            algo: HashAlgorithm::Blake3,
+            hex: blake3::hash(&bytes).to_hex().as_str().into(),
+        })
+    }
+}
+
+/// Rewrites the map stored under `field` of `value` as an array of `[key, value]` pairs, keeping
+/// its entry order through canonicalisation.
+///
+/// Does nothing when `value` is not a map or `field` does not hold a map.
+pub fn ordered_entries(value: &mut Value, field: &str) {
+    let Some(slot) = map_field_mut(value, field) else {
+        return;
+    };
+    if let Value::Map(entries) = slot {
+        let pairs = core::mem::take(entries)
+            .into_iter()
+            .map(|(key, value)| Value::Array(vec![key, value]))
+            .collect();
+        *slot = Value::Array(pairs);
+    }
+}
+
+/// Returns the value stored under the text key `field` of a CBOR map.
+pub(crate) fn map_field_mut<'a>(value: &'a mut Value, field: &str) -> Option<&'a mut Value> {
+    match value {
+        Value::Map(entries) => entries
+            .iter_mut()
+            .find(|(key, _)| matches!(key, Value::Text(text) if text == field))
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+impl StableHash for crate::PackManifest {
+    const FLOATS: FloatPolicy = FloatPolicy::Shortest;
+
+    /// Keeps the node order of every embedded flow; see [`crate::Flow::content_digest`].
+    fn stable_value(&self) -> Result<Value> {
+        let mut value =
+            Value::serialized(self).map_err(|err| CanonicalError::Encode(err.to_string()))?;
+        if let Some(Value::Array(flows)) = map_field_mut(&mut value, "flows") {
+            for entry in flows {
+                if let Some(flow) = map_field_mut(entry, "flow") {
+                    ordered_entries(flow, "nodes");
+                }
+            }
+        }
+        Ok(value)
+    }
+}
+
+impl StableHash for crate::Flow {
+    /// Mappings and metadata are free-form JSON, whose numbers may be floats.
+    const FLOATS: FloatPolicy = FloatPolicy::Shortest;
+
+    /// Keeps the node order, since the first node is the flow's ingress.
+    fn stable_value(&self) -> Result<Value> {
+        let mut value =
+            Value::serialized(self).map_err(|err| CanonicalError::Encode(err.to_string()))?;
+        ordered_entries(&mut value, "nodes");
+        Ok(value)
+    }
+}
+
+impl StableHash for crate::Capabilities {}
+impl StableHash for crate::DesiredState {}
+
 const BASE32_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
 
 /// Encode bytes to Crockford Base32 (no padding, uppercase).
@@ -11,7 +11,7 @@ use indexmap::IndexMap;
 use semver::Version;
 use serde::{Deserialize, Serialize, de};
 
-use crate::component::{ComponentDevFlow, ComponentOperation, ResourceHints};
+use crate::component::{ComponentDevFlow, ComponentOperation, ComponentTarget, ResourceHints};
 use crate::flow::{
     ComponentRef, Flow, FlowHasher, FlowKind, FlowMetadata, InputMapping, Node, OutputMapping,
     Routing, TelemetryHints,
@@ -178,6 +178,8 @@ struct EncodedComponent {
     config_schema: Option<serde_json::Value>,
     resources: ResourceHints,
     #[serde(default)]
+    targets: Vec<ComponentTarget>,
+    #[serde(default)]
     dev_flows: BTreeMap<FlowId, ComponentDevFlow>,
 }
 
@@ -208,6 +210,8 @@ struct EncodedNode {
     output: OutputMapping,
     routing: EncodedRouting,
     telemetry: TelemetryHints,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    capabilities_override: Option<ComponentCapabilities>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -287,6 +291,7 @@ impl TryFrom<&PackManifest> for EncodedPackManifest {
                     operations: component.operations.clone(),
                     config_schema: component.config_schema.clone(),
                     resources: component.resources.clone(),
+                    targets: component.targets.clone(),
                     dev_flows: component.dev_flows.clone(),
                 })
             })
@@ -406,6 +411,7 @@ fn encode_flow(flow: &Flow, indexes: &SymbolIndexes) -> Result<EncodedFlow, Cbor
                 output: node.output.clone(),
                 routing: encode_routing(&node.routing, indexes)?,
                 telemetry: node.telemetry.clone(),
+                capabilities_override: node.capabilities_override.clone(),
             })
         })
         .collect::<Result<_, CborError>>()?;
@@ -552,6 +558,7 @@ impl TryFrom<EncodedPackManifest> for PackManifest {
                     operations: component.operations,
                     config_schema: component.config_schema,
                     resources: component.resources,
+                    targets: component.targets,
                     dev_flows: component.dev_flows,
                 })
             })
@@ -681,6 +688,7 @@ fn decode_flow(
             output: encoded.output,
             routing,
             telemetry: encoded.telemetry,
+            capabilities_override: encoded.capabilities_override,
         };
         nodes.insert(node_id, node);
     }
@@ -0,0 +1,897 @@
+//! Version negotiation between services built against different releases of this crate.
+//!
+//! Each peer advertises [`PeerVersions`]: the crate [`VERSION`](crate::VERSION) it was built with
+//! and the schema revision it writes for each versioned document. [`NegotiatedVersion::negotiate`]
+//! picks the highest revision both sides understand using Cargo's caret rules (same major, or
+//! same minor while the major is `0`) and reports every downgrade as a [`CompatWarning`].
+//!
+//! Once a lower version is agreed, [`VersionedDocument::serialize_for_version`] writes the major
+//! document types without the fields the peer does not know yet and with enum variants it does
+//! not know replaced by legacy equivalents, listing every change in a [`MigrationReport`].
+
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "serde")]
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+use semver::Version;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde_with::serde_as;
+
+use crate::migration::MIGRATIONS;
+#[cfg(feature = "serde")]
+use crate::migration::{MigrationNoteKind, MigrationReport};
+#[cfg(feature = "serde")]
+use crate::{ErrorCode, GResult, GreenticError};
+#[cfg(feature = "serde")]
+use serde_json::Value;
+
+/// Release that introduces the changes recorded below.
+///
+/// Peers on the published 0.4.48 predate every one of them, so documents written for that
+/// version drop or replace them. Changes added in later releases get their own constant.
+#[cfg(feature = "serde")]
+const V0_4_49: &str = "0.4.49";
+
+/// Versions a peer can read and write.
+#[cfg_attr(feature = "serde", serde_as)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PeerVersions {
+    /// Version of this crate the peer was built with.
+    #[cfg_attr(
+        feature = "serde",
+        serde_as(as = "serde_with::formats::DisplayFromStr")
+    )]
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "SemVer version")
+    )]
+    pub crate_version: Version,
+    /// Schema revision per document name (for example `component.qa`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde_as(as = "BTreeMap<_, serde_with::formats::DisplayFromStr>")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "BTreeMap<String, String>"))]
+    pub documents: BTreeMap<String, Version>,
+}
+
+impl PeerVersions {
+    /// Versions spoken by this build: [`crate::VERSION`] and the newest revision of every
+    /// document with registered migrations.
+    pub fn current() -> Self {
+        let mut documents: BTreeMap<String, Version> = BTreeMap::new();
+        for step in MIGRATIONS {
+            let Ok(version) = Version::parse(step.to_version) else {
+                continue;
+            };
+            let entry = documents
+                .entry(String::from(step.schema))
+                .or_insert_with(|| version.clone());
+            if version > *entry {
+                *entry = version;
+            }
+        }
+        Self {
+            crate_version: Version::parse(crate::VERSION).unwrap_or_else(|_| Version::new(0, 0, 0)),
+            documents,
+        }
+    }
+}
+
+/// Peer a [`CompatWarning`] refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum PeerSide {
+    /// The local service.
+    Ours,
+    /// The remote service.
+    Theirs,
+}
+
+/// Degradation accepted during negotiation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompatWarning {
+    /// Documents are exchanged at an older revision than ours; fields added after `to` are not
+    /// understood by the other side and must not be relied on.
+    DocumentDowngraded {
+        /// Document name.
+        document: String,
+        /// Revision we would write.
+        from: Version,
+        /// Negotiated revision.
+        to: Version,
+    },
+    /// Only one side knows the document, so it cannot be exchanged.
+    DocumentUnsupported {
+        /// Document name.
+        document: String,
+        /// Side that does not know the document.
+        missing_on: PeerSide,
+    },
+    /// Both sides know the document but their revisions are not caret-compatible.
+    DocumentIncompatible {
+        /// Document name.
+        document: String,
+        /// Our revision.
+        ours: Version,
+        /// Their revision.
+        theirs: Version,
+    },
+}
+
+/// Failure to find a common crate version.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CompatError {
+    /// The crate versions differ in a breaking component.
+    #[error("crate version {ours} is incompatible with peer version {theirs}")]
+    IncompatibleCrate {
+        /// Our crate version.
+        ours: Version,
+        /// Their crate version.
+        theirs: Version,
+    },
+}
+
+/// Outcome of negotiating with a peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegotiatedVersion {
+    /// Lower of the two crate versions; features introduced later must stay disabled.
+    pub crate_version: Version,
+    /// Revision to exchange for each document both sides can handle.
+    pub documents: BTreeMap<String, Version>,
+    /// Downgrades and unsupported documents, in document-name order.
+    pub warnings: Vec<CompatWarning>,
+}
+
+impl NegotiatedVersion {
+    /// Negotiates the versions used between `ours` and `theirs`.
+    pub fn negotiate(ours: &PeerVersions, theirs: &PeerVersions) -> Result<Self, CompatError> {
+        if !is_compatible(&ours.crate_version, &theirs.crate_version) {
+            return Err(CompatError::IncompatibleCrate {
+                ours: ours.crate_version.clone(),
+                theirs: theirs.crate_version.clone(),
+            });
+        }
+        let mut documents = BTreeMap::new();
+        let mut warnings = Vec::new();
+        let names: BTreeSet<&String> = ours
+            .documents
+            .keys()
+            .chain(theirs.documents.keys())
+            .collect();
+        for name in names {
+            match (ours.documents.get(name), theirs.documents.get(name)) {
+                (Some(our), Some(their)) if is_compatible(our, their) => {
+                    let agreed = our.min(their).clone();
+                    if agreed < *our {
+                        warnings.push(CompatWarning::DocumentDowngraded {
+                            document: name.clone(),
+                            from: our.clone(),
+                            to: agreed.clone(),
+                        });
+                    }
+                    documents.insert(name.clone(), agreed);
+                }
+                (Some(our), Some(their)) => warnings.push(CompatWarning::DocumentIncompatible {
+                    document: name.clone(),
+                    ours: our.clone(),
+                    theirs: their.clone(),
+                }),
+                (Some(_), None) => warnings.push(CompatWarning::DocumentUnsupported {
+                    document: name.clone(),
+                    missing_on: PeerSide::Theirs,
+                }),
+                (None, _) => warnings.push(CompatWarning::DocumentUnsupported {
+                    document: name.clone(),
+                    missing_on: PeerSide::Ours,
+                }),
+            }
+        }
+        Ok(Self {
+            crate_version: ours.crate_version.clone().min(theirs.crate_version.clone()),
+            documents,
+            warnings,
+        })
+    }
+
+    /// Negotiated revision for `document`, when both sides can exchange it.
+    pub fn document_version(&self, document: &str) -> Option<&Version> {
+        self.documents.get(document)
+    }
+
+    /// Returns `true` when anything was downgraded or left out.
+    pub fn is_degraded(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+}
+
+/// Returns `true` when `a` and `b` are compatible under Cargo's caret rules.
+pub fn is_compatible(a: &Version, b: &Version) -> bool {
+    match (a.major, a.minor) {
+        _ if a.major != b.major => false,
+        (0, 0) => a.minor == b.minor && a.patch == b.patch,
+        (0, _) => a.minor == b.minor,
+        _ => true,
+    }
+}
+
+/// Field or enum variant added to a document in a given crate release.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaChange {
+    /// Dotted path to the field; `*` matches every array element or map value.
+    pub path: String,
+    /// Variant name when the change is a new value of the field rather than the field itself.
+    pub variant: Option<&'static str>,
+    /// Older variant written in place of [`SchemaChange::variant`]; without one the document
+    /// cannot be written for older peers while it holds the variant.
+    pub fallback: Option<&'static str>,
+    /// Values the field could hold before it became open-ended; any other value was introduced
+    /// in [`SchemaChange::since`] and has no older equivalent.
+    pub legacy_values: Option<&'static [&'static str]>,
+    /// Crate version that introduced the change.
+    pub since: &'static str,
+}
+
+impl SchemaChange {
+    /// Field at `path` added in `since`.
+    pub fn field(path: impl Into<String>, since: &'static str) -> Self {
+        Self {
+            path: path.into(),
+            variant: None,
+            fallback: None,
+            legacy_values: None,
+            since,
+        }
+    }
+
+    /// Field at `path` that accepts values beyond `legacy_values` since `since`.
+    pub fn open_ended(
+        path: impl Into<String>,
+        legacy_values: &'static [&'static str],
+        since: &'static str,
+    ) -> Self {
+        Self {
+            legacy_values: Some(legacy_values),
+            ..Self::field(path, since)
+        }
+    }
+
+    /// Value `variant` of the field at `path` added in `since`, with no older equivalent.
+    pub fn variant(path: impl Into<String>, variant: &'static str, since: &'static str) -> Self {
+        Self {
+            path: path.into(),
+            variant: Some(variant),
+            fallback: None,
+            legacy_values: None,
+            since,
+        }
+    }
+
+    /// Value `variant` of the field at `path` added in `since`, written as `fallback` for older
+    /// peers.
+    pub fn variant_or(
+        path: impl Into<String>,
+        variant: &'static str,
+        fallback: &'static str,
+        since: &'static str,
+    ) -> Self {
+        Self {
+            fallback: Some(fallback),
+            ..Self::variant(path, variant, since)
+        }
+    }
+}
+
+/// Document that can be written for peers built against an older crate release.
+#[cfg(feature = "serde")]
+pub trait VersionedDocument: Serialize {
+    /// Document name used in reports.
+    const SCHEMA: &'static str;
+
+    /// Appends every recorded change, with paths prefixed by `prefix`.
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>);
+
+    /// Serializes to JSON without the fields introduced after `target`.
+    ///
+    /// Variants introduced after `target` are replaced by their [`SchemaChange::fallback`];
+    /// when a variant has none, the document cannot be expressed for the peer and an
+    /// [`ErrorCode::InvalidInput`] error is returned instead of a document it cannot read.
+    fn serialize_for_version(&self, target: &Version) -> GResult<(Value, MigrationReport)> {
+        let mut value = serde_json::to_value(self).map_err(|err| {
+            GreenticError::new(
+                ErrorCode::Internal,
+                format!("failed to encode {}: {err}", Self::SCHEMA),
+            )
+        })?;
+        let mut report = MigrationReport::new(Self::SCHEMA, crate::VERSION, target.to_string());
+        let mut changes = Vec::new();
+        Self::schema_changes("", &mut changes);
+        for change in &changes {
+            let newer = Version::parse(change.since).map_or(true, |since| since > *target);
+            if newer {
+                let segments: Vec<&str> = change.path.split('.').collect();
+                drop_change(&mut value, &segments, String::new(), change, &mut report)?;
+            }
+        }
+        Ok((value, report))
+    }
+}
+
+#[cfg(feature = "serde")]
+fn drop_change(
+    value: &mut Value,
+    segments: &[&str],
+    location: String,
+    change: &SchemaChange,
+    report: &mut MigrationReport,
+) -> GResult<()> {
+    let Some((segment, rest)) = segments.split_first() else {
+        return Ok(());
+    };
+    let join = |key: &str| {
+        if location.is_empty() {
+            String::from(key)
+        } else {
+            format!("{location}.{key}")
+        }
+    };
+    if *segment == "*" {
+        if let (true, Some(legacy)) = (rest.is_empty(), change.legacy_values) {
+            let items: Vec<(String, &Value)> = match value {
+                Value::Array(items) => items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| (join(&index.to_string()), item))
+                    .collect(),
+                Value::Object(map) => map.iter().map(|(key, item)| (join(key), item)).collect(),
+                _ => Vec::new(),
+            };
+            for (location, item) in items {
+                check_legacy_value(item, &location, legacy, change)?;
+            }
+            return Ok(());
+        }
+        match value {
+            Value::Array(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    drop_change(item, rest, join(&index.to_string()), change, report)?;
+                }
+            }
+            Value::Object(map) => {
+                for (key, item) in map.iter_mut() {
+                    drop_change(item, rest, join(key), change, report)?;
+                }
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+    let Value::Object(map) = value else {
+        return Ok(());
+    };
+    if !rest.is_empty() {
+        if let Some(child) = map.get_mut(*segment) {
+            drop_change(child, rest, join(segment), change, report)?;
+        }
+        return Ok(());
+    }
+    if let Some(legacy) = change.legacy_values {
+        return match map.get(*segment) {
+            Some(found) => check_legacy_value(found, &join(segment), legacy, change),
+            None => Ok(()),
+        };
+    }
+    let matches = match (change.variant, map.get(*segment)) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(variant), Some(Value::String(found))) => found == variant,
+        (Some(variant), Some(Value::Object(object))) => {
+            object.len() == 1 && object.contains_key(variant)
+        }
+        (Some(_), Some(_)) => false,
+    };
+    if !matches {
+        return Ok(());
+    }
+    match (change.variant, change.fallback) {
+        (None, _) => {
+            map.remove(*segment);
+            report.note(
+                MigrationNoteKind::Dropped,
+                join(segment),
+                format!("field requires {}", change.since),
+            );
+        }
+        (Some(variant), Some(fallback)) => {
+            map.insert(
+                String::from(*segment),
+                Value::String(String::from(fallback)),
+            );
+            report.note(
+                MigrationNoteKind::Approximated,
+                join(segment),
+                format!(
+                    "variant `{variant}` requires {}; written as `{fallback}`",
+                    change.since
+                ),
+            );
+        }
+        (Some(variant), None) => {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "{} holds variant `{variant}`, which requires {} and has no older equivalent",
+                    join(segment),
+                    change.since
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+fn check_legacy_value(
+    value: &Value,
+    location: &str,
+    legacy: &[&str],
+    change: &SchemaChange,
+) -> GResult<()> {
+    match value {
+        Value::String(found) if !legacy.contains(&found.as_str()) => Err(GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "{location} holds `{found}`, which requires {} and has no older equivalent",
+                change.since
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Built-in [`crate::FlowKind`] names; any other kind is a custom kind added in 0.4.49.
+#[cfg(feature = "serde")]
+const BUILTIN_FLOW_KINDS: &[&str] = &["messaging", "event", "component_config", "job", "http"];
+
+#[cfg(feature = "serde")]
+fn fields(prefix: &str, since: &'static str, paths: &[&str], out: &mut Vec<SchemaChange>) {
+    out.extend(
+        paths
+            .iter()
+            .map(|path| SchemaChange::field(format!("{prefix}{path}"), since)),
+    );
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::Flow {
+    const SCHEMA: &'static str = "flow";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(
+            prefix,
+            V0_4_49,
+            &[
+                "nodes.*.capabilities_override",
+                "nodes.*.telemetry.sample_ratio",
+                "nodes.*.telemetry.attribute_allowlist",
+                "metadata.telemetry",
+            ],
+            out,
+        );
+        out.push(SchemaChange::open_ended(
+            format!("{prefix}kind"),
+            BUILTIN_FLOW_KINDS,
+            V0_4_49,
+        ));
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::ComponentManifest {
+    const SCHEMA: &'static str = "component.manifest";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["targets", "resources.fuel"], out);
+        out.push(SchemaChange::open_ended(
+            format!("{prefix}supports.*"),
+            BUILTIN_FLOW_KINDS,
+            V0_4_49,
+        ));
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::PackManifest {
+    const SCHEMA: &'static str = "pack.manifest";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        crate::ComponentManifest::schema_changes(&format!("{prefix}components.*."), out);
+        crate::Flow::schema_changes(&format!("{prefix}flows.*.flow."), out);
+        out.push(SchemaChange::open_ended(
+            format!("{prefix}flows.*.kind"),
+            BUILTIN_FLOW_KINDS,
+            V0_4_49,
+        ));
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::DesiredState {
+    const SCHEMA: &'static str = "desired.state";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(
+            prefix,
+            V0_4_49,
+            &[
+                "event_subscriptions",
+                "entries.*.config_merge",
+                "entries.*.plan_id",
+            ],
+            out,
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::SessionData {
+    const SCHEMA: &'static str = "session.data";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["ttl", "last_activity_at", "turns"], out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::StoreProduct {
+    const SCHEMA: &'static str = "store.product";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(
+            prefix,
+            V0_4_49,
+            &[
+                "compatibility",
+                "lifecycle",
+                "deprecated_at",
+                "sunset_at",
+                "replacement_id",
+            ],
+            out,
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::Subscription {
+    const SCHEMA: &'static str = "store.subscription";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["billing_account_id", "discount"], out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::RolloutStatus {
+    const SCHEMA: &'static str = "store.rollout_status";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["deferred_by", "progress"], out);
+        out.push(SchemaChange::variant_or(
+            format!("{prefix}state"),
+            "deferred",
+            "pending",
+            V0_4_49,
+        ));
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::DeploymentPlan {
+    const SCHEMA: &'static str = "deployment.plan";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["maintenance_windows"], out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::WorkerRequest {
+    const SCHEMA: &'static str = "worker.request";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["payload_blob"], out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::WorkerResponse {
+    const SCHEMA: &'static str = "worker.response";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["retry_policy"], out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::ResolveComponentRequest {
+    const SCHEMA: &'static str = "distributor.resolve_request";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["accept_encodings"], out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::ResolveComponentResponse {
+    const SCHEMA: &'static str = "distributor.resolve_response";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["transfer"], out);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl VersionedDocument for crate::RunResult {
+    const SCHEMA: &'static str = "run.result";
+
+    fn schema_changes(prefix: &str, out: &mut Vec<SchemaChange>) {
+        fields(prefix, V0_4_49, &["artifacts"], out);
+        for variant in ["pending", "running"] {
+            out.push(SchemaChange::variant(
+                format!("{prefix}status"),
+                variant,
+                V0_4_49,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(value: &str) -> Version {
+        Version::parse(value).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    fn peer(crate_version: &str, documents: &[(&str, &str)]) -> PeerVersions {
+        PeerVersions {
+            crate_version: version(crate_version),
+            documents: documents
+                .iter()
+                .map(|(name, rev)| (String::from(*name), version(rev)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn caret_rules() {
+        assert!(is_compatible(&version("1.2.0"), &version("1.9.3")));
+        assert!(!is_compatible(&version("1.2.0"), &version("2.0.0")));
+        assert!(is_compatible(&version("0.6.0"), &version("0.6.4")));
+        assert!(!is_compatible(&version("0.6.0"), &version("0.7.0")));
+        assert!(!is_compatible(&version("0.0.1"), &version("0.0.2")));
+    }
+
+    #[test]
+    fn negotiates_lowest_common_revision_with_warnings() {
+        let ours = peer(
+            "0.4.7",
+            &[
+                ("component.qa", "0.6.2"),
+                ("flow.resolve", "1.1.0"),
+                ("store.plan", "2.0.0"),
+            ],
+        );
+        let theirs = peer(
+            "0.4.2",
+            &[
+                ("component.qa", "0.6.0"),
+                ("flow.resolve", "2.0.0"),
+                ("tenant.flags", "1.0.0"),
+            ],
+        );
+        let negotiated =
+            NegotiatedVersion::negotiate(&ours, &theirs).unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(negotiated.crate_version, version("0.4.2"));
+        assert_eq!(
+            negotiated.document_version("component.qa"),
+            Some(&version("0.6.0"))
+        );
+        assert_eq!(negotiated.document_version("flow.resolve"), None);
+        assert_eq!(
+            negotiated.warnings,
+            [
+                CompatWarning::DocumentDowngraded {
+                    document: "component.qa".into(),
+                    from: version("0.6.2"),
+                    to: version("0.6.0"),
+                },
+                CompatWarning::DocumentIncompatible {
+                    document: "flow.resolve".into(),
+                    ours: version("1.1.0"),
+                    theirs: version("2.0.0"),
+                },
+                CompatWarning::DocumentUnsupported {
+                    document: "store.plan".into(),
+                    missing_on: PeerSide::Theirs,
+                },
+                CompatWarning::DocumentUnsupported {
+                    document: "tenant.flags".into(),
+                    missing_on: PeerSide::Ours,
+                },
+            ]
+        );
+
+        let upgraded = peer("0.5.0", &[]);
+        assert_eq!(
+            NegotiatedVersion::negotiate(&ours, &upgraded),
+            Err(CompatError::IncompatibleCrate {
+                ours: version("0.4.7"),
+                theirs: version("0.5.0"),
+            })
+        );
+    }
+
+    #[test]
+    fn current_advertises_crate_and_migrated_documents() {
+        let current = PeerVersions::current();
+        assert_eq!(current.crate_version.to_string(), crate::VERSION);
+        assert_eq!(
+            current.documents.get(crate::migration::COMPONENT_QA_SCHEMA),
+            Some(&version("0.6.0"))
+        );
+        let negotiated =
+            NegotiatedVersion::negotiate(&current, &current).unwrap_or_else(|err| panic!("{err}"));
+        assert!(!negotiated.is_degraded());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_for_version_drops_newer_fields() {
+        let mut session = crate::SessionData {
+            tenant_ctx: crate::TenantCtx::new(
+                "prod".parse().unwrap_or_else(|err| panic!("{err}")),
+                "acme".parse().unwrap_or_else(|err| panic!("{err}")),
+            ),
+            flow_id: "flow-1".parse().unwrap_or_else(|err| panic!("{err}")),
+            pack_id: None,
+            cursor: crate::SessionCursor::new("node-1"),
+            context_json: String::from("{}"),
+            ttl: Some(crate::SessionTtl::default()),
+            last_activity_at: None,
+            turns: 0,
+        };
+        session.record_turn(crate::UnixMillis::from_millis(1_000));
+
+        let (old, report) = session
+            .serialize_for_version(&version("0.4.48"))
+            .unwrap_or_else(|err| panic!("{err}"));
+        for field in ["ttl", "last_activity_at", "turns"] {
+            assert!(old.get(field).is_none(), "{field}");
+        }
+        assert_eq!(report.to_version, "0.4.48");
+        assert_eq!(
+            report
+                .notes
+                .iter()
+                .map(|note| note.path.as_str())
+                .collect::<Vec<_>>(),
+            ["ttl", "last_activity_at", "turns"]
+        );
+        assert!(report.is_lossy());
+
+        let negotiated =
+            NegotiatedVersion::negotiate(&PeerVersions::current(), &PeerVersions::current())
+                .unwrap_or_else(|err| panic!("{err}"));
+        let (current, report) = session
+            .serialize_for_version(&negotiated.crate_version)
+            .unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(current["turns"], 1);
+        assert!(report.notes.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn drops_nested_fields_and_replaces_unknown_variants() {
+        let mut value = serde_json::json!({
+            "nodes": {
+                "a": {"telemetry": {"sample_ratio": 0.5, "span_name": "a"}},
+                "b": {"telemetry": {}}
+            },
+            "entries": [{"state": "deferred"}, {"state": "pending"}]
+        });
+        let mut report = MigrationReport::new("test", "0.4.49", "0.4.48");
+        let apply = |value: &mut Value, change: &SchemaChange, report: &mut MigrationReport| {
+            let segments: Vec<&str> = change.path.split('.').collect();
+            drop_change(value, &segments, String::new(), change, report)
+        };
+        let nested = SchemaChange::field("nodes.*.telemetry.sample_ratio", V0_4_49);
+        apply(&mut value, &nested, &mut report).unwrap_or_else(|err| panic!("{err}"));
+        let variant = SchemaChange::variant_or("entries.*.state", "deferred", "pending", V0_4_49);
+        apply(&mut value, &variant, &mut report).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "nodes": {"a": {"telemetry": {"span_name": "a"}}, "b": {"telemetry": {}}},
+                "entries": [{"state": "pending"}, {"state": "pending"}]
+            })
+        );
+        assert_eq!(
+            report
+                .notes
+                .iter()
+                .map(|note| (note.kind, note.path.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (MigrationNoteKind::Dropped, "nodes.a.telemetry.sample_ratio"),
+                (MigrationNoteKind::Approximated, "entries.0.state")
+            ]
+        );
+
+        let mut running = serde_json::json!({ "status": "running" });
+        let unknown = SchemaChange::variant("status", "running", V0_4_49);
+        let err = apply(&mut running, &unknown, &mut report).err();
+        assert_eq!(err.map(|err| err.code), Some(ErrorCode::InvalidInput));
+        assert_eq!(running["status"], "running");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn custom_flow_kinds_cannot_be_written_for_older_peers() {
+        let builtin: Vec<&str> = crate::FlowKind::BUILTIN
+            .iter()
+            .map(crate::FlowKind::as_str)
+            .collect();
+        assert_eq!(builtin, BUILTIN_FLOW_KINDS);
+
+        let mut changes = Vec::new();
+        crate::PackManifest::schema_changes("", &mut changes);
+        let apply = |value: &mut Value| -> GResult<()> {
+            let mut report = MigrationReport::new("test", "0.4.49", "0.4.48");
+            for change in &changes {
+                let segments: Vec<&str> = change.path.split('.').collect();
+                drop_change(value, &segments, String::new(), change, &mut report)?;
+            }
+            Ok(())
+        };
+
+        let mut builtin = serde_json::json!({
+            "flows": [{"kind": "messaging", "flow": {"kind": "messaging"}}],
+            "components": [{"supports": ["event", "http"]}]
+        });
+        apply(&mut builtin).unwrap_or_else(|err| panic!("{err}"));
+
+        let mut custom_flow = serde_json::json!({
+            "flows": [{"kind": "messaging", "flow": {"kind": "acme.batch"}}]
+        });
+        let err = apply(&mut custom_flow).err();
+        assert_eq!(
+            err.as_ref().map(|err| err.code),
+            Some(ErrorCode::InvalidInput)
+        );
+        assert!(err.is_some_and(|err| err.message.contains("flows.0.flow.kind")));
+
+        let mut custom_support = serde_json::json!({
+            "components": [{"supports": ["event", "acme.batch"]}]
+        });
+        let err = apply(&mut custom_support).err();
+        assert!(err.is_some_and(|err| err.message.contains("components.0.supports.1")));
+    }
+}
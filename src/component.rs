@@ -1,13 +1,17 @@
 //! Component manifest structures with generic capability declarations.
 
 use alloc::collections::BTreeMap;
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
 use semver::Version;
 
 use crate::flow::FlowKind;
-use crate::{ComponentId, FlowId, SecretRequirement};
+use crate::{
+    ByteSize, ComponentId, DurationMs, FlowId, MockComponentSpec, OciImageRef, Platform,
+    SecretRequirement,
+};
 
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
@@ -27,6 +31,12 @@ pub struct ComponentDevFlow {
     pub format: String,
     /// FlowIR JSON graph for this flow.
     pub graph: serde_json::Value,
+    /// Components stubbed out while running this flow.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub mocks: Vec<MockComponentSpec>,
 }
 
 fn dev_flow_default_format() -> String {
@@ -73,6 +83,13 @@ pub struct ComponentManifest {
     /// Resource usage hints for deployers/schedulers.
     #[cfg_attr(feature = "serde", serde(default))]
     pub resources: ResourceHints,
+    /// Builds shipped for different runtimes. Manifests without targets describe a single
+    /// [`DEFAULT_COMPONENT_TARGET_TRIPLE`] build; see [`ComponentManifest::effective_targets`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub targets: Vec<ComponentTarget>,
     /// Development-time flows used for authoring only. This field is optional and ignored by
     /// runtime systems. Tools may store FlowIR-as-JSON values here to allow editing flows without
     /// sidecar files.
@@ -85,8 +102,18 @@ pub struct ComponentManifest {
 
 impl ComponentManifest {
     /// Returns `true` when the component supports the specified flow kind.
+    ///
+    /// See [`ComponentManifest::accepts_kind`].
     pub fn supports_kind(&self, kind: FlowKind) -> bool {
-        self.supports.iter().copied().any(|entry| entry == kind)
+        self.accepts_kind(&kind)
+    }
+
+    /// Returns `true` when the component supports `kind`.
+    ///
+    /// Custom kinds are matched with [`FlowKind::accepts`], so support for a namespaced kind
+    /// covers its refinements.
+    pub fn accepts_kind(&self, kind: &FlowKind) -> bool {
+        self.supports.iter().any(|entry| entry.accepts(kind))
     }
 
     /// Resolves the effective profile name, returning the requested profile when supported or
@@ -124,6 +151,112 @@ impl ComponentManifest {
             .as_ref()
             .and_then(|cfg| cfg.full.as_ref())
     }
+
+    /// Target implied by manifests that predate [`ComponentManifest::targets`]: a
+    /// [`DEFAULT_COMPONENT_TARGET_TRIPLE`] build of the manifest world, runnable on any platform.
+    pub fn default_target(&self) -> ComponentTarget {
+        ComponentTarget {
+            triple: DEFAULT_COMPONENT_TARGET_TRIPLE.to_owned(),
+            world: Some(self.world.clone()),
+            image: None,
+            platforms: Vec::new(),
+            resources: Some(self.resources.clone()),
+        }
+    }
+
+    /// Declared targets with the manifest world and resources filled in where a target leaves
+    /// them unset, or the [`default_target`](Self::default_target) when none are declared.
+    pub fn effective_targets(&self) -> Vec<ComponentTarget> {
+        if self.targets.is_empty() {
+            return alloc::vec![self.default_target()];
+        }
+        self.targets
+            .iter()
+            .map(|target| self.resolve_target(target))
+            .collect()
+    }
+
+    /// Selects the build to run on `platform`.
+    ///
+    /// Targets listing `platform` explicitly win over targets that run anywhere; ties go to the
+    /// first declared target. The result has the manifest world and resources filled in.
+    pub fn select_target(&self, platform: &Platform) -> Option<ComponentTarget> {
+        if self.targets.is_empty() {
+            return Some(self.default_target());
+        }
+        self.targets
+            .iter()
+            .find(|target| target.platforms.contains(platform))
+            .or_else(|| {
+                self.targets
+                    .iter()
+                    .find(|target| target.platforms.is_empty())
+            })
+            .map(|target| self.resolve_target(target))
+    }
+
+    fn resolve_target(&self, target: &ComponentTarget) -> ComponentTarget {
+        let mut resolved = target.clone();
+        resolved.world.get_or_insert_with(|| self.world.clone());
+        resolved
+            .resources
+            .get_or_insert_with(|| self.resources.clone());
+        resolved
+    }
+}
+
+/// Target triple assumed for manifests that do not declare [`ComponentManifest::targets`].
+pub const DEFAULT_COMPONENT_TARGET_TRIPLE: &str = "wasm32-wasip2";
+
+/// One build of a component for a specific runtime.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ComponentTarget {
+    /// Target triple (for example `wasm32-wasip2` or `x86_64-unknown-linux-gnu`).
+    pub triple: String,
+    /// WIT world implemented by this build, when it differs from the manifest world.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub world: Option<String>,
+    /// OCI image carrying the build (native containers or published wasm artifacts).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub image: Option<OciImageRef>,
+    /// Platforms the build runs on; empty means any platform.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub platforms: Vec<Platform>,
+    /// Resource hints for this build, overriding the manifest hints.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub resources: Option<ResourceHints>,
+}
+
+impl ComponentTarget {
+    /// Creates a target for `triple` that runs on any platform.
+    pub fn new(triple: impl Into<String>) -> Self {
+        Self {
+            triple: triple.into(),
+            world: None,
+            image: None,
+            platforms: Vec::new(),
+            resources: None,
+        }
+    }
+
+    /// Returns `true` for WebAssembly builds.
+    pub fn is_wasm(&self) -> bool {
+        self.triple.starts_with("wasm32-") || self.triple.starts_with("wasm64-")
+    }
 }
 
 /// Component profile declaration.
@@ -174,6 +307,18 @@ pub struct ComponentOperation {
     pub output_schema: serde_json::Value,
 }
 
+#[cfg(feature = "schemars")]
+impl ComponentOperation {
+    /// Creates an operation whose schemas are generated from the input and output types.
+    pub fn from_types<I: JsonSchema, O: JsonSchema>(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            input_schema: schemars::schema_for!(I).to_value(),
+            output_schema: schemars::schema_for!(O).to_value(),
+        }
+    }
+}
+
 /// Resource usage hints for a component.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -185,18 +330,37 @@ pub struct ResourceHints {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub cpu_millis: Option<u32>,
-    /// Suggested memory in MiB.
+    /// Suggested memory (serialized as `memory_mb`; bare numbers are mebibytes).
     #[cfg_attr(
         feature = "serde",
-        serde(default, skip_serializing_if = "Option::is_none")
+        serde(
+            rename = "memory_mb",
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::mebibytes::option"
+        )
     )]
-    pub memory_mb: Option<u32>,
-    /// Expected average latency in milliseconds.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<crate::units::MebibyteSize>")
+    )]
+    pub memory: Option<ByteSize>,
+    /// Expected average latency (serialized as `average_latency_ms`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "average_latency_ms",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub average_latency: Option<DurationMs>,
+    /// Expected fuel consumed per invocation on deterministic engines.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub average_latency_ms: Option<u32>,
+    pub fuel: Option<u64>,
 }
 
 /// Host + WASI capabilities required by a component.
@@ -440,6 +604,230 @@ pub struct IaCCapabilities {
     pub execute_plans: bool,
 }
 
+impl FilesystemMode {
+    fn rank(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::ReadOnly => 1,
+            Self::Sandbox => 2,
+        }
+    }
+}
+
+impl TelemetryScope {
+    fn breadth(&self) -> u8 {
+        match self {
+            Self::Node => 0,
+            Self::Pack => 1,
+            Self::Tenant => 2,
+        }
+    }
+}
+
+impl ComponentCapabilities {
+    /// Returns `true` when `granted` covers every capability declared here.
+    pub fn is_satisfied_by(&self, granted: &ComponentCapabilities) -> bool {
+        self.check_against(granted).is_satisfied()
+    }
+
+    /// Compares these required capabilities with the host `granted` set.
+    ///
+    /// Boolean surfaces must be granted when required, filesystem modes and telemetry scopes
+    /// must be at least as permissive, and every mount, environment variable, and secret key
+    /// must appear in the grant.
+    pub fn check_against(&self, granted: &ComponentCapabilities) -> CapabilityCheckReport {
+        let mut report = CapabilityCheckReport::default();
+        let (wasi, granted_wasi) = (&self.wasi, &granted.wasi);
+        report.flag("wasi.random", wasi.random, granted_wasi.random);
+        report.flag("wasi.clocks", wasi.clocks, granted_wasi.clocks);
+        if let Some(fs) = &wasi.filesystem {
+            match &granted_wasi.filesystem {
+                None if fs.mode == FilesystemMode::None && fs.mounts.is_empty() => {}
+                None => report.not_granted("wasi.filesystem"),
+                Some(granted_fs) => {
+                    if fs.mode.rank() > granted_fs.mode.rank() {
+                        report.insufficient(
+                            "wasi.filesystem.mode",
+                            format!("{:?}", fs.mode),
+                            format!("{:?}", granted_fs.mode),
+                        );
+                    }
+                    for mount in &fs.mounts {
+                        let path = format!("wasi.filesystem.mounts.{}", mount.name);
+                        match granted_fs.mounts.iter().find(|m| m.name == mount.name) {
+                            None => report.not_granted(path),
+                            Some(granted_mount) if granted_mount != mount => report.insufficient(
+                                path,
+                                format!("{} at {}", mount.host_class, mount.guest_path),
+                                format!(
+                                    "{} at {}",
+                                    granted_mount.host_class, granted_mount.guest_path
+                                ),
+                            ),
+                            Some(_) => {}
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(env) = &wasi.env {
+            let allowed = granted_wasi
+                .env
+                .as_ref()
+                .map(|env| env.allow.as_slice())
+                .unwrap_or(&[]);
+            for var in &env.allow {
+                if !allowed.contains(var) {
+                    report.not_granted(format!("wasi.env.{var}"));
+                }
+            }
+        }
+
+        let (host, granted_host) = (&self.host, &granted.host);
+        if let Some(secrets) = &host.secrets {
+            let granted_keys = granted_host
+                .secrets
+                .as_ref()
+                .map(|secrets| secrets.required.as_slice())
+                .unwrap_or(&[]);
+            for secret in &secrets.required {
+                if !granted_keys.iter().any(|granted| granted.key == secret.key) {
+                    report.not_granted(format!("host.secrets.{}", secret.key.as_str()));
+                }
+            }
+        }
+        if let Some(state) = &host.state {
+            let granted_state = granted_host.state.clone().unwrap_or_default();
+            report.flag("host.state.read", state.read, granted_state.read);
+            report.flag("host.state.write", state.write, granted_state.write);
+        }
+        if let Some(messaging) = &host.messaging {
+            let granted_messaging = granted_host.messaging.clone().unwrap_or_default();
+            report.flag(
+                "host.messaging.inbound",
+                messaging.inbound,
+                granted_messaging.inbound,
+            );
+            report.flag(
+                "host.messaging.outbound",
+                messaging.outbound,
+                granted_messaging.outbound,
+            );
+        }
+        if let Some(events) = &host.events {
+            let granted_events = granted_host.events.clone().unwrap_or_default();
+            report.flag(
+                "host.events.inbound",
+                events.inbound,
+                granted_events.inbound,
+            );
+            report.flag(
+                "host.events.outbound",
+                events.outbound,
+                granted_events.outbound,
+            );
+        }
+        if let Some(http) = &host.http {
+            let granted_http = granted_host.http.clone().unwrap_or_default();
+            report.flag("host.http.client", http.client, granted_http.client);
+            report.flag("host.http.server", http.server, granted_http.server);
+        }
+        if let Some(telemetry) = &host.telemetry {
+            match &granted_host.telemetry {
+                None => report.not_granted("host.telemetry"),
+                Some(granted_telemetry)
+                    if telemetry.scope.breadth() > granted_telemetry.scope.breadth() =>
+                {
+                    report.insufficient(
+                        "host.telemetry.scope",
+                        format!("{:?}", telemetry.scope),
+                        format!("{:?}", granted_telemetry.scope),
+                    );
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(iac) = &host.iac {
+            let (write_templates, execute_plans) =
+                granted_host.iac.as_ref().map_or((false, false), |iac| {
+                    (iac.write_templates, iac.execute_plans)
+                });
+            report.flag(
+                "host.iac.write_templates",
+                iac.write_templates,
+                write_templates,
+            );
+            report.flag("host.iac.execute_plans", iac.execute_plans, execute_plans);
+        }
+        report
+    }
+}
+
+/// Why a required capability is not covered by the grant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum CapabilityMismatchReason {
+    /// The capability is absent from the grant.
+    NotGranted,
+    /// The capability is granted with a narrower setting than required.
+    Insufficient {
+        /// Setting requested by the component.
+        required: String,
+        /// Setting offered by the host.
+        granted: String,
+    },
+}
+
+/// Single required capability that the grant does not cover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CapabilityMismatch {
+    /// Dotted capability path (for example `host.http.client` or `wasi.env.HOME`).
+    pub path: String,
+    /// Mismatch details.
+    pub reason: CapabilityMismatchReason,
+}
+
+/// Result of comparing required component capabilities with a host grant.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CapabilityCheckReport {
+    /// Uncovered capabilities in declaration order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mismatches: Vec<CapabilityMismatch>,
+}
+
+impl CapabilityCheckReport {
+    /// Returns `true` when every required capability is granted.
+    pub fn is_satisfied(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    fn not_granted(&mut self, path: impl Into<String>) {
+        self.mismatches.push(CapabilityMismatch {
+            path: path.into(),
+            reason: CapabilityMismatchReason::NotGranted,
+        });
+    }
+
+    fn insufficient(&mut self, path: impl Into<String>, required: String, granted: String) {
+        self.mismatches.push(CapabilityMismatch {
+            path: path.into(),
+            reason: CapabilityMismatchReason::Insufficient { required, granted },
+        });
+    }
+
+    fn flag(&mut self, path: &str, required: bool, granted: bool) {
+        if required && !granted {
+            self.not_granted(path);
+        }
+    }
+}
+
 /// Profile resolution errors.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ComponentProfileError {
@@ -0,0 +1,207 @@
+//! Mock component descriptors for development flows and pack tests.
+//!
+//! A [`MockComponentSpec`] stands in for a real component: hosts materialize it as a stub that
+//! answers each operation with canned [`MockResponse`]s, optionally after a simulated latency.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ComponentId, DurationMs, ErrorCode, GResult, GreenticError};
+
+/// Stub replacing a component during development or tests.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MockComponentSpec {
+    /// Component being replaced.
+    pub component_id: ComponentId,
+    /// Optional description shown by tooling.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+    /// Canned responses per operation.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub operations: Vec<MockOperation>,
+    /// Response for operations without an entry in `operations`; such calls fail when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub fallback: Option<MockResponse>,
+    /// Latency applied to every call unless the operation overrides it.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub latency: Option<MockLatency>,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl MockComponentSpec {
+    /// Creates a mock without operations.
+    pub fn new(component_id: ComponentId) -> Self {
+        Self {
+            component_id,
+            description: None,
+            operations: Vec::new(),
+            fallback: None,
+            latency: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an operation answering every call with `output`.
+    pub fn with_output(mut self, operation: impl Into<String>, output: Value) -> Self {
+        self.operations.push(MockOperation {
+            operation: operation.into(),
+            responses: alloc::vec![MockResponse::Output { value: output }],
+            latency: None,
+        });
+        self
+    }
+
+    /// Sets the latency applied to every call.
+    pub fn with_latency(mut self, latency: MockLatency) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Returns the response for the `call`-th invocation (starting at `0`) of `operation`.
+    ///
+    /// Calls past the end of an operation's response list repeat its last response.
+    pub fn response_for(&self, operation: &str, call: usize) -> Option<&MockResponse> {
+        match self.operation(operation) {
+            Some(entry) => entry.response_for(call),
+            None => self.fallback.as_ref(),
+        }
+    }
+
+    /// Returns the latency to simulate for `operation`, if any.
+    pub fn latency_for(&self, operation: &str) -> Option<&MockLatency> {
+        self.operation(operation)
+            .and_then(|entry| entry.latency.as_ref())
+            .or(self.latency.as_ref())
+    }
+
+    /// Ensures operations are named, unique, and have at least one response.
+    pub fn validate(&self) -> GResult<()> {
+        let mut seen = BTreeSet::new();
+        for entry in &self.operations {
+            if entry.operation.trim().is_empty() {
+                return Err(self.invalid("operation names must not be empty"));
+            }
+            if !seen.insert(entry.operation.as_str()) {
+                return Err(self.invalid(&format!("duplicate operation '{}'", entry.operation)));
+            }
+            if entry.responses.is_empty() {
+                return Err(
+                    self.invalid(&format!("operation '{}' has no responses", entry.operation))
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn operation(&self, operation: &str) -> Option<&MockOperation> {
+        self.operations
+            .iter()
+            .find(|entry| entry.operation == operation)
+    }
+
+    fn invalid(&self, reason: &str) -> GreenticError {
+        GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("mock component {}: {reason}", self.component_id),
+        )
+    }
+}
+
+/// Canned behaviour of one operation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MockOperation {
+    /// Operation name (for example `handle_message`).
+    pub operation: String,
+    /// Responses returned by successive calls; the last one repeats.
+    pub responses: Vec<MockResponse>,
+    /// Latency overriding the component-wide setting.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub latency: Option<MockLatency>,
+}
+
+impl MockOperation {
+    /// Returns the response for the `call`-th invocation, starting at `0`.
+    pub fn response_for(&self, call: usize) -> Option<&MockResponse> {
+        self.responses.get(call).or_else(|| self.responses.last())
+    }
+}
+
+/// Result returned by a mocked call.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum MockResponse {
+    /// Successful call producing `value`.
+    Output {
+        /// Operation output.
+        value: Value,
+    },
+    /// Failed call.
+    Error {
+        /// Error code reported to the runtime.
+        code: ErrorCode,
+        /// Error message.
+        message: String,
+    },
+}
+
+/// Simulated call latency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MockLatency {
+    /// Minimum delay before the response.
+    pub base: DurationMs,
+    /// Maximum extra delay added on top of `base`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub jitter: Option<DurationMs>,
+}
+
+impl MockLatency {
+    /// Creates a fixed latency.
+    pub fn fixed(base: DurationMs) -> Self {
+        Self { base, jitter: None }
+    }
+
+    /// Returns the delay for one call, mapping caller-supplied `entropy` into the jitter range.
+    pub fn delay(&self, entropy: u64) -> DurationMs {
+        let extra = match self.jitter {
+            Some(jitter) => entropy % jitter.as_millis().saturating_add(1),
+            None => 0,
+        };
+        DurationMs::from_millis(self.base.as_millis().saturating_add(extra))
+    }
+}
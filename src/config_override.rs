@@ -0,0 +1,356 @@
+//! Merge semantics for configuration overrides.
+//!
+//! A [`ConfigOverride`] patches a component's base configuration with one of two strategies:
+//!
+//! - [`MergeStrategy::MergePatch`] follows JSON merge-patch (RFC 7396): objects merge key by
+//!   key, `null` removes a key, and any other value (including arrays) replaces the base value.
+//! - [`MergeStrategy::Strategic`] behaves like merge-patch but honours directives inside the
+//!   patch:
+//!   - `{"$append": [...]}` / `{"$prepend": [...]}` extend the base array;
+//!   - `{"$replace": value}` replaces the base value without merging;
+//!   - `"$patch": "replace"` in an object replaces the base object with the remaining keys;
+//!   - `"$patch": "delete"` in an object removes the key.
+//!
+//! [`ConfigOverride::apply_to`] reports type conflicts and malformed directives as diagnostics
+//! so every distributor applying the same override reaches the same result.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::ConfigValue;
+use crate::validate::{Diagnostic, Severity, core_diagnostic};
+
+/// How a configuration override is merged into the base configuration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum MergeStrategy {
+    /// JSON merge-patch (RFC 7396).
+    #[default]
+    MergePatch,
+    /// Merge-patch with `$append`, `$prepend`, `$replace`, and `$patch` directives.
+    Strategic,
+}
+
+impl MergeStrategy {
+    /// Returns `true` for [`MergeStrategy::MergePatch`].
+    pub fn is_merge_patch(&self) -> bool {
+        matches!(self, Self::MergePatch)
+    }
+}
+
+/// Configuration patch applied on top of a base configuration.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ConfigOverride {
+    /// Merge strategy.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MergeStrategy::is_merge_patch")
+    )]
+    pub strategy: MergeStrategy,
+    /// Patch document; strings may reference `${secret:...}` and `${env:...}`.
+    pub patch: ConfigValue,
+}
+
+impl ConfigOverride {
+    /// Creates an override from a patch document.
+    pub fn new(strategy: MergeStrategy, patch: ConfigValue) -> Self {
+        Self { strategy, patch }
+    }
+
+    /// Builds an object patch from per-key overrides, as stored on subscription entries.
+    pub fn from_entries(strategy: MergeStrategy, entries: &BTreeMap<String, ConfigValue>) -> Self {
+        Self {
+            strategy,
+            patch: entries
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        }
+    }
+
+    /// Merges the patch into `base` and returns conflict diagnostics.
+    ///
+    /// Conflicts never abort the merge: type changes are applied and reported as warnings, and
+    /// malformed directives are skipped and reported as errors.
+    pub fn apply_to(&self, base: &mut Value) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        match self.strategy {
+            MergeStrategy::MergePatch => merge_patch(base, self.patch.as_value()),
+            MergeStrategy::Strategic => {
+                strategic_merge(base, self.patch.as_value(), "config", &mut diagnostics)
+            }
+        }
+        diagnostics
+    }
+}
+
+fn merge_patch(base: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *base = patch.clone();
+        return;
+    };
+    if !base.is_object() {
+        *base = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = base {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+fn strategic_merge(base: &mut Value, patch: &Value, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let Value::Object(patch) = patch else {
+        replace(base, patch.clone(), path, diagnostics);
+        return;
+    };
+    if let Some(directive) = list_directive(patch) {
+        apply_list_directive(base, directive, path, diagnostics);
+        return;
+    }
+
+    let mut patch = patch.clone();
+    match patch.remove("$patch") {
+        None => {}
+        Some(Value::String(mode)) if mode == "replace" => {
+            replace(base, Value::Object(patch), path, diagnostics);
+            return;
+        }
+        Some(other) => {
+            diagnostics.push(directive_error(
+                path,
+                &format!("unsupported `$patch` directive {other}"),
+            ));
+            return;
+        }
+    }
+
+    if !base.is_object() {
+        if !base.is_null() {
+            diagnostics.push(type_changed(path, base, "object"));
+        }
+        *base = Value::Object(Map::new());
+    }
+    if let Value::Object(target) = base {
+        for (key, value) in &patch {
+            let child = format!("{path}.{key}");
+            if value.is_null() || is_delete_directive(value) {
+                target.remove(key);
+                continue;
+            }
+            let existed = target.contains_key(key);
+            let slot = target.entry(key.clone()).or_insert(Value::Null);
+            strategic_merge(slot, value, &child, diagnostics);
+            if !existed && slot.is_null() {
+                target.remove(key);
+            }
+        }
+    }
+}
+
+enum ListDirective<'a> {
+    Append(&'a Value),
+    Prepend(&'a Value),
+    Replace(&'a Value),
+}
+
+fn list_directive(patch: &Map<String, Value>) -> Option<ListDirective<'_>> {
+    if patch.len() != 1 {
+        return None;
+    }
+    let (key, value) = patch.iter().next()?;
+    match key.as_str() {
+        "$append" => Some(ListDirective::Append(value)),
+        "$prepend" => Some(ListDirective::Prepend(value)),
+        "$replace" => Some(ListDirective::Replace(value)),
+        _ => None,
+    }
+}
+
+fn is_delete_directive(value: &Value) -> bool {
+    value
+        .as_object()
+        .and_then(|map| map.get("$patch"))
+        .and_then(Value::as_str)
+        == Some("delete")
+}
+
+fn apply_list_directive(
+    base: &mut Value,
+    directive: ListDirective<'_>,
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let (items, prepend) = match directive {
+        ListDirective::Replace(value) => {
+            replace(base, value.clone(), path, diagnostics);
+            return;
+        }
+        ListDirective::Append(items) => (items, false),
+        ListDirective::Prepend(items) => (items, true),
+    };
+    let Value::Array(items) = items else {
+        diagnostics.push(directive_error(
+            path,
+            "`$append`/`$prepend` expect an array of items",
+        ));
+        return;
+    };
+    if !base.is_array() {
+        if !base.is_null() {
+            diagnostics.push(type_changed(path, base, "array"));
+        }
+        *base = Value::Array(Vec::new());
+    }
+    if let Value::Array(target) = base {
+        if prepend {
+            target.splice(0..0, items.iter().cloned());
+        } else {
+            target.extend(items.iter().cloned());
+        }
+    }
+}
+
+fn replace(base: &mut Value, value: Value, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if !base.is_null() && json_type(base) != json_type(&value) {
+        diagnostics.push(type_changed(path, base, json_type(&value)));
+    }
+    *base = value;
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_changed(path: &str, base: &Value, new_type: &str) -> Diagnostic {
+    core_diagnostic(
+        Severity::Warn,
+        "CONFIG_OVERRIDE_TYPE_CHANGED",
+        &format!(
+            "override replaces {} at {path} with {new_type}",
+            json_type(base)
+        ),
+        Some(path.into()),
+        Some("check that the override matches the component's config schema".into()),
+    )
+}
+
+fn directive_error(path: &str, message: &str) -> Diagnostic {
+    core_diagnostic(
+        Severity::Error,
+        "CONFIG_OVERRIDE_INVALID_DIRECTIVE",
+        &format!("{message} at {path}; the directive was skipped"),
+        Some(path.into()),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn override_of(strategy: MergeStrategy, patch: Value) -> ConfigOverride {
+        ConfigOverride::new(
+            strategy,
+            ConfigValue::parse(patch).unwrap_or_else(|err| panic!("{err}")),
+        )
+    }
+
+    #[test]
+    fn merge_patch_follows_rfc_7396() {
+        let mut base = json!({"a": {"b": 1, "c": 2}, "list": [1, 2], "drop": true});
+        let patch = override_of(
+            MergeStrategy::MergePatch,
+            json!({"a": {"c": null, "d": 3}, "list": [3], "drop": null, "$append": 1}),
+        );
+        assert!(patch.apply_to(&mut base).is_empty());
+        assert_eq!(
+            base,
+            json!({"a": {"b": 1, "d": 3}, "list": [3], "$append": 1})
+        );
+    }
+
+    #[test]
+    fn strategic_merge_honours_directives() {
+        let mut base = json!({
+            "hosts": ["a"],
+            "tags": ["x"],
+            "limits": {"cpu": 1, "memory": 2},
+            "auth": {"mode": "basic"},
+            "legacy": {"on": true},
+        });
+        let patch = override_of(
+            MergeStrategy::Strategic,
+            json!({
+                "hosts": {"$append": ["b", "c"]},
+                "tags": {"$prepend": ["w"]},
+                "limits": {"$patch": "replace", "cpu": 4},
+                "auth": {"token": "${secret:AUTH_TOKEN}"},
+                "legacy": {"$patch": "delete"},
+            }),
+        );
+        assert!(patch.apply_to(&mut base).is_empty());
+        assert_eq!(
+            base,
+            json!({
+                "hosts": ["a", "b", "c"],
+                "tags": ["w", "x"],
+                "limits": {"cpu": 4},
+                "auth": {"mode": "basic", "token": "${secret:AUTH_TOKEN}"},
+            })
+        );
+    }
+
+    #[test]
+    fn strategic_merge_reports_conflicts() {
+        let mut base = json!({"hosts": "a", "port": 80, "keep": [1]});
+        let patch = override_of(
+            MergeStrategy::Strategic,
+            json!({
+                "hosts": {"$append": ["b"]},
+                "port": "8080",
+                "keep": {"$append": 2},
+                "mode": {"$patch": "merge", "x": 1},
+            }),
+        );
+        let diagnostics = patch.apply_to(&mut base);
+        let codes: Vec<_> = diagnostics
+            .iter()
+            .map(|diag| (diag.code.as_str(), diag.path.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                ("CONFIG_OVERRIDE_TYPE_CHANGED", "config.hosts"),
+                ("CONFIG_OVERRIDE_INVALID_DIRECTIVE", "config.keep"),
+                ("CONFIG_OVERRIDE_INVALID_DIRECTIVE", "config.mode"),
+                ("CONFIG_OVERRIDE_TYPE_CHANGED", "config.port"),
+            ]
+        );
+        assert_eq!(base, json!({"hosts": ["b"], "port": "8080", "keep": [1]}));
+    }
+}
@@ -0,0 +1,315 @@
+//! Configuration values with `${secret:...}` and `${env:...}` references.
+//!
+//! A [`ConfigValue`] is plain JSON on the wire. Strings inside it may embed references that are
+//! validated when the value is parsed and substituted later through a [`ConfigResolver`]:
+//!
+//! - `${secret:KEY}` reads a secret; `KEY` must be a valid [`SecretKey`].
+//! - `${env:NAME}` reads an environment variable; `NAME` must match `[A-Za-z_][A-Za-z0-9_]*`.
+//! - `$${` produces a literal `${`.
+//!
+//! Deserialization does not validate: documents stored before references existed may hold
+//! strings such as `${HOME}`, and those strings are kept and resolved as literal text.
+//! [`ConfigValue::validate`] reports them.
+
+#[cfg(feature = "schemars")]
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::{ErrorCode, GResult, GreenticError, SecretKey};
+
+/// Reference embedded in a configuration string.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigRef {
+    /// Secret looked up by key.
+    Secret(SecretKey),
+    /// Environment variable looked up by name.
+    Env(String),
+}
+
+impl fmt::Display for ConfigRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Secret(key) => write!(f, "${{secret:{}}}", key.as_str()),
+            Self::Env(name) => write!(f, "${{env:{name}}}"),
+        }
+    }
+}
+
+/// Piece of a parsed configuration string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigSegment {
+    /// Literal text, with `$${` escapes already unescaped.
+    Text(String),
+    /// Reference to substitute.
+    Ref(ConfigRef),
+}
+
+/// Splits `input` into literal text and references.
+pub fn parse_config_string(input: &str) -> GResult<Vec<ConfigSegment>> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = input;
+    while let Some(start) = rest.find('$') {
+        text.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if let Some(after) = tail.strip_prefix("$${") {
+            text.push_str("${");
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("${") {
+            let end = match after.find('}') {
+                Some(end) => end,
+                None => return Err(invalid(input, "unterminated `${`")),
+            };
+            if !text.is_empty() {
+                segments.push(ConfigSegment::Text(core::mem::take(&mut text)));
+            }
+            segments.push(ConfigSegment::Ref(parse_ref(input, &after[..end])?));
+            rest = &after[end + 1..];
+        } else {
+            text.push('$');
+            rest = &tail[1..];
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        segments.push(ConfigSegment::Text(text));
+    }
+    Ok(segments)
+}
+
+fn parse_ref(input: &str, body: &str) -> GResult<ConfigRef> {
+    let (source, name) = match body.split_once(':') {
+        Some(parts) => parts,
+        None => {
+            return Err(invalid(
+                input,
+                &format!("reference `${{{body}}}` must be `${{secret:...}}` or `${{env:...}}`"),
+            ));
+        }
+    };
+    match source {
+        "secret" => SecretKey::parse(name)
+            .map(ConfigRef::Secret)
+            .map_err(|err| invalid(input, &format!("{err}"))),
+        "env" if is_env_name(name) => Ok(ConfigRef::Env(name.to_owned())),
+        "env" => Err(invalid(
+            input,
+            &format!("invalid environment variable name '{name}'"),
+        )),
+        other => Err(invalid(
+            input,
+            &format!("unknown reference source '{other}'"),
+        )),
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn invalid(input: &str, reason: &str) -> GreenticError {
+    GreenticError::new(
+        ErrorCode::InvalidInput,
+        format!("invalid config value '{input}': {reason}"),
+    )
+}
+
+/// Supplies the values of [`ConfigRef`]s during resolution.
+pub trait ConfigResolver {
+    /// Returns the value of `reference`.
+    fn resolve(&self, reference: &ConfigRef) -> GResult<String>;
+}
+
+impl<F> ConfigResolver for F
+where
+    F: Fn(&ConfigRef) -> GResult<String>,
+{
+    fn resolve(&self, reference: &ConfigRef) -> GResult<String> {
+        self(reference)
+    }
+}
+
+/// JSON configuration value whose strings may reference secrets and environment variables.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(into = "Value"))]
+pub struct ConfigValue(Value);
+
+impl ConfigValue {
+    /// Validates every reference embedded in `value`.
+    pub fn parse(value: Value) -> GResult<Self> {
+        let value = Self(value);
+        value.validate()?;
+        Ok(value)
+    }
+
+    /// Checks that every string is a well-formed template.
+    ///
+    /// Values built through [`ConfigValue::parse`] always pass; deserialized values may hold
+    /// malformed strings, which the other methods treat as literal text.
+    pub fn validate(&self) -> GResult<()> {
+        for_each_string(&self.0, &mut |text| parse_config_string(text).map(drop))
+    }
+
+    /// Creates a string value referencing the secret `key`.
+    pub fn secret(key: SecretKey) -> Self {
+        Self(Value::String(ConfigRef::Secret(key).to_string()))
+    }
+
+    /// Creates a string value referencing the environment variable `name`.
+    pub fn env(name: impl Into<String>) -> GResult<Self> {
+        let name = name.into();
+        if !is_env_name(&name) {
+            return Err(invalid(
+                &name,
+                "environment variable names must match [A-Za-z_][A-Za-z0-9_]*",
+            ));
+        }
+        Ok(Self(Value::String(ConfigRef::Env(name).to_string())))
+    }
+
+    /// Returns the unresolved JSON value.
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Consumes the wrapper and returns the unresolved JSON value.
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+
+    /// Returns every distinct reference, sorted.
+    pub fn references(&self) -> Vec<ConfigRef> {
+        let mut references = Vec::new();
+        // The visitor never fails.
+        let _ = for_each_string(&self.0, &mut |text| {
+            for segment in segments(text) {
+                if let ConfigSegment::Ref(reference) = segment {
+                    references.push(reference);
+                }
+            }
+            Ok(())
+        });
+        references.sort();
+        references.dedup();
+        references
+    }
+
+    /// Returns `true` when the value embeds at least one reference.
+    pub fn has_references(&self) -> bool {
+        !self.references().is_empty()
+    }
+
+    /// Substitutes every reference using `resolver` and returns the resulting JSON.
+    pub fn resolve(&self, resolver: &impl ConfigResolver) -> GResult<Value> {
+        resolve_value(&self.0, resolver)
+    }
+}
+
+/// Parses `text`, treating a malformed template as literal text.
+fn segments(text: &str) -> Vec<ConfigSegment> {
+    parse_config_string(text).unwrap_or_else(|_| alloc::vec![ConfigSegment::Text(text.to_owned())])
+}
+
+fn for_each_string(value: &Value, visit: &mut impl FnMut(&str) -> GResult<()>) -> GResult<()> {
+    match value {
+        Value::String(text) => visit(text),
+        Value::Array(items) => items
+            .iter()
+            .try_for_each(|item| for_each_string(item, visit)),
+        Value::Object(map) => map
+            .values()
+            .try_for_each(|item| for_each_string(item, visit)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => Ok(()),
+    }
+}
+
+fn resolve_value(value: &Value, resolver: &impl ConfigResolver) -> GResult<Value> {
+    Ok(match value {
+        Value::String(text) => {
+            let mut resolved = String::new();
+            for segment in segments(text) {
+                match segment {
+                    ConfigSegment::Text(text) => resolved.push_str(&text),
+                    ConfigSegment::Ref(reference) => {
+                        resolved.push_str(&resolver.resolve(&reference)?)
+                    }
+                }
+            }
+            Value::String(resolved)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_value(item, resolver))
+                .collect::<GResult<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, item)| Ok((key.clone(), resolve_value(item, resolver)?)))
+                .collect::<GResult<_>>()?,
+        ),
+        Value::Null | Value::Bool(_) | Value::Number(_) => value.clone(),
+    })
+}
+
+impl FromIterator<(String, ConfigValue)> for ConfigValue {
+    /// Collects keyed values into an object; the parts are already validated.
+    fn from_iter<I: IntoIterator<Item = (String, ConfigValue)>>(iter: I) -> Self {
+        Self(Value::Object(
+            iter.into_iter()
+                .map(|(key, value)| (key, value.0))
+                .collect(),
+        ))
+    }
+}
+
+impl From<ConfigValue> for Value {
+    fn from(value: ConfigValue) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Value> for ConfigValue {
+    type Error = GreenticError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        ConfigValue::parse(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ConfigValue {
+    /// Accepts any JSON; see the [module documentation](self) for malformed templates.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for ConfigValue {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("ConfigValue")
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let mut schema = <Value>::json_schema(generator);
+        schema.insert(
+            "description".into(),
+            "JSON value whose strings may contain ${secret:KEY} or ${env:NAME} references".into(),
+        );
+        schema
+    }
+}
@@ -1,6 +1,8 @@
 //! Shared deployment context primitives for Greentic runtimes.
 
 use alloc::string::String;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +29,7 @@ pub enum Cloud {
 /// Platform-level schedulers supported by Greentic deployments.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub enum Platform {
     /// Kubernetes workloads.
     K8s,
@@ -0,0 +1,454 @@
+#![cfg(feature = "serde")]
+
+//! Signed envelopes that authenticate payloads travelling between services.
+//!
+//! [`SignedEnvelope`] carries the encoded payload together with a detached signature, and
+//! [`Signed`] protects tenant-facing documents with one or more signatures over their canonical
+//! CBOR encoding. [`EncryptedPayload`] stores sensitive data (session snapshots, artifacts)
+//! encrypted at rest. The crate does not implement any cryptography itself; callers plug it in through
+//! [`EnvelopeSigner`], [`EnvelopeVerifier`], [`PayloadEncryptor`], and [`PayloadDecryptor`].
+
+use alloc::{format, vec::Vec};
+use core::marker::PhantomData;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::{DesiredState, RepoTenantConfig, cbor::canonical};
+use crate::{
+    EncryptionKeyRef, ErrorCode, GResult, GreenticError, HashDigest, SignatureAlgorithm,
+    SigningKeyRef, WorkerRequest, WorkerResponse,
+};
+
+/// Domain separation tag prefixed to every signing input.
+pub const SIGNED_ENVELOPE_DOMAIN: &str = "greentic.signed-envelope.v1";
+/// Domain separation tag prefixed to every [`Signed`] document signing input.
+pub const SIGNED_DOCUMENT_DOMAIN: &str = "greentic.signed-document.v1";
+
+/// Produces detached signatures for [`SignedEnvelope::seal`].
+pub trait EnvelopeSigner {
+    /// Algorithm used by this signer.
+    fn algorithm(&self) -> SignatureAlgorithm;
+    /// Reference to the key the signature is produced with.
+    fn key(&self) -> SigningKeyRef;
+    /// Signs `message`, returning the raw signature bytes.
+    fn sign(&self, message: &[u8]) -> GResult<Vec<u8>>;
+}
+
+/// Checks detached signatures for [`SignedEnvelope::verify`].
+pub trait EnvelopeVerifier {
+    /// Returns `Ok(true)` when `signature` is valid for `message` under `key`.
+    ///
+    /// Errors are reserved for failures such as unknown keys or unsupported algorithms.
+    fn verify(
+        &self,
+        algorithm: &SignatureAlgorithm,
+        key: &SigningKeyRef,
+        message: &[u8],
+        signature: &[u8],
+    ) -> GResult<bool>;
+}
+
+/// Payload of type `T` encoded as JSON bytes and authenticated by a detached signature.
+///
+/// The signature covers [`SIGNED_ENVELOPE_DOMAIN`], the algorithm, the key reference, and the
+/// payload bytes, so none of them can be swapped without invalidating the envelope.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(bound = ""))]
+pub struct SignedEnvelope<T> {
+    /// Encoded payload (base64 encoded when serialized).
+    #[serde(with = "serde_with::As::<serde_with::base64::Base64>")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub payload: Vec<u8>,
+    /// Signature algorithm.
+    pub algorithm: SignatureAlgorithm,
+    /// Key the signature was produced with.
+    pub key: SigningKeyRef,
+    /// Detached signature (base64 encoded when serialized).
+    #[serde(with = "serde_with::As::<serde_with::base64::Base64>")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub signature: Vec<u8>,
+    #[serde(skip)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    marker: PhantomData<fn() -> T>,
+}
+
+/// Signed [`WorkerRequest`].
+pub type SignedWorkerRequest = SignedEnvelope<WorkerRequest>;
+/// Signed [`WorkerResponse`].
+pub type SignedWorkerResponse = SignedEnvelope<WorkerResponse>;
+
+impl<T> Clone for SignedEnvelope<T> {
+    fn clone(&self) -> Self {
+        Self {
+            payload: self.payload.clone(),
+            algorithm: self.algorithm.clone(),
+            key: self.key.clone(),
+            signature: self.signature.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> SignedEnvelope<T> {
+    /// Encodes `payload` and signs it with `signer`.
+    pub fn seal(payload: &T, signer: &impl EnvelopeSigner) -> GResult<Self> {
+        let payload = serde_json::to_vec(payload).map_err(|err| {
+            GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("failed to encode envelope payload: {err}"),
+            )
+        })?;
+        let algorithm = signer.algorithm();
+        let key = signer.key();
+        let signature = signer.sign(&signing_input(
+            SIGNED_ENVELOPE_DOMAIN,
+            &algorithm,
+            &key,
+            &payload,
+        ))?;
+        Ok(Self {
+            payload,
+            algorithm,
+            key,
+            signature,
+            marker: PhantomData,
+        })
+    }
+
+    /// Checks the signature with `verifier`.
+    pub fn verify(&self, verifier: &impl EnvelopeVerifier) -> GResult<()> {
+        let message = self.signing_input();
+        if verifier.verify(&self.algorithm, &self.key, &message, &self.signature)? {
+            Ok(())
+        } else {
+            Err(GreenticError::new(
+                ErrorCode::Unauthenticated,
+                format!("envelope signature by {} is invalid", self.key.as_str()),
+            ))
+        }
+    }
+
+    /// Verifies the signature and decodes the payload.
+    pub fn open(&self, verifier: &impl EnvelopeVerifier) -> GResult<T> {
+        self.verify(verifier)?;
+        serde_json::from_slice(&self.payload).map_err(|err| {
+            GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("failed to decode envelope payload: {err}"),
+            )
+        })
+    }
+
+    /// Returns the exact bytes covered by the signature.
+    pub fn signing_input(&self) -> Vec<u8> {
+        signing_input(
+            SIGNED_ENVELOPE_DOMAIN,
+            &self.algorithm,
+            &self.key,
+            &self.payload,
+        )
+    }
+}
+
+/// One signature over a [`Signed`] document.
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DocumentSignature {
+    /// Signature algorithm.
+    pub algorithm: SignatureAlgorithm,
+    /// Key the signature was produced with.
+    pub key: SigningKeyRef,
+    /// Detached signature (base64 encoded when serialized).
+    #[serde_as(as = "serde_with::base64::Base64")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub signature: Vec<u8>,
+}
+
+/// Document of type `T` stored as canonical CBOR bytes and protected by detached signatures.
+///
+/// Each signature covers [`SIGNED_DOCUMENT_DOMAIN`], its algorithm, its key reference, and the
+/// canonical payload bytes, so the document verifies identically wherever it is re-serialized.
+/// Documents containing floats cannot be signed, since canonical CBOR rejects them.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(bound = ""))]
+pub struct Signed<T> {
+    /// Canonical CBOR encoding of the document (base64 encoded when serialized).
+    #[serde(with = "serde_with::As::<serde_with::base64::Base64>")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub payload: Vec<u8>,
+    /// Signatures over the payload.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<DocumentSignature>,
+    #[serde(skip)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    marker: PhantomData<fn() -> T>,
+}
+
+/// Signed [`DesiredState`].
+#[cfg(feature = "std")]
+pub type SignedDesiredState = Signed<DesiredState>;
+/// Signed [`RepoTenantConfig`].
+#[cfg(feature = "std")]
+pub type SignedRepoTenantConfig = Signed<RepoTenantConfig>;
+
+impl<T> Clone for Signed<T> {
+    fn clone(&self) -> Self {
+        Self {
+            payload: self.payload.clone(),
+            signatures: self.signatures.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Serialize + DeserializeOwned> Signed<T> {
+    /// Encodes `document` as canonical CBOR without signing it.
+    pub fn new(document: &T) -> GResult<Self> {
+        let payload = canonical::to_canonical_cbor(document).map_err(|err| {
+            GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("failed to encode signed document: {err}"),
+            )
+        })?;
+        Ok(Self {
+            payload,
+            signatures: Vec::new(),
+            marker: PhantomData,
+        })
+    }
+
+    /// Encodes `document` and signs it with `signer`.
+    pub fn seal(document: &T, signer: &impl EnvelopeSigner) -> GResult<Self> {
+        let mut signed = Self::new(document)?;
+        signed.sign(signer)?;
+        Ok(signed)
+    }
+
+    /// Adds a signature produced by `signer`.
+    pub fn sign(&mut self, signer: &impl EnvelopeSigner) -> GResult<()> {
+        let algorithm = signer.algorithm();
+        let key = signer.key();
+        let signature = signer.sign(&self.signing_input(&algorithm, &key))?;
+        self.signatures.push(DocumentSignature {
+            algorithm,
+            key,
+            signature,
+        });
+        Ok(())
+    }
+
+    /// Checks that the payload is canonical and carries at least one signature, all of which
+    /// `verifier` accepts.
+    pub fn verify(&self, verifier: &impl EnvelopeVerifier) -> GResult<()> {
+        canonical::ensure_canonical(&self.payload).map_err(|err| {
+            GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("signed document payload is not canonical: {err}"),
+            )
+        })?;
+        if self.signatures.is_empty() {
+            return Err(GreenticError::new(
+                ErrorCode::Unauthenticated,
+                "signed document carries no signatures",
+            ));
+        }
+        for entry in &self.signatures {
+            let message = self.signing_input(&entry.algorithm, &entry.key);
+            if !verifier.verify(&entry.algorithm, &entry.key, &message, &entry.signature)? {
+                return Err(GreenticError::new(
+                    ErrorCode::Unauthenticated,
+                    format!("document signature by {} is invalid", entry.key.as_str()),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies the signatures and decodes the document.
+    pub fn open(&self, verifier: &impl EnvelopeVerifier) -> GResult<T> {
+        self.verify(verifier)?;
+        canonical::from_cbor(&self.payload).map_err(|err| {
+            GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("failed to decode signed document: {err}"),
+            )
+        })
+    }
+
+    /// Returns the key references of all signatures.
+    pub fn key_refs(&self) -> impl Iterator<Item = &SigningKeyRef> {
+        self.signatures.iter().map(|entry| &entry.key)
+    }
+
+    /// Returns the exact bytes a signature by `key` with `algorithm` covers.
+    pub fn signing_input(&self, algorithm: &SignatureAlgorithm, key: &SigningKeyRef) -> Vec<u8> {
+        signing_input(SIGNED_DOCUMENT_DOMAIN, algorithm, key, &self.payload)
+    }
+}
+
+/// Authenticated encryption algorithm of an [`EncryptedPayload`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum EncryptionAlgorithm {
+    /// AES-256 in Galois/Counter Mode.
+    Aes256Gcm,
+    /// ChaCha20-Poly1305.
+    #[serde(rename = "chacha20_poly1305")]
+    ChaCha20Poly1305,
+    /// XChaCha20-Poly1305 (extended nonce).
+    #[serde(rename = "xchacha20_poly1305")]
+    XChaCha20Poly1305,
+    /// Other algorithms identified by name.
+    Other(String),
+}
+
+impl EncryptionAlgorithm {
+    /// Nonce length in bytes required by the algorithm, when known.
+    pub fn nonce_len(&self) -> Option<usize> {
+        match self {
+            Self::Aes256Gcm | Self::ChaCha20Poly1305 => Some(12),
+            Self::XChaCha20Poly1305 => Some(24),
+            Self::Other(_) => None,
+        }
+    }
+}
+
+/// Encrypts plaintext for [`EncryptedPayload::seal`].
+pub trait PayloadEncryptor {
+    /// Algorithm used by this encryptor.
+    fn algorithm(&self) -> EncryptionAlgorithm;
+    /// Reference to the key the payload is encrypted with.
+    fn key(&self) -> EncryptionKeyRef;
+    /// Encrypts `plaintext` bound to `aad`, returning the nonce and the ciphertext.
+    fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> GResult<(Vec<u8>, Vec<u8>)>;
+}
+
+/// Decrypts ciphertext for [`EncryptedPayload::open`].
+pub trait PayloadDecryptor {
+    /// Decrypts `ciphertext` under `key`, failing when authentication does not succeed.
+    fn decrypt(
+        &self,
+        algorithm: &EncryptionAlgorithm,
+        key: &EncryptionKeyRef,
+        nonce: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> GResult<Vec<u8>>;
+}
+
+/// Ciphertext encrypted at rest with authenticated encryption.
+///
+/// The additional authenticated data (AAD) is not stored; only its Blake3 digest is kept, so a
+/// reader can detect that it is opening the payload in the wrong context before decrypting.
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct EncryptedPayload {
+    /// Key the payload was encrypted with.
+    pub key: EncryptionKeyRef,
+    /// Encryption algorithm.
+    pub algorithm: EncryptionAlgorithm,
+    /// Nonce used for encryption (base64 encoded when serialized).
+    #[serde_as(as = "serde_with::base64::Base64")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub nonce: Vec<u8>,
+    /// Ciphertext including the authentication tag (base64 encoded when serialized).
+    #[serde_as(as = "serde_with::base64::Base64")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub ciphertext: Vec<u8>,
+    /// Digest of the additional authenticated data, when any was bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aad_digest: Option<HashDigest>,
+}
+
+impl EncryptedPayload {
+    /// Encrypts `plaintext` bound to `aad` with `encryptor`.
+    pub fn seal(plaintext: &[u8], aad: &[u8], encryptor: &impl PayloadEncryptor) -> GResult<Self> {
+        let algorithm = encryptor.algorithm();
+        let key = encryptor.key();
+        let (nonce, ciphertext) = encryptor.encrypt(plaintext, aad)?;
+        let payload = Self {
+            key,
+            algorithm,
+            nonce,
+            ciphertext,
+            aad_digest: (!aad.is_empty()).then(|| HashDigest::blake3_of(aad)),
+        };
+        payload.check_nonce()?;
+        Ok(payload)
+    }
+
+    /// Returns `true` when `aad` matches the bound digest (or both are absent).
+    pub fn matches_aad(&self, aad: &[u8]) -> bool {
+        match &self.aad_digest {
+            Some(digest) => *digest == HashDigest::blake3_of(aad),
+            None => aad.is_empty(),
+        }
+    }
+
+    /// Checks `aad` and the nonce length, then decrypts with `decryptor`.
+    pub fn open(&self, aad: &[u8], decryptor: &impl PayloadDecryptor) -> GResult<Vec<u8>> {
+        if !self.matches_aad(aad) {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "additional authenticated data does not match the encrypted payload",
+            ));
+        }
+        self.check_nonce()?;
+        decryptor.decrypt(
+            &self.algorithm,
+            &self.key,
+            &self.nonce,
+            &self.ciphertext,
+            aad,
+        )
+    }
+
+    fn check_nonce(&self) -> GResult<()> {
+        match self.algorithm.nonce_len() {
+            Some(expected) if expected != self.nonce.len() => Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "nonce must be {expected} bytes for {:?}, got {}",
+                    self.algorithm,
+                    self.nonce.len()
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn signing_input(
+    domain: &str,
+    algorithm: &SignatureAlgorithm,
+    key: &SigningKeyRef,
+    payload: &[u8],
+) -> Vec<u8> {
+    let algorithm = match algorithm {
+        SignatureAlgorithm::Ed25519 => "ed25519",
+        SignatureAlgorithm::Other(name) => name.as_str(),
+    };
+    let mut out = Vec::with_capacity(payload.len() + 64);
+    for part in [
+        domain.as_bytes(),
+        algorithm.as_bytes(),
+        key.as_str().as_bytes(),
+        payload,
+    ] {
+        out.extend_from_slice(&(part.len() as u64).to_be_bytes());
+        out.extend_from_slice(part);
+    }
+    out
+}
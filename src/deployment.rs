@@ -3,7 +3,7 @@
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use crate::SecretRequirement;
+use crate::{MaintenanceWindow, SecretRequirement};
 use semver::Version;
 
 #[cfg(feature = "schemars")]
@@ -49,6 +49,12 @@ pub struct DeploymentPlan {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub telemetry: Option<TelemetryPlan>,
+    /// Maintenance windows during which rollouts of this plan must be deferred.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
     /// Free-form extension space.
     #[cfg_attr(feature = "serde", serde(default))]
     pub extra: Value,
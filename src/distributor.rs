@@ -11,7 +11,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{SecretRequirement, TenantCtx};
+use crate::{ByteSize, SecretRequirement, TenantCtx};
 
 /// Identifier for a distributor environment.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -118,6 +118,82 @@ pub enum ArtifactLocation {
     },
 }
 
+/// Content encoding applied to an artifact download.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum CompressionCodec {
+    /// No compression.
+    Identity,
+    /// gzip.
+    Gzip,
+    /// Zstandard.
+    Zstd,
+    /// Brotli.
+    Brotli,
+}
+
+/// Alternate download location for an artifact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ArtifactMirror {
+    /// Download URL or reference understood by the runner.
+    pub url: String,
+    /// Priority of the mirror; lower values are tried first.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority: u32,
+}
+
+/// Download metadata accompanying an [`ArtifactLocation`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct TransferHints {
+    /// Codecs the distributor can serve, in its order of preference.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub compression: Vec<CompressionCodec>,
+    /// Preferred chunk size for ranged downloads.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub chunk_size: Option<ByteSize>,
+    /// Whether interrupted downloads can resume with byte-range requests.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub resumable: bool,
+    /// Mirrors that serve the same artifact.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub mirrors: Vec<ArtifactMirror>,
+}
+
+impl TransferHints {
+    /// Picks the distributor's most preferred codec that the client accepts.
+    ///
+    /// Falls back to [`CompressionCodec::Identity`] when nothing matches.
+    pub fn negotiate(&self, accept_encodings: &[CompressionCodec]) -> CompressionCodec {
+        self.compression
+            .iter()
+            .copied()
+            .find(|codec| accept_encodings.contains(codec))
+            .unwrap_or(CompressionCodec::Identity)
+    }
+
+    /// Returns mirrors ordered by priority, keeping declaration order for ties.
+    pub fn mirrors_by_priority(&self) -> Vec<&ArtifactMirror> {
+        let mut mirrors: Vec<&ArtifactMirror> = self.mirrors.iter().collect();
+        mirrors.sort_by_key(|mirror| mirror.priority);
+        mirrors
+    }
+}
+
 /// Summary of artifact signature verification.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -159,6 +235,12 @@ pub struct ResolveComponentRequest {
     pub component_id: String,
     /// Requested version or label.
     pub version: String,
+    /// Codecs the caller can decode; empty means uncompressed only.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub accept_encodings: Vec<CompressionCodec>,
     /// Opaque extension field.
     pub extra: Value,
 }
@@ -174,6 +256,12 @@ pub struct ResolveComponentResponse {
     pub digest: ComponentDigest,
     /// Location of the resolved artifact.
     pub artifact: ArtifactLocation,
+    /// Download hints for `artifact`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub transfer: Option<TransferHints>,
     /// Signature summary.
     pub signature: SignatureSummary,
     /// Cache metadata.
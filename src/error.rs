@@ -1,11 +1,14 @@
 //! Shared error types for Greentic crates.
 
+use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 
 #[cfg(feature = "std")]
@@ -39,6 +42,113 @@ pub enum ErrorCode {
     Internal,
 }
 
+/// Broad class of an [`ErrorCode`], used to pick handling and reporting paths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ErrorCategory {
+    /// The request is malformed or refers to missing or conflicting data.
+    Validation,
+    /// The caller is not authenticated or not allowed to perform the operation.
+    Auth,
+    /// The caller exceeded a quota or rate limit.
+    Capacity,
+    /// A timeout or unavailable dependency; the same request may succeed later.
+    Transient,
+    /// A platform fault or unclassified failure.
+    Internal,
+}
+
+impl ErrorCode {
+    /// Every error code, in declaration order.
+    pub const ALL: [ErrorCode; 10] = [
+        ErrorCode::Unknown,
+        ErrorCode::InvalidInput,
+        ErrorCode::NotFound,
+        ErrorCode::Conflict,
+        ErrorCode::Timeout,
+        ErrorCode::Unauthenticated,
+        ErrorCode::PermissionDenied,
+        ErrorCode::RateLimited,
+        ErrorCode::Unavailable,
+        ErrorCode::Internal,
+    ];
+
+    /// Category the code belongs to.
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            Self::InvalidInput | Self::NotFound | Self::Conflict => ErrorCategory::Validation,
+            Self::Unauthenticated | Self::PermissionDenied => ErrorCategory::Auth,
+            Self::RateLimited => ErrorCategory::Capacity,
+            Self::Timeout | Self::Unavailable => ErrorCategory::Transient,
+            Self::Unknown | Self::Internal => ErrorCategory::Internal,
+        }
+    }
+
+    /// Whether retrying the same request may succeed without changes.
+    ///
+    /// Transient and capacity errors are retryable; callers should still honour backoff and
+    /// any `Retry-After` hints.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self.category(),
+            ErrorCategory::Transient | ErrorCategory::Capacity
+        )
+    }
+
+    /// HTTP status code services should respond with.
+    pub fn http_status(self) -> u16 {
+        match self {
+            Self::InvalidInput => 400,
+            Self::Unauthenticated => 401,
+            Self::PermissionDenied => 403,
+            Self::NotFound => 404,
+            Self::Conflict => 409,
+            Self::RateLimited => 429,
+            Self::Unknown | Self::Internal => 500,
+            Self::Unavailable => 503,
+            Self::Timeout => 504,
+        }
+    }
+
+    /// gRPC status code (as defined in `google.rpc.Code`) services should respond with.
+    pub fn grpc_status(self) -> u32 {
+        match self {
+            Self::Unknown => 2,
+            Self::InvalidInput => 3,
+            Self::Timeout => 4,
+            Self::NotFound => 5,
+            Self::PermissionDenied => 7,
+            Self::RateLimited => 8,
+            Self::Conflict => 10,
+            Self::Internal => 13,
+            Self::Unavailable => 14,
+            Self::Unauthenticated => 16,
+        }
+    }
+
+    /// Maps an HTTP status code back to the closest error code.
+    ///
+    /// Returns `None` for non-error statuses (below 400).
+    pub fn from_http_status(status: u16) -> Option<Self> {
+        let code = match status {
+            0..=399 => return None,
+            400 | 405 | 406 | 411 | 413..=416 | 422 => Self::InvalidInput,
+            401 => Self::Unauthenticated,
+            403 => Self::PermissionDenied,
+            404 | 410 => Self::NotFound,
+            409 | 412 => Self::Conflict,
+            408 | 504 => Self::Timeout,
+            429 => Self::RateLimited,
+            502 | 503 => Self::Unavailable,
+            500..=599 => Self::Internal,
+            _ => Self::Unknown,
+        };
+        Some(code)
+    }
+}
+
 /// Error type carrying a code and message.
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -49,6 +159,18 @@ pub struct GreenticError {
     pub code: ErrorCode,
     /// Human-readable error message.
     pub message: String,
+    /// Context added by callers as the error propagated, innermost first.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub context: Vec<String>,
+    /// Structured fields attached while the error propagated.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub fields: BTreeMap<String, Value>,
     /// Optional source error for debugging.
     #[cfg(feature = "std")]
     #[cfg_attr(feature = "serde", serde(skip, default = "default_source"))]
@@ -63,11 +185,48 @@ impl GreenticError {
         Self {
             code,
             message: message.into(),
+            context: Vec::new(),
+            fields: BTreeMap::new(),
             #[cfg(feature = "std")]
             source: None,
         }
     }
 
+    /// Category of the error code.
+    pub fn category(&self) -> ErrorCategory {
+        self.code.category()
+    }
+
+    /// Whether retrying the failed request may succeed; see [`ErrorCode::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        self.code.is_retryable()
+    }
+
+    /// Adds a context message describing what the caller was doing when the error occurred.
+    ///
+    /// The code and message are left untouched so callers can keep matching on them.
+    pub fn context(mut self, message: impl Into<String>) -> Self {
+        self.context.push(message.into());
+        self
+    }
+
+    /// Attaches a structured field; later values for the same key replace earlier ones.
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.fields.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the message prefixed by the context stack, outermost first.
+    pub fn display_chain(&self) -> String {
+        let mut out = String::new();
+        for context in self.context.iter().rev() {
+            out.push_str(context);
+            out.push_str(": ");
+        }
+        out.push_str(&self.message);
+        out
+    }
+
     /// Attaches a source error to the `GreenticError`.
     #[cfg(feature = "std")]
     pub fn with_source<E>(mut self, source: E) -> Self
@@ -107,3 +266,29 @@ impl From<uuid::Error> for GreenticError {
 
 /// Convenient result alias for Greentic APIs.
 pub type GResult<T> = core::result::Result<T, GreenticError>;
+
+/// Adds [`GreenticError`] context to results.
+pub trait ResultExt<T> {
+    /// Adds a context message to the error, if any.
+    fn context(self, message: impl Into<String>) -> GResult<T>;
+
+    /// Adds a lazily built context message to the error, if any.
+    fn with_context<F, S>(self, message: F) -> GResult<T>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>;
+}
+
+impl<T> ResultExt<T> for GResult<T> {
+    fn context(self, message: impl Into<String>) -> GResult<T> {
+        self.map_err(|err| err.context(message))
+    }
+
+    fn with_context<F, S>(self, message: F) -> GResult<T>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>,
+    {
+        self.map_err(|err| err.context(message()))
+    }
+}
@@ -0,0 +1,228 @@
+//! Declarative event subscriptions reconciled as part of a [`DesiredState`](crate::DesiredState).
+//!
+//! An [`EventSubscriptionSpec`] binds an event provider to a consumer: which events to take
+//! ([`EventFilter`]), which consumer group shares the work, where a new group starts reading
+//! ([`CursorStrategy`]), and where undeliverable events go ([`DeadLetterTarget`]).
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ErrorCode, EventEnvelope, GResult, GreenticError, UnixMillis, validate_identifier};
+
+/// Selects the events a subscription receives.
+///
+/// Empty lists match everything. Topic patterns are exact, or end in `*` to match by prefix
+/// (`greentic.repo.*`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct EventFilter {
+    /// Topic patterns.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub topics: Vec<String>,
+    /// Accepted event types.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub types: Vec<String>,
+    /// Required subject prefix.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub subject_prefix: Option<String>,
+}
+
+impl EventFilter {
+    /// Creates a filter for the given topic patterns.
+    pub fn topics<I, S>(topics: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            topics: topics.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` when `event` passes every criterion.
+    pub fn matches(&self, event: &EventEnvelope) -> bool {
+        (self.topics.is_empty() || self.matches_topic(&event.topic))
+            && (self.types.is_empty() || self.types.contains(&event.r#type))
+            && self.subject_prefix.as_deref().is_none_or(|prefix| {
+                event
+                    .subject
+                    .as_deref()
+                    .is_some_and(|subject| subject.starts_with(prefix))
+            })
+    }
+
+    /// Returns `true` when `topic` matches one of the topic patterns.
+    pub fn matches_topic(&self, topic: &str) -> bool {
+        self.topics
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => topic.starts_with(prefix),
+                None => pattern == topic,
+            })
+    }
+}
+
+/// Position a consumer group starts from when it has no committed cursor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum CursorStrategy {
+    /// Only events published after the subscription is created.
+    #[default]
+    Latest,
+    /// The oldest event the provider still retains.
+    Earliest,
+    /// The first event published at or after `at`.
+    Timestamp {
+        /// Start instant.
+        #[cfg_attr(
+            feature = "schemars",
+            schemars(with = "String", description = "RFC3339 timestamp in UTC")
+        )]
+        #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+        at: UnixMillis,
+    },
+}
+
+/// Destination for events that exhausted their delivery attempts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DeadLetterTarget {
+    /// Topic receiving dead-lettered events.
+    pub topic: String,
+    /// Deliveries attempted before an event is dead-lettered; provider default when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_deliveries: Option<u32>,
+}
+
+/// Desired subscription of a consumer to an event provider.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct EventSubscriptionSpec {
+    /// Subscription name, unique within a desired state.
+    pub name: String,
+    /// Event provider name, matching
+    /// [`EventProviderDescriptor::name`](crate::EventProviderDescriptor::name).
+    pub provider: String,
+    /// Events to deliver.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub filter: EventFilter,
+    /// Consumer group sharing deliveries; the subscription name is used when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub consumer_group: Option<String>,
+    /// Start position for a group without a committed cursor.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cursor: CursorStrategy,
+    /// Dead-letter destination; undeliverable events are dropped when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub dead_letter: Option<DeadLetterTarget>,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl EventSubscriptionSpec {
+    /// Creates a subscription receiving every event from `provider`, starting at the latest event.
+    pub fn new(name: impl Into<String>, provider: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            provider: provider.into(),
+            filter: EventFilter::default(),
+            consumer_group: None,
+            cursor: CursorStrategy::default(),
+            dead_letter: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the event filter.
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets the consumer group.
+    pub fn with_consumer_group(mut self, group: impl Into<String>) -> Self {
+        self.consumer_group = Some(group.into());
+        self
+    }
+
+    /// Sets the cursor strategy.
+    pub fn with_cursor(mut self, cursor: CursorStrategy) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Sets the dead-letter destination.
+    pub fn with_dead_letter(mut self, dead_letter: DeadLetterTarget) -> Self {
+        self.dead_letter = Some(dead_letter);
+        self
+    }
+
+    /// Consumer group the provider should register.
+    pub fn effective_consumer_group(&self) -> &str {
+        self.consumer_group.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Checks identifiers and rejects dead-letter topics the subscription itself consumes.
+    pub fn validate(&self) -> GResult<()> {
+        validate_identifier(&self.name, "event subscription name")?;
+        validate_identifier(&self.provider, "event subscription provider")?;
+        if let Some(group) = &self.consumer_group {
+            validate_identifier(group, "event subscription consumer group")?;
+        }
+        if let Some(dead_letter) = &self.dead_letter {
+            if dead_letter.topic.is_empty() {
+                return Err(self.invalid("dead-letter topic must not be empty"));
+            }
+            if dead_letter.max_deliveries == Some(0) {
+                return Err(self.invalid("dead-letter max_deliveries must be positive"));
+            }
+            if self.filter.topics.is_empty() || self.filter.matches_topic(&dead_letter.topic) {
+                return Err(self.invalid(&format!(
+                    "dead-letter topic {} is consumed by the subscription itself",
+                    dead_letter.topic
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn invalid(&self, reason: &str) -> GreenticError {
+        GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("event subscription {}: {reason}", self.name),
+        )
+    }
+}
@@ -1,6 +1,6 @@
 //! Canonical Greentic event envelope shared across repos.
 
-use alloc::{collections::BTreeMap, string::String};
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
 use core::fmt;
 use core::str::FromStr;
 
@@ -11,7 +11,10 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{GResult, GreenticError, TenantCtx, validate_identifier};
+use crate::{
+    ByteSize, EnvelopeLimitError, ErrorCode, GResult, GreenticError, HashedPayload, Limits,
+    OrderingKind, TenantCtx, validate_identifier,
+};
 
 /// Map of metadata entries propagated with an event.
 pub type EventMetadata = BTreeMap<String, String>;
@@ -118,7 +121,236 @@ pub struct EventEnvelope {
     pub correlation_id: Option<String>,
     /// Opaque JSON payload representing the event body.
     pub payload: Value,
+    /// Binary body with its content digest, for events whose body is not JSON or that receivers
+    /// should verify and deduplicate; `payload` is typically `null` when set.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub hashed_payload: Option<HashedPayload>,
     /// Free-form metadata such as idempotency keys.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: EventMetadata,
 }
+
+impl EventEnvelope {
+    /// Combined size of the compact JSON `payload` and any `hashed_payload` bytes.
+    ///
+    /// The JSON size is not stored: every call walks the whole `payload` tree to measure its
+    /// encoding (without allocating it), so callers checking several limits should keep the
+    /// result.
+    pub fn compute_payload_size(&self) -> ByteSize {
+        struct Counter(u64);
+
+        impl fmt::Write for Counter {
+            fn write_str(&mut self, text: &str) -> fmt::Result {
+                self.0 += text.len() as u64;
+                Ok(())
+            }
+        }
+
+        let mut json = Counter(0);
+        // `Value`'s `Display` writes compact JSON and never fails on a counting writer.
+        let _ = fmt::Write::write_fmt(&mut json, format_args!("{}", self.payload));
+        let hashed = self
+            .hashed_payload
+            .as_ref()
+            .map_or(0, |hashed| hashed.bytes.len() as u64);
+        ByteSize::from_bytes(json.0 + hashed)
+    }
+
+    /// Rejects envelopes whose payload exceeds `limits`; see
+    /// [`EventEnvelope::compute_payload_size`] for the cost.
+    pub fn check_limits(&self, limits: &Limits) -> Result<(), EnvelopeLimitError> {
+        limits.check_payload_size(self.compute_payload_size())
+    }
+}
+
+/// Ordered group of envelopes delivered together by an event provider.
+///
+/// `ordering` mirrors the guarantee declared by the provider's
+/// [`EventProviderDescriptor`](crate::EventProviderDescriptor); the split and merge helpers only
+/// perform operations that keep that guarantee intact.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct EventBatch {
+    /// Stable identifier for the batch.
+    pub id: EventId,
+    /// Ordering guarantee the envelopes were delivered with.
+    pub ordering: OrderingKind,
+    /// Partition key shared by every envelope, when the provider partitions by key.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub partition_key: Option<String>,
+    /// Deadline by which the batch must be acknowledged before redelivery.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    pub ack_deadline: Option<DateTime<Utc>>,
+    /// Envelopes in delivery order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub events: Vec<EventEnvelope>,
+}
+
+impl EventBatch {
+    /// Creates an empty batch with the given ordering guarantee.
+    pub fn new(id: EventId, ordering: OrderingKind) -> Self {
+        Self {
+            id,
+            ordering,
+            partition_key: None,
+            ack_deadline: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Sets the partition key.
+    pub fn with_partition_key(mut self, key: impl Into<String>) -> Self {
+        self.partition_key = Some(key.into());
+        self
+    }
+
+    /// Sets the acknowledgement deadline.
+    pub fn with_ack_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.ack_deadline = Some(deadline);
+        self
+    }
+
+    /// Appends an envelope to the end of the batch.
+    pub fn push(&mut self, event: EventEnvelope) {
+        self.events.push(event);
+    }
+
+    /// Number of envelopes in the batch.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` when the batch carries no envelopes.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns `true` when the acknowledgement deadline has passed at `now`.
+    pub fn is_ack_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.ack_deadline.is_some_and(|deadline| now > deadline)
+    }
+
+    /// Splits the batch into contiguous chunks of at most `max_len` envelopes.
+    ///
+    /// Chunks keep the original order, partition key, and deadline, so consuming them in sequence
+    /// preserves every [`OrderingKind`]. Chunk ids are `<id>.<index>`.
+    pub fn split(self, max_len: usize) -> GResult<Vec<EventBatch>> {
+        if max_len == 0 {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "batch chunk size must be greater than zero",
+            ));
+        }
+        if self.events.len() <= max_len {
+            return Ok(alloc::vec![self]);
+        }
+        let mut chunks = Vec::new();
+        let mut events = self.events.into_iter().peekable();
+        while events.peek().is_some() {
+            let id = EventId::new(format!("{}.{}", self.id, chunks.len()))?;
+            chunks.push(EventBatch {
+                id,
+                ordering: self.ordering,
+                partition_key: self.partition_key.clone(),
+                ack_deadline: self.ack_deadline,
+                events: events.by_ref().take(max_len).collect(),
+            });
+        }
+        Ok(chunks)
+    }
+
+    /// Regroups the envelopes by the key returned from `key_of`, keeping relative order per key.
+    ///
+    /// Regrouping reorders envelopes across keys, so it is rejected for [`OrderingKind::Global`]
+    /// batches. Sub-batch ids are `<id>.<index>` in first-seen key
+    /// order.
+    pub fn partition_by<F>(self, mut key_of: F) -> GResult<Vec<EventBatch>>
+    where
+        F: FnMut(&EventEnvelope) -> String,
+    {
+        if self.ordering == OrderingKind::Global {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "cannot partition a batch with global ordering",
+            ));
+        }
+        let mut groups: Vec<(String, Vec<EventEnvelope>)> = Vec::new();
+        for event in self.events {
+            let key = key_of(&event);
+            match groups.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, events)) => events.push(event),
+                None => groups.push((key, alloc::vec![event])),
+            }
+        }
+        groups
+            .into_iter()
+            .enumerate()
+            .map(|(index, (key, events))| {
+                Ok(EventBatch {
+                    id: EventId::new(format!("{}.{index}", self.id))?,
+                    ordering: self.ordering,
+                    partition_key: Some(key),
+                    ack_deadline: self.ack_deadline,
+                    events,
+                })
+            })
+            .collect()
+    }
+
+    /// Concatenates `batches` in the given order into a single batch.
+    ///
+    /// All batches must declare the same ordering. For [`OrderingKind::Global`] they must also
+    /// share a partition key (or all have none), since the merged batch can only describe one
+    /// stream. The partition key is kept when shared and cleared otherwise; the earliest ack
+    /// deadline wins.
+    pub fn merge(id: EventId, batches: Vec<EventBatch>) -> GResult<EventBatch> {
+        let Some(first) = batches.first() else {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "cannot merge an empty list of batches",
+            ));
+        };
+        let ordering = first.ordering;
+        let partition_key = first.partition_key.clone();
+        let shared_key = batches
+            .iter()
+            .all(|batch| batch.partition_key == partition_key);
+        if let Some(batch) = batches.iter().find(|batch| batch.ordering != ordering) {
+            return Err(GreenticError::new(
+                ErrorCode::Conflict,
+                format!(
+                    "batch {} declares {:?} ordering, expected {ordering:?}",
+                    batch.id, batch.ordering
+                ),
+            ));
+        }
+        if ordering == OrderingKind::Global && !shared_key {
+            return Err(GreenticError::new(
+                ErrorCode::Conflict,
+                "globally ordered batches from different partitions cannot be merged",
+            ));
+        }
+        let ack_deadline = batches.iter().filter_map(|batch| batch.ack_deadline).min();
+        Ok(EventBatch {
+            id,
+            ordering,
+            partition_key: if shared_key { partition_key } else { None },
+            ack_deadline,
+            events: batches.into_iter().flat_map(|batch| batch.events).collect(),
+        })
+    }
+}
@@ -1,4 +1,4 @@
-//! Provider capability descriptors for event fabrics.
+//! Provider capability descriptors for event fabrics and webhook subscriptions.
 
 use alloc::{string::String, vec::Vec};
 
@@ -7,6 +7,11 @@ use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    AuthUserRefV1, DurationMs, SecretKey, SubscriptionEnsureInV1, SubscriptionRenewInV1, TenantCtx,
+    UnixMillis, UrlRef, WebhookId,
+};
+
 /// High-level role of an event provider.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -58,7 +63,7 @@ pub enum ReliabilityKind {
 }
 
 /// Ordering guarantees for delivered events.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
@@ -113,3 +118,174 @@ impl Default for EventProviderDescriptor {
         }
     }
 }
+
+/// Version of the subscription DTOs produced by [`WebhookSubscription`].
+const SUBSCRIPTION_DTO_VERSION: u32 = 1;
+
+/// How long a provider should keep a webhook subscription alive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum WebhookExpiryPolicy {
+    /// Let the provider apply its default (often maximum) lifetime.
+    ProviderDefault,
+    /// Expire a fixed duration after every ensure or renewal.
+    Sliding {
+        /// Lifetime requested from the provider.
+        lifetime: DurationMs,
+    },
+    /// Expire at a fixed instant.
+    Fixed {
+        /// Expiry instant.
+        #[cfg_attr(
+            feature = "schemars",
+            schemars(with = "String", description = "RFC3339 timestamp in UTC")
+        )]
+        #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+        at: UnixMillis,
+    },
+}
+
+/// Canonical, provider-neutral description of a webhook subscription.
+///
+/// Control planes store this shape and derive the transport-level
+/// [`SubscriptionEnsureInV1`] and [`SubscriptionRenewInV1`] requests from it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct WebhookSubscription {
+    /// Webhook identifier; sent to providers as the binding id.
+    pub webhook_id: WebhookId,
+    /// Tenant owning the subscription.
+    pub tenant: TenantCtx,
+    /// Provider identifier (for example `teams` or `graph`).
+    pub provider: String,
+    /// Resource to watch.
+    pub resource: String,
+    /// Change types to deliver; every change type when empty.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub change_types: Vec<String>,
+    /// Endpoint notifications are delivered to.
+    pub endpoint: UrlRef,
+    /// Secret used to validate deliveries (sent as the provider client state once resolved).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub secret: Option<SecretKey>,
+    /// Expiry requested from the provider.
+    pub expiry: WebhookExpiryPolicy,
+    /// How long before expiry the subscription should be renewed.
+    pub renewal_window: DurationMs,
+}
+
+impl WebhookSubscription {
+    /// Creates a subscription using the provider's default expiry and no renewal window.
+    pub fn new(
+        webhook_id: WebhookId,
+        tenant: TenantCtx,
+        provider: impl Into<String>,
+        resource: impl Into<String>,
+        endpoint: UrlRef,
+    ) -> Self {
+        Self {
+            webhook_id,
+            tenant,
+            provider: provider.into(),
+            resource: resource.into(),
+            change_types: Vec::new(),
+            endpoint,
+            secret: None,
+            expiry: WebhookExpiryPolicy::ProviderDefault,
+            renewal_window: DurationMs::ZERO,
+        }
+    }
+
+    /// Adds a change type to deliver.
+    pub fn with_change_type(mut self, change_type: impl Into<String>) -> Self {
+        self.change_types.push(change_type.into());
+        self
+    }
+
+    /// Sets the delivery validation secret.
+    pub fn with_secret(mut self, secret: SecretKey) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    /// Sets the expiry policy and renewal window.
+    pub fn with_expiry(mut self, expiry: WebhookExpiryPolicy, renewal_window: DurationMs) -> Self {
+        self.expiry = expiry;
+        self.renewal_window = renewal_window;
+        self
+    }
+
+    /// Returns `true` when a subscription expiring at `expires_at` should be renewed at `now`.
+    pub fn renewal_due(&self, expires_at: UnixMillis, now: UnixMillis) -> bool {
+        expires_at.saturating_duration_since(now) <= self.renewal_window
+    }
+
+    /// Builds the provider ensure request.
+    ///
+    /// `client_state` carries the resolved [`secret`](Self::secret); the descriptor only stores
+    /// the reference.
+    pub fn to_ensure_in(
+        &self,
+        client_state: Option<String>,
+        user: AuthUserRefV1,
+    ) -> SubscriptionEnsureInV1 {
+        let (expiration_minutes, expiration_target_unix_ms) = self.expiration();
+        SubscriptionEnsureInV1 {
+            v: SUBSCRIPTION_DTO_VERSION,
+            provider: self.provider.clone(),
+            tenant_hint: Some(self.tenant.tenant_id.as_str().into()),
+            team_hint: self
+                .tenant
+                .team_id
+                .as_ref()
+                .map(|team| team.as_str().into()),
+            binding_id: Some(self.webhook_id.as_str().into()),
+            resource: self.resource.clone(),
+            change_types: self.change_types.clone(),
+            notification_url: self.endpoint.as_str().into(),
+            expiration_minutes,
+            expiration_target_unix_ms,
+            client_state,
+            metadata: None,
+            user,
+        }
+    }
+
+    /// Builds the provider renewal request for the provider's `subscription_id`.
+    pub fn to_renew_in(
+        &self,
+        subscription_id: impl Into<String>,
+        user: AuthUserRefV1,
+    ) -> SubscriptionRenewInV1 {
+        let (expiration_minutes, expiration_target_unix_ms) = self.expiration();
+        SubscriptionRenewInV1 {
+            v: SUBSCRIPTION_DTO_VERSION,
+            provider: self.provider.clone(),
+            subscription_id: subscription_id.into(),
+            expiration_minutes,
+            expiration_target_unix_ms,
+            metadata: None,
+            user,
+        }
+    }
+
+    fn expiration(&self) -> (Option<u32>, Option<u64>) {
+        match &self.expiry {
+            WebhookExpiryPolicy::ProviderDefault => (None, None),
+            WebhookExpiryPolicy::Sliding { lifetime } => {
+                let minutes = lifetime.as_millis().div_ceil(60_000);
+                (Some(u32::try_from(minutes).unwrap_or(u32::MAX)), None)
+            }
+            WebhookExpiryPolicy::Fixed { at } => (None, u64::try_from(at.as_millis()).ok()),
+        }
+    }
+}
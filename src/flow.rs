@@ -2,26 +2,35 @@
 
 use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::hash::BuildHasherDefault;
 
 use fnv::FnvHasher;
 use indexmap::IndexMap;
 use serde_json::Value;
 
-use crate::{ComponentId, FlowId, NodeId};
+use crate::{ComponentCapabilities, ComponentId, ComponentManifest, FlowId, NodeId, Ratio};
 
 /// Build hasher used for flow node maps (Fnv for `no_std` friendliness).
 pub type FlowHasher = BuildHasherDefault<FnvHasher>;
 
+#[cfg(all(feature = "std", feature = "serde"))]
+use crate::HashDigest;
+#[cfg(all(feature = "std", feature = "serde"))]
+use crate::cbor::canonical::CanonicalError;
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Supported flow kinds across Greentic packs.
+///
+/// Built-in kinds serialize as their snake_case name. Third-party runtimes introduce their own
+/// kinds through [`FlowKind::Custom`], which serializes as the namespaced name itself (for example
+/// `acme.batch`).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub enum FlowKind {
     /// Inbound messaging flows (Telegram, Teams, HTTP chat).
@@ -34,6 +43,191 @@ pub enum FlowKind {
     Job,
     /// HTTP-style request/response flows.
     Http,
+    /// Namespaced kind defined outside this crate (`vendor.kind`).
+    ///
+    /// Construct through [`FlowKind::custom`] or parsing so the name is validated.
+    Custom(CustomFlowKind),
+}
+
+/// Namespace reserved for kinds defined by this crate.
+pub const FLOW_KIND_RESERVED_NAMESPACE: &str = "greentic";
+
+/// Maximum length in bytes of a custom flow kind name.
+pub const CUSTOM_FLOW_KIND_MAX_LEN: usize = 63;
+
+/// Validated name of a [`FlowKind::Custom`] kind.
+///
+/// The name is stored inline, up to [`CUSTOM_FLOW_KIND_MAX_LEN`] bytes, so `FlowKind` stays
+/// `Copy`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomFlowKind {
+    len: u8,
+    bytes: [u8; CUSTOM_FLOW_KIND_MAX_LEN],
+}
+
+impl CustomFlowKind {
+    /// Returns the namespaced name (for example `acme.batch`).
+    pub fn as_str(&self) -> &str {
+        // Only validated ASCII names are stored.
+        core::str::from_utf8(&self.bytes[..usize::from(self.len)]).unwrap_or_default()
+    }
+}
+
+impl AsRef<str> for CustomFlowKind {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl core::fmt::Debug for CustomFlowKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl core::fmt::Display for CustomFlowKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Errors produced when parsing a [`FlowKind`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum FlowKindError {
+    /// The name is neither a built-in kind nor a well-formed `vendor.kind` name.
+    #[error(
+        "invalid flow kind `{0}`: expected a built-in kind or lowercase `vendor.kind` segments"
+    )]
+    Invalid(String),
+    /// The name uses the namespace reserved for built-in kinds.
+    #[error("flow kind `{0}` uses the reserved `greentic` namespace")]
+    ReservedNamespace(String),
+    /// The name is longer than [`CUSTOM_FLOW_KIND_MAX_LEN`] bytes.
+    #[error("flow kind `{0}` is longer than {CUSTOM_FLOW_KIND_MAX_LEN} bytes")]
+    TooLong(String),
+}
+
+impl FlowKind {
+    /// Built-in kinds, in declaration order.
+    pub const BUILTIN: [FlowKind; 5] = [
+        FlowKind::Messaging,
+        FlowKind::Event,
+        FlowKind::ComponentConfig,
+        FlowKind::Job,
+        FlowKind::Http,
+    ];
+
+    /// Creates a custom kind after validating its namespaced name.
+    ///
+    /// Names consist of at least two `.`-separated segments of lowercase ASCII letters, digits,
+    /// `_` or `-`, each starting with a letter, and are at most [`CUSTOM_FLOW_KIND_MAX_LEN`] bytes
+    /// long. The first segment is the vendor namespace and must not be
+    /// [`FLOW_KIND_RESERVED_NAMESPACE`].
+    pub fn custom(name: impl Into<String>) -> Result<Self, FlowKindError> {
+        let name = name.into();
+        let mut segments = name.split('.');
+        let namespace = segments.next().unwrap_or_default();
+        let well_formed = name.contains('.')
+            && name.split('.').all(|segment| {
+                segment
+                    .bytes()
+                    .next()
+                    .is_some_and(|b| b.is_ascii_lowercase())
+                    && segment.bytes().all(|b| {
+                        b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'_' | b'-')
+                    })
+            });
+        if !well_formed {
+            return Err(FlowKindError::Invalid(name));
+        }
+        if namespace == FLOW_KIND_RESERVED_NAMESPACE {
+            return Err(FlowKindError::ReservedNamespace(name));
+        }
+        if name.len() > CUSTOM_FLOW_KIND_MAX_LEN {
+            return Err(FlowKindError::TooLong(name));
+        }
+        let mut bytes = [0; CUSTOM_FLOW_KIND_MAX_LEN];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+        Ok(FlowKind::Custom(CustomFlowKind {
+            len: name.len() as u8,
+            bytes,
+        }))
+    }
+
+    /// Parses a built-in kind name or a namespaced custom kind.
+    pub fn parse(name: &str) -> Result<Self, FlowKindError> {
+        Self::BUILTIN
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+            .map_or_else(|| Self::custom(name), Ok)
+    }
+
+    /// Serialized name of the kind.
+    pub fn as_str(&self) -> &str {
+        match self {
+            FlowKind::Messaging => "messaging",
+            FlowKind::Event => "event",
+            FlowKind::ComponentConfig => "component_config",
+            FlowKind::Job => "job",
+            FlowKind::Http => "http",
+            FlowKind::Custom(name) => name.as_str(),
+        }
+    }
+
+    /// Returns `true` for kinds defined by this crate.
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, FlowKind::Custom(_))
+    }
+
+    /// Vendor namespace of a custom kind.
+    pub fn namespace(&self) -> Option<&str> {
+        match self {
+            FlowKind::Custom(name) => name.as_str().split('.').next(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` when something declaring support for `self` can run a flow of `kind`.
+    ///
+    /// Kinds accept themselves. A custom kind also accepts its namespaced refinements, so support
+    /// for `acme.batch` covers `acme.batch.nightly`; built-in kinds never accept custom kinds.
+    pub fn accepts(&self, kind: &FlowKind) -> bool {
+        match (self, kind) {
+            (FlowKind::Custom(supported), FlowKind::Custom(requested)) => requested
+                .as_str()
+                .strip_prefix(supported.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.')),
+            _ => self == kind,
+        }
+    }
+}
+
+impl core::fmt::Display for FlowKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::str::FromStr for FlowKind {
+    type Err = FlowKindError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for FlowKind {
+    type Error = FlowKindError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<FlowKind> for String {
+    fn from(value: FlowKind) -> Self {
+        value.as_str().into()
+    }
 }
 
 /// Canonical flow representation embedded in packs.
@@ -75,6 +269,17 @@ pub struct Flow {
 }
 
 impl Flow {
+    /// Blake3 digest of the flow's canonical CBOR encoding.
+    ///
+    /// Map keys are sorted during canonicalisation, except for the nodes: they are hashed as an
+    /// ordered list of `[id, node]` pairs because the first node is the ingress. Floats in
+    /// mappings and metadata are encoded in their shortest exact IEEE 754 width, so hashing a
+    /// flow that came from JSON does not fail.
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub fn content_digest(&self) -> Result<HashDigest, CanonicalError> {
+        crate::cbor::canonical::StableHash::stable_hash(self)
+    }
+
     /// Returns `true` when no nodes are defined.
     pub fn is_empty(&self) -> bool {
         self.nodes.is_empty()
@@ -84,6 +289,15 @@ impl Flow {
     pub fn ingress(&self) -> Option<(&NodeId, &Node)> {
         self.nodes.iter().next()
     }
+
+    /// Returns the effective telemetry hints for a node, layered over the flow defaults.
+    pub fn node_telemetry(&self, node_id: &NodeId) -> Option<TelemetryHints> {
+        let node = self.nodes.get(node_id)?;
+        Some(match &self.metadata.telemetry {
+            Some(defaults) => node.telemetry.resolve_with(defaults),
+            None => node.telemetry.clone(),
+        })
+    }
 }
 
 /// Flow node representation.
@@ -104,6 +318,31 @@ pub struct Node {
     /// Optional telemetry hints for this node.
     #[cfg_attr(feature = "serde", serde(default))]
     pub telemetry: TelemetryHints,
+    /// Restricted capabilities granted to the component at this node.
+    ///
+    /// Must be covered by the component's declared capabilities; pack validation reports
+    /// anything broader.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub capabilities_override: Option<ComponentCapabilities>,
+}
+
+impl Node {
+    /// Capabilities the component runs with at this node: the override when present, otherwise
+    /// the component's declaration.
+    ///
+    /// The override is returned as is; pack validation rejects overrides that exceed the
+    /// component or that cannot be checked because the component is aliased or undeclared.
+    pub fn effective_capabilities<'a>(
+        &'a self,
+        component: &'a ComponentManifest,
+    ) -> &'a ComponentCapabilities {
+        self.capabilities_override
+            .as_ref()
+            .unwrap_or(&component.capabilities)
+    }
 }
 
 /// Component reference within a flow.
@@ -170,6 +409,12 @@ pub struct FlowMetadata {
     /// Free-form metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub extra: Value,
+    /// Flow-wide telemetry defaults applied beneath node-level hints.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub telemetry: Option<TelemetryHints>,
 }
 
 impl Default for FlowMetadata {
@@ -179,6 +424,7 @@ impl Default for FlowMetadata {
             description: None,
             tags: BTreeSet::new(),
             extra: Value::Null,
+            telemetry: None,
         }
     }
 }
@@ -218,12 +464,15 @@ pub enum Routing {
     Custom(Value),
 }
 
-/// Optional telemetry hints for a node.
+/// Optional telemetry hints for a node or flow.
+///
+/// Flow-level hints (see [`FlowMetadata::telemetry`]) act as defaults; node-level hints override
+/// them field by field.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub struct TelemetryHints {
-    /// Optional span name.
+    /// Optional span name override.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -238,4 +487,73 @@ pub struct TelemetryHints {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub sampling: Option<String>,
+    /// Optional sampling ratio in `0.0..=1.0` overriding the global sampler, kept at basis-point
+    /// precision.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub sample_ratio: Option<Ratio>,
+    /// Attribute keys allowed on exported spans/logs (empty allows every key).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attribute_allowlist: Vec<String>,
+}
+
+impl TelemetryHints {
+    /// Returns `true` when no hint is set.
+    pub fn is_empty(&self) -> bool {
+        self.span_name.is_none()
+            && self.attributes.is_empty()
+            && self.sampling.is_none()
+            && self.sample_ratio.is_none()
+            && self.attribute_allowlist.is_empty()
+    }
+
+    /// Returns `true` when the attribute key may be exported.
+    pub fn allows_attribute(&self, key: &str) -> bool {
+        self.attribute_allowlist.is_empty()
+            || self
+                .attribute_allowlist
+                .iter()
+                .any(|allowed| allowed == key)
+    }
+
+    /// Returns the attributes filtered through the allow-list.
+    pub fn exported_attributes(&self) -> BTreeMap<String, String> {
+        self.attributes
+            .iter()
+            .filter(|(key, _)| self.allows_attribute(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Resolves these hints on top of `defaults`, with `self` taking precedence.
+    ///
+    /// Attributes are merged key by key; the allow-list is inherited only when `self` does not
+    /// declare one.
+    pub fn resolve_with(&self, defaults: &TelemetryHints) -> TelemetryHints {
+        let mut attributes = defaults.attributes.clone();
+        attributes.extend(
+            self.attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        TelemetryHints {
+            span_name: self
+                .span_name
+                .clone()
+                .or_else(|| defaults.span_name.clone()),
+            attributes,
+            sampling: self.sampling.clone().or_else(|| defaults.sampling.clone()),
+            sample_ratio: self.sample_ratio.or(defaults.sample_ratio),
+            attribute_allowlist: if self.attribute_allowlist.is_empty() {
+                defaults.attribute_allowlist.clone()
+            } else {
+                self.attribute_allowlist.clone()
+            },
+        }
+    }
 }
@@ -0,0 +1,238 @@
+//! Static checks of flow node bindings against component manifests.
+//!
+//! [`validate_flow_bindings`] resolves each node's component and operation and, when the
+//! operation declares an input schema, checks the literal parts of the node's input mapping
+//! against it. Only a structural subset of JSON Schema is understood (`type`, `enum`, `const`,
+//! `properties`, `required`, `additionalProperties`, `items`); other keywords are ignored, so a
+//! clean result does not prove the mapping valid.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde_json::Value;
+
+use crate::validate::{Diagnostic, Severity, core_diagnostic};
+use crate::{ComponentManifest, ComponentOperation, Flow, Node};
+
+/// Checks that every node of `flow` binds to an operation declared by `components` and that
+/// literal input mapping values satisfy the operation input schema.
+///
+/// Nodes whose component is not in `components` produce a warning and are otherwise skipped.
+/// A node without an explicit operation is checked against the component's operation only when
+/// the component declares exactly one. String values containing `{{` or `${` are treated as
+/// templates resolved at runtime and never checked; `required` is not enforced on the top-level
+/// mapping, which the runtime may complete from the incoming payload. A `null` mapping means the
+/// node has no mapping and passes the incoming payload through, so it is not checked either.
+pub fn validate_flow_bindings(flow: &Flow, components: &[ComponentManifest]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (node_id, node) in flow.nodes.iter() {
+        let base = format!("nodes.{}", node_id.as_str());
+        let Some(component) = components
+            .iter()
+            .find(|component| component.id == node.component.id)
+        else {
+            diagnostics.push(core_diagnostic(
+                Severity::Warn,
+                "FLOW_BINDING_COMPONENT_UNKNOWN",
+                &format!(
+                    "Component {} is not available; bindings were not checked.",
+                    node.component.id.as_str()
+                ),
+                Some(format!("{base}.component.id")),
+                Some("Pass the component manifest to validate this node.".to_owned()),
+            ));
+            continue;
+        };
+        let Some(operation) =
+            resolve_operation(node, &component.operations, &base, &mut diagnostics)
+        else {
+            continue;
+        };
+        if node.input.mapping.is_null() {
+            continue;
+        }
+        if let Value::Object(_) = operation.input_schema {
+            let mut violations = Vec::new();
+            check_literal(
+                &operation.input_schema,
+                &node.input.mapping,
+                &format!("{base}.input.mapping"),
+                true,
+                &mut violations,
+            );
+            diagnostics.extend(violations.into_iter().map(|(path, message)| {
+                core_diagnostic(
+                    Severity::Error,
+                    "FLOW_BINDING_INPUT_SCHEMA_MISMATCH",
+                    &format!(
+                        "Input mapping does not satisfy the {} input schema: {message}.",
+                        operation.name
+                    ),
+                    Some(path),
+                    None,
+                )
+            }));
+        }
+    }
+    diagnostics
+}
+
+fn resolve_operation<'a>(
+    node: &Node,
+    operations: &'a [ComponentOperation],
+    base: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<&'a ComponentOperation> {
+    match &node.component.operation {
+        Some(name) => {
+            let found = operations.iter().find(|operation| operation.name == *name);
+            if found.is_none() {
+                diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "FLOW_BINDING_OPERATION_MISSING",
+                    &format!(
+                        "Component {} does not declare operation {name}.",
+                        node.component.id.as_str()
+                    ),
+                    Some(format!("{base}.component.operation")),
+                    Some(operation_hint(operations)),
+                ));
+            }
+            found
+        }
+        None => match operations {
+            [only] => Some(only),
+            _ => None,
+        },
+    }
+}
+
+fn operation_hint(operations: &[ComponentOperation]) -> String {
+    if operations.is_empty() {
+        return "The component declares no operations.".to_owned();
+    }
+    let names: Vec<&str> = operations
+        .iter()
+        .map(|operation| operation.name.as_str())
+        .collect();
+    format!("Declared operations: {}.", names.join(", "))
+}
+
+fn is_template(value: &Value) -> bool {
+    matches!(value, Value::String(text) if text.contains("{{") || text.contains("${"))
+}
+
+fn check_literal(
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    top_level: bool,
+    violations: &mut Vec<(String, String)>,
+) {
+    let Value::Object(schema) = schema else {
+        return;
+    };
+    if is_template(value) {
+        return;
+    }
+    if let Some(expected) = schema.get("type") {
+        if !type_matches(expected, value) {
+            violations.push((
+                path.to_owned(),
+                format!("expected type {expected}, found {}", value_type(value)),
+            ));
+            return;
+        }
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            violations.push((path.to_owned(), format!("{value} is not an allowed value")));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            violations.push((path.to_owned(), format!("expected {expected}")));
+        }
+    }
+    match value {
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, field) in fields {
+                let field_path = format!("{path}.{key}");
+                match (
+                    properties.and_then(|props| props.get(key)),
+                    schema.get("additionalProperties"),
+                ) {
+                    (Some(field_schema), _) => {
+                        check_literal(field_schema, field, &field_path, false, violations)
+                    }
+                    (None, Some(Value::Bool(false))) => {
+                        violations.push((field_path, "property is not allowed".to_owned()))
+                    }
+                    (None, Some(extra)) => {
+                        check_literal(extra, field, &field_path, false, violations)
+                    }
+                    (None, None) => {}
+                }
+            }
+            if !top_level {
+                if let Some(Value::Array(required)) = schema.get("required") {
+                    for key in required.iter().filter_map(Value::as_str) {
+                        if !fields.contains_key(key) {
+                            violations.push((
+                                path.to_owned(),
+                                format!("missing required property {key}"),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check_literal(
+                        item_schema,
+                        item,
+                        &format!("{path}.{index}"),
+                        false,
+                        violations,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => type_name_matches(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| type_name_matches(name, value)),
+        _ => true,
+    }
+}
+
+fn type_name_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        other => value_type(value) == other,
+    }
+}
+
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
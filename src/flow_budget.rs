@@ -0,0 +1,241 @@
+//! Pre-flight resource estimates for flows.
+//!
+//! [`estimate_flow_budget`] adds up the [`ResourceHints`] of the components a flow invokes along
+//! its worst-case path from the ingress node, and [`FlowBudget::check`] compares the estimate with
+//! the [`Limits`] the flow runs under. Every `Branch` target is considered, so the estimate holds
+//! whichever status a node returns. Each dimension takes its own worst path: the memory estimate
+//! may come from a different branch than the wall-time estimate.
+//!
+//! Memory is summed rather than maximised because component instances stay resident for the
+//! lifetime of the flow instance, which is what [`Limits::memory`] bounds. Loops are counted once;
+//! a flow that routes back to an earlier node is flagged as [`FlowBudget::cyclic`] because its
+//! real cost depends on how often the loop runs.
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::flow_metrics::successors;
+use crate::validate::{Diagnostic, Severity, core_diagnostic};
+use crate::{ByteSize, ComponentId, DurationMs, Flow, FlowId, Limits, NodeId, ResourceHints};
+
+/// Worst-case resource estimate for one run of a flow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct FlowBudget {
+    /// Sum of component memory hints along the most memory-hungry path.
+    #[cfg_attr(feature = "serde", serde(rename = "memory_bytes"))]
+    pub memory: ByteSize,
+    /// Sum of component latency hints along the slowest path.
+    #[cfg_attr(feature = "serde", serde(rename = "wall_time_ms"))]
+    pub wall_time: DurationMs,
+    /// Sum of component fuel hints along the most fuel-hungry path.
+    pub fuel: u64,
+    /// Nodes of the slowest path, starting at the ingress node.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub critical_path: Vec<NodeId>,
+    /// Reachable nodes whose component has no resource hints; they count as free.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub missing_hints: Vec<NodeId>,
+    /// Whether the flow routes back to a node it already passed through.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cyclic: bool,
+}
+
+impl FlowBudget {
+    /// Returns `true` when every reachable node had hints and the flow has no loops.
+    pub fn is_exact(&self) -> bool {
+        self.missing_hints.is_empty() && !self.cyclic
+    }
+
+    /// Returns an error for every estimate above `limits` and a warning for every reason the
+    /// estimate may be too low.
+    ///
+    /// A zero memory or wall-time ceiling (as in [`Limits::default`]) is treated as undeclared.
+    pub fn check(&self, flow_id: &FlowId, limits: &Limits) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let base = format!("flows.{flow_id}");
+        let mut exceeded = |code: &str, what: &str, value: u64, limit: Option<u64>| {
+            if let Some(limit) = limit
+                && value > limit
+            {
+                let mut diagnostic = core_diagnostic(
+                    Severity::Error,
+                    code,
+                    &format!(
+                        "Flow {flow_id} needs an estimated {what} of {value}, above the limit of {limit}."
+                    ),
+                    Some(format!("{base}.nodes")),
+                    Some(
+                        "Raise the limit, trim the critical path, or move work to a separate flow."
+                            .to_owned(),
+                    ),
+                );
+                diagnostic.data = json!({ "estimate": value, "limit": limit });
+                diagnostics.push(diagnostic);
+            }
+        };
+        let declared = |value: u64| (value > 0).then_some(value);
+        exceeded(
+            "FLOW_BUDGET_MEMORY_EXCEEDED",
+            "memory in bytes",
+            self.memory.as_bytes(),
+            declared(limits.memory.as_bytes()),
+        );
+        exceeded(
+            "FLOW_BUDGET_WALL_TIME_EXCEEDED",
+            "wall time in milliseconds",
+            self.wall_time.as_millis(),
+            declared(limits.wall_time.as_millis()),
+        );
+        exceeded("FLOW_BUDGET_FUEL_EXCEEDED", "fuel", self.fuel, limits.fuel);
+
+        for node in &self.missing_hints {
+            diagnostics.push(core_diagnostic(
+                Severity::Warn,
+                "FLOW_BUDGET_MISSING_HINTS",
+                &format!("Node {node} in flow {flow_id} uses a component without resource hints."),
+                Some(format!("{base}.nodes.{node}")),
+                Some("Declare resources in the component manifest.".to_owned()),
+            ));
+        }
+        if self.cyclic {
+            diagnostics.push(core_diagnostic(
+                Severity::Warn,
+                "FLOW_BUDGET_CYCLIC",
+                &format!("Flow {flow_id} loops; the estimate covers a single pass."),
+                Some(format!("{base}.nodes")),
+                None,
+            ));
+        }
+        diagnostics
+    }
+}
+
+/// Estimates the worst-case resources for one run of `flow`, given the hints of its components.
+///
+/// Unreachable nodes and dangling routing targets are ignored; see [`Flow::metrics`] for those.
+pub fn estimate_flow_budget(
+    flow: &Flow,
+    components: &BTreeMap<ComponentId, ResourceHints>,
+) -> FlowBudget {
+    let mut estimator = Estimator {
+        flow,
+        components,
+        visits: BTreeMap::new(),
+        cyclic: false,
+    };
+    let Some((ingress, _)) = flow.ingress() else {
+        return FlowBudget::default();
+    };
+    let cost = estimator.visit(ingress).unwrap_or_default();
+
+    let mut critical_path = Vec::new();
+    let mut cursor = Some(ingress);
+    while let Some(id) = cursor {
+        critical_path.push(id.clone());
+        cursor = match estimator.visits.get(id) {
+            Some(Visit::Done { next, .. }) => *next,
+            _ => None,
+        };
+    }
+    let missing_hints = flow
+        .nodes
+        .values()
+        .filter(|node| {
+            estimator.visits.contains_key(&node.id) && !components.contains_key(&node.component.id)
+        })
+        .map(|node| node.id.clone())
+        .collect();
+
+    FlowBudget {
+        memory: ByteSize::from_bytes(cost.memory),
+        wall_time: DurationMs::from_millis(cost.wall_time),
+        fuel: cost.fuel,
+        critical_path,
+        missing_hints,
+        cyclic: estimator.cyclic,
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Cost {
+    memory: u64,
+    wall_time: u64,
+    fuel: u64,
+}
+
+enum Visit<'a> {
+    Active,
+    Done {
+        cost: Cost,
+        next: Option<&'a NodeId>,
+    },
+}
+
+struct Estimator<'a> {
+    flow: &'a Flow,
+    components: &'a BTreeMap<ComponentId, ResourceHints>,
+    visits: BTreeMap<&'a NodeId, Visit<'a>>,
+    cyclic: bool,
+}
+
+impl<'a> Estimator<'a> {
+    /// Worst-case cost from `id` to the end of the flow, or `None` when `id` is missing or already
+    /// on the current path.
+    fn visit(&mut self, id: &'a NodeId) -> Option<Cost> {
+        match self.visits.get(id) {
+            Some(Visit::Active) => {
+                self.cyclic = true;
+                return None;
+            }
+            Some(Visit::Done { cost, .. }) => return Some(*cost),
+            None => {}
+        }
+        let flow = self.flow;
+        let (id, node) = flow.nodes.get_key_value(id)?;
+        self.visits.insert(id, Visit::Active);
+
+        let mut worst = Cost::default();
+        let mut next = None;
+        for successor in successors(&node.routing) {
+            let Some(cost) = self.visit(successor) else {
+                continue;
+            };
+            if next.is_none() || cost.wall_time > worst.wall_time {
+                next = Some(successor);
+            }
+            worst.memory = worst.memory.max(cost.memory);
+            worst.wall_time = worst.wall_time.max(cost.wall_time);
+            worst.fuel = worst.fuel.max(cost.fuel);
+        }
+
+        let hints = self.components.get(&node.component.id);
+        let own = |field: fn(&ResourceHints) -> Option<u64>| hints.and_then(field).unwrap_or(0);
+        let cost = Cost {
+            memory: worst
+                .memory
+                .saturating_add(own(|hints| hints.memory.map(|memory| memory.as_bytes()))),
+            wall_time: worst.wall_time.saturating_add(own(|hints| {
+                hints.average_latency.map(|latency| latency.as_millis())
+            })),
+            fuel: worst.fuel.saturating_add(own(|hints| hints.fuel)),
+        };
+        self.visits.insert(id, Visit::Done { cost, next });
+        Some(cost)
+    }
+}
@@ -0,0 +1,681 @@
+//! Compact text syntax for [`Flow`] documents (feature `flow-dsl`).
+//!
+//! The DSL is line based; blank lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! flow support-triage messaging
+//! title "Support triage"
+//! tags ["support", "demo"]
+//! entry default {"channel": "teams"}
+//!
+//! node classify = ai.classifier#classify -> [billing: billing, _: answer]
+//!   in {"text": "{{input.text}}"}
+//! node billing = shared/crm.lookup -> answer
+//! node answer = templates.reply -> reply
+//! ```
+//!
+//! - `flow <id> <kind>` is required; `version <schema-version>` defaults to `flow-v1`.
+//! - `title`, `description`, and `tags` take JSON values; `entry <name> [<json>]` adds an
+//!   entrypoint.
+//! - `node <id> = [<pack-alias>/]<component>[#<operation>] -> <routing>` declares a node, in
+//!   order. Routing is `end`, `reply`, `custom <json>`, a node id, or a branch list
+//!   `[<status>: <node>, ..., _: <default>]`.
+//! - `in <json>` and `out <json>` set the input and output mapping of the preceding node.
+//!
+//! Words may be written as JSON strings, which is how a node literally named `end` is targeted.
+//! Problems are reported as [`Diagnostic`]s whose `path` is `line:column` and whose `data` holds
+//! `line`, `column`, and `end_column` (1-based, in characters).
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+use serde_json::{Value, json};
+
+use crate::flow::{
+    ComponentRef, Flow, FlowKind, FlowMetadata, InputMapping, Node, OutputMapping, Routing,
+    TelemetryHints,
+};
+use crate::validate::{Diagnostic, Severity};
+use crate::{ComponentId, ErrorCode, FlowId, GResult, GreenticError, NodeId};
+
+const DEFAULT_SCHEMA_VERSION: &str = "flow-v1";
+
+/// Parses DSL `source` into a flow, returning every problem found on failure.
+pub fn parse_flow_dsl(source: &str) -> Result<Flow, Vec<Diagnostic>> {
+    let mut parser = Parser::default();
+    for (index, text) in source.lines().enumerate() {
+        let mut line = Line {
+            number: index + 1,
+            text,
+            pos: 0,
+        };
+        if line.at_end() || line.rest().starts_with('#') {
+            continue;
+        }
+        if let Err(err) = parser.statement(&mut line) {
+            parser.diagnostics.push(err.into_diagnostic());
+        }
+    }
+    parser.finish()
+}
+
+/// Renders `flow` in the DSL accepted by [`parse_flow_dsl`].
+///
+/// Fails with [`ErrorCode::InvalidInput`] when the flow uses features the DSL cannot express:
+/// telemetry hints, `metadata.extra`, or aliases and operations containing whitespace, `/`, or
+/// `#`.
+pub fn flow_to_dsl(flow: &Flow) -> GResult<String> {
+    if !flow.metadata.extra.is_null() || flow.metadata.telemetry.is_some() {
+        return Err(unsupported(
+            "flow metadata `extra` and `telemetry` have no DSL form",
+        ));
+    }
+    let mut out = String::new();
+    let _ = writeln!(out, "flow {} {}", flow.id, flow.kind);
+    if flow.schema_version != DEFAULT_SCHEMA_VERSION {
+        let _ = writeln!(out, "version {}", word(&flow.schema_version, ""));
+    }
+    if let Some(title) = &flow.metadata.title {
+        let _ = writeln!(out, "title {}", to_json(&Value::from(title.as_str()))?);
+    }
+    if let Some(description) = &flow.metadata.description {
+        let _ = writeln!(
+            out,
+            "description {}",
+            to_json(&Value::from(description.as_str()))?
+        );
+    }
+    if !flow.metadata.tags.is_empty() {
+        let tags: Vec<Value> = flow
+            .metadata
+            .tags
+            .iter()
+            .map(|tag| Value::from(tag.as_str()))
+            .collect();
+        let _ = writeln!(out, "tags {}", to_json(&Value::Array(tags))?);
+    }
+    for (name, value) in &flow.entrypoints {
+        match value {
+            Value::Null => {
+                let _ = writeln!(out, "entry {}", word(name, ""));
+            }
+            value => {
+                let _ = writeln!(out, "entry {} {}", word(name, ""), to_json(value)?);
+            }
+        }
+    }
+
+    for (id, node) in &flow.nodes {
+        if node.telemetry != TelemetryHints::default() {
+            return Err(unsupported(&format!(
+                "node '{id}' has telemetry hints, which have no DSL form"
+            )));
+        }
+        if node.capabilities_override.is_some() {
+            return Err(unsupported(&format!(
+                "node '{id}' overrides capabilities, which have no DSL form"
+            )));
+        }
+        let _ = writeln!(
+            out,
+            "\nnode {id} = {} -> {}",
+            component_word(id, &node.component)?,
+            routing_text(&node.routing)?
+        );
+        if !node.input.mapping.is_null() {
+            let _ = writeln!(out, "  in {}", to_json(&node.input.mapping)?);
+        }
+        if !node.output.mapping.is_null() {
+            let _ = writeln!(out, "  out {}", to_json(&node.output.mapping)?);
+        }
+    }
+    Ok(out)
+}
+
+fn component_word(id: &NodeId, component: &ComponentRef) -> GResult<String> {
+    let mut text = String::new();
+    if let Some(alias) = &component.pack_alias {
+        if !is_plain(alias, "/#") {
+            return Err(unsupported(&format!(
+                "node '{id}' uses pack alias '{alias}', which has no DSL form"
+            )));
+        }
+        text.push_str(alias);
+        text.push('/');
+    }
+    text.push_str(component.id.as_str());
+    if let Some(operation) = &component.operation {
+        if !is_plain(operation, "/#") {
+            return Err(unsupported(&format!(
+                "node '{id}' uses operation '{operation}', which has no DSL form"
+            )));
+        }
+        text.push('#');
+        text.push_str(operation);
+    }
+    Ok(text)
+}
+
+fn routing_text(routing: &Routing) -> GResult<String> {
+    Ok(match routing {
+        Routing::End => "end".into(),
+        Routing::Reply => "reply".into(),
+        Routing::Custom(value) => format!("custom {}", to_json(value)?),
+        Routing::Next { node_id } => match node_id.as_str() {
+            keyword @ ("end" | "reply" | "custom") => to_json(&Value::from(keyword))?,
+            id => id.into(),
+        },
+        Routing::Branch { on_status, default } => {
+            let mut arms: Vec<String> = on_status
+                .iter()
+                .map(|(status, target)| {
+                    let key = match status.as_str() {
+                        "_" => "\"_\"".into(),
+                        status => word(status, ":,]"),
+                    };
+                    format!("{key}: {target}")
+                })
+                .collect();
+            if let Some(target) = default {
+                arms.push(format!("_: {target}"));
+            }
+            format!("[{}]", arms.join(", "))
+        }
+    })
+}
+
+/// Writes `text` bare when it can be read back as a word, otherwise as a JSON string.
+fn word(text: &str, delims: &str) -> String {
+    if is_plain(text, delims) && !text.starts_with('"') {
+        text.into()
+    } else {
+        Value::from(text).to_string()
+    }
+}
+
+fn is_plain(text: &str, delims: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| !c.is_whitespace() && !delims.contains(c))
+}
+
+fn to_json(value: &Value) -> GResult<String> {
+    serde_json::to_string(value).map_err(|err| {
+        GreenticError::new(ErrorCode::Internal, format!("failed to encode JSON: {err}"))
+    })
+}
+
+fn unsupported(reason: &str) -> GreenticError {
+    GreenticError::new(
+        ErrorCode::InvalidInput,
+        format!("flow cannot be written as DSL: {reason}"),
+    )
+}
+
+#[derive(Default)]
+struct Parser {
+    header: Option<(FlowId, FlowKind)>,
+    schema_version: Option<String>,
+    metadata: FlowMetadata,
+    entrypoints: BTreeMap<String, Value>,
+    nodes: Vec<Node>,
+    /// Routing targets with the position they were written at, checked once all nodes are known.
+    targets: Vec<(NodeId, DslError)>,
+    /// Whether `in`/`out` lines belong to a node that failed to parse.
+    in_broken_node: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Parser {
+    fn statement(&mut self, line: &mut Line<'_>) -> Result<(), DslError> {
+        let keyword = line.word("")?;
+        if keyword.quoted {
+            return Err(keyword.error("FLOW_DSL_SYNTAX", "expected a statement keyword"));
+        }
+        match keyword.text.as_str() {
+            "flow" => {
+                if self.header.is_some() {
+                    return Err(keyword.error("FLOW_DSL_SYNTAX", "duplicate `flow` header"));
+                }
+                let id = line.word("")?;
+                let id = id.parse::<FlowId>()?;
+                let kind = line.word("")?;
+                let kind = match FlowKind::parse(&kind.text) {
+                    Ok(kind) => kind,
+                    Err(err) => return Err(kind.error("FLOW_DSL_SYNTAX", &err.to_string())),
+                };
+                self.header = Some((id, kind));
+            }
+            "version" => self.schema_version = Some(line.word("")?.text),
+            "title" => self.metadata.title = Some(line.json_string()?),
+            "description" => self.metadata.description = Some(line.json_string()?),
+            "tags" => {
+                let start = line.pos;
+                let tags = match line.json()? {
+                    Value::Array(tags) => tags
+                        .into_iter()
+                        .map(|tag| match tag {
+                            Value::String(tag) => Some(tag),
+                            _ => None,
+                        })
+                        .collect::<Option<_>>(),
+                    _ => None,
+                };
+                match tags {
+                    Some(tags) => self.metadata.tags = tags,
+                    None => {
+                        return Err(line.error(
+                            start,
+                            line.pos,
+                            "FLOW_DSL_SYNTAX",
+                            "tags must be a JSON array of strings",
+                        ));
+                    }
+                }
+            }
+            "entry" => {
+                let name = line.word("")?;
+                let value = if line.at_end() {
+                    Value::Null
+                } else {
+                    line.json()?
+                };
+                self.entrypoints.insert(name.text, value);
+            }
+            "node" => {
+                self.in_broken_node = true;
+                let node = self.node(line)?;
+                self.in_broken_node = false;
+                self.nodes.push(node);
+            }
+            "in" | "out" => {
+                let mapping = line.json()?;
+                match self.nodes.last_mut() {
+                    _ if self.in_broken_node => {}
+                    Some(node) if keyword.text == "in" => node.input.mapping = mapping,
+                    Some(node) => node.output.mapping = mapping,
+                    None => {
+                        return Err(keyword.error(
+                            "FLOW_DSL_SYNTAX",
+                            &format!("`{}` must follow a `node` line", keyword.text),
+                        ));
+                    }
+                }
+            }
+            other => {
+                return Err(
+                    keyword.error("FLOW_DSL_SYNTAX", &format!("unknown statement `{other}`"))
+                );
+            }
+        }
+        line.expect_end()
+    }
+
+    fn node(&mut self, line: &mut Line<'_>) -> Result<Node, DslError> {
+        let id = line.word("")?;
+        if self.nodes.iter().any(|node| node.id.as_str() == id.text) {
+            return Err(id.error(
+                "FLOW_DSL_DUPLICATE_NODE",
+                &format!("node '{}' is declared more than once", id.text),
+            ));
+        }
+        let id = id.parse::<NodeId>()?;
+        line.expect("=")?;
+        let component = line.word("")?;
+        let (alias, rest) = match component.text.split_once('/') {
+            Some((alias, rest)) => (Some(alias.to_string()), rest),
+            None => (None, component.text.as_str()),
+        };
+        let (component_id, operation) = match rest.split_once('#') {
+            Some((component_id, operation)) => (component_id, Some(operation.to_string())),
+            None => (rest, None),
+        };
+        if alias.as_deref() == Some("") {
+            return Err(
+                component.error("FLOW_DSL_SYNTAX", "pack alias before `/` must not be empty")
+            );
+        }
+        if operation.as_deref() == Some("") {
+            return Err(component.error("FLOW_DSL_SYNTAX", "operation after `#` must not be empty"));
+        }
+        let component_id = match component_id.parse::<ComponentId>() {
+            Ok(component_id) => component_id,
+            Err(err) => return Err(component.error("FLOW_DSL_SYNTAX", &err.message)),
+        };
+        line.expect("->")?;
+        let routing = self.routing(line)?;
+        Ok(Node {
+            id,
+            component: ComponentRef {
+                id: component_id,
+                pack_alias: alias,
+                operation,
+            },
+            input: InputMapping {
+                mapping: Value::Null,
+            },
+            output: OutputMapping {
+                mapping: Value::Null,
+            },
+            routing,
+            telemetry: TelemetryHints::default(),
+            capabilities_override: None,
+        })
+    }
+
+    fn routing(&mut self, line: &mut Line<'_>) -> Result<Routing, DslError> {
+        if line.eat('[') {
+            return self.branch(line);
+        }
+        let target = line.word("")?;
+        if !target.quoted {
+            match target.text.as_str() {
+                "end" => return Ok(Routing::End),
+                "reply" => return Ok(Routing::Reply),
+                "custom" => return Ok(Routing::Custom(line.json()?)),
+                _ => {}
+            }
+        }
+        Ok(Routing::Next {
+            node_id: self.target(target)?,
+        })
+    }
+
+    fn branch(&mut self, line: &mut Line<'_>) -> Result<Routing, DslError> {
+        let mut on_status = BTreeMap::new();
+        let mut default = None;
+        loop {
+            if line.eat(']') {
+                break;
+            }
+            let status = line.word(":,]")?;
+            line.expect(":")?;
+            let target = line.word(",]")?;
+            let target = self.target(target)?;
+            let duplicate = match status.text.as_str() {
+                "_" if !status.quoted => default.replace(target).is_some(),
+                _ => on_status.insert(status.text.clone(), target).is_some(),
+            };
+            if duplicate {
+                return Err(status.error(
+                    "FLOW_DSL_SYNTAX",
+                    &format!("branch `{}` is listed more than once", status.text),
+                ));
+            }
+            if !line.eat(',') && !line.peek_is(']') {
+                let pos = line.pos;
+                return Err(line.error(pos, pos, "FLOW_DSL_SYNTAX", "expected `,` or `]`"));
+            }
+        }
+        Ok(Routing::Branch { on_status, default })
+    }
+
+    fn target(&mut self, word: Word) -> Result<NodeId, DslError> {
+        let node_id = word.parse::<NodeId>()?;
+        self.targets.push((
+            node_id.clone(),
+            word.error(
+                "FLOW_DSL_UNKNOWN_NODE",
+                &format!("routing targets unknown node '{node_id}'"),
+            ),
+        ));
+        Ok(node_id)
+    }
+
+    fn finish(mut self) -> Result<Flow, Vec<Diagnostic>> {
+        for (node_id, err) in core::mem::take(&mut self.targets) {
+            if !self.nodes.iter().any(|node| node.id == node_id) {
+                self.diagnostics.push(err.into_diagnostic());
+            }
+        }
+        let (id, kind) = match self.header {
+            Some(header) => header,
+            None => {
+                let err = DslError {
+                    code: "FLOW_DSL_MISSING_HEADER",
+                    message: "missing `flow <id> <kind>` header".into(),
+                    line: 1,
+                    column: 1,
+                    end_column: 1,
+                };
+                self.diagnostics.insert(0, err.into_diagnostic());
+                return Err(self.diagnostics);
+            }
+        };
+        if !self.diagnostics.is_empty() {
+            return Err(self.diagnostics);
+        }
+        Ok(Flow {
+            schema_version: self
+                .schema_version
+                .unwrap_or_else(|| DEFAULT_SCHEMA_VERSION.into()),
+            id,
+            kind,
+            entrypoints: self.entrypoints,
+            nodes: self
+                .nodes
+                .into_iter()
+                .map(|node| (node.id.clone(), node))
+                .collect(),
+            metadata: self.metadata,
+        })
+    }
+}
+
+/// Problem located on a single line.
+struct DslError {
+    code: &'static str,
+    message: String,
+    line: usize,
+    column: usize,
+    end_column: usize,
+}
+
+impl DslError {
+    fn into_diagnostic(self) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code: self.code.into(),
+            message: self.message,
+            path: Some(format!("{}:{}", self.line, self.column)),
+            hint: None,
+            data: json!({
+                "line": self.line,
+                "column": self.column,
+                "end_column": self.end_column,
+            }),
+        }
+    }
+}
+
+/// Bare or JSON-quoted token.
+struct Word {
+    text: String,
+    quoted: bool,
+    line: usize,
+    column: usize,
+    end_column: usize,
+}
+
+impl Word {
+    fn error(&self, code: &'static str, message: &str) -> DslError {
+        DslError {
+            code,
+            message: message.into(),
+            line: self.line,
+            column: self.column,
+            end_column: self.end_column,
+        }
+    }
+
+    fn parse<T>(&self) -> Result<T, DslError>
+    where
+        T: core::str::FromStr<Err = GreenticError>,
+    {
+        self.text
+            .parse()
+            .map_err(|err: GreenticError| self.error("FLOW_DSL_SYNTAX", &err.message))
+    }
+}
+
+struct Line<'a> {
+    number: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl Line<'_> {
+    fn rest(&self) -> &str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_ws();
+        self.pos == self.text.len()
+    }
+
+    fn peek_is(&mut self, c: char) -> bool {
+        self.skip_ws();
+        self.rest().starts_with(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek_is(c);
+        if found {
+            self.pos += c.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), DslError> {
+        self.skip_ws();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            return Ok(());
+        }
+        let pos = self.pos;
+        Err(self.error(pos, pos, "FLOW_DSL_SYNTAX", &format!("expected `{token}`")))
+    }
+
+    fn expect_end(&mut self) -> Result<(), DslError> {
+        if self.at_end() {
+            return Ok(());
+        }
+        let (start, end) = (self.pos, self.text.len());
+        Err(self.error(start, end, "FLOW_DSL_SYNTAX", "unexpected trailing input"))
+    }
+
+    /// Reads a JSON string or a run of characters up to whitespace or one of `delims`.
+    fn word(&mut self, delims: &str) -> Result<Word, DslError> {
+        self.skip_ws();
+        let start = self.pos;
+        let rest = self.rest();
+        let (text, quoted, len) = if rest.starts_with('"') {
+            let len = json_string_len(rest).ok_or_else(|| {
+                self.error(
+                    start,
+                    self.text.len(),
+                    "FLOW_DSL_SYNTAX",
+                    "unterminated string",
+                )
+            })?;
+            let text = serde_json::from_str::<String>(&rest[..len]).map_err(|err| {
+                self.error(start, start + len, "FLOW_DSL_SYNTAX", &err.to_string())
+            })?;
+            (text, true, len)
+        } else {
+            let len = rest
+                .find(|c: char| c.is_whitespace() || delims.contains(c))
+                .unwrap_or(rest.len());
+            (rest[..len].to_string(), false, len)
+        };
+        if len == 0 {
+            let message = match rest.chars().next() {
+                Some(c) => format!("unexpected `{c}`"),
+                None => "unexpected end of line".into(),
+            };
+            return Err(self.error(start, start, "FLOW_DSL_SYNTAX", &message));
+        }
+        self.pos += len;
+        Ok(Word {
+            text,
+            quoted,
+            line: self.number,
+            column: self.column(start),
+            end_column: self.column(self.pos),
+        })
+    }
+
+    /// Reads the rest of the line as JSON.
+    fn json(&mut self) -> Result<Value, DslError> {
+        self.skip_ws();
+        let start = self.pos;
+        let end = self.text.len();
+        let value = serde_json::from_str(self.rest()).map_err(|err| {
+            // serde_json columns are 1-based within the fragment.
+            let offset = self
+                .rest()
+                .char_indices()
+                .nth(err.column().saturating_sub(1))
+                .map_or(end, |(offset, _)| start + offset);
+            self.error(
+                offset,
+                offset,
+                "FLOW_DSL_JSON",
+                &format!("invalid JSON: {err}"),
+            )
+        })?;
+        self.pos = end;
+        Ok(value)
+    }
+
+    fn json_string(&mut self) -> Result<String, DslError> {
+        let start = self.pos;
+        match self.json()? {
+            Value::String(text) => Ok(text),
+            _ => Err(self.error(
+                start,
+                self.text.len(),
+                "FLOW_DSL_SYNTAX",
+                "expected a JSON string",
+            )),
+        }
+    }
+
+    fn column(&self, pos: usize) -> usize {
+        self.text[..pos].chars().count() + 1
+    }
+
+    fn error(&self, start: usize, end: usize, code: &'static str, message: &str) -> DslError {
+        DslError {
+            code,
+            message: message.into(),
+            line: self.number,
+            column: self.column(start),
+            end_column: self.column(end),
+        }
+    }
+}
+
+/// Byte length of the JSON string literal at the start of `text`, including both quotes.
+fn json_string_len(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (offset, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(offset + 1),
+            _ => {}
+        }
+    }
+    None
+}
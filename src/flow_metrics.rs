@@ -0,0 +1,181 @@
+//! Graph metrics for reviewing flow complexity.
+//!
+//! [`Flow::metrics`] measures the routing graph, and [`FlowMetrics::check`] turns measurements
+//! above a [`FlowMetricsThresholds`] into warning diagnostics. Edges come from `Next` and
+//! `Branch` routing only; `Custom` routing is opaque and contributes no edges.
+
+use alloc::borrow::ToOwned;
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::format;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::validate::{Diagnostic, Severity, core_diagnostic};
+use crate::{ComponentId, Flow, FlowId, NodeId, Routing};
+
+/// Size and shape measurements of a flow graph.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct FlowMetrics {
+    /// Number of nodes in the flow.
+    pub node_count: usize,
+    /// Number of nodes on the longest shortest path from the ingress node.
+    pub max_depth: usize,
+    /// Largest number of distinct successors of a single node.
+    pub max_branching_factor: usize,
+    /// Number of nodes bound to each component.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub component_fan_in: BTreeMap<ComponentId, usize>,
+    /// Nodes that cannot be reached from the ingress node.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub unreachable: Vec<NodeId>,
+}
+
+impl FlowMetrics {
+    /// Largest number of nodes bound to one component.
+    pub fn max_component_fan_in(&self) -> usize {
+        self.component_fan_in.values().copied().max().unwrap_or(0)
+    }
+
+    /// Returns a warning for every measurement above `thresholds`.
+    pub fn check(&self, flow_id: &FlowId, thresholds: &FlowMetricsThresholds) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut exceeded = |code: &str, what: &str, value: usize, limit: usize, path: String| {
+            if value > limit {
+                let mut diagnostic = core_diagnostic(
+                    Severity::Warn,
+                    code,
+                    &format!("Flow {flow_id} has {what} {value}, above the limit of {limit}."),
+                    Some(path),
+                    Some("Split the flow into smaller flows or shared sub-flows.".to_owned()),
+                );
+                diagnostic.data = json!({ "value": value, "threshold": limit });
+                diagnostics.push(diagnostic);
+            }
+        };
+        let base = format!("flows.{flow_id}");
+        exceeded(
+            "FLOW_METRICS_NODE_COUNT_EXCEEDED",
+            "node count",
+            self.node_count,
+            thresholds.max_nodes,
+            format!("{base}.nodes"),
+        );
+        exceeded(
+            "FLOW_METRICS_DEPTH_EXCEEDED",
+            "depth",
+            self.max_depth,
+            thresholds.max_depth,
+            format!("{base}.nodes"),
+        );
+        exceeded(
+            "FLOW_METRICS_BRANCHING_EXCEEDED",
+            "branching factor",
+            self.max_branching_factor,
+            thresholds.max_branching_factor,
+            format!("{base}.nodes"),
+        );
+        for (component, uses) in &self.component_fan_in {
+            exceeded(
+                "FLOW_METRICS_COMPONENT_FAN_IN_EXCEEDED",
+                &format!("component {component} fan-in"),
+                *uses,
+                thresholds.max_component_fan_in,
+                format!("{base}.components.{component}"),
+            );
+        }
+        diagnostics
+    }
+}
+
+/// Limits above which [`FlowMetrics::check`] warns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct FlowMetricsThresholds {
+    /// Maximum number of nodes.
+    pub max_nodes: usize,
+    /// Maximum depth from the ingress node.
+    pub max_depth: usize,
+    /// Maximum successors of a single node.
+    pub max_branching_factor: usize,
+    /// Maximum nodes bound to one component.
+    pub max_component_fan_in: usize,
+}
+
+impl Default for FlowMetricsThresholds {
+    fn default() -> Self {
+        Self {
+            max_nodes: 50,
+            max_depth: 20,
+            max_branching_factor: 8,
+            max_component_fan_in: 10,
+        }
+    }
+}
+
+impl Flow {
+    /// Measures the routing graph of this flow.
+    ///
+    /// Depth is measured breadth-first from the ingress node, so cycles do not inflate it.
+    pub fn metrics(&self) -> FlowMetrics {
+        let mut metrics = FlowMetrics {
+            node_count: self.nodes.len(),
+            ..FlowMetrics::default()
+        };
+        for node in self.nodes.values() {
+            *metrics
+                .component_fan_in
+                .entry(node.component.id.clone())
+                .or_default() += 1;
+            metrics.max_branching_factor = metrics
+                .max_branching_factor
+                .max(successors(&node.routing).len());
+        }
+
+        let mut depths: BTreeMap<&NodeId, usize> = BTreeMap::new();
+        if let Some((ingress, _)) = self.ingress() {
+            depths.insert(ingress, 1);
+            let mut queue = VecDeque::from([ingress]);
+            while let Some(id) = queue.pop_front() {
+                let depth = depths[id];
+                metrics.max_depth = metrics.max_depth.max(depth);
+                let Some(node) = self.nodes.get(id) else {
+                    continue;
+                };
+                for next in successors(&node.routing) {
+                    if self.nodes.contains_key(next) && !depths.contains_key(next) {
+                        depths.insert(next, depth + 1);
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        metrics.unreachable = self
+            .nodes
+            .keys()
+            .filter(|id| !depths.contains_key(id))
+            .cloned()
+            .collect();
+        metrics
+    }
+}
+
+pub(crate) fn successors(routing: &Routing) -> BTreeSet<&NodeId> {
+    match routing {
+        Routing::Next { node_id } => BTreeSet::from([node_id]),
+        Routing::Branch { on_status, default } => {
+            on_status.values().chain(default.as_ref()).collect()
+        }
+        Routing::End | Routing::Reply | Routing::Custom(_) => BTreeSet::new(),
+    }
+}
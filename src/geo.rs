@@ -0,0 +1,255 @@
+//! ISO 3166 country and subdivision codes for data residency and tax.
+//!
+//! Codes are checked for shape (two letters for countries, `CC-XXX` for subdivisions) and stored
+//! upper-case; whether a code is currently assigned by ISO is left to the services consuming it.
+
+#[cfg(feature = "schemars")]
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorCode, GResult, GreenticError};
+
+/// ISO 3166-1 alpha-2 country code (for example `DE`).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct CountryCode(String);
+
+impl CountryCode {
+    /// Validates a two-letter code, normalizing it to upper case.
+    pub fn parse(value: impl AsRef<str>) -> GResult<Self> {
+        let value = value.as_ref();
+        if value.len() != 2 || !value.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("invalid country code '{value}': expected two ASCII letters"),
+            ));
+        }
+        Ok(Self(value.to_ascii_uppercase()))
+    }
+
+    /// Returns the upper-case code.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// ISO 3166-2 subdivision code (for example `US-CA` or `DE-BY`).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct RegionCode(String);
+
+impl RegionCode {
+    /// Validates a `CC-XXX` code (country plus one to three alphanumerics), normalizing it to
+    /// upper case.
+    pub fn parse(value: impl AsRef<str>) -> GResult<Self> {
+        let value = value.as_ref();
+        let well_formed = match value.split_once('-') {
+            Some((country, subdivision)) => {
+                CountryCode::parse(country).is_ok()
+                    && (1..=3).contains(&subdivision.len())
+                    && subdivision.bytes().all(|b| b.is_ascii_alphanumeric())
+            }
+            None => false,
+        };
+        if !well_formed {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("invalid region code '{value}': expected `CC-XXX`"),
+            ));
+        }
+        Ok(Self(value.to_ascii_uppercase()))
+    }
+
+    /// Returns the upper-case code.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Country the subdivision belongs to.
+    pub fn country(&self) -> CountryCode {
+        CountryCode(String::from(&self.0[..2]))
+    }
+
+    /// Subdivision part after the country (for example `CA` for `US-CA`).
+    pub fn subdivision(&self) -> &str {
+        &self.0[3..]
+    }
+}
+
+macro_rules! code_newtype_impls {
+    ($name:ident, $description:literal) => {
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = GreenticError;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                $name::parse(value)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = GreenticError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                $name::parse(value)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = GreenticError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                $name::parse(value)
+            }
+        }
+
+        #[cfg(feature = "schemars")]
+        impl JsonSchema for $name {
+            fn schema_name() -> Cow<'static, str> {
+                Cow::Borrowed(stringify!($name))
+            }
+
+            fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                let mut schema = <String>::json_schema(generator);
+                if schema.get("description").is_none() {
+                    schema.insert("description".into(), $description.into());
+                }
+                schema
+            }
+        }
+    };
+}
+
+pub(crate) use code_newtype_impls;
+
+code_newtype_impls!(CountryCode, "ISO 3166-1 alpha-2 country code");
+code_newtype_impls!(RegionCode, "ISO 3166-2 subdivision code");
+
+/// Geographic constraint on where tenant data may be stored or processed.
+///
+/// An empty allow list permits every location not explicitly denied. Region entries also allow
+/// their country, but only that subdivision within it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct RegionConstraint {
+    /// Countries where data may reside.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub allowed_countries: Vec<CountryCode>,
+    /// Subdivisions where data may reside.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub allowed_regions: Vec<RegionCode>,
+    /// Countries where data must never reside; takes precedence over the allow lists.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub denied_countries: Vec<CountryCode>,
+}
+
+impl RegionConstraint {
+    /// Allows only the given countries.
+    pub fn countries(countries: impl IntoIterator<Item = CountryCode>) -> Self {
+        Self {
+            allowed_countries: countries.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` when the constraint has no allow list.
+    pub fn is_unrestricted(&self) -> bool {
+        self.allowed_countries.is_empty() && self.allowed_regions.is_empty()
+    }
+
+    /// Whether data may reside anywhere in `country`.
+    pub fn permits_country(&self, country: &CountryCode) -> bool {
+        if self.denied_countries.contains(country) {
+            return false;
+        }
+        self.is_unrestricted() || self.allowed_countries.contains(country)
+    }
+
+    /// Whether data may reside in the `region` subdivision.
+    pub fn permits_region(&self, region: &RegionCode) -> bool {
+        let country = region.country();
+        if self.denied_countries.contains(&country) {
+            return false;
+        }
+        self.is_unrestricted()
+            || self.allowed_countries.contains(&country)
+            || self.allowed_regions.contains(region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_normalize_and_reject_bad_shapes() {
+        let country = CountryCode::parse("de").unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(country.as_str(), "DE");
+        let region = RegionCode::parse("us-ca").unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(region.as_str(), "US-CA");
+        assert_eq!(region.country().as_str(), "US");
+        assert_eq!(region.subdivision(), "CA");
+
+        for invalid in ["", "D", "DEU", "1A", "é1"] {
+            assert!(CountryCode::parse(invalid).is_err(), "{invalid}");
+        }
+        for invalid in ["US", "US-", "US-CALI", "U1-CA", "US_CA", "US-C A"] {
+            assert!(RegionCode::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn region_constraint_applies_allow_and_deny_lists() {
+        let code = |value: &str| CountryCode::parse(value).unwrap_or_else(|err| panic!("{err}"));
+        let region = |value: &str| RegionCode::parse(value).unwrap_or_else(|err| panic!("{err}"));
+
+        let open = RegionConstraint {
+            denied_countries: Vec::from([code("RU")]),
+            ..RegionConstraint::default()
+        };
+        assert!(open.permits_country(&code("US")));
+        assert!(!open.permits_region(&region("RU-MOW")));
+
+        let eu = RegionConstraint {
+            allowed_regions: Vec::from([region("US-CA")]),
+            ..RegionConstraint::countries([code("DE"), code("FR")])
+        };
+        assert!(eu.permits_country(&code("DE")));
+        assert!(eu.permits_region(&region("DE-BY")));
+        assert!(eu.permits_region(&region("US-CA")));
+        assert!(!eu.permits_region(&region("US-NY")));
+        assert!(!eu.permits_country(&code("US")));
+    }
+}
@@ -0,0 +1,133 @@
+//! Content-addressed binary payloads.
+//!
+//! A [`HashedPayload`] carries bytes together with their digest so receivers can check integrity
+//! before decoding and drop duplicates by comparing digests alone. The digest always covers the
+//! bytes exactly as carried, i.e. after compression. Deserialization rejects Blake3 payloads whose
+//! bytes do not match their digest; digests of other algorithms are passed through unchecked.
+
+use alloc::format;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{BinaryPayload, ErrorCode, GResult, GreenticError, HashAlgorithm, HashDigest};
+
+/// Compression applied to payload bytes before hashing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum PayloadCompression {
+    /// gzip (RFC 1952).
+    Gzip,
+    /// Raw deflate (RFC 1951).
+    Deflate,
+    /// Zstandard.
+    Zstd,
+    /// Brotli.
+    Brotli,
+}
+
+/// Binary payload paired with its content digest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct HashedPayload {
+    /// Payload bytes (base64 encoded when serialized).
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::base64::Base64>")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "alloc::string::String"))]
+    pub bytes: BinaryPayload,
+    /// Digest of `bytes`.
+    pub digest: HashDigest,
+    /// Compression applied to `bytes`; uncompressed when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub compression: Option<PayloadCompression>,
+}
+
+impl HashedPayload {
+    /// Wraps uncompressed `bytes`, computing their Blake3 digest.
+    pub fn new(bytes: impl Into<BinaryPayload>) -> Self {
+        let bytes = bytes.into();
+        Self {
+            digest: HashDigest::blake3_of(&bytes),
+            bytes,
+            compression: None,
+        }
+    }
+
+    /// Wraps bytes already compressed with `compression`, computing their Blake3 digest.
+    pub fn compressed(bytes: impl Into<BinaryPayload>, compression: PayloadCompression) -> Self {
+        Self {
+            compression: Some(compression),
+            ..Self::new(bytes)
+        }
+    }
+
+    /// Returns `true` when both payloads carry the same content.
+    ///
+    /// Only digests and compression are compared, which makes this suitable for deduplication.
+    pub fn same_content(&self, other: &HashedPayload) -> bool {
+        self.compression == other.compression
+            && self.digest.algo == other.digest.algo
+            && self.digest.hex.eq_ignore_ascii_case(&other.digest.hex)
+    }
+
+    /// Checks `bytes` against `digest`.
+    ///
+    /// Fails with [`ErrorCode::InvalidInput`] on a mismatch and with [`ErrorCode::Unavailable`]
+    /// for digest algorithms other than Blake3.
+    pub fn verify(&self) -> GResult<()> {
+        match &self.digest.algo {
+            HashAlgorithm::Blake3 => {
+                let actual = HashDigest::blake3_of(&self.bytes);
+                if !actual.hex.eq_ignore_ascii_case(&self.digest.hex) {
+                    return Err(GreenticError::new(
+                        ErrorCode::InvalidInput,
+                        format!(
+                            "payload digest mismatch: expected {}, computed {}",
+                            self.digest.hex, actual.hex
+                        ),
+                    ));
+                }
+                Ok(())
+            }
+            HashAlgorithm::Other(algo) => Err(GreenticError::new(
+                ErrorCode::Unavailable,
+                format!("cannot verify {algo} payload digests"),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for HashedPayload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(with = "serde_with::As::<serde_with::base64::Base64>")]
+            bytes: BinaryPayload,
+            digest: HashDigest,
+            #[serde(default)]
+            compression: Option<PayloadCompression>,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        let payload = Self {
+            bytes: raw.bytes,
+            digest: raw.digest,
+            compression: raw.compression,
+        };
+        if matches!(payload.digest.algo, HashAlgorithm::Blake3) {
+            payload.verify().map_err(serde::de::Error::custom)?;
+        }
+        Ok(payload)
+    }
+}
@@ -1,5 +1,10 @@
 //! Internationalization tags, IDs, and minimal profiles.
-use alloc::{format, string::String};
+#[cfg(feature = "schemars")]
+use alloc::borrow::Cow;
+use alloc::{format, string::String, vec::Vec};
+use core::str::FromStr;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use unic_langid::{LanguageIdentifier, LanguageIdentifierError};
@@ -38,6 +43,145 @@ pub enum I18nTagError {
     /// Input string is not a valid BCP 47 tag.
     #[error("invalid locale tag: {0}")]
     Invalid(#[from] LanguageIdentifierError),
+    /// Input string is empty.
+    #[error("locale tag must not be empty")]
+    Empty,
+}
+
+/// BCP 47 language tag (for example `de-AT` or `zh-Hant-TW`) stored in canonical casing.
+///
+/// Used to key localized content; [`LocaleTag::fallback_chain`] lists the progressively less
+/// specific tags to try when content for the exact locale is missing.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct LocaleTag(String);
+
+impl LocaleTag {
+    /// Parses and canonicalizes a tag (`en-gb` becomes `en-GB`).
+    pub fn parse(value: &str) -> Result<Self, I18nTagError> {
+        if value.trim().is_empty() {
+            return Err(I18nTagError::Empty);
+        }
+        let langid: LanguageIdentifier = value.parse()?;
+        Ok(Self(langid.to_string()))
+    }
+
+    /// Canonical tag string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Primary language subtag (for example `de`).
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// Region subtag, when present (for example `AT`).
+    pub fn region(&self) -> Option<String> {
+        self.langid()
+            .region
+            .map(|region| String::from(region.as_str()))
+    }
+
+    /// Tags to try in order, from this tag down to its bare language.
+    ///
+    /// Variants are dropped first, then the region, then the script, so `zh-Hant-TW` yields
+    /// `zh-Hant-TW`, `zh-Hant`, `zh`. Callers append their own default locale.
+    pub fn fallback_chain(&self) -> Vec<LocaleTag> {
+        let mut langid = self.langid();
+        let mut chain = Vec::from([self.clone()]);
+        let mut push = |langid: &LanguageIdentifier| {
+            let tag = Self(langid.to_string());
+            if chain.last() != Some(&tag) {
+                chain.push(tag);
+            }
+        };
+        langid.clear_variants();
+        push(&langid);
+        langid.region = None;
+        push(&langid);
+        langid.script = None;
+        push(&langid);
+        chain
+    }
+
+    /// Returns the first entry of `available` matching this tag or one of its fallbacks.
+    pub fn best_match<'a>(
+        &self,
+        available: impl IntoIterator<Item = &'a LocaleTag>,
+    ) -> Option<&'a LocaleTag> {
+        let available: Vec<&LocaleTag> = available.into_iter().collect();
+        self.fallback_chain().iter().find_map(|tag| {
+            available
+                .iter()
+                .copied()
+                .find(|candidate| *candidate == tag)
+        })
+    }
+
+    fn langid(&self) -> LanguageIdentifier {
+        self.0
+            .parse()
+            .unwrap_or_else(|_| LanguageIdentifier::default())
+    }
+}
+
+impl core::fmt::Display for LocaleTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<LocaleTag> for String {
+    fn from(value: LocaleTag) -> Self {
+        value.0
+    }
+}
+
+impl From<LocaleTag> for I18nTag {
+    fn from(value: LocaleTag) -> Self {
+        Self(value.0)
+    }
+}
+
+impl TryFrom<String> for LocaleTag {
+    type Error = I18nTagError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl TryFrom<&str> for LocaleTag {
+    type Error = I18nTagError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl FromStr for LocaleTag {
+    type Err = I18nTagError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for LocaleTag {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("LocaleTag")
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let mut schema = <String>::json_schema(generator);
+        if schema.get("description").is_none() {
+            schema.insert("description".into(), "BCP 47 language tag".into());
+        }
+        schema
+    }
 }
 
 /// Stable identifier for a normalized locale tag or profile.
@@ -152,4 +296,45 @@ mod tests {
         };
         assert_eq!(roundtrip.as_str(), id.as_str());
     }
+
+    #[test]
+    fn locale_fallback_chain() {
+        let tag = LocaleTag::parse("zh-hant-tw").unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(tag.as_str(), "zh-Hant-TW");
+        assert_eq!(tag.language(), "zh");
+        assert_eq!(tag.region().as_deref(), Some("TW"));
+        let chain: Vec<_> = tag.fallback_chain().into_iter().map(String::from).collect();
+        assert_eq!(chain, ["zh-Hant-TW", "zh-Hant", "zh"]);
+
+        let plain = LocaleTag::parse("de").unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(plain.fallback_chain().len(), 1);
+        assert!(LocaleTag::parse("").is_err());
+        assert!(LocaleTag::parse("not a tag").is_err());
+    }
+
+    #[test]
+    fn locale_best_match_walks_fallbacks() {
+        let parse = |value: &str| LocaleTag::parse(value).unwrap_or_else(|err| panic!("{err}"));
+        let available = [parse("en"), parse("de"), parse("de-CH")];
+        assert_eq!(parse("de-AT").best_match(&available), Some(&available[1]));
+        assert_eq!(parse("de-CH").best_match(&available), Some(&available[2]));
+        assert_eq!(parse("fr-FR").best_match(&available), None);
+    }
+
+    #[test]
+    fn i18n_text_resolves_through_fallbacks() {
+        let parse = |value: &str| LocaleTag::parse(value).unwrap_or_else(|err| panic!("{err}"));
+        let text = crate::I18nText::new("greeting", Some("Hello".into()))
+            .with_translation(parse("de"), "Hallo")
+            .with_translation(parse("de-CH"), "Grüezi");
+        assert_eq!(text.resolve(&parse("de-CH")), Some("Grüezi"));
+        assert_eq!(text.resolve(&parse("de-AT")), Some("Hallo"));
+        assert_eq!(text.resolve(&parse("fr")), Some("Hello"));
+
+        let json = serde_json::to_value(&text).unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(json["translations"]["de-CH"], "Grüezi");
+        let plain = serde_json::to_value(crate::I18nText::new("greeting", None))
+            .unwrap_or_else(|err| panic!("{err}"));
+        assert!(plain.get("translations").is_none());
+    }
 }
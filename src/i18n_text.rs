@@ -1,9 +1,12 @@
 //! Simple i18n text wrapper used by CBOR schemas.
+use alloc::collections::BTreeMap;
 use alloc::string::String;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::LocaleTag;
+
 /// I18n-aware text value with a stable key and optional fallback string.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -12,6 +15,12 @@ pub struct I18nText {
     pub key: String,
     /// Optional fallback string (usually legacy text).
     pub fallback: Option<String>,
+    /// Inline translations keyed by locale.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub translations: BTreeMap<LocaleTag, String>,
 }
 
 impl I18nText {
@@ -20,9 +29,25 @@ impl I18nText {
         Self {
             key: key.into(),
             fallback,
+            translations: BTreeMap::new(),
         }
     }
 
+    /// Adds an inline translation for `locale`.
+    pub fn with_translation(mut self, locale: LocaleTag, text: impl Into<String>) -> Self {
+        self.translations.insert(locale, text.into());
+        self
+    }
+
+    /// Resolves the text for `locale`, walking its fallback chain before the fallback string.
+    pub fn resolve(&self, locale: &LocaleTag) -> Option<&str> {
+        locale
+            .best_match(self.translations.keys())
+            .and_then(|tag| self.translations.get(tag))
+            .or(self.fallback.as_ref())
+            .map(String::as_str)
+    }
+
     /// Iterate over the key.
     pub fn keys(&self) -> impl Iterator<Item = &str> {
         core::iter::once(self.key.as_str())
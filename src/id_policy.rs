@@ -0,0 +1,304 @@
+//! Validated string identifiers for downstream crates.
+//!
+//! [`define_id!`](crate::define_id) generates the same API as the identifiers in this crate
+//! (`TenantId`, `FlowId`, …): a string newtype with `new`, `as_str`, `FromStr`,
+//! `TryFrom<String>`, `Display`, and string (de)serialization that rejects invalid values. The
+//! inner string is private, so every value of the type has passed validation. Which strings are
+//! valid is described by an [`IdPolicy`]; the JSON Schema of the type carries the same rules as
+//! `minLength`, `maxLength`, and `pattern`.
+//!
+//! ```
+//! use greentic_types::{IdPolicy, define_id};
+//!
+//! define_id!(OrderId, "Identifier of a customer order.");
+//! define_id!(
+//!     SkuCode,
+//!     "Lowercase stock keeping unit code.",
+//!     IdPolicy::IDENTIFIER.lowercase().with_max_len(32)
+//! );
+//!
+//! assert!(OrderId::new("order-42").is_ok());
+//! assert!(SkuCode::new("Widget").is_err());
+//! ```
+//!
+//! Code outside the defining module cannot bypass validation:
+//!
+//! ```compile_fail
+//! mod ids {
+//!     greentic_types::define_id!(OrderId, "Identifier of a customer order.");
+//! }
+//!
+//! let id = ids::OrderId(String::from("not valid!"));
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::{ErrorCode, GResult, GreenticError};
+
+/// Rules a [`define_id!`](crate::define_id) identifier must satisfy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IdPolicy {
+    /// Minimum length in bytes.
+    pub min_len: usize,
+    /// Maximum length in bytes, if bounded.
+    pub max_len: Option<usize>,
+    /// Characters allowed besides ASCII letters and digits.
+    pub extra_chars: &'static str,
+    /// Whether ASCII uppercase letters are rejected.
+    pub lowercase: bool,
+}
+
+impl IdPolicy {
+    /// Rules of the identifiers in this crate: non-empty ASCII letters, digits, `.`, `_`, and `-`.
+    pub const IDENTIFIER: Self = Self {
+        min_len: 1,
+        max_len: None,
+        extra_chars: "._-",
+        lowercase: false,
+    };
+
+    /// Limits the identifier to `max_len` bytes.
+    pub const fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Replaces the characters allowed besides ASCII letters and digits.
+    pub const fn with_extra_chars(mut self, extra_chars: &'static str) -> Self {
+        self.extra_chars = extra_chars;
+        self
+    }
+
+    /// Rejects ASCII uppercase letters.
+    pub const fn lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+
+    /// Checks `value` against the policy; `label` names the identifier type in errors.
+    pub fn validate(&self, value: &str, label: &str) -> GResult<()> {
+        let invalid = |message: String| Err(GreenticError::new(ErrorCode::InvalidInput, message));
+        if value.len() < self.min_len.max(1) {
+            return invalid(if self.min_len <= 1 {
+                format!("{label} must not be empty")
+            } else {
+                format!("{label} must be at least {} bytes long", self.min_len)
+            });
+        }
+        if let Some(max_len) = self.max_len
+            && value.len() > max_len
+        {
+            return invalid(format!("{label} must be at most {max_len} bytes long"));
+        }
+        if let Some(c) = value.chars().find(|c| !self.allows(*c)) {
+            return invalid(format!(
+                "{label} must not contain {c:?}; allowed are ASCII {}digits and {:?}",
+                if self.lowercase {
+                    "lowercase letters, "
+                } else {
+                    "letters, "
+                },
+                self.extra_chars
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns an ECMA-262 regular expression matching the allowed characters.
+    pub fn pattern(&self) -> String {
+        let mut pattern = String::from(if self.lowercase {
+            "^[a-z0-9"
+        } else {
+            "^[A-Za-z0-9"
+        });
+        for c in self.extra_chars.chars() {
+            if matches!(c, '\\' | ']' | '[' | '^' | '-') {
+                pattern.push('\\');
+            }
+            pattern.push(c);
+        }
+        pattern.push_str("]*$");
+        pattern
+    }
+
+    fn allows(&self, c: char) -> bool {
+        c.is_ascii_digit()
+            || c.is_ascii_lowercase()
+            || (c.is_ascii_uppercase() && !self.lowercase)
+            || self.extra_chars.contains(c)
+    }
+}
+
+impl Default for IdPolicy {
+    fn default() -> Self {
+        Self::IDENTIFIER
+    }
+}
+
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+pub fn id_schema(
+    generator: &mut schemars::SchemaGenerator,
+    description: &str,
+    policy: &IdPolicy,
+) -> schemars::Schema {
+    use schemars::JsonSchema;
+
+    let mut schema = String::json_schema(generator);
+    schema.insert("description".into(), description.into());
+    schema.insert("minLength".into(), policy.min_len.max(1).into());
+    if let Some(max_len) = policy.max_len {
+        schema.insert("maxLength".into(), max_len.into());
+    }
+    schema.insert("pattern".into(), policy.pattern().into());
+    schema
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::borrow::Cow;
+    pub use alloc::string::String;
+    #[cfg(feature = "schemars")]
+    pub use schemars;
+    #[cfg(feature = "serde")]
+    pub use serde;
+}
+
+/// Defines a validated string identifier newtype.
+///
+/// `define_id!(Name, "doc")` uses [`IdPolicy::IDENTIFIER`]; pass a policy expression as third
+/// argument to change the rules. The policy is available as `Name::POLICY`. Serde and JSON Schema
+/// support follow the `serde` and `schemars` features of `greentic-types`.
+#[macro_export]
+macro_rules! define_id {
+    ($name:ident, $doc:literal) => {
+        $crate::define_id!($name, $doc, $crate::IdPolicy::IDENTIFIER);
+    };
+    ($name:ident, $doc:literal, $policy:expr) => {
+        #[doc = $doc]
+        #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        pub struct $name($crate::id_policy::__private::String);
+
+        impl $name {
+            /// Validation rules of the identifier.
+            pub const POLICY: $crate::IdPolicy = $policy;
+
+            /// Returns the identifier as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Validates and constructs the identifier from the provided value.
+            pub fn new(value: impl AsRef<str>) -> $crate::GResult<Self> {
+                value.as_ref().parse()
+            }
+        }
+
+        impl From<$name> for $crate::id_policy::__private::String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::GreenticError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Self::POLICY.validate(value, stringify!($name))?;
+                Ok(Self(value.into()))
+            }
+        }
+
+        impl TryFrom<$crate::id_policy::__private::String> for $name {
+            type Error = $crate::GreenticError;
+
+            fn try_from(value: $crate::id_policy::__private::String) -> Result<Self, Self::Error> {
+                Self::POLICY.validate(&value, stringify!($name))?;
+                Ok(Self(value))
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = $crate::GreenticError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+
+        $crate::__define_id_serde!($name);
+        $crate::__define_id_schemars!($name, $doc);
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident) => {
+        impl $crate::id_policy::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::id_policy::__private::serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> $crate::id_policy::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::id_policy::__private::serde::Deserializer<'de>,
+            {
+                let value = <$crate::id_policy::__private::String as $crate::id_policy::__private::serde::Deserialize>::deserialize(deserializer)?;
+                Self::try_from(value).map_err(<D::Error as $crate::id_policy::__private::serde::de::Error>::custom)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_serde {
+    ($name:ident) => {};
+}
+
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_schemars {
+    ($name:ident, $doc:literal) => {
+        impl $crate::id_policy::__private::schemars::JsonSchema for $name {
+            fn schema_name() -> $crate::id_policy::__private::Cow<'static, str> {
+                $crate::id_policy::__private::Cow::Borrowed(stringify!($name))
+            }
+
+            fn json_schema(
+                generator: &mut $crate::id_policy::__private::schemars::SchemaGenerator,
+            ) -> $crate::id_policy::__private::schemars::Schema {
+                $crate::id_policy::id_schema(generator, $doc.trim(), &Self::POLICY)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "schemars"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_id_schemars {
+    ($name:ident, $doc:literal) => {};
+}
@@ -26,12 +26,11 @@
 //!
 //! ## Run results & serialization
 //! ```
-//! # #[cfg(feature = "time")] {
-//! use greentic_types::{FlowId, PackId, RunResult, RunStatus, SessionKey};
+//! # #[cfg(feature = "serde")] {
+//! use greentic_types::{FlowId, PackId, RunResult, RunStatus, SessionKey, UnixMillis};
 //! use semver::Version;
-//! use time::OffsetDateTime;
 //!
-//! let now = OffsetDateTime::UNIX_EPOCH;
+//! let now = UnixMillis::EPOCH;
 //! let result = RunResult {
 //!     session_id: SessionKey::from("sess-1"),
 //!     pack_id: "greentic.demo.pack".parse().unwrap(),
@@ -43,6 +42,7 @@
 //!     node_summaries: Vec::new(),
 //!     failures: Vec::new(),
 //!     artifacts_dir: None,
+//!     artifacts: Vec::new(),
 //! };
 //! println!("{}", serde_json::to_string_pretty(&result).unwrap());
 //! # }
@@ -59,29 +59,55 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const SCHEMA_BASE_URL: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1";
 
 pub mod adapters;
+pub mod alert;
+pub mod billing;
 pub mod bindings;
+pub mod blob;
 pub mod capabilities;
 #[cfg(feature = "std")]
 pub mod cbor;
 pub mod cbor_bytes;
+pub mod compat;
 pub mod component;
+pub mod component_mock;
 pub mod component_source;
+pub mod config_override;
+pub mod config_value;
+pub mod crypto_envelope;
 pub mod deployment;
 pub mod distributor;
 pub mod envelope;
+pub mod event_subscription;
 pub mod events;
 pub mod events_provider;
 pub mod flow;
+pub mod flow_bindings;
+pub mod flow_budget;
+#[cfg(feature = "flow-dsl")]
+pub mod flow_dsl;
+pub mod flow_metrics;
 pub mod flow_resolve;
 pub mod flow_resolve_summary;
+pub mod geo;
+pub mod hashed_payload;
 pub mod i18n;
 pub mod i18n_text;
+pub mod id_policy;
+pub mod lint;
+pub mod maintenance;
 pub mod messaging;
+pub mod migration;
+pub mod money;
+pub mod notification;
+pub mod oci;
 pub mod op_descriptor;
 pub mod pack_manifest;
 pub mod provider;
 pub mod provider_install;
 pub mod qa;
+pub mod rate_limit;
+pub mod redact;
+pub mod retry;
 pub mod schema_id;
 pub mod schema_registry;
 pub mod store;
@@ -97,50 +123,86 @@ pub mod run;
 #[cfg(all(feature = "schemars", feature = "std"))]
 pub mod schema;
 pub mod schemas;
+pub mod secret_plan;
 pub mod secrets;
 pub mod session;
 pub mod state;
 pub mod telemetry;
 pub mod tenant;
 pub mod tenant_config;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod units;
 pub mod validate;
 
+pub use alert::{Alert, AlertSeverity, AlertState, AlertSubject};
+pub use billing::{SubscriptionUsage, UnitUsage, UsageAggregate, UsageRecord};
 pub use bindings::hints::{
     BindingsHints, EnvHints, McpHints, McpServer, NetworkHints, SecretsHints,
 };
+pub use blob::BlobRef;
 pub use capabilities::{
-    Capabilities, FsCaps, HttpCaps, KvCaps, Limits, NetCaps, SecretsCaps, TelemetrySpec, ToolsCaps,
+    Capabilities, EnvelopeLimitError, FsCaps, HttpCaps, KvCaps, Limits, NetCaps, SecretsCaps,
+    TelemetrySpec, ToolsCaps,
 };
 #[cfg(feature = "std")]
+pub use cbor::canonical::StableHash;
+#[cfg(feature = "std")]
 pub use cbor::{CborError, decode_pack_manifest, encode_pack_manifest};
 pub use cbor_bytes::{Blob, CborBytes};
 pub use component::{
-    ComponentCapabilities, ComponentConfigurators, ComponentDevFlow, ComponentManifest,
-    ComponentOperation, ComponentProfileError, ComponentProfiles, EnvCapabilities,
-    EventsCapabilities, FilesystemCapabilities, FilesystemMode, FilesystemMount, HostCapabilities,
-    HttpCapabilities, IaCCapabilities, MessagingCapabilities, ResourceHints, SecretsCapabilities,
-    StateCapabilities, TelemetryCapabilities, TelemetryScope, WasiCapabilities,
+    CapabilityCheckReport, CapabilityMismatch, CapabilityMismatchReason, ComponentCapabilities,
+    ComponentConfigurators, ComponentDevFlow, ComponentManifest, ComponentOperation,
+    ComponentProfileError, ComponentProfiles, ComponentTarget, DEFAULT_COMPONENT_TARGET_TRIPLE,
+    EnvCapabilities, EventsCapabilities, FilesystemCapabilities, FilesystemMode, FilesystemMount,
+    HostCapabilities, HttpCapabilities, IaCCapabilities, MessagingCapabilities, ResourceHints,
+    SecretsCapabilities, StateCapabilities, TelemetryCapabilities, TelemetryScope,
+    WasiCapabilities,
 };
+pub use component_mock::{MockComponentSpec, MockLatency, MockOperation, MockResponse};
 pub use component_source::{ComponentSourceRef, ComponentSourceRefError};
+pub use config_override::{ConfigOverride, MergeStrategy};
+pub use config_value::{
+    ConfigRef, ConfigResolver, ConfigSegment, ConfigValue, parse_config_string,
+};
 pub use context::{Cloud, DeploymentCtx, Platform};
+#[cfg(feature = "serde")]
+pub use crypto_envelope::{
+    DocumentSignature, EncryptedPayload, EncryptionAlgorithm, EnvelopeSigner, EnvelopeVerifier,
+    PayloadDecryptor, PayloadEncryptor, Signed, SignedEnvelope, SignedWorkerRequest,
+    SignedWorkerResponse,
+};
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use crypto_envelope::{SignedDesiredState, SignedRepoTenantConfig};
 pub use deployment::{
     ChannelPlan, DeploymentPlan, MessagingPlan, MessagingSubjectPlan, OAuthPlan, RunnerPlan,
     TelemetryPlan,
 };
 pub use distributor::{
-    ArtifactLocation, CacheInfo, ComponentDigest, ComponentStatus, DistributorEnvironmentId,
-    PackStatusResponseV2, ResolveComponentRequest, ResolveComponentResponse, SignatureSummary,
+    ArtifactLocation, ArtifactMirror, CacheInfo, ComponentDigest, ComponentStatus,
+    CompressionCodec, DistributorEnvironmentId, PackStatusResponseV2, ResolveComponentRequest,
+    ResolveComponentResponse, SignatureSummary, TransferHints,
 };
 pub use envelope::Envelope;
-pub use error::{ErrorCode, GResult, GreenticError};
-pub use events::{EventEnvelope, EventId, EventMetadata};
+pub use error::{ErrorCategory, ErrorCode, GResult, GreenticError, ResultExt};
+pub use event_subscription::{
+    CursorStrategy, DeadLetterTarget, EventFilter, EventSubscriptionSpec,
+};
+pub use events::{EventBatch, EventEnvelope, EventId, EventMetadata};
 pub use events_provider::{
     EventProviderDescriptor, EventProviderKind, OrderingKind, ReliabilityKind, TransportKind,
+    WebhookExpiryPolicy, WebhookSubscription,
 };
 pub use flow::{
-    ComponentRef as FlowComponentRef, Flow, FlowKind, FlowMetadata, InputMapping, Node,
+    CUSTOM_FLOW_KIND_MAX_LEN, ComponentRef as FlowComponentRef, CustomFlowKind,
+    FLOW_KIND_RESERVED_NAMESPACE, Flow, FlowKind, FlowKindError, FlowMetadata, InputMapping, Node,
     OutputMapping, Routing, TelemetryHints,
 };
+pub use flow_bindings::validate_flow_bindings;
+pub use flow_budget::{FlowBudget, estimate_flow_budget};
+#[cfg(feature = "flow-dsl")]
+pub use flow_dsl::{flow_to_dsl, parse_flow_dsl};
+pub use flow_metrics::{FlowMetrics, FlowMetricsThresholds};
 pub use flow_resolve::{
     ComponentSourceRefV1, FLOW_RESOLVE_SCHEMA_VERSION, FlowResolveV1, NodeResolveV1, ResolveModeV1,
 };
@@ -156,22 +218,48 @@ pub use flow_resolve_summary::{
 pub use flow_resolve_summary::{read_flow_resolve_summary, write_flow_resolve_summary};
 #[cfg(feature = "std")]
 pub use flow_resolve_summary::{resolve_summary_path_for_flow, validate_flow_resolve_summary};
-pub use i18n::{Direction, I18nId, I18nTag, MinimalI18nProfile, id_for_tag};
+pub use geo::{CountryCode, RegionCode, RegionConstraint};
+#[cfg(feature = "macros")]
+pub use greentic_types_macros::greentic_operation;
+pub use hashed_payload::{HashedPayload, PayloadCompression};
+pub use i18n::{
+    Direction, I18nId, I18nTag, I18nTagError, LocaleTag, MinimalI18nProfile, id_for_tag,
+};
 pub use i18n_text::I18nText;
+pub use id_policy::IdPolicy;
+pub use lint::{
+    ComponentWithoutOperationsRule, FlowWithoutTelemetryRule, LintConfig, LintLevel, LintRegistry,
+    LintRule, MissingDescriptionRule, UnusedCapabilitiesRule, lint_pack_manifest,
+};
+pub use maintenance::{MaintenanceAction, MaintenanceRecurrence, MaintenanceWindow, Weekday};
 pub use messaging::{
-    Actor, Attachment, ChannelMessageEnvelope, Destination, MessageMetadata,
+    Actor, Attachment, ChannelId, ChannelMessageEnvelope, ChannelMessageEnvelopeBuilder,
+    ConversationBinding, ConversationId, DeliveryOptions, DeliveryPriority, Destination,
+    MessageMetadata, PeerId,
+    chunking::{
+        AttachmentChunk, AttachmentChunker, DEFAULT_ATTACHMENT_CHUNK_SIZE, reassemble_attachment,
+    },
     rendering::{
         AdaptiveCardVersion, CapabilityProfile, RenderDiagnostics, RenderPlanHints, RendererMode,
         Tier,
     },
+    template::{MessageTemplate, RenderedTemplate},
     universal_dto::{
-        AuthUserRefV1, EncodeInV1, Header, HttpInV1, HttpOutV1, ProviderPayloadV1, RenderPlanInV1,
-        RenderPlanOutV1, SendPayloadInV1, SendPayloadResultV1, SubscriptionDeleteInV1,
-        SubscriptionDeleteOutV1, SubscriptionDeleteResultV1, SubscriptionEnsureInV1,
-        SubscriptionEnsureOutV1, SubscriptionEnsureResultV1, SubscriptionRenewInV1,
-        SubscriptionRenewOutV1, SubscriptionRenewalInV1, SubscriptionRenewalOutV1,
+        AuthUserRefV1, BroadcastAudienceV1, BroadcastRecipientResultV1, BroadcastRecipientV1,
+        BroadcastSendInV1, BroadcastSendResultV1, EncodeInV1, Header, HttpInV1, HttpOutV1,
+        ProviderErrorKind, ProviderPayloadV1, RenderPlanInV1, RenderPlanOutV1, SendPayloadInV1,
+        SendPayloadResultV1, SubscriptionDeleteInV1, SubscriptionDeleteOutV1,
+        SubscriptionDeleteResultV1, SubscriptionEnsureInV1, SubscriptionEnsureOutV1,
+        SubscriptionEnsureResultV1, SubscriptionRenewInV1, SubscriptionRenewOutV1,
+        SubscriptionRenewalInV1, SubscriptionRenewalOutV1,
     },
 };
+pub use money::{CurrencyCode, MoneyError, MoneyMicro, RoundingMode};
+pub use notification::{
+    CategoryPreference, DigestFrequency, NotificationCategory, NotificationChannel,
+    NotificationPreferences, NotificationRule, QuietHours,
+};
+pub use oci::OciImageRef;
 pub use op_descriptor::{IoSchema, OpDescriptor, OpExample};
 pub use outcome::Outcome;
 pub use pack::extensions::component_manifests::{
@@ -191,12 +279,25 @@ pub use pack::extensions::component_sources::{
 pub use pack::extensions::component_sources::{
     decode_component_sources_v1_from_cbor_bytes, encode_component_sources_v1_to_cbor_bytes,
 };
-pub use pack::{PackRef, Signature, SignatureAlgorithm};
+pub use pack::extensions::pack_tests::{
+    EXT_PACK_TESTS_V1, ExpectedOutcome, OutputMatcher, PackTestSpec, PackTestsError, PackTestsV1,
+    TestFixture, TranscriptAssertion,
+};
+pub use pack::resolve::{
+    AvailablePack, Requirement, ResolutionError, ResolutionPlan, ResolvedPack, resolve_dependencies,
+};
+pub use pack::{
+    PackRef, Signature, SignatureAlgorithm, SignaturePolicy, SignaturePolicyError,
+    SignatureVerifier, SignerRole, verify_signature_policy,
+};
 pub use pack_manifest::{
     BootstrapSpec, ComponentCapability, ExtensionInline, ExtensionRef, PackDependency,
-    PackFlowEntry, PackKind, PackManifest, PackSignatures,
+    PackFlowEntry, PackKind, PackManifest, PackManifestOverlay, PackSignatures,
+};
+pub use policy::{
+    AllowList, NetworkPolicy, ObligationCapabilities, PolicyDecision, PolicyDecisionStatus,
+    PolicyObligation, Protocol,
 };
-pub use policy::{AllowList, NetworkPolicy, PolicyDecision, PolicyDecisionStatus, Protocol};
 pub use provider::{
     PROVIDER_EXTENSION_ID, ProviderDecl, ProviderExtensionInline, ProviderManifest,
     ProviderRuntimeRef,
@@ -205,9 +306,13 @@ pub use provider_install::{ProviderInstallRecord, ProviderInstallRefs};
 pub use qa::{
     CanonicalPolicy, ExampleAnswers, QaSpecSource, SetupContract, SetupOutput, validate_answers,
 };
-#[cfg(feature = "time")]
-pub use run::RunResult;
-pub use run::{NodeFailure, NodeStatus, NodeSummary, RunStatus, TranscriptOffset};
+pub use rate_limit::{RateLimitDecision, RateLimitPolicy, RateLimitScope, RateLimitState};
+pub use redact::{
+    DEFAULT_REDACTION_PLACEHOLDER, RedactionKey, RedactionKeys, RedactionSegment,
+    RedactionStrategy, apply_redactions, apply_redactions_with_keys,
+};
+pub use retry::{BackoffKind, RetryPolicy};
+pub use run::{NodeFailure, NodeStatus, NodeSummary, RunResult, RunStatus, TranscriptOffset};
 pub use schema_id::{IoSchemaSource, QaSchemaSource, SchemaId, SchemaSource, schema_id_for_cbor};
 pub use schema_registry::{SCHEMAS, SchemaDef};
 pub use schemas::component::v0_5_0::LegacyComponentQaSpec;
@@ -220,34 +325,58 @@ pub use schemas::pack::v0_6_0::{
     CapabilityDescriptor, CapabilityMetadata, PackDescribe, PackInfo, PackQaSpec,
     PackValidationResult,
 };
+pub use secret_plan::{
+    AvailableSecret, SecretResolution, SecretResolutionPlan, SecretResolutionStatus, SecretSource,
+    plan_secrets,
+};
 pub use secrets::{SecretFormat, SecretKey, SecretRequirement, SecretScope};
 pub use session::canonical_session_key;
-pub use session::{ReplyScope, SessionCursor, SessionData, SessionKey, WaitScope};
+pub use session::{
+    ConversationError, ConversationState, ConversationStatus, CursorHop,
+    DEFAULT_CURSOR_HISTORY_LIMIT, EvictionPolicy, PendingQuestion, ReplyScope, SessionCursor,
+    SessionData, SessionKey, SessionTtl, WaitScope,
+};
 pub use state::{StateKey, StatePath};
 pub use store::{
-    ArtifactSelector, BundleSpec, CapabilityMap, Collection, ConnectionKind, DesiredState,
-    DesiredStateExportSpec, DesiredSubscriptionEntry, Environment, LayoutSection,
-    LayoutSectionKind, PackOrComponentRef, PlanLimits, PriceModel, ProductOverride, RolloutState,
-    RolloutStatus, StoreFront, StorePlan, StoreProduct, StoreProductKind, Subscription,
-    SubscriptionStatus, Theme, VersionStrategy,
+    ApprovalDecision, ApprovalRecord, ApprovalStatus, ApprovalVerdict, ArtifactSelector,
+    BillingAccount, BundleArtifactDigest, BundleIntegrity, BundleMismatch, BundleSpec,
+    COST_ESTIMATE_MONTH_DAYS, CapabilityGapReport, CapabilityMap, CapabilityMapExt, CatalogFilter,
+    Collection, CompatibilityIssue, ConfigOverrideChange, ConnectionKind, CostConfidence,
+    CostEstimate, DesiredState, DesiredStateChangeRequest, DesiredStateDiff,
+    DesiredStateExportSpec, DesiredSubscriptionChange, DesiredSubscriptionEntry, EntryCostEstimate,
+    Environment, EventSubscriptionChange, LayoutSection, LayoutSectionKind, LifecycleState,
+    MeteredProjection, PackOrComponentRef, Page, PageRequest, PaymentMethod, PaymentMethodKind,
+    PlanFeature, PlanFeatureMatrix, PlanFeatureRow, PlanFeatureValue, PlanLimits, PriceModel,
+    ProductCompatibility, ProductOverride, RolloutState, RolloutStatus, StoreFront, StorePlan,
+    StorePlanPage, StoreProduct, StoreProductKind, StoreProductPage, Subscription,
+    SubscriptionPage, SubscriptionStatus, Theme, VersionStrategy, diff_desired_state,
 };
 pub use supply_chain::{
-    AttestationStatement, BuildPlan, BuildStatus, BuildStatusKind, MetadataRecord, PredicateType,
-    RepoContext, ScanKind, ScanRequest, ScanResult, ScanStatusKind, SignRequest, StoreContext,
-    VerifyRequest, VerifyResult,
+    AttestationBundle, AttestationStatement, BuildLogChunk, BuildLogCursor, BuildLogStream,
+    BuildPlan, BuildStatus, BuildStatusKind, FindingSeverity, MetadataRecord, PipelineRunStatus,
+    PipelineStage, PipelineVerdict, PolicyEvaluation, PredicateType, ProvenanceChain,
+    ProvenanceCompleteness, ProvenanceIssue, ProvenanceStage, RepoContext, ScanKind, ScanRequest,
+    ScanResult, ScanStatusKind, SeveritySummary, SignRequest, StoreContext, VerifyRequest,
+    VerifyResult,
 };
-#[cfg(feature = "otel-keys")]
-pub use telemetry::OtlpKeys;
-pub use telemetry::SpanContext;
 #[cfg(feature = "telemetry-autoinit")]
 pub use telemetry::TelemetryCtx;
-pub use tenant::{Impersonation, TenantIdentity};
+pub use telemetry::{
+    MessageHeaders, SpanContext, SpanLink, SpanLinkKind, TRACE_STATE_MAX_MEMBERS,
+    TraceContextError, TraceParent, TraceState,
+};
+#[cfg(feature = "otel-keys")]
+pub use telemetry::{OtlpKeys, TelemetryAttributes};
+pub use tenant::{Impersonation, ImpersonationEvent, TenantIdentity};
 pub use tenant_config::{
-    DefaultPipeline, DidContext, DidService, DistributorTarget, EnabledPacks,
-    IdentityProviderOption, RepoAuth, RepoConfigFeatures, RepoSkin, RepoSkinLayout, RepoSkinLinks,
-    RepoSkinTheme, RepoTenantConfig, RepoWorkerPanel, StoreTarget, TenantDidDocument,
-    VerificationMethod,
+    BootstrapAdmin, DefaultPipeline, DidContext, DidDocumentError, DidService, DistributorTarget,
+    EnabledPacks, FeatureFlag, FeatureFlags, FlagValue, IdentityProviderOption, MultibaseBase,
+    MultibaseKey, PublicJwk, PublicKeyMaterial, QuotaResource, QuotaUsage, QuotaViolation,
+    RepoAuth, RepoConfigFeatures, RepoSkin, RepoSkinLayout, RepoSkinLinks, RepoSkinTheme,
+    RepoTenantConfig, RepoWorkerPanel, StoreTarget, TenantBootstrap, TenantDidDocument,
+    TenantQuota, VerificationMethod, rollout_bucket,
 };
+pub use units::{ByteSize, DurationMs, Percent, Ratio, UnixMillis};
 pub use validate::{
     Diagnostic, PackValidator, Severity, ValidationCounts, ValidationReport,
     validate_pack_manifest_core,
@@ -343,6 +472,42 @@ pub mod ids {
     /// Redaction path schema.
     pub const REDACTION_PATH: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/redaction-path.schema.json";
+    /// URL reference schema.
+    pub const URL_REF: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/url-ref.schema.json";
+    /// Locale tag schema.
+    pub const LOCALE_TAG: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/locale-tag.schema.json";
+    /// Country code schema.
+    pub const COUNTRY_CODE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/country-code.schema.json";
+    /// Region code schema.
+    pub const REGION_CODE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/region-code.schema.json";
+    /// Region constraint schema.
+    pub const REGION_CONSTRAINT: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/region-constraint.schema.json";
+    /// Currency code schema.
+    pub const CURRENCY_CODE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/currency-code.schema.json";
+    /// Money amount schema.
+    pub const MONEY_MICRO: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/money-micro.schema.json";
+    /// Impersonation audit event schema.
+    pub const IMPERSONATION_EVENT: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/impersonation-event.schema.json";
+    /// Feature flags document schema.
+    pub const FEATURE_FLAGS: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/feature-flags.schema.json";
+    /// Session TTL schema.
+    pub const SESSION_TTL: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/session-ttl.schema.json";
+    /// Session eviction policy schema.
+    pub const EVICTION_POLICY: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/eviction-policy.schema.json";
+    /// Pack signature policy schema.
+    pub const SIGNATURE_POLICY: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/signature-policy.schema.json";
     /// Capabilities schema.
     pub const CAPABILITIES: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/capabilities.schema.json";
@@ -358,6 +523,17 @@ pub mod ids {
     /// Tenant DID document (did.json) schema.
     pub const TENANT_DID_DOCUMENT: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/tenant-did-document.schema.json";
+    /// Tenant onboarding document schema.
+    pub const TENANT_BOOTSTRAP: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/tenant-bootstrap.schema.json";
+    /// Tenant quota schema.
+    pub const TENANT_QUOTA: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/tenant-quota.schema.json";
+    /// Notification preferences schema.
+    pub const NOTIFICATION_PREFERENCES: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1/notification-preferences.schema.json";
+    /// Conversation state schema.
+    pub const CONVERSATION_STATE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/conversation-state.schema.json";
     /// Flow schema.
     pub const FLOW: &str = "greentic.flow.v1";
     /// Flow resolve sidecar schema.
@@ -381,6 +557,9 @@ pub mod ids {
     /// Validation report schema.
     pub const VALIDATION_REPORT: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/validation-report.schema.json";
+    /// Lint configuration schema.
+    pub const LINT_CONFIG: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/lint-config.schema.json";
     /// Provider manifest schema.
     pub const PROVIDER_MANIFEST: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/provider-manifest.schema.json";
@@ -421,6 +600,8 @@ pub mod ids {
     /// Secrets capability schema.
     pub const SECRETS_CAPS: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/secrets-caps.schema.json";
+    /// Secret resolution plan schema.
+    pub const SECRET_RESOLUTION_PLAN: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1/secret-resolution-plan.schema.json";
     /// Branch reference schema.
     pub const BRANCH_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/branch-ref.schema.json";
@@ -463,12 +644,18 @@ pub mod ids {
     /// Policy reference schema.
     pub const POLICY_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/policy-ref.schema.json";
+    /// Pipeline run reference schema.
+    pub const PIPELINE_RUN_REF: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/pipeline-run-ref.schema.json";
     /// Policy input reference schema.
     pub const POLICY_INPUT_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/policy-input-ref.schema.json";
     /// Store reference schema.
     pub const STORE_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/store-ref.schema.json";
+    /// Blob reference schema.
+    pub const BLOB_REF: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/blob-ref.schema.json";
     /// Registry reference schema.
     pub const REGISTRY_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/registry-ref.schema.json";
@@ -484,6 +671,12 @@ pub mod ids {
     /// Signing key reference schema.
     pub const SIGNING_KEY_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/signing-key-ref.schema.json";
+    /// Encryption key reference schema.
+    pub const ENCRYPTION_KEY_REF: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/encryption-key-ref.schema.json";
+    /// Encrypted payload schema.
+    pub const ENCRYPTED_PAYLOAD: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/encrypted-payload.schema.json";
     /// Signature reference schema.
     pub const SIGNATURE_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/signature-ref.schema.json";
@@ -493,6 +686,26 @@ pub mod ids {
     /// Build log reference schema.
     pub const BUILD_LOG_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/build-log-ref.schema.json";
+    /// Alert identifier schema.
+    pub const ALERT_ID: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/alert-id.schema.json";
+    /// Alert schema.
+    pub const ALERT: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/alert.schema.json";
+    /// Maintenance window identifier schema.
+    pub const MAINTENANCE_WINDOW_ID: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/maintenance-window-id.schema.json";
+    /// Maintenance window schema.
+    pub const MAINTENANCE_WINDOW: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/maintenance-window.schema.json";
+    /// Retry policy schema.
+    pub const RETRY_POLICY: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/retry-policy.schema.json";
+    /// Event subscription spec schema.
+    pub const EVENT_SUBSCRIPTION_SPEC: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1/event-subscription-spec.schema.json";
+    /// Rate limit policy schema.
+    pub const RATE_LIMIT_POLICY: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/rate-limit-policy.schema.json";
     /// Metadata record reference schema.
     pub const METADATA_RECORD_REF: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/metadata-record-ref.schema.json";
@@ -517,12 +730,24 @@ pub mod ids {
     /// Subscription identifier schema.
     pub const SUBSCRIPTION_ID: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/subscription-id.schema.json";
+    /// Usage record schema.
+    pub const USAGE_RECORD: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/usage-record.schema.json";
+    /// Usage aggregate schema.
+    pub const USAGE_AGGREGATE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/usage-aggregate.schema.json";
+    /// Subscription usage snapshot schema.
+    pub const SUBSCRIPTION_USAGE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/subscription-usage.schema.json";
     /// Bundle identifier schema.
     pub const BUNDLE_ID: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/bundle-id.schema.json";
     /// Collection identifier schema.
     pub const COLLECTION_ID: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/collection-id.schema.json";
+    /// Change request identifier schema.
+    pub const CHANGE_REQUEST_ID: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/change-request-id.schema.json";
     /// Artifact selector schema.
     pub const ARTIFACT_SELECTOR: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/artifact-selector.schema.json";
@@ -547,9 +772,21 @@ pub mod ids {
     /// Plan limits schema.
     pub const PLAN_LIMITS: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/plan-limits.schema.json";
+    /// Plan feature schema.
+    pub const PLAN_FEATURE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/plan-feature.schema.json";
     /// Price model schema.
     pub const PRICE_MODEL: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/price-model.schema.json";
+    /// Page request schema.
+    pub const PAGE_REQUEST: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/page-request.schema.json";
+    /// Catalog filter schema.
+    pub const CATALOG_FILTER: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/catalog-filter.schema.json";
+    /// Store product page schema.
+    pub const STORE_PRODUCT_PAGE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/store-product-page.schema.json";
     /// Subscription status schema.
     pub const SUBSCRIPTION_STATUS: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/subscription-status.schema.json";
@@ -559,6 +796,12 @@ pub mod ids {
     /// Build status schema.
     pub const BUILD_STATUS: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/build-status.schema.json";
+    /// Pipeline run status schema.
+    pub const PIPELINE_RUN_STATUS: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/pipeline-run-status.schema.json";
+    /// Build log chunk schema.
+    pub const BUILD_LOG_CHUNK: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/build-log-chunk.schema.json";
     /// Scan request schema.
     pub const SCAN_REQUEST: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/scan-request.schema.json";
@@ -577,6 +820,12 @@ pub mod ids {
     /// Attestation statement schema.
     pub const ATTESTATION_STATEMENT: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/attestation-statement.schema.json";
+    /// Attestation bundle schema.
+    pub const ATTESTATION_BUNDLE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/attestation-bundle.schema.json";
+    /// Provenance chain schema.
+    pub const PROVENANCE_CHAIN: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/provenance-chain.schema.json";
     /// Metadata record schema.
     pub const METADATA_RECORD: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/metadata-record.schema.json";
@@ -592,11 +841,19 @@ pub mod ids {
     /// Bundle export specification schema.
     pub const DESIRED_STATE_EXPORT: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/desired-state-export.schema.json";
+    /// Cost estimate schema.
+    pub const COST_ESTIMATE: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/cost-estimate.schema.json";
     /// Desired state schema.
     pub const DESIRED_STATE: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/desired-state.schema.json";
     /// Desired subscription entry schema.
     pub const DESIRED_SUBSCRIPTION_ENTRY: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1/desired-subscription-entry.schema.json";
+    /// Desired state change request schema.
+    pub const DESIRED_STATE_CHANGE_REQUEST: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1/desired-state-change-request.schema.json";
+    /// Approval record schema.
+    pub const APPROVAL_RECORD: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/approval-record.schema.json";
     /// Storefront schema.
     pub const STOREFRONT: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/storefront.schema.json";
@@ -609,6 +866,15 @@ pub mod ids {
     /// Subscription schema.
     pub const SUBSCRIPTION: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/subscription.schema.json";
+    /// Billing account schema.
+    pub const BILLING_ACCOUNT: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/billing-account.schema.json";
+    /// Billing account identifier schema.
+    pub const BILLING_ACCOUNT_ID: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/billing-account-id.schema.json";
+    /// Payment method reference schema.
+    pub const PAYMENT_METHOD_REF: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/payment-method-ref.schema.json";
     /// Environment schema.
     pub const ENVIRONMENT: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/environment.schema.json";
@@ -627,13 +893,25 @@ pub mod ids {
     /// Event envelope schema.
     pub const EVENT_ENVELOPE: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/event-envelope.schema.json";
+    /// Event batch schema.
+    pub const EVENT_BATCH: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/event-batch.schema.json";
     /// Event provider descriptor schema.
     pub const EVENT_PROVIDER_DESCRIPTOR: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1/event-provider-descriptor.schema.json";
+    /// Webhook subscription schema.
+    pub const WEBHOOK_SUBSCRIPTION: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/webhook-subscription.schema.json";
     /// Channel message envelope schema.
     pub const CHANNEL_MESSAGE_ENVELOPE: &str = "https://greentic-ai.github.io/greentic-types/schemas/v1/channel-message-envelope.schema.json";
     /// Attachment schema.
     pub const ATTACHMENT: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/attachment.schema.json";
+    /// Conversation binding schema.
+    pub const CONVERSATION_BINDING: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/conversation-binding.schema.json";
+    /// Attachment chunk schema.
+    pub const ATTACHMENT_CHUNK: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/attachment-chunk.schema.json";
     /// Worker request envelope schema.
     pub const WORKER_REQUEST: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/worker-request.schema.json";
@@ -643,6 +921,9 @@ pub mod ids {
     /// Worker response envelope schema.
     pub const WORKER_RESPONSE: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/worker-response.schema.json";
+    /// Worker v2 stream frame schema.
+    pub const WORKER_FRAME: &str =
+        "https://greentic-ai.github.io/greentic-types/schemas/v1/worker-frame.schema.json";
     /// OTLP attribute key schema.
     pub const OTLP_KEYS: &str =
         "https://greentic-ai.github.io/greentic-types/schemas/v1/otlp-keys.schema.json";
@@ -661,15 +942,14 @@ pub fn write_all_schemas(out_dir: &std::path::Path) -> anyhow::Result<()> {
         .with_context(|| format!("failed to create {}", out_dir.display()))?;
 
     for entry in crate::schema::entries() {
-        let schema = (entry.generator)();
         let path = out_dir.join(entry.file_name);
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("failed to create {}", parent.display()))?;
         }
 
-        let json =
-            serde_json::to_vec_pretty(&schema).context("failed to serialize schema to JSON")?;
+        let json = serde_json::to_string_pretty(&(entry.generator)())
+            .with_context(|| format!("failed to serialize {}", entry.id))?;
         fs::write(&path, json).with_context(|| format!("failed to write {}", path.display()))?;
     }
 
@@ -786,6 +1066,18 @@ id_newtype!(
     SubscriptionId,
     "Identifier referencing a subscription entry."
 );
+id_newtype!(
+    BillingAccountId,
+    "Identifier referencing a tenant billing account."
+);
+id_newtype!(
+    PaymentMethodRef,
+    "Tokenized payment method reference issued by a payment provider."
+);
+id_newtype!(
+    ChangeRequestId,
+    "Identifier referencing a desired state change request."
+);
 id_newtype!(BundleId, "Identifier referencing a distributor bundle.");
 id_newtype!(CollectionId, "Identifier referencing a product collection.");
 id_newtype!(RepoRef, "Repository reference within a supply chain.");
@@ -805,6 +1097,10 @@ id_newtype!(
 );
 id_newtype!(AttestationId, "Identifier referencing an attestation.");
 id_newtype!(PolicyRef, "Policy reference within a supply chain.");
+id_newtype!(
+    PipelineRunRef,
+    "Pipeline run reference within a supply chain."
+);
 id_newtype!(
     PolicyInputRef,
     "Reference to a policy input payload for evaluation."
@@ -814,10 +1110,6 @@ id_newtype!(
     RegistryRef,
     "Registry reference for OCI or artifact storage."
 );
-id_newtype!(
-    OciImageRef,
-    "Reference to an OCI image for distribution (oci://repo/name:tag or oci://repo/name@sha256:...)."
-);
 id_newtype!(
     ArtifactRef,
     "Artifact reference within a build or scan result."
@@ -827,12 +1119,18 @@ id_newtype!(
     "Reference to a Software Bill of Materials artifact."
 );
 id_newtype!(SigningKeyRef, "Reference to a signing key handle.");
+id_newtype!(EncryptionKeyRef, "Reference to an encryption key handle.");
 id_newtype!(SignatureRef, "Reference to a generated signature.");
 id_newtype!(StatementRef, "Reference to an attestation statement.");
 id_newtype!(
     BuildLogRef,
     "Reference to a build log output produced during execution."
 );
+id_newtype!(AlertId, "Identifier referencing an operational alert.");
+id_newtype!(
+    MaintenanceWindowId,
+    "Identifier referencing a tenant-declared maintenance window."
+);
 id_newtype!(
     MetadataRecordRef,
     "Reference to a metadata record attached to artifacts or bundles."
@@ -997,6 +1295,14 @@ impl HashDigest {
     pub fn blake3(hex: impl Into<String>) -> GResult<Self> {
         Self::new(HashAlgorithm::Blake3, hex)
     }
+
+    /// Computes the Blake3 digest of `bytes`.
+    pub fn blake3_of(bytes: &[u8]) -> Self {
+        Self {
+            algo: HashAlgorithm::Blake3,
+            hex: blake3::hash(bytes).to_hex().as_str().into(),
+        }
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1160,6 +1466,128 @@ impl TryFrom<&str> for RedactionPath {
     }
 }
 
+/// Absolute URL validated for a scheme and the absence of whitespace.
+///
+/// Only the shape is checked: a scheme, no whitespace or control characters, and a host for
+/// `http`/`https` URLs. Use [`UrlRef::parse_https`] or [`UrlRef::require_https`] where plain
+/// HTTP must be rejected.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct UrlRef(String);
+
+impl UrlRef {
+    /// Validates and stores an absolute URL.
+    pub fn parse(value: impl AsRef<str>) -> GResult<Self> {
+        let value = value.as_ref();
+        validate_url(value)?;
+        Ok(Self(value.to_owned()))
+    }
+
+    /// Validates and stores an absolute `https` URL.
+    pub fn parse_https(value: impl AsRef<str>) -> GResult<Self> {
+        let url = Self::parse(value)?;
+        url.require_https()?;
+        Ok(url)
+    }
+
+    /// Returns the URL string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the scheme (for example `https`), as written.
+    pub fn scheme(&self) -> &str {
+        self.0.split_once(':').map_or("", |(scheme, _)| scheme)
+    }
+
+    /// Returns `true` when the URL uses the `https` scheme.
+    pub fn is_https(&self) -> bool {
+        self.scheme().eq_ignore_ascii_case("https")
+    }
+
+    /// Fails unless the URL uses the `https` scheme.
+    pub fn require_https(&self) -> GResult<()> {
+        if self.is_https() {
+            return Ok(());
+        }
+        Err(GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("url '{}' must use https", self.0),
+        ))
+    }
+}
+
+impl fmt::Display for UrlRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<UrlRef> for String {
+    fn from(value: UrlRef) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for UrlRef {
+    type Error = GreenticError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        validate_url(&value)?;
+        Ok(Self(value))
+    }
+}
+
+impl TryFrom<&str> for UrlRef {
+    type Error = GreenticError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        UrlRef::parse(value)
+    }
+}
+
+impl FromStr for UrlRef {
+    type Err = GreenticError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UrlRef::parse(s)
+    }
+}
+
+fn validate_url(value: &str) -> GResult<()> {
+    let invalid = |reason: &str| {
+        GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("invalid url '{value}': {reason}"),
+        )
+    };
+    if value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid("whitespace and control characters are not allowed"));
+    }
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return Err(invalid("missing scheme"));
+    };
+    let mut chars = scheme.chars();
+    let scheme_ok = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !scheme_ok {
+        return Err(invalid("scheme must start with a letter"));
+    }
+    if rest.is_empty() {
+        return Err(invalid("nothing follows the scheme"));
+    }
+    if scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("https") {
+        let host = rest
+            .strip_prefix("//")
+            .map(|authority| authority.split(['/', '?', '#']).next().unwrap_or_default());
+        if host.is_none_or(str::is_empty) {
+            return Err(invalid("http urls require a host"));
+        }
+    }
+    Ok(())
+}
+
 fn validate_jsonpath(path: &str) -> GResult<()> {
     if path.is_empty() {
         return Err(GreenticError::new(
@@ -1218,6 +1646,25 @@ impl JsonSchema for RedactionPath {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl JsonSchema for UrlRef {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("UrlRef")
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let mut schema = <String>::json_schema(generator);
+        schema.insert("format".into(), "uri".into());
+        if schema.get("description").is_none() {
+            schema.insert(
+                "description".into(),
+                "Absolute URL without whitespace".into(),
+            );
+        }
+        schema
+    }
+}
+
 /// Deadline metadata for an invocation, stored as Unix epoch milliseconds.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1444,10 +1891,34 @@ pub struct InvocationEnvelope {
     pub op: String,
     /// Normalized payload for the invocation.
     pub payload: BinaryPayload,
+    /// Payload with its content digest, for invocations that receivers should verify and
+    /// deduplicate; `payload` is typically empty when set.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub hashed_payload: Option<HashedPayload>,
     /// Raw metadata propagated from the ingress surface.
     pub metadata: BinaryPayload,
 }
 
+impl InvocationEnvelope {
+    /// Combined size of `payload` and any `hashed_payload` bytes as carried, i.e. after
+    /// compression.
+    pub fn compute_payload_size(&self) -> ByteSize {
+        let hashed = self
+            .hashed_payload
+            .as_ref()
+            .map_or(0, |hashed| hashed.bytes.len());
+        ByteSize::from_bytes((self.payload.len() + hashed) as u64)
+    }
+
+    /// Rejects envelopes whose payload exceeds `limits`.
+    pub fn check_limits(&self, limits: &Limits) -> Result<(), EnvelopeLimitError> {
+        limits.check_payload_size(self.compute_payload_size())
+    }
+}
+
 /// Structured detail payload attached to a node error.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1470,8 +1941,14 @@ pub struct NodeError {
     pub message: String,
     /// Whether the failure is retryable by the runtime.
     pub retryable: bool,
-    /// Optional backoff duration in milliseconds for the next retry.
-    pub backoff_ms: Option<u64>,
+    /// Optional backoff before the next retry (milliseconds or a duration string such as `2s`).
+    pub backoff_ms: Option<DurationMs>,
+    /// Optional retry policy superseding `backoff_ms`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub retry_policy: Option<RetryPolicy>,
     /// Optional structured error detail payload.
     pub details: Option<ErrorDetail>,
     #[cfg(feature = "std")]
@@ -1493,6 +1970,7 @@ impl NodeError {
             message: message.into(),
             retryable: false,
             backoff_ms: None,
+            retry_policy: None,
             details: None,
             #[cfg(feature = "std")]
             source: None,
@@ -1500,12 +1978,35 @@ impl NodeError {
     }
 
     /// Marks the error as retryable with an optional backoff value.
-    pub fn with_retry(mut self, backoff_ms: Option<u64>) -> Self {
+    pub fn with_retry(mut self, backoff_ms: Option<DurationMs>) -> Self {
         self.retryable = true;
         self.backoff_ms = backoff_ms;
         self
     }
 
+    /// Marks the error as retryable under the supplied policy.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retryable = true;
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Returns the delay before retrying after `attempt` failed with `code`, or `None` when the
+    /// error should not be retried.
+    ///
+    /// The retry policy wins when present and only retries codes listed in its `retry_on`;
+    /// otherwise `backoff_ms` (or zero) is used.
+    pub fn next_retry_delay(&self, code: ErrorCode, attempt: u32) -> Option<DurationMs> {
+        if !self.retryable {
+            return None;
+        }
+        match &self.retry_policy {
+            Some(policy) if !policy.should_retry(code, attempt) => None,
+            Some(policy) => policy.next_delay(attempt),
+            None => Some(self.backoff_ms.unwrap_or_default()),
+        }
+    }
+
     /// Attaches structured details to the error.
     pub fn with_detail(mut self, detail: ErrorDetail) -> Self {
         self.details = Some(detail);
@@ -1544,6 +2045,66 @@ impl NodeError {
     pub fn source(&self) -> Option<&(dyn StdError + Send + Sync + 'static)> {
         self.source.as_deref()
     }
+
+    /// Captures the error and its source chain in a serializable form.
+    ///
+    /// Codes are preserved for sources that are themselves [`NodeError`]s or were rebuilt by
+    /// [`NodeError::from_chain`]; other sources contribute their display message only.
+    pub fn to_chain(&self) -> NodeErrorChain {
+        #[allow(unused_mut)]
+        let mut causes = Vec::new();
+        #[cfg(feature = "std")]
+        {
+            let mut next: Option<&(dyn StdError + 'static)> = StdError::source(self);
+            while let Some(err) = next {
+                causes.push(ErrorFrame::from_error(err));
+                next = err.source();
+            }
+        }
+        NodeErrorChain {
+            code: self.code.clone(),
+            message: self.message.clone(),
+            retryable: self.retryable,
+            backoff_ms: self.backoff_ms,
+            retry_policy: self.retry_policy.clone(),
+            details: self.details.clone(),
+            causes,
+        }
+    }
+
+    /// Rebuilds an error from a transported chain.
+    ///
+    /// Without the `std` feature the cause frames are dropped because `NodeError` cannot hold a
+    /// source.
+    pub fn from_chain(chain: NodeErrorChain) -> Self {
+        let NodeErrorChain {
+            code,
+            message,
+            retryable,
+            backoff_ms,
+            retry_policy,
+            details,
+            causes,
+        } = chain;
+        #[cfg(not(feature = "std"))]
+        let _ = causes;
+        Self {
+            code,
+            message,
+            retryable,
+            backoff_ms,
+            retry_policy,
+            details,
+            #[cfg(feature = "std")]
+            source: causes
+                .into_iter()
+                .rev()
+                .fold(None, |source, frame| {
+                    Some(Box::new(ChainedError { frame, source }))
+                })
+                .map(|err| err as Box<dyn StdError + Send + Sync>),
+        }
+    }
 }
 
 impl fmt::Display for NodeError {
@@ -1564,6 +2125,124 @@ impl StdError for NodeError {
 /// Alias for results returned by node handlers.
 pub type NodeResult<T> = Result<T, NodeError>;
 
+/// Single cause within a [`NodeErrorChain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ErrorFrame {
+    /// Machine readable code, when the cause carried one.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub code: Option<String>,
+    /// Display message of the cause.
+    pub message: String,
+}
+
+impl ErrorFrame {
+    /// Creates a frame without a code.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            code: None,
+            message: message.into(),
+        }
+    }
+
+    /// Attaches a machine readable code.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    #[cfg(feature = "std")]
+    fn from_error(err: &(dyn StdError + 'static)) -> Self {
+        if let Some(node) = err.downcast_ref::<NodeError>() {
+            return Self::new(node.message.clone()).with_code(node.code.clone());
+        }
+        if let Some(chained) = err.downcast_ref::<ChainedError>() {
+            return chained.frame.clone();
+        }
+        Self::new(err.to_string())
+    }
+}
+
+/// Serializable [`NodeError`] including its source chain, outermost cause first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct NodeErrorChain {
+    /// Machine readable error code.
+    pub code: String,
+    /// Human readable message explaining the failure.
+    pub message: String,
+    /// Whether the failure is retryable by the runtime.
+    pub retryable: bool,
+    /// Optional backoff before the next retry (milliseconds or a duration string such as `2s`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub backoff_ms: Option<DurationMs>,
+    /// Optional retry policy superseding `backoff_ms`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub retry_policy: Option<RetryPolicy>,
+    /// Optional structured error detail payload.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub details: Option<ErrorDetail>,
+    /// Source errors, starting with the direct source.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub causes: Vec<ErrorFrame>,
+}
+
+impl From<&NodeError> for NodeErrorChain {
+    fn from(err: &NodeError) -> Self {
+        err.to_chain()
+    }
+}
+
+impl From<NodeErrorChain> for NodeError {
+    fn from(chain: NodeErrorChain) -> Self {
+        NodeError::from_chain(chain)
+    }
+}
+
+/// Source error rebuilt from an [`ErrorFrame`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct ChainedError {
+    frame: ErrorFrame,
+    source: Option<Box<ChainedError>>,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ChainedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.frame.code {
+            Some(code) => write!(f, "{code}: {}", self.frame.message),
+            None => f.write_str(&self.frame.message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl StdError for ChainedError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_deref()
+            .map(|err| err as &(dyn StdError + 'static))
+    }
+}
+
 /// Generates a stable idempotency key for a node invocation.
 ///
 /// The key uses tenant, flow, node, and correlation identifiers. Missing
@@ -1668,11 +2347,11 @@ mod tests {
     #[test]
     fn node_error_builder_sets_fields() {
         let err = NodeError::new("TEST", "example")
-            .with_retry(Some(500))
+            .with_retry(Some(DurationMs::from_millis(500)))
             .with_detail_text("context");
 
         assert!(err.retryable);
-        assert_eq!(err.backoff_ms, Some(500));
+        assert_eq!(err.backoff_ms, Some(DurationMs::from_millis(500)));
         match err.detail() {
             Some(ErrorDetail::Text(detail)) => assert_eq!(detail, "context"),
             other => panic!("unexpected detail {other:?}"),
@@ -1688,4 +2367,37 @@ mod tests {
         let err = NodeError::new("TEST", "example").with_source(source);
         assert!(err.source().is_some());
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn node_error_chain_preserves_causes() {
+        use std::io::Error;
+
+        let inner = NodeError::new("INNER", "inner failed").with_source(Error::other("io broke"));
+        let err = NodeError::new("OUTER", "outer failed")
+            .with_retry(Some(DurationMs::from_millis(250)))
+            .with_source(inner);
+
+        let chain = err.to_chain();
+        assert_eq!(chain.code, "OUTER");
+        assert_eq!(chain.backoff_ms, Some(DurationMs::from_millis(250)));
+        assert_eq!(
+            chain.causes,
+            vec![
+                ErrorFrame::new("inner failed").with_code("INNER"),
+                ErrorFrame::new("io broke"),
+            ]
+        );
+
+        let rebuilt = NodeError::from_chain(chain.clone());
+        assert_eq!(rebuilt.to_string(), "OUTER: outer failed");
+        assert_eq!(
+            rebuilt
+                .source()
+                .map(|source| source.to_string())
+                .unwrap_or_default(),
+            "INNER: inner failed"
+        );
+        assert_eq!(rebuilt.to_chain(), chain);
+    }
 }
@@ -0,0 +1,319 @@
+//! Opinionated lint rules for pack manifests.
+//!
+//! Lints complement [`validate_pack_manifest_core`](crate::validate_pack_manifest_core): they
+//! flag manifests that are valid but hard to operate, such as flows without descriptions or
+//! telemetry hints. Every [`LintRule`] has a default severity that a [`LintConfig`] document can
+//! raise, lower, or turn off.
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::validate::{Diagnostic, Severity, ValidationReport, core_diagnostic};
+use crate::{ComponentCapabilities, PackManifest};
+
+/// Lint rule applied to a pack manifest.
+pub trait LintRule {
+    /// Stable rule identifier used as the key in [`LintConfig::rules`].
+    fn id(&self) -> &'static str;
+    /// Severity used when the configuration does not override the rule.
+    fn default_severity(&self) -> Severity;
+    /// Checks the manifest and returns diagnostics at the default severity.
+    fn check(&self, manifest: &PackManifest) -> Vec<Diagnostic>;
+}
+
+/// Configured level for a lint rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum LintLevel {
+    /// Rule is disabled.
+    Off,
+    /// Report as informational.
+    Info,
+    /// Report as a warning.
+    Warn,
+    /// Report as an error.
+    Error,
+}
+
+impl LintLevel {
+    /// Severity reported at this level, or `None` when the rule is disabled.
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            Self::Off => None,
+            Self::Info => Some(Severity::Info),
+            Self::Warn => Some(Severity::Warn),
+            Self::Error => Some(Severity::Error),
+        }
+    }
+}
+
+/// Per-rule severity overrides, typically loaded from a repository lint file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct LintConfig {
+    /// Levels keyed by rule identifier; rules not listed use their default severity.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub rules: BTreeMap<String, LintLevel>,
+}
+
+impl LintConfig {
+    /// Sets the level for `rule`.
+    pub fn with_rule(mut self, rule: impl Into<String>, level: LintLevel) -> Self {
+        self.rules.insert(rule.into(), level);
+        self
+    }
+
+    /// Resolves the severity for `rule`, or `None` when it is turned off.
+    pub fn severity_for(&self, rule: &dyn LintRule) -> Option<Severity> {
+        match self.rules.get(rule.id()) {
+            Some(level) => level.severity(),
+            None => Some(rule.default_severity()),
+        }
+    }
+}
+
+/// Ordered set of lint rules.
+pub struct LintRegistry {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl LintRegistry {
+    /// Creates a registry without rules.
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Adds a rule; it runs after the rules already registered.
+    pub fn register(&mut self, rule: Box<dyn LintRule>) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Identifiers of the registered rules, in execution order.
+    pub fn rule_ids(&self) -> Vec<&'static str> {
+        self.rules.iter().map(|rule| rule.id()).collect()
+    }
+
+    /// Runs every enabled rule against `manifest`.
+    ///
+    /// Diagnostics carry the configured severity and record the rule identifier under
+    /// `data.rule`. Configured rule identifiers that no registered rule uses are reported first as
+    /// `LINT_UNKNOWN_RULE` warnings, so typos do not silently leave a rule at its default.
+    pub fn lint(&self, manifest: &PackManifest, config: &LintConfig) -> ValidationReport {
+        let mut report = ValidationReport {
+            pack_id: Some(manifest.pack_id.clone()),
+            pack_version: Some(manifest.version.clone()),
+            diagnostics: Vec::new(),
+        };
+        let known = self.rule_ids();
+        for id in config.rules.keys() {
+            if known.contains(&id.as_str()) {
+                continue;
+            }
+            let mut diagnostic = core_diagnostic(
+                Severity::Warn,
+                "LINT_UNKNOWN_RULE",
+                &format!("Lint configuration references unknown rule {id}."),
+                Some(format!("rules.{id}")),
+                Some(format!("Known rules: {}.", known.join(", "))),
+            );
+            diagnostic.data = json!({ "rule": id });
+            report.push(diagnostic);
+        }
+        for rule in &self.rules {
+            let Some(severity) = config.severity_for(rule.as_ref()) else {
+                continue;
+            };
+            for mut diagnostic in rule.check(manifest) {
+                diagnostic.severity = severity;
+                if diagnostic.data.is_null() {
+                    diagnostic.data = json!({ "rule": rule.id() });
+                }
+                report.push(diagnostic);
+            }
+        }
+        report
+    }
+}
+
+impl Default for LintRegistry {
+    /// Registry with every built-in rule.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register(Box::new(MissingDescriptionRule))
+            .register(Box::new(UnusedCapabilitiesRule))
+            .register(Box::new(ComponentWithoutOperationsRule))
+            .register(Box::new(FlowWithoutTelemetryRule));
+        registry
+    }
+}
+
+/// Lints `manifest` with the built-in rules and the levels from `config`.
+pub fn lint_pack_manifest(manifest: &PackManifest, config: &LintConfig) -> ValidationReport {
+    LintRegistry::default().lint(manifest, config)
+}
+
+/// Flags flows and pack capabilities without a description.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MissingDescriptionRule;
+
+impl LintRule for MissingDescriptionRule {
+    fn id(&self) -> &'static str {
+        "missing-description"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, manifest: &PackManifest) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for entry in &manifest.flows {
+            if is_blank(entry.flow.metadata.description.as_deref()) {
+                diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "LINT_MISSING_DESCRIPTION",
+                    &format!("Flow {} has no description.", entry.id),
+                    Some(format!("flows.{}.metadata.description", entry.id)),
+                    Some("Describe what the flow does for operators and catalogs.".into()),
+                ));
+            }
+        }
+        for (index, capability) in manifest.capabilities.iter().enumerate() {
+            if is_blank(capability.description.as_deref()) {
+                diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "LINT_MISSING_DESCRIPTION",
+                    &format!("Capability {} has no description.", capability.name),
+                    Some(format!("capabilities.{index}.description")),
+                    Some("Describe what the capability grants.".into()),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Flags components that declare capabilities but are not referenced by any flow in the pack.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnusedCapabilitiesRule;
+
+impl LintRule for UnusedCapabilitiesRule {
+    fn id(&self) -> &'static str {
+        "unused-capabilities"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, manifest: &PackManifest) -> Vec<Diagnostic> {
+        let referenced: BTreeSet<&str> = manifest
+            .flows
+            .iter()
+            .flat_map(|entry| entry.flow.nodes.values())
+            .filter(|node| node.component.pack_alias.is_none())
+            .map(|node| node.component.id.as_str())
+            .collect();
+        manifest
+            .components
+            .iter()
+            .filter(|component| component.capabilities != ComponentCapabilities::default())
+            .filter(|component| !referenced.contains(component.id.as_str()))
+            .map(|component| {
+                core_diagnostic(
+                    Severity::Warn,
+                    "LINT_UNUSED_CAPABILITIES",
+                    &format!(
+                        "Component {} declares capabilities but no flow in the pack uses it.",
+                        component.id
+                    ),
+                    Some(format!("components.{}.capabilities", component.id)),
+                    Some("Drop the capability declarations or reference the component.".into()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Flags components that expose no operations.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ComponentWithoutOperationsRule;
+
+impl LintRule for ComponentWithoutOperationsRule {
+    fn id(&self) -> &'static str {
+        "component-without-operations"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, manifest: &PackManifest) -> Vec<Diagnostic> {
+        manifest
+            .components
+            .iter()
+            .filter(|component| component.operations.is_empty())
+            .map(|component| {
+                core_diagnostic(
+                    Severity::Warn,
+                    "LINT_COMPONENT_WITHOUT_OPERATIONS",
+                    &format!("Component {} declares no operations.", component.id),
+                    Some(format!("components.{}.operations", component.id)),
+                    Some("Declare the operations flows may invoke.".into()),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Flags flows without telemetry hints.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FlowWithoutTelemetryRule;
+
+impl LintRule for FlowWithoutTelemetryRule {
+    fn id(&self) -> &'static str {
+        "flow-without-telemetry"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn check(&self, manifest: &PackManifest) -> Vec<Diagnostic> {
+        manifest
+            .flows
+            .iter()
+            .filter(|entry| entry.flow.metadata.telemetry.is_none())
+            .map(|entry| {
+                core_diagnostic(
+                    Severity::Info,
+                    "LINT_FLOW_WITHOUT_TELEMETRY",
+                    &format!("Flow {} has no telemetry hints.", entry.id),
+                    Some(format!("flows.{}.metadata.telemetry", entry.id)),
+                    Some("Set a span name or sampling hints so traces are attributable.".into()),
+                )
+            })
+            .collect()
+    }
+}
+
+fn is_blank(value: Option<&str>) -> bool {
+    value.is_none_or(|value| value.trim().is_empty())
+}
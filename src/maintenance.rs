@@ -0,0 +1,304 @@
+//! Tenant-declared maintenance windows and change freezes.
+//!
+//! A [`MaintenanceWindow`] describes when (a one-off or recurring slot), where (the affected
+//! environments), and what (the suppressed [`MaintenanceAction`]s) distributors must hold back.
+//! [`DeploymentPlan`](crate::DeploymentPlan) carries the windows that apply to a deployment, and
+//! [`RolloutStatus`](crate::RolloutStatus) records the window that deferred a rollout.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "time")]
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::units::{MINUTE_MS, MINUTES_PER_DAY};
+use crate::{
+    DurationMs, EnvironmentRef, ErrorCode, GResult, GreenticError, MaintenanceWindowId, UnixMillis,
+};
+
+const MINUTES_PER_WEEK: u64 = 7 * MINUTES_PER_DAY as u64;
+
+/// Day of the week, starting on Monday.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum Weekday {
+    /// Monday.
+    Monday,
+    /// Tuesday.
+    Tuesday,
+    /// Wednesday.
+    Wednesday,
+    /// Thursday.
+    Thursday,
+    /// Friday.
+    Friday,
+    /// Saturday.
+    Saturday,
+    /// Sunday.
+    Sunday,
+}
+
+impl Weekday {
+    /// All days, Monday first.
+    pub const ALL: [Self; 7] = [
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+        Self::Saturday,
+        Self::Sunday,
+    ];
+
+    /// Days since Monday (`0` for Monday, `6` for Sunday).
+    pub const fn days_from_monday(self) -> u8 {
+        self as u8
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::Weekday> for Weekday {
+    fn from(value: time::Weekday) -> Self {
+        Self::ALL[usize::from(value.number_days_from_monday())]
+    }
+}
+
+/// When a maintenance window opens.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum MaintenanceRecurrence {
+    /// Single window starting at the window's `starts_at_utc`.
+    Once,
+    /// Every day at `start_minute` local time.
+    Daily {
+        /// Opening time in minutes after local midnight.
+        start_minute: u16,
+    },
+    /// On the listed days at `start_minute` local time.
+    Weekly {
+        /// Days the window opens on.
+        days: Vec<Weekday>,
+        /// Opening time in minutes after local midnight.
+        start_minute: u16,
+    },
+}
+
+/// Operation a maintenance window holds back.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum MaintenanceAction {
+    /// Rolling out a new desired state.
+    Rollout,
+    /// Restarting runners.
+    Restart,
+    /// Scaling runners up or down.
+    Scale,
+    /// Rotating secrets.
+    SecretRotation,
+    /// Product-specific action.
+    Other(String),
+}
+
+/// Period during which a tenant freezes changes to some of its environments.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MaintenanceWindow {
+    /// Window identifier.
+    pub id: MaintenanceWindowId,
+    /// Optional human-readable reason (for example `year-end freeze`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+    /// When the window opens.
+    pub recurrence: MaintenanceRecurrence,
+    /// IANA timezone recurring windows are expressed in (for example `Europe/Berlin`).
+    pub timezone: String,
+    /// Start of a one-off window (UTC).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub starts_at_utc: Option<UnixMillis>,
+    /// How long the window stays open.
+    pub duration: DurationMs,
+    /// Environments the window applies to; every environment when empty.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub environments: Vec<EnvironmentRef>,
+    /// Actions held back while the window is open; every action when empty.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub suppressed_actions: Vec<MaintenanceAction>,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl MaintenanceWindow {
+    /// Creates a window covering every environment and action.
+    pub fn new(
+        id: MaintenanceWindowId,
+        recurrence: MaintenanceRecurrence,
+        timezone: impl Into<String>,
+        duration: DurationMs,
+    ) -> Self {
+        Self {
+            id,
+            description: None,
+            recurrence,
+            timezone: timezone.into(),
+            starts_at_utc: None,
+            duration,
+            environments: Vec::new(),
+            suppressed_actions: Vec::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` when a one-off window is open at `now`; recurring windows return `false`.
+    pub fn is_once_active_at(&self, now: UnixMillis) -> bool {
+        match self.recurrence {
+            MaintenanceRecurrence::Once => self.starts_at_utc.is_some_and(|start| {
+                now >= start && now.saturating_duration_since(start) < self.duration
+            }),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` when the window covers `environment`.
+    pub fn applies_to(&self, environment: &EnvironmentRef) -> bool {
+        self.environments.is_empty() || self.environments.contains(environment)
+    }
+
+    /// Returns `true` when the window holds back `action`.
+    pub fn suppresses(&self, action: &MaintenanceAction) -> bool {
+        self.suppressed_actions.is_empty() || self.suppressed_actions.contains(action)
+    }
+
+    /// Returns `true` when a recurring window is open at local `weekday` and `minute_of_day`.
+    ///
+    /// Windows may extend past midnight into the following days. One-off windows are anchored
+    /// in UTC and always return `false` here; use [`MaintenanceWindow::is_once_active_at`] for
+    /// them.
+    pub fn is_active_local(&self, weekday: Weekday, minute_of_day: u16) -> bool {
+        let (days, start_minute): (&[Weekday], u16) = match &self.recurrence {
+            MaintenanceRecurrence::Once => return false,
+            MaintenanceRecurrence::Daily { start_minute } => (&Weekday::ALL, *start_minute),
+            MaintenanceRecurrence::Weekly { days, start_minute } => (days, *start_minute),
+        };
+        let length = self.duration.as_millis().div_ceil(MINUTE_MS);
+        let now = week_minute(weekday, minute_of_day);
+        days.iter().any(|day| {
+            let start = week_minute(*day, start_minute);
+            let elapsed = (now + MINUTES_PER_WEEK - start) % MINUTES_PER_WEEK;
+            length >= MINUTES_PER_WEEK || elapsed < length
+        })
+    }
+
+    /// Returns `true` when the window is open at `now`.
+    ///
+    /// `local_offset` is the current UTC offset of the window's `timezone`; callers resolve it
+    /// because the crate does not ship a timezone database.
+    #[cfg(feature = "time")]
+    pub fn is_active_at(&self, now: OffsetDateTime, local_offset: UtcOffset) -> bool {
+        match self.recurrence {
+            MaintenanceRecurrence::Once => self.is_once_active_at(now.into()),
+            _ => {
+                let local = now.to_offset(local_offset);
+                let minute = u16::from(local.hour()) * 60 + u16::from(local.minute());
+                self.is_active_local(local.weekday().into(), minute)
+            }
+        }
+    }
+
+    /// Returns `true` when `action` on `environment` must wait at local `weekday` and
+    /// `minute_of_day`.
+    pub fn blocks(
+        &self,
+        environment: &EnvironmentRef,
+        action: &MaintenanceAction,
+        weekday: Weekday,
+        minute_of_day: u16,
+    ) -> bool {
+        self.applies_to(environment)
+            && self.suppresses(action)
+            && self.is_active_local(weekday, minute_of_day)
+    }
+
+    /// Ensures the window has a positive duration, a timezone, and a well-formed recurrence.
+    pub fn validate(&self) -> GResult<()> {
+        if self.duration == DurationMs::ZERO {
+            return Err(self.invalid("duration must be positive"));
+        }
+        if self.timezone.trim().is_empty() {
+            return Err(self.invalid("a timezone is required"));
+        }
+        match &self.recurrence {
+            MaintenanceRecurrence::Once => {
+                if self.starts_at_utc.is_none() {
+                    return Err(self.invalid("one-off windows require `starts_at_utc`"));
+                }
+            }
+            MaintenanceRecurrence::Daily { start_minute } => {
+                self.check_start_minute(*start_minute)?;
+            }
+            MaintenanceRecurrence::Weekly { days, start_minute } => {
+                if days.is_empty() {
+                    return Err(self.invalid("weekly windows require at least one day"));
+                }
+                self.check_start_minute(*start_minute)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_start_minute(&self, start_minute: u16) -> GResult<()> {
+        if start_minute >= MINUTES_PER_DAY {
+            return Err(self.invalid(&format!(
+                "start minute {start_minute} must be below {MINUTES_PER_DAY}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn invalid(&self, reason: &str) -> GreenticError {
+        GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("maintenance window {}: {reason}", self.id),
+        )
+    }
+}
+
+fn week_minute(weekday: Weekday, minute_of_day: u16) -> u64 {
+    u64::from(weekday.days_from_monday()) * u64::from(MINUTES_PER_DAY)
+        + u64::from(minute_of_day % MINUTES_PER_DAY)
+}
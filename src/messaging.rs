@@ -1,13 +1,100 @@
 //! Generic channel messaging envelope shared across providers.
 
-use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloc::{borrow::ToOwned, collections::BTreeMap, format, string::String, vec::Vec};
+use core::fmt;
 
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{ReplyScope, TenantCtx};
+use crate::{
+    BlobRef, DurationMs, EnvId, ErrorCode, FlowId, GResult, GreenticError, PackId, ReplyScope,
+    SessionKey, TenantCtx, TenantId, UnixMillis, UrlRef,
+};
+
+/// Declares an opaque provider-space identifier that serializes as a plain string.
+///
+/// Unlike the crate's validated id newtypes these accept any value, since their format is owned
+/// by the messaging provider (for example `teamId:channelId` or `+15551234567`).
+macro_rules! provider_id {
+    ($name:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
+        #[cfg_attr(feature = "schemars", derive(JsonSchema))]
+        #[cfg_attr(feature = "schemars", schemars(transparent))]
+        pub struct $name(pub String);
+
+        impl $name {
+            /// Wraps a provider identifier.
+            pub fn new(value: impl Into<String>) -> Self {
+                Self(value.into())
+            }
+
+            /// Returns the identifier as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                Self(value.to_owned())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+provider_id!(
+    ChannelId,
+    "Abstract channel identifier or type (for example `slack`)."
+);
+provider_id!(
+    ConversationId,
+    "Conversation or thread identifier within a channel."
+);
+provider_id!(
+    PeerId,
+    "Participant or destination identifier in provider space (user, bot, room, or address)."
+);
 
 /// Message actor (sender/initiator).
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -15,7 +102,7 @@ use crate::{ReplyScope, TenantCtx};
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub struct Actor {
     /// Actor identifier in provider space (e.g., slack user id, webex person id).
-    pub id: String,
+    pub id: PeerId,
     /// Optional actor kind (e.g. "user", "bot", "system").
     #[cfg_attr(
         feature = "serde",
@@ -30,7 +117,7 @@ pub struct Actor {
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub struct Destination {
     /// Destination identifier (provider specific; may be composite e.g. "teamId:channelId").
-    pub id: String,
+    pub id: PeerId,
     /// Optional destination kind (e.g. "chat", "room", "user", "channel", "email", "phone").
     #[cfg_attr(
         feature = "serde",
@@ -50,7 +137,7 @@ pub struct Attachment {
     /// MIME type of the attachment (for example `image/png`).
     pub mime_type: String,
     /// URL pointing at the attachment payload.
-    pub url: String,
+    pub url: UrlRef,
     /// Optional display name for the attachment.
     #[cfg_attr(
         feature = "serde",
@@ -63,6 +150,19 @@ pub struct Attachment {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub size_bytes: Option<u64>,
+    /// Chunked transfer carrying the payload, when it is streamed as
+    /// [`AttachmentChunk`](crate::AttachmentChunk)s instead of fetched from `url`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub transfer_id: Option<String>,
+    /// Payload held in a content store, when it is fetched from there instead of `url`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub blob: Option<BlobRef>,
 }
 
 /// Envelope for channel messages exchanged with adapters.
@@ -75,9 +175,9 @@ pub struct ChannelMessageEnvelope {
     /// Tenant context propagated with the message.
     pub tenant: TenantCtx,
     /// Abstract channel identifier or type.
-    pub channel: String,
+    pub channel: ChannelId,
     /// Conversation or thread identifier.
-    pub session_id: String,
+    pub session_id: ConversationId,
     /// Optional reply scope that can be used for resumption.
     #[cfg_attr(
         feature = "serde",
@@ -117,7 +217,356 @@ pub struct ChannelMessageEnvelope {
     /// Free-form metadata for adapters and flows.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: MessageMetadata,
+    /// Scheduling, expiry, and priority hints for egress.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub delivery_options: Option<DeliveryOptions>,
+}
+
+impl ChannelMessageEnvelope {
+    /// Starts building an envelope; `id`, `tenant`, `channel`, and `session_id` are required.
+    pub fn builder() -> ChannelMessageEnvelopeBuilder {
+        ChannelMessageEnvelopeBuilder::default()
+    }
+}
+
+/// Fluent builder for [`ChannelMessageEnvelope`].
+#[derive(Clone, Debug, Default)]
+pub struct ChannelMessageEnvelopeBuilder {
+    id: Option<String>,
+    tenant: Option<TenantCtx>,
+    channel: Option<ChannelId>,
+    session_id: Option<ConversationId>,
+    reply_scope: Option<ReplyScope>,
+    from: Option<Actor>,
+    to: Vec<Destination>,
+    correlation_id: Option<String>,
+    text: Option<String>,
+    attachments: Vec<Attachment>,
+    metadata: MessageMetadata,
+    delivery_options: Option<DeliveryOptions>,
+}
+
+impl ChannelMessageEnvelopeBuilder {
+    /// Sets the message identifier.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the tenant context.
+    pub fn tenant(mut self, tenant: TenantCtx) -> Self {
+        self.tenant = Some(tenant);
+        self
+    }
+
+    /// Sets the channel.
+    pub fn channel(mut self, channel: impl Into<ChannelId>) -> Self {
+        self.channel = Some(channel.into());
+        self
+    }
+
+    /// Sets the conversation.
+    pub fn session_id(mut self, session_id: impl Into<ConversationId>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Sets the reply scope.
+    pub fn reply_scope(mut self, reply_scope: ReplyScope) -> Self {
+        self.reply_scope = Some(reply_scope);
+        self
+    }
+
+    /// Sets the sending actor.
+    pub fn from(mut self, id: impl Into<PeerId>, kind: Option<String>) -> Self {
+        self.from = Some(Actor {
+            id: id.into(),
+            kind,
+        });
+        self
+    }
+
+    /// Adds an outbound destination.
+    pub fn to(mut self, id: impl Into<PeerId>, kind: Option<String>) -> Self {
+        self.to.push(Destination {
+            id: id.into(),
+            kind,
+        });
+        self
+    }
+
+    /// Sets the correlation identifier.
+    pub fn correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Sets the text content.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Adds an attachment.
+    pub fn attachment(mut self, attachment: Attachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Adds a metadata entry.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the delivery options.
+    pub fn delivery_options(mut self, delivery_options: DeliveryOptions) -> Self {
+        self.delivery_options = Some(delivery_options);
+        self
+    }
+
+    /// Builds the envelope, failing when a required field is missing or empty.
+    pub fn build(self) -> GResult<ChannelMessageEnvelope> {
+        Ok(ChannelMessageEnvelope {
+            id: required("id", self.id.filter(|id| !id.is_empty()))?,
+            tenant: required("tenant", self.tenant)?,
+            channel: required("channel", self.channel.filter(|id| !id.0.is_empty()))?,
+            session_id: required("session_id", self.session_id.filter(|id| !id.0.is_empty()))?,
+            reply_scope: self.reply_scope,
+            from: self.from,
+            to: self.to,
+            correlation_id: self.correlation_id,
+            text: self.text,
+            attachments: self.attachments,
+            metadata: self.metadata,
+            delivery_options: self.delivery_options,
+        })
+    }
+}
+
+/// Relative urgency of an outbound message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum DeliveryPriority {
+    /// May be delayed behind other traffic (digests, reminders).
+    Low,
+    /// Regular conversational traffic.
+    #[default]
+    Normal,
+    /// Delivered ahead of normal traffic (alerts, one-time codes).
+    High,
+}
+
+/// Scheduling and expiry hints for an outbound message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DeliveryOptions {
+    /// Earliest time the message may be sent; sent immediately when absent.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub send_after: Option<UnixMillis>,
+    /// Time-to-live after which an undelivered message is dropped.
+    ///
+    /// Measured from `send_after` when set, otherwise from the time the message was queued.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub expire_after: Option<DurationMs>,
+    /// Delivery priority.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority: DeliveryPriority,
+}
+
+impl DeliveryOptions {
+    /// Delays delivery until `send_after`.
+    pub fn with_send_after(mut self, send_after: UnixMillis) -> Self {
+        self.send_after = Some(send_after);
+        self
+    }
+
+    /// Drops the message when it is not delivered within `ttl`.
+    pub fn with_expire_after(mut self, ttl: DurationMs) -> Self {
+        self.expire_after = Some(ttl);
+        self
+    }
+
+    /// Sets the delivery priority.
+    pub fn with_priority(mut self, priority: DeliveryPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns `true` when the message may be sent at `now`.
+    pub fn is_due(&self, now: UnixMillis) -> bool {
+        self.send_after.is_none_or(|send_after| now >= send_after)
+    }
+
+    /// Returns the expiry instant of a message queued at `queued_at`, if it expires.
+    pub fn expires_at(&self, queued_at: UnixMillis) -> Option<UnixMillis> {
+        self.send_after
+            .unwrap_or(queued_at)
+            .checked_add(self.expire_after?)
+    }
+
+    /// Returns `true` when a message queued at `queued_at` must no longer be sent at `now`.
+    pub fn is_expired(&self, queued_at: UnixMillis, now: UnixMillis) -> bool {
+        self.expires_at(queued_at)
+            .is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// Record linking a provider conversation to the flow session that owns it.
+///
+/// Operators store one binding per `(channel, conversation, thread)` and look it up with
+/// [`ConversationBinding::lookup_key`] when a provider event arrives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ConversationBinding {
+    /// Environment the session runs in.
+    pub env: EnvId,
+    /// Tenant owning the conversation.
+    pub tenant: TenantId,
+    /// Channel the conversation lives on.
+    pub channel: ChannelId,
+    /// Provider conversation identifier.
+    pub conversation: ConversationId,
+    /// Optional provider thread within the conversation.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub thread: Option<String>,
+    /// Session owning the conversation.
+    pub session_key: SessionKey,
+    /// Flow the session executes.
+    pub flow_id: FlowId,
+    /// Pack providing the flow, when known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub pack_id: Option<PackId>,
+    /// When the binding was created.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub created_at: UnixMillis,
+    /// When the binding stops applying; bindings without expiry live until removed.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub expires_at: Option<UnixMillis>,
+}
+
+impl ConversationBinding {
+    /// Creates a binding for the tenant in `ctx` without thread, pack, or expiry.
+    pub fn new(
+        ctx: &TenantCtx,
+        channel: impl Into<ChannelId>,
+        conversation: impl Into<ConversationId>,
+        session_key: SessionKey,
+        flow_id: FlowId,
+        created_at: UnixMillis,
+    ) -> Self {
+        Self {
+            env: ctx.env.clone(),
+            tenant: ctx.tenant_id.clone(),
+            channel: channel.into(),
+            conversation: conversation.into(),
+            thread: None,
+            session_key,
+            flow_id,
+            pack_id: None,
+            created_at,
+            expires_at: None,
+        }
+    }
+
+    /// Scopes the binding to a provider thread.
+    pub fn with_thread(mut self, thread: impl Into<String>) -> Self {
+        self.thread = Some(thread.into());
+        self
+    }
+
+    /// Expires the binding `ttl` after its creation time.
+    pub fn with_ttl(mut self, ttl: DurationMs) -> Self {
+        self.expires_at = self.created_at.checked_add(ttl);
+        self
+    }
+
+    /// Returns `true` once `now` reaches the expiry time.
+    pub fn is_expired(&self, now: UnixMillis) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Store key identifying the bound conversation.
+    pub fn lookup_key(&self) -> String {
+        Self::key_for(
+            &self.env,
+            &self.tenant,
+            &self.channel,
+            &self.conversation,
+            self.thread.as_deref(),
+        )
+    }
+
+    /// Builds the store key for an incoming provider event:
+    /// `{env}:{tenant}:{channel}:{conversation}`, followed by `#{thread}` when a thread is given.
+    pub fn key_for(
+        env: &EnvId,
+        tenant: &TenantId,
+        channel: &ChannelId,
+        conversation: &ConversationId,
+        thread: Option<&str>,
+    ) -> String {
+        let mut key = format!("{env}:{tenant}:{}:{}", channel.0, conversation.0);
+        if let Some(thread) = thread {
+            key.push('#');
+            key.push_str(thread);
+        }
+        key
+    }
+}
+
+fn required<T>(field: &str, value: Option<T>) -> GResult<T> {
+    value.ok_or_else(|| {
+        GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("channel message envelope requires `{field}`"),
+        )
+    })
 }
 
+pub mod chunking;
 pub mod rendering;
+pub mod template;
 pub mod universal_dto;
@@ -0,0 +1,179 @@
+//! Chunked transfer of attachment payloads.
+//!
+//! Providers with message-size limits stream large attachments as a sequence of
+//! [`AttachmentChunk`]s sharing a `transfer_id`; the [`Attachment`](crate::Attachment) announcing
+//! the file carries the same identifier in `transfer_id`. [`AttachmentChunker`] splits a payload
+//! and [`reassemble_attachment`] restores it, verifying ordering, size, and the Blake3 digest.
+
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorCode, GResult, GreenticError, HashDigest};
+
+/// Default chunk size (256 KiB).
+pub const DEFAULT_ATTACHMENT_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Slice of an attachment payload sent in its own message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct AttachmentChunk {
+    /// Identifier shared by every chunk of the same attachment.
+    pub transfer_id: String,
+    /// Position of the chunk, starting at `0`.
+    pub sequence: u32,
+    /// Size of the complete attachment in bytes.
+    pub total_size: u64,
+    /// Blake3 digest of the complete attachment.
+    pub digest: HashDigest,
+    /// Set on the last chunk of the transfer.
+    #[cfg_attr(feature = "serde", serde(rename = "final"))]
+    pub is_final: bool,
+    /// Chunk payload (base64 encoded when serialized).
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::base64::Base64>")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub bytes: Vec<u8>,
+}
+
+/// Splits attachment payloads into chunks of at most `chunk_size` bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttachmentChunker {
+    chunk_size: usize,
+}
+
+impl AttachmentChunker {
+    /// Creates a chunker; `chunk_size` must be positive.
+    pub fn new(chunk_size: usize) -> GResult<Self> {
+        if chunk_size == 0 {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "attachment chunk size must be positive",
+            ));
+        }
+        Ok(Self { chunk_size })
+    }
+
+    /// Returns the maximum payload size of a chunk.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Splits `payload` into ordered chunks; an empty payload yields a single empty final chunk.
+    ///
+    /// Fails with [`ErrorCode::InvalidInput`] when the payload needs more chunks than a `u32`
+    /// sequence number can address.
+    pub fn split(
+        &self,
+        transfer_id: impl Into<String>,
+        payload: &[u8],
+    ) -> GResult<Vec<AttachmentChunk>> {
+        let transfer_id = transfer_id.into();
+        let mut parts: Vec<&[u8]> = payload.chunks(self.chunk_size).collect();
+        if parts.is_empty() {
+            parts.push(&[]);
+        }
+        let last = u32::try_from(parts.len() - 1).map_err(|_| {
+            invalid(&format!(
+                "payload of {} bytes needs more than {} chunks of {} bytes",
+                payload.len(),
+                u32::MAX as u64 + 1,
+                self.chunk_size
+            ))
+        })?;
+        let digest = HashDigest::blake3_of(payload);
+        let total_size = payload.len() as u64;
+        Ok((0..=last)
+            .zip(parts)
+            .map(|(sequence, bytes)| AttachmentChunk {
+                transfer_id: transfer_id.clone(),
+                sequence,
+                total_size,
+                digest: digest.clone(),
+                is_final: sequence == last,
+                bytes: bytes.to_vec(),
+            })
+            .collect())
+    }
+}
+
+impl Default for AttachmentChunker {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_ATTACHMENT_CHUNK_SIZE,
+        }
+    }
+}
+
+/// Reassembles the chunks of a single transfer, accepting them in any order.
+///
+/// Fails with [`ErrorCode::InvalidInput`] when chunks belong to different transfers, sequence
+/// numbers are missing or duplicated, the final flag is not set on exactly the last chunk, or
+/// the reassembled payload does not match `total_size` and `digest`.
+pub fn reassemble_attachment(
+    chunks: impl IntoIterator<Item = AttachmentChunk>,
+) -> GResult<Vec<u8>> {
+    let mut chunks: Vec<AttachmentChunk> = chunks.into_iter().collect();
+    chunks.sort_by_key(|chunk| chunk.sequence);
+    let first = match chunks.first() {
+        Some(first) => first,
+        None => return Err(invalid("no chunks to reassemble")),
+    };
+    let last = chunks.len() - 1;
+    for (index, chunk) in chunks.iter().enumerate() {
+        if chunk.transfer_id != first.transfer_id
+            || chunk.total_size != first.total_size
+            || chunk.digest != first.digest
+        {
+            return Err(invalid(&format!(
+                "chunk {} does not belong to transfer '{}'",
+                chunk.sequence, first.transfer_id
+            )));
+        }
+        if chunk.sequence as usize != index {
+            return Err(invalid(&format!(
+                "transfer '{}' is missing chunk {index}",
+                first.transfer_id
+            )));
+        }
+        if chunk.is_final != (index == last) {
+            return Err(invalid(&format!(
+                "transfer '{}' has an unexpected final flag on chunk {index}",
+                first.transfer_id
+            )));
+        }
+    }
+
+    let payload: Vec<u8> = chunks
+        .iter()
+        .flat_map(|chunk| chunk.bytes.iter().copied())
+        .collect();
+    if payload.len() as u64 != first.total_size {
+        return Err(invalid(&format!(
+            "transfer '{}' has {} bytes, expected {}",
+            first.transfer_id,
+            payload.len(),
+            first.total_size
+        )));
+    }
+    if HashDigest::blake3_of(&payload) != first.digest {
+        return Err(invalid(&format!(
+            "transfer '{}' does not match its digest",
+            first.transfer_id
+        )));
+    }
+    Ok(payload)
+}
+
+fn invalid(reason: &str) -> GreenticError {
+    GreenticError::new(
+        ErrorCode::InvalidInput,
+        format!("attachment chunks: {reason}"),
+    )
+}
@@ -0,0 +1,158 @@
+//! Localized message templates rendered by channel providers.
+//!
+//! A [`MessageTemplate`] names an i18n key and carries the texts for it. Texts contain
+//! `{variable}` placeholders; `{{` and `}}` write literal braces. [`MessageTemplate::render`]
+//! picks the text through the locale's fallback chain (`de-CH` → `de` → fallback string → key)
+//! and substitutes variables from the caller, then from the template defaults. Placeholders
+//! without a value are left in the output verbatim and reported by
+//! [`MessageTemplate::render_report`], so a broken template is visible instead of silently empty.
+
+use alloc::{borrow::ToOwned, collections::BTreeMap, format, string::String, vec::Vec};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::validate::{Diagnostic, Severity, core_diagnostic};
+use crate::{I18nText, LocaleTag};
+
+/// Localized message text with `{variable}` placeholders.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MessageTemplate {
+    /// I18n key, fallback string, and inline translations.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub text: I18nText,
+    /// Values used for variables the caller does not supply.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub defaults: BTreeMap<String, String>,
+}
+
+/// Output of [`MessageTemplate::render_report`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedTemplate {
+    /// Rendered text.
+    pub text: String,
+    /// Translation that was used, or `None` when the fallback string or key was rendered.
+    pub locale: Option<LocaleTag>,
+    /// Problems found while rendering.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl MessageTemplate {
+    /// Creates a template for `key` with an optional fallback string.
+    pub fn new(key: impl Into<String>, fallback: Option<String>) -> Self {
+        Self {
+            text: I18nText::new(key, fallback),
+            defaults: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the text for `locale`.
+    pub fn with_translation(mut self, locale: LocaleTag, text: impl Into<String>) -> Self {
+        self.text = self.text.with_translation(locale, text);
+        self
+    }
+
+    /// Sets the default value of `variable`.
+    pub fn with_default(mut self, variable: impl Into<String>, value: impl Into<String>) -> Self {
+        self.defaults.insert(variable.into(), value.into());
+        self
+    }
+
+    /// Returns the i18n key.
+    pub fn key(&self) -> &str {
+        &self.text.key
+    }
+
+    /// Renders the template for `locale`; see [`MessageTemplate::render_report`] for diagnostics.
+    pub fn render(&self, locale: &LocaleTag, vars: &BTreeMap<String, String>) -> String {
+        self.render_report(locale, vars).text
+    }
+
+    /// Renders the template for `locale` and reports missing texts, missing variables, and
+    /// unbalanced braces as warnings.
+    pub fn render_report(
+        &self,
+        locale: &LocaleTag,
+        vars: &BTreeMap<String, String>,
+    ) -> RenderedTemplate {
+        let key = self.key();
+        let mut diagnostics = Vec::new();
+        let matched = locale.best_match(self.text.translations.keys());
+        let source = match (matched, &self.text.fallback) {
+            (Some(tag), _) => self.text.translations[tag].as_str(),
+            (None, Some(fallback)) => fallback.as_str(),
+            (None, None) => {
+                diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "TEMPLATE_MISSING_TEXT",
+                    &format!("Template {key} has no text for {locale} and no fallback."),
+                    Some(key.to_owned()),
+                    Some("Add a translation or a fallback string.".to_owned()),
+                ));
+                key
+            }
+        };
+
+        let mut text = String::with_capacity(source.len());
+        let mut rest = source;
+        while let Some(index) = rest.find(['{', '}']) {
+            text.push_str(&rest[..index]);
+            let tail = &rest[index..];
+            if let Some(after) = tail.strip_prefix("{{").or_else(|| tail.strip_prefix("}}")) {
+                text.push_str(&tail[..1]);
+                rest = after;
+                continue;
+            }
+            let placeholder = tail
+                .strip_prefix('{')
+                .and_then(|inner| inner.split_once('}'))
+                .filter(|(name, _)| is_variable_name(name));
+            let Some((name, after)) = placeholder else {
+                diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "TEMPLATE_UNBALANCED_BRACE",
+                    &format!("Template {key} has an unmatched `{}`.", &tail[..1]),
+                    Some(key.to_owned()),
+                    Some("Escape literal braces as `{{` and `}}`.".to_owned()),
+                ));
+                text.push_str(&tail[..1]);
+                rest = &tail[1..];
+                continue;
+            };
+            match vars.get(name).or_else(|| self.defaults.get(name)) {
+                Some(value) => text.push_str(value),
+                None => {
+                    let mut diagnostic = core_diagnostic(
+                        Severity::Warn,
+                        "TEMPLATE_MISSING_VARIABLE",
+                        &format!("Template {key} uses variable {name} without a value."),
+                        Some(format!("{key}.{name}")),
+                        Some("Pass the variable or declare a default.".to_owned()),
+                    );
+                    diagnostic.data = serde_json::json!({ "variable": name });
+                    diagnostics.push(diagnostic);
+                    text.push_str(&tail[..name.len() + 2]);
+                }
+            }
+            rest = after;
+        }
+        text.push_str(rest);
+
+        RenderedTemplate {
+            text,
+            locale: matched.cloned(),
+            diagnostics,
+        }
+    }
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-'))
+}
@@ -8,7 +8,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::ChannelMessageEnvelope;
+use crate::{ChannelMessageEnvelope, DeliveryOptions, ErrorCode, PeerId};
 
 /// HTTP header name/value pair.
 #[derive(Clone, Debug, PartialEq)]
@@ -150,6 +150,12 @@ pub struct SendPayloadInV1 {
     pub auth_user: Option<AuthUserRefV1>,
     /// Provider payload to deliver.
     pub payload: ProviderPayloadV1,
+    /// Scheduling, expiry, and priority hints.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub delivery_options: Option<DeliveryOptions>,
 }
 
 /// Send result status and retry hint.
@@ -165,6 +171,206 @@ pub struct SendPayloadResultV1 {
     /// Whether the operation is retryable.
     #[cfg_attr(feature = "serde", serde(default))]
     pub retryable: bool,
+    /// Failure category, when the send failed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error_kind: Option<ProviderErrorKind>,
+}
+
+impl SendPayloadResultV1 {
+    /// Successful send.
+    pub fn success() -> Self {
+        Self {
+            ok: true,
+            message: None,
+            retryable: false,
+            error_kind: None,
+        }
+    }
+
+    /// Failed send; `retryable` follows [`ProviderErrorKind::is_retryable`].
+    pub fn failure(kind: ProviderErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            message: Some(message.into()),
+            retryable: kind.is_retryable(),
+            error_kind: Some(kind),
+        }
+    }
+}
+
+/// Category of a provider send failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ProviderErrorKind {
+    /// Provider credentials expired or were revoked; refresh them before retrying.
+    AuthExpired,
+    /// Provider throttled the request.
+    RateLimited,
+    /// Destination user, chat, or channel does not exist.
+    RecipientNotFound,
+    /// Payload exceeds the provider's size limits.
+    PayloadTooLarge,
+    /// Temporary provider or network failure.
+    Transient,
+    /// Any other failure; not retried automatically.
+    Other,
+}
+
+impl ProviderErrorKind {
+    /// Returns `true` when resending the same payload may succeed.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::RateLimited | Self::Transient)
+    }
+
+    /// Canonical error code for the category.
+    pub fn error_code(self) -> ErrorCode {
+        match self {
+            Self::AuthExpired => ErrorCode::Unauthenticated,
+            Self::RateLimited => ErrorCode::RateLimited,
+            Self::RecipientNotFound => ErrorCode::NotFound,
+            Self::PayloadTooLarge => ErrorCode::InvalidInput,
+            Self::Transient => ErrorCode::Unavailable,
+            Self::Other => ErrorCode::Unknown,
+        }
+    }
+}
+
+/// Send request delivering one payload template to many recipients.
+///
+/// Providers render `payload` once per recipient, substituting that recipient's `variables`,
+/// and report each outcome in a [`BroadcastSendResultV1`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BroadcastSendInV1 {
+    /// Provider type identifier.
+    pub provider_type: String,
+    /// Optional tenant identifier override.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tenant_id: Option<String>,
+    /// Optional auth user reference.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auth_user: Option<AuthUserRefV1>,
+    /// Payload template shared by all recipients.
+    pub payload: ProviderPayloadV1,
+    /// Who receives the broadcast.
+    pub audience: BroadcastAudienceV1,
+    /// Maximum sends per second; the provider's own limit applies when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub throttle_per_second: Option<u32>,
+    /// Scheduling, expiry, and priority hints applied to every recipient.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub delivery_options: Option<DeliveryOptions>,
+}
+
+/// Recipients of a broadcast.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum BroadcastAudienceV1 {
+    /// Explicit recipient list.
+    Recipients {
+        /// Recipients in send order.
+        recipients: Vec<BroadcastRecipientV1>,
+    },
+    /// Audience resolved by the provider (for example a segment or mailing list).
+    Selector {
+        /// Provider-defined audience identifier or query.
+        selector: String,
+        /// Variables applied to every resolved recipient.
+        #[cfg_attr(feature = "serde", serde(default))]
+        variables: BTreeMap<String, Value>,
+    },
+}
+
+/// Single broadcast recipient with personalization variables.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BroadcastRecipientV1 {
+    /// Provider-specific recipient identifier.
+    pub id: PeerId,
+    /// Optional recipient kind (for example `user` or `chat`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub kind: Option<String>,
+    /// Variables substituted into the payload template for this recipient.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variables: BTreeMap<String, Value>,
+}
+
+impl BroadcastRecipientV1 {
+    /// Creates a recipient without variables.
+    pub fn new(id: impl Into<PeerId>) -> Self {
+        Self {
+            id: id.into(),
+            kind: None,
+            variables: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a personalization variable.
+    pub fn with_variable(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.variables.insert(name.into(), value);
+        self
+    }
+}
+
+/// Per-recipient outcomes of a broadcast.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BroadcastSendResultV1 {
+    /// One entry per attempted recipient.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub results: Vec<BroadcastRecipientResultV1>,
+}
+
+impl BroadcastSendResultV1 {
+    /// Returns `true` when every recipient was sent successfully.
+    pub fn all_ok(&self) -> bool {
+        self.results.iter().all(|entry| entry.result.ok)
+    }
+
+    /// Number of successful sends.
+    pub fn sent_count(&self) -> usize {
+        self.results.iter().filter(|entry| entry.result.ok).count()
+    }
+
+    /// Iterates over failed recipients.
+    pub fn failed(&self) -> impl Iterator<Item = &BroadcastRecipientResultV1> {
+        self.results.iter().filter(|entry| !entry.result.ok)
+    }
+
+    /// Iterates over failed recipients worth resending.
+    pub fn retryable(&self) -> impl Iterator<Item = &BroadcastRecipientResultV1> {
+        self.failed().filter(|entry| entry.result.retryable)
+    }
+}
+
+/// Send outcome for one broadcast recipient.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BroadcastRecipientResultV1 {
+    /// Recipient the result belongs to.
+    pub recipient: PeerId,
+    /// Send result.
+    pub result: SendPayloadResultV1,
 }
 
 /// Subscription ensure request (v1).
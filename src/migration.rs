@@ -0,0 +1,262 @@
+//! Versioned migrations between schema revisions in [`crate::schemas`].
+//!
+//! Each migration implements [`Migrate`] and reports anything it could not carry over in a
+//! [`MigrationReport`]. [`MIGRATIONS`] lists every available step so tooling can plan upgrades of
+//! stored documents.
+
+use alloc::{format, string::String, vec::Vec};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::component_v0_5_0_to_v0_6_0::map_component_qa_spec;
+use crate::schemas::component::v0_5_0::LegacyComponentQaSpec;
+use crate::schemas::component::v0_5_0::qa::LegacyQuestionKind;
+use crate::schemas::component::v0_6_0::{ComponentQaSpec, QaMode};
+#[cfg(feature = "serde")]
+use crate::{ErrorCode, GResult, GreenticError};
+
+/// Schema name for component QA specs.
+pub const COMPONENT_QA_SCHEMA: &str = "component.qa";
+
+/// Converts a document from one schema revision to the next.
+pub trait Migrate<From, To> {
+    /// Migrates `input`, recording lossy or defaulted conversions in the report.
+    fn migrate(&self, input: &From) -> (To, MigrationReport);
+}
+
+/// How a migration note affects the migrated document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum MigrationNoteKind {
+    /// Source data had no equivalent and was discarded.
+    Dropped,
+    /// Source data was mapped onto an approximate equivalent.
+    Approximated,
+    /// Target field had no source and was filled with a default.
+    Defaulted,
+}
+
+/// Single conversion remark produced by a migration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MigrationNote {
+    /// Effect on the document.
+    pub kind: MigrationNoteKind,
+    /// Location in the source document (for example `questions.region.choices`).
+    pub path: String,
+    /// Human-readable explanation.
+    pub message: String,
+}
+
+/// Outcome details for a migration run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MigrationReport {
+    /// Schema name that was migrated.
+    pub schema: String,
+    /// Source version.
+    pub from_version: String,
+    /// Target version.
+    pub to_version: String,
+    /// Conversion remarks, in document order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub notes: Vec<MigrationNote>,
+}
+
+impl MigrationReport {
+    /// Creates an empty report for the given step.
+    pub fn new(
+        schema: impl Into<String>,
+        from_version: impl Into<String>,
+        to_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            schema: schema.into(),
+            from_version: from_version.into(),
+            to_version: to_version.into(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// Records a conversion remark.
+    pub fn note(
+        &mut self,
+        kind: MigrationNoteKind,
+        path: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.notes.push(MigrationNote {
+            kind,
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Returns `true` when source data was dropped or approximated.
+    pub fn is_lossy(&self) -> bool {
+        self.notes
+            .iter()
+            .any(|note| note.kind != MigrationNoteKind::Defaulted)
+    }
+
+    /// Appends the notes of a later step, keeping the original source version.
+    pub fn chain(mut self, next: MigrationReport) -> Self {
+        self.to_version = next.to_version;
+        self.notes.extend(next.notes);
+        self
+    }
+}
+
+/// Component QA migration from v0.5.0 to v0.6.0.
+///
+/// Legacy specs carry no mode, so the caller supplies the one the spec is used for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentQaV0_5ToV0_6 {
+    /// Mode assigned to the migrated spec.
+    pub mode: QaMode,
+}
+
+impl Migrate<LegacyComponentQaSpec, ComponentQaSpec> for ComponentQaV0_5ToV0_6 {
+    fn migrate(&self, input: &LegacyComponentQaSpec) -> (ComponentQaSpec, MigrationReport) {
+        let mut report = MigrationReport::new(COMPONENT_QA_SCHEMA, "0.5.0", "0.6.0");
+        report.note(
+            MigrationNoteKind::Approximated,
+            "title",
+            "literal text became the fallback of a generated i18n key",
+        );
+        for question in &input.questions {
+            let path = format!("questions.{}", question.id);
+            match (&question.kind, &question.choices) {
+                (LegacyQuestionKind::Choice, None) => report.note(
+                    MigrationNoteKind::Defaulted,
+                    format!("{path}.choices"),
+                    "choice question without choices migrated with an empty option list",
+                ),
+                (LegacyQuestionKind::Choice, Some(_)) | (_, None) => {}
+                (_, Some(_)) => report.note(
+                    MigrationNoteKind::Dropped,
+                    format!("{path}.choices"),
+                    "choices are only kept for choice questions",
+                ),
+            }
+        }
+        (map_component_qa_spec(self.mode.clone(), input), report)
+    }
+}
+
+/// Migrates a legacy v0.5.0 component QA spec to v0.6.0.
+pub fn migrate_component_qa_v0_5_to_v0_6(
+    mode: QaMode,
+    legacy: &LegacyComponentQaSpec,
+) -> (ComponentQaSpec, MigrationReport) {
+    ComponentQaV0_5ToV0_6 { mode }.migrate(legacy)
+}
+
+/// Registry entry describing one migration step.
+#[derive(Clone, Copy, Debug)]
+pub struct MigrationDescriptor {
+    /// Schema name (for example [`COMPONENT_QA_SCHEMA`]).
+    pub schema: &'static str,
+    /// Source version.
+    pub from_version: &'static str,
+    /// Target version.
+    pub to_version: &'static str,
+    /// Short description of the step.
+    pub description: &'static str,
+    /// Upgrades a JSON document from `from_version` to `to_version`.
+    #[cfg(feature = "serde")]
+    pub upgrade_json: fn(&serde_json::Value) -> GResult<(serde_json::Value, MigrationReport)>,
+}
+
+/// Every migration step known to this crate.
+pub const MIGRATIONS: &[MigrationDescriptor] = &[MigrationDescriptor {
+    schema: COMPONENT_QA_SCHEMA,
+    from_version: "0.5.0",
+    to_version: "0.6.0",
+    description: "Legacy component QA to i18n-aware QA spec (mode defaults to `default`)",
+    #[cfg(feature = "serde")]
+    upgrade_json: upgrade_component_qa_v0_5_json,
+}];
+
+/// Returns the ordered steps needed to move `schema` from `from_version` to `to_version`.
+///
+/// Returns an empty list when the versions match and `None` when no path exists.
+pub fn migration_path(
+    schema: &str,
+    from_version: &str,
+    to_version: &str,
+) -> Option<Vec<&'static MigrationDescriptor>> {
+    let mut path = Vec::new();
+    let mut current = from_version;
+    while current != to_version {
+        let step = MIGRATIONS
+            .iter()
+            .find(|step| step.schema == schema && step.from_version == current)?;
+        if path.len() >= MIGRATIONS.len() {
+            return None;
+        }
+        path.push(step);
+        current = step.to_version;
+    }
+    Some(path)
+}
+
+/// Upgrades a JSON document along the registered migration path.
+#[cfg(feature = "serde")]
+pub fn upgrade_json(
+    schema: &str,
+    from_version: &str,
+    to_version: &str,
+    document: &serde_json::Value,
+) -> GResult<(serde_json::Value, MigrationReport)> {
+    let path = migration_path(schema, from_version, to_version).ok_or_else(|| {
+        GreenticError::new(
+            ErrorCode::NotFound,
+            format!("no migration path for {schema} from {from_version} to {to_version}"),
+        )
+    })?;
+    let mut value = document.clone();
+    let mut report = MigrationReport::new(schema, from_version, from_version);
+    for step in path {
+        let (next, step_report) = (step.upgrade_json)(&value)?;
+        value = next;
+        report = report.chain(step_report);
+    }
+    Ok((value, report))
+}
+
+#[cfg(feature = "serde")]
+fn upgrade_component_qa_v0_5_json(
+    document: &serde_json::Value,
+) -> GResult<(serde_json::Value, MigrationReport)> {
+    let legacy: LegacyComponentQaSpec =
+        serde_json::from_value(document.clone()).map_err(|err| {
+            GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("invalid component QA v0.5.0 document: {err}"),
+            )
+        })?;
+    let (spec, mut report) = migrate_component_qa_v0_5_to_v0_6(QaMode::Default, &legacy);
+    report.note(
+        MigrationNoteKind::Defaulted,
+        "mode",
+        "legacy specs have no mode; `default` was assigned",
+    );
+    let value = serde_json::to_value(&spec).map_err(|err| {
+        GreenticError::new(
+            ErrorCode::Internal,
+            format!("failed to encode component QA v0.6.0 document: {err}"),
+        )
+    })?;
+    Ok((value, report))
+}
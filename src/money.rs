@@ -0,0 +1,336 @@
+//! Monetary amounts in micro-units of an ISO 4217 currency.
+//!
+//! [`MoneyMicro`] pairs an amount in millionths of the currency's major unit with its
+//! [`CurrencyCode`], so prices, cost estimates, and usage charges cannot be added across
+//! currencies or mistaken for plain counts. Arithmetic is checked; proration and rounding to the
+//! currency's minor unit take an explicit [`RoundingMode`].
+
+#[cfg(feature = "schemars")]
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::geo::code_newtype_impls;
+use crate::{ErrorCode, GResult, GreenticError};
+
+/// Micro-units per major currency unit.
+pub const MICROS_PER_UNIT: u64 = 1_000_000;
+
+/// ISO 4217 alphabetic currency code (for example `EUR`).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct CurrencyCode(String);
+
+impl CurrencyCode {
+    /// Validates a three-letter code, normalizing it to upper case.
+    pub fn parse(value: impl AsRef<str>) -> GResult<Self> {
+        let value = value.as_ref();
+        if value.len() != 3 || !value.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("invalid currency code '{value}': expected three ASCII letters"),
+            ));
+        }
+        Ok(Self(value.to_ascii_uppercase()))
+    }
+
+    /// Returns the upper-case code.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Number of decimal places of the currency's minor unit (2 unless ISO 4217 says otherwise).
+    pub fn minor_units(&self) -> u32 {
+        match self.0.as_str() {
+            "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF"
+            | "UGX" | "UYI" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+            "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+            _ => 2,
+        }
+    }
+
+    /// Micro-units per minor unit (for example `10_000` for cents).
+    fn micros_per_minor_unit(&self) -> u64 {
+        10u64.pow(6 - self.minor_units())
+    }
+}
+
+code_newtype_impls!(CurrencyCode, "ISO 4217 currency code");
+
+/// How fractional results are rounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum RoundingMode {
+    /// Toward zero.
+    Down,
+    /// Away from zero.
+    Up,
+    /// To nearest, ties away from zero.
+    HalfUp,
+    /// To nearest, ties to the even neighbour (banker's rounding).
+    #[default]
+    HalfEven,
+}
+
+impl RoundingMode {
+    /// Divides `numerator` by a non-zero `denominator`.
+    fn divide(self, numerator: u128, denominator: u128) -> u128 {
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+        if remainder == 0 {
+            return quotient;
+        }
+        let round_up = match self {
+            Self::Down => false,
+            Self::Up => true,
+            Self::HalfUp => remainder * 2 >= denominator,
+            Self::HalfEven => {
+                remainder * 2 > denominator || (remainder * 2 == denominator && quotient % 2 == 1)
+            }
+        };
+        quotient + u128::from(round_up)
+    }
+}
+
+/// Errors raised by [`MoneyMicro`] arithmetic.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum MoneyError {
+    /// The operands use different currencies.
+    #[error("cannot combine {expected} with {found}")]
+    CurrencyMismatch {
+        /// Currency of the left-hand operand.
+        expected: CurrencyCode,
+        /// Currency of the right-hand operand.
+        found: CurrencyCode,
+    },
+    /// The result does not fit in a `u64` amount (or would be negative).
+    #[error("{currency} amount out of range")]
+    Overflow {
+        /// Currency of the operation.
+        currency: CurrencyCode,
+    },
+    /// A proration used a zero denominator.
+    #[error("division by zero")]
+    DivideByZero,
+}
+
+/// Amount of money in micro-units (millionths of the major unit) of a currency.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MoneyMicro {
+    /// Amount in micro-units (`1_500_000` is 1.50).
+    pub amount_micro: u64,
+    /// Currency of the amount.
+    pub currency: CurrencyCode,
+}
+
+impl MoneyMicro {
+    /// Creates an amount from micro-units.
+    pub fn new(amount_micro: u64, currency: CurrencyCode) -> Self {
+        Self {
+            amount_micro,
+            currency,
+        }
+    }
+
+    /// Zero in `currency`.
+    pub fn zero(currency: CurrencyCode) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Creates an amount from minor units (for example cents), failing on overflow.
+    pub fn from_minor_units(minor: u64, currency: CurrencyCode) -> Result<Self, MoneyError> {
+        match minor.checked_mul(currency.micros_per_minor_unit()) {
+            Some(amount_micro) => Ok(Self::new(amount_micro, currency)),
+            None => Err(MoneyError::Overflow { currency }),
+        }
+    }
+
+    /// Returns `true` for a zero amount.
+    pub fn is_zero(&self) -> bool {
+        self.amount_micro == 0
+    }
+
+    /// Adds `other`, which must use the same currency.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, MoneyError> {
+        self.ensure_currency(other)?;
+        self.with_amount(self.amount_micro.checked_add(other.amount_micro))
+    }
+
+    /// Subtracts `other`, failing when the result would be negative.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, MoneyError> {
+        self.ensure_currency(other)?;
+        self.with_amount(self.amount_micro.checked_sub(other.amount_micro))
+    }
+
+    /// Multiplies by a quantity (for example a per-unit rate times units).
+    pub fn checked_mul(&self, quantity: u64) -> Result<Self, MoneyError> {
+        self.with_amount(self.amount_micro.checked_mul(quantity))
+    }
+
+    /// Multiplies by a quantity, clamping at `u64::MAX` micro-units.
+    pub fn saturating_mul(&self, quantity: u64) -> Self {
+        Self::new(
+            self.amount_micro.saturating_mul(quantity),
+            self.currency.clone(),
+        )
+    }
+
+    /// Scales by `numerator / denominator` (for example to prorate a billing period).
+    pub fn mul_ratio(
+        &self,
+        numerator: u64,
+        denominator: u64,
+        mode: RoundingMode,
+    ) -> Result<Self, MoneyError> {
+        if denominator == 0 {
+            return Err(MoneyError::DivideByZero);
+        }
+        let scaled = mode.divide(
+            u128::from(self.amount_micro) * u128::from(numerator),
+            u128::from(denominator),
+        );
+        self.with_amount(u64::try_from(scaled).ok())
+    }
+
+    /// Rounds to a whole number of the currency's minor units (cents for `EUR`, yen for `JPY`).
+    pub fn round_to_minor_units(&self, mode: RoundingMode) -> Result<Self, MoneyError> {
+        let step = self.currency.micros_per_minor_unit();
+        let minor = mode.divide(u128::from(self.amount_micro), u128::from(step));
+        self.with_amount(u64::try_from(minor * u128::from(step)).ok())
+    }
+
+    fn ensure_currency(&self, other: &Self) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch {
+                expected: self.currency.clone(),
+                found: other.currency.clone(),
+            })
+        }
+    }
+
+    fn with_amount(&self, amount_micro: Option<u64>) -> Result<Self, MoneyError> {
+        match amount_micro {
+            Some(amount_micro) => Ok(Self::new(amount_micro, self.currency.clone())),
+            None => Err(MoneyError::Overflow {
+                currency: self.currency.clone(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for MoneyMicro {
+    /// Formats as `12.50 EUR`, showing at least the currency's minor-unit digits and any further
+    /// non-zero micro-unit digits.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.amount_micro / MICROS_PER_UNIT;
+        let fraction = format!("{:06}", self.amount_micro % MICROS_PER_UNIT);
+        let min_digits = self.currency.minor_units() as usize;
+        let digits = fraction.trim_end_matches('0').len().max(min_digits);
+        if digits == 0 {
+            write!(f, "{whole} {}", self.currency)
+        } else {
+            write!(f, "{whole}.{} {}", &fraction[..digits], self.currency)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(amount_micro: u64, currency: &str) -> MoneyMicro {
+        MoneyMicro::new(
+            amount_micro,
+            CurrencyCode::parse(currency).unwrap_or_else(|err| panic!("{err}")),
+        )
+    }
+
+    #[test]
+    fn rounding_modes_resolve_ties() {
+        let cases = [
+            (RoundingMode::Down, [2, 2, 2, 3]),
+            (RoundingMode::Up, [3, 3, 3, 4]),
+            (RoundingMode::HalfUp, [2, 3, 3, 4]),
+            (RoundingMode::HalfEven, [2, 2, 3, 4]),
+        ];
+        for (mode, expected) in cases {
+            let actual = [(9, 4), (5, 2), (11, 4), (7, 2)].map(|(n, d)| mode.divide(n, d));
+            assert_eq!(actual, expected, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn arithmetic_checks_currency_and_range() {
+        let price = money(19_990_000, "eur");
+        assert_eq!(price.currency.as_str(), "EUR");
+        assert_eq!(
+            price.checked_add(&money(10_000, "EUR")),
+            Ok(money(20_000_000, "EUR"))
+        );
+        assert!(matches!(
+            price.checked_add(&money(1, "USD")),
+            Err(MoneyError::CurrencyMismatch { .. })
+        ));
+        assert!(matches!(
+            money(1, "EUR").checked_sub(&money(2, "EUR")),
+            Err(MoneyError::Overflow { .. })
+        ));
+        assert!(money(u64::MAX, "EUR").checked_mul(2).is_err());
+        assert_eq!(
+            money(u64::MAX, "EUR").saturating_mul(2).amount_micro,
+            u64::MAX
+        );
+        assert_eq!(
+            money(365_000_000, "EUR").mul_ratio(30, 365, RoundingMode::HalfEven),
+            Ok(money(30_000_000, "EUR"))
+        );
+        assert_eq!(
+            price.mul_ratio(1, 0, RoundingMode::Down),
+            Err(MoneyError::DivideByZero)
+        );
+        assert_eq!(
+            MoneyMicro::from_minor_units(
+                1_999,
+                CurrencyCode::parse("JPY").unwrap_or_else(|err| panic!("{err}"))
+            ),
+            Ok(money(1_999_000_000, "JPY"))
+        );
+    }
+
+    #[test]
+    fn rounds_and_displays_in_minor_units() {
+        let amount = money(1_234_567, "EUR");
+        assert_eq!(amount.to_string(), "1.234567 EUR");
+        assert_eq!(
+            amount.round_to_minor_units(RoundingMode::HalfEven),
+            Ok(money(1_230_000, "EUR"))
+        );
+        assert_eq!(
+            amount.round_to_minor_units(RoundingMode::Up),
+            Ok(money(1_240_000, "EUR"))
+        );
+        assert_eq!(money(12_500_000, "USD").to_string(), "12.50 USD");
+        assert_eq!(money(1_500_000_000, "JPY").to_string(), "1500 JPY");
+        assert_eq!(money(2_500_000, "KWD").to_string(), "2.500 KWD");
+        assert_eq!(
+            money(2_500_000, "JPY").round_to_minor_units(RoundingMode::HalfEven),
+            Ok(money(2_000_000, "JPY"))
+        );
+        assert!(CurrencyCode::parse("EU").is_err());
+        assert!(CurrencyCode::parse("E1R").is_err());
+    }
+}
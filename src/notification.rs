@@ -0,0 +1,282 @@
+//! Notification routing preferences shared by runtimes, distributors, and the console.
+//!
+//! A [`NotificationPreferences`] document belongs to a tenant and optionally a single user. It
+//! holds a default [`NotificationRule`] and per-[`NotificationCategory`] overrides that decide the
+//! delivery channels, digest frequency, and quiet hours for run failures, rollout events, billing
+//! alerts, and other categories.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub use crate::units::MINUTES_PER_DAY;
+use crate::{ErrorCode, GResult, GreenticError, TenantCtx, UserId};
+
+/// Kind of event a notification is raised for.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum NotificationCategory {
+    /// Flow or job run failures.
+    RunFailure,
+    /// Rollout progress and outcomes.
+    Rollout,
+    /// Billing alerts (invoices, payment failures, spend thresholds).
+    Billing,
+    /// Security findings and policy violations.
+    Security,
+    /// Product-specific category.
+    Other(String),
+}
+
+/// Delivery channel for a notification.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum NotificationChannel {
+    /// In-app notification centre.
+    InApp,
+    /// Email to the user's address on file.
+    Email,
+    /// Chat delivery through a messaging provider.
+    Chat,
+    /// Webhook call.
+    Webhook,
+    /// Provider-specific channel.
+    Other(String),
+}
+
+/// How often notifications are delivered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum DigestFrequency {
+    /// Deliver each notification as it happens.
+    #[default]
+    Immediate,
+    /// Batch into an hourly digest.
+    Hourly,
+    /// Batch into a daily digest.
+    Daily,
+    /// Batch into a weekly digest.
+    Weekly,
+    /// Do not deliver.
+    Muted,
+}
+
+/// Daily window during which immediate notifications are held back.
+///
+/// Bounds are minutes after local midnight in `timezone`; a window whose end is before its start
+/// wraps past midnight (for example `22:00`–`07:00`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct QuietHours {
+    /// Start of the window (inclusive), in minutes after midnight.
+    pub start_minute: u16,
+    /// End of the window (exclusive), in minutes after midnight.
+    pub end_minute: u16,
+    /// IANA timezone the window is expressed in (for example `Europe/Berlin`).
+    pub timezone: String,
+}
+
+impl QuietHours {
+    /// Creates a window from `HH:MM`-style hour and minute pairs.
+    pub fn new(start: (u16, u16), end: (u16, u16), timezone: impl Into<String>) -> GResult<Self> {
+        let quiet = Self {
+            start_minute: start.0.saturating_mul(60).saturating_add(start.1),
+            end_minute: end.0.saturating_mul(60).saturating_add(end.1),
+            timezone: timezone.into(),
+        };
+        quiet.validate()?;
+        Ok(quiet)
+    }
+
+    /// Ensures both bounds fall within a day and the timezone is set.
+    pub fn validate(&self) -> GResult<()> {
+        if self.start_minute >= MINUTES_PER_DAY || self.end_minute >= MINUTES_PER_DAY {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "quiet hours {}..{} must be below {MINUTES_PER_DAY} minutes",
+                    self.start_minute, self.end_minute
+                ),
+            ));
+        }
+        if self.timezone.trim().is_empty() {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "quiet hours require a timezone",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns `true` when the local `minute_of_day` falls inside the window.
+    pub fn contains(&self, minute_of_day: u16) -> bool {
+        let minute = minute_of_day % MINUTES_PER_DAY;
+        if self.start_minute <= self.end_minute {
+            (self.start_minute..self.end_minute).contains(&minute)
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+}
+
+/// Channels, frequency, and quiet hours applied to a category.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct NotificationRule {
+    /// Channels to deliver on.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub channels: Vec<NotificationChannel>,
+    /// Delivery frequency.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub frequency: DigestFrequency,
+    /// Window during which immediate delivery is held back.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl Default for NotificationRule {
+    fn default() -> Self {
+        Self {
+            channels: vec![NotificationChannel::InApp],
+            frequency: DigestFrequency::Immediate,
+            quiet_hours: None,
+        }
+    }
+}
+
+impl NotificationRule {
+    /// Returns `true` when a notification should go out right away at local `minute_of_day`.
+    ///
+    /// Digested and muted rules never deliver immediately; neither do rules without channels.
+    pub fn delivers_now(&self, minute_of_day: u16) -> bool {
+        self.frequency == DigestFrequency::Immediate
+            && !self.channels.is_empty()
+            && !self
+                .quiet_hours
+                .as_ref()
+                .is_some_and(|quiet| quiet.contains(minute_of_day))
+    }
+}
+
+/// Rule override for a single category.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CategoryPreference {
+    /// Category the rule applies to.
+    pub category: NotificationCategory,
+    /// Rule for the category.
+    pub rule: NotificationRule,
+}
+
+/// Notification preferences for a tenant, or for one user within it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct NotificationPreferences {
+    /// Tenant the preferences belong to.
+    pub tenant: TenantCtx,
+    /// User the preferences belong to; tenant-wide defaults when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub user_id: Option<UserId>,
+    /// Rule applied to categories without an override.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub defaults: NotificationRule,
+    /// Per-category overrides.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub categories: Vec<CategoryPreference>,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl NotificationPreferences {
+    /// Creates tenant-wide preferences using the default rule.
+    pub fn new(tenant: TenantCtx) -> Self {
+        Self {
+            tenant,
+            user_id: None,
+            defaults: NotificationRule::default(),
+            categories: Vec::new(),
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Scopes the preferences to a single user.
+    pub fn for_user(mut self, user_id: UserId) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    /// Sets the rule for `category`, replacing any existing override.
+    pub fn set_rule(&mut self, category: NotificationCategory, rule: NotificationRule) {
+        match self
+            .categories
+            .iter_mut()
+            .find(|preference| preference.category == category)
+        {
+            Some(preference) => preference.rule = rule,
+            None => self.categories.push(CategoryPreference { category, rule }),
+        }
+    }
+
+    /// Returns the effective rule for `category`.
+    pub fn rule_for(&self, category: &NotificationCategory) -> &NotificationRule {
+        self.categories
+            .iter()
+            .find(|preference| &preference.category == category)
+            .map_or(&self.defaults, |preference| &preference.rule)
+    }
+
+    /// Ensures quiet hours are well-formed and each category is overridden at most once.
+    pub fn validate(&self) -> GResult<()> {
+        let rules = core::iter::once(&self.defaults)
+            .chain(self.categories.iter().map(|preference| &preference.rule));
+        for quiet in rules.filter_map(|rule| rule.quiet_hours.as_ref()) {
+            quiet.validate()?;
+        }
+        for (index, preference) in self.categories.iter().enumerate() {
+            if self.categories[..index]
+                .iter()
+                .any(|earlier| earlier.category == preference.category)
+            {
+                return Err(GreenticError::new(
+                    ErrorCode::InvalidInput,
+                    format!(
+                        "notification category {:?} is configured more than once",
+                        preference.category
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
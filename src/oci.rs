@@ -0,0 +1,311 @@
+//! OCI distribution references for container images and published wasm artifacts.
+//!
+//! [`OciImageRef`] follows the reference grammar of the OCI distribution spec:
+//! `[registry/]repository[:tag][@digest]`. The first path component is treated as the registry
+//! when it contains `.` or `:` or is `localhost`, matching how container tooling resolves names.
+//! An `oci://` prefix is accepted and dropped.
+
+#[cfg(feature = "schemars")]
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::geo::code_newtype_impls;
+use crate::{ErrorCode, GResult, GreenticError};
+
+const SCHEME: &str = "oci://";
+const MAX_NAME_LEN: usize = 255;
+const MAX_TAG_LEN: usize = 128;
+
+/// Reference to an OCI image (for example `ghcr.io/greentic/runner:1.4@sha256:…`).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct OciImageRef(String);
+
+impl OciImageRef {
+    /// Validates a reference against the OCI distribution grammar.
+    pub fn parse(value: impl AsRef<str>) -> GResult<Self> {
+        let raw = value.as_ref();
+        let value = raw.strip_prefix(SCHEME).unwrap_or(raw);
+        let parts = split(value);
+        if parts.name.len() > MAX_NAME_LEN {
+            return Err(invalid(raw, "name exceeds 255 characters"));
+        }
+        if let Some(registry) = parts.registry
+            && !is_registry(registry)
+        {
+            return Err(invalid(raw, "malformed registry host"));
+        }
+        if !parts.repository.split('/').all(is_path_component) {
+            return Err(invalid(
+                raw,
+                "repository components must be lower-case alphanumerics separated by `.`, `_`, `__`, or `-`",
+            ));
+        }
+        if let Some(tag) = parts.tag
+            && !is_tag(tag)
+        {
+            return Err(invalid(raw, "malformed tag"));
+        }
+        if let Some(digest) = parts.digest
+            && !is_digest(digest)
+        {
+            return Err(invalid(raw, "malformed digest"));
+        }
+        Ok(Self(String::from(value)))
+    }
+
+    /// Alias for [`OciImageRef::parse`].
+    pub fn new(value: impl AsRef<str>) -> GResult<Self> {
+        Self::parse(value)
+    }
+
+    /// Returns the reference without the `oci://` prefix.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Registry host (with optional port), when the reference names one.
+    pub fn registry(&self) -> Option<&str> {
+        split(&self.0).registry
+    }
+
+    /// Repository path within the registry.
+    pub fn repository(&self) -> &str {
+        split(&self.0).repository
+    }
+
+    /// Registry and repository without tag or digest.
+    pub fn name(&self) -> &str {
+        split(&self.0).name
+    }
+
+    /// Tag, when present.
+    pub fn tag(&self) -> Option<&str> {
+        split(&self.0).tag
+    }
+
+    /// Content digest (`algorithm:hex`), when present.
+    pub fn digest(&self) -> Option<&str> {
+        split(&self.0).digest
+    }
+
+    /// Returns `true` when the reference names immutable content by digest.
+    pub fn is_pinned(&self) -> bool {
+        self.digest().is_some()
+    }
+
+    /// Returns the reference pinned to `digest`, keeping the tag for readability.
+    pub fn with_digest(&self, digest: &str) -> GResult<Self> {
+        let parts = split(&self.0);
+        match parts.tag {
+            Some(tag) => Self::parse(format!("{}:{tag}@{digest}", parts.name)),
+            None => Self::parse(format!("{}@{digest}", parts.name)),
+        }
+    }
+
+    /// Returns the reference with `tag`, dropping any digest.
+    pub fn with_tag(&self, tag: &str) -> GResult<Self> {
+        Self::parse(format!("{}:{tag}", self.name()))
+    }
+}
+
+impl AsRef<str> for OciImageRef {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+code_newtype_impls!(
+    OciImageRef,
+    "OCI image reference `[registry/]repository[:tag][@digest]`"
+);
+
+struct Parts<'a> {
+    name: &'a str,
+    registry: Option<&'a str>,
+    repository: &'a str,
+    tag: Option<&'a str>,
+    digest: Option<&'a str>,
+}
+
+fn split(value: &str) -> Parts<'_> {
+    let (rest, digest) = match value.split_once('@') {
+        Some((rest, digest)) => (rest, Some(digest)),
+        None => (value, None),
+    };
+    let last_slash = rest.rfind('/').map_or(0, |index| index + 1);
+    let (name, tag) = match rest[last_slash..].rfind(':') {
+        Some(index) => (
+            &rest[..last_slash + index],
+            Some(&rest[last_slash + index + 1..]),
+        ),
+        None => (rest, None),
+    };
+    let (registry, repository) = match name.split_once('/') {
+        Some((first, remainder)) if first.contains(['.', ':']) || first == "localhost" => {
+            (Some(first), remainder)
+        }
+        _ => (None, name),
+    };
+    Parts {
+        name,
+        registry,
+        repository,
+        tag,
+        digest,
+    }
+}
+
+fn is_registry(value: &str) -> bool {
+    let (host, port) = match value.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (value, None),
+    };
+    let host_ok = !host.is_empty()
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+        });
+    let port_ok =
+        port.is_none_or(|port| !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()));
+    host_ok && port_ok
+}
+
+fn is_path_component(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    if bytes.is_empty() {
+        return false;
+    }
+    let alnum = |b: u8| b.is_ascii_lowercase() || b.is_ascii_digit();
+    if !alnum(bytes[0]) || !alnum(bytes[bytes.len() - 1]) {
+        return false;
+    }
+    let mut index = 0;
+    while index < bytes.len() {
+        if alnum(bytes[index]) {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < bytes.len() && !alnum(bytes[index]) {
+            index += 1;
+        }
+        let separator = &value[start..index];
+        let valid = matches!(separator, "." | "_" | "__") || separator.bytes().all(|b| b == b'-');
+        if !valid {
+            return false;
+        }
+    }
+    true
+}
+
+fn is_tag(value: &str) -> bool {
+    let word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    value.len() <= MAX_TAG_LEN
+        && value.bytes().next().is_some_and(word)
+        && value.bytes().all(|b| word(b) || b == b'.' || b == b'-')
+}
+
+fn is_digest(value: &str) -> bool {
+    let Some((algorithm, hex)) = value.split_once(':') else {
+        return false;
+    };
+    let algorithm_ok = algorithm.split(['+', '.', '_', '-']).all(|part| {
+        part.bytes().next().is_some_and(|b| b.is_ascii_alphabetic())
+            && part.bytes().all(|b| b.is_ascii_alphanumeric())
+    });
+    let hex_ok = match algorithm {
+        "sha256" => hex.len() == 64,
+        "sha512" => hex.len() == 128,
+        _ => hex.len() >= 32,
+    } && hex
+        .bytes()
+        .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    algorithm_ok && hex_ok
+}
+
+fn invalid(value: &str, reason: &str) -> GreenticError {
+    GreenticError::new(
+        ErrorCode::InvalidInput,
+        format!("invalid OCI reference '{value}': {reason}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn parse(value: &str) -> OciImageRef {
+        OciImageRef::parse(value).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    #[test]
+    fn parses_components() {
+        let full = parse(&format!("oci://ghcr.io:443/greentic/runner:1.4.0@{DIGEST}"));
+        assert_eq!(full.registry(), Some("ghcr.io:443"));
+        assert_eq!(full.repository(), "greentic/runner");
+        assert_eq!(full.name(), "ghcr.io:443/greentic/runner");
+        assert_eq!(full.tag(), Some("1.4.0"));
+        assert_eq!(full.digest(), Some(DIGEST));
+        assert!(full.is_pinned());
+        assert!(!full.as_str().starts_with("oci://"));
+
+        let short = parse("library/my_app__x-y");
+        assert_eq!(short.registry(), None);
+        assert_eq!(short.repository(), "library/my_app__x-y");
+        assert_eq!(short.tag(), None);
+        assert!(!short.is_pinned());
+
+        let local = parse("localhost/app:dev");
+        assert_eq!(local.registry(), Some("localhost"));
+        assert_eq!(local.tag(), Some("dev"));
+    }
+
+    #[test]
+    fn pins_and_retags() {
+        let image = parse("registry.example.com/team/app:v1");
+        let pinned = image
+            .with_digest(DIGEST)
+            .unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(
+            pinned.as_str(),
+            format!("registry.example.com/team/app:v1@{DIGEST}")
+        );
+        let retagged = pinned.with_tag("v2").unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(retagged.as_str(), "registry.example.com/team/app:v2");
+        assert!(image.with_digest("sha256:abc").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_references() {
+        for invalid in [
+            "",
+            "App/Upper",
+            "team//app",
+            "team/app:",
+            "team/app:-tag",
+            "team/app@sha256:short",
+            "team/app@md5",
+            "bad_host.com:port/app",
+            "-host.io/app",
+            "team/app.",
+        ] {
+            assert!(OciImageRef::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+}
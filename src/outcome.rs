@@ -1,5 +1,7 @@
 //! Normalized execution outcomes.
 
+use alloc::borrow::ToOwned;
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -8,7 +10,7 @@ use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
-use crate::ErrorCode;
+use crate::{ErrorCode, GResult, GreenticError, NodeError, NodeResult};
 
 /// Outcome of a node, adapter, or tool invocation.
 #[derive(Clone, Debug, PartialEq)]
@@ -43,6 +45,30 @@ pub enum Outcome<T> {
 }
 
 impl<T> Outcome<T> {
+    /// Creates a pending outcome with the supplied wait reason.
+    pub fn pending(reason: impl Into<String>) -> Self {
+        Self::Pending {
+            reason: reason.into(),
+            expected_input: None,
+        }
+    }
+
+    /// Creates a pending outcome waiting for the listed inputs.
+    pub fn pending_with_inputs(inputs: &[&str]) -> Self {
+        Self::Pending {
+            reason: format!("waiting for input: {}", inputs.join(", ")),
+            expected_input: Some(inputs.iter().map(|input| (*input).to_owned()).collect()),
+        }
+    }
+
+    /// Creates an error outcome.
+    pub fn error(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self::Error {
+            code,
+            message: message.into(),
+        }
+    }
+
     /// Returns `true` when the outcome is [`Outcome::Done`].
     pub fn is_done(&self) -> bool {
         matches!(self, Self::Done(_))
@@ -75,4 +101,109 @@ impl<T> Outcome<T> {
             Outcome::Error { code, message } => Outcome::Error { code, message },
         }
     }
+
+    /// Chains another step after a [`Outcome::Done`] value; pending and error outcomes pass
+    /// through unchanged.
+    pub fn and_then<U, F>(self, f: F) -> Outcome<U>
+    where
+        F: FnOnce(T) -> Outcome<U>,
+    {
+        match self {
+            Outcome::Done(inner) => f(inner),
+            Outcome::Pending {
+                reason,
+                expected_input,
+            } => Outcome::Pending {
+                reason,
+                expected_input,
+            },
+            Outcome::Error { code, message } => Outcome::Error { code, message },
+        }
+    }
+
+    /// Borrows the done value.
+    pub fn as_ref(&self) -> Outcome<&T> {
+        match self {
+            Outcome::Done(inner) => Outcome::Done(inner),
+            Outcome::Pending {
+                reason,
+                expected_input,
+            } => Outcome::Pending {
+                reason: reason.clone(),
+                expected_input: expected_input.clone(),
+            },
+            Outcome::Error { code, message } => Outcome::Error {
+                code: *code,
+                message: message.clone(),
+            },
+        }
+    }
+
+    /// Returns the done value, if any.
+    pub fn done(self) -> Option<T> {
+        match self {
+            Outcome::Done(inner) => Some(inner),
+            _ => None,
+        }
+    }
+
+    /// Returns the wait reason of a pending outcome.
+    pub fn pending_reason(&self) -> Option<&str> {
+        match self {
+            Outcome::Pending { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Returns the inputs a pending outcome is waiting for.
+    pub fn expected_input(&self) -> Option<&[String]> {
+        match self {
+            Outcome::Pending { expected_input, .. } => expected_input.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Converts into a `Result`, with `Ok(None)` standing for a pending outcome.
+    pub fn into_result(self) -> GResult<Option<T>> {
+        match self {
+            Outcome::Done(inner) => Ok(Some(inner)),
+            Outcome::Pending { .. } => Ok(None),
+            Outcome::Error { code, message } => Err(GreenticError::new(code, message)),
+        }
+    }
+}
+
+impl<T> From<NodeResult<T>> for Outcome<T> {
+    /// Maps `Ok` to [`Outcome::Done`] and `Err` to [`Outcome::Error`].
+    ///
+    /// Node error codes matching an [`ErrorCode`] name (for example `not_found`) keep that code;
+    /// other codes become [`ErrorCode::Unknown`] and are prefixed to the message.
+    fn from(result: NodeResult<T>) -> Self {
+        match result {
+            Ok(value) => Outcome::Done(value),
+            Err(NodeError { code, message, .. }) => match error_code(&code) {
+                Some(code) => Outcome::Error { code, message },
+                None => Outcome::Error {
+                    code: ErrorCode::Unknown,
+                    message: format!("{code}: {message}"),
+                },
+            },
+        }
+    }
+}
+
+fn error_code(code: &str) -> Option<ErrorCode> {
+    Some(match code {
+        "unknown" => ErrorCode::Unknown,
+        "invalid_input" => ErrorCode::InvalidInput,
+        "not_found" => ErrorCode::NotFound,
+        "conflict" => ErrorCode::Conflict,
+        "timeout" => ErrorCode::Timeout,
+        "unauthenticated" => ErrorCode::Unauthenticated,
+        "permission_denied" => ErrorCode::PermissionDenied,
+        "rate_limited" => ErrorCode::RateLimited,
+        "unavailable" => ErrorCode::Unavailable,
+        "internal" => ErrorCode::Internal,
+        _ => return None,
+    })
 }
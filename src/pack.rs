@@ -1,9 +1,16 @@
 //! Pack reference metadata.
+//!
+//! Pack signatures are detached [`Signature`]s. A [`SignaturePolicy`] requires several independent
+//! signers before install; [`verify_signature_policy`] checks a signature list against it.
 
 pub mod extensions;
+pub mod resolve;
 
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt;
 
 use semver::Version;
 
@@ -14,6 +21,8 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde")]
 use serde_with::serde_as;
 
+use crate::{ErrorCode, GResult, GreenticError};
+
 /// Reference to a pack stored in an OCI registry.
 #[cfg_attr(feature = "serde", serde_as)]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -95,3 +104,215 @@ pub enum SignatureAlgorithm {
     /// Other algorithms identified by name.
     Other(String),
 }
+
+/// Party a signing key acts for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SignerRole {
+    /// Author or vendor of the pack.
+    Publisher,
+    /// Store that reviewed and distributes the pack.
+    Store,
+    /// Security team that scanned the pack.
+    Security,
+}
+
+impl fmt::Display for SignerRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Publisher => "publisher",
+            Self::Store => "store",
+            Self::Security => "security",
+        })
+    }
+}
+
+/// Signatures a pack must carry before it may be installed.
+///
+/// Only signatures from keys listed in [`SignaturePolicy::keys`] count. At least `threshold`
+/// distinct keys must sign, and every role in `required_roles` needs a signature from a key with
+/// that role. The threshold must lie between one and the number of trusted keys, so a policy can
+/// neither pass an unsigned pack nor demand more signers than it trusts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SignaturePolicy {
+    /// Minimum number of distinct trusted keys with a valid signature.
+    #[cfg_attr(feature = "schemars", schemars(range(min = 1)))]
+    pub threshold: u32,
+    /// Roles that must each be covered by at least one valid signature.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeSet::is_empty")
+    )]
+    pub required_roles: BTreeSet<SignerRole>,
+    /// Trusted key identifiers and the role each acts for.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub keys: BTreeMap<String, SignerRole>,
+}
+
+impl SignaturePolicy {
+    /// Creates an `m`-of-`n` policy over `keys` with no required roles.
+    ///
+    /// Fails when `threshold` is zero or exceeds the number of distinct keys.
+    pub fn threshold(
+        threshold: u32,
+        keys: impl IntoIterator<Item = (String, SignerRole)>,
+    ) -> GResult<Self> {
+        let policy = Self {
+            threshold,
+            required_roles: BTreeSet::new(),
+            keys: keys.into_iter().collect(),
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// Checks that the threshold can be met and is not trivially met.
+    pub fn validate(&self) -> Result<(), SignaturePolicyError> {
+        let keys = u32::try_from(self.keys.len()).unwrap_or(u32::MAX);
+        if self.threshold == 0 || self.threshold > keys {
+            return Err(SignaturePolicyError::InvalidThreshold {
+                threshold: self.threshold,
+                keys,
+            });
+        }
+        Ok(())
+    }
+
+    /// Adds a role that must sign.
+    pub fn require_role(mut self, role: SignerRole) -> Self {
+        self.required_roles.insert(role);
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SignaturePolicy {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            threshold: u32,
+            #[serde(default)]
+            required_roles: BTreeSet<SignerRole>,
+            #[serde(default)]
+            keys: BTreeMap<String, SignerRole>,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        let policy = Self {
+            threshold: raw.threshold,
+            required_roles: raw.required_roles,
+            keys: raw.keys,
+        };
+        policy.validate().map_err(serde::de::Error::custom)?;
+        Ok(policy)
+    }
+}
+
+/// Checks detached signatures against the signed content and trusted key material.
+///
+/// Implementations know what was signed (usually the pack digest) and hold the public keys.
+pub trait SignatureVerifier {
+    /// Returns `Ok(true)` when `signature` is valid, `Ok(false)` when it does not match, and an
+    /// error when the key cannot be resolved.
+    fn verify(&self, signature: &Signature) -> GResult<bool>;
+}
+
+/// Signature list rejected by a [`SignaturePolicy`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SignaturePolicyError {
+    /// The policy threshold is zero or larger than the number of trusted keys.
+    #[error("policy threshold {threshold} must be between 1 and the {keys} trusted key(s)")]
+    InvalidThreshold {
+        /// Configured threshold.
+        threshold: u32,
+        /// Number of trusted keys.
+        keys: u32,
+    },
+    /// A trusted key signed, but its signature does not match.
+    #[error("signature from key {key_id} is invalid")]
+    InvalidSignature {
+        /// Key that produced the invalid signature.
+        key_id: String,
+    },
+    /// Too few trusted keys signed.
+    #[error("{valid} trusted signature(s) present, policy requires {threshold}")]
+    ThresholdNotMet {
+        /// Distinct trusted keys with a valid signature.
+        valid: u32,
+        /// Required number of keys.
+        threshold: u32,
+    },
+    /// No valid signature from a key with a required role.
+    #[error("no valid signature from a {role} key")]
+    MissingRole {
+        /// Role without a signature.
+        role: SignerRole,
+    },
+}
+
+impl From<SignaturePolicyError> for GreenticError {
+    fn from(err: SignaturePolicyError) -> Self {
+        let code = match err {
+            SignaturePolicyError::InvalidThreshold { .. } => ErrorCode::InvalidInput,
+            _ => ErrorCode::Unauthenticated,
+        };
+        let error = GreenticError::new(code, err.to_string());
+        match err {
+            SignaturePolicyError::InvalidThreshold { threshold, keys } => error
+                .with_field("threshold", threshold)
+                .with_field("keys", keys),
+            SignaturePolicyError::InvalidSignature { key_id } => error.with_field("key_id", key_id),
+            SignaturePolicyError::ThresholdNotMet { valid, threshold } => error
+                .with_field("valid", valid)
+                .with_field("threshold", threshold),
+            SignaturePolicyError::MissingRole { role } => {
+                error.with_field("role", format!("{role}"))
+            }
+        }
+    }
+}
+
+/// Verifies `signatures` against `policy` and returns the trusted key ids that signed.
+///
+/// Signatures from keys outside the policy are skipped without calling the verifier. An invalid
+/// signature from a trusted key fails the whole check, since it points at tampering rather than a
+/// missing signer. A policy whose threshold fails [`SignaturePolicy::validate`] rejects every
+/// pack.
+pub fn verify_signature_policy(
+    signatures: &[Signature],
+    policy: &SignaturePolicy,
+    verifier: &impl SignatureVerifier,
+) -> GResult<BTreeSet<String>> {
+    policy.validate()?;
+    let mut signers = BTreeSet::new();
+    let mut roles = BTreeSet::new();
+    for signature in signatures {
+        let Some(role) = policy.keys.get(&signature.key_id) else {
+            continue;
+        };
+        if !verifier.verify(signature)? {
+            return Err(SignaturePolicyError::InvalidSignature {
+                key_id: signature.key_id.clone(),
+            }
+            .into());
+        }
+        signers.insert(signature.key_id.clone());
+        roles.insert(*role);
+    }
+    let valid = u32::try_from(signers.len()).unwrap_or(u32::MAX);
+    if valid < policy.threshold {
+        return Err(SignaturePolicyError::ThresholdNotMet {
+            valid,
+            threshold: policy.threshold,
+        }
+        .into());
+    }
+    if let Some(role) = policy.required_roles.difference(&roles).next() {
+        return Err(SignaturePolicyError::MissingRole { role: *role }.into());
+    }
+    Ok(signers)
+}
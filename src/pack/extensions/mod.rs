@@ -2,3 +2,4 @@
 
 pub mod component_manifests;
 pub mod component_sources;
+pub mod pack_tests;
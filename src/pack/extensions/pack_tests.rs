@@ -0,0 +1,478 @@
+//! Extension payload describing tests shipped with a pack.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::redact::{parse_segments, select};
+use crate::validate::{Diagnostic, Severity, core_diagnostic};
+use crate::{DurationMs, FlowId, MockComponentSpec, NodeId, NodeStatus, PackManifest, RunStatus};
+
+/// Pack extension identifier for pack test metadata (v1).
+pub const EXT_PACK_TESTS_V1: &str = "greentic.pack.tests@v1";
+
+/// Pack tests extension payload (v1).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PackTestsV1 {
+    /// Schema version for this payload.
+    pub schema_version: u32,
+    /// Test specifications.
+    pub tests: Vec<PackTestSpec>,
+}
+
+impl PackTestsV1 {
+    /// Creates a new pack tests payload.
+    pub fn new(tests: Vec<PackTestSpec>) -> Self {
+        Self {
+            schema_version: 1,
+            tests,
+        }
+    }
+
+    /// Validates the schema version for forward-compatible decoders.
+    pub fn validate_schema_version(&self) -> Result<(), PackTestsError> {
+        if self.schema_version == 1 {
+            Ok(())
+        } else {
+            Err(PackTestsError::UnsupportedSchemaVersion(
+                self.schema_version,
+            ))
+        }
+    }
+
+    /// Converts the payload to an extension value suitable for `ExtensionInline::Other`.
+    #[cfg(feature = "serde")]
+    pub fn to_extension_value(&self) -> Result<serde_json::Value, PackTestsError> {
+        serde_json::to_value(self).map_err(|err| PackTestsError::Serialize(err.to_string()))
+    }
+
+    /// Parses the payload from an extension value.
+    #[cfg(feature = "serde")]
+    pub fn from_extension_value(value: &serde_json::Value) -> Result<Self, PackTestsError> {
+        let decoded: Self = serde_json::from_value(value.clone())
+            .map_err(|err| PackTestsError::Deserialize(err.to_string()))?;
+        decoded.validate_schema_version()?;
+        Ok(decoded)
+    }
+
+    /// Checks the tests against `manifest` and returns diagnostics.
+    ///
+    /// Test names must be unique and non-empty, every test must target a flow declared in the
+    /// manifest, fixture paths must stay inside the pack, mock capabilities must be named, and
+    /// each mocked component must appear once with well-formed operations.
+    pub fn validate(&self, manifest: &PackManifest) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut names = BTreeSet::new();
+        for (index, test) in self.tests.iter().enumerate() {
+            let label = if test.name.trim().is_empty() {
+                diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "PACK_TEST_NAME_MISSING",
+                    "Pack test name is required.",
+                    Some(format!("tests.{index}.name")),
+                    Some("Give each pack test a non-empty name.".to_owned()),
+                ));
+                index.to_string()
+            } else {
+                if !names.insert(test.name.as_str()) {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_TEST_NAME_DUPLICATE",
+                        "Duplicate pack test names are not allowed.",
+                        Some(format!("tests.{}", test.name)),
+                        Some("Ensure each test name is unique within the pack.".to_owned()),
+                    ));
+                }
+                test.name.clone()
+            };
+
+            let flow = manifest.flows.iter().find(|entry| entry.id == test.flow_id);
+            if flow.is_none() {
+                diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "PACK_TEST_FLOW_MISSING",
+                    "Pack test targets a flow that is not present in the pack manifest.",
+                    Some(format!("tests.{label}.flow_id")),
+                    Some("Point the test at a flow declared in manifest.flows.".to_owned()),
+                ));
+            }
+
+            for (position, assertion) in test.assertions.iter().enumerate() {
+                let path = format!("tests.{label}.assertions.{position}");
+                if flow.is_some_and(|entry| !entry.flow.nodes.contains_key(&assertion.node_id)) {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_TEST_ASSERTION_NODE_MISSING",
+                        &format!(
+                            "Transcript assertion references unknown node '{}'.",
+                            assertion.node_id
+                        ),
+                        Some(format!("{path}.node_id")),
+                        None,
+                    ));
+                }
+                if let Some(Err(reason)) = assertion.output.as_ref().map(OutputMatcher::check) {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_TEST_ASSERTION_MATCHER_INVALID",
+                        &reason,
+                        Some(format!("{path}.output")),
+                        None,
+                    ));
+                }
+            }
+
+            let mut fixture_names = BTreeSet::new();
+            for fixture in &test.fixtures {
+                if !fixture_names.insert(fixture.name.as_str()) {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_TEST_FIXTURE_DUPLICATE",
+                        "Duplicate fixture names are not allowed within a test.",
+                        Some(format!("tests.{label}.fixtures.{}", fixture.name)),
+                        None,
+                    ));
+                }
+                if !is_pack_relative(&fixture.path) {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_TEST_FIXTURE_PATH_INVALID",
+                        "Fixture paths must be relative to the pack root and must not contain '..'.",
+                        Some(format!("tests.{label}.fixtures.{}.path", fixture.name)),
+                        Some("Use a pack-relative path such as `tests/fixtures/input.json`.".to_owned()),
+                    ));
+                }
+            }
+
+            if test
+                .mock_capabilities
+                .iter()
+                .any(|capability| capability.trim().is_empty())
+            {
+                diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "PACK_TEST_MOCK_CAPABILITY_EMPTY",
+                    "Mock capability names must not be empty.",
+                    Some(format!("tests.{label}.mock_capabilities")),
+                    None,
+                ));
+            }
+
+            let mut mocked = BTreeSet::new();
+            for mock in &test.mocks {
+                let path = format!("tests.{label}.mocks.{}", mock.component_id);
+                if !mocked.insert(&mock.component_id) {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_TEST_MOCK_DUPLICATE",
+                        "A component may only be mocked once per test.",
+                        Some(path.clone()),
+                        None,
+                    ));
+                }
+                if let Err(err) = mock.validate() {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_TEST_MOCK_INVALID",
+                        &err.message,
+                        Some(path),
+                        None,
+                    ));
+                }
+            }
+
+            if test.timeout == Some(DurationMs::ZERO) {
+                diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "PACK_TEST_TIMEOUT_ZERO",
+                    "Pack test timeout is zero, so the test can never pass.",
+                    Some(format!("tests.{label}.timeout")),
+                    Some("Remove the timeout or set a positive duration.".to_owned()),
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+fn is_pack_relative(path: &str) -> bool {
+    !path.trim().is_empty()
+        && !path.starts_with('/')
+        && !path.starts_with('\\')
+        && !path.contains(':')
+        && path.split(['/', '\\']).all(|segment| segment != "..")
+}
+
+/// Test executed against a flow of the pack.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PackTestSpec {
+    /// Unique test name within the pack.
+    pub name: String,
+    /// Flow the test runs.
+    pub flow_id: FlowId,
+    /// Optional description shown by test runners.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+    /// Input payload passed to the flow entrypoint.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Value::is_null")
+    )]
+    pub input: Value,
+    /// Files the test needs, resolved relative to the pack root.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub fixtures: Vec<TestFixture>,
+    /// Expected result of the run.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub expected: ExpectedOutcome,
+    /// Per-node expectations checked against the run transcript.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub assertions: Vec<TranscriptAssertion>,
+    /// Capabilities the runner must mock (for example `http` or `secrets`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub mock_capabilities: Vec<String>,
+    /// Components replaced by stubs for this test.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub mocks: Vec<MockComponentSpec>,
+    /// Maximum run time before the test fails.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub timeout: Option<DurationMs>,
+    /// Tags used to select subsets of tests.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tags: Vec<String>,
+}
+
+impl PackTestSpec {
+    /// Creates a test expecting `flow_id` to succeed.
+    pub fn new(name: impl Into<String>, flow_id: FlowId) -> Self {
+        Self {
+            name: name.into(),
+            flow_id,
+            description: None,
+            input: Value::Null,
+            fixtures: Vec::new(),
+            expected: ExpectedOutcome::default(),
+            assertions: Vec::new(),
+            mock_capabilities: Vec::new(),
+            mocks: Vec::new(),
+            timeout: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+/// File made available to a pack test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TestFixture {
+    /// Name the test refers to the fixture by.
+    pub name: String,
+    /// Pack-relative path to the fixture file.
+    pub path: String,
+    /// Optional media type (for example `application/json`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub content_type: Option<String>,
+}
+
+/// Expected result of a pack test run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpectedOutcome {
+    /// Expected final run status.
+    pub status: RunStatus,
+    /// Expected flow output; not compared when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub output: Option<Value>,
+    /// Expected error code for failing runs.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub error_code: Option<String>,
+}
+
+impl Default for ExpectedOutcome {
+    fn default() -> Self {
+        Self {
+            status: RunStatus::Success,
+            output: None,
+            error_code: None,
+        }
+    }
+}
+
+/// Expectation on a single node of a golden transcript.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TranscriptAssertion {
+    /// Node the assertion applies to.
+    pub node_id: NodeId,
+    /// Expected node status; not compared when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub status: Option<NodeStatus>,
+    /// Matcher applied to the node output; not compared when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub output: Option<OutputMatcher>,
+}
+
+impl TranscriptAssertion {
+    /// Creates an assertion that only expects `node_id` to have run successfully.
+    pub fn node_ok(node_id: NodeId) -> Self {
+        Self {
+            node_id,
+            status: Some(NodeStatus::Ok),
+            output: None,
+        }
+    }
+
+    /// Evaluates the assertion against a node's recorded status and output.
+    ///
+    /// Returns `None` when the outcome depends on a [`OutputMatcher::Regex`], which the test
+    /// harness evaluates with its own regex engine.
+    pub fn evaluate(&self, status: NodeStatus, output: &Value) -> Option<bool> {
+        if self.status.is_some_and(|expected| expected != status) {
+            return Some(false);
+        }
+        match &self.output {
+            Some(matcher) => matcher.matches(output),
+            None => Some(true),
+        }
+    }
+}
+
+/// How a node output is compared.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum OutputMatcher {
+    /// Output must equal `value`.
+    Exact {
+        /// Expected output.
+        value: Value,
+    },
+    /// Values selected by `path` must exist and, when `equals` is set, all equal it.
+    ///
+    /// Supports the same JSONPath subset as redaction paths: member access, indices, and
+    /// wildcards.
+    JsonPath {
+        /// JSONPath expression (for example `$.reply.text`).
+        path: String,
+        /// Value every match must equal.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        equals: Option<Value>,
+    },
+    /// String output (or the string at `path`) must match `pattern`.
+    Regex {
+        /// Regular expression in the syntax of the Rust `regex` crate.
+        pattern: String,
+        /// Optional JSONPath selecting the string to match.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        path: Option<String>,
+    },
+}
+
+impl OutputMatcher {
+    /// Returns whether `output` matches, or `None` for regex matchers.
+    ///
+    /// Unparseable JSONPath expressions never match; [`PackTestsV1::validate`] reports them.
+    pub fn matches(&self, output: &Value) -> Option<bool> {
+        match self {
+            Self::Exact { value } => Some(value == output),
+            Self::JsonPath { path, equals } => {
+                let segments = match parse_segments(path) {
+                    Ok(segments) => segments,
+                    Err(_) => return Some(false),
+                };
+                let selected = select(output, &segments);
+                Some(
+                    !selected.is_empty()
+                        && equals
+                            .as_ref()
+                            .is_none_or(|expected| selected.iter().all(|found| *found == expected)),
+                )
+            }
+            Self::Regex { .. } => None,
+        }
+    }
+
+    /// Ensures paths parse and regex patterns are non-empty.
+    fn check(&self) -> Result<(), String> {
+        let path = match self {
+            Self::Exact { .. } => None,
+            Self::JsonPath { path, .. } => Some(path),
+            Self::Regex { pattern, path } => {
+                if pattern.is_empty() {
+                    return Err("Regex output matcher requires a non-empty pattern.".to_owned());
+                }
+                path.as_ref()
+            }
+        };
+        match path.map(|path| parse_segments(path)) {
+            Some(Err(err)) => Err(err.message),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Errors produced while encoding or decoding pack tests payloads.
+#[derive(Debug, thiserror::Error)]
+pub enum PackTestsError {
+    /// Serialization failed.
+    #[error("pack tests serialize failed: {0}")]
+    Serialize(String),
+    /// Deserialization failed.
+    #[error("pack tests deserialize failed: {0}")]
+    Deserialize(String),
+    /// Unsupported schema version.
+    #[error("unsupported pack tests schema_version {0}")]
+    UnsupportedSchemaVersion(u32),
+    /// Extension payload used an unexpected inline type.
+    #[error("pack tests extension inline payload has unexpected type")]
+    UnexpectedInline,
+}
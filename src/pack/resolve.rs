@@ -0,0 +1,338 @@
+//! Dependency resolution for pack manifests.
+//!
+//! [`resolve_dependencies`] walks the transitive [`PackDependency`] graph of a [`PackManifest`],
+//! selects the highest available version satisfying every [`SemverReq`](crate::SemverReq) placed
+//! on each pack, and returns a deterministic install order with dependencies before dependents.
+//!
+//! A [`PackRef`] only records where a pack lives, so candidates are described with
+//! [`AvailablePack`], which adds the pack identifier and the dependencies it declares.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{PackDependency, PackId, PackManifest, PackRef, SemverReq};
+
+/// Pack version that can be selected during resolution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct AvailablePack {
+    /// Identifier of the pack.
+    pub pack_id: PackId,
+    /// Location and version of the pack.
+    pub pack_ref: PackRef,
+    /// Dependencies declared by this version of the pack.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub dependencies: Vec<PackDependency>,
+}
+
+impl AvailablePack {
+    /// Creates a candidate without dependencies.
+    pub fn new(pack_id: PackId, pack_ref: PackRef) -> Self {
+        Self {
+            pack_id,
+            pack_ref,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Creates a candidate from a manifest published at `pack_ref`.
+    pub fn from_manifest(manifest: &PackManifest, pack_ref: PackRef) -> Self {
+        Self {
+            pack_id: manifest.pack_id.clone(),
+            pack_ref,
+            dependencies: manifest.dependencies.clone(),
+        }
+    }
+
+    /// Adds a dependency.
+    pub fn with_dependency(mut self, dependency: PackDependency) -> Self {
+        self.dependencies.push(dependency);
+        self
+    }
+}
+
+/// Pack selected for installation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ResolvedPack {
+    /// Identifier of the pack.
+    pub pack_id: PackId,
+    /// Selected version.
+    pub pack_ref: PackRef,
+    /// Packs that depend on this one, sorted.
+    pub required_by: Vec<PackId>,
+}
+
+/// Result of a successful resolution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ResolutionPlan {
+    /// Pack whose dependencies were resolved.
+    pub root: PackId,
+    /// Packs to install, dependencies before dependents; the root is not included.
+    pub install_order: Vec<ResolvedPack>,
+}
+
+impl ResolutionPlan {
+    /// Returns the selected version of `pack_id`, when it is part of the plan.
+    pub fn get(&self, pack_id: &PackId) -> Option<&ResolvedPack> {
+        self.install_order
+            .iter()
+            .find(|resolved| &resolved.pack_id == pack_id)
+    }
+}
+
+/// Requirement placed on a pack by one of its dependents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Requirement {
+    /// Pack declaring the dependency.
+    pub required_by: PackId,
+    /// Accepted versions.
+    pub version_req: SemverReq,
+}
+
+/// Errors produced by [`resolve_dependencies`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ResolutionError {
+    /// No available version satisfies a single requirement.
+    #[error("no available version of {pack_id} matches {version_req} (required by {required_by})")]
+    Missing {
+        /// Pack that could not be found.
+        pack_id: PackId,
+        /// Requirement that no candidate satisfies.
+        version_req: SemverReq,
+        /// Pack declaring the requirement.
+        required_by: PackId,
+    },
+    /// Candidates exist for each requirement, but no version satisfies all of them.
+    #[error("conflicting requirements on {pack_id}: {}", format_requirements(.requirements))]
+    Conflict {
+        /// Pack with incompatible requirements.
+        pack_id: PackId,
+        /// Every requirement placed on the pack.
+        requirements: Vec<Requirement>,
+    },
+    /// Dependencies form a cycle; the path starts and ends with the same pack.
+    #[error("dependency cycle: {}", format_cycle(.path))]
+    Cycle {
+        /// Packs along the cycle.
+        path: Vec<PackId>,
+    },
+}
+
+fn format_requirements(requirements: &[Requirement]) -> alloc::string::String {
+    requirements
+        .iter()
+        .map(|requirement| {
+            alloc::format!(
+                "{} from {}",
+                requirement.version_req,
+                requirement.required_by
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_cycle(path: &[PackId]) -> alloc::string::String {
+    path.iter()
+        .map(PackId::as_str)
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Resolves the transitive dependencies of `manifest` against `available` packs.
+///
+/// Each pack is pinned to the highest version satisfying every requirement placed on it by the
+/// root or by other selected packs; ties between identical versions keep the first candidate in
+/// `available`. Selection is repeated until it stops changing, then packs are ordered so that
+/// dependencies precede their dependents, breaking ties by pack identifier.
+///
+/// The resolver does not backtrack: if the highest version of a dependent imposes a requirement
+/// that cannot be met, the result is [`ResolutionError::Conflict`] even when an older version of
+/// that dependent would have resolved.
+pub fn resolve_dependencies(
+    manifest: &PackManifest,
+    available: &[AvailablePack],
+) -> Result<ResolutionPlan, ResolutionError> {
+    let root = &manifest.pack_id;
+    let mut candidates: BTreeMap<&PackId, Vec<&AvailablePack>> = BTreeMap::new();
+    for pack in available {
+        candidates.entry(&pack.pack_id).or_default().push(pack);
+    }
+    for versions in candidates.values_mut() {
+        // Stable sort keeps the caller's order among identical versions.
+        versions.sort_by(|a, b| b.pack_ref.version.cmp(&a.pack_ref.version));
+    }
+
+    let mut selected: BTreeMap<PackId, &AvailablePack> = BTreeMap::new();
+    // Selections that keep changing after this many rounds are reported as a conflict.
+    for _ in 0..=available.len() + 1 {
+        let requirements = collect_requirements(manifest, &selected);
+        let mut next = BTreeMap::new();
+        for (pack_id, reqs) in &requirements {
+            if pack_id == root {
+                continue;
+            }
+            next.insert(pack_id.clone(), select(pack_id, reqs, &candidates)?);
+        }
+        if next == selected {
+            return Ok(ResolutionPlan {
+                root: root.clone(),
+                install_order: install_order(manifest, &selected, &requirements)?,
+            });
+        }
+        selected = next;
+    }
+    let requirements = collect_requirements(manifest, &selected);
+    let (pack_id, requirements) = requirements
+        .into_iter()
+        .find(|(pack_id, _)| pack_id != root)
+        .unwrap_or_else(|| (root.clone(), Vec::new()));
+    Err(ResolutionError::Conflict {
+        pack_id,
+        requirements,
+    })
+}
+
+fn collect_requirements(
+    manifest: &PackManifest,
+    selected: &BTreeMap<PackId, &AvailablePack>,
+) -> BTreeMap<PackId, Vec<Requirement>> {
+    let mut requirements: BTreeMap<PackId, Vec<Requirement>> = BTreeMap::new();
+    let dependents = core::iter::once((&manifest.pack_id, &manifest.dependencies)).chain(
+        selected
+            .values()
+            .map(|pack| (&pack.pack_id, &pack.dependencies)),
+    );
+    for (dependent, dependencies) in dependents {
+        for dependency in dependencies {
+            requirements
+                .entry(dependency.pack_id.clone())
+                .or_default()
+                .push(Requirement {
+                    required_by: dependent.clone(),
+                    version_req: dependency.version_req.clone(),
+                });
+        }
+    }
+    requirements
+}
+
+fn select<'a>(
+    pack_id: &PackId,
+    requirements: &[Requirement],
+    candidates: &BTreeMap<&PackId, Vec<&'a AvailablePack>>,
+) -> Result<&'a AvailablePack, ResolutionError> {
+    let versions = candidates.get(pack_id).map(Vec::as_slice).unwrap_or(&[]);
+    let parsed: Vec<_> = requirements
+        .iter()
+        .map(|requirement| requirement.version_req.to_version_req())
+        .collect();
+    if let Some(pack) = versions
+        .iter()
+        .find(|pack| parsed.iter().all(|req| req.matches(&pack.pack_ref.version)))
+    {
+        return Ok(pack);
+    }
+    for (requirement, req) in requirements.iter().zip(&parsed) {
+        if !versions
+            .iter()
+            .any(|pack| req.matches(&pack.pack_ref.version))
+        {
+            return Err(ResolutionError::Missing {
+                pack_id: pack_id.clone(),
+                version_req: requirement.version_req.clone(),
+                required_by: requirement.required_by.clone(),
+            });
+        }
+    }
+    Err(ResolutionError::Conflict {
+        pack_id: pack_id.clone(),
+        requirements: requirements.to_vec(),
+    })
+}
+
+fn install_order(
+    manifest: &PackManifest,
+    selected: &BTreeMap<PackId, &AvailablePack>,
+    requirements: &BTreeMap<PackId, Vec<Requirement>>,
+) -> Result<Vec<ResolvedPack>, ResolutionError> {
+    let mut order = Vec::new();
+    let mut done = BTreeSet::new();
+    let mut path = Vec::new();
+    visit(
+        &manifest.pack_id,
+        &manifest.dependencies,
+        selected,
+        &mut path,
+        &mut done,
+        &mut order,
+    )?;
+    Ok(order
+        .into_iter()
+        .filter(|pack_id| pack_id != &manifest.pack_id)
+        .map(|pack_id| {
+            let mut required_by: Vec<PackId> = requirements
+                .get(&pack_id)
+                .into_iter()
+                .flatten()
+                .map(|requirement| requirement.required_by.clone())
+                .collect();
+            required_by.sort();
+            required_by.dedup();
+            ResolvedPack {
+                pack_ref: selected[&pack_id].pack_ref.clone(),
+                pack_id,
+                required_by,
+            }
+        })
+        .collect())
+}
+
+/// Depth-first post-order traversal; `path` holds the packs currently being visited.
+fn visit(
+    pack_id: &PackId,
+    dependencies: &[PackDependency],
+    selected: &BTreeMap<PackId, &AvailablePack>,
+    path: &mut Vec<PackId>,
+    done: &mut BTreeSet<PackId>,
+    order: &mut Vec<PackId>,
+) -> Result<(), ResolutionError> {
+    if let Some(start) = path.iter().position(|visiting| visiting == pack_id) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(pack_id.clone());
+        return Err(ResolutionError::Cycle { path: cycle });
+    }
+    if done.contains(pack_id) {
+        return Ok(());
+    }
+    path.push(pack_id.clone());
+    let children: BTreeSet<&PackId> = dependencies
+        .iter()
+        .map(|dependency| &dependency.pack_id)
+        .collect();
+    for child in children {
+        // The root is never selected, so reaching it again is caught as a cycle above.
+        let grandchildren = selected
+            .get(child)
+            .map_or(&[][..], |pack| pack.dependencies.as_slice());
+        visit(child, grandchildren, selected, path, done, order)?;
+    }
+    path.pop();
+    done.insert(pack_id.clone());
+    order.push(pack_id.clone());
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! Canonical pack manifest (.gtpack) representation embedding flows and components.
 
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -9,11 +10,18 @@ use semver::Version;
 use crate::pack::extensions::component_sources::{
     ComponentSourcesError, ComponentSourcesV1, EXT_COMPONENT_SOURCES_V1,
 };
+use crate::pack::extensions::pack_tests::{EXT_PACK_TESTS_V1, PackTestsError, PackTestsV1};
+use crate::validate::{Severity, ValidationReport, core_diagnostic};
 use crate::{
-    ComponentManifest, Flow, FlowId, FlowKind, PROVIDER_EXTENSION_ID, PackId,
-    ProviderExtensionInline, SecretRequirement, SemverReq, Signature,
+    ComponentId, ComponentManifest, Flow, FlowId, FlowKind, GResult, PROVIDER_EXTENSION_ID, PackId,
+    ProviderExtensionInline, ResourceHints, SecretKey, SecretRequirement, SemverReq, Signature,
+    SignaturePolicy, SignatureVerifier, verify_signature_policy,
 };
 
+#[cfg(all(feature = "std", feature = "serde"))]
+use crate::HashDigest;
+#[cfg(all(feature = "std", feature = "serde"))]
+use crate::cbor::canonical::CanonicalError;
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
@@ -177,6 +185,17 @@ pub struct PackSignatures {
     pub signatures: Vec<Signature>,
 }
 
+impl PackSignatures {
+    /// Verifies the bundle against `policy`; see [`verify_signature_policy`].
+    pub fn verify_policy(
+        &self,
+        policy: &SignaturePolicy,
+        verifier: &impl SignatureVerifier,
+    ) -> GResult<BTreeSet<String>> {
+        verify_signature_policy(&self.signatures, policy, verifier)
+    }
+}
+
 /// Optional bootstrap/install hints for platform-managed packs.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -262,6 +281,16 @@ pub struct ExtensionRef {
 }
 
 impl PackManifest {
+    /// Blake3 digest of the manifest's canonical CBOR encoding.
+    ///
+    /// Map keys are sorted during canonicalisation, so the digest is independent of field and
+    /// map insertion order; flow nodes keep their declaration order (see [`Flow::content_digest`]).
+    /// Floats are encoded in their shortest exact IEEE 754 width.
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub fn content_digest(&self) -> Result<HashDigest, CanonicalError> {
+        crate::cbor::canonical::StableHash::stable_hash(self)
+    }
+
     /// Returns the inline provider extension payload if present.
     pub fn provider_extension_inline(&self) -> Option<&ProviderExtensionInline> {
         self.extensions
@@ -356,4 +385,186 @@ impl PackManifest {
         );
         Ok(())
     }
+
+    /// Returns the pack tests extension payload if present.
+    #[cfg(feature = "serde")]
+    pub fn get_pack_tests_v1(&self) -> Result<Option<PackTestsV1>, PackTestsError> {
+        let extension = self
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get(EXT_PACK_TESTS_V1));
+        let inline = match extension.and_then(|entry| entry.inline.as_ref()) {
+            Some(ExtensionInline::Other(value)) => value,
+            Some(_) => return Err(PackTestsError::UnexpectedInline),
+            None => return Ok(None),
+        };
+        PackTestsV1::from_extension_value(inline).map(Some)
+    }
+
+    /// Sets the pack tests extension payload.
+    #[cfg(feature = "serde")]
+    pub fn set_pack_tests_v1(&mut self, tests: PackTestsV1) -> Result<(), PackTestsError> {
+        tests.validate_schema_version()?;
+        let inline = tests.to_extension_value()?;
+        let extensions = self.extensions.get_or_insert_with(BTreeMap::new);
+        extensions.insert(
+            EXT_PACK_TESTS_V1.to_string(),
+            ExtensionRef {
+                kind: EXT_PACK_TESTS_V1.to_string(),
+                version: "1.0.0".to_string(),
+                digest: None,
+                location: None,
+                inline: Some(ExtensionInline::Other(inline)),
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Tenant or environment overrides layered on top of a base [`PackManifest`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PackManifestOverlay {
+    /// Flows removed from the manifest.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub disabled_flows: Vec<FlowId>,
+    /// Pack-level secret requirements added, or replaced when the key already exists.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub secret_requirements: Vec<SecretRequirement>,
+    /// Pack-level secret requirement keys removed from the manifest.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub removed_secret_requirements: Vec<SecretKey>,
+    /// Resource hint overrides per component; only the fields set in the overlay are replaced.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub component_resources: BTreeMap<ComponentId, ResourceHints>,
+}
+
+impl PackManifestOverlay {
+    /// Returns `true` when the overlay changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.disabled_flows.is_empty()
+            && self.secret_requirements.is_empty()
+            && self.removed_secret_requirements.is_empty()
+            && self.component_resources.is_empty()
+    }
+}
+
+impl PackManifest {
+    /// Applies a tenant/environment overlay in place.
+    ///
+    /// Merge order is: removed secret requirements, added/replaced secret requirements (matched
+    /// by key), resource hint overrides (field by field), then disabled flows. The overlay is
+    /// checked first; when the returned report contains errors (for example an unknown flow or
+    /// component) the manifest is left untouched.
+    pub fn apply_overlay(&mut self, overlay: &PackManifestOverlay) -> ValidationReport {
+        let mut report = ValidationReport {
+            pack_id: Some(self.pack_id.clone()),
+            pack_version: Some(self.version.clone()),
+            diagnostics: Vec::new(),
+        };
+
+        for flow_id in &overlay.disabled_flows {
+            if !self.flows.iter().any(|entry| &entry.id == flow_id) {
+                report.diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "PACK_OVERLAY_UNKNOWN_FLOW",
+                    &format!("overlay disables unknown flow '{flow_id}'"),
+                    Some(format!("overlay.disabled_flows.{flow_id}")),
+                    None,
+                ));
+            } else if self.bootstrap.as_ref().is_some_and(|bootstrap| {
+                [&bootstrap.install_flow, &bootstrap.upgrade_flow]
+                    .into_iter()
+                    .any(|flow| flow.as_deref() == Some(flow_id.as_str()))
+            }) {
+                report.diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "PACK_OVERLAY_DISABLES_BOOTSTRAP_FLOW",
+                    &format!("overlay disables bootstrap flow '{flow_id}'"),
+                    Some(format!("overlay.disabled_flows.{flow_id}")),
+                    Some("bootstrap will fail unless the flow is re-enabled".into()),
+                ));
+            }
+        }
+        for component_id in overlay.component_resources.keys() {
+            if !self
+                .components
+                .iter()
+                .any(|component| &component.id == component_id)
+            {
+                report.diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "PACK_OVERLAY_UNKNOWN_COMPONENT",
+                    &format!("overlay overrides resources of unknown component '{component_id}'"),
+                    Some(format!("overlay.component_resources.{component_id}")),
+                    None,
+                ));
+            }
+        }
+        for key in &overlay.removed_secret_requirements {
+            if !self
+                .secret_requirements
+                .iter()
+                .any(|requirement| &requirement.key == key)
+            {
+                report.diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "PACK_OVERLAY_UNKNOWN_SECRET",
+                    &format!(
+                        "overlay removes undeclared secret requirement '{}'",
+                        key.as_str()
+                    ),
+                    Some(format!(
+                        "overlay.removed_secret_requirements.{}",
+                        key.as_str()
+                    )),
+                    None,
+                ));
+            }
+        }
+        if report.has_errors() {
+            return report;
+        }
+
+        self.secret_requirements.retain(|requirement| {
+            !overlay
+                .removed_secret_requirements
+                .contains(&requirement.key)
+        });
+        for requirement in &overlay.secret_requirements {
+            match self
+                .secret_requirements
+                .iter_mut()
+                .find(|existing| existing.key == requirement.key)
+            {
+                Some(existing) => *existing = requirement.clone(),
+                None => self.secret_requirements.push(requirement.clone()),
+            }
+        }
+        for component in &mut self.components {
+            if let Some(hints) = overlay.component_resources.get(&component.id) {
+                let resources = &mut component.resources;
+                resources.cpu_millis = hints.cpu_millis.or(resources.cpu_millis);
+                resources.memory = hints.memory.or(resources.memory);
+                resources.average_latency = hints.average_latency.or(resources.average_latency);
+                resources.fuel = hints.fuel.or(resources.fuel);
+            }
+        }
+        self.flows
+            .retain(|entry| !overlay.disabled_flows.contains(&entry.id));
+        report
+    }
 }
@@ -1,4 +1,4 @@
-//! Network policy primitives.
+//! Network policy primitives and policy decisions.
 
 use alloc::string::String;
 use alloc::vec::Vec;
@@ -7,6 +7,9 @@ use alloc::vec::Vec;
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{DurationMs, RedactionPath};
 
 /// Network protocols supported by allow lists.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -108,6 +111,130 @@ pub struct PolicyDecision {
     pub allow: Option<bool>,
     /// Legacy single reason (retained for backward compatibility).
     pub reason: Option<String>,
+    /// Conditions the enforcement point must apply for an allow to hold.
+    pub obligations: Vec<PolicyObligation>,
+}
+
+impl PolicyDecision {
+    /// Returns `true` for an allow that carries obligations.
+    pub fn is_conditional(&self) -> bool {
+        self.status == PolicyDecisionStatus::Allow && !self.obligations.is_empty()
+    }
+
+    /// Obligations `capabilities` cannot enforce.
+    pub fn unsatisfied_obligations<'a>(
+        &'a self,
+        capabilities: &ObligationCapabilities,
+    ) -> Vec<&'a PolicyObligation> {
+        self.obligations
+            .iter()
+            .filter(|obligation| !capabilities.supports(obligation))
+            .collect()
+    }
+
+    /// Returns `true` when `capabilities` can enforce every obligation.
+    pub fn is_satisfiable_by(&self, capabilities: &ObligationCapabilities) -> bool {
+        self.obligations
+            .iter()
+            .all(|obligation| capabilities.supports(obligation))
+    }
+
+    /// Status to act on: an allow whose obligations cannot be enforced is treated as a deny.
+    pub fn effective_status(&self, capabilities: &ObligationCapabilities) -> PolicyDecisionStatus {
+        match self.status {
+            PolicyDecisionStatus::Allow if !self.is_satisfiable_by(capabilities) => {
+                PolicyDecisionStatus::Deny
+            }
+            ref status => status.clone(),
+        }
+    }
+
+    /// Shortest time-to-live among the obligations.
+    pub fn ttl(&self) -> Option<DurationMs> {
+        self.obligations
+            .iter()
+            .filter_map(|obligation| match obligation {
+                PolicyObligation::Ttl { duration } => Some(*duration),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Paths to redact across all obligations.
+    pub fn redaction_paths(&self) -> impl Iterator<Item = &RedactionPath> {
+        self.obligations
+            .iter()
+            .filter_map(|obligation| match obligation {
+                PolicyObligation::Redact { paths } => Some(paths.iter()),
+                _ => None,
+            })
+            .flatten()
+    }
+}
+
+/// Condition attached to a policy decision.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum PolicyObligation {
+    /// Redact the given paths before releasing data.
+    Redact {
+        /// JSONPath expressions to redact.
+        paths: Vec<RedactionPath>,
+    },
+    /// The decision holds only for `duration` and must be re-evaluated afterwards.
+    Ttl {
+        /// Validity period.
+        duration: DurationMs,
+    },
+    /// The subject must have completed multi-factor authentication.
+    RequireMfa,
+    /// Engine-specific obligation.
+    Custom {
+        /// Obligation name understood by the enforcement point.
+        name: String,
+        /// Obligation parameters.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Value::is_null")
+        )]
+        params: Value,
+    },
+}
+
+/// Obligations an enforcement point declares it can apply.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ObligationCapabilities {
+    /// Can redact payload paths.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub redact: bool,
+    /// Can expire decisions and re-evaluate.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ttl: bool,
+    /// Can enforce multi-factor authentication.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub require_mfa: bool,
+    /// Names of supported custom obligations.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub custom: Vec<String>,
+}
+
+impl ObligationCapabilities {
+    /// Returns `true` when `obligation` can be enforced.
+    pub fn supports(&self, obligation: &PolicyObligation) -> bool {
+        match obligation {
+            PolicyObligation::Redact { .. } => self.redact,
+            PolicyObligation::Ttl { .. } => self.ttl,
+            PolicyObligation::RequireMfa => self.require_mfa,
+            PolicyObligation::Custom { name, .. } => self.custom.iter().any(|known| known == name),
+        }
+    }
 }
 
 /// Status for a policy decision.
@@ -124,7 +251,7 @@ pub enum PolicyDecisionStatus {
 
 #[cfg(feature = "serde")]
 mod serde_impls {
-    use super::{PolicyDecision, PolicyDecisionStatus};
+    use super::{PolicyDecision, PolicyDecisionStatus, PolicyObligation};
     use alloc::vec::Vec;
     use serde::de::{self, MapAccess, Visitor};
     use serde::ser::SerializeStruct;
@@ -135,7 +262,7 @@ mod serde_impls {
         where
             S: Serializer,
         {
-            // status + reasons always emitted; legacy fields only when present.
+            // status + reasons always emitted; legacy fields and obligations only when present.
             let mut len = 2;
             if self.allow.is_some() {
                 len += 1;
@@ -143,6 +270,9 @@ mod serde_impls {
             if self.reason.is_some() {
                 len += 1;
             }
+            if !self.obligations.is_empty() {
+                len += 1;
+            }
             let mut state = serializer.serialize_struct("PolicyDecision", len)?;
             state.serialize_field("status", &self.status)?;
             state.serialize_field("reasons", &self.reasons)?;
@@ -152,6 +282,9 @@ mod serde_impls {
             if let Some(reason) = &self.reason {
                 state.serialize_field("reason", reason)?;
             }
+            if !self.obligations.is_empty() {
+                state.serialize_field("obligations", &self.obligations)?;
+            }
             state.end()
         }
     }
@@ -166,6 +299,7 @@ mod serde_impls {
                 Reason,
                 Status,
                 Reasons,
+                Obligations,
                 Unknown,
             }
 
@@ -183,7 +317,9 @@ mod serde_impls {
                             &self,
                             formatter: &mut core::fmt::Formatter,
                         ) -> core::fmt::Result {
-                            formatter.write_str("`allow`, `reason`, `status`, or `reasons`")
+                            formatter.write_str(
+                                "`allow`, `reason`, `status`, `reasons`, or `obligations`",
+                            )
                         }
 
                         fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
@@ -195,6 +331,7 @@ mod serde_impls {
                                 "reason" => Field::Reason,
                                 "status" => Field::Status,
                                 "reasons" => Field::Reasons,
+                                "obligations" => Field::Obligations,
                                 _ => Field::Unknown,
                             })
                         }
@@ -221,6 +358,7 @@ mod serde_impls {
                     let mut reason: Option<Option<String>> = None;
                     let mut status: Option<PolicyDecisionStatus> = None;
                     let mut reasons: Option<Vec<String>> = None;
+                    let mut obligations: Option<Vec<PolicyObligation>> = None;
 
                     while let Some(key) = map.next_key()? {
                         match key {
@@ -248,6 +386,12 @@ mod serde_impls {
                                 }
                                 reasons = Some(map.next_value()?);
                             }
+                            Field::Obligations => {
+                                if obligations.is_some() {
+                                    return Err(de::Error::duplicate_field("obligations"));
+                                }
+                                obligations = Some(map.next_value()?);
+                            }
                             Field::Unknown => {
                                 // Ignore unknown fields for forward compatibility.
                                 let _ = map.next_value::<de::IgnoredAny>()?;
@@ -278,13 +422,14 @@ mod serde_impls {
                         reasons: reasons_vec,
                         allow: allow.flatten(),
                         reason: reason.flatten(),
+                        obligations: obligations.unwrap_or_default(),
                     })
                 }
             }
 
             deserializer.deserialize_struct(
                 "PolicyDecision",
-                &["status", "reasons", "allow", "reason"],
+                &["status", "reasons", "allow", "reason", "obligations"],
                 PolicyDecisionVisitor,
             )
         }
@@ -0,0 +1,230 @@
+//! Rate limit descriptors shared by gateways and workers.
+//!
+//! A [`RateLimitPolicy`] is a token bucket: it admits up to `burst` requests at once and refills
+//! at `max_requests` per `window`. Callers keep one [`RateLimitState`] per
+//! [`RateLimitPolicy::scope_key`] and consult it with [`RateLimitState::check`], which returns a
+//! [`RateLimitDecision`] suitable for `RateLimit-*` / `Retry-After` response headers.
+
+use alloc::format;
+use alloc::string::String;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{DurationMs, ErrorCode, GResult, GreenticError, TenantCtx, UnixMillis};
+
+/// Dimension a limit is counted per.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum RateLimitScope {
+    /// One bucket per tenant.
+    Tenant,
+    /// One bucket per team within a tenant.
+    Team,
+    /// One bucket per user within a tenant.
+    User,
+    /// One bucket per session.
+    Session,
+}
+
+/// Token-bucket rate limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct RateLimitPolicy {
+    /// Dimension the limit applies to.
+    pub scope: RateLimitScope,
+    /// Sustained number of requests allowed per `window`.
+    pub max_requests: u32,
+    /// Refill window.
+    pub window: DurationMs,
+    /// Bucket capacity; defaults to `max_requests` when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub burst: Option<u32>,
+}
+
+impl RateLimitPolicy {
+    /// Creates a policy allowing `max_requests` per `window` without extra burst.
+    pub fn new(scope: RateLimitScope, max_requests: u32, window: DurationMs) -> Self {
+        Self {
+            scope,
+            max_requests,
+            window,
+            burst: None,
+        }
+    }
+
+    /// Sets the bucket capacity.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = Some(burst);
+        self
+    }
+
+    /// Number of requests that may be made back to back from a full bucket.
+    pub fn capacity(&self) -> u32 {
+        self.burst.unwrap_or(self.max_requests)
+    }
+
+    /// Ensures the policy admits traffic and refills over a non-empty window.
+    pub fn validate(&self) -> GResult<()> {
+        if self.max_requests == 0 {
+            return Err(invalid("max_requests must be positive"));
+        }
+        if self.window == DurationMs::ZERO {
+            return Err(invalid("window must be positive"));
+        }
+        if self.capacity() == 0 {
+            return Err(invalid("burst must be positive"));
+        }
+        Ok(())
+    }
+
+    /// Returns the bucket key for `ctx`, or `None` when the context lacks the scoped identity.
+    ///
+    /// Keys are prefixed with `env:tenant`, so identical team, user, or session identifiers in
+    /// different tenants never share a bucket.
+    pub fn scope_key(&self, ctx: &TenantCtx) -> Option<String> {
+        let tenant = format!("{}:{}", ctx.env.as_str(), ctx.tenant_id.as_str());
+        match self.scope {
+            RateLimitScope::Tenant => Some(tenant),
+            RateLimitScope::Team => ctx
+                .team_id
+                .as_ref()
+                .map(|team| format!("{tenant}:team={}", team.as_str())),
+            RateLimitScope::User => ctx
+                .user_id
+                .as_ref()
+                .map(|user| format!("{tenant}:user={}", user.as_str())),
+            RateLimitScope::Session => ctx
+                .session_id()
+                .map(|session| format!("{tenant}:session={session}")),
+        }
+    }
+
+    /// Milliseconds needed to refill one token, rounded up.
+    fn refill_interval(&self) -> Option<u64> {
+        match self.max_requests {
+            0 => None,
+            max => Some(self.window.as_millis().div_ceil(u64::from(max)).max(1)),
+        }
+    }
+}
+
+/// Outcome of checking a request against a policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct RateLimitDecision {
+    /// Whether the request may proceed.
+    pub allowed: bool,
+    /// Bucket capacity.
+    pub limit: u32,
+    /// Requests still available after this decision.
+    pub remaining: u32,
+    /// Wait before the next request can succeed; set when the request was rejected.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub retry_after: Option<DurationMs>,
+}
+
+impl RateLimitDecision {
+    /// Converts a rejection into an [`ErrorCode::RateLimited`] error.
+    pub fn into_result(self) -> GResult<Self> {
+        if self.allowed {
+            return Ok(self);
+        }
+        let message = match self.retry_after {
+            Some(wait) => format!("rate limit exceeded; retry after {} ms", wait.as_millis()),
+            None => "rate limit exceeded".into(),
+        };
+        Err(GreenticError::new(ErrorCode::RateLimited, message))
+    }
+}
+
+/// Persisted token-bucket state for one scope key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct RateLimitState {
+    /// Tokens currently available.
+    pub tokens: u32,
+    /// Instant up to which refills have been credited.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub updated_at: UnixMillis,
+}
+
+impl RateLimitState {
+    /// Creates a full bucket for `policy`.
+    pub fn full(policy: &RateLimitPolicy, now: UnixMillis) -> Self {
+        Self {
+            tokens: policy.capacity(),
+            updated_at: now,
+        }
+    }
+
+    /// Refills the bucket up to `now` and consumes one token when available.
+    pub fn check(&mut self, policy: &RateLimitPolicy, now: UnixMillis) -> RateLimitDecision {
+        self.refill(policy, now);
+        let allowed = self.tokens > 0;
+        let retry_after = if allowed {
+            self.tokens -= 1;
+            None
+        } else {
+            policy.refill_interval().map(|interval| {
+                let waited = now.saturating_duration_since(self.updated_at).as_millis();
+                DurationMs::from_millis(interval.saturating_sub(waited))
+            })
+        };
+        RateLimitDecision {
+            allowed,
+            limit: policy.capacity(),
+            remaining: self.tokens,
+            retry_after,
+        }
+    }
+
+    fn refill(&mut self, policy: &RateLimitPolicy, now: UnixMillis) {
+        let capacity = policy.capacity();
+        let interval = match policy.refill_interval() {
+            Some(interval) => interval,
+            None => return,
+        };
+        let elapsed = now.saturating_duration_since(self.updated_at).as_millis();
+        let added = elapsed / interval;
+        if added == 0 {
+            return;
+        }
+        let missing = u64::from(capacity.saturating_sub(self.tokens));
+        if added >= missing {
+            self.tokens = capacity.max(self.tokens);
+            self.updated_at = now;
+        } else {
+            // `added < missing <= u32::MAX`, so the cast is lossless.
+            self.tokens += added as u32;
+            self.updated_at = self
+                .updated_at
+                .checked_add(DurationMs::from_millis(added * interval))
+                .unwrap_or(now);
+        }
+    }
+}
+
+fn invalid(reason: &str) -> GreenticError {
+    GreenticError::new(
+        ErrorCode::InvalidInput,
+        format!("invalid rate limit policy: {reason}"),
+    )
+}
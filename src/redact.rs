@@ -0,0 +1,379 @@
+//! Applies [`RedactionPath`] expressions to JSON documents.
+//!
+//! Only a JSONPath subset is evaluated: member access (`.name`, `['name']`, `["name"]`), array
+//! indices (`[0]`, `[-1]` counting from the end) and wildcards (`.*`, `[*]`). Filters, slices,
+//! unions and recursive descent are rejected with [`ErrorCode::InvalidInput`].
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::session::hex_encode;
+use crate::{ErrorCode, GResult, GreenticError, RedactionPath};
+
+/// Placeholder written by [`RedactionStrategy::default`].
+pub const DEFAULT_REDACTION_PLACEHOLDER: &str = "[REDACTED]";
+
+/// How a matched value is redacted.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum RedactionStrategy {
+    /// Replace the value with the given string.
+    Placeholder(String),
+    /// Replace the value with `blake3-keyed:<hex>`, a keyed hash of its compact JSON encoding, so
+    /// equal values stay correlatable without being revealed or brute-forced without the key.
+    ///
+    /// Only the key's name is part of the strategy; the key itself is looked up through
+    /// [`RedactionKeys`] when redacting, so serialized strategies never carry secrets.
+    Hash {
+        /// Name of the key, resolved by the caller (for example a tenant secret name).
+        key_ref: String,
+    },
+    /// Remove the object member or array element entirely.
+    Drop,
+}
+
+impl Default for RedactionStrategy {
+    fn default() -> Self {
+        Self::Placeholder(DEFAULT_REDACTION_PLACEHOLDER.into())
+    }
+}
+
+/// 32-byte secret key for [`RedactionStrategy::Hash`].
+///
+/// Use one key per tenant or environment: hashes only correlate under the same key, and low-entropy
+/// values such as emails cannot be recovered by hashing guesses without it. The key is neither
+/// serializable nor printed by `Debug`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RedactionKey([u8; 32]);
+
+impl RedactionKey {
+    /// Wraps raw key bytes.
+    pub const fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Derives a key from arbitrary secret material, such as a tenant secret.
+    pub fn derive(material: &[u8]) -> Self {
+        Self(blake3::derive_key(
+            "greentic-types redaction key v1",
+            material,
+        ))
+    }
+
+    /// Raw key bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl core::fmt::Debug for RedactionKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("RedactionKey(..)")
+    }
+}
+
+/// Source of the keys named by [`RedactionStrategy::Hash`].
+pub trait RedactionKeys {
+    /// Returns the key named `key_ref`, or `None` when it is unknown.
+    fn redaction_key(&self, key_ref: &str) -> Option<RedactionKey>;
+}
+
+impl RedactionKeys for BTreeMap<String, RedactionKey> {
+    fn redaction_key(&self, key_ref: &str) -> Option<RedactionKey> {
+        self.get(key_ref).cloned()
+    }
+}
+
+/// Strategy with its key resolved, as applied to matched values.
+enum Applied<'a> {
+    Placeholder(&'a str),
+    Hash(RedactionKey),
+    Drop,
+}
+
+/// Single step of a parsed redaction path.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RedactionSegment {
+    /// Object member by name.
+    Key(String),
+    /// Array element; negative values count from the end.
+    Index(i64),
+    /// Every member of an object or element of an array.
+    Wildcard,
+}
+
+impl RedactionPath {
+    /// Parses the expression into the segments understood by [`apply_redactions`].
+    pub fn segments(&self) -> GResult<Vec<RedactionSegment>> {
+        parse_segments(self.as_str())
+    }
+}
+
+/// Redacts every location matched by `paths`, returning the number of values redacted.
+///
+/// Paths are applied in order, so with [`RedactionStrategy::Drop`] later index-based paths see
+/// arrays that earlier paths already shortened. Paths that match nothing are not an error; paths
+/// that use unsupported syntax are, and leave `value` untouched. [`RedactionStrategy::Hash`]
+/// needs a key and fails here; use [`apply_redactions_with_keys`] for it.
+pub fn apply_redactions(
+    value: &mut Value,
+    paths: &[RedactionPath],
+    strategy: RedactionStrategy,
+) -> GResult<usize> {
+    apply_redactions_with_keys(value, paths, strategy, &BTreeMap::new())
+}
+
+/// Like [`apply_redactions`], resolving the key of [`RedactionStrategy::Hash`] through `keys`.
+///
+/// An unknown key fails with [`ErrorCode::NotFound`] and leaves `value` untouched.
+pub fn apply_redactions_with_keys(
+    value: &mut Value,
+    paths: &[RedactionPath],
+    strategy: RedactionStrategy,
+    keys: &impl RedactionKeys,
+) -> GResult<usize> {
+    let parsed = paths
+        .iter()
+        .map(RedactionPath::segments)
+        .collect::<GResult<Vec<_>>>()?;
+    let strategy = match &strategy {
+        RedactionStrategy::Placeholder(text) => Applied::Placeholder(text),
+        RedactionStrategy::Hash { key_ref } => {
+            Applied::Hash(keys.redaction_key(key_ref).ok_or_else(|| {
+                GreenticError::new(
+                    ErrorCode::NotFound,
+                    format!("redaction key `{key_ref}` is not available"),
+                )
+            })?)
+        }
+        RedactionStrategy::Drop => Applied::Drop,
+    };
+    let mut redacted = 0;
+    for segments in &parsed {
+        if segments.is_empty() {
+            // `$` addresses the document itself, which cannot be dropped.
+            redacted += 1;
+            *value = match &strategy {
+                Applied::Drop => Value::Null,
+                other => replacement(value, other),
+            };
+        } else {
+            redacted += redact_at(value, segments, &strategy);
+        }
+    }
+    Ok(redacted)
+}
+
+fn redact_at(value: &mut Value, segments: &[RedactionSegment], strategy: &Applied<'_>) -> usize {
+    let (segment, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => return 0,
+    };
+    if rest.is_empty() {
+        return redact_leaf(value, segment, strategy);
+    }
+    match (value, segment) {
+        (Value::Object(map), RedactionSegment::Key(key)) => map
+            .get_mut(key)
+            .map_or(0, |child| redact_at(child, rest, strategy)),
+        (Value::Object(map), RedactionSegment::Wildcard) => map
+            .values_mut()
+            .map(|child| redact_at(child, rest, strategy))
+            .sum(),
+        (Value::Array(items), RedactionSegment::Index(index)) => resolve_index(items.len(), *index)
+            .map_or(0, |index| redact_at(&mut items[index], rest, strategy)),
+        (Value::Array(items), RedactionSegment::Wildcard) => items
+            .iter_mut()
+            .map(|child| redact_at(child, rest, strategy))
+            .sum(),
+        _ => 0,
+    }
+}
+
+fn redact_leaf(value: &mut Value, segment: &RedactionSegment, strategy: &Applied<'_>) -> usize {
+    match (value, segment, strategy) {
+        (Value::Object(map), RedactionSegment::Key(key), Applied::Drop) => {
+            usize::from(map.remove(key).is_some())
+        }
+        (Value::Object(map), RedactionSegment::Wildcard, Applied::Drop) => {
+            let count = map.len();
+            map.clear();
+            count
+        }
+        (Value::Array(items), RedactionSegment::Index(index), Applied::Drop) => {
+            match resolve_index(items.len(), *index) {
+                Some(index) => {
+                    items.remove(index);
+                    1
+                }
+                None => 0,
+            }
+        }
+        (Value::Array(items), RedactionSegment::Wildcard, Applied::Drop) => {
+            let count = items.len();
+            items.clear();
+            count
+        }
+        (Value::Object(map), RedactionSegment::Key(key), strategy) => match map.get_mut(key) {
+            Some(child) => {
+                *child = replacement(child, strategy);
+                1
+            }
+            None => 0,
+        },
+        (Value::Object(map), RedactionSegment::Wildcard, strategy) => {
+            for child in map.values_mut() {
+                *child = replacement(child, strategy);
+            }
+            map.len()
+        }
+        (Value::Array(items), RedactionSegment::Index(index), strategy) => {
+            match resolve_index(items.len(), *index) {
+                Some(index) => {
+                    items[index] = replacement(&items[index], strategy);
+                    1
+                }
+                None => 0,
+            }
+        }
+        (Value::Array(items), RedactionSegment::Wildcard, strategy) => {
+            for child in items.iter_mut() {
+                *child = replacement(child, strategy);
+            }
+            items.len()
+        }
+        _ => 0,
+    }
+}
+
+fn replacement(value: &Value, strategy: &Applied<'_>) -> Value {
+    match strategy {
+        Applied::Placeholder(text) => Value::String((*text).into()),
+        Applied::Hash(key) => {
+            let encoded = value.to_string();
+            let digest = blake3::keyed_hash(key.as_bytes(), encoded.as_bytes());
+            Value::String(format!("blake3-keyed:{}", hex_encode(digest.as_bytes())))
+        }
+        Applied::Drop => Value::Null,
+    }
+}
+
+/// Returns every value matched by `segments`, in document order.
+pub(crate) fn select<'a>(value: &'a Value, segments: &[RedactionSegment]) -> Vec<&'a Value> {
+    let (segment, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => return alloc::vec![value],
+    };
+    match (value, segment) {
+        (Value::Object(map), RedactionSegment::Key(key)) => map
+            .get(key)
+            .map_or_else(Vec::new, |child| select(child, rest)),
+        (Value::Object(map), RedactionSegment::Wildcard) => {
+            map.values().flat_map(|child| select(child, rest)).collect()
+        }
+        (Value::Array(items), RedactionSegment::Index(index)) => resolve_index(items.len(), *index)
+            .map_or_else(Vec::new, |index| select(&items[index], rest)),
+        (Value::Array(items), RedactionSegment::Wildcard) => {
+            items.iter().flat_map(|child| select(child, rest)).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn resolve_index(len: usize, index: i64) -> Option<usize> {
+    let resolved = if index < 0 {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)?
+    } else {
+        usize::try_from(index).ok()?
+    };
+    (resolved < len).then_some(resolved)
+}
+
+pub(crate) fn parse_segments(path: &str) -> GResult<Vec<RedactionSegment>> {
+    let mut rest = path
+        .strip_prefix('$')
+        .ok_or_else(|| unsupported(path, "must start with '$'"))?;
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            if after_dot.starts_with('.') {
+                return Err(unsupported(path, "recursive descent is not supported"));
+            }
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            let (name, tail) = after_dot.split_at(end);
+            segments.push(match name {
+                "" => return Err(unsupported(path, "empty member name")),
+                "*" => RedactionSegment::Wildcard,
+                name => RedactionSegment::Key(name.to_string()),
+            });
+            rest = tail;
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let (segment, tail) = parse_bracket(path, after_bracket)?;
+            segments.push(segment);
+            rest = tail;
+        } else {
+            return Err(unsupported(path, "expected '.' or '['"));
+        }
+    }
+    Ok(segments)
+}
+
+/// Parses the contents of a `[...]` selector; `input` starts just after the opening bracket.
+fn parse_bracket<'a>(path: &str, input: &'a str) -> GResult<(RedactionSegment, &'a str)> {
+    if let Some(quote) = input.chars().next().filter(|c| *c == '\'' || *c == '"') {
+        let body = &input[1..];
+        let mut name = String::new();
+        let mut chars = body.char_indices();
+        while let Some((offset, ch)) = chars.next() {
+            match ch {
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => name.push(escaped),
+                    None => break,
+                },
+                ch if ch == quote => {
+                    let tail = body[offset + 1..]
+                        .strip_prefix(']')
+                        .ok_or_else(|| unsupported(path, "expected ']' after quoted name"))?;
+                    return Ok((RedactionSegment::Key(name), tail));
+                }
+                ch => name.push(ch),
+            }
+        }
+        return Err(unsupported(path, "unterminated quoted name"));
+    }
+    let end = input
+        .find(']')
+        .ok_or_else(|| unsupported(path, "unterminated '['"))?;
+    let (selector, tail) = (input[..end].trim(), &input[end + 1..]);
+    if selector == "*" {
+        return Ok((RedactionSegment::Wildcard, tail));
+    }
+    selector
+        .parse::<i64>()
+        .map(|index| (RedactionSegment::Index(index), tail))
+        .map_err(|_| {
+            unsupported(
+                path,
+                &format!("selector '[{selector}]' is not an index, quoted name, or '*'"),
+            )
+        })
+}
+
+fn unsupported(path: &str, reason: &str) -> GreenticError {
+    GreenticError::new(
+        ErrorCode::InvalidInput,
+        format!("unsupported JSONPath '{path}': {reason}"),
+    )
+}
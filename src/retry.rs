@@ -0,0 +1,143 @@
+//! Retry policies shared by node errors and worker responses.
+//!
+//! A [`RetryPolicy`] tells the runtime how many attempts to make, how long to wait between them,
+//! and which [`ErrorCode`]s are worth retrying. Attempts are counted from `1`, so
+//! `next_delay(1)` is the wait after the first failed attempt.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{DurationMs, ErrorCode};
+
+/// How the delay grows between attempts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum BackoffKind {
+    /// Wait `initial_delay` between every attempt.
+    #[default]
+    Fixed,
+    /// Double the delay after each attempt, starting at `initial_delay`.
+    Exponential,
+    /// Pick a random delay between zero and the exponential delay ("full jitter").
+    Jitter,
+}
+
+/// Retry semantics attached to a failure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct RetryPolicy {
+    /// Total attempts allowed, including the first one.
+    pub max_attempts: u32,
+    /// Delay growth strategy.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub backoff: BackoffKind,
+    /// Delay after the first failed attempt.
+    pub initial_delay: DurationMs,
+    /// Upper bound for any single delay.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_delay: Option<DurationMs>,
+    /// Error codes worth retrying; every code is retried when empty.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub retry_on: Vec<ErrorCode>,
+}
+
+impl RetryPolicy {
+    /// Creates a fixed-delay policy.
+    pub fn fixed(max_attempts: u32, delay: DurationMs) -> Self {
+        Self {
+            max_attempts,
+            backoff: BackoffKind::Fixed,
+            initial_delay: delay,
+            max_delay: None,
+            retry_on: Vec::new(),
+        }
+    }
+
+    /// Creates an exponential policy capped at `max_delay`.
+    pub fn exponential(
+        max_attempts: u32,
+        initial_delay: DurationMs,
+        max_delay: DurationMs,
+    ) -> Self {
+        Self {
+            backoff: BackoffKind::Exponential,
+            max_delay: Some(max_delay),
+            ..Self::fixed(max_attempts, initial_delay)
+        }
+    }
+
+    /// Overrides the backoff kind.
+    pub fn with_backoff(mut self, backoff: BackoffKind) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Restricts retries to the given error codes.
+    pub fn with_retry_on(mut self, codes: impl IntoIterator<Item = ErrorCode>) -> Self {
+        self.retry_on.extend(codes);
+        self
+    }
+
+    /// Returns `true` when another attempt is allowed after `attempt` failed.
+    pub fn allows_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// Returns `true` when a failure with `code` on `attempt` should be retried.
+    pub fn should_retry(&self, code: ErrorCode, attempt: u32) -> bool {
+        self.allows_retry(attempt) && (self.retry_on.is_empty() || self.retry_on.contains(&code))
+    }
+
+    /// Returns the delay before the attempt following `attempt`, or `None` once attempts are
+    /// exhausted.
+    ///
+    /// For [`BackoffKind::Jitter`] this is the upper bound of the jitter window; use
+    /// [`RetryPolicy::jittered_delay`] to pick a point inside it.
+    pub fn next_delay(&self, attempt: u32) -> Option<DurationMs> {
+        if attempt == 0 || !self.allows_retry(attempt) {
+            return None;
+        }
+        let initial = self.initial_delay.as_millis();
+        let millis = match self.backoff {
+            BackoffKind::Fixed => initial,
+            BackoffKind::Exponential | BackoffKind::Jitter => {
+                let factor = 1u64.checked_shl(attempt - 1).unwrap_or(u64::MAX);
+                initial.saturating_mul(factor)
+            }
+        };
+        let capped = match self.max_delay {
+            Some(max) => millis.min(max.as_millis()),
+            None => millis,
+        };
+        Some(DurationMs::from_millis(capped))
+    }
+
+    /// Like [`RetryPolicy::next_delay`], but maps `entropy` into the jitter window for
+    /// [`BackoffKind::Jitter`] policies.
+    ///
+    /// Callers supply the randomness so the computation stays deterministic and `no_std`-friendly;
+    /// other backoff kinds ignore `entropy`.
+    pub fn jittered_delay(&self, attempt: u32, entropy: u64) -> Option<DurationMs> {
+        let delay = self.next_delay(attempt)?;
+        match self.backoff {
+            BackoffKind::Jitter => {
+                let bound = delay.as_millis().saturating_add(1);
+                Some(DurationMs::from_millis(entropy % bound))
+            }
+            BackoffKind::Fixed | BackoffKind::Exponential => Some(delay),
+        }
+    }
+}
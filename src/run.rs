@@ -4,23 +4,26 @@ use alloc::{collections::BTreeMap, string::String, vec::Vec};
 
 use semver::Version;
 
-use crate::{ComponentId, FlowId, NodeId, PackId, SessionKey};
+use crate::{BlobRef, ComponentId, FlowId, NodeId, PackId, SessionKey, UnixMillis};
 
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-#[cfg(all(feature = "serde", feature = "time"))]
+#[cfg(feature = "serde")]
 use serde_with::serde_as;
-#[cfg(feature = "time")]
-use time::OffsetDateTime;
 
 /// Overall execution status emitted by the runner.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[non_exhaustive]
 pub enum RunStatus {
+    /// Run accepted by the scheduler but not started yet.
+    Pending,
+    /// Run is executing.
+    Running,
     /// Flow finished successfully.
     Success,
     /// Flow finished with partial failures but continued.
@@ -29,6 +32,34 @@ pub enum RunStatus {
     Failure,
 }
 
+impl RunStatus {
+    /// Returns `true` when the run has finished and will not progress further on its own.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Success | Self::PartialFailure | Self::Failure)
+    }
+
+    /// Returns `true` when a scheduler may re-queue the run.
+    ///
+    /// Only outright failures qualify; partially failed runs already applied some side effects
+    /// and must be retried explicitly.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Failure)
+    }
+
+    /// Returns `true` when moving from `self` to `next` is a valid lifecycle step.
+    ///
+    /// Valid steps are `pending -> running`, `pending -> failure` (the run could not start),
+    /// `running -> <terminal>`, and `<retryable> -> pending`.
+    pub fn can_transition_to(self, next: RunStatus) -> bool {
+        match self {
+            Self::Pending => matches!(next, Self::Running | Self::Failure),
+            Self::Running => next.is_terminal(),
+            status if status.is_retryable() => next == Self::Pending,
+            _ => false,
+        }
+    }
+}
+
 /// Per-node execution status.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -43,6 +74,13 @@ pub enum NodeStatus {
     Error,
 }
 
+impl NodeStatus {
+    /// Returns `true` when the node errored.
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Error)
+    }
+}
+
 /// Aggregated timing summary per node.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -99,7 +137,6 @@ pub struct NodeFailure {
 }
 
 /// Aggregated run outcome emitted by the runtime.
-#[cfg(feature = "time")]
 #[cfg_attr(feature = "serde", serde_as)]
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -122,17 +159,19 @@ pub struct RunResult {
     /// Flow identifier executed for the session.
     pub flow_id: FlowId,
     /// Wall-clock start timestamp in UTC.
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
     #[cfg_attr(
         feature = "schemars",
         schemars(with = "String", description = "RFC3339 timestamp (UTC)")
     )]
-    pub started_at_utc: OffsetDateTime,
+    pub started_at_utc: UnixMillis,
     /// Wall-clock finish timestamp in UTC.
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
     #[cfg_attr(
         feature = "schemars",
         schemars(with = "String", description = "RFC3339 timestamp (UTC)")
     )]
-    pub finished_at_utc: OffsetDateTime,
+    pub finished_at_utc: UnixMillis,
     /// Final run status.
     pub status: RunStatus,
     /// Per-node execution summaries.
@@ -153,13 +192,19 @@ pub struct RunResult {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub artifacts_dir: Option<String>,
+    /// Artifacts uploaded to a content store.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub artifacts: Vec<BlobRef>,
 }
 
-#[cfg(feature = "time")]
 impl RunResult {
     /// Returns the total duration in milliseconds.
     pub fn duration_ms(&self) -> u64 {
-        let duration = self.finished_at_utc - self.started_at_utc;
-        duration.whole_milliseconds().max(0) as u64
+        self.finished_at_utc
+            .saturating_duration_since(self.started_at_utc)
+            .as_millis()
     }
 }
@@ -5,28 +5,30 @@
 #[allow(unused_imports)]
 use alloc::vec::Vec;
 
-#[cfg(feature = "time")]
 use crate::run::RunResult;
 #[cfg(feature = "otel-keys")]
 use crate::telemetry::OtlpKeys;
 use crate::{
-    ApiKeyRef, ArtifactRef, ArtifactSelector, Attachment, AttestationId, AttestationRef,
-    AttestationStatement, BranchRef, BuildLogRef, BuildPlan, BuildRef, BuildStatus, BundleSpec,
-    Capabilities, CapabilityMap, ChannelMessageEnvelope, Collection, CommitRef, ComponentId,
-    ComponentManifest, ComponentRef, ConnectionKind, DesiredState, DesiredStateExportSpec,
-    DesiredSubscriptionEntry, Diagnostic, Environment, EnvironmentRef, EventEnvelope,
-    EventProviderDescriptor, Flow, FlowId, FlowResolveSummaryV1, FlowResolveV1, GitProviderRef,
-    HashDigest, LayoutSection, Limits, MetadataRecord, MetadataRecordRef, Node, NodeFailure,
-    NodeId, NodeStatus, NodeSummary, OciImageRef, PackId, PackManifest, PackOrComponentRef,
-    PlanLimits, PolicyInputRef, PolicyRef, PriceModel, ProductOverride, ProviderDecl,
-    ProviderExtensionInline, ProviderInstallId, ProviderInstallRecord, ProviderManifest,
-    ProviderRuntimeRef, RedactionPath, RegistryRef, RepoAuth, RepoContext, RepoRef, RepoSkin,
-    RepoTenantConfig, RolloutStatus, RunStatus, SbomRef, ScanRef, ScanRequest, ScanResult,
-    ScannerRef, SecretsCaps, SemverReq, Severity, SignRequest, SignatureRef, SigningKeyRef,
-    StatementRef, StoreContext, StoreFront, StorePlan, StoreProduct, StoreProductKind, StoreRef,
-    Subscription, SubscriptionStatus, TelemetrySpec, TenantContext, TenantDidDocument, Theme,
-    ToolsCaps, TranscriptOffset, ValidationReport, VerifyRequest, VerifyResult, VersionRef,
-    VersionStrategy, WebhookId, WorkerMessage, WorkerRequest, WorkerResponse, ids,
+    ApiKeyRef, ApprovalRecord, ArtifactRef, ArtifactSelector, Attachment, AttestationBundle,
+    AttestationId, AttestationRef, AttestationStatement, BillingAccount, BranchRef, BuildLogChunk,
+    BuildLogRef, BuildPlan, BuildRef, BuildStatus, BundleSpec, Capabilities, CapabilityMap,
+    CatalogFilter, ChannelMessageEnvelope, Collection, CommitRef, ComponentId, ComponentManifest,
+    ComponentRef, ConnectionKind, CostEstimate, DesiredState, DesiredStateChangeRequest,
+    DesiredStateExportSpec, DesiredSubscriptionEntry, Diagnostic, Environment, EnvironmentRef,
+    EventBatch, EventEnvelope, EventProviderDescriptor, Flow, FlowId, FlowResolveSummaryV1,
+    FlowResolveV1, GitProviderRef, HashDigest, LayoutSection, Limits, MetadataRecord,
+    MetadataRecordRef, Node, NodeFailure, NodeId, NodeStatus, NodeSummary, NotificationPreferences,
+    OciImageRef, PackId, PackManifest, PackOrComponentRef, PageRequest, PipelineRunRef,
+    PipelineRunStatus, PlanLimits, PolicyInputRef, PolicyRef, PriceModel, ProductOverride,
+    ProviderDecl, ProviderExtensionInline, ProviderInstallId, ProviderInstallRecord,
+    ProviderManifest, ProviderRuntimeRef, RedactionPath, RegistryRef, RepoAuth, RepoContext,
+    RepoRef, RepoSkin, RepoTenantConfig, RolloutStatus, RunStatus, SbomRef, ScanRef, ScanRequest,
+    ScanResult, ScannerRef, SecretsCaps, SemverReq, Severity, SignRequest, SignatureRef,
+    SigningKeyRef, StatementRef, StoreContext, StoreFront, StorePlan, StoreProduct,
+    StoreProductKind, StoreProductPage, StoreRef, Subscription, SubscriptionStatus, TelemetrySpec,
+    TenantBootstrap, TenantContext, TenantDidDocument, Theme, ToolsCaps, TranscriptOffset,
+    ValidationReport, VerifyRequest, VerifyResult, VersionRef, VersionStrategy, WebhookId,
+    WorkerMessage, WorkerRequest, WorkerResponse, ids,
 };
 use schemars::{JsonSchema, Schema, schema_for};
 
@@ -44,6 +46,9 @@ fn schema_with_id<T: JsonSchema>(id: &str) -> Schema {
 pub(crate) struct SchemaEntry {
     /// Output file name (including `.schema.json`).
     pub file_name: &'static str,
+    /// Canonical `$id` of the schema.
+    #[cfg_attr(not(feature = "schema-validate"), allow(dead_code))]
+    pub id: &'static str,
     /// Generator used to materialise the schema document.
     pub generator: fn() -> Schema,
 }
@@ -87,6 +92,7 @@ define_schema_fn!(
     ids::VALIDATION_DIAGNOSTIC
 );
 define_schema_fn!(validation_report, ValidationReport, ids::VALIDATION_REPORT);
+define_schema_fn!(lint_config, crate::LintConfig, ids::LINT_CONFIG);
 define_schema_fn!(provider_manifest, ProviderManifest, ids::PROVIDER_MANIFEST);
 define_schema_fn!(
     provider_runtime_ref,
@@ -105,9 +111,25 @@ define_schema_fn!(
     ids::PROVIDER_INSTALL_RECORD
 );
 define_schema_fn!(tenant_context, TenantContext, ids::TENANT_CONTEXT);
+define_schema_fn!(
+    impersonation_event,
+    crate::ImpersonationEvent,
+    ids::IMPERSONATION_EVENT
+);
 define_schema_fn!(hash_digest, HashDigest, ids::HASH_DIGEST);
 define_schema_fn!(semver_req, SemverReq, ids::SEMVER_REQ);
 define_schema_fn!(redaction_path, RedactionPath, ids::REDACTION_PATH);
+define_schema_fn!(url_ref, crate::UrlRef, ids::URL_REF);
+define_schema_fn!(locale_tag, crate::LocaleTag, ids::LOCALE_TAG);
+define_schema_fn!(country_code, crate::CountryCode, ids::COUNTRY_CODE);
+define_schema_fn!(region_code, crate::RegionCode, ids::REGION_CODE);
+define_schema_fn!(
+    region_constraint,
+    crate::RegionConstraint,
+    ids::REGION_CONSTRAINT
+);
+define_schema_fn!(currency_code, crate::CurrencyCode, ids::CURRENCY_CODE);
+define_schema_fn!(money_micro, crate::MoneyMicro, ids::MONEY_MICRO);
 define_schema_fn!(capabilities, Capabilities, ids::CAPABILITIES);
 define_schema_fn!(repo_skin, RepoSkin, ids::REPO_SKIN);
 define_schema_fn!(repo_auth, RepoAuth, ids::REPO_AUTH);
@@ -121,6 +143,26 @@ define_schema_fn!(
     TenantDidDocument,
     ids::TENANT_DID_DOCUMENT
 );
+define_schema_fn!(tenant_bootstrap, TenantBootstrap, ids::TENANT_BOOTSTRAP);
+define_schema_fn!(tenant_quota, crate::TenantQuota, ids::TENANT_QUOTA);
+define_schema_fn!(feature_flags, crate::FeatureFlags, ids::FEATURE_FLAGS);
+define_schema_fn!(session_ttl, crate::SessionTtl, ids::SESSION_TTL);
+define_schema_fn!(eviction_policy, crate::EvictionPolicy, ids::EVICTION_POLICY);
+define_schema_fn!(
+    signature_policy,
+    crate::SignaturePolicy,
+    ids::SIGNATURE_POLICY
+);
+define_schema_fn!(
+    notification_preferences,
+    NotificationPreferences,
+    ids::NOTIFICATION_PREFERENCES
+);
+define_schema_fn!(
+    conversation_state,
+    crate::ConversationState,
+    ids::CONVERSATION_STATE
+);
 define_schema_fn!(limits, Limits, ids::LIMITS);
 define_schema_fn!(telemetry_spec, TelemetrySpec, ids::TELEMETRY_SPEC);
 define_schema_fn!(node_summary, NodeSummary, ids::NODE_SUMMARY);
@@ -130,6 +172,11 @@ define_schema_fn!(run_status, RunStatus, ids::RUN_STATUS);
 define_schema_fn!(transcript_offset, TranscriptOffset, ids::TRANSCRIPT_OFFSET);
 define_schema_fn!(tools_caps, ToolsCaps, ids::TOOLS_CAPS);
 define_schema_fn!(secrets_caps, SecretsCaps, ids::SECRETS_CAPS);
+define_schema_fn!(
+    secret_resolution_plan,
+    crate::SecretResolutionPlan,
+    ids::SECRET_RESOLUTION_PLAN
+);
 define_schema_fn!(branch_ref, BranchRef, ids::BRANCH_REF);
 define_schema_fn!(commit_ref, CommitRef, ids::COMMIT_REF);
 define_schema_fn!(git_provider_ref, GitProviderRef, ids::GIT_PROVIDER_REF);
@@ -148,16 +195,51 @@ define_schema_fn!(scan_ref, ScanRef, ids::SCAN_REF);
 define_schema_fn!(attestation_ref, AttestationRef, ids::ATTESTATION_REF);
 define_schema_fn!(attestation_id, AttestationId, ids::ATTESTATION_ID);
 define_schema_fn!(policy_ref, PolicyRef, ids::POLICY_REF);
+define_schema_fn!(pipeline_run_ref, PipelineRunRef, ids::PIPELINE_RUN_REF);
 define_schema_fn!(policy_input_ref, PolicyInputRef, ids::POLICY_INPUT_REF);
 define_schema_fn!(store_ref, StoreRef, ids::STORE_REF);
+define_schema_fn!(blob_ref, crate::BlobRef, ids::BLOB_REF);
 define_schema_fn!(registry_ref, RegistryRef, ids::REGISTRY_REF);
 define_schema_fn!(oci_image_ref, OciImageRef, ids::OCI_IMAGE_REF);
 define_schema_fn!(artifact_ref, ArtifactRef, ids::ARTIFACT_REF);
 define_schema_fn!(sbom_ref, SbomRef, ids::SBOM_REF);
 define_schema_fn!(signing_key_ref, SigningKeyRef, ids::SIGNING_KEY_REF);
+define_schema_fn!(
+    encryption_key_ref,
+    crate::EncryptionKeyRef,
+    ids::ENCRYPTION_KEY_REF
+);
+define_schema_fn!(
+    encrypted_payload,
+    crate::EncryptedPayload,
+    ids::ENCRYPTED_PAYLOAD
+);
 define_schema_fn!(signature_ref, SignatureRef, ids::SIGNATURE_REF);
 define_schema_fn!(statement_ref, StatementRef, ids::STATEMENT_REF);
 define_schema_fn!(build_log_ref, BuildLogRef, ids::BUILD_LOG_REF);
+define_schema_fn!(alert_id, crate::AlertId, ids::ALERT_ID);
+define_schema_fn!(alert, crate::Alert, ids::ALERT);
+define_schema_fn!(
+    maintenance_window_id,
+    crate::MaintenanceWindowId,
+    ids::MAINTENANCE_WINDOW_ID
+);
+define_schema_fn!(
+    maintenance_window,
+    crate::MaintenanceWindow,
+    ids::MAINTENANCE_WINDOW
+);
+define_schema_fn!(retry_policy, crate::RetryPolicy, ids::RETRY_POLICY);
+define_schema_fn!(
+    event_subscription_spec,
+    crate::EventSubscriptionSpec,
+    ids::EVENT_SUBSCRIPTION_SPEC
+);
+define_schema_fn!(
+    rate_limit_policy,
+    crate::RateLimitPolicy,
+    ids::RATE_LIMIT_POLICY
+);
 define_schema_fn!(
     metadata_record_ref,
     MetadataRecordRef,
@@ -174,10 +256,28 @@ define_schema_fn!(
 );
 define_schema_fn!(store_plan_id, crate::StorePlanId, ids::STORE_PLAN_ID);
 define_schema_fn!(subscription_id, crate::SubscriptionId, ids::SUBSCRIPTION_ID);
+define_schema_fn!(usage_record, crate::UsageRecord, ids::USAGE_RECORD);
+define_schema_fn!(usage_aggregate, crate::UsageAggregate, ids::USAGE_AGGREGATE);
+define_schema_fn!(
+    subscription_usage,
+    crate::SubscriptionUsage,
+    ids::SUBSCRIPTION_USAGE
+);
 define_schema_fn!(bundle_id, crate::BundleId, ids::BUNDLE_ID);
 define_schema_fn!(collection_id, crate::CollectionId, ids::COLLECTION_ID);
+define_schema_fn!(
+    change_request_id,
+    crate::ChangeRequestId,
+    ids::CHANGE_REQUEST_ID
+);
 define_schema_fn!(build_plan, BuildPlan, ids::BUILD_PLAN);
 define_schema_fn!(build_status, BuildStatus, ids::BUILD_STATUS);
+define_schema_fn!(build_log_chunk, BuildLogChunk, ids::BUILD_LOG_CHUNK);
+define_schema_fn!(
+    pipeline_run_status,
+    PipelineRunStatus,
+    ids::PIPELINE_RUN_STATUS
+);
 define_schema_fn!(scan_request, ScanRequest, ids::SCAN_REQUEST);
 define_schema_fn!(scan_result, ScanResult, ids::SCAN_RESULT);
 define_schema_fn!(sign_request, SignRequest, ids::SIGN_REQUEST);
@@ -188,24 +288,55 @@ define_schema_fn!(
     AttestationStatement,
     ids::ATTESTATION_STATEMENT
 );
+define_schema_fn!(
+    attestation_bundle,
+    AttestationBundle,
+    ids::ATTESTATION_BUNDLE
+);
+define_schema_fn!(
+    provenance_chain,
+    crate::ProvenanceChain,
+    ids::PROVENANCE_CHAIN
+);
 define_schema_fn!(metadata_record, MetadataRecord, ids::METADATA_RECORD);
 define_schema_fn!(repo_context, RepoContext, ids::REPO_CONTEXT);
 define_schema_fn!(store_context, StoreContext, ids::STORE_CONTEXT);
 define_schema_fn!(event_envelope, EventEnvelope, ids::EVENT_ENVELOPE);
+define_schema_fn!(event_batch, EventBatch, ids::EVENT_BATCH);
 define_schema_fn!(
     event_provider_descriptor,
     EventProviderDescriptor,
     ids::EVENT_PROVIDER_DESCRIPTOR
 );
+define_schema_fn!(
+    webhook_subscription,
+    crate::WebhookSubscription,
+    ids::WEBHOOK_SUBSCRIPTION
+);
 define_schema_fn!(
     channel_message_envelope,
     ChannelMessageEnvelope,
     ids::CHANNEL_MESSAGE_ENVELOPE
 );
 define_schema_fn!(attachment, Attachment, ids::ATTACHMENT);
+define_schema_fn!(
+    conversation_binding,
+    crate::ConversationBinding,
+    ids::CONVERSATION_BINDING
+);
+define_schema_fn!(
+    attachment_chunk,
+    crate::AttachmentChunk,
+    ids::ATTACHMENT_CHUNK
+);
 define_schema_fn!(worker_request, WorkerRequest, ids::WORKER_REQUEST);
 define_schema_fn!(worker_message, WorkerMessage, ids::WORKER_MESSAGE);
 define_schema_fn!(worker_response, WorkerResponse, ids::WORKER_RESPONSE);
+define_schema_fn!(
+    worker_frame,
+    crate::worker::v2::WorkerFrame,
+    ids::WORKER_FRAME
+);
 define_schema_fn!(bundle_spec, BundleSpec, ids::BUNDLE);
 define_schema_fn!(
     desired_state_export_spec,
@@ -213,11 +344,18 @@ define_schema_fn!(
     ids::DESIRED_STATE_EXPORT
 );
 define_schema_fn!(desired_state, DesiredState, ids::DESIRED_STATE);
+define_schema_fn!(cost_estimate, CostEstimate, ids::COST_ESTIMATE);
 define_schema_fn!(
     desired_subscription_entry,
     DesiredSubscriptionEntry,
     ids::DESIRED_SUBSCRIPTION_ENTRY
 );
+define_schema_fn!(
+    desired_state_change_request,
+    DesiredStateChangeRequest,
+    ids::DESIRED_STATE_CHANGE_REQUEST
+);
+define_schema_fn!(approval_record, ApprovalRecord, ids::APPROVAL_RECORD);
 define_schema_fn!(artifact_selector, ArtifactSelector, ids::ARTIFACT_SELECTOR);
 define_schema_fn!(storefront, StoreFront, ids::STOREFRONT);
 define_schema_fn!(store_product, StoreProduct, ids::STORE_PRODUCT);
@@ -243,7 +381,26 @@ define_schema_fn!(
     ids::PACK_OR_COMPONENT_REF
 );
 define_schema_fn!(plan_limits, PlanLimits, ids::PLAN_LIMITS);
+define_schema_fn!(plan_feature, crate::PlanFeature, ids::PLAN_FEATURE);
 define_schema_fn!(price_model, PriceModel, ids::PRICE_MODEL);
+define_schema_fn!(billing_account, BillingAccount, ids::BILLING_ACCOUNT);
+define_schema_fn!(
+    billing_account_id,
+    crate::BillingAccountId,
+    ids::BILLING_ACCOUNT_ID
+);
+define_schema_fn!(
+    payment_method_ref,
+    crate::PaymentMethodRef,
+    ids::PAYMENT_METHOD_REF
+);
+define_schema_fn!(page_request, PageRequest, ids::PAGE_REQUEST);
+define_schema_fn!(catalog_filter, CatalogFilter, ids::CATALOG_FILTER);
+define_schema_fn!(
+    store_product_page,
+    StoreProductPage,
+    ids::STORE_PRODUCT_PAGE
+);
 define_schema_fn!(
     subscription_status,
     SubscriptionStatus,
@@ -251,7 +408,6 @@ define_schema_fn!(
 );
 #[cfg(feature = "otel-keys")]
 define_schema_fn!(otlp_keys, OtlpKeys, ids::OTLP_KEYS);
-#[cfg(feature = "time")]
 define_schema_fn!(run_result, RunResult, ids::RUN_RESULT);
 
 #[allow(unused_macros)]
@@ -265,6 +421,7 @@ macro_rules! schema_entries_vec {
                 {
                     entries.push(SchemaEntry {
                         file_name: concat!($slug, ".schema.json"),
+                        id: $id_const,
                         generator: $fn_name,
                     });
                 }
@@ -281,14 +438,30 @@ schema_entries_vec! {
     { flow_id, "flow-id", ids::FLOW_ID },
     { node_id, "node-id", ids::NODE_ID },
     { tenant_context, "tenant-context", ids::TENANT_CONTEXT },
+    { impersonation_event, "impersonation-event", ids::IMPERSONATION_EVENT },
     { hash_digest, "hash-digest", ids::HASH_DIGEST },
     { semver_req, "semver-req", ids::SEMVER_REQ },
     { redaction_path, "redaction-path", ids::REDACTION_PATH },
+    { url_ref, "url-ref", ids::URL_REF },
+    { locale_tag, "locale-tag", ids::LOCALE_TAG },
+    { country_code, "country-code", ids::COUNTRY_CODE },
+    { region_code, "region-code", ids::REGION_CODE },
+    { region_constraint, "region-constraint", ids::REGION_CONSTRAINT },
+    { currency_code, "currency-code", ids::CURRENCY_CODE },
+    { money_micro, "money-micro", ids::MONEY_MICRO },
     { capabilities, "capabilities", ids::CAPABILITIES },
     { repo_skin, "repo-skin", ids::REPO_SKIN },
     { repo_auth, "repo-auth", ids::REPO_AUTH },
     { repo_tenant_config, "repo-tenant-config", ids::REPO_TENANT_CONFIG },
     { tenant_did_document, "tenant-did-document", ids::TENANT_DID_DOCUMENT },
+    { tenant_bootstrap, "tenant-bootstrap", ids::TENANT_BOOTSTRAP },
+    { tenant_quota, "tenant-quota", ids::TENANT_QUOTA },
+    { feature_flags, "feature-flags", ids::FEATURE_FLAGS },
+    { session_ttl, "session-ttl", ids::SESSION_TTL },
+    { eviction_policy, "eviction-policy", ids::EVICTION_POLICY },
+    { signature_policy, "signature-policy", ids::SIGNATURE_POLICY },
+    { notification_preferences, "notification-preferences", ids::NOTIFICATION_PREFERENCES },
+    { conversation_state, "conversation-state", ids::CONVERSATION_STATE },
     { flow, "flow", ids::FLOW },
     { flow_resolve, "flow-resolve", ids::FLOW_RESOLVE },
     { flow_resolve_summary, "flow-resolve-summary", ids::FLOW_RESOLVE_SUMMARY },
@@ -298,6 +471,7 @@ schema_entries_vec! {
     { validation_severity, "validation-severity", ids::VALIDATION_SEVERITY },
     { validation_diagnostic, "validation-diagnostic", ids::VALIDATION_DIAGNOSTIC },
     { validation_report, "validation-report", ids::VALIDATION_REPORT },
+    { lint_config, "lint-config", ids::LINT_CONFIG },
     { provider_manifest, "provider-manifest", ids::PROVIDER_MANIFEST },
     { provider_runtime_ref, "provider-runtime-ref", ids::PROVIDER_RUNTIME_REF },
     { provider_decl, "provider-decl", ids::PROVIDER_DECL },
@@ -312,6 +486,7 @@ schema_entries_vec! {
     { transcript_offset, "transcript-offset", ids::TRANSCRIPT_OFFSET },
     { tools_caps, "tools-caps", ids::TOOLS_CAPS },
     { secrets_caps, "secrets-caps", ids::SECRETS_CAPS },
+    { secret_resolution_plan, "secret-resolution-plan", ids::SECRET_RESOLUTION_PLAN },
     { branch_ref, "branch-ref", ids::BRANCH_REF },
     { commit_ref, "commit-ref", ids::COMMIT_REF },
     { git_provider_ref, "git-provider-ref", ids::GIT_PROVIDER_REF },
@@ -326,16 +501,27 @@ schema_entries_vec! {
     { attestation_ref, "attestation-ref", ids::ATTESTATION_REF },
     { attestation_id, "attestation-id", ids::ATTESTATION_ID },
     { policy_ref, "policy-ref", ids::POLICY_REF },
+    { pipeline_run_ref, "pipeline-run-ref", ids::PIPELINE_RUN_REF },
     { policy_input_ref, "policy-input-ref", ids::POLICY_INPUT_REF },
     { store_ref, "store-ref", ids::STORE_REF },
+    { blob_ref, "blob-ref", ids::BLOB_REF },
     { registry_ref, "registry-ref", ids::REGISTRY_REF },
     { oci_image_ref, "oci-image-ref", ids::OCI_IMAGE_REF },
     { artifact_ref, "artifact-ref", ids::ARTIFACT_REF },
     { sbom_ref, "sbom-ref", ids::SBOM_REF },
     { signing_key_ref, "signing-key-ref", ids::SIGNING_KEY_REF },
+    { encryption_key_ref, "encryption-key-ref", ids::ENCRYPTION_KEY_REF },
+    { encrypted_payload, "encrypted-payload", ids::ENCRYPTED_PAYLOAD },
     { signature_ref, "signature-ref", ids::SIGNATURE_REF },
     { statement_ref, "statement-ref", ids::STATEMENT_REF },
     { build_log_ref, "build-log-ref", ids::BUILD_LOG_REF },
+    { alert_id, "alert-id", ids::ALERT_ID },
+    { alert, "alert", ids::ALERT },
+    { maintenance_window_id, "maintenance-window-id", ids::MAINTENANCE_WINDOW_ID },
+    { maintenance_window, "maintenance-window", ids::MAINTENANCE_WINDOW },
+    { retry_policy, "retry-policy", ids::RETRY_POLICY },
+    { event_subscription_spec, "event-subscription-spec", ids::EVENT_SUBSCRIPTION_SPEC },
+    { rate_limit_policy, "rate-limit-policy", ids::RATE_LIMIT_POLICY },
     { metadata_record_ref, "metadata-record-ref", ids::METADATA_RECORD_REF },
     { api_key_ref, "api-key-ref", ids::API_KEY_REF },
     { environment_ref, "environment-ref", ids::ENVIRONMENT_REF },
@@ -344,10 +530,16 @@ schema_entries_vec! {
     { store_product_id, "store-product-id", ids::STORE_PRODUCT_ID },
     { store_plan_id, "store-plan-id", ids::STORE_PLAN_ID },
     { subscription_id, "subscription-id", ids::SUBSCRIPTION_ID },
+    { usage_record, "usage-record", ids::USAGE_RECORD },
+    { usage_aggregate, "usage-aggregate", ids::USAGE_AGGREGATE },
+    { subscription_usage, "subscription-usage", ids::SUBSCRIPTION_USAGE },
     { bundle_id, "bundle-id", ids::BUNDLE_ID },
     { collection_id, "collection-id", ids::COLLECTION_ID },
+    { change_request_id, "change-request-id", ids::CHANGE_REQUEST_ID },
     { build_plan, "build-plan", ids::BUILD_PLAN },
     { build_status, "build-status", ids::BUILD_STATUS },
+    { build_log_chunk, "build-log-chunk", ids::BUILD_LOG_CHUNK },
+    { pipeline_run_status, "pipeline-run-status", ids::PIPELINE_RUN_STATUS },
     { rollout_status, "rollout-status", ids::ROLLOUT_STATUS },
     { scan_request, "scan-request", ids::SCAN_REQUEST },
     { scan_result, "scan-result", ids::SCAN_RESULT },
@@ -355,26 +547,39 @@ schema_entries_vec! {
     { verify_request, "verify-request", ids::VERIFY_REQUEST },
     { verify_result, "verify-result", ids::VERIFY_RESULT },
     { attestation_statement, "attestation-statement", ids::ATTESTATION_STATEMENT },
+    { attestation_bundle, "attestation-bundle", ids::ATTESTATION_BUNDLE },
+    { provenance_chain, "provenance-chain", ids::PROVENANCE_CHAIN },
     { metadata_record, "metadata-record", ids::METADATA_RECORD },
     { repo_context, "repo-context", ids::REPO_CONTEXT },
     { store_context, "store-context", ids::STORE_CONTEXT },
     { event_envelope, "event-envelope", ids::EVENT_ENVELOPE },
+    { event_batch, "event-batch", ids::EVENT_BATCH },
     { event_provider_descriptor, "event-provider-descriptor", ids::EVENT_PROVIDER_DESCRIPTOR },
+    { webhook_subscription, "webhook-subscription", ids::WEBHOOK_SUBSCRIPTION },
     { channel_message_envelope, "channel-message-envelope", ids::CHANNEL_MESSAGE_ENVELOPE },
     { attachment, "attachment", ids::ATTACHMENT },
+    { attachment_chunk, "attachment-chunk", ids::ATTACHMENT_CHUNK },
+    { conversation_binding, "conversation-binding", ids::CONVERSATION_BINDING },
     { worker_request, "worker-request", ids::WORKER_REQUEST },
     { worker_message, "worker-message", ids::WORKER_MESSAGE },
     { worker_response, "worker-response", ids::WORKER_RESPONSE },
+    { worker_frame, "worker-frame", ids::WORKER_FRAME },
     { bundle_spec, "bundle", ids::BUNDLE },
     { desired_state_export_spec, "desired-state-export", ids::DESIRED_STATE_EXPORT },
     { desired_state, "desired-state", ids::DESIRED_STATE },
+    { cost_estimate, "cost-estimate", ids::COST_ESTIMATE },
     { desired_subscription_entry, "desired-subscription-entry", ids::DESIRED_SUBSCRIPTION_ENTRY },
+    { desired_state_change_request, "desired-state-change-request", ids::DESIRED_STATE_CHANGE_REQUEST },
+    { approval_record, "approval-record", ids::APPROVAL_RECORD },
     { artifact_selector, "artifact-selector", ids::ARTIFACT_SELECTOR },
     { storefront, "storefront", ids::STOREFRONT },
     { store_product, "store-product", ids::STORE_PRODUCT },
     { store_plan, "store-plan", ids::STORE_PLAN },
     { capability_map, "capability-map", ids::CAPABILITY_MAP },
     { subscription, "subscription", ids::SUBSCRIPTION },
+    { billing_account, "billing-account", ids::BILLING_ACCOUNT },
+    { billing_account_id, "billing-account-id", ids::BILLING_ACCOUNT_ID },
+    { payment_method_ref, "payment-method-ref", ids::PAYMENT_METHOD_REF },
     { environment, "environment", ids::ENVIRONMENT },
     { theme, "theme", ids::THEME },
     { layout_section, "layout-section", ids::LAYOUT_SECTION },
@@ -385,10 +590,167 @@ schema_entries_vec! {
     { connection_kind, "connection-kind", ids::CONNECTION_KIND },
     { pack_or_component_ref, "pack-or-component-ref", ids::PACK_OR_COMPONENT_REF },
     { plan_limits, "plan-limits", ids::PLAN_LIMITS },
+    { plan_feature, "plan-feature", ids::PLAN_FEATURE },
     { price_model, "price-model", ids::PRICE_MODEL },
+    { page_request, "page-request", ids::PAGE_REQUEST },
+    { catalog_filter, "catalog-filter", ids::CATALOG_FILTER },
+    { store_product_page, "store-product-page", ids::STORE_PRODUCT_PAGE },
     { subscription_status, "subscription-status", ids::SUBSCRIPTION_STATUS },
     #[cfg(feature = "otel-keys")]
     { otlp_keys, "otlp-keys", ids::OTLP_KEYS },
-    #[cfg(feature = "time")]
     { run_result, "run-result", ids::RUN_RESULT },
 }
+
+/// Schema document embedded in the crate at build time.
+///
+/// `json` is the committed `schemas/v1/<slug>.schema.json` file, included verbatim, and
+/// `digest_hex` is the Blake3 digest of those bytes computed by the build script, so services can
+/// serve the document directly and use the digest as an `ETag`.
+#[cfg(feature = "schema")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmbeddedSchema {
+    /// Canonical `$id` of the schema.
+    pub id: &'static str,
+    /// File name used by [`crate::write_all_schemas`] (including `.schema.json`).
+    pub file_name: &'static str,
+    /// Pretty-printed schema JSON.
+    pub json: &'static str,
+    /// Hex-encoded Blake3 digest of `json`.
+    pub digest_hex: &'static str,
+}
+
+#[cfg(feature = "schema")]
+impl EmbeddedSchema {
+    /// File slug (for example `pack-manifest`).
+    pub fn slug(&self) -> &'static str {
+        self.file_name
+            .strip_suffix(".schema.json")
+            .unwrap_or(self.file_name)
+    }
+
+    /// Blake3 digest of `json`.
+    pub fn digest(&self) -> HashDigest {
+        HashDigest {
+            algo: crate::HashAlgorithm::Blake3,
+            hex: self.digest_hex.into(),
+        }
+    }
+
+    /// Strong `ETag` header value derived from the digest.
+    pub fn etag(&self) -> String {
+        alloc::format!("\"{}\"", self.digest_hex)
+    }
+}
+
+#[cfg(feature = "schema")]
+static EMBEDDED: &[EmbeddedSchema] = include!(concat!(env!("OUT_DIR"), "/embedded_schemas.rs"));
+
+/// Returns every embedded schema, sorted by `$id`.
+///
+/// The documents are the committed `schemas/v1` files compiled into the crate; regenerate them
+/// with `cargo run --bin export-schemas --all-features -- schemas/v1` after changing a type.
+#[cfg(feature = "schema")]
+pub fn embedded() -> &'static [EmbeddedSchema] {
+    EMBEDDED
+}
+
+/// Looks up an embedded schema by `$id` or file slug (for example `pack-manifest`).
+#[cfg(feature = "schema")]
+pub fn embedded_schema(id_or_slug: &str) -> Option<&'static EmbeddedSchema> {
+    match EMBEDDED.binary_search_by(|schema| schema.id.cmp(id_or_slug)) {
+        Ok(index) => Some(&EMBEDDED[index]),
+        Err(_) => EMBEDDED.iter().find(|schema| schema.slug() == id_or_slug),
+    }
+}
+
+/// Single validation failure reported by [`validate_value`].
+#[cfg(feature = "schema-validate")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value in the document.
+    pub instance_path: String,
+    /// JSON pointer to the schema keyword that failed.
+    pub schema_path: String,
+    /// Human-readable description.
+    pub message: String,
+}
+
+#[cfg(feature = "schema-validate")]
+impl core::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.instance_path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.instance_path, self.message)
+        }
+    }
+}
+
+#[cfg(feature = "schema-validate")]
+impl SchemaViolation {
+    fn document(message: impl Into<String>) -> Self {
+        Self {
+            instance_path: String::new(),
+            schema_path: String::new(),
+            message: message.into(),
+        }
+    }
+}
+
+#[cfg(feature = "schema-validate")]
+type CompiledSchema = Result<jsonschema::Validator, String>;
+
+#[cfg(feature = "schema-validate")]
+fn compiled_schemas() -> &'static std::collections::BTreeMap<&'static str, CompiledSchema> {
+    static COMPILED: std::sync::OnceLock<std::collections::BTreeMap<&'static str, CompiledSchema>> =
+        std::sync::OnceLock::new();
+    COMPILED.get_or_init(|| {
+        entries()
+            .into_iter()
+            .map(|entry| {
+                let schema = (entry.generator)();
+                let compiled =
+                    jsonschema::validator_for(schema.as_value()).map_err(|err| err.to_string());
+                (entry.id, compiled)
+            })
+            .collect()
+    })
+}
+
+/// Validates `value` against one of the schemas exported by this crate.
+///
+/// `schema_id` is either the canonical `$id` (see [`crate::ids`]) or the file slug used by
+/// [`crate::write_all_schemas`] (for example `pack-manifest`). Schemas are generated from the
+/// crate's types and compiled once on first use, so no network access is needed. An unknown id
+/// is reported as a single violation with empty paths.
+#[cfg(feature = "schema-validate")]
+pub fn validate_value(
+    schema_id: &str,
+    value: &serde_json::Value,
+) -> Result<(), Vec<SchemaViolation>> {
+    let schemas = compiled_schemas();
+    let id = embedded_schema(schema_id).map(|schema| schema.id);
+    let Some(compiled) = id.and_then(|id| schemas.get(id)) else {
+        return Err(alloc::vec![SchemaViolation::document(alloc::format!(
+            "unknown schema `{schema_id}`"
+        ))]);
+    };
+    let validator = compiled.as_ref().map_err(|err| {
+        alloc::vec![SchemaViolation::document(alloc::format!(
+            "schema `{schema_id}` failed to compile: {err}"
+        ))]
+    })?;
+    let violations: Vec<SchemaViolation> = validator
+        .iter_errors(value)
+        .map(|err| SchemaViolation {
+            instance_path: err.instance_path.to_string(),
+            schema_path: err.schema_path.to_string(),
+            message: err.to_string(),
+        })
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
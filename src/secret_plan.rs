@@ -0,0 +1,262 @@
+//! Resolution of declared secret requirements against available secrets.
+//!
+//! Packs and deployments declare [`SecretRequirement`]s; secrets providers expose
+//! [`AvailableSecret`]s. [`plan_secrets`] links the two into a [`SecretResolutionPlan`] so
+//! runtimes and CLIs report the same statuses and diagnostics.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::validate::{Diagnostic, Severity, core_diagnostic};
+use crate::{ApiKeyRef, SecretFormat, SecretKey, SecretRequirement, SecretScope};
+
+/// Where a secret value is stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum SecretSource {
+    /// API key held by a secrets provider.
+    ApiKey {
+        /// Provider reference to the key.
+        reference: ApiKeyRef,
+    },
+    /// Entry in a vault-style store.
+    Vault {
+        /// Path of the entry (for example `kv/prod/acme/db-password`).
+        path: String,
+    },
+}
+
+/// Secret a provider can supply.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct AvailableSecret {
+    /// Logical key the secret satisfies.
+    pub key: SecretKey,
+    /// Where the value is stored.
+    pub source: SecretSource,
+    /// Scope the secret is visible in; visible everywhere when absent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub scope: Option<SecretScope>,
+    /// Format of the stored value, when known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub format: Option<SecretFormat>,
+}
+
+impl AvailableSecret {
+    /// Creates an unscoped secret without format information.
+    pub fn new(key: SecretKey, source: SecretSource) -> Self {
+        Self {
+            key,
+            source,
+            scope: None,
+            format: None,
+        }
+    }
+
+    /// Returns `true` when a requirement in `scope` may read this secret.
+    ///
+    /// Unscoped secrets and requirements match everything. Otherwise environment and tenant
+    /// must be equal, and a team-scoped secret is only visible to the same team.
+    pub fn visible_in(&self, scope: Option<&SecretScope>) -> bool {
+        match (&self.scope, scope) {
+            (None, _) | (_, None) => true,
+            (Some(own), Some(requested)) => {
+                own.env == requested.env
+                    && own.tenant == requested.tenant
+                    && (own.team.is_none() || own.team == requested.team)
+            }
+        }
+    }
+}
+
+/// Outcome of resolving one requirement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum SecretResolutionStatus {
+    /// A visible secret satisfies the requirement.
+    Resolved,
+    /// No secret with the requested key exists.
+    Missing,
+    /// Secrets with the key exist, but none is visible in the requested scope.
+    Forbidden,
+}
+
+/// Resolution of a single requirement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SecretResolution {
+    /// Requirement being resolved.
+    pub requirement: SecretRequirement,
+    /// Resolution status.
+    pub status: SecretResolutionStatus,
+    /// Selected source when resolved.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub source: Option<SecretSource>,
+}
+
+/// Result of [`plan_secrets`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SecretResolutionPlan {
+    /// One entry per distinct requirement key, in declaration order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub entries: Vec<SecretResolution>,
+    /// Problems found while planning.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl SecretResolutionPlan {
+    /// Returns the first entry for `key`; see [`SecretResolutionPlan::get_in`] when the key is
+    /// requested in several scopes.
+    pub fn get(&self, key: &SecretKey) -> Option<&SecretResolution> {
+        self.entries
+            .iter()
+            .find(|entry| &entry.requirement.key == key)
+    }
+
+    /// Returns the entry for `key` requested in `scope`.
+    pub fn get_in(
+        &self,
+        key: &SecretKey,
+        scope: Option<&SecretScope>,
+    ) -> Option<&SecretResolution> {
+        self.entries.iter().find(|entry| {
+            &entry.requirement.key == key && entry.requirement.scope.as_ref() == scope
+        })
+    }
+
+    /// Returns `true` when every required secret is resolved.
+    pub fn is_complete(&self) -> bool {
+        self.entries.iter().all(|entry| {
+            !entry.requirement.required || entry.status == SecretResolutionStatus::Resolved
+        })
+    }
+
+    /// Iterates over required entries that are not resolved.
+    pub fn unresolved_required(&self) -> impl Iterator<Item = &SecretResolution> {
+        self.entries.iter().filter(|entry| {
+            entry.requirement.required && entry.status != SecretResolutionStatus::Resolved
+        })
+    }
+}
+
+/// Resolves `requirements` against `available` secrets.
+///
+/// Requirements sharing a key and scope are merged; the merged requirement is mandatory when any
+/// of them is. The same key requested in different scopes is resolved once per scope, since a
+/// secret visible in one scope may be hidden in another. The first visible candidate wins.
+/// Unresolved required secrets produce error diagnostics, unresolved optional ones warnings, and
+/// format mismatches warnings.
+pub fn plan_secrets(
+    requirements: &[SecretRequirement],
+    available: &[AvailableSecret],
+) -> SecretResolutionPlan {
+    let mut merged: Vec<SecretRequirement> = Vec::new();
+    for requirement in requirements {
+        match merged
+            .iter_mut()
+            .find(|seen| seen.key == requirement.key && seen.scope == requirement.scope)
+        {
+            Some(seen) => seen.required |= requirement.required,
+            None => merged.push(requirement.clone()),
+        }
+    }
+
+    let mut plan = SecretResolutionPlan::default();
+    for requirement in merged {
+        let path = Some(format!("secrets.{}", requirement.key.as_str()));
+        let severity = if requirement.required {
+            Severity::Error
+        } else {
+            Severity::Warn
+        };
+        let mut candidates = available
+            .iter()
+            .filter(|secret| secret.key == requirement.key)
+            .peekable();
+        let exists = candidates.peek().is_some();
+        let selected = candidates.find(|secret| secret.visible_in(requirement.scope.as_ref()));
+
+        let (status, source) = match selected {
+            Some(secret) => {
+                match (&requirement.format, &secret.format) {
+                    (Some(expected), Some(actual)) if expected != actual => {
+                        plan.diagnostics.push(core_diagnostic(
+                            Severity::Warn,
+                            "SECRET_FORMAT_MISMATCH",
+                            &format!(
+                                "Secret '{}' is stored as {actual:?} but {expected:?} is expected.",
+                                requirement.key.as_str()
+                            ),
+                            path,
+                            None,
+                        ));
+                    }
+                    _ => {}
+                }
+                (
+                    SecretResolutionStatus::Resolved,
+                    Some(secret.source.clone()),
+                )
+            }
+            None if exists => {
+                plan.diagnostics.push(core_diagnostic(
+                    severity,
+                    "SECRET_FORBIDDEN",
+                    &format!(
+                        "Secret '{}' exists but is not visible in the requested scope.",
+                        requirement.key.as_str()
+                    ),
+                    path,
+                    Some("Grant the secret to the requesting environment, tenant, or team.".into()),
+                ));
+                (SecretResolutionStatus::Forbidden, None)
+            }
+            None => {
+                plan.diagnostics.push(core_diagnostic(
+                    severity,
+                    "SECRET_MISSING",
+                    &format!("Secret '{}' is not available.", requirement.key.as_str()),
+                    path,
+                    Some("Create the secret in a configured secrets provider.".into()),
+                ));
+                (SecretResolutionStatus::Missing, None)
+            }
+        };
+        plan.entries.push(SecretResolution {
+            requirement,
+            status,
+            source,
+        });
+    }
+    plan
+}
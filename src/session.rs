@@ -1,14 +1,17 @@
 //! Session identity and cursor helpers.
 
 use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
 use alloc::string::String;
+use alloc::vec::Vec;
 
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::{FlowId, PackId, TenantCtx};
+use crate::{DurationMs, FlowId, PackId, TenantCtx, UnixMillis};
 
 use sha2::{Digest, Sha256};
 
@@ -30,6 +33,61 @@ impl SessionKey {
         Self(value.into())
     }
 
+    /// Derives a deterministic, tenant-scoped session key for a channel peer.
+    ///
+    /// The key has the shape `{env}:{tenant}:{digest}` where `digest` is the first 128 bits of a
+    /// SHA-256 over the environment, tenant, team, channel, and peer. The readable prefix lets
+    /// storage layers shard per tenant; the digest keeps raw peer identifiers out of the key.
+    pub fn derive(ctx: &TenantCtx, channel: &str, peer: &str) -> Self {
+        let mut hasher = Sha256::new();
+        for part in [
+            ctx.env.as_str(),
+            ctx.tenant_id.as_str(),
+            ctx.team_id.as_ref().map(|team| team.as_str()).unwrap_or(""),
+            channel,
+            peer,
+        ] {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part.as_bytes());
+        }
+        let digest = hasher.finalize();
+        Self(format!(
+            "{}:{}:{}",
+            ctx.env.as_str(),
+            ctx.tenant_id.as_str(),
+            hex_encode(&digest[..DERIVED_DIGEST_BYTES])
+        ))
+    }
+
+    /// Returns the `{env}:{tenant}` prefix of a key produced by [`SessionKey::derive`].
+    ///
+    /// Returns `None` for keys that do not follow the derived format.
+    pub fn tenant_prefix(&self) -> Option<&str> {
+        self.derived_parts()
+            .map(|(env, tenant, _)| &self.0[..env.len() + 1 + tenant.len()])
+    }
+
+    /// Returns the environment segment of a derived key.
+    pub fn env(&self) -> Option<&str> {
+        self.derived_parts().map(|(env, _, _)| env)
+    }
+
+    /// Returns the tenant segment of a derived key.
+    pub fn tenant(&self) -> Option<&str> {
+        self.derived_parts().map(|(_, tenant, _)| tenant)
+    }
+
+    fn derived_parts(&self) -> Option<(&str, &str, &str)> {
+        let mut parts = self.0.split(':');
+        let (env, tenant, digest) = (parts.next()?, parts.next()?, parts.next()?);
+        let is_digest = digest.len() == DERIVED_DIGEST_BYTES * 2
+            && digest
+                .bytes()
+                .all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'));
+        (parts.next().is_none() && !env.is_empty() && !tenant.is_empty() && is_digest)
+            .then_some((env, tenant, digest))
+    }
+
     /// Generates a random session key using [`uuid`], when enabled.
     #[cfg(feature = "uuid")]
     pub fn generate() -> Self {
@@ -62,6 +120,8 @@ impl From<uuid::Uuid> for SessionKey {
     }
 }
 
+const DERIVED_DIGEST_BYTES: usize = 16;
+
 const DEFAULT_CANONICAL_ANCHOR: &str = "conversation";
 const DEFAULT_CANONICAL_USER: &str = "user";
 
@@ -85,6 +145,39 @@ pub fn canonical_session_key(
     ))
 }
 
+/// Number of hops [`SessionCursor::advance_to`] keeps unless told otherwise.
+pub const DEFAULT_CURSOR_HISTORY_LIMIT: usize = 32;
+
+/// Visit of a session to a flow node, recorded in [`SessionCursor::history`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CursorHop {
+    /// Identifier of the visited node.
+    pub node_id: String,
+    /// Instant the session entered the node.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub entered_at: UnixMillis,
+    /// Instant the session left the node; absent while it is still there.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub left_at: Option<UnixMillis>,
+}
+
 /// Cursor pointing at a session's position in a flow graph.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -104,6 +197,13 @@ pub struct SessionCursor {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub outbox_marker: Option<String>,
+    /// Most recent node visits, oldest first; bounded by the limit passed to
+    /// [`SessionCursor::advance_to`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub history: Vec<CursorHop>,
 }
 
 impl SessionCursor {
@@ -113,9 +213,36 @@ impl SessionCursor {
             node_pointer: node_pointer.into(),
             wait_reason: None,
             outbox_marker: None,
+            history: Vec::new(),
         }
     }
 
+    /// Moves the cursor to `node_pointer` at `at`, recording the hop.
+    ///
+    /// The open hop, if any, is closed at `at`, and the oldest hops are dropped so at most
+    /// `history_limit` remain. Wait reason and outbox marker are left untouched.
+    pub fn advance_to(
+        &mut self,
+        node_pointer: impl Into<String>,
+        at: UnixMillis,
+        history_limit: usize,
+    ) {
+        if let Some(open) = self.history.last_mut() {
+            if open.left_at.is_none() {
+                open.left_at = Some(at);
+            }
+        }
+        let node_pointer = node_pointer.into();
+        self.history.push(CursorHop {
+            node_id: node_pointer.clone(),
+            entered_at: at,
+            left_at: None,
+        });
+        let excess = self.history.len().saturating_sub(history_limit);
+        self.history.drain(..excess);
+        self.node_pointer = node_pointer;
+    }
+
     /// Assigns a wait reason to the cursor.
     pub fn with_wait_reason(mut self, reason: impl Into<String>) -> Self {
         self.wait_reason = Some(reason.into());
@@ -129,6 +256,245 @@ impl SessionCursor {
     }
 }
 
+/// Lifecycle of a [`ConversationState`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ConversationStatus {
+    /// The conversation accepts input.
+    Active,
+    /// The conversation expired before it finished.
+    TimedOut,
+    /// The conversation was cancelled by the user or runtime.
+    Cancelled,
+}
+
+/// Question from a QA spec that the conversation is waiting on.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PendingQuestion {
+    /// Question identifier within the QA spec.
+    pub question_id: String,
+    /// Reference to the QA spec declaring the question (URI or pack path).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub spec_ref: Option<String>,
+    /// Slot that receives the answer.
+    pub slot: String,
+    /// Instant the question was asked.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub asked_at: UnixMillis,
+}
+
+impl PendingQuestion {
+    /// Creates a pending question whose answer fills the slot named after the question.
+    pub fn new(question_id: impl Into<String>, asked_at: UnixMillis) -> Self {
+        let question_id = question_id.into();
+        Self {
+            slot: question_id.clone(),
+            question_id,
+            spec_ref: None,
+            asked_at,
+        }
+    }
+
+    /// Sets the QA spec reference.
+    pub fn with_spec_ref(mut self, spec_ref: impl Into<String>) -> Self {
+        self.spec_ref = Some(spec_ref.into());
+        self
+    }
+
+    /// Stores the answer in `slot` instead of the question identifier.
+    pub fn with_slot(mut self, slot: impl Into<String>) -> Self {
+        self.slot = slot.into();
+        self
+    }
+}
+
+/// Errors returned by [`ConversationState`] transitions.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ConversationError {
+    /// The conversation already ended.
+    #[error("conversation is {status:?} and no longer accepts input")]
+    Closed {
+        /// Status the conversation ended with.
+        status: ConversationStatus,
+    },
+    /// The conversation expired; call [`ConversationState::timeout`].
+    #[error("conversation expired")]
+    Expired,
+    /// No question is awaiting an answer.
+    #[error("no question is pending")]
+    NoPendingQuestion,
+    /// The answer targets a different question than the pending one.
+    #[error("answer targets question `{answered}` but `{pending}` is pending")]
+    UnexpectedAnswer {
+        /// Pending question identifier.
+        pending: String,
+        /// Question identifier the answer was given for.
+        answered: String,
+    },
+    /// The conversation has not reached its expiry yet.
+    #[error("conversation has not expired")]
+    NotExpired,
+}
+
+/// Multi-turn messaging state persisted alongside a session.
+///
+/// Chat runtimes move a conversation forward with [`ask`](Self::ask) and
+/// [`answer`](Self::answer), and end it with [`timeout`](Self::timeout) or
+/// [`cancel`](Self::cancel). Slots collected so far survive a timeout or cancellation so the
+/// runtime can report partial input.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ConversationState {
+    /// Current step, usually the flow node driving the conversation.
+    pub step: String,
+    /// Lifecycle status.
+    pub status: ConversationStatus,
+    /// Answers collected so far, keyed by slot name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slots: BTreeMap<String, Value>,
+    /// Question awaiting an answer.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub pending_question: Option<PendingQuestion>,
+    /// Instant after which the conversation times out.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub expires_at: Option<UnixMillis>,
+}
+
+impl ConversationState {
+    /// Creates an active conversation at `step` with no slots.
+    pub fn new(step: impl Into<String>) -> Self {
+        Self {
+            step: step.into(),
+            status: ConversationStatus::Active,
+            slots: BTreeMap::new(),
+            pending_question: None,
+            expires_at: None,
+        }
+    }
+
+    /// Sets the expiry instant.
+    pub fn with_expires_at(mut self, expires_at: UnixMillis) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Returns `true` while the conversation accepts input.
+    pub fn is_active(&self) -> bool {
+        self.status == ConversationStatus::Active
+    }
+
+    /// Returns `true` when the conversation has an expiry at or before `now`.
+    pub fn is_expired(&self, now: UnixMillis) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Moves to `step` and waits for an answer to `question`, replacing any pending question.
+    pub fn ask(
+        &mut self,
+        step: impl Into<String>,
+        question: PendingQuestion,
+    ) -> Result<(), ConversationError> {
+        self.ensure_open(question.asked_at)?;
+        self.step = step.into();
+        self.pending_question = Some(question);
+        Ok(())
+    }
+
+    /// Records `value` as the answer to the pending question `question_id`.
+    ///
+    /// The value is stored in the question's slot and the question is cleared.
+    pub fn answer(
+        &mut self,
+        question_id: &str,
+        value: Value,
+        now: UnixMillis,
+    ) -> Result<(), ConversationError> {
+        self.ensure_open(now)?;
+        let pending = self
+            .pending_question
+            .as_ref()
+            .ok_or(ConversationError::NoPendingQuestion)?;
+        if pending.question_id != question_id {
+            return Err(ConversationError::UnexpectedAnswer {
+                pending: pending.question_id.clone(),
+                answered: question_id.to_owned(),
+            });
+        }
+        if let Some(pending) = self.pending_question.take() {
+            self.slots.insert(pending.slot, value);
+        }
+        Ok(())
+    }
+
+    /// Ends the conversation as timed out once its expiry has passed.
+    pub fn timeout(&mut self, now: UnixMillis) -> Result<(), ConversationError> {
+        if !self.is_active() {
+            return Err(ConversationError::Closed {
+                status: self.status,
+            });
+        }
+        if !self.is_expired(now) {
+            return Err(ConversationError::NotExpired);
+        }
+        self.close(ConversationStatus::TimedOut);
+        Ok(())
+    }
+
+    /// Cancels the conversation.
+    pub fn cancel(&mut self) -> Result<(), ConversationError> {
+        if !self.is_active() {
+            return Err(ConversationError::Closed {
+                status: self.status,
+            });
+        }
+        self.close(ConversationStatus::Cancelled);
+        Ok(())
+    }
+
+    fn ensure_open(&self, now: UnixMillis) -> Result<(), ConversationError> {
+        if !self.is_active() {
+            return Err(ConversationError::Closed {
+                status: self.status,
+            });
+        }
+        if self.is_expired(now) {
+            return Err(ConversationError::Expired);
+        }
+        Ok(())
+    }
+
+    fn close(&mut self, status: ConversationStatus) {
+        self.status = status;
+        self.pending_question = None;
+    }
+}
+
 /// Persisted session payload describing how to resume a flow.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -148,6 +514,137 @@ pub struct SessionData {
     pub cursor: SessionCursor,
     /// Serialized execution context/state snapshot.
     pub context_json: String,
+    /// Retention limits for the session.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub ttl: Option<SessionTtl>,
+    /// Instant of the last turn, used for idle expiry.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub last_activity_at: Option<UnixMillis>,
+    /// Number of turns processed so far.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub turns: u32,
+}
+
+impl SessionData {
+    /// Records a processed turn at `now`.
+    pub fn record_turn(&mut self, now: UnixMillis) {
+        self.turns = self.turns.saturating_add(1);
+        self.last_activity_at = Some(now);
+    }
+
+    /// Returns `true` when the session's [`SessionTtl`] has run out at `now`.
+    pub fn is_expired(&self, now: UnixMillis) -> bool {
+        self.ttl
+            .as_ref()
+            .is_some_and(|ttl| ttl.is_expired(now, self.last_activity_at, self.turns))
+    }
+}
+
+/// Retention limits for a persisted session; a session expires when any limit is reached.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SessionTtl {
+    /// Absolute instant after which the session expires.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub expires_at: Option<UnixMillis>,
+    /// Inactivity after which the session expires.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub idle_timeout: Option<DurationMs>,
+    /// Number of turns after which the session expires.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_turns: Option<u32>,
+}
+
+impl SessionTtl {
+    /// Returns `true` once `now` reaches the absolute or idle deadline, or `turns` reaches
+    /// `max_turns`.
+    pub fn is_expired(
+        &self,
+        now: UnixMillis,
+        last_activity_at: Option<UnixMillis>,
+        turns: u32,
+    ) -> bool {
+        self.deadline(last_activity_at)
+            .is_some_and(|deadline| now >= deadline)
+            || self.max_turns.is_some_and(|max_turns| turns >= max_turns)
+    }
+
+    /// Earliest instant at which the session expires by time, suitable for a store-level TTL.
+    pub fn deadline(&self, last_activity_at: Option<UnixMillis>) -> Option<UnixMillis> {
+        let idle = self
+            .idle_timeout
+            .zip(last_activity_at)
+            .and_then(|(timeout, last)| last.checked_add(timeout));
+        match (self.expires_at, idle) {
+            (Some(absolute), Some(idle)) => Some(absolute.min(idle)),
+            (absolute, idle) => absolute.or(idle),
+        }
+    }
+}
+
+/// How a session store reclaims space beyond expiry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum EvictionPolicy {
+    /// Sessions are removed only when their [`SessionTtl`] runs out.
+    #[default]
+    ExpireOnly,
+    /// Beyond `max_sessions`, the sessions with the oldest activity are removed first.
+    LeastRecentlyUsed {
+        /// Sessions kept per tenant.
+        max_sessions: u64,
+    },
+    /// Beyond `max_sessions`, the oldest sessions are removed first.
+    OldestFirst {
+        /// Sessions kept per tenant.
+        max_sessions: u64,
+    },
+}
+
+impl EvictionPolicy {
+    /// Session cap per tenant, if any.
+    pub fn max_sessions(&self) -> Option<u64> {
+        match self {
+            Self::ExpireOnly => None,
+            Self::LeastRecentlyUsed { max_sessions } | Self::OldestFirst { max_sessions } => {
+                Some(*max_sessions)
+            }
+        }
+    }
 }
 
 /// Stable scope describing where a reply is anchored (conversation/thread/reply).
@@ -197,7 +694,7 @@ impl ReplyScope {
     }
 }
 
-fn hex_encode(bytes: &[u8]) -> String {
+pub(crate) fn hex_encode(bytes: &[u8]) -> String {
     const HEX: &[u8; 16] = b"0123456789abcdef";
     let mut out = String::with_capacity(bytes.len() * 2);
     for &byte in bytes {
@@ -211,15 +708,43 @@ fn hex_encode(bytes: &[u8]) -> String {
 mod tests {
     use super::*;
 
-    #[cfg(feature = "serde")]
-    use serde_json::Value;
-
     #[test]
     fn canonical_session_key_includes_components() {
         let key = canonical_session_key("tenant", "webhook", Some("room-1"), Some("user-5"));
         assert_eq!(key.as_str(), "tenant:webhook:room-1:user-5");
     }
 
+    #[test]
+    fn derived_session_key_is_tenant_scoped() {
+        let ctx = TenantCtx::new(
+            "prod".parse().unwrap_or_else(|err| panic!("env: {err}")),
+            "acme".parse().unwrap_or_else(|err| panic!("tenant: {err}")),
+        );
+        let key = SessionKey::derive(&ctx, "telegram", "user-42");
+        assert_eq!(key, SessionKey::derive(&ctx, "telegram", "user-42"));
+        assert_ne!(key, SessionKey::derive(&ctx, "telegram", "user-43"));
+        assert_ne!(key, SessionKey::derive(&ctx, "telegra", "muser-42"));
+        assert_eq!(key.tenant_prefix(), Some("prod:acme"));
+        assert_eq!(key.env(), Some("prod"));
+        assert_eq!(key.tenant(), Some("acme"));
+        assert!(!key.as_str().contains("user-42"));
+
+        let other = TenantCtx::new(
+            "prod".parse().unwrap_or_else(|err| panic!("env: {err}")),
+            "globex"
+                .parse()
+                .unwrap_or_else(|err| panic!("tenant: {err}")),
+        );
+        let digest = |key: &SessionKey| key.as_str().rsplit(':').next().map(str::to_owned);
+        assert_ne!(
+            digest(&SessionKey::derive(&other, "telegram", "user-42")),
+            digest(&key)
+        );
+
+        let legacy = canonical_session_key("tenant", "webhook", None, None);
+        assert_eq!(legacy.tenant_prefix(), None);
+    }
+
     #[test]
     fn canonical_session_key_defaults_anchor_and_user() {
         let key = canonical_session_key("tenant", "webhook", None, None);
@@ -274,6 +799,101 @@ mod tests {
         assert_eq!(roundtrip, scope);
     }
 
+    #[test]
+    fn conversation_state_collects_answers_and_closes() {
+        let mut state = ConversationState::new("collect-order")
+            .with_expires_at(UnixMillis::from_millis(10_000));
+        assert_eq!(
+            state.answer("size", Value::from("large"), UnixMillis::from_millis(1)),
+            Err(ConversationError::NoPendingQuestion)
+        );
+
+        let question = PendingQuestion::new("size", UnixMillis::from_millis(1))
+            .with_spec_ref("qa/order.cbor")
+            .with_slot("pizza_size");
+        state
+            .ask("ask-size", question)
+            .unwrap_or_else(|err| panic!("ask failed: {err}"));
+        assert_eq!(
+            state.answer("topping", Value::from("ham"), UnixMillis::from_millis(2)),
+            Err(ConversationError::UnexpectedAnswer {
+                pending: "size".to_owned(),
+                answered: "topping".to_owned(),
+            })
+        );
+        state
+            .answer("size", Value::from("large"), UnixMillis::from_millis(2))
+            .unwrap_or_else(|err| panic!("answer failed: {err}"));
+        assert_eq!(state.step, "ask-size");
+        assert_eq!(state.slots.get("pizza_size"), Some(&Value::from("large")));
+        assert!(state.pending_question.is_none());
+
+        let late = PendingQuestion::new("topping", UnixMillis::from_millis(10_000));
+        assert_eq!(
+            state.ask("ask-topping", late),
+            Err(ConversationError::Expired)
+        );
+        assert_eq!(
+            state.timeout(UnixMillis::from_millis(9_999)),
+            Err(ConversationError::NotExpired)
+        );
+        state
+            .timeout(UnixMillis::from_millis(10_000))
+            .unwrap_or_else(|err| panic!("timeout failed: {err}"));
+        assert_eq!(state.status, ConversationStatus::TimedOut);
+        assert_eq!(
+            state.cancel(),
+            Err(ConversationError::Closed {
+                status: ConversationStatus::TimedOut
+            })
+        );
+        assert_eq!(state.slots.len(), 1);
+    }
+
+    #[test]
+    fn conversation_state_cancel_clears_pending_question() {
+        let mut state = ConversationState::new("start");
+        state
+            .ask(
+                "ask-name",
+                PendingQuestion::new("name", UnixMillis::from_millis(0)),
+            )
+            .unwrap_or_else(|err| panic!("ask failed: {err}"));
+        state
+            .cancel()
+            .unwrap_or_else(|err| panic!("cancel failed: {err}"));
+        assert_eq!(state.status, ConversationStatus::Cancelled);
+        assert!(state.pending_question.is_none());
+        assert_eq!(
+            state.timeout(UnixMillis::from_millis(0)),
+            Err(ConversationError::Closed {
+                status: ConversationStatus::Cancelled
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn conversation_state_roundtrip() {
+        let mut state =
+            ConversationState::new("start").with_expires_at(UnixMillis::from_millis(60_000));
+        state.slots.insert("name".to_owned(), Value::from("Ada"));
+        state
+            .ask(
+                "ask-email",
+                PendingQuestion::new("email", UnixMillis::from_millis(5)),
+            )
+            .unwrap_or_else(|err| panic!("ask failed: {err}"));
+
+        let value = serde_json::to_value(&state)
+            .unwrap_or_else(|err| panic!("serialize conversation failed: {err}"));
+        assert_eq!(value["status"], "active");
+        assert_eq!(value["pending_question"]["slot"], "email");
+        let roundtrip: ConversationState = serde_json::from_value(value)
+            .unwrap_or_else(|err| panic!("deserialize conversation failed: {err}"));
+        assert_eq!(roundtrip, state);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn session_data_pack_id_is_optional() {
@@ -292,6 +912,9 @@ mod tests {
             pack_id: None,
             cursor: SessionCursor::new("node-1"),
             context_json: "{}".to_owned(),
+            ttl: None,
+            last_activity_at: None,
+            turns: 0,
         };
 
         let value = serde_json::to_value(&data)
@@ -320,4 +943,54 @@ mod tests {
             .unwrap_or_else(|err| panic!("deserialize session failed: {err}"));
         assert_eq!(roundtrip.pack_id, data_with_pack.pack_id);
     }
+
+    #[test]
+    fn session_ttl_expires_on_first_limit() {
+        let at = UnixMillis::from_millis;
+        let ttl = SessionTtl {
+            expires_at: Some(at(10_000)),
+            idle_timeout: Some(DurationMs::from_millis(3_000)),
+            max_turns: Some(3),
+        };
+        assert_eq!(ttl.deadline(None), Some(at(10_000)));
+        assert_eq!(ttl.deadline(Some(at(2_000))), Some(at(5_000)));
+        assert_eq!(ttl.deadline(Some(at(9_000))), Some(at(10_000)));
+        assert!(!ttl.is_expired(at(4_999), Some(at(2_000)), 2));
+        assert!(ttl.is_expired(at(5_000), Some(at(2_000)), 2));
+        assert!(ttl.is_expired(at(1_000), Some(at(1_000)), 3));
+        assert!(!SessionTtl::default().is_expired(at(i64::MAX), None, u32::MAX));
+
+        let mut data = SessionData {
+            tenant_ctx: TenantCtx::new(
+                "prod".parse().unwrap_or_else(|err| panic!("env: {err}")),
+                "acme".parse().unwrap_or_else(|err| panic!("tenant: {err}")),
+            ),
+            flow_id: "flow-1".parse().unwrap_or_else(|err| panic!("flow: {err}")),
+            pack_id: None,
+            cursor: SessionCursor::new("node-1"),
+            context_json: "{}".to_owned(),
+            ttl: Some(ttl),
+            last_activity_at: None,
+            turns: 0,
+        };
+        data.record_turn(at(1_000));
+        data.record_turn(at(2_000));
+        assert!(!data.is_expired(at(4_000)));
+        data.record_turn(at(3_000));
+        assert!(data.is_expired(at(3_000)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn eviction_policy_serializes_with_kind() {
+        let policy = EvictionPolicy::LeastRecentlyUsed { max_sessions: 500 };
+        let value = serde_json::to_value(policy)
+            .unwrap_or_else(|err| panic!("serialize policy failed: {err}"));
+        assert_eq!(
+            value,
+            serde_json::json!({"kind": "least_recently_used", "max_sessions": 500})
+        );
+        assert_eq!(policy.max_sessions(), Some(500));
+        assert_eq!(EvictionPolicy::default().max_sessions(), None);
+    }
 }
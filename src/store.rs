@@ -1,19 +1,26 @@
 //! Storefront, catalog, subscription, and desired state shared models.
 
 use alloc::collections::BTreeMap;
-use alloc::string::String;
+use alloc::format;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
+use semver::Version;
+
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::validate::Diagnostic;
 use crate::{
-    ArtifactRef, BundleId, CollectionId, ComponentRef, DistributorRef, EnvironmentRef,
-    MetadataRecordRef, PackId, PackRef, SemverReq, StoreFrontId, StorePlanId, StoreProductId,
-    SubscriptionId, TenantCtx,
+    ArtifactRef, BillingAccountId, BundleId, ChangeRequestId, CollectionId, ComponentRef,
+    ConfigOverride, ConfigValue, CountryCode, CurrencyCode, DistributorRef, EnvironmentRef,
+    ErrorCode, EventSubscriptionSpec, GResult, GreenticError, HashAlgorithm, HashDigest, LocaleTag,
+    MaintenanceWindowId, MergeStrategy, MetadataRecordRef, MoneyMicro, PackId, PackRef,
+    PaymentMethodRef, Percent, RegionCode, RegionConstraint, RoundingMode, SemverReq, StoreFrontId,
+    StorePlanId, StoreProductId, SubscriptionId, TenantCtx, UnixMillis, UrlRef, UserId,
 };
 
 /// Visual theme tokens for a storefront.
@@ -38,19 +45,19 @@ pub struct Theme {
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub logo_url: Option<String>,
+    pub logo_url: Option<UrlRef>,
     /// Optional favicon URL.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub favicon_url: Option<String>,
+    pub favicon_url: Option<UrlRef>,
     /// Optional hero image URL.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub hero_image_url: Option<String>,
+    pub hero_image_url: Option<UrlRef>,
     /// Optional hero title.
     #[cfg_attr(
         feature = "serde",
@@ -262,11 +269,35 @@ pub struct StoreFront {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub worker_id: Option<String>,
+    /// Locale served when the visitor's locale is not supported.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub default_locale: Option<LocaleTag>,
+    /// Locales the storefront content is translated into.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub locales: Vec<LocaleTag>,
     /// Additional metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: BTreeMap<String, Value>,
 }
 
+impl StoreFront {
+    /// Picks the locale to render for a visitor requesting `requested`.
+    ///
+    /// Walks the requested tag's fallback chain against [`StoreFront::locales`] and falls back
+    /// to [`StoreFront::default_locale`].
+    pub fn resolve_locale(&self, requested: Option<&LocaleTag>) -> Option<&LocaleTag> {
+        requested
+            .and_then(|tag| tag.best_match(&self.locales))
+            .or(self.default_locale.as_ref())
+    }
+}
+
 /// Kinds of products exposed by the store catalog.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -443,6 +474,84 @@ impl<'de> Deserialize<'de> for VersionStrategy {
 /// Map of capability group -> list of capability values.
 pub type CapabilityMap = BTreeMap<String, Vec<String>>;
 
+/// Coverage checks between capability maps.
+///
+/// Implemented for [`CapabilityMap`]; a required group with no values only needs the group to be
+/// present in the available map.
+pub trait CapabilityMapExt {
+    /// Returns `true` when `available` covers every required group and value.
+    fn satisfies(&self, available: &CapabilityMap) -> bool;
+
+    /// Lists the required groups and values missing from `available`.
+    fn gaps(&self, available: &CapabilityMap) -> CapabilityGapReport;
+}
+
+impl CapabilityMapExt for CapabilityMap {
+    fn satisfies(&self, available: &CapabilityMap) -> bool {
+        self.gaps(available).is_satisfied()
+    }
+
+    fn gaps(&self, available: &CapabilityMap) -> CapabilityGapReport {
+        let mut missing = CapabilityMap::new();
+        for (group, values) in self {
+            let Some(have) = available.get(group) else {
+                missing.insert(group.clone(), values.clone());
+                continue;
+            };
+            let absent: Vec<String> = values
+                .iter()
+                .filter(|value| !have.contains(value))
+                .cloned()
+                .collect();
+            if !absent.is_empty() {
+                missing.insert(group.clone(), absent);
+            }
+        }
+        CapabilityGapReport { missing }
+    }
+}
+
+/// Required capabilities that an environment does not report.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CapabilityGapReport {
+    /// Missing values per group; a group mapped to no values is missing entirely.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub missing: CapabilityMap,
+}
+
+impl CapabilityGapReport {
+    /// Returns `true` when nothing is missing.
+    pub fn is_satisfied(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Converts the gaps into [`CompatibilityIssue::MissingHostCapability`] entries; a group
+    /// missing entirely is reported with capability `*`.
+    pub fn issues(&self) -> Vec<CompatibilityIssue> {
+        self.missing
+            .iter()
+            .flat_map(|(group, values)| {
+                let values: Vec<&str> = if values.is_empty() {
+                    Vec::from(["*"])
+                } else {
+                    values.iter().map(String::as_str).collect()
+                };
+                values
+                    .into_iter()
+                    .map(|value| CompatibilityIssue::MissingHostCapability {
+                        group: group.clone(),
+                        capability: value.into(),
+                    })
+            })
+            .collect()
+    }
+}
+
 /// Catalog product describing a component, flow, or pack.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -497,11 +606,221 @@ pub struct StoreProduct {
     pub default_plan_id: Option<StorePlanId>,
     /// Convenience flag indicating the default plan is free.
     pub is_free: bool,
+    /// Runtime and catalog compatibility requirements, if declared.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub compatibility: Option<ProductCompatibility>,
+    /// Catalog lifecycle state.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lifecycle: LifecycleState,
+    /// Instant from which the product counts as deprecated.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub deprecated_at: Option<UnixMillis>,
+    /// Instant from which the product counts as retired.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub sunset_at: Option<UnixMillis>,
+    /// Product customers should move to.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub replacement_id: Option<StoreProductId>,
     /// Additional metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: BTreeMap<String, Value>,
 }
 
+impl StoreProduct {
+    /// Returns the lifecycle state in effect at `now`, taking `deprecated_at` and `sunset_at`
+    /// into account.
+    pub fn lifecycle_at(&self, now: UnixMillis) -> LifecycleState {
+        self.lifecycle
+            .effective_at(self.deprecated_at, self.sunset_at, now)
+    }
+
+    /// Returns `true` when new subscriptions may be created at `now`.
+    pub fn is_purchasable(&self, now: UnixMillis) -> bool {
+        self.lifecycle_at(now).is_purchasable()
+    }
+
+    /// Capabilities the product declares (including required host capabilities) that the
+    /// environment does not report; the store blocks subscription unless the report is empty.
+    pub fn capability_gaps(&self, environment: &CapabilityMap) -> CapabilityGapReport {
+        let mut required = self.capabilities.clone();
+        if let Some(compatibility) = &self.compatibility {
+            for (group, values) in &compatibility.required_host_capabilities {
+                let merged = required.entry(group.clone()).or_default();
+                for value in values {
+                    if !merged.contains(value) {
+                        merged.push(value.clone());
+                    }
+                }
+            }
+        }
+        required.gaps(environment)
+    }
+}
+
+/// Catalog lifecycle of a product or plan.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum LifecycleState {
+    /// Early access; purchasable but without stability guarantees.
+    Preview,
+    /// Generally available.
+    #[default]
+    Ga,
+    /// Still served to existing subscribers but no longer sold.
+    Deprecated,
+    /// No longer served.
+    Retired,
+}
+
+impl LifecycleState {
+    /// Returns `true` for states that accept new subscriptions.
+    pub fn is_purchasable(self) -> bool {
+        matches!(self, Self::Preview | Self::Ga)
+    }
+
+    /// Advances the declared state once `deprecated_at` or `sunset_at` has passed.
+    ///
+    /// Timestamps only move the state forward; a retired entry never reports an earlier state.
+    pub fn effective_at(
+        self,
+        deprecated_at: Option<UnixMillis>,
+        sunset_at: Option<UnixMillis>,
+        now: UnixMillis,
+    ) -> Self {
+        let reached = |at: Option<UnixMillis>| at.is_some_and(|at| now >= at);
+        let scheduled = if reached(sunset_at) {
+            Self::Retired
+        } else if reached(deprecated_at) {
+            Self::Deprecated
+        } else {
+            self
+        };
+        self.max(scheduled)
+    }
+}
+
+/// Compatibility matrix declared by a store product.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ProductCompatibility {
+    /// Runner versions able to host the product.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub runner_versions: Option<SemverReq>,
+    /// Host capabilities that must be available (capability group -> values).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub required_host_capabilities: CapabilityMap,
+    /// Products that cannot be subscribed alongside this one.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub conflicts_with: Vec<StoreProductId>,
+}
+
+impl ProductCompatibility {
+    /// Returns `true` when the runner version satisfies [`Self::runner_versions`].
+    pub fn supports_runner(&self, runner_version: &Version) -> bool {
+        self.runner_versions
+            .as_ref()
+            .is_none_or(|req| req.to_version_req().matches(runner_version))
+    }
+
+    /// Checks the product against a target environment, returning every blocking issue.
+    pub fn check(
+        &self,
+        runner_version: &Version,
+        host_capabilities: &CapabilityMap,
+        subscribed: &[StoreProductId],
+    ) -> Vec<CompatibilityIssue> {
+        let mut issues = Vec::new();
+        if let Some(required) = &self.runner_versions
+            && !self.supports_runner(runner_version)
+        {
+            issues.push(CompatibilityIssue::UnsupportedRunner {
+                required: required.clone(),
+                actual: runner_version.to_string(),
+            });
+        }
+        issues.extend(
+            self.required_host_capabilities
+                .gaps(host_capabilities)
+                .issues(),
+        );
+        for product in &self.conflicts_with {
+            if subscribed.contains(product) {
+                issues.push(CompatibilityIssue::ConflictingProduct {
+                    product: product.clone(),
+                });
+            }
+        }
+        issues
+    }
+}
+
+/// Reason a product cannot be subscribed in a given environment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum CompatibilityIssue {
+    /// Runner version falls outside the supported range.
+    UnsupportedRunner {
+        /// Declared runner requirement.
+        required: SemverReq,
+        /// Runner version found in the environment.
+        actual: String,
+    },
+    /// Host lacks a required capability.
+    MissingHostCapability {
+        /// Capability group.
+        group: String,
+        /// Missing capability value.
+        capability: String,
+    },
+    /// A conflicting product is already subscribed.
+    ConflictingProduct {
+        /// Conflicting product identifier.
+        product: StoreProductId,
+    },
+}
+
 /// Pricing model for a plan.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -512,8 +831,8 @@ pub enum PriceModel {
     Free,
     /// Flat recurring price.
     Flat {
-        /// Amount in micro-units per period.
-        amount_micro: u64,
+        /// Price per period.
+        price: MoneyMicro,
         /// Billing period length in days.
         period_days: u16,
     },
@@ -521,8 +840,8 @@ pub enum PriceModel {
     Metered {
         /// Included units per period.
         included_units: u64,
-        /// Overage rate per additional unit (micro-units).
-        overage_rate_micro: u64,
+        /// Overage rate per additional unit.
+        overage_rate: MoneyMicro,
         /// Unit label (for example `build-minute`).
         unit_label: String,
     },
@@ -533,6 +852,17 @@ pub enum PriceModel {
     },
 }
 
+impl PriceModel {
+    /// Currency the plan is priced in; `None` for free and enterprise plans.
+    pub fn currency(&self) -> Option<&CurrencyCode> {
+        match self {
+            Self::Flat { price, .. } => Some(&price.currency),
+            Self::Metered { overage_rate, .. } => Some(&overage_rate.currency),
+            Self::Free | Self::Enterprise { .. } => None,
+        }
+    }
+}
+
 /// Plan limits used for entitlements.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -561,6 +891,127 @@ pub struct PlanLimits {
     pub metadata: BTreeMap<String, Value>,
 }
 
+/// Amount of a feature granted by a plan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum PlanFeatureValue {
+    /// On/off feature.
+    Included(bool),
+    /// Countable feature capped at the given quantity.
+    Quantity(u64),
+    /// Countable feature without a cap.
+    Unlimited,
+}
+
+impl PlanFeatureValue {
+    /// Returns `true` when the feature is available at all.
+    pub fn is_included(&self) -> bool {
+        match self {
+            PlanFeatureValue::Included(included) => *included,
+            PlanFeatureValue::Quantity(quantity) => *quantity > 0,
+            PlanFeatureValue::Unlimited => true,
+        }
+    }
+
+    /// Returns `true` when this value grants at least as much as `other`.
+    ///
+    /// Values of different shapes (on/off versus countable) never cover each other unless
+    /// `other` grants nothing.
+    pub fn covers(&self, other: &PlanFeatureValue) -> bool {
+        match (self, other) {
+            (_, other) if !other.is_included() => true,
+            (PlanFeatureValue::Unlimited, PlanFeatureValue::Quantity(_))
+            | (PlanFeatureValue::Unlimited, PlanFeatureValue::Unlimited) => true,
+            (PlanFeatureValue::Included(mine), PlanFeatureValue::Included(theirs)) => {
+                mine >= theirs
+            }
+            (PlanFeatureValue::Quantity(mine), PlanFeatureValue::Quantity(theirs)) => {
+                mine >= theirs
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Entry of a plan's feature matrix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PlanFeature {
+    /// Stable feature key shared across plans (for example `sso` or `seats`).
+    pub key: String,
+    /// Label shown on pricing pages.
+    pub display_name: String,
+    /// Amount granted by the plan.
+    pub value: PlanFeatureValue,
+}
+
+impl PlanFeature {
+    /// Creates a feature entry.
+    pub fn new(
+        key: impl Into<String>,
+        display_name: impl Into<String>,
+        value: PlanFeatureValue,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            display_name: display_name.into(),
+            value,
+        }
+    }
+}
+
+/// Row of a [`PlanFeatureMatrix`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PlanFeatureRow {
+    /// Feature key.
+    pub key: String,
+    /// Display name from the first plan declaring the feature.
+    pub display_name: String,
+    /// Value per plan, in [`PlanFeatureMatrix::plan_ids`] order; `None` when a plan omits it.
+    pub values: Vec<Option<PlanFeatureValue>>,
+}
+
+/// Side-by-side feature comparison of several plans.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PlanFeatureMatrix {
+    /// Compared plans, one column each.
+    pub plan_ids: Vec<StorePlanId>,
+    /// Features in order of first appearance across the plans.
+    pub rows: Vec<PlanFeatureRow>,
+}
+
+impl PlanFeatureMatrix {
+    /// Builds the comparison for `plans`, in the given order.
+    pub fn from_plans(plans: &[StorePlan]) -> Self {
+        let mut rows: Vec<PlanFeatureRow> = Vec::new();
+        for plan in plans {
+            for feature in &plan.features {
+                if !rows.iter().any(|row| row.key == feature.key) {
+                    rows.push(PlanFeatureRow {
+                        key: feature.key.clone(),
+                        display_name: feature.display_name.clone(),
+                        values: plans
+                            .iter()
+                            .map(|plan| plan.feature(&feature.key).map(|found| found.value))
+                            .collect(),
+                    });
+                }
+            }
+        }
+        Self {
+            plan_ids: plans.iter().map(|plan| plan.id.clone()).collect(),
+            rows,
+        }
+    }
+}
+
 /// Plan associated with a store product.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -583,11 +1034,90 @@ pub struct StorePlan {
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
     pub tags: Vec<String>,
+    /// Feature matrix shown on pricing pages and used for entitlement checks.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub features: Vec<PlanFeature>,
+    /// Catalog lifecycle state.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lifecycle: LifecycleState,
+    /// Instant from which the plan counts as deprecated.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub deprecated_at: Option<UnixMillis>,
+    /// Instant from which the plan counts as retired.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub sunset_at: Option<UnixMillis>,
+    /// Plan customers should move to.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub replacement_id: Option<StorePlanId>,
     /// Additional metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: BTreeMap<String, Value>,
 }
 
+impl StorePlan {
+    /// Returns the lifecycle state in effect at `now`, taking `deprecated_at` and `sunset_at`
+    /// into account.
+    pub fn lifecycle_at(&self, now: UnixMillis) -> LifecycleState {
+        self.lifecycle
+            .effective_at(self.deprecated_at, self.sunset_at, now)
+    }
+
+    /// Returns `true` when new subscriptions may be created at `now`.
+    pub fn is_purchasable(&self, now: UnixMillis) -> bool {
+        self.lifecycle_at(now).is_purchasable()
+    }
+
+    /// Returns the feature entry for `key`.
+    pub fn feature(&self, key: &str) -> Option<&PlanFeature> {
+        self.features.iter().find(|feature| feature.key == key)
+    }
+
+    /// Returns `true` when the plan grants at least `required` of feature `key`.
+    pub fn grants(&self, key: &str, required: &PlanFeatureValue) -> bool {
+        match self.feature(key) {
+            Some(feature) => feature.value.covers(required),
+            None => !required.is_included(),
+        }
+    }
+
+    /// Returns `true` when the plan grants everything `other` grants, so moving from `other` to
+    /// this plan loses no features.
+    pub fn covers_features_of(&self, other: &StorePlan) -> bool {
+        other
+            .features
+            .iter()
+            .all(|feature| self.grants(&feature.key, &feature.value))
+    }
+}
+
 /// Subscription lifecycle status.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -633,91 +1163,1177 @@ pub struct Subscription {
     pub distributor_ref: Option<DistributorRef>,
     /// Current status.
     pub status: SubscriptionStatus,
+    /// Billing account charged for the subscription, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub billing_account_id: Option<BillingAccountId>,
+    /// Discount applied to the plan price.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub discount: Option<Percent>,
     /// Additional metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: BTreeMap<String, Value>,
 }
 
-/// Choice between component or pack reference.
+/// Kind of payment instrument behind a [`PaymentMethodRef`].
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
-pub enum PackOrComponentRef {
-    /// Component reference.
-    Component(ComponentRef),
-    /// Pack reference.
-    Pack(PackId),
+pub enum PaymentMethodKind {
+    /// Credit or debit card.
+    Card,
+    /// Bank account / direct debit.
+    BankAccount,
+    /// Invoiced payment terms.
+    Invoice,
+    /// Provider-specific instrument.
+    Other(String),
 }
 
-/// Selector describing whether a component or pack should be deployed.
+/// Payment method stored with a payment provider and referenced by token.
+///
+/// Raw card or account numbers never appear here; only the provider token and an optional
+/// display hint such as `"Visa •••• 4242"`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
-pub enum ArtifactSelector {
-    /// Component reference.
-    Component(ComponentRef),
-    /// Pack reference.
-    Pack(PackRef),
-}
-
-/// Desired subscription entry supplied to the distributor.
-#[derive(Clone, Debug, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
-pub struct DesiredSubscriptionEntry {
-    /// Target artifact selection.
-    pub selector: ArtifactSelector,
-    /// Version strategy to apply.
-    pub version_strategy: VersionStrategy,
-    /// Configuration overrides.
-    #[cfg_attr(feature = "serde", serde(default))]
-    pub config_overrides: BTreeMap<String, Value>,
-    /// Policy tags for downstream enforcement.
+pub struct PaymentMethod {
+    /// Provider token for the payment method.
+    pub reference: PaymentMethodRef,
+    /// Payment provider that issued the token (for example `stripe`).
+    pub provider: String,
+    /// Instrument kind.
+    pub kind: PaymentMethodKind,
+    /// Masked, human-readable label.
     #[cfg_attr(
         feature = "serde",
-        serde(default, skip_serializing_if = "Vec::is_empty")
+        serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub policy_tags: Vec<String>,
-    /// Additional metadata.
+    pub display_hint: Option<String>,
+    /// Whether this is the account's default method.
     #[cfg_attr(feature = "serde", serde(default))]
-    pub metadata: BTreeMap<String, Value>,
+    pub is_default: bool,
 }
 
-/// Desired state for an environment.
+/// Billing context for a tenant, referenced by subscriptions and invoices.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
-pub struct DesiredState {
-    /// Tenant context owning the desired state.
-    pub tenant: TenantCtx,
-    /// Target environment reference.
-    pub environment_ref: EnvironmentRef,
-    /// Desired subscriptions.
+pub struct BillingAccount {
+    /// Billing account identifier.
+    pub id: BillingAccountId,
+    /// Tenant context owning the account.
+    pub tenant_ctx: TenantCtx,
+    /// Address invoices are sent to.
+    pub billing_email: String,
+    /// Tax or VAT identifier, if registered.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub tax_id: Option<String>,
+    /// Country of tax residence, used to pick tax rules.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub country: Option<CountryCode>,
+    /// Subdivision of tax residence, for countries taxing per state or province.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub region: Option<RegionCode>,
+    /// Tokenized payment methods on file.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
-    pub entries: Vec<DesiredSubscriptionEntry>,
-    /// Desired state version.
-    pub version: u64,
+    pub payment_methods: Vec<PaymentMethod>,
     /// Additional metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: BTreeMap<String, Value>,
 }
 
-/// Connection kind for an environment.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
-pub enum ConnectionKind {
-    /// Online environment with direct connectivity.
-    Online,
-    /// Offline or air-gapped environment.
-    Offline,
-}
+impl BillingAccount {
+    /// Returns the default payment method, falling back to the only one on file.
+    pub fn default_payment_method(&self) -> Option<&PaymentMethod> {
+        self.payment_methods
+            .iter()
+            .find(|method| method.is_default)
+            .or(match self.payment_methods.as_slice() {
+                [only] => Some(only),
+                _ => None,
+            })
+    }
+
+    /// Checks the billing email, tax location, default selection, and that no raw card number
+    /// is stored.
+    pub fn validate(&self) -> GResult<()> {
+        if !crate::tenant_config::is_plausible_email(&self.billing_email) {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "billing email `{}` is not a valid address",
+                    self.billing_email
+                ),
+            ));
+        }
+        match &self.region {
+            Some(region) if self.country.as_ref() != Some(&region.country()) => {
+                return Err(GreenticError::new(
+                    ErrorCode::InvalidInput,
+                    format!("billing region `{region}` does not belong to the billing country"),
+                ));
+            }
+            _ => {}
+        }
+        if self
+            .payment_methods
+            .iter()
+            .filter(|method| method.is_default)
+            .count()
+            > 1
+        {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "billing account has more than one default payment method",
+            ));
+        }
+        for method in &self.payment_methods {
+            let hint = method.display_hint.as_deref().unwrap_or_default();
+            if looks_like_card_number(method.reference.as_str()) || looks_like_card_number(hint) {
+                return Err(GreenticError::new(
+                    ErrorCode::InvalidInput,
+                    "payment methods must be tokenized; found what looks like a raw card number",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` when `value` holds a run of 13–19 digits (spaces and dashes allowed between
+/// them) that passes the Luhn check, the shape of a card PAN.
+fn looks_like_card_number(value: &str) -> bool {
+    let mut digits = Vec::new();
+    for ch in value.chars().chain(core::iter::once('\0')) {
+        match ch.to_digit(10) {
+            Some(digit) => digits.push(digit),
+            _ if matches!(ch, ' ' | '-') && !digits.is_empty() => {}
+            _ => {
+                if (13..=19).contains(&digits.len()) && luhn_valid(&digits) {
+                    return true;
+                }
+                digits.clear();
+            }
+        }
+    }
+    false
+}
+
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(position, &digit)| match position % 2 {
+            0 => digit,
+            _ if digit > 4 => digit * 2 - 9,
+            _ => digit * 2,
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Cursor-based page request shared by store list APIs.
+///
+/// Cursors are opaque to clients; they echo the `next_cursor` of the previous [`Page`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PageRequest {
+    /// Cursor returned by the previous page; `None` requests the first page.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub cursor: Option<String>,
+    /// Maximum number of items to return (defaults to [`PageRequest::DEFAULT_LIMIT`]).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub limit: Option<u32>,
+}
+
+impl PageRequest {
+    /// Page size used when no limit is requested.
+    pub const DEFAULT_LIMIT: u32 = 50;
+    /// Largest page size servers are expected to honour.
+    pub const MAX_LIMIT: u32 = 500;
+
+    /// Requests the first page with the given limit.
+    pub fn first(limit: u32) -> Self {
+        Self {
+            cursor: None,
+            limit: Some(limit),
+        }
+    }
+
+    /// Returns the requested limit clamped to `1..=MAX_LIMIT`.
+    pub fn effective_limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .clamp(1, Self::MAX_LIMIT)
+    }
+
+    /// Returns the request for the page following `page`, or `None` on the last page.
+    pub fn next<T>(&self, page: &Page<T>) -> Option<Self> {
+        page.next_cursor.as_ref().map(|cursor| Self {
+            cursor: Some(cursor.clone()),
+            limit: self.limit,
+        })
+    }
+}
+
+/// One page of results from a store list API.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "T: Deserialize<'de>")))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct Page<T> {
+    /// Items on this page.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub items: Vec<T>,
+    /// Cursor for the next page; `None` on the last page.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub next_cursor: Option<String>,
+    /// Total number of matching items, when the server can compute it cheaply.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub total: Option<u64>,
+}
+
+impl<T> Page<T> {
+    /// Returns `true` when more pages follow.
+    pub fn has_more(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+
+    /// Converts the items, keeping the cursor and total.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            total: self.total,
+        }
+    }
+}
+
+impl<T: Clone> Page<T> {
+    /// Pages through an in-memory list using decimal offsets as cursors.
+    ///
+    /// Intended for services that hold the full result set; others should issue their own
+    /// opaque cursors.
+    pub fn from_slice(items: &[T], request: &PageRequest) -> GResult<Self> {
+        let start = match request.cursor.as_deref() {
+            None => 0,
+            Some(cursor) => cursor
+                .parse::<usize>()
+                .ok()
+                .filter(|offset| *offset <= items.len())
+                .ok_or_else(|| {
+                    GreenticError::new(
+                        ErrorCode::InvalidInput,
+                        format!("invalid page cursor `{cursor}`"),
+                    )
+                })?,
+        };
+        let end = start
+            .saturating_add(request.effective_limit() as usize)
+            .min(items.len());
+        Ok(Self {
+            items: items[start..end].to_vec(),
+            next_cursor: (end < items.len()).then(|| end.to_string()),
+            total: Some(items.len() as u64),
+        })
+    }
+}
+
+/// Standard filter for store catalog queries.
+///
+/// Unset fields match everything; set fields must all match.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CatalogFilter {
+    /// Restricts results to one product kind.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub kind: Option<StoreProductKind>,
+    /// Tags every result must carry.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tags: Vec<String>,
+    /// Capability the product must expose, as `group` or `group:value`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub capability: Option<String>,
+    /// Only return free products or plans.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub free_only: bool,
+}
+
+impl CatalogFilter {
+    /// Returns `true` when no criterion is set.
+    pub fn is_empty(&self) -> bool {
+        self.kind.is_none() && self.tags.is_empty() && self.capability.is_none() && !self.free_only
+    }
+
+    /// Returns `true` when `product` satisfies every criterion.
+    pub fn matches_product(&self, product: &StoreProduct) -> bool {
+        self.kind.as_ref().is_none_or(|kind| *kind == product.kind)
+            && self.tags.iter().all(|tag| product.tags.contains(tag))
+            && self
+                .capability
+                .as_deref()
+                .is_none_or(|capability| has_capability(&product.capabilities, capability))
+            && (!self.free_only || product.is_free)
+    }
+
+    /// Returns `true` when `plan` satisfies the tag and price criteria.
+    ///
+    /// Kind and capability criteria describe products and are ignored for plans.
+    pub fn matches_plan(&self, plan: &StorePlan) -> bool {
+        self.tags.iter().all(|tag| plan.tags.contains(tag))
+            && (!self.free_only || plan.price_model == PriceModel::Free)
+    }
+}
+
+fn has_capability(capabilities: &CapabilityMap, capability: &str) -> bool {
+    match capability.split_once(':') {
+        Some((group, value)) => capabilities
+            .get(group)
+            .is_some_and(|values| values.iter().any(|v| v == value)),
+        None => capabilities.contains_key(capability),
+    }
+}
+
+/// Page of store products.
+pub type StoreProductPage = Page<StoreProduct>;
+/// Page of store plans.
+pub type StorePlanPage = Page<StorePlan>;
+/// Page of subscriptions.
+pub type SubscriptionPage = Page<Subscription>;
+
+/// Choice between component or pack reference.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum PackOrComponentRef {
+    /// Component reference.
+    Component(ComponentRef),
+    /// Pack reference.
+    Pack(PackId),
+}
+
+/// Selector describing whether a component or pack should be deployed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ArtifactSelector {
+    /// Component reference.
+    Component(ComponentRef),
+    /// Pack reference.
+    Pack(PackRef),
+}
+
+/// Desired subscription entry supplied to the distributor.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DesiredSubscriptionEntry {
+    /// Target artifact selection.
+    pub selector: ArtifactSelector,
+    /// Version strategy to apply.
+    pub version_strategy: VersionStrategy,
+    /// Store plan the entry is billed under, if any.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub plan_id: Option<StorePlanId>,
+    /// Configuration overrides; strings may reference `${secret:...}` and `${env:...}`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub config_overrides: BTreeMap<String, ConfigValue>,
+    /// How `config_overrides` merge into the artifact's base configuration.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "MergeStrategy::is_merge_patch")
+    )]
+    pub config_merge: MergeStrategy,
+    /// Policy tags for downstream enforcement.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub policy_tags: Vec<String>,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl DesiredSubscriptionEntry {
+    /// Configuration overrides as a single patch using [`DesiredSubscriptionEntry::config_merge`].
+    pub fn config_override(&self) -> ConfigOverride {
+        ConfigOverride::from_entries(self.config_merge, &self.config_overrides)
+    }
+
+    /// Applies the configuration overrides to `base`, returning conflict diagnostics.
+    pub fn apply_config_overrides(&self, base: &mut Value) -> Vec<Diagnostic> {
+        self.config_override().apply_to(base)
+    }
+}
+
+/// Desired state for an environment.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DesiredState {
+    /// Tenant context owning the desired state.
+    pub tenant: TenantCtx,
+    /// Target environment reference.
+    pub environment_ref: EnvironmentRef,
+    /// Desired subscriptions.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub entries: Vec<DesiredSubscriptionEntry>,
+    /// Desired event subscriptions, keyed by name.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub event_subscriptions: Vec<EventSubscriptionSpec>,
+    /// Desired state version.
+    pub version: u64,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+/// Change to a single configuration override key.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "op", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ConfigOverrideChange {
+    /// Key introduced by the new entry.
+    Added {
+        /// New value.
+        value: Value,
+    },
+    /// Key dropped by the new entry.
+    Removed {
+        /// Previous value.
+        value: Value,
+    },
+    /// Key present in both entries with different values.
+    Changed {
+        /// Previous value.
+        old: Value,
+        /// New value.
+        new: Value,
+    },
+}
+
+/// Entry whose selector is present in both desired states but whose contents differ.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DesiredSubscriptionChange {
+    /// Entry from the previous desired state.
+    pub old: DesiredSubscriptionEntry,
+    /// Entry from the new desired state.
+    pub new: DesiredSubscriptionEntry,
+    /// Per-key configuration override changes.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    pub config_overrides: BTreeMap<String, ConfigOverrideChange>,
+}
+
+impl DesiredSubscriptionChange {
+    /// Selector shared by both entries.
+    pub fn selector(&self) -> &ArtifactSelector {
+        &self.new.selector
+    }
+
+    /// Returns `true` when the version strategy changed.
+    pub fn version_strategy_changed(&self) -> bool {
+        self.old.version_strategy != self.new.version_strategy
+    }
+}
+
+/// Event subscription present in both desired states with different contents.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct EventSubscriptionChange {
+    /// Subscription from the previous desired state.
+    pub old: EventSubscriptionSpec,
+    /// Subscription from the new desired state.
+    pub new: EventSubscriptionSpec,
+}
+
+/// Difference between two desired states.
+///
+/// Entries are keyed by [`ArtifactSelector`], event subscriptions by name.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DesiredStateDiff {
+    /// Entries only present in the new desired state.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub added: Vec<DesiredSubscriptionEntry>,
+    /// Entries only present in the previous desired state.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub removed: Vec<DesiredSubscriptionEntry>,
+    /// Entries present in both states with different contents.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub changed: Vec<DesiredSubscriptionChange>,
+    /// Event subscriptions only present in the new desired state.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub added_event_subscriptions: Vec<EventSubscriptionSpec>,
+    /// Event subscriptions only present in the previous desired state.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub removed_event_subscriptions: Vec<EventSubscriptionSpec>,
+    /// Event subscriptions present in both states with different contents.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub changed_event_subscriptions: Vec<EventSubscriptionChange>,
+}
+
+impl DesiredStateDiff {
+    /// Returns `true` when both desired states declare the same entries and event subscriptions.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.added_event_subscriptions.is_empty()
+            && self.removed_event_subscriptions.is_empty()
+            && self.changed_event_subscriptions.is_empty()
+    }
+}
+
+/// Computes the entry-level difference between two desired states.
+///
+/// Entries are matched by selector and event subscriptions by name; when a key appears more than
+/// once the first occurrence wins. Added and changed items follow the order of `new`, removed
+/// items follow the order of `old`. State-level fields (`version`, `metadata`) are not compared.
+pub fn diff_desired_state(old: &DesiredState, new: &DesiredState) -> DesiredStateDiff {
+    let find = |entries: &'_ [DesiredSubscriptionEntry], selector: &ArtifactSelector| {
+        entries.iter().position(|entry| &entry.selector == selector)
+    };
+
+    let mut diff = DesiredStateDiff::default();
+    for (index, entry) in new.entries.iter().enumerate() {
+        if find(&new.entries[..index], &entry.selector).is_some() {
+            continue;
+        }
+        match find(&old.entries, &entry.selector) {
+            None => diff.added.push(entry.clone()),
+            Some(position) => {
+                let previous = &old.entries[position];
+                if previous != entry {
+                    diff.changed.push(DesiredSubscriptionChange {
+                        old: previous.clone(),
+                        new: entry.clone(),
+                        config_overrides: diff_config_overrides(
+                            &previous.config_overrides,
+                            &entry.config_overrides,
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    for (index, entry) in old.entries.iter().enumerate() {
+        if find(&old.entries[..index], &entry.selector).is_none()
+            && find(&new.entries, &entry.selector).is_none()
+        {
+            diff.removed.push(entry.clone());
+        }
+    }
+
+    let find = |subscriptions: &'_ [EventSubscriptionSpec], name: &str| {
+        subscriptions.iter().position(|spec| spec.name == name)
+    };
+    for (index, spec) in new.event_subscriptions.iter().enumerate() {
+        if find(&new.event_subscriptions[..index], &spec.name).is_some() {
+            continue;
+        }
+        match find(&old.event_subscriptions, &spec.name) {
+            None => diff.added_event_subscriptions.push(spec.clone()),
+            Some(position) => {
+                let previous = &old.event_subscriptions[position];
+                if previous != spec {
+                    diff.changed_event_subscriptions
+                        .push(EventSubscriptionChange {
+                            old: previous.clone(),
+                            new: spec.clone(),
+                        });
+                }
+            }
+        }
+    }
+    for (index, spec) in old.event_subscriptions.iter().enumerate() {
+        if find(&old.event_subscriptions[..index], &spec.name).is_none()
+            && find(&new.event_subscriptions, &spec.name).is_none()
+        {
+            diff.removed_event_subscriptions.push(spec.clone());
+        }
+    }
+    diff
+}
+
+fn diff_config_overrides(
+    old: &BTreeMap<String, ConfigValue>,
+    new: &BTreeMap<String, ConfigValue>,
+) -> BTreeMap<String, ConfigOverrideChange> {
+    let mut changes = BTreeMap::new();
+    for (key, value) in new {
+        match old.get(key) {
+            None => {
+                changes.insert(
+                    key.clone(),
+                    ConfigOverrideChange::Added {
+                        value: value.as_value().clone(),
+                    },
+                );
+            }
+            Some(previous) if previous != value => {
+                changes.insert(
+                    key.clone(),
+                    ConfigOverrideChange::Changed {
+                        old: previous.as_value().clone(),
+                        new: value.as_value().clone(),
+                    },
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, value) in old {
+        if !new.contains_key(key) {
+            changes.insert(
+                key.clone(),
+                ConfigOverrideChange::Removed {
+                    value: value.as_value().clone(),
+                },
+            );
+        }
+    }
+    changes
+}
+
+/// Length of the billing month used to normalise flat prices, in days.
+pub const COST_ESTIMATE_MONTH_DAYS: u16 = 30;
+
+/// How far a cost figure can be trusted.
+///
+/// Ordered from least to most reliable, so the confidence of a total is the minimum over its parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum CostConfidence {
+    /// Price could not be determined (missing plan, custom pricing, unknown usage).
+    Unknown,
+    /// Price depends on projected usage or a normalised billing period.
+    Estimated,
+    /// Price follows directly from the plan.
+    Exact,
+}
+
+/// Projected usage charges for a metered plan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct MeteredProjection {
+    /// Unit label from the plan (for example `build-minute`).
+    pub unit_label: String,
+    /// Projected units per month.
+    pub projected_units: u64,
+    /// Units included in the plan.
+    pub included_units: u64,
+    /// Projected units billed at the overage rate.
+    pub overage_units: u64,
+    /// Overage rate per unit.
+    pub overage_rate: MoneyMicro,
+}
+
+impl MeteredProjection {
+    /// Projected overage charge, saturating on overflow.
+    pub fn cost(&self) -> MoneyMicro {
+        self.overage_rate.saturating_mul(self.overage_units)
+    }
+}
+
+/// Monthly cost projection for one desired subscription entry.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct EntryCostEstimate {
+    /// Entry selector.
+    pub selector: ArtifactSelector,
+    /// Plan the entry is billed under.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub plan_id: Option<StorePlanId>,
+    /// Monthly cost; `None` when it cannot be priced.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub monthly: Option<MoneyMicro>,
+    /// Usage projection for metered plans.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub metered: Option<MeteredProjection>,
+    /// Reliability of `monthly`.
+    pub confidence: CostConfidence,
+    /// Explanation when the estimate is not exact.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub note: Option<String>,
+}
+
+/// Projected monthly cost of a desired state, shown before a change is approved.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CostEstimate {
+    /// Environment the desired state targets.
+    pub environment_ref: EnvironmentRef,
+    /// Desired state version the estimate was computed for.
+    pub desired_state_version: u64,
+    /// Per-entry estimates in desired state order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub entries: Vec<EntryCostEstimate>,
+    /// Sum of the priced entries per month, in the estimate's currency.
+    pub monthly_total: MoneyMicro,
+    /// Lowest confidence across the entries.
+    pub confidence: CostConfidence,
+}
+
+impl CostEstimate {
+    /// Prices every entry of `state` against `plans`.
+    ///
+    /// `projected_usage` maps metered unit labels to the expected units per month. Flat prices are
+    /// normalised to a [`COST_ESTIMATE_MONTH_DAYS`]-day month. Entries that cannot be priced are
+    /// kept with `monthly: None` and lower the overall confidence to
+    /// [`CostConfidence::Unknown`]; this includes plans priced in a currency other than
+    /// `currency`.
+    pub fn compute(
+        state: &DesiredState,
+        plans: &[StorePlan],
+        currency: CurrencyCode,
+        projected_usage: &BTreeMap<String, u64>,
+    ) -> Self {
+        let entries: Vec<EntryCostEstimate> = state
+            .entries
+            .iter()
+            .map(|entry| estimate_entry(entry, plans, &currency, projected_usage))
+            .collect();
+        let monthly_total = entries
+            .iter()
+            .filter_map(|entry| entry.monthly.as_ref())
+            .fold(MoneyMicro::zero(currency), |total, monthly| {
+                total
+                    .checked_add(monthly)
+                    .unwrap_or_else(|_| MoneyMicro::new(u64::MAX, total.currency.clone()))
+            });
+        let confidence = entries
+            .iter()
+            .map(|entry| entry.confidence)
+            .min()
+            .unwrap_or(CostConfidence::Exact);
+        Self {
+            environment_ref: state.environment_ref.clone(),
+            desired_state_version: state.version,
+            entries,
+            monthly_total,
+            confidence,
+        }
+    }
+
+    /// Monthly difference against `baseline` in micro-units (positive when this costs more).
+    ///
+    /// Returns `None` when the estimates use different currencies.
+    pub fn delta_micro(&self, baseline: &CostEstimate) -> Option<i128> {
+        (self.monthly_total.currency == baseline.monthly_total.currency).then(|| {
+            i128::from(self.monthly_total.amount_micro)
+                - i128::from(baseline.monthly_total.amount_micro)
+        })
+    }
+}
+
+fn estimate_entry(
+    entry: &DesiredSubscriptionEntry,
+    plans: &[StorePlan],
+    currency: &CurrencyCode,
+    projected_usage: &BTreeMap<String, u64>,
+) -> EntryCostEstimate {
+    let mut estimate = EntryCostEstimate {
+        selector: entry.selector.clone(),
+        plan_id: entry.plan_id.clone(),
+        monthly: None,
+        metered: None,
+        confidence: CostConfidence::Unknown,
+        note: None,
+    };
+    let Some(plan_id) = &entry.plan_id else {
+        estimate.note = Some("entry has no plan".into());
+        return estimate;
+    };
+    let Some(plan) = plans.iter().find(|plan| &plan.id == plan_id) else {
+        estimate.note = Some(format!("plan `{plan_id}` not found"));
+        return estimate;
+    };
+    match plan.price_model.currency() {
+        Some(plan_currency) if plan_currency != currency => {
+            estimate.note = Some(format!("plan `{plan_id}` is priced in {plan_currency}"));
+            return estimate;
+        }
+        _ => {}
+    }
+    match &plan.price_model {
+        PriceModel::Free => {
+            estimate.monthly = Some(MoneyMicro::zero(currency.clone()));
+            estimate.confidence = CostConfidence::Exact;
+        }
+        PriceModel::Flat { period_days: 0, .. } => {
+            estimate.note = Some("flat price has a zero-day billing period".into());
+        }
+        PriceModel::Flat { price, period_days } => {
+            if *period_days == COST_ESTIMATE_MONTH_DAYS {
+                estimate.monthly = Some(price.clone());
+                estimate.confidence = CostConfidence::Exact;
+            } else {
+                let monthly = price
+                    .mul_ratio(
+                        u64::from(COST_ESTIMATE_MONTH_DAYS),
+                        u64::from(*period_days),
+                        RoundingMode::Down,
+                    )
+                    .unwrap_or_else(|_| MoneyMicro::new(u64::MAX, price.currency.clone()));
+                estimate.monthly = Some(monthly);
+                estimate.confidence = CostConfidence::Estimated;
+                estimate.note = Some(format!(
+                    "normalised from a {period_days}-day billing period"
+                ));
+            }
+        }
+        PriceModel::Metered {
+            included_units,
+            overage_rate,
+            unit_label,
+        } => match projected_usage.get(unit_label) {
+            Some(projected_units) => {
+                let projection = MeteredProjection {
+                    unit_label: unit_label.clone(),
+                    projected_units: *projected_units,
+                    included_units: *included_units,
+                    overage_units: projected_units.saturating_sub(*included_units),
+                    overage_rate: overage_rate.clone(),
+                };
+                estimate.monthly = Some(projection.cost());
+                estimate.metered = Some(projection);
+                estimate.confidence = CostConfidence::Estimated;
+            }
+            None => {
+                estimate.note = Some(format!("no usage projection for `{unit_label}`"));
+            }
+        },
+        PriceModel::Enterprise { .. } => {
+            estimate.note = Some("enterprise pricing is negotiated per contract".into());
+        }
+    }
+    estimate
+}
+
+/// Lifecycle of a desired state approval.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ApprovalStatus {
+    /// Waiting for approvers.
+    Pending,
+    /// Enough approvals were recorded.
+    Approved,
+    /// An approver rejected the change.
+    Rejected,
+    /// The approval window elapsed before a decision.
+    Expired,
+    /// The requester withdrew the change.
+    Withdrawn,
+}
+
+/// Decision recorded by a single approver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ApprovalVerdict {
+    /// Approver signs off on the change.
+    Approve,
+    /// Approver blocks the change.
+    Reject,
+}
+
+/// Sign-off recorded against an approval.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ApprovalDecision {
+    /// User that made the decision.
+    pub approver: UserId,
+    /// Decision taken.
+    pub verdict: ApprovalVerdict,
+    /// Decision timestamp (UTC).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub decided_at_utc: UnixMillis,
+    /// Optional justification.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub comment: Option<String>,
+}
+
+/// Approval state attached to a desired state change request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ApprovalRecord {
+    /// User that requested the change.
+    pub requester: UserId,
+    /// Users allowed to approve (empty allows any user other than the requester).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub approvers: Vec<UserId>,
+    /// Number of approvals required before the change may be pushed.
+    pub required_approvals: u32,
+    /// Current status.
+    pub status: ApprovalStatus,
+    /// Decisions recorded so far, in order.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub decisions: Vec<ApprovalDecision>,
+    /// Expiry timestamp (UTC), if the approval window is bounded.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub expires_at_utc: Option<UnixMillis>,
+}
+
+impl ApprovalRecord {
+    /// Creates a pending approval record.
+    pub fn new(requester: UserId, approvers: Vec<UserId>, required_approvals: u32) -> Self {
+        Self {
+            requester,
+            approvers,
+            required_approvals,
+            status: ApprovalStatus::Pending,
+            decisions: Vec::new(),
+            expires_at_utc: None,
+        }
+    }
+
+    /// Sets the expiry timestamp.
+    pub fn with_expiry(mut self, expires_at_utc: UnixMillis) -> Self {
+        self.expires_at_utc = Some(expires_at_utc);
+        self
+    }
+
+    /// Returns the number of approving decisions.
+    pub fn approval_count(&self) -> usize {
+        self.decisions
+            .iter()
+            .filter(|decision| decision.verdict == ApprovalVerdict::Approve)
+            .count()
+    }
+
+    /// Returns `true` when `user` may record a decision.
+    pub fn can_decide(&self, user: &UserId) -> bool {
+        user != &self.requester
+            && (self.approvers.is_empty() || self.approvers.contains(user))
+            && !self
+                .decisions
+                .iter()
+                .any(|decision| &decision.approver == user)
+    }
+
+    /// Records a decision and updates the status.
+    ///
+    /// A single rejection rejects the change; it is approved once `required_approvals`
+    /// approvals are recorded.
+    pub fn record(&mut self, decision: ApprovalDecision) -> GResult<ApprovalStatus> {
+        if self.status != ApprovalStatus::Pending {
+            return Err(GreenticError::new(
+                ErrorCode::Conflict,
+                format!("approval is no longer pending ({:?})", self.status),
+            ));
+        }
+        if !self.can_decide(&decision.approver) {
+            return Err(GreenticError::new(
+                ErrorCode::PermissionDenied,
+                format!(
+                    "user '{}' may not decide on this approval",
+                    decision.approver
+                ),
+            ));
+        }
+        let verdict = decision.verdict;
+        self.decisions.push(decision);
+        if verdict == ApprovalVerdict::Reject {
+            self.status = ApprovalStatus::Rejected;
+        } else if self.approval_count() >= self.required_approvals as usize {
+            self.status = ApprovalStatus::Approved;
+        }
+        Ok(self.status)
+    }
+
+    /// Marks a pending approval as expired.
+    pub fn expire(&mut self) {
+        if self.status == ApprovalStatus::Pending {
+            self.status = ApprovalStatus::Expired;
+        }
+    }
+
+    /// Marks a pending approval as withdrawn by the requester.
+    pub fn withdraw(&mut self) {
+        if self.status == ApprovalStatus::Pending {
+            self.status = ApprovalStatus::Withdrawn;
+        }
+    }
+}
+
+/// Proposed desired state version awaiting sign-off before it is pushed to an environment.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct DesiredStateChangeRequest {
+    /// Change request identifier.
+    pub id: ChangeRequestId,
+    /// Desired state version the change was prepared against.
+    pub base_version: u64,
+    /// Proposed desired state.
+    pub proposed: DesiredState,
+    /// Approval state.
+    pub approval: ApprovalRecord,
+    /// Creation timestamp (UTC).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub created_at_utc: UnixMillis,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl DesiredStateChangeRequest {
+    /// Returns `true` when the change is approved and still applies on top of `current_version`.
+    pub fn is_ready_to_push(&self, current_version: u64) -> bool {
+        self.approval.status == ApprovalStatus::Approved && self.base_version == current_version
+    }
+
+    /// Computes the entry-level changes relative to the current desired state.
+    pub fn diff(&self, current: &DesiredState) -> DesiredStateDiff {
+        diff_desired_state(current, &self.proposed)
+    }
+}
+
+/// Connection kind for an environment.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ConnectionKind {
+    /// Online environment with direct connectivity.
+    Online,
+    /// Offline or air-gapped environment.
+    Offline,
+}
 
 /// Environment registry entry.
 #[derive(Clone, Debug, PartialEq)]
@@ -737,6 +2353,12 @@ pub struct Environment {
     pub distributor_ref: DistributorRef,
     /// Connection kind.
     pub connection_kind: ConnectionKind,
+    /// Where the environment may store and process tenant data.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub residency: Option<RegionConstraint>,
     /// Additional metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub metadata: BTreeMap<String, Value>,
@@ -757,6 +2379,7 @@ impl Environment {
             name: name.into(),
             distributor_ref,
             connection_kind,
+            residency: None,
             labels: BTreeMap::new(),
             metadata: BTreeMap::new(),
         }
@@ -781,6 +2404,8 @@ pub enum RolloutState {
     Failed,
     /// Rollout is blocked (for example policy or compliance).
     Blocked,
+    /// Rollout is held back by a maintenance window.
+    Deferred,
 }
 
 /// Status record for an environment rollout.
@@ -804,6 +2429,18 @@ pub struct RolloutStatus {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub bundle_id: Option<BundleId>,
+    /// Maintenance window that deferred the rollout.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub deferred_by: Option<MaintenanceWindowId>,
+    /// Share of targets already updated.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub progress: Option<Percent>,
     /// Optional human-readable message.
     #[cfg_attr(
         feature = "serde",
@@ -840,11 +2477,249 @@ pub struct BundleSpec {
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
     pub metadata_refs: Vec<MetadataRecordRef>,
+    /// Digests used to verify the bundled artifacts offline.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub integrity: Option<BundleIntegrity>,
     /// Additional metadata.
     #[cfg_attr(feature = "serde", serde(default))]
     pub additional_metadata: BTreeMap<String, Value>,
 }
 
+/// Recorded digest and size of one bundled artifact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BundleArtifactDigest {
+    /// Artifact the digest belongs to.
+    pub artifact: ArtifactRef,
+    /// Digest of the artifact bytes.
+    pub digest: HashDigest,
+    /// Artifact size in bytes.
+    pub size_bytes: u64,
+}
+
+/// Integrity section of a [`BundleSpec`].
+///
+/// The Merkle root commits to every artifact digest, so a verifier holding only the root (for
+/// example from a signed attestation) can detect edits to the per-artifact list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BundleIntegrity {
+    /// Per-artifact digests ordered by artifact reference.
+    pub artifacts: Vec<BundleArtifactDigest>,
+    /// Sum of the artifact sizes in bytes.
+    pub total_size_bytes: u64,
+    /// Blake3 Merkle root over the artifact digests.
+    pub merkle_root: HashDigest,
+}
+
+impl BundleIntegrity {
+    /// Records Blake3 digests for the bundled artifact bytes.
+    ///
+    /// A repeated artifact reference keeps its last bytes.
+    pub fn from_artifacts<'a>(entries: impl IntoIterator<Item = (ArtifactRef, &'a [u8])>) -> Self {
+        let artifacts: BTreeMap<ArtifactRef, BundleArtifactDigest> = entries
+            .into_iter()
+            .map(|(artifact, bytes)| {
+                let entry = BundleArtifactDigest {
+                    artifact: artifact.clone(),
+                    digest: HashDigest::blake3_of(bytes),
+                    size_bytes: bytes.len() as u64,
+                };
+                (artifact, entry)
+            })
+            .collect();
+        let artifacts: Vec<_> = artifacts.into_values().collect();
+        Self {
+            total_size_bytes: artifacts
+                .iter()
+                .fold(0u64, |total, entry| total.saturating_add(entry.size_bytes)),
+            merkle_root: Self::merkle_root_of(&artifacts),
+            artifacts,
+        }
+    }
+
+    /// Computes the Merkle root of `artifacts`.
+    ///
+    /// Leaves hash each artifact reference with its digest, in artifact reference order, so the
+    /// root does not depend on list order. Interior nodes hash the concatenation of their
+    /// children and an odd node is promoted unchanged; an empty list hashes to the Blake3 digest
+    /// of no bytes.
+    pub fn merkle_root_of(artifacts: &[BundleArtifactDigest]) -> HashDigest {
+        let mut sorted: Vec<&BundleArtifactDigest> = artifacts.iter().collect();
+        sorted.sort_by(|a, b| a.artifact.cmp(&b.artifact));
+        let mut level: Vec<blake3::Hash> = sorted
+            .into_iter()
+            .map(|entry| {
+                let mut hasher = blake3::Hasher::new();
+                hasher.update(&[0]);
+                hasher.update(entry.artifact.as_str().as_bytes());
+                hasher.update(&[0]);
+                hasher.update(entry.digest.hex.to_ascii_lowercase().as_bytes());
+                hasher.finalize()
+            })
+            .collect();
+        if level.is_empty() {
+            return HashDigest::blake3_of(&[]);
+        }
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let mut hasher = blake3::Hasher::new();
+                        hasher.update(&[1]);
+                        hasher.update(left.as_bytes());
+                        hasher.update(right.as_bytes());
+                        hasher.finalize()
+                    }
+                    [single] => *single,
+                    _ => unreachable!("chunks(2) yields one or two hashes"),
+                })
+                .collect();
+        }
+        HashDigest {
+            algo: HashAlgorithm::Blake3,
+            hex: level[0].to_hex().as_str().into(),
+        }
+    }
+
+    /// Verifies fetched artifact bytes against the recorded digests.
+    ///
+    /// Returns every mismatch found, or an empty list when the bundle is intact. The recorded
+    /// total size and Merkle root are checked against the recorded artifact list as well, so an
+    /// edited integrity section is reported even when the bytes match it.
+    pub fn verify_bundle<'a>(
+        &self,
+        entries: impl Iterator<Item = (ArtifactRef, &'a [u8])>,
+    ) -> Vec<BundleMismatch> {
+        let mut mismatches = Vec::new();
+        let recorded_total = self
+            .artifacts
+            .iter()
+            .fold(0u64, |total, entry| total.saturating_add(entry.size_bytes));
+        if recorded_total != self.total_size_bytes {
+            mismatches.push(BundleMismatch::TotalSize {
+                expected: self.total_size_bytes,
+                actual: recorded_total,
+            });
+        }
+        let root = Self::merkle_root_of(&self.artifacts);
+        if !root.hex.eq_ignore_ascii_case(&self.merkle_root.hex)
+            || root.algo != self.merkle_root.algo
+        {
+            mismatches.push(BundleMismatch::MerkleRoot {
+                expected: self.merkle_root.clone(),
+                actual: root,
+            });
+        }
+
+        let mut pending: BTreeMap<&ArtifactRef, &BundleArtifactDigest> = self
+            .artifacts
+            .iter()
+            .map(|entry| (&entry.artifact, entry))
+            .collect();
+        for (artifact, bytes) in entries {
+            let Some(expected) = pending.remove(&artifact) else {
+                mismatches.push(BundleMismatch::Unexpected { artifact });
+                continue;
+            };
+            let size_bytes = bytes.len() as u64;
+            if size_bytes != expected.size_bytes {
+                mismatches.push(BundleMismatch::Size {
+                    artifact,
+                    expected: expected.size_bytes,
+                    actual: size_bytes,
+                });
+                continue;
+            }
+            match &expected.digest.algo {
+                HashAlgorithm::Blake3 => {
+                    let actual = HashDigest::blake3_of(bytes);
+                    if !actual.hex.eq_ignore_ascii_case(&expected.digest.hex) {
+                        mismatches.push(BundleMismatch::Digest {
+                            artifact,
+                            expected: expected.digest.clone(),
+                            actual,
+                        });
+                    }
+                }
+                HashAlgorithm::Other(algo) => {
+                    mismatches.push(BundleMismatch::UnsupportedAlgorithm {
+                        artifact,
+                        algo: algo.clone(),
+                    });
+                }
+            }
+        }
+        mismatches.extend(pending.into_keys().map(|artifact| BundleMismatch::Missing {
+            artifact: artifact.clone(),
+        }));
+        mismatches
+    }
+}
+
+/// Problem found by [`BundleIntegrity::verify_bundle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum BundleMismatch {
+    /// A recorded artifact was not supplied.
+    Missing {
+        /// Artifact reference.
+        artifact: ArtifactRef,
+    },
+    /// A supplied artifact is not recorded, or was supplied twice.
+    Unexpected {
+        /// Artifact reference.
+        artifact: ArtifactRef,
+    },
+    /// Artifact size differs from the recorded size.
+    Size {
+        /// Artifact reference.
+        artifact: ArtifactRef,
+        /// Recorded size in bytes.
+        expected: u64,
+        /// Supplied size in bytes.
+        actual: u64,
+    },
+    /// Artifact bytes do not match the recorded digest.
+    Digest {
+        /// Artifact reference.
+        artifact: ArtifactRef,
+        /// Recorded digest.
+        expected: HashDigest,
+        /// Digest of the supplied bytes.
+        actual: HashDigest,
+    },
+    /// The recorded digest uses an algorithm that cannot be recomputed.
+    UnsupportedAlgorithm {
+        /// Artifact reference.
+        artifact: ArtifactRef,
+        /// Algorithm name.
+        algo: String,
+    },
+    /// Recorded total size disagrees with the recorded artifact sizes.
+    TotalSize {
+        /// Recorded total.
+        expected: u64,
+        /// Sum of the recorded artifact sizes.
+        actual: u64,
+    },
+    /// Recorded Merkle root disagrees with the recorded artifact digests.
+    MerkleRoot {
+        /// Recorded root.
+        expected: HashDigest,
+        /// Root recomputed from the recorded artifact digests.
+        actual: HashDigest,
+    },
+}
+
 /// Export specification used to request a bundle from a desired state.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
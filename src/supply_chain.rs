@@ -1,6 +1,6 @@
 //! Supply-chain oriented shared types (builds, scans, signing, metadata).
 
-use alloc::{string::String, vec::Vec};
+use alloc::{collections::BTreeSet, format, string::String, vec::Vec};
 use core::hash::BuildHasherDefault;
 use fnv::FnvHasher;
 use indexmap::IndexMap;
@@ -10,15 +10,16 @@ use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 
-#[cfg(feature = "time")]
-use time::OffsetDateTime;
-
+use crate::session::hex_encode;
 use crate::{
     ArtifactRef, AttestationId, AttestationRef, BranchRef, BuildLogRef, BuildRef, CommitRef,
-    ComponentRef, RegistryRef, RepoRef, SbomRef, ScanRef, SignatureRef, SigningKeyRef,
-    StatementRef, StoreRef, TenantCtx, VersionRef,
+    ComponentRef, PipelineRunRef, PolicyDecision, PolicyDecisionStatus, PolicyRef, RegistryRef,
+    RepoRef, SbomRef, ScanRef, SignatureRef, SigningKeyRef, StatementRef, StoreRef, TenantCtx,
+    UnixMillis, VersionRef,
 };
+use crate::{ErrorCode, GResult, GreenticError};
 
 /// Hasher used for IndexMap fields to stay `no_std` friendly.
 pub type SupplyHasher = BuildHasherDefault<FnvHasher>;
@@ -105,26 +106,32 @@ pub struct BuildStatus {
     pub status: BuildStatusKind,
     /// Build start time (UTC).
     #[cfg_attr(
-        all(feature = "schemars", feature = "time"),
+        feature = "schemars",
         schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
     )]
     #[cfg_attr(
         feature = "serde",
-        serde(default, skip_serializing_if = "Option::is_none")
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
     )]
-    #[cfg(feature = "time")]
-    pub started_at_utc: Option<OffsetDateTime>,
+    pub started_at_utc: Option<UnixMillis>,
     /// Build finish time (UTC).
     #[cfg_attr(
-        all(feature = "schemars", feature = "time"),
+        feature = "schemars",
         schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
     )]
     #[cfg_attr(
         feature = "serde",
-        serde(default, skip_serializing_if = "Option::is_none")
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
     )]
-    #[cfg(feature = "time")]
-    pub finished_at_utc: Option<OffsetDateTime>,
+    pub finished_at_utc: Option<UnixMillis>,
     /// Produced artifacts.
     #[cfg_attr(
         feature = "serde",
@@ -148,6 +155,135 @@ pub struct BuildStatus {
     pub metadata: Value,
 }
 
+/// Output stream a build log chunk was captured from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum BuildLogStream {
+    /// Standard output.
+    Stdout,
+    /// Standard error.
+    Stderr,
+}
+
+/// Slice of build log output streamed by a build provider.
+///
+/// Sequence numbers start at zero and increase by one per chunk of the same log, across both
+/// streams, so consumers can order output and resume after a disconnect (see
+/// [`BuildLogCursor`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BuildLogChunk {
+    /// Log the chunk belongs to.
+    pub log: BuildLogRef,
+    /// Position of the chunk within the log.
+    pub sequence: u64,
+    /// Stream the output was captured from.
+    pub stream: BuildLogStream,
+    /// Raw output bytes (base64 encoded when serialized).
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::base64::Base64>")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub bytes: Vec<u8>,
+    /// Capture time (UTC).
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub emitted_at_utc: Option<UnixMillis>,
+    /// Whether this is the last chunk of the log.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last: bool,
+}
+
+impl BuildLogChunk {
+    /// Returns the output decoded as UTF-8, replacing invalid sequences.
+    pub fn text_lossy(&self) -> alloc::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(&self.bytes)
+    }
+}
+
+/// Consumer-side position in a build log stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BuildLogCursor {
+    /// Log being followed.
+    pub log: BuildLogRef,
+    /// Sequence number expected next; resume requests start here.
+    pub next_sequence: u64,
+    /// Whether the final chunk has been accepted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub complete: bool,
+}
+
+impl BuildLogCursor {
+    /// Starts following `log` from the first chunk.
+    pub fn new(log: BuildLogRef) -> Self {
+        Self {
+            log,
+            next_sequence: 0,
+            complete: false,
+        }
+    }
+
+    /// Advances past `chunk` when it is the next one in sequence.
+    ///
+    /// Returns `Ok(false)` for chunks already seen (redelivery after resumption) and an error
+    /// when the chunk belongs to another log, arrives after the final chunk, or skips ahead.
+    pub fn accept(&mut self, chunk: &BuildLogChunk) -> GResult<bool> {
+        if chunk.log != self.log {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!(
+                    "chunk for log {} does not belong to log {}",
+                    chunk.log.as_str(),
+                    self.log.as_str()
+                ),
+            ));
+        }
+        if chunk.sequence < self.next_sequence {
+            return Ok(false);
+        }
+        if self.complete {
+            return Err(GreenticError::new(
+                ErrorCode::Conflict,
+                format!(
+                    "log {} already completed before sequence {}",
+                    self.log.as_str(),
+                    chunk.sequence
+                ),
+            ));
+        }
+        if chunk.sequence > self.next_sequence {
+            return Err(GreenticError::new(
+                ErrorCode::Conflict,
+                format!(
+                    "log {} skipped from sequence {} to {}",
+                    self.log.as_str(),
+                    self.next_sequence,
+                    chunk.sequence
+                ),
+            ));
+        }
+        self.next_sequence += 1;
+        self.complete = chunk.last;
+        Ok(true)
+    }
+}
+
 /// Supported scan kinds.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -232,26 +368,195 @@ pub struct ScanResult {
     pub findings: Value,
     /// Scan start time (UTC).
     #[cfg_attr(
-        all(feature = "schemars", feature = "time"),
+        feature = "schemars",
         schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
     )]
     #[cfg_attr(
         feature = "serde",
-        serde(default, skip_serializing_if = "Option::is_none")
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
     )]
-    #[cfg(feature = "time")]
-    pub started_at_utc: Option<OffsetDateTime>,
+    pub started_at_utc: Option<UnixMillis>,
     /// Scan finish time (UTC).
     #[cfg_attr(
-        all(feature = "schemars", feature = "time"),
+        feature = "schemars",
         schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
     )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub finished_at_utc: Option<UnixMillis>,
+    /// Optional persisted severity summary derived from the findings.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub summary: Option<SeveritySummary>,
+}
+
+impl ScanResult {
+    /// Returns the persisted severity summary, or derives one from the findings.
+    ///
+    /// Findings are walked recursively; every object carrying a string `severity` field
+    /// (case-insensitive key) counts as one finding.
+    pub fn severity_summary(&self) -> SeveritySummary {
+        self.summary
+            .clone()
+            .unwrap_or_else(|| SeveritySummary::from_findings(&self.findings))
+    }
+
+    /// Derives the severity summary from the findings and stores it on the result.
+    pub fn persist_severity_summary(&mut self) -> &SeveritySummary {
+        self.summary
+            .insert(SeveritySummary::from_findings(&self.findings))
+    }
+}
+
+/// Normalized severity of a scan finding, ordered from least to most severe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum FindingSeverity {
+    /// Severity missing or not recognized.
+    Unknown,
+    /// Informational or negligible finding.
+    Info,
+    /// Low severity.
+    Low,
+    /// Medium severity.
+    Medium,
+    /// High severity.
+    High,
+    /// Critical severity.
+    Critical,
+}
+
+impl FindingSeverity {
+    /// Maps scanner-specific severity labels onto the normalized scale.
+    pub fn parse_label(label: &str) -> Self {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "critical" | "crit" => Self::Critical,
+            "high" | "error" => Self::High,
+            "medium" | "moderate" | "warning" | "warn" => Self::Medium,
+            "low" | "minor" => Self::Low,
+            "info" | "informational" | "negligible" | "note" | "none" => Self::Info,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Finding counts per severity for a scan.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SeveritySummary {
+    /// Critical findings.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub critical: u32,
+    /// High findings.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub high: u32,
+    /// Medium findings.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub medium: u32,
+    /// Low findings.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub low: u32,
+    /// Informational findings.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub info: u32,
+    /// Findings with a missing or unrecognized severity.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unknown: u32,
+    /// Most severe finding, if any were found.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    #[cfg(feature = "time")]
-    pub finished_at_utc: Option<OffsetDateTime>,
+    pub worst: Option<FindingSeverity>,
+}
+
+impl SeveritySummary {
+    /// Derives a summary from scanner findings JSON.
+    pub fn from_findings(findings: &Value) -> Self {
+        let mut summary = Self::default();
+        summary.collect(findings);
+        summary
+    }
+
+    /// Records a single finding.
+    pub fn record(&mut self, severity: FindingSeverity) {
+        let slot = match severity {
+            FindingSeverity::Critical => &mut self.critical,
+            FindingSeverity::High => &mut self.high,
+            FindingSeverity::Medium => &mut self.medium,
+            FindingSeverity::Low => &mut self.low,
+            FindingSeverity::Info => &mut self.info,
+            FindingSeverity::Unknown => &mut self.unknown,
+        };
+        *slot = slot.saturating_add(1);
+        self.worst = self.worst.max(Some(severity));
+    }
+
+    /// Returns the number of findings for the given severity.
+    pub fn count(&self, severity: FindingSeverity) -> u32 {
+        match severity {
+            FindingSeverity::Critical => self.critical,
+            FindingSeverity::High => self.high,
+            FindingSeverity::Medium => self.medium,
+            FindingSeverity::Low => self.low,
+            FindingSeverity::Info => self.info,
+            FindingSeverity::Unknown => self.unknown,
+        }
+    }
+
+    /// Returns the total number of findings.
+    pub fn total(&self) -> u32 {
+        [
+            self.critical,
+            self.high,
+            self.medium,
+            self.low,
+            self.info,
+            self.unknown,
+        ]
+        .into_iter()
+        .fold(0, u32::saturating_add)
+    }
+
+    /// Returns `true` when any finding is at or above `threshold`.
+    pub fn exceeds(&self, threshold: FindingSeverity) -> bool {
+        self.worst.is_some_and(|worst| worst >= threshold)
+    }
+
+    fn collect(&mut self, value: &Value) {
+        match value {
+            Value::Array(items) => items.iter().for_each(|item| self.collect(item)),
+            Value::Object(map) => {
+                let severity = map.iter().find_map(|(key, value)| {
+                    if key.eq_ignore_ascii_case("severity") {
+                        value.as_str()
+                    } else {
+                        None
+                    }
+                });
+                match severity {
+                    Some(label) => self.record(FindingSeverity::parse_label(label)),
+                    None => map.values().for_each(|value| self.collect(value)),
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Signing request for an artifact.
@@ -353,6 +658,773 @@ pub struct AttestationStatement {
     pub metadata: Value,
 }
 
+impl PredicateType {
+    /// Stable label used when hashing statements (`slsa`, `vulnerability`, `custom:<name>`).
+    pub fn label(&self) -> String {
+        match self {
+            Self::Slsa => "slsa".into(),
+            Self::Vulnerability => "vulnerability".into(),
+            Self::Custom(name) => format!("custom:{name}"),
+        }
+    }
+}
+
+/// Set of attestation statements for one artifact, fetched as a single document.
+///
+/// The digest covers the artifact, the bundle metadata, and every field of every statement,
+/// independent of statement order, so clients can detect a bundle that was altered in any way.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct AttestationBundle {
+    /// Artifact every statement refers to.
+    pub artifact: ArtifactRef,
+    /// Statements included in the bundle (provenance, SBOM, scans, ...).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub statements: Vec<AttestationStatement>,
+    /// `sha256:<hex>` digest of the rest of the bundle, set by [`AttestationBundle::seal`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub digest: Option<String>,
+    /// Provider-specific metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: Value,
+}
+
+impl AttestationBundle {
+    /// Creates an empty, unsealed bundle for `artifact`.
+    pub fn new(artifact: ArtifactRef) -> Self {
+        Self {
+            artifact,
+            statements: Vec::new(),
+            digest: None,
+            metadata: Value::Null,
+        }
+    }
+
+    /// Adds a statement and clears any previously computed digest.
+    pub fn push(&mut self, statement: AttestationStatement) {
+        self.statements.push(statement);
+        self.digest = None;
+    }
+
+    /// Computes the `sha256:<hex>` digest of every field except `digest`.
+    ///
+    /// Each component is length-prefixed, optional fields carry a presence marker, and metadata
+    /// is hashed as JSON with sorted object keys, so no two different bundles share an encoding.
+    pub fn compute_digest(&self) -> String {
+        let mut statements: Vec<Vec<u8>> = self
+            .statements
+            .iter()
+            .map(|statement| {
+                let mut encoded = Vec::new();
+                let optional = |encoded: &mut Vec<u8>, value: Option<&str>| match value {
+                    Some(value) => {
+                        encoded.push(1);
+                        push_prefixed(encoded, value.as_bytes());
+                    }
+                    None => encoded.push(0),
+                };
+                optional(
+                    &mut encoded,
+                    statement.attestation_id.as_ref().map(AttestationId::as_str),
+                );
+                push_prefixed(&mut encoded, statement.attestation.as_str().as_bytes());
+                push_prefixed(&mut encoded, statement.predicate_type.label().as_bytes());
+                push_prefixed(&mut encoded, statement.statement.as_str().as_bytes());
+                optional(
+                    &mut encoded,
+                    statement.registry.as_ref().map(RegistryRef::as_str),
+                );
+                optional(&mut encoded, statement.store.as_ref().map(StoreRef::as_str));
+                push_prefixed(&mut encoded, canonical_json(&statement.metadata).as_bytes());
+                encoded
+            })
+            .collect();
+        statements.sort();
+        let mut encoded = Vec::new();
+        push_prefixed(&mut encoded, self.artifact.as_str().as_bytes());
+        push_prefixed(&mut encoded, canonical_json(&self.metadata).as_bytes());
+        encoded.extend_from_slice(&(statements.len() as u64).to_be_bytes());
+        for statement in &statements {
+            push_prefixed(&mut encoded, statement);
+        }
+        format!("sha256:{}", hex_encode(&Sha256::digest(&encoded)))
+    }
+
+    /// Stores the digest of the current statement set.
+    pub fn seal(&mut self) -> &str {
+        self.digest.insert(self.compute_digest())
+    }
+
+    /// Checks that the bundle is sealed and its digest matches the statements.
+    pub fn verify_digest(&self) -> GResult<()> {
+        let Some(digest) = &self.digest else {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                "attestation bundle is not sealed",
+            ));
+        };
+        let expected = self.compute_digest();
+        if *digest != expected {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("attestation bundle digest mismatch: expected {expected}, found {digest}"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the statements carrying the given predicate type.
+    pub fn statements_for<'a>(
+        &'a self,
+        predicate: &'a PredicateType,
+    ) -> impl Iterator<Item = &'a AttestationStatement> + 'a {
+        self.statements
+            .iter()
+            .filter(move |statement| statement.predicate_type == *predicate)
+    }
+
+    /// Returns the predicate types from `required` that no statement provides.
+    pub fn missing_predicates(&self, required: &[PredicateType]) -> Vec<PredicateType> {
+        required
+            .iter()
+            .filter(|predicate| self.statements_for(predicate).next().is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Verifies the digest and that every `required` predicate type is present.
+    pub fn verify(&self, required: &[PredicateType]) -> GResult<()> {
+        self.verify_digest()?;
+        let missing = self.missing_predicates(required);
+        if !missing.is_empty() {
+            let labels: Vec<String> = missing.iter().map(PredicateType::label).collect();
+            return Err(GreenticError::new(
+                ErrorCode::NotFound,
+                format!(
+                    "attestation bundle for {} is missing predicates: {}",
+                    self.artifact.as_str(),
+                    labels.join(", ")
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn push_prefixed(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// JSON text of `value` with object keys sorted, independent of `serde_json`'s map ordering.
+fn canonical_json(value: &Value) -> String {
+    fn write(value: &Value, out: &mut String) {
+        match value {
+            Value::Array(items) => {
+                out.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    write(item, out);
+                }
+                out.push(']');
+            }
+            Value::Object(map) => {
+                let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                out.push('{');
+                for (index, (key, item)) in entries.into_iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&Value::String(key.clone()).to_string());
+                    out.push(':');
+                    write(item, out);
+                }
+                out.push('}');
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+
+    let mut out = String::new();
+    write(value, &mut out);
+    out
+}
+
+/// Outcome of evaluating one policy against a pipeline run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PolicyEvaluation {
+    /// Policy that was evaluated.
+    pub policy: PolicyRef,
+    /// Evaluation decision.
+    pub decision: PolicyDecision,
+}
+
+/// Stage of a supply-chain pipeline run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum PipelineStage {
+    /// Artifact build.
+    Build,
+    /// Vulnerability, license, and SBOM scans.
+    Scan,
+    /// Artifact signing.
+    Sign,
+    /// Signature verification.
+    Verify,
+    /// Policy evaluation.
+    Policy,
+}
+
+impl PipelineStage {
+    /// Every stage in execution order.
+    pub const ALL: [PipelineStage; 5] = [
+        PipelineStage::Build,
+        PipelineStage::Scan,
+        PipelineStage::Sign,
+        PipelineStage::Verify,
+        PipelineStage::Policy,
+    ];
+}
+
+/// Verdict for a pipeline stage or a whole pipeline run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum PipelineVerdict {
+    /// Nothing has started yet.
+    #[default]
+    Pending,
+    /// Work is in progress.
+    Running,
+    /// Every stage completed successfully.
+    Passed,
+    /// Stage produced no results and the run allows skipping it.
+    Skipped,
+    /// Completed, but findings or policy decisions block promotion.
+    Blocked,
+    /// A stage failed.
+    Failed,
+    /// The build was cancelled.
+    Cancelled,
+}
+
+impl PipelineVerdict {
+    /// Returns `true` when no further stage updates can change the verdict.
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::Passed | Self::Blocked | Self::Failed | Self::Cancelled
+        )
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            Self::Failed => 6,
+            Self::Cancelled => 5,
+            Self::Blocked => 4,
+            Self::Running => 3,
+            Self::Pending => 2,
+            Self::Passed => 1,
+            Self::Skipped => 0,
+        }
+    }
+}
+
+/// Aggregated state of a supply-chain pipeline run (build, scans, signing, verification, and
+/// policy evaluation) rendered as one document.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PipelineRunStatus {
+    /// Identifier for the pipeline run.
+    pub run_id: PipelineRunRef,
+    /// Overall verdict, kept current by the `record_*` helpers.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub verdict: PipelineVerdict,
+    /// Build status, once the build has been submitted.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub build: Option<BuildStatus>,
+    /// Scan results, one per scan.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub scans: Vec<ScanResult>,
+    /// Signatures produced for the build outputs.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub signatures: Vec<SignatureRef>,
+    /// Signature verification results.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub verifications: Vec<VerifyResult>,
+    /// Policy evaluations.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub policies: Vec<PolicyEvaluation>,
+    /// Scan findings at or above this severity block the run.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub blocking_severity: Option<FindingSeverity>,
+    /// Stages that may finish without results; every other stage must report before the run can
+    /// pass.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeSet::is_empty")
+    )]
+    pub skippable_stages: BTreeSet<PipelineStage>,
+}
+
+impl PipelineRunStatus {
+    /// Creates an empty, pending pipeline run.
+    pub fn new(run_id: PipelineRunRef) -> Self {
+        Self {
+            run_id,
+            verdict: PipelineVerdict::Pending,
+            build: None,
+            scans: Vec::new(),
+            signatures: Vec::new(),
+            verifications: Vec::new(),
+            policies: Vec::new(),
+            blocking_severity: None,
+            skippable_stages: BTreeSet::new(),
+        }
+    }
+
+    /// Allows `stage` to finish without results.
+    ///
+    /// The build stage is always required and cannot be skipped.
+    pub fn with_skippable_stage(mut self, stage: PipelineStage) -> Self {
+        if stage != PipelineStage::Build {
+            self.skippable_stages.insert(stage);
+        }
+        self.refresh()
+    }
+
+    /// Sets the severity threshold that blocks the run.
+    pub fn with_blocking_severity(mut self, severity: FindingSeverity) -> Self {
+        self.blocking_severity = Some(severity);
+        self.refresh()
+    }
+
+    /// Records the latest build status.
+    pub fn record_build(&mut self, build: BuildStatus) -> PipelineVerdict {
+        self.build = Some(build);
+        self.update_verdict()
+    }
+
+    /// Records a scan result, replacing an earlier result for the same scan.
+    pub fn record_scan(&mut self, scan: ScanResult) -> PipelineVerdict {
+        match self.scans.iter_mut().find(|s| s.scan_id == scan.scan_id) {
+            Some(existing) => *existing = scan,
+            None => self.scans.push(scan),
+        }
+        self.update_verdict()
+    }
+
+    /// Records a produced signature.
+    pub fn record_signature(&mut self, signature: SignatureRef) -> PipelineVerdict {
+        if !self.signatures.contains(&signature) {
+            self.signatures.push(signature);
+        }
+        self.update_verdict()
+    }
+
+    /// Records a verification result, replacing an earlier result for the same signature.
+    pub fn record_verification(&mut self, result: VerifyResult) -> PipelineVerdict {
+        match self
+            .verifications
+            .iter_mut()
+            .find(|v| v.signature == result.signature)
+        {
+            Some(existing) => *existing = result,
+            None => self.verifications.push(result),
+        }
+        self.update_verdict()
+    }
+
+    /// Records a policy evaluation, replacing an earlier one for the same policy.
+    pub fn record_policy(&mut self, evaluation: PolicyEvaluation) -> PipelineVerdict {
+        match self
+            .policies
+            .iter_mut()
+            .find(|p| p.policy == evaluation.policy)
+        {
+            Some(existing) => *existing = evaluation,
+            None => self.policies.push(evaluation),
+        }
+        self.update_verdict()
+    }
+
+    /// Returns the verdict for a single stage.
+    ///
+    /// A stage without results is [`PipelineVerdict::Skipped`] when it is listed in
+    /// [`PipelineRunStatus::skippable_stages`] and [`PipelineVerdict::Pending`] otherwise.
+    pub fn stage_verdict(&self, stage: PipelineStage) -> PipelineVerdict {
+        let missing = if self.skippable_stages.contains(&stage) {
+            PipelineVerdict::Skipped
+        } else {
+            PipelineVerdict::Pending
+        };
+        match stage {
+            PipelineStage::Build => match self.build.as_ref().map(|build| build.status) {
+                None | Some(BuildStatusKind::Pending) => PipelineVerdict::Pending,
+                Some(BuildStatusKind::Running) => PipelineVerdict::Running,
+                Some(BuildStatusKind::Succeeded) => PipelineVerdict::Passed,
+                Some(BuildStatusKind::Failed) => PipelineVerdict::Failed,
+                Some(BuildStatusKind::Cancelled) => PipelineVerdict::Cancelled,
+            },
+            PipelineStage::Scan => {
+                if self.scans.is_empty() {
+                    missing
+                } else if self
+                    .scans
+                    .iter()
+                    .any(|s| s.status == ScanStatusKind::Failed)
+                {
+                    PipelineVerdict::Failed
+                } else if self
+                    .scans
+                    .iter()
+                    .all(|s| s.status == ScanStatusKind::Pending)
+                {
+                    PipelineVerdict::Pending
+                } else if self
+                    .scans
+                    .iter()
+                    .any(|s| s.status != ScanStatusKind::Succeeded)
+                {
+                    PipelineVerdict::Running
+                } else if self.blocking_severity.is_some_and(|threshold| {
+                    self.scans
+                        .iter()
+                        .any(|s| s.severity_summary().exceeds(threshold))
+                }) {
+                    PipelineVerdict::Blocked
+                } else {
+                    PipelineVerdict::Passed
+                }
+            }
+            PipelineStage::Sign => {
+                if self.signatures.is_empty() {
+                    missing
+                } else {
+                    PipelineVerdict::Passed
+                }
+            }
+            PipelineStage::Verify => {
+                if self.verifications.is_empty() {
+                    missing
+                } else if self.verifications.iter().any(|v| !v.valid) {
+                    PipelineVerdict::Failed
+                } else {
+                    PipelineVerdict::Passed
+                }
+            }
+            PipelineStage::Policy => {
+                if self.policies.is_empty() {
+                    missing
+                } else if self
+                    .policies
+                    .iter()
+                    .any(|p| p.decision.status == PolicyDecisionStatus::Deny)
+                {
+                    PipelineVerdict::Blocked
+                } else {
+                    PipelineVerdict::Passed
+                }
+            }
+        }
+    }
+
+    /// Computes the overall verdict from the stage verdicts.
+    ///
+    /// Failures win over cancellation, which wins over blocking; otherwise the run is running
+    /// while any stage is in flight or has yet to report, and passes once every stage passed or
+    /// was skipped.
+    pub fn compute_verdict(&self) -> PipelineVerdict {
+        let stages = PipelineStage::ALL.map(|stage| self.stage_verdict(stage));
+        let worst = stages
+            .into_iter()
+            .max_by_key(|verdict| verdict.precedence())
+            .unwrap_or_default();
+        match worst {
+            PipelineVerdict::Pending if stages.contains(&PipelineVerdict::Passed) => {
+                PipelineVerdict::Running
+            }
+            PipelineVerdict::Skipped => PipelineVerdict::Passed,
+            verdict => verdict,
+        }
+    }
+
+    /// Recomputes and stores the overall verdict.
+    pub fn refresh(mut self) -> Self {
+        self.update_verdict();
+        self
+    }
+
+    fn update_verdict(&mut self) -> PipelineVerdict {
+        self.verdict = self.compute_verdict();
+        self.verdict
+    }
+}
+
+/// Stage of a component version's provenance chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ProvenanceStage {
+    /// Artifact build.
+    Build,
+    /// Scans of the component.
+    Scan,
+    /// Signing of build outputs.
+    Sign,
+    /// Signature verification.
+    Verify,
+    /// Attestation statements.
+    Attest,
+}
+
+impl ProvenanceStage {
+    /// Every stage in chain order.
+    pub const ALL: [ProvenanceStage; 5] = [
+        ProvenanceStage::Build,
+        ProvenanceStage::Scan,
+        ProvenanceStage::Sign,
+        ProvenanceStage::Verify,
+        ProvenanceStage::Attest,
+    ];
+
+    /// Stage that must be recorded before this one, if any.
+    pub fn predecessor(self) -> Option<ProvenanceStage> {
+        match self {
+            Self::Build => None,
+            Self::Scan => Some(Self::Build),
+            Self::Sign => Some(Self::Scan),
+            Self::Verify => Some(Self::Sign),
+            Self::Attest => Some(Self::Verify),
+        }
+    }
+}
+
+/// Stages recorded and missing in a [`ProvenanceChain`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ProvenanceCompleteness {
+    /// Stages with at least one record.
+    pub present: Vec<ProvenanceStage>,
+    /// Stages without any record.
+    pub missing: Vec<ProvenanceStage>,
+}
+
+impl ProvenanceCompleteness {
+    /// Returns `true` when every stage has been recorded.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Broken or mismatched link reported by [`ProvenanceChain::verify_links`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum ProvenanceIssue {
+    /// A stage was recorded although the stage it builds on was not.
+    MissingPredecessor {
+        /// Stage that was recorded.
+        stage: ProvenanceStage,
+        /// Stage that should have been recorded first.
+        requires: ProvenanceStage,
+    },
+    /// The build did not succeed.
+    BuildNotSucceeded {
+        /// Recorded build status.
+        status: BuildStatusKind,
+    },
+    /// A scan covers a different component.
+    ScanComponentMismatch {
+        /// Scan identifier.
+        scan_id: ScanRef,
+        /// Component the scan covers.
+        component: ComponentRef,
+    },
+    /// A scan did not succeed.
+    ScanNotSucceeded {
+        /// Scan identifier.
+        scan_id: ScanRef,
+        /// Recorded scan status.
+        status: ScanStatusKind,
+    },
+    /// A signed artifact is not among the build outputs.
+    SignedArtifactNotBuilt {
+        /// Artifact that was signed.
+        artifact: ArtifactRef,
+    },
+    /// A signature failed verification.
+    VerificationFailed {
+        /// Signature that failed.
+        signature: SignatureRef,
+    },
+}
+
+/// Build, scan, sign, and attestation records for one component version.
+///
+/// Unlike [`PipelineRunStatus`], which tracks a single run while it executes, the chain is the
+/// durable evidence that a released version went through every stage.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ProvenanceChain {
+    /// Component the chain describes.
+    pub component: ComponentRef,
+    /// Component version the chain describes.
+    pub version: VersionRef,
+    /// Build that produced the version.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub build: Option<BuildStatus>,
+    /// Scan results for the version.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub scans: Vec<ScanResult>,
+    /// Signing requests issued for the build outputs.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub signatures: Vec<SignRequest>,
+    /// Signature verification results.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub verifications: Vec<VerifyResult>,
+    /// Attestation statements about the version.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attestations: Vec<AttestationStatement>,
+}
+
+impl ProvenanceChain {
+    /// Creates an empty chain for `component` at `version`.
+    pub fn new(component: ComponentRef, version: VersionRef) -> Self {
+        Self {
+            component,
+            version,
+            build: None,
+            scans: Vec::new(),
+            signatures: Vec::new(),
+            verifications: Vec::new(),
+            attestations: Vec::new(),
+        }
+    }
+
+    /// Returns `true` when at least one record exists for `stage`.
+    pub fn has_stage(&self, stage: ProvenanceStage) -> bool {
+        match stage {
+            ProvenanceStage::Build => self.build.is_some(),
+            ProvenanceStage::Scan => !self.scans.is_empty(),
+            ProvenanceStage::Sign => !self.signatures.is_empty(),
+            ProvenanceStage::Verify => !self.verifications.is_empty(),
+            ProvenanceStage::Attest => !self.attestations.is_empty(),
+        }
+    }
+
+    /// Reports which stages have been recorded.
+    pub fn completeness(&self) -> ProvenanceCompleteness {
+        let (present, missing) = ProvenanceStage::ALL
+            .into_iter()
+            .partition(|stage| self.has_stage(*stage));
+        ProvenanceCompleteness { present, missing }
+    }
+
+    /// Checks that the recorded stages link up.
+    ///
+    /// Every recorded stage needs its predecessor, the build and scans must have succeeded for
+    /// this component, signed artifacts must be build outputs, and verifications must pass.
+    /// Missing trailing stages are not issues; use [`completeness`](Self::completeness) for those.
+    pub fn verify_links(&self) -> Vec<ProvenanceIssue> {
+        let mut issues = Vec::new();
+        for stage in ProvenanceStage::ALL {
+            match stage.predecessor() {
+                Some(requires) if self.has_stage(stage) && !self.has_stage(requires) => {
+                    issues.push(ProvenanceIssue::MissingPredecessor { stage, requires });
+                }
+                _ => {}
+            }
+        }
+        if let Some(build) = &self.build {
+            if build.status != BuildStatusKind::Succeeded {
+                issues.push(ProvenanceIssue::BuildNotSucceeded {
+                    status: build.status,
+                });
+            }
+        }
+        for scan in &self.scans {
+            if scan.component != self.component {
+                issues.push(ProvenanceIssue::ScanComponentMismatch {
+                    scan_id: scan.scan_id.clone(),
+                    component: scan.component.clone(),
+                });
+            }
+            if scan.status != ScanStatusKind::Succeeded {
+                issues.push(ProvenanceIssue::ScanNotSucceeded {
+                    scan_id: scan.scan_id.clone(),
+                    status: scan.status,
+                });
+            }
+        }
+        if let Some(build) = &self.build {
+            for request in &self.signatures {
+                if !build.artifacts.contains(&request.artifact) {
+                    issues.push(ProvenanceIssue::SignedArtifactNotBuilt {
+                        artifact: request.artifact.clone(),
+                    });
+                }
+            }
+        }
+        for result in self.verifications.iter().filter(|result| !result.valid) {
+            issues.push(ProvenanceIssue::VerificationFailed {
+                signature: result.signature.clone(),
+            });
+        }
+        issues
+    }
+}
+
 /// Generic metadata record attached to supply-chain entities.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
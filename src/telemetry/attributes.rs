@@ -0,0 +1,253 @@
+//! Typed builder for OTLP attribute maps.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{OtlpKeys, SpanContext};
+use crate::{ErrorCode, GResult, GreenticError, RunStatus, Subscription, TenantCtx};
+
+/// Namespace reserved for the canonical keys in [`OtlpKeys::ALL`].
+const RESERVED_PREFIX: &str = "greentic.";
+
+/// OTLP attributes keyed by the canonical [`OtlpKeys`].
+///
+/// Known keys are set through dedicated methods or [`TelemetryAttributes::try_insert`], which
+/// rejects keys outside [`OtlpKeys::ALL`]; product-specific keys go through
+/// [`TelemetryAttributes::extra`]. The `greentic.` namespace is reserved for canonical keys, and
+/// deserialization enforces the same rule.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "BTreeMap<String, String>",
+        into = "BTreeMap<String, String>"
+    )
+)]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct TelemetryAttributes {
+    values: BTreeMap<String, String>,
+}
+
+macro_rules! known_attributes {
+    ($($(#[$doc:meta])* $method:ident => $key:ident;)*) => {
+        impl TelemetryAttributes {
+            $(
+                $(#[$doc])*
+                pub fn $method(self, value: impl Into<String>) -> Self {
+                    self.with(OtlpKeys::$key, value)
+                }
+            )*
+        }
+    };
+}
+
+known_attributes! {
+    /// Sets [`OtlpKeys::SERVICE_NAME`].
+    service_name => SERVICE_NAME;
+    /// Sets [`OtlpKeys::PACK_ID`].
+    pack_id => PACK_ID;
+    /// Sets [`OtlpKeys::PACK_VERSION`].
+    pack_version => PACK_VERSION;
+    /// Sets [`OtlpKeys::FLOW_ID`].
+    flow_id => FLOW_ID;
+    /// Sets [`OtlpKeys::NODE_ID`].
+    node_id => NODE_ID;
+    /// Sets [`OtlpKeys::COMPONENT_NAME`].
+    component_name => COMPONENT_NAME;
+    /// Sets [`OtlpKeys::COMPONENT_VERSION`].
+    component_version => COMPONENT_VERSION;
+    /// Sets [`OtlpKeys::TENANT_ID`].
+    tenant_id => TENANT_ID;
+    /// Sets [`OtlpKeys::TEAM_ID`].
+    team_id => TEAM_ID;
+    /// Sets [`OtlpKeys::USER_ID`].
+    user_id => USER_ID;
+    /// Sets [`OtlpKeys::SESSION_ID`].
+    session_id => SESSION_ID;
+    /// Sets [`OtlpKeys::CAPABILITY`].
+    capability => CAPABILITY;
+    /// Sets [`OtlpKeys::ARTIFACTS_DIR`].
+    artifacts_dir => ARTIFACTS_DIR;
+    /// Sets [`OtlpKeys::STORE_PRODUCT_ID`].
+    store_product_id => STORE_PRODUCT_ID;
+    /// Sets [`OtlpKeys::STORE_PLAN_ID`].
+    store_plan_id => STORE_PLAN_ID;
+    /// Sets [`OtlpKeys::STORE_SUBSCRIPTION_ID`].
+    store_subscription_id => STORE_SUBSCRIPTION_ID;
+    /// Sets [`OtlpKeys::BUILD_REF`].
+    build_ref => BUILD_REF;
+    /// Sets [`OtlpKeys::SCAN_REF`].
+    scan_ref => SCAN_REF;
+    /// Sets [`OtlpKeys::ATTESTATION_ID`].
+    attestation_id => ATTESTATION_ID;
+}
+
+impl TelemetryAttributes {
+    /// Creates an empty attribute set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets [`OtlpKeys::RUN_STATUS`] using the status' wire name.
+    pub fn run_status(self, status: RunStatus) -> Self {
+        let value = match status {
+            RunStatus::Pending => "pending",
+            RunStatus::Running => "running",
+            RunStatus::Success => "success",
+            RunStatus::PartialFailure => "partial_failure",
+            RunStatus::Failure => "failure",
+        };
+        self.with(OtlpKeys::RUN_STATUS, value)
+    }
+
+    /// Sets a known attribute chosen at runtime.
+    ///
+    /// Fails with [`ErrorCode::InvalidInput`] when `key` is not one of [`OtlpKeys::ALL`].
+    pub fn try_insert(&mut self, key: &str, value: impl Into<String>) -> GResult<()> {
+        if !OtlpKeys::is_known(key) {
+            return Err(GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("unknown telemetry attribute key '{key}'; use `extra` for custom keys"),
+            ));
+        }
+        self.values.insert(key.into(), value.into());
+        Ok(())
+    }
+
+    /// Sets an attribute that is not part of [`OtlpKeys`].
+    ///
+    /// Use a product-specific namespace (for example `acme.queue.depth`) to avoid clashing with
+    /// future canonical keys. Fails with [`ErrorCode::InvalidInput`] for keys in the reserved
+    /// `greentic.` namespace that are not one of [`OtlpKeys::ALL`].
+    pub fn extra(mut self, key: impl Into<String>, value: impl Into<String>) -> GResult<Self> {
+        let key = key.into();
+        check_key(&key)?;
+        self.values.insert(key, value.into());
+        Ok(self)
+    }
+
+    /// Adds every attribute of `other`, overriding existing values.
+    pub fn merge(mut self, other: TelemetryAttributes) -> Self {
+        self.values.extend(other.values);
+        self
+    }
+
+    /// Returns the value recorded for `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` when no attribute is set.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over attributes in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns the attributes as a map for exporters.
+    pub fn as_map(&self) -> &BTreeMap<String, String> {
+        &self.values
+    }
+
+    /// Consumes the builder and returns the attribute map.
+    pub fn into_map(self) -> BTreeMap<String, String> {
+        self.values
+    }
+
+    fn with(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.values.insert(key.into(), value.into());
+        self
+    }
+
+    fn with_opt(self, key: &str, value: Option<&str>) -> Self {
+        match value {
+            Some(value) => self.with(key, value),
+            None => self,
+        }
+    }
+}
+
+/// Rejects keys in the reserved namespace that are not canonical.
+fn check_key(key: &str) -> GResult<()> {
+    if key.starts_with(RESERVED_PREFIX) && !OtlpKeys::is_known(key) {
+        return Err(GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "telemetry attribute key '{key}' uses the reserved `{RESERVED_PREFIX}` namespace"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+impl TryFrom<BTreeMap<String, String>> for TelemetryAttributes {
+    type Error = GreenticError;
+
+    fn try_from(values: BTreeMap<String, String>) -> GResult<Self> {
+        values.keys().try_for_each(|key| check_key(key))?;
+        Ok(Self { values })
+    }
+}
+
+impl From<&TenantCtx> for TelemetryAttributes {
+    fn from(ctx: &TenantCtx) -> Self {
+        Self::new()
+            .tenant_id(ctx.tenant_id.as_str())
+            .with_opt(
+                OtlpKeys::TEAM_ID,
+                ctx.team_id.as_ref().map(|id| id.as_str()),
+            )
+            .with_opt(
+                OtlpKeys::USER_ID,
+                ctx.user_id.as_ref().map(|id| id.as_str()),
+            )
+            .with_opt(OtlpKeys::SESSION_ID, ctx.session_id())
+            .with_opt(OtlpKeys::FLOW_ID, ctx.flow_id())
+            .with_opt(OtlpKeys::NODE_ID, ctx.node_id())
+    }
+}
+
+impl From<&SpanContext> for TelemetryAttributes {
+    fn from(span: &SpanContext) -> Self {
+        Self::new()
+            .tenant_id(span.tenant.as_str())
+            .with_opt(
+                OtlpKeys::SESSION_ID,
+                span.session_id.as_ref().map(|key| key.as_str()),
+            )
+            .flow_id(span.flow_id.as_str())
+            .with_opt(OtlpKeys::NODE_ID, span.node_id.as_deref())
+    }
+}
+
+impl From<&Subscription> for TelemetryAttributes {
+    fn from(subscription: &Subscription) -> Self {
+        Self::from(&subscription.tenant_ctx)
+            .store_subscription_id(subscription.id.as_str())
+            .store_product_id(subscription.product_id.as_str())
+            .store_plan_id(subscription.plan_id.as_str())
+    }
+}
+
+impl From<TelemetryAttributes> for BTreeMap<String, String> {
+    fn from(attributes: TelemetryAttributes) -> Self {
+        attributes.values
+    }
+}
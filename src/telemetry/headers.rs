@@ -0,0 +1,127 @@
+//! Propagation of [`SpanContext`] through message broker headers.
+//!
+//! Event providers publishing to NATS, Kafka, or similar brokers copy the context into message
+//! headers with [`SpanContext::to_message_headers`] and restore it on the consumer side with
+//! [`SpanContext::from_message_headers`]. Trace context uses the W3C `traceparent` and
+//! `tracestate` names; Greentic identifiers use `greentic-*` names. The broker client's header
+//! type plugs in through [`MessageHeaders`].
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+use super::{SpanContext, TraceParent, TraceState};
+use crate::{GResult, SessionKey, TenantId};
+
+/// Header carrying the W3C `traceparent`.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+/// Header carrying the W3C `tracestate`.
+pub const TRACESTATE_HEADER: &str = "tracestate";
+/// Header carrying [`SpanContext::tenant`].
+pub const TENANT_HEADER: &str = "greentic-tenant-id";
+/// Header carrying [`SpanContext::flow_id`].
+pub const FLOW_HEADER: &str = "greentic-flow-id";
+/// Header carrying [`SpanContext::node_id`].
+pub const NODE_HEADER: &str = "greentic-node-id";
+/// Header carrying [`SpanContext::session_id`].
+pub const SESSION_HEADER: &str = "greentic-session-id";
+
+/// String header map of a message broker client.
+///
+/// Implementations for clients with byte-valued headers (such as Kafka) should skip values that
+/// are not UTF-8.
+pub trait MessageHeaders {
+    /// Returns the value of header `name`.
+    fn get_header(&self, name: &str) -> Option<&str>;
+
+    /// Sets header `name`, replacing any previous value.
+    fn set_header(&mut self, name: &str, value: String);
+}
+
+impl MessageHeaders for BTreeMap<String, String> {
+    fn get_header(&self, name: &str) -> Option<&str> {
+        self.get(name).map(String::as_str)
+    }
+
+    fn set_header(&mut self, name: &str, value: String) {
+        self.insert(name.to_string(), value);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: core::hash::BuildHasher> MessageHeaders for std::collections::HashMap<String, String, S> {
+    fn get_header(&self, name: &str) -> Option<&str> {
+        self.get(name).map(String::as_str)
+    }
+
+    fn set_header(&mut self, name: &str, value: String) {
+        self.insert(name.to_string(), value);
+    }
+}
+
+impl SpanContext {
+    /// Writes the trace context and Greentic identifiers into `headers`.
+    ///
+    /// Links, timestamps, and the provider stay local to the span.
+    pub fn to_message_headers(&self, headers: &mut impl MessageHeaders) {
+        headers.set_header(TENANT_HEADER, self.tenant.to_string());
+        headers.set_header(FLOW_HEADER, self.flow_id.clone());
+        if let Some(node) = &self.node_id {
+            headers.set_header(NODE_HEADER, node.clone());
+        }
+        if let Some(session) = &self.session_id {
+            headers.set_header(SESSION_HEADER, session.as_str().to_string());
+        }
+        if let Some(parent) = &self.trace_parent {
+            headers.set_header(TRACEPARENT_HEADER, parent.to_string());
+        }
+        if let Some(state) = self.trace_state.as_ref().filter(|state| !state.is_empty()) {
+            headers.set_header(TRACESTATE_HEADER, state.to_string());
+        }
+    }
+
+    /// Restores a context written by [`SpanContext::to_message_headers`], attributed to the
+    /// consuming `provider`.
+    ///
+    /// Returns `Ok(None)` when the tenant or flow header is absent, and an error when the tenant
+    /// header is malformed. Trace headers come from [`SpanContext::trace_from_message_headers`],
+    /// so a broken trace context from an upstream producer never drops the message's context.
+    pub fn from_message_headers(
+        headers: &impl MessageHeaders,
+        provider: impl Into<String>,
+    ) -> GResult<Option<Self>> {
+        let (Some(tenant), Some(flow)) = (
+            headers.get_header(TENANT_HEADER),
+            headers.get_header(FLOW_HEADER),
+        ) else {
+            return Ok(None);
+        };
+        let tenant: TenantId = tenant.parse()?;
+        let mut context = Self::new(tenant, flow, provider);
+        if let Some(node) = headers.get_header(NODE_HEADER) {
+            context = context.with_node(node);
+        }
+        if let Some(session) = headers.get_header(SESSION_HEADER) {
+            context = context.with_session(SessionKey::new(session));
+        }
+        if let Some((parent, state)) = Self::trace_from_message_headers(headers) {
+            context = context.with_trace(parent, state);
+        }
+        Ok(Some(context))
+    }
+
+    /// Reads the W3C trace context from `headers`, with or without the Greentic headers.
+    ///
+    /// Follows the W3C propagation rules: an invalid `traceparent` is treated as absent, and an
+    /// invalid `tracestate` is ignored while the parent is kept. Consumers of messages from
+    /// non-Greentic producers use this to continue the trace when
+    /// [`SpanContext::from_message_headers`] returns `None`.
+    pub fn trace_from_message_headers(
+        headers: &impl MessageHeaders,
+    ) -> Option<(TraceParent, Option<TraceState>)> {
+        let parent = TraceParent::parse(headers.get_header(TRACEPARENT_HEADER)?).ok()?;
+        let state = headers
+            .get_header(TRACESTATE_HEADER)
+            .and_then(|state| TraceState::parse(state).ok());
+        Some((parent, state))
+    }
+}
@@ -40,4 +40,45 @@ impl OtlpKeys {
     pub const CAPABILITY: &'static str = "greentic.capability";
     /// Artifact directory attribute.
     pub const ARTIFACTS_DIR: &'static str = "greentic.artifacts.dir";
+    /// Store product identifier attribute.
+    pub const STORE_PRODUCT_ID: &'static str = "greentic.store.product.id";
+    /// Store plan identifier attribute.
+    pub const STORE_PLAN_ID: &'static str = "greentic.store.plan.id";
+    /// Store subscription identifier attribute.
+    pub const STORE_SUBSCRIPTION_ID: &'static str = "greentic.store.subscription.id";
+    /// Supply-chain build reference attribute.
+    pub const BUILD_REF: &'static str = "greentic.supply_chain.build.ref";
+    /// Supply-chain scan reference attribute.
+    pub const SCAN_REF: &'static str = "greentic.supply_chain.scan.ref";
+    /// Supply-chain attestation identifier attribute.
+    pub const ATTESTATION_ID: &'static str = "greentic.supply_chain.attestation.id";
+
+    /// Every canonical key.
+    pub const ALL: [&'static str; 20] = [
+        Self::SERVICE_NAME,
+        Self::PACK_ID,
+        Self::PACK_VERSION,
+        Self::FLOW_ID,
+        Self::NODE_ID,
+        Self::COMPONENT_NAME,
+        Self::COMPONENT_VERSION,
+        Self::TENANT_ID,
+        Self::TEAM_ID,
+        Self::USER_ID,
+        Self::SESSION_ID,
+        Self::RUN_STATUS,
+        Self::CAPABILITY,
+        Self::ARTIFACTS_DIR,
+        Self::STORE_PRODUCT_ID,
+        Self::STORE_PLAN_ID,
+        Self::STORE_SUBSCRIPTION_ID,
+        Self::BUILD_REF,
+        Self::SCAN_REF,
+        Self::ATTESTATION_ID,
+    ];
+
+    /// Returns `true` when `key` is one of the canonical keys.
+    pub fn is_known(key: &str) -> bool {
+        Self::ALL.contains(&key)
+    }
 }
@@ -1,12 +1,26 @@
 //! Telemetry helpers exposed by `greentic-types`.
 
+#[cfg(feature = "otel-keys")]
+mod attributes;
+mod headers;
 #[cfg(feature = "otel-keys")]
 mod keys;
 mod span_context;
+mod trace_context;
+pub mod truncate;
 
+#[cfg(feature = "otel-keys")]
+pub use attributes::TelemetryAttributes;
+pub use headers::{
+    FLOW_HEADER, MessageHeaders, NODE_HEADER, SESSION_HEADER, TENANT_HEADER, TRACEPARENT_HEADER,
+    TRACESTATE_HEADER,
+};
 #[cfg(feature = "otel-keys")]
 pub use keys::OtlpKeys;
 pub use span_context::SpanContext;
+pub use trace_context::{
+    SpanLink, SpanLinkKind, TRACE_STATE_MAX_MEMBERS, TraceContextError, TraceParent, TraceState,
+};
 
 #[cfg(feature = "telemetry-autoinit")]
 use greentic_telemetry::set_current_telemetry_ctx;
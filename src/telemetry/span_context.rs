@@ -1,6 +1,7 @@
 //! Telemetry span context shared across providers.
 
 use alloc::string::String;
+use alloc::vec::Vec;
 
 #[cfg(feature = "schemars")]
 use schemars::JsonSchema;
@@ -9,7 +10,8 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "time")]
 use time::OffsetDateTime;
 
-use crate::{SessionKey, TenantId};
+use super::{SpanLink, SpanLinkKind, TraceParent, TraceState};
+use crate::{SessionKey, TenantId, UnixMillis};
 
 /// Minimal telemetry context compatible with OTLP semantic conventions.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -34,36 +36,52 @@ pub struct SpanContext {
     pub node_id: Option<String>,
     /// Provider or runtime emitting the span.
     pub provider: String,
+    /// W3C `traceparent` identifying this span's trace and parent.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub trace_parent: Option<TraceParent>,
+    /// Vendor-specific W3C `tracestate` propagated with the trace.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub trace_state: Option<TraceState>,
+    /// Spans outside the parent chain that this span relates to.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub links: Vec<SpanLink>,
     /// Span start timestamp.
-    #[cfg(feature = "time")]
     #[cfg_attr(
         feature = "serde",
         serde(
             default,
             skip_serializing_if = "Option::is_none",
-            with = "time::serde::rfc3339::option"
+            with = "crate::units::rfc3339::option"
         )
     )]
     #[cfg_attr(
         feature = "schemars",
         schemars(with = "Option<String>", description = "RFC3339 timestamp")
     )]
-    pub start: Option<OffsetDateTime>,
+    pub start: Option<UnixMillis>,
     /// Span end timestamp.
-    #[cfg(feature = "time")]
     #[cfg_attr(
         feature = "serde",
         serde(
             default,
             skip_serializing_if = "Option::is_none",
-            with = "time::serde::rfc3339::option"
+            with = "crate::units::rfc3339::option"
         )
     )]
     #[cfg_attr(
         feature = "schemars",
         schemars(with = "Option<String>", description = "RFC3339 timestamp")
     )]
-    pub end: Option<OffsetDateTime>,
+    pub end: Option<UnixMillis>,
 }
 
 impl SpanContext {
@@ -75,9 +93,10 @@ impl SpanContext {
             flow_id: flow_id.into(),
             node_id: None,
             provider: provider.into(),
-            #[cfg(feature = "time")]
+            trace_parent: None,
+            trace_state: None,
+            links: Vec::new(),
             start: None,
-            #[cfg(feature = "time")]
             end: None,
         }
     }
@@ -94,16 +113,39 @@ impl SpanContext {
         self
     }
 
-    /// Marks the span start timestamp.
+    /// Sets the `traceparent` and, optionally, the `tracestate`.
+    pub fn with_trace(mut self, parent: TraceParent, state: Option<TraceState>) -> Self {
+        self.trace_parent = Some(parent);
+        self.trace_state = state;
+        self
+    }
+
+    /// Adds a link to the span identified by `linked`.
+    pub fn with_link(mut self, linked: &TraceParent, kind: SpanLinkKind) -> Self {
+        self.links.push(SpanLink::new(linked, kind));
+        self
+    }
+
+    /// Marks the span start timestamp, truncated to whole milliseconds.
     #[cfg(feature = "time")]
-    pub fn started(mut self, start: OffsetDateTime) -> Self {
+    pub fn started(self, start: OffsetDateTime) -> Self {
+        self.started_at(start.into())
+    }
+
+    /// Marks the span end timestamp, truncated to whole milliseconds.
+    #[cfg(feature = "time")]
+    pub fn finished(self, end: OffsetDateTime) -> Self {
+        self.finished_at(end.into())
+    }
+
+    /// Marks the span start as Unix epoch milliseconds.
+    pub fn started_at(mut self, start: UnixMillis) -> Self {
         self.start = Some(start);
         self
     }
 
-    /// Marks the span end timestamp.
-    #[cfg(feature = "time")]
-    pub fn finished(mut self, end: OffsetDateTime) -> Self {
+    /// Marks the span end as Unix epoch milliseconds.
+    pub fn finished_at(mut self, end: UnixMillis) -> Self {
         self.end = Some(end);
         self
     }
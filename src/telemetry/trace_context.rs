@@ -0,0 +1,352 @@
+//! W3C Trace Context headers and span links.
+//!
+//! [`TraceParent`] and [`TraceState`] hold the `traceparent` / `tracestate` header values in
+//! canonical form, so they serialize as the exact strings providers forward to external systems.
+//! [`SpanLink`] records causality that does not fit a single parent, such as batch consumers or
+//! retries.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of list members in a `tracestate` header.
+pub const TRACE_STATE_MAX_MEMBERS: usize = 32;
+
+/// Errors produced when parsing or building trace context headers.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum TraceContextError {
+    /// `traceparent` value is malformed.
+    #[error("invalid traceparent: {reason}")]
+    InvalidTraceParent {
+        /// Why the value was rejected.
+        reason: &'static str,
+    },
+    /// `tracestate` value is malformed.
+    #[error("invalid tracestate: {reason}")]
+    InvalidTraceState {
+        /// Why the value was rejected.
+        reason: String,
+    },
+}
+
+/// W3C `traceparent` header in canonical version `00` form.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct TraceParent(String);
+
+impl TraceParent {
+    /// Builds a header from lowercase hex trace and span identifiers.
+    pub fn new(trace_id: &str, span_id: &str, flags: u8) -> Result<Self, TraceContextError> {
+        check_id(
+            trace_id,
+            32,
+            "trace-id must be 32 lowercase hex digits",
+            "trace-id must not be all zeros",
+        )?;
+        check_id(
+            span_id,
+            16,
+            "parent-id must be 16 lowercase hex digits",
+            "parent-id must not be all zeros",
+        )?;
+        Ok(Self(format!("00-{trace_id}-{span_id}-{flags:02x}")))
+    }
+
+    /// Parses a `traceparent` header value.
+    ///
+    /// Headers from future versions are accepted when their leading fields are well formed; the
+    /// result is always re-encoded as version `00`.
+    pub fn parse(header: &str) -> Result<Self, TraceContextError> {
+        let mut parts = header.split('-');
+        let mut next = || {
+            parts
+                .next()
+                .ok_or(invalid_parent("expected four '-' separated fields"))
+        };
+        let version = next()?;
+        let trace_id = next()?;
+        let span_id = next()?;
+        let flags = next()?;
+        let has_extra_fields = parts.next().is_some();
+
+        if !is_lower_hex(version, 2) || version == "ff" {
+            return Err(invalid_parent(
+                "version must be two hex digits other than ff",
+            ));
+        }
+        if version == "00" && has_extra_fields {
+            return Err(invalid_parent("version 00 has exactly four fields"));
+        }
+        if !is_lower_hex(flags, 2) {
+            return Err(invalid_parent(
+                "trace-flags must be two lowercase hex digits",
+            ));
+        }
+        let flags = u8::from_str_radix(flags, 16)
+            .map_err(|_| invalid_parent("trace-flags must be two lowercase hex digits"))?;
+        Self::new(trace_id, span_id, flags)
+    }
+
+    /// 32-character trace identifier.
+    pub fn trace_id(&self) -> &str {
+        &self.0[3..35]
+    }
+
+    /// 16-character identifier of the parent span.
+    pub fn span_id(&self) -> &str {
+        &self.0[36..52]
+    }
+
+    /// Trace flags byte.
+    pub fn flags(&self) -> u8 {
+        u8::from_str_radix(&self.0[53..55], 16).unwrap_or_default()
+    }
+
+    /// Returns `true` when the `sampled` flag is set.
+    pub fn is_sampled(&self) -> bool {
+        self.flags() & 0x01 != 0
+    }
+
+    /// Returns the header value.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for TraceParent {
+    type Err = TraceContextError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for TraceParent {
+    type Error = TraceContextError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<TraceParent> for String {
+    fn from(value: TraceParent) -> Self {
+        value.0
+    }
+}
+
+/// W3C `tracestate` header, kept as canonical `key=value` members joined by `,`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct TraceState(String);
+
+impl TraceState {
+    /// Parses a `tracestate` header value, dropping empty members and optional whitespace.
+    pub fn parse(header: &str) -> Result<Self, TraceContextError> {
+        let mut members: Vec<(&str, &str)> = Vec::new();
+        for member in header.split(',') {
+            let member = member.trim_matches([' ', '\t']);
+            if member.is_empty() {
+                continue;
+            }
+            let (key, value) = member.split_once('=').ok_or_else(|| {
+                invalid_state(format!("member `{member}` is not a key=value pair"))
+            })?;
+            check_state_member(key, value)?;
+            if members.iter().any(|(existing, _)| *existing == key) {
+                return Err(invalid_state(format!("duplicate key `{key}`")));
+            }
+            members.push((key, value));
+        }
+        if members.len() > TRACE_STATE_MAX_MEMBERS {
+            return Err(invalid_state(format!(
+                "more than {TRACE_STATE_MAX_MEMBERS} members"
+            )));
+        }
+        Ok(Self::join(members.into_iter()))
+    }
+
+    /// Returns the members in header order.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .split(',')
+            .filter(|member| !member.is_empty())
+            .filter_map(|member| member.split_once('='))
+    }
+
+    /// Returns the value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries()
+            .find(|(existing, _)| *existing == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Sets `key` to `value`, moving it to the front as required when a vendor updates its entry.
+    ///
+    /// The oldest members are dropped to stay within [`TRACE_STATE_MAX_MEMBERS`].
+    pub fn insert(&mut self, key: &str, value: &str) -> Result<(), TraceContextError> {
+        check_state_member(key, value)?;
+        let members = core::iter::once((key, value))
+            .chain(self.entries().filter(|(existing, _)| *existing != key))
+            .take(TRACE_STATE_MAX_MEMBERS);
+        *self = Self::join(members);
+        Ok(())
+    }
+
+    /// Returns `true` when there are no members.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the header value.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn join<'a>(members: impl Iterator<Item = (&'a str, &'a str)>) -> Self {
+        let members: Vec<String> = members
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        Self(members.join(","))
+    }
+}
+
+impl fmt::Display for TraceState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for TraceState {
+    type Err = TraceContextError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for TraceState {
+    type Error = TraceContextError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<TraceState> for String {
+    fn from(value: TraceState) -> Self {
+        value.0
+    }
+}
+
+/// Relationship between a span and a linked span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum SpanLinkKind {
+    /// The span was caused by the linked span but does not wait on it.
+    FollowsFrom,
+    /// The span processes the linked span's message as part of a batch.
+    BatchedWith,
+    /// The span retries the work of the linked span.
+    RetriedFrom,
+}
+
+/// Link from a span to a span outside its parent chain.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct SpanLink {
+    /// Trace identifier of the linked span (32 lowercase hex characters).
+    pub trace_id: String,
+    /// Identifier of the linked span (16 lowercase hex characters).
+    pub span_id: String,
+    /// Relationship to the linked span.
+    pub kind: SpanLinkKind,
+}
+
+impl SpanLink {
+    /// Links to the span identified by `parent`.
+    pub fn new(parent: &TraceParent, kind: SpanLinkKind) -> Self {
+        Self {
+            trace_id: parent.trace_id().to_owned(),
+            span_id: parent.span_id().to_owned(),
+            kind,
+        }
+    }
+}
+
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn check_id(
+    value: &str,
+    len: usize,
+    malformed: &'static str,
+    zero: &'static str,
+) -> Result<(), TraceContextError> {
+    if !is_lower_hex(value, len) {
+        return Err(invalid_parent(malformed));
+    }
+    if value.bytes().all(|b| b == b'0') {
+        return Err(invalid_parent(zero));
+    }
+    Ok(())
+}
+
+fn check_state_member(key: &str, value: &str) -> Result<(), TraceContextError> {
+    let key_ok = (1..=256).contains(&key.len())
+        && key
+            .bytes()
+            .next()
+            .is_some_and(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        && key.bytes().all(|b| {
+            b.is_ascii_lowercase()
+                || b.is_ascii_digit()
+                || matches!(b, b'_' | b'-' | b'*' | b'/' | b'@')
+        })
+        && key.bytes().filter(|b| *b == b'@').count() <= 1;
+    if !key_ok {
+        return Err(invalid_state(format!("invalid key `{key}`")));
+    }
+    let value_ok = (1..=256).contains(&value.len())
+        && value
+            .bytes()
+            .all(|b| (0x20..=0x7e).contains(&b) && b != b',' && b != b'=')
+        && !value.ends_with(' ');
+    if !value_ok {
+        return Err(invalid_state(format!("invalid value for key `{key}`")));
+    }
+    Ok(())
+}
+
+fn invalid_parent(reason: &'static str) -> TraceContextError {
+    TraceContextError::InvalidTraceParent { reason }
+}
+
+fn invalid_state(reason: String) -> TraceContextError {
+    TraceContextError::InvalidTraceState { reason }
+}
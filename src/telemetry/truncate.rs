@@ -0,0 +1,98 @@
+//! Byte-budget truncation for telemetry payloads.
+//!
+//! OTLP exporters and collectors reject spans whose attribute values exceed their size limits,
+//! dropping the whole span. These helpers shorten oversized values instead. Truncation is
+//! deterministic: the kept prefix ends on a character boundary and is followed by a marker naming
+//! how many bytes were removed and a Blake3 digest prefix of the removed content, so identical
+//! inputs still group together in the backend and the original can be matched against logs.
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use crate::{ErrorDetail, NodeError};
+
+/// Hex characters of the digest kept in the truncation marker.
+const DIGEST_HEX_LEN: usize = 16;
+
+/// Shortens `value` to at most `max_bytes` bytes.
+///
+/// The result ends with `…[+N bytes blake3:xxxxxxxxxxxxxxxx]` describing the removed tail. When
+/// the budget cannot even hold the marker, the value is cut at `max_bytes` without one.
+pub fn truncate_str(value: &str, max_bytes: usize) -> Cow<'_, str> {
+    if value.len() <= max_bytes {
+        return Cow::Borrowed(value);
+    }
+    // The marker grows with the removed byte count; reserve room for the largest possible one.
+    let reserved = marker_len(value.len());
+    if reserved > max_bytes {
+        return Cow::Owned(value[..floor_char_boundary(value, max_bytes)].into());
+    }
+    let keep = floor_char_boundary(value, max_bytes - reserved);
+    let (kept, removed) = value.split_at(keep);
+    Cow::Owned(format!("{kept}{}", marker(removed.as_bytes())))
+}
+
+/// Truncates every attribute value longer than `max_bytes` and returns how many were shortened.
+pub fn truncate_attributes(map: &mut BTreeMap<String, String>, max_bytes: usize) -> usize {
+    let mut truncated = 0;
+    for value in map.values_mut() {
+        if let Cow::Owned(shorter) = truncate_str(value, max_bytes) {
+            *value = shorter;
+            truncated += 1;
+        }
+    }
+    truncated
+}
+
+/// Limits the message and detail of `error` to `max_bytes` each; returns `true` when anything
+/// was shortened.
+///
+/// Text details are truncated like attribute values. Binary details over budget are replaced by
+/// a text note carrying their size and digest, since exporters cannot render them anyway.
+pub fn truncate_error_detail(error: &mut NodeError, max_bytes: usize) -> bool {
+    let mut changed = false;
+    if let Cow::Owned(message) = truncate_str(&error.message, max_bytes) {
+        error.message = message;
+        changed = true;
+    }
+    match &mut error.details {
+        Some(ErrorDetail::Text(text)) => {
+            if let Cow::Owned(shorter) = truncate_str(text, max_bytes) {
+                *text = shorter;
+                changed = true;
+            }
+        }
+        Some(ErrorDetail::Binary(bytes)) if bytes.len() > max_bytes => {
+            let note = format!("[binary detail]{}", marker(bytes));
+            error.details = Some(ErrorDetail::Text(
+                truncate_str(&note, max_bytes).into_owned(),
+            ));
+            changed = true;
+        }
+        _ => {}
+    }
+    changed
+}
+
+fn marker(removed: &[u8]) -> String {
+    let digest = blake3::hash(removed).to_hex();
+    format!(
+        "…[+{} bytes blake3:{}]",
+        removed.len(),
+        &digest.as_str()[..DIGEST_HEX_LEN]
+    )
+}
+
+fn marker_len(removed: usize) -> usize {
+    "…[+ bytes blake3:]".len() + removed.to_string().len() + DIGEST_HEX_LEN
+}
+
+fn floor_char_boundary(value: &str, index: usize) -> usize {
+    let mut index = index.min(value.len());
+    while !value.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
@@ -8,7 +8,7 @@ use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{TeamId, TenantContext, TenantCtx, TenantId, UserId};
+use crate::{DurationMs, EnvId, TeamId, TenantContext, TenantCtx, TenantId, UnixMillis, UserId};
 
 /// Metadata describing an impersonated user acting on behalf of the main identity.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -25,6 +25,88 @@ pub struct Impersonation {
     pub reason: Option<String>,
 }
 
+/// Audit record for one impersonation session.
+///
+/// Services emit an event whenever they act on a [`TenantCtx`] with `impersonation` set, so
+/// break-glass access can be reviewed: who acted, on which tenant and user, why, for how long, and
+/// how many actions were taken.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct ImpersonationEvent {
+    /// User performing the impersonation.
+    pub actor_id: UserId,
+    /// Environment of the target tenant.
+    pub env: EnvId,
+    /// Tenant being accessed.
+    pub target_tenant: TenantId,
+    /// User being impersonated, when acting as a specific user.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub target_user: Option<UserId>,
+    /// Justification recorded when the impersonation began.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub reason: Option<String>,
+    /// When the impersonation began.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "String", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(feature = "serde", serde(with = "crate::units::rfc3339"))]
+    pub started_at: UnixMillis,
+    /// When the impersonation ended; `None` while it is still active.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub ended_at: Option<UnixMillis>,
+    /// Number of actions taken while impersonating.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub actions: u64,
+    /// Correlation identifier of the originating request.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub correlation_id: Option<String>,
+}
+
+impl ImpersonationEvent {
+    /// Returns `true` until [`Self::end`] has been called.
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    /// Counts one action taken under the impersonation.
+    pub fn record_action(&mut self) {
+        self.actions = self.actions.saturating_add(1);
+    }
+
+    /// Marks the impersonation as ended at `at`.
+    pub fn end(&mut self, at: UnixMillis) {
+        self.ended_at = Some(at);
+    }
+
+    /// Length of the session, once ended.
+    pub fn duration(&self) -> Option<DurationMs> {
+        self.ended_at
+            .map(|ended| ended.saturating_duration_since(self.started_at))
+    }
+}
+
 /// Stable multi-tenant identity extracted from [`TenantCtx`].
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -99,6 +181,22 @@ impl TenantCtx {
         self.impersonation.as_ref()
     }
 
+    /// Opens the audit event for this context's impersonation, when one is set.
+    pub fn impersonation_event(&self, started_at: UnixMillis) -> Option<ImpersonationEvent> {
+        let impersonation = self.impersonation.as_ref()?;
+        Some(ImpersonationEvent {
+            actor_id: impersonation.actor_id.clone(),
+            env: self.env.clone(),
+            target_tenant: self.tenant_id.clone(),
+            target_user: self.user_id.clone().or_else(|| self.user.clone()),
+            reason: impersonation.reason.clone(),
+            started_at,
+            ended_at: None,
+            actions: 0,
+            correlation_id: self.correlation_id.clone(),
+        })
+    }
+
     /// Updates the identity fields to match the provided value.
     pub fn with_identity(mut self, identity: TenantIdentity) -> Self {
         self.tenant = identity.tenant_id.clone();
@@ -1,10 +1,11 @@
-//! Tenant-facing configuration document shapes (skin/auth/config/did).
+//! Tenant-facing configuration document shapes (skin/auth/config/did/quota).
 //!
 //! These structs mirror the JSON documents served to the Loveable UI. They intentionally avoid
 //! hard-coding UI navigation semantics (tabs, slots, etc.) to keep the types crate forward
 //! compatible.
 
 use alloc::collections::BTreeMap;
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -14,6 +15,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::validate::{Severity, ValidationReport, core_diagnostic};
+use crate::{ByteSize, Environment, PackId, Percent, TenantCtx, UnixMillis, UrlRef, UserId};
+
 /// Branding and layout configuration for a tenant (`skin.json`).
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -61,19 +65,19 @@ pub struct RepoSkin {
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub struct RepoSkinTheme {
     /// Primary logo URL.
-    pub logo_url: String,
+    pub logo_url: UrlRef,
     /// Optional favicon URL.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub favicon_url: Option<String>,
+    pub favicon_url: Option<UrlRef>,
     /// Optional hero image URL.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
-    pub hero_image_url: Option<String>,
+    pub hero_image_url: Option<UrlRef>,
     /// Primary brand color.
     pub primary_color: String,
     /// Accent brand color.
@@ -186,11 +190,11 @@ pub struct RepoWorkerPanel {
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub struct RepoSkinLinks {
     /// Optional documentation URL.
-    pub docs_url: Option<String>,
+    pub docs_url: Option<UrlRef>,
     /// Optional support URL.
-    pub support_url: Option<String>,
+    pub support_url: Option<UrlRef>,
     /// Optional status page URL.
-    pub status_url: Option<String>,
+    pub status_url: Option<UrlRef>,
 }
 
 /// Login options for a tenant (`auth.json`).
@@ -242,7 +246,7 @@ pub struct IdentityProviderOption {
     )]
     pub order: Option<i32>,
     /// Login URL for the provider.
-    pub login_url: String,
+    pub login_url: UrlRef,
     /// Optional description text.
     #[cfg_attr(
         feature = "serde",
@@ -354,7 +358,7 @@ pub struct StoreTarget {
     /// Human-readable label.
     pub label: String,
     /// Store URL.
-    pub url: String,
+    pub url: UrlRef,
     /// Optional description.
     #[cfg_attr(
         feature = "serde",
@@ -373,7 +377,7 @@ pub struct DistributorTarget {
     /// Human-readable label.
     pub label: String,
     /// Distributor URL.
-    pub url: String,
+    pub url: UrlRef,
     /// Optional description.
     #[cfg_attr(
         feature = "serde",
@@ -396,6 +400,415 @@ pub struct RepoConfigFeatures {
     pub show_experimental_modules: Option<bool>,
 }
 
+/// Tenant-scoped feature flags (`flags.json`).
+///
+/// Every service evaluates flags through [`FeatureFlags::is_enabled`] and [`FeatureFlags::value`],
+/// so a percentage rollout places the same hash key (tenant, user, or session id) in the same
+/// bucket everywhere.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct FeatureFlags {
+    /// Flags keyed by name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flags: BTreeMap<String, FeatureFlag>,
+}
+
+impl FeatureFlags {
+    /// Returns the flag's value for `hash_key` at `now`.
+    ///
+    /// Returns `None` when the flag is unknown, expired, or outside its rollout for `hash_key`.
+    pub fn value(&self, flag: &str, hash_key: &str, now: UnixMillis) -> Option<&FlagValue> {
+        let definition = self.flags.get(flag)?;
+        if definition.is_expired(now) {
+            return None;
+        }
+        match definition.rollout {
+            Some(rollout) if !rollout_includes(rollout, flag, hash_key) => None,
+            _ => Some(&definition.value),
+        }
+    }
+
+    /// Returns `true` when the flag serves a value for `hash_key` at `now` and that value is not
+    /// `false`.
+    pub fn is_enabled(&self, flag: &str, hash_key: &str, now: UnixMillis) -> bool {
+        self.value(flag, hash_key, now)
+            .is_some_and(|value| *value != FlagValue::Bool(false))
+    }
+}
+
+/// Definition of a single feature flag.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct FeatureFlag {
+    /// Value served to keys inside the rollout.
+    pub value: FlagValue,
+    /// Share of hash keys that receive the flag; absent means everyone.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub rollout: Option<Percent>,
+    /// Instant from which the flag is no longer served.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "Option<String>", description = "RFC3339 timestamp in UTC")
+    )]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "crate::units::rfc3339::option"
+        )
+    )]
+    pub expires_at: Option<UnixMillis>,
+    /// Optional description for operators.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
+}
+
+impl FeatureFlag {
+    /// Creates a flag served to everyone without expiry.
+    pub fn new(value: impl Into<FlagValue>) -> Self {
+        Self {
+            value: value.into(),
+            rollout: None,
+            expires_at: None,
+            description: None,
+        }
+    }
+
+    /// Limits the flag to a share of hash keys.
+    pub fn with_rollout(mut self, rollout: Percent) -> Self {
+        self.rollout = Some(rollout);
+        self
+    }
+
+    /// Stops serving the flag from `expires_at`.
+    pub fn with_expiry(mut self, expires_at: UnixMillis) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Returns `true` once `expires_at` has passed.
+    pub fn is_expired(&self, now: UnixMillis) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// Variant served by a feature flag.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum FlagValue {
+    /// On/off flag.
+    Bool(bool),
+    /// Numeric setting.
+    Number(f64),
+    /// String variant (for example an experiment arm).
+    String(String),
+}
+
+impl From<bool> for FlagValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<f64> for FlagValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<&str> for FlagValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for FlagValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+/// Deterministic rollout bucket in `0..10_000` (basis points) for `hash_key` under `flag`.
+///
+/// The bucket is derived from the Blake3 hash of `flag`, a NUL byte, and `hash_key`, so it is
+/// stable across services and releases and independent between flags.
+pub fn rollout_bucket(flag: &str, hash_key: &str) -> u16 {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(flag.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(hash_key.as_bytes());
+    let digest = hasher.finalize();
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest.as_bytes()[..8]);
+    (u64::from_be_bytes(prefix) % 10_000) as u16
+}
+
+fn rollout_includes(rollout: Percent, flag: &str, hash_key: &str) -> bool {
+    rollout_bucket(flag, hash_key) < rollout.basis_points()
+}
+
+/// Administrator account created while provisioning a tenant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct BootstrapAdmin {
+    /// User identifier for the administrator.
+    pub user_id: UserId,
+    /// Contact email used for the initial invitation.
+    pub email: String,
+    /// Optional display name.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub display_name: Option<String>,
+}
+
+/// Onboarding document that provisions a tenant in one step.
+///
+/// Bundles the initial `config.json`, the packs enabled by default, the first environment, and
+/// the administrator identity so provisioning services apply them together.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct TenantBootstrap {
+    /// Tenant being provisioned.
+    pub tenant: TenantCtx,
+    /// Initial console configuration.
+    pub config: RepoTenantConfig,
+    /// Packs installed for the tenant by default.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub default_packs: Vec<PackId>,
+    /// First environment registered for the tenant.
+    pub default_environment: Environment,
+    /// Administrator identity.
+    pub admin: BootstrapAdmin,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl TenantBootstrap {
+    /// Checks that the parts of the document agree with each other.
+    ///
+    /// Mismatched tenants and malformed admin emails are errors; duplicated default packs are
+    /// warnings.
+    pub fn validate(&self) -> ValidationReport {
+        let mut diagnostics = Vec::new();
+        if self.config.tenant_id != self.tenant.tenant_id.as_str() {
+            diagnostics.push(core_diagnostic(
+                Severity::Error,
+                "TENANT_BOOTSTRAP_CONFIG_TENANT_MISMATCH",
+                &format!(
+                    "config belongs to tenant `{}`, expected `{}`",
+                    self.config.tenant_id,
+                    self.tenant.tenant_id.as_str()
+                ),
+                Some("config.tenant_id".into()),
+                None,
+            ));
+        }
+        let environment = &self.default_environment;
+        if environment.tenant.env != self.tenant.env
+            || environment.tenant.tenant_id != self.tenant.tenant_id
+        {
+            diagnostics.push(core_diagnostic(
+                Severity::Error,
+                "TENANT_BOOTSTRAP_ENVIRONMENT_TENANT_MISMATCH",
+                &format!(
+                    "environment `{}` belongs to {}/{}, expected {}/{}",
+                    environment.id.as_str(),
+                    environment.tenant.env.as_str(),
+                    environment.tenant.tenant_id.as_str(),
+                    self.tenant.env.as_str(),
+                    self.tenant.tenant_id.as_str()
+                ),
+                Some("default_environment.tenant".into()),
+                None,
+            ));
+        }
+        if !is_plausible_email(&self.admin.email) {
+            diagnostics.push(core_diagnostic(
+                Severity::Error,
+                "TENANT_BOOTSTRAP_INVALID_ADMIN_EMAIL",
+                &format!("admin email `{}` is not a valid address", self.admin.email),
+                Some("admin.email".into()),
+                None,
+            ));
+        }
+        for (index, pack) in self.default_packs.iter().enumerate() {
+            if self.default_packs[..index].contains(pack) {
+                diagnostics.push(core_diagnostic(
+                    Severity::Warn,
+                    "TENANT_BOOTSTRAP_DUPLICATE_PACK",
+                    &format!("pack `{}` is listed more than once", pack.as_str()),
+                    Some(format!("default_packs[{index}]")),
+                    Some("remove the duplicate entry".into()),
+                ));
+            }
+        }
+        ValidationReport {
+            pack_id: None,
+            pack_version: None,
+            diagnostics,
+        }
+    }
+}
+
+pub(crate) fn is_plausible_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+                && !domain.contains('@')
+        }
+        None => false,
+    }
+}
+
+/// Declarative resource quotas for a tenant.
+///
+/// Absent limits are unlimited. Runner, store, and distributor compare the same document against
+/// their share of [`QuotaUsage`] with [`TenantQuota::check`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct TenantQuota {
+    /// Maximum number of deployed flows.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_flows: Option<u32>,
+    /// Maximum number of concurrently active sessions.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_sessions: Option<u32>,
+    /// Maximum number of flow runs per calendar month.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_monthly_runs: Option<u64>,
+    /// Maximum stored data (state, transcripts, blobs).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_storage: Option<ByteSize>,
+    /// Additional metadata.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl TenantQuota {
+    /// Returns every limit `usage` exceeds, in [`QuotaResource`] order.
+    ///
+    /// Usage equal to a limit is allowed; callers about to allocate should check the usage they
+    /// would reach.
+    pub fn check(&self, usage: &QuotaUsage) -> Vec<QuotaViolation> {
+        [
+            (
+                QuotaResource::Flows,
+                self.max_flows.map(u64::from),
+                u64::from(usage.flows),
+            ),
+            (
+                QuotaResource::Sessions,
+                self.max_sessions.map(u64::from),
+                u64::from(usage.sessions),
+            ),
+            (
+                QuotaResource::MonthlyRuns,
+                self.max_monthly_runs,
+                usage.monthly_runs,
+            ),
+            (
+                QuotaResource::Storage,
+                self.max_storage.map(|max| max.as_bytes()),
+                usage.storage.as_bytes(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(resource, limit, used)| match limit {
+            Some(limit) if used > limit => Some(QuotaViolation {
+                resource,
+                limit,
+                used,
+            }),
+            _ => None,
+        })
+        .collect()
+    }
+}
+
+/// Current consumption of the resources governed by [`TenantQuota`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct QuotaUsage {
+    /// Deployed flows.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flows: u32,
+    /// Active sessions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sessions: u32,
+    /// Flow runs in the current calendar month.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub monthly_runs: u64,
+    /// Stored data.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub storage: ByteSize,
+}
+
+/// Resource governed by a [`TenantQuota`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum QuotaResource {
+    /// [`TenantQuota::max_flows`].
+    Flows,
+    /// [`TenantQuota::max_sessions`].
+    Sessions,
+    /// [`TenantQuota::max_monthly_runs`].
+    MonthlyRuns,
+    /// [`TenantQuota::max_storage`], measured in bytes.
+    Storage,
+}
+
+/// Limit exceeded by a tenant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct QuotaViolation {
+    /// Resource over its limit.
+    pub resource: QuotaResource,
+    /// Configured limit.
+    pub limit: u64,
+    /// Current usage.
+    pub used: u64,
+}
+
 /// DID document used for tenant discovery (`did.json`).
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -439,6 +852,286 @@ impl TenantDidDocument {
             None => Vec::new(),
         }
     }
+
+    /// Resolves a verification method by reference.
+    ///
+    /// Accepts relative fragments (`#key-1`) and absolute DID URLs (`did:web:...#key-1`); method
+    /// ids declared relative are resolved against the document id.
+    pub fn verification_method(&self, reference: &str) -> Option<&VerificationMethod> {
+        let wanted = self.absolute_ref(reference);
+        self.verification_method
+            .iter()
+            .flatten()
+            .find(|method| self.absolute_ref(&method.id) == wanted)
+    }
+
+    /// Resolves every `authentication` reference to its verification method.
+    pub fn authentication_methods(&self) -> Result<Vec<&VerificationMethod>, DidDocumentError> {
+        self.authentication
+            .iter()
+            .flatten()
+            .map(|reference| {
+                self.verification_method(reference).ok_or_else(|| {
+                    DidDocumentError::UnresolvedReference {
+                        reference: reference.clone(),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Host encoded in a `did:web` identifier, with `%3A` port separators decoded.
+    ///
+    /// Returns `None` when the document id is not a `did:web` DID.
+    pub fn did_web_host(&self) -> Option<String> {
+        let rest = self.id.strip_prefix("did:web:")?;
+        let host = rest.split(':').next().unwrap_or_default();
+        if host.is_empty() {
+            return None;
+        }
+        Some(host.replace("%3A", ":").replace("%3a", ":"))
+    }
+
+    /// Checks that the document is a `did:web` DID served from `host`.
+    ///
+    /// Hosts compare case-insensitively.
+    pub fn ensure_host(&self, host: &str) -> Result<(), DidDocumentError> {
+        let actual = self
+            .did_web_host()
+            .ok_or_else(|| DidDocumentError::NotDidWeb {
+                id: self.id.clone(),
+            })?;
+        if actual.eq_ignore_ascii_case(host) {
+            Ok(())
+        } else {
+            Err(DidDocumentError::HostMismatch {
+                expected: host.into(),
+                actual,
+            })
+        }
+    }
+
+    /// Checks the document for unresolved references, duplicate or keyless verification
+    /// methods, and a non-`did:web` identifier.
+    pub fn validate(&self) -> ValidationReport {
+        let mut diagnostics = Vec::new();
+        if self.did_web_host().is_none() {
+            diagnostics.push(core_diagnostic(
+                Severity::Error,
+                "DID_NOT_DID_WEB",
+                &format!("document id `{}` is not a did:web identifier", self.id),
+                Some("id".into()),
+                Some("use a `did:web:<host>[:path]` identifier".into()),
+            ));
+        }
+        let methods = self.verification_method.as_deref().unwrap_or_default();
+        for (index, method) in methods.iter().enumerate() {
+            let id = self.absolute_ref(&method.id);
+            if methods[..index]
+                .iter()
+                .any(|earlier| self.absolute_ref(&earlier.id) == id)
+            {
+                diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "DID_VERIFICATION_METHOD_DUPLICATE",
+                    &format!(
+                        "verification method `{}` is declared more than once",
+                        method.id
+                    ),
+                    Some(format!("verificationMethod[{index}].id")),
+                    None,
+                ));
+            }
+            if let Err(err) = method.public_key() {
+                diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "DID_VERIFICATION_METHOD_INVALID_KEY",
+                    &format!("{err}"),
+                    Some(format!("verificationMethod[{index}]")),
+                    None,
+                ));
+            }
+        }
+        for (index, reference) in self.authentication.iter().flatten().enumerate() {
+            if self.verification_method(reference).is_none() {
+                diagnostics.push(core_diagnostic(
+                    Severity::Error,
+                    "DID_AUTHENTICATION_UNRESOLVED",
+                    &format!("authentication reference `{reference}` has no verification method"),
+                    Some(format!("authentication[{index}]")),
+                    Some("declare the key under verificationMethod".into()),
+                ));
+            }
+        }
+        ValidationReport {
+            pack_id: None,
+            pack_version: None,
+            diagnostics,
+        }
+    }
+
+    fn absolute_ref(&self, reference: &str) -> String {
+        if reference.starts_with('#') {
+            format!("{}{reference}", self.id)
+        } else {
+            reference.into()
+        }
+    }
+}
+
+/// Errors raised while resolving DID document references and keys.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum DidDocumentError {
+    /// A reference does not match any verification method.
+    #[error("reference `{reference}` does not match a verification method")]
+    UnresolvedReference {
+        /// Unresolved reference.
+        reference: String,
+    },
+    /// The document id is not a `did:web` DID.
+    #[error("`{id}` is not a did:web identifier")]
+    NotDidWeb {
+        /// Document identifier.
+        id: String,
+    },
+    /// The `did:web` host differs from the host serving the document.
+    #[error("did:web host `{actual}` does not match `{expected}`")]
+    HostMismatch {
+        /// Host the document was expected to name.
+        expected: String,
+        /// Host named by the document.
+        actual: String,
+    },
+    /// A verification method carries neither `publicKeyJwk` nor `publicKeyMultibase`.
+    #[error("verification method `{method}` has no public key")]
+    MissingKey {
+        /// Verification method identifier.
+        method: String,
+    },
+    /// A verification method carries a malformed key.
+    #[error("verification method `{method}` has an invalid key: {reason}")]
+    InvalidKey {
+        /// Verification method identifier.
+        method: String,
+        /// What is wrong with the key.
+        reason: String,
+    },
+}
+
+/// Public key extracted from a [`VerificationMethod`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PublicKeyMaterial {
+    /// Key from `publicKeyJwk`.
+    Jwk(PublicJwk),
+    /// Key from `publicKeyMultibase`.
+    Multibase(MultibaseKey),
+}
+
+/// Public JSON Web Key parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicJwk {
+    /// Key type (`EC`, `OKP`, `RSA`).
+    pub kty: String,
+    /// Curve for `EC`/`OKP` keys.
+    pub crv: Option<String>,
+    /// X coordinate or `OKP` public key (base64url).
+    pub x: Option<String>,
+    /// Y coordinate for `EC` keys (base64url).
+    pub y: Option<String>,
+    /// RSA modulus (base64url).
+    pub n: Option<String>,
+    /// RSA exponent (base64url).
+    pub e: Option<String>,
+    /// Optional key identifier.
+    pub kid: Option<String>,
+}
+
+/// Multibase encoding named by the prefix character of `publicKeyMultibase`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultibaseBase {
+    /// `z`: base58 (Bitcoin alphabet).
+    Base58Btc,
+    /// `u`: unpadded base64url.
+    Base64Url,
+    /// `f`: lowercase hexadecimal.
+    Base16,
+    /// `b`: lowercase unpadded base32.
+    Base32,
+}
+
+impl MultibaseBase {
+    /// Encoding for a multibase prefix character.
+    pub fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix {
+            'z' => Some(Self::Base58Btc),
+            'u' => Some(Self::Base64Url),
+            'f' => Some(Self::Base16),
+            'b' => Some(Self::Base32),
+            _ => None,
+        }
+    }
+}
+
+/// Multibase-encoded public key split into its encoding and payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultibaseKey {
+    /// Encoding of the payload.
+    pub base: MultibaseBase,
+    /// Encoded key bytes without the prefix character.
+    pub encoded: String,
+}
+
+impl VerificationMethod {
+    /// Extracts the typed public key, preferring `publicKeyJwk` over `publicKeyMultibase`.
+    ///
+    /// JWKs must be objects with a `kty` and must not carry private parameters (`d`, `p`, `q`,
+    /// ...); multibase keys must use a supported prefix and have a payload.
+    pub fn public_key(&self) -> Result<PublicKeyMaterial, DidDocumentError> {
+        let invalid = |reason: &str| DidDocumentError::InvalidKey {
+            method: self.id.clone(),
+            reason: reason.into(),
+        };
+        if let Some(jwk) = &self.public_key_jwk {
+            let object = jwk
+                .as_object()
+                .ok_or_else(|| invalid("publicKeyJwk is not an object"))?;
+            if ["d", "p", "q", "dp", "dq", "qi", "k"]
+                .iter()
+                .any(|param| object.contains_key(*param))
+            {
+                return Err(invalid("publicKeyJwk contains private key parameters"));
+            }
+            let field = |name: &str| object.get(name).and_then(Value::as_str).map(String::from);
+            let kty = field("kty").ok_or_else(|| invalid("publicKeyJwk has no `kty`"))?;
+            return Ok(PublicKeyMaterial::Jwk(PublicJwk {
+                kty,
+                crv: field("crv"),
+                x: field("x"),
+                y: field("y"),
+                n: field("n"),
+                e: field("e"),
+                kid: field("kid"),
+            }));
+        }
+        if let Some(multibase) = &self.public_key_multibase {
+            let mut chars = multibase.chars();
+            let base = chars
+                .next()
+                .and_then(MultibaseBase::from_prefix)
+                .ok_or_else(|| invalid("unsupported multibase prefix"))?;
+            let encoded = chars.as_str();
+            if encoded.is_empty() {
+                return Err(invalid("multibase key is empty"));
+            }
+            return Ok(PublicKeyMaterial::Multibase(MultibaseKey {
+                base,
+                encoded: encoded.into(),
+            }));
+        }
+        Err(DidDocumentError::MissingKey {
+            method: self.id.clone(),
+        })
+    }
 }
 
 /// @context representation supporting single string or array.
@@ -499,5 +1192,5 @@ pub struct DidService {
     pub r#type: String,
     /// Service endpoint URL.
     #[cfg_attr(feature = "serde", serde(rename = "serviceEndpoint"))]
-    pub service_endpoint: String,
+    pub service_endpoint: UrlRef,
 }
@@ -0,0 +1,326 @@
+//! Proptest strategies for property-testing code built on the shared types.
+//!
+//! Enabled by the `test-utils` feature. Each strategy yields values that pass the crate's own
+//! validation: identifiers are well formed, flow routing only targets nodes in the same flow,
+//! and pack manifests declare every component their flows reference. The core types also
+//! implement [`Arbitrary`], so `any::<TenantCtx>()` works in downstream `proptest!` blocks.
+//!
+//! ```
+//! use greentic_types::PackManifest;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(manifest in any::<PackManifest>())| {
+//!     prop_assert!(!manifest.pack_id.as_str().is_empty());
+//! });
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use indexmap::IndexMap;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::{btree_map, btree_set, vec};
+use proptest::option;
+use proptest::prelude::*;
+use proptest::strategy::BoxedStrategy;
+use semver::Version;
+use serde_json::Value;
+
+use crate::flow::FlowHasher;
+use crate::{
+    BranchRef, ChannelMessageEnvelope, CommitRef, ComponentCapabilities, ComponentCapability,
+    ComponentId, ComponentManifest, ComponentOperation, ComponentProfiles, EnvId, Flow,
+    FlowComponentRef, FlowId, FlowKind, FlowMetadata, InputMapping, Node, NodeId, OutputMapping,
+    PackFlowEntry, PackId, PackKind, PackManifest, PackSignatures, ResourceHints, Routing, TeamId,
+    TelemetryHints, TenantCtx, TenantId, UserId,
+};
+
+/// Identifier strings accepted by every id newtype (`[a-z][a-z0-9._-]{0,23}`).
+pub fn identifier() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9._-]{0,23}"
+}
+
+/// Semantic versions with small numeric components.
+pub fn version() -> impl Strategy<Value = Version> {
+    (0u64..20, 0u64..50, 0u64..100)
+        .prop_map(|(major, minor, patch)| Version::new(major, minor, patch))
+}
+
+/// Built-in flow kinds.
+pub fn flow_kind() -> impl Strategy<Value = FlowKind> {
+    proptest::sample::select(FlowKind::BUILTIN.to_vec())
+}
+
+/// Tenant contexts with optional team, user, and session scoping.
+pub fn tenant_ctx() -> impl Strategy<Value = TenantCtx> {
+    (
+        any::<EnvId>(),
+        any::<TenantId>(),
+        option::of(any::<TeamId>()),
+        option::of(any::<UserId>()),
+        option::of(identifier()),
+        0u32..5,
+        btree_map(identifier(), "[ -~]{0,16}", 0..3),
+    )
+        .prop_map(|(env, tenant, team, user, session, attempt, attributes)| {
+            let mut ctx = TenantCtx::new(env, tenant)
+                .with_team(team)
+                .with_user(user)
+                .with_attempt(attempt)
+                .with_attributes(attributes);
+            if let Some(session) = session {
+                ctx = ctx.with_session(session);
+            }
+            ctx
+        })
+}
+
+/// Flows whose nodes invoke components from `components` and route in a chain ending at the
+/// last node.
+pub fn flow_with_components(
+    id: FlowId,
+    kind: FlowKind,
+    components: Vec<ComponentId>,
+) -> impl Strategy<Value = Flow> {
+    let component_count = components.len().max(1);
+    (
+        btree_set(any::<NodeId>(), 1..6),
+        vec(0..component_count, 6),
+        option::of("[ -~]{1,32}"),
+    )
+        .prop_map(move |(node_ids, picks, description)| {
+            let node_ids: Vec<NodeId> = node_ids.into_iter().collect();
+            let mut nodes: IndexMap<NodeId, Node, FlowHasher> = IndexMap::default();
+            for (index, node_id) in node_ids.iter().enumerate() {
+                let component = components
+                    .get(picks[index])
+                    .cloned()
+                    .unwrap_or_else(|| ComponentId(String::from("component.default")));
+                let routing = match node_ids.get(index + 1) {
+                    Some(next) => Routing::Next {
+                        node_id: next.clone(),
+                    },
+                    None => Routing::End,
+                };
+                nodes.insert(
+                    node_id.clone(),
+                    Node {
+                        id: node_id.clone(),
+                        component: FlowComponentRef {
+                            id: component,
+                            pack_alias: None,
+                            operation: Some(String::from("handle")),
+                        },
+                        input: InputMapping {
+                            mapping: Value::Null,
+                        },
+                        output: OutputMapping {
+                            mapping: Value::Null,
+                        },
+                        routing,
+                        telemetry: TelemetryHints::default(),
+                        capabilities_override: None,
+                    },
+                );
+            }
+            Flow {
+                schema_version: String::from("flow-v1"),
+                id: id.clone(),
+                kind,
+                entrypoints: BTreeMap::from([(String::from("default"), Value::Null)]),
+                nodes,
+                metadata: FlowMetadata {
+                    description,
+                    ..FlowMetadata::default()
+                },
+            }
+        })
+}
+
+/// Standalone flows with a single `handle` component.
+pub fn flow() -> impl Strategy<Value = Flow> {
+    (any::<FlowId>(), flow_kind()).prop_flat_map(|(id, kind)| {
+        flow_with_components(
+            id,
+            kind,
+            Vec::from([ComponentId(String::from("component.default"))]),
+        )
+    })
+}
+
+/// Component manifests exposing a single `handle` operation for every built-in flow kind.
+pub fn component_manifest(id: ComponentId) -> impl Strategy<Value = ComponentManifest> {
+    version().prop_map(move |version| ComponentManifest {
+        id: id.clone(),
+        version,
+        supports: FlowKind::BUILTIN.to_vec(),
+        world: String::from("greentic:component/component@0.6.0"),
+        profiles: ComponentProfiles::default(),
+        capabilities: ComponentCapabilities::default(),
+        configurators: None,
+        operations: Vec::from([ComponentOperation {
+            name: String::from("handle"),
+            input_schema: Value::Null,
+            output_schema: Value::Null,
+        }]),
+        config_schema: None,
+        resources: ResourceHints::default(),
+        targets: Vec::new(),
+        dev_flows: BTreeMap::new(),
+    })
+}
+
+/// Pack manifests whose flows only reference components declared in the pack.
+pub fn pack_manifest() -> impl Strategy<Value = PackManifest> {
+    (
+        btree_set(any::<ComponentId>(), 1..4),
+        btree_set(any::<FlowId>(), 0..3),
+    )
+        .prop_flat_map(|(component_ids, flow_ids)| {
+            let component_ids: Vec<ComponentId> = component_ids.into_iter().collect();
+            let components: Vec<_> = component_ids
+                .iter()
+                .cloned()
+                .map(component_manifest)
+                .collect();
+            let flows: Vec<_> = flow_ids
+                .into_iter()
+                .map(|flow_id| {
+                    let components = component_ids.clone();
+                    flow_kind().prop_flat_map(move |kind| {
+                        flow_with_components(flow_id.clone(), kind, components.clone()).prop_map(
+                            move |flow| PackFlowEntry {
+                                id: flow.id.clone(),
+                                kind,
+                                flow,
+                                tags: Vec::new(),
+                                entrypoints: Vec::from([String::from("default")]),
+                            },
+                        )
+                    })
+                })
+                .collect();
+            (
+                any::<PackId>(),
+                version(),
+                proptest::sample::select(Vec::from([
+                    PackKind::Application,
+                    PackKind::Provider,
+                    PackKind::Infrastructure,
+                    PackKind::Library,
+                ])),
+                identifier(),
+                components,
+                flows,
+                btree_set(identifier(), 0..3),
+            )
+        })
+        .prop_map(
+            |(pack_id, version, kind, publisher, components, flows, capabilities)| PackManifest {
+                schema_version: String::from("pack-v1"),
+                pack_id,
+                name: None,
+                version,
+                kind,
+                publisher,
+                components,
+                flows,
+                dependencies: Vec::new(),
+                capabilities: capabilities
+                    .into_iter()
+                    .map(|name| ComponentCapability {
+                        description: Some(format!("Grants {name}.")),
+                        name,
+                    })
+                    .collect(),
+                secret_requirements: Vec::new(),
+                signatures: PackSignatures::default(),
+                bootstrap: None,
+                extensions: None,
+            },
+        )
+}
+
+/// Channel message envelopes with optional text and metadata.
+pub fn channel_message_envelope() -> impl Strategy<Value = ChannelMessageEnvelope> {
+    (
+        identifier(),
+        tenant_ctx(),
+        identifier(),
+        identifier(),
+        option::of(identifier()),
+        option::of(".{0,64}"),
+        btree_map(identifier(), "[ -~]{0,16}", 0..3),
+    )
+        .prop_map(|(id, tenant, channel, session, from, text, metadata)| {
+            let mut builder = ChannelMessageEnvelope::builder()
+                .id(id)
+                .tenant(tenant)
+                .channel(channel)
+                .session_id(session);
+            if let Some(from) = from {
+                builder = builder.from(from, None);
+            }
+            if let Some(text) = text {
+                builder = builder.text(text);
+            }
+            for (key, value) in metadata {
+                builder = builder.metadata(key, value);
+            }
+            builder
+                .build()
+                .unwrap_or_else(|err| panic!("generated envelope is complete: {err}"))
+        })
+}
+
+macro_rules! arbitrary_id {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl Arbitrary for $name {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    identifier().prop_map($name).boxed()
+                }
+            }
+        )*
+    };
+}
+
+arbitrary_id!(
+    EnvId,
+    TenantId,
+    TeamId,
+    UserId,
+    BranchRef,
+    CommitRef,
+    PackId,
+    ComponentId,
+    FlowId,
+    NodeId,
+);
+
+macro_rules! arbitrary_via {
+    ($($name:ident => $strategy:ident),* $(,)?) => {
+        $(
+            impl Arbitrary for $name {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    $strategy().boxed()
+                }
+            }
+        )*
+    };
+}
+
+arbitrary_via!(
+    TenantCtx => tenant_ctx,
+    Flow => flow,
+    PackManifest => pack_manifest,
+    ChannelMessageEnvelope => channel_message_envelope,
+);
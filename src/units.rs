@@ -0,0 +1,1054 @@
+//! Typed size, duration, and timestamp quantities used by limits, capability declarations, and
+//! status records.
+//!
+//! [`ByteSize`] and [`DurationMs`] serialize as plain integers (bytes and milliseconds
+//! respectively) and accept either integers or human-readable strings such as `"512MiB"` or
+//! `"30s"` when deserializing. [`UnixMillis`] is a plain integer timestamp that stays available
+//! without the `std` or `time` features; records that publish it as RFC 3339 text use the
+//! [`rfc3339`] serde adapter. [`Percent`] and [`Ratio`] are bounded numbers that
+//! reject out-of-range values when constructed or deserialized.
+
+use alloc::{format, string::String};
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "schemars")]
+use alloc::borrow::Cow;
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{ErrorCode, GResult, GreenticError};
+
+const KIB: u64 = 1024;
+const MIB: u64 = KIB * 1024;
+const GIB: u64 = MIB * 1024;
+const TIB: u64 = GIB * 1024;
+
+const SECOND_MS: u64 = 1_000;
+pub(crate) const MINUTE_MS: u64 = 60 * SECOND_MS;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+
+/// Minutes in a day.
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Size expressed in bytes.
+///
+/// Parses decimal (`kB`, `MB`, `GB`, `TB`) and binary (`KiB`, `MiB`, `GiB`, `TiB`) suffixes;
+/// bare numbers are bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// Zero bytes.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a size from a byte count.
+    pub const fn from_bytes(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    /// Creates a size from kibibytes, saturating on overflow.
+    pub const fn from_kib(kib: u64) -> Self {
+        Self(kib.saturating_mul(KIB))
+    }
+
+    /// Creates a size from mebibytes, saturating on overflow.
+    pub const fn from_mib(mib: u64) -> Self {
+        Self(mib.saturating_mul(MIB))
+    }
+
+    /// Creates a size from gibibytes, saturating on overflow.
+    pub const fn from_gib(gib: u64) -> Self {
+        Self(gib.saturating_mul(GIB))
+    }
+
+    /// Returns the size in bytes.
+    pub const fn as_bytes(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the size in whole mebibytes, rounding up partial mebibytes.
+    pub const fn as_mib_ceil(&self) -> u64 {
+        self.0.div_ceil(MIB)
+    }
+
+    /// Parses a size string such as `512MiB`, `1.5GB`, or `2048`.
+    pub fn parse(value: &str) -> GResult<Self> {
+        let trimmed = value.trim();
+        let (number, unit) = split_number(trimmed);
+        let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kb" => 1_000,
+            "m" | "mb" => 1_000_000,
+            "g" | "gb" => 1_000_000_000,
+            "t" | "tb" => 1_000_000_000_000,
+            "ki" | "kib" => KIB,
+            "mi" | "mib" => MIB,
+            "gi" | "gib" => GIB,
+            "ti" | "tib" => TIB,
+            other => {
+                return Err(invalid_quantity(
+                    "size",
+                    value,
+                    &format!("unknown unit '{other}'"),
+                ));
+            }
+        };
+        scale(number, multiplier)
+            .map(Self)
+            .ok_or_else(|| invalid_quantity("size", value, "expected a non-negative number"))
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0;
+        for (unit, size) in [("TiB", TIB), ("GiB", GIB), ("MiB", MIB), ("KiB", KIB)] {
+            if bytes != 0 && bytes % size == 0 {
+                return write!(f, "{}{unit}", bytes / size);
+            }
+        }
+        write!(f, "{bytes}B")
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = GreenticError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(value: ByteSize) -> Self {
+        value.0
+    }
+}
+
+/// Duration expressed in milliseconds.
+///
+/// Parses `ms`, `s`, `m`, `h`, and `d` suffixes, including compound values such as `1m30s`;
+/// bare numbers are milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DurationMs(u64);
+
+impl DurationMs {
+    /// Zero milliseconds.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a duration from milliseconds.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis)
+    }
+
+    /// Creates a duration from seconds, saturating on overflow.
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(secs.saturating_mul(SECOND_MS))
+    }
+
+    /// Creates a duration from minutes, saturating on overflow.
+    pub const fn from_mins(mins: u64) -> Self {
+        Self(mins.saturating_mul(MINUTE_MS))
+    }
+
+    /// Returns the duration in milliseconds.
+    pub const fn as_millis(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the duration in whole seconds (truncating).
+    pub const fn as_secs(&self) -> u64 {
+        self.0 / SECOND_MS
+    }
+
+    /// Parses a duration string such as `30s`, `1m30s`, `1.5h`, or `250`.
+    pub fn parse(value: &str) -> GResult<Self> {
+        let mut rest = value.trim();
+        if rest.is_empty() {
+            return Err(invalid_quantity("duration", value, "value is empty"));
+        }
+        let mut total: u64 = 0;
+        while !rest.is_empty() {
+            let (number, tail) = split_number(rest);
+            if number.is_empty() {
+                return Err(invalid_quantity("duration", value, "expected a number"));
+            }
+            let tail = tail.trim_start();
+            let unit_len = tail
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(tail.len());
+            let (unit, remainder) = tail.split_at(unit_len);
+            let multiplier = match unit.to_ascii_lowercase().as_str() {
+                "" | "ms" => 1,
+                "s" | "sec" | "secs" => SECOND_MS,
+                "m" | "min" | "mins" => MINUTE_MS,
+                "h" | "hr" | "hrs" => HOUR_MS,
+                "d" | "day" | "days" => DAY_MS,
+                other => {
+                    return Err(invalid_quantity(
+                        "duration",
+                        value,
+                        &format!("unknown unit '{other}'"),
+                    ));
+                }
+            };
+            if unit.is_empty() && !remainder.trim().is_empty() {
+                return Err(invalid_quantity("duration", value, "missing unit"));
+            }
+            let part = scale(number, multiplier).ok_or_else(|| {
+                invalid_quantity("duration", value, "expected a non-negative number")
+            })?;
+            total = total
+                .checked_add(part)
+                .ok_or_else(|| invalid_quantity("duration", value, "value overflows"))?;
+            rest = remainder.trim_start();
+        }
+        Ok(Self(total))
+    }
+}
+
+impl fmt::Display for DurationMs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = self.0;
+        for (unit, size) in [
+            ("d", DAY_MS),
+            ("h", HOUR_MS),
+            ("m", MINUTE_MS),
+            ("s", SECOND_MS),
+        ] {
+            if millis != 0 && millis % size == 0 {
+                return write!(f, "{}{unit}", millis / size);
+            }
+        }
+        write!(f, "{millis}ms")
+    }
+}
+
+impl FromStr for DurationMs {
+    type Err = GreenticError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl From<u64> for DurationMs {
+    fn from(millis: u64) -> Self {
+        Self(millis)
+    }
+}
+
+impl From<DurationMs> for u64 {
+    fn from(value: DurationMs) -> Self {
+        value.0
+    }
+}
+
+impl From<core::time::Duration> for DurationMs {
+    fn from(value: core::time::Duration) -> Self {
+        Self(u64::try_from(value.as_millis()).unwrap_or(u64::MAX))
+    }
+}
+
+impl From<DurationMs> for core::time::Duration {
+    fn from(value: DurationMs) -> Self {
+        core::time::Duration::from_millis(value.0)
+    }
+}
+
+/// Point in time expressed as milliseconds since the Unix epoch (UTC).
+///
+/// Unlike `OffsetDateTime` fields, which require the `time` feature, this type is available in
+/// every build so `no_std` components can still carry timestamps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(transparent))]
+pub struct UnixMillis(i64);
+
+impl UnixMillis {
+    /// The Unix epoch.
+    pub const EPOCH: Self = Self(0);
+
+    /// Creates a timestamp from milliseconds since the epoch.
+    pub const fn from_millis(millis: i64) -> Self {
+        Self(millis)
+    }
+
+    /// Returns milliseconds since the epoch.
+    pub const fn as_millis(&self) -> i64 {
+        self.0
+    }
+
+    /// Returns the timestamp `duration` later, or `None` on overflow.
+    pub fn checked_add(self, duration: DurationMs) -> Option<Self> {
+        let millis = i64::try_from(duration.as_millis()).ok()?;
+        self.0.checked_add(millis).map(Self)
+    }
+
+    /// Returns the time elapsed since `earlier`, or zero when `earlier` is later than `self`.
+    pub fn saturating_duration_since(self, earlier: Self) -> DurationMs {
+        let elapsed = i128::from(self.0) - i128::from(earlier.0);
+        DurationMs::from_millis(u64::try_from(elapsed.max(0)).unwrap_or(u64::MAX))
+    }
+
+    /// Parses an RFC 3339 timestamp (`2025-01-02T03:04:05.250Z` or with a numeric offset),
+    /// truncating to whole milliseconds.
+    pub fn parse_rfc3339(text: &str) -> GResult<Self> {
+        parse_rfc3339(text).ok_or_else(|| {
+            GreenticError::new(
+                ErrorCode::InvalidInput,
+                format!("invalid RFC 3339 timestamp `{text}`"),
+            )
+        })
+    }
+
+    /// Formats the timestamp as RFC 3339 in UTC, with milliseconds when they are not zero.
+    ///
+    /// Returns `None` outside the years `0000` to `9999`, which RFC 3339 cannot express.
+    pub fn to_rfc3339(self) -> Option<String> {
+        let seconds = self.0.div_euclid(1_000);
+        let millis = self.0.rem_euclid(1_000);
+        let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+        if !(0..=9_999).contains(&year) {
+            return None;
+        }
+        let second_of_day = seconds.rem_euclid(86_400);
+        let mut text = format!(
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            second_of_day / 3_600,
+            second_of_day / 60 % 60,
+            second_of_day % 60
+        );
+        if millis != 0 {
+            text.push_str(&format!(".{millis:03}"));
+        }
+        text.push('Z');
+        Some(text)
+    }
+
+    /// Converts the timestamp into an `OffsetDateTime` in UTC.
+    #[cfg(feature = "time")]
+    pub fn to_offset_date_time(self) -> Result<time::OffsetDateTime, time::error::ComponentRange> {
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(self.0) * 1_000_000)
+    }
+}
+
+impl From<i64> for UnixMillis {
+    fn from(millis: i64) -> Self {
+        Self(millis)
+    }
+}
+
+impl From<UnixMillis> for i64 {
+    fn from(value: UnixMillis) -> Self {
+        value.0
+    }
+}
+
+/// Truncates to whole milliseconds.
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for UnixMillis {
+    fn from(value: time::OffsetDateTime) -> Self {
+        let millis = value.unix_timestamp_nanos().div_euclid(1_000_000);
+        Self(i64::try_from(millis).unwrap_or(if millis < 0 { i64::MIN } else { i64::MAX }))
+    }
+}
+
+impl fmt::Display for UnixMillis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date of a day count since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    (year_of_era + era * 400 + i64::from(month <= 2), month, day)
+}
+
+fn parse_rfc3339(text: &str) -> Option<UnixMillis> {
+    let bytes = text.as_bytes();
+    let number = |range: core::ops::Range<usize>| -> Option<i64> {
+        let digits = bytes.get(range)?;
+        digits.iter().try_fold(0i64, |acc, digit| {
+            digit
+                .is_ascii_digit()
+                .then(|| acc * 10 + i64::from(digit - b'0'))
+        })
+    };
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    if separators
+        .iter()
+        .any(|(index, expected)| bytes.get(*index) != Some(expected))
+        || !matches!(bytes.get(10), Some(b'T' | b't' | b' '))
+    {
+        return None;
+    }
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month) || day < 1 || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    if civil_from_days(days) != (year, month, day) {
+        return None;
+    }
+
+    let mut rest = &bytes[19..];
+    let mut millis = 0;
+    if let Some((b'.', fraction)) = rest.split_first() {
+        let digits = fraction.iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+        millis = fraction[..digits.min(3)]
+            .iter()
+            .fold(0i64, |acc, digit| acc * 10 + i64::from(digit - b'0'))
+            * 10i64.pow(3 - digits.min(3) as u32);
+        rest = &fraction[digits..];
+    }
+    let offset_seconds = match rest {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let field = |high: u8, low: u8| {
+                (high.is_ascii_digit() && low.is_ascii_digit())
+                    .then(|| i64::from(high - b'0') * 10 + i64::from(low - b'0'))
+            };
+            let (hours, minutes) = (field(*h1, *h2)?, field(*m1, *m2)?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3_600 + minutes * 60;
+            if *sign == b'-' { -offset } else { offset }
+        }
+        _ => return None,
+    };
+    let seconds = days * 86_400 + hour * 3_600 + minute * 60 + second - offset_seconds;
+    Some(UnixMillis(seconds * 1_000 + millis))
+}
+
+/// Serde adapter encoding [`UnixMillis`] as an RFC 3339 string in UTC.
+///
+/// Use with `#[serde(with = "greentic_types::units::rfc3339")]`, or the [`option`](rfc3339::option)
+/// variant for optional fields. Deserialization accepts any RFC 3339 offset as well as integer
+/// epoch milliseconds.
+#[cfg(feature = "serde")]
+pub mod rfc3339 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use super::UnixMillis;
+
+    struct Rfc3339(UnixMillis);
+
+    impl Serialize for Rfc3339 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.0.to_rfc3339() {
+                Some(text) => serializer.serialize_str(&text),
+                None => Err(serde::ser::Error::custom(
+                    "timestamp is outside the years RFC 3339 can express",
+                )),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Rfc3339 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct Visitor;
+
+            impl de::Visitor<'_> for Visitor {
+                type Value = Rfc3339;
+
+                fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    f.write_str("an RFC 3339 timestamp or epoch milliseconds")
+                }
+
+                fn visit_str<E: de::Error>(self, value: &str) -> Result<Rfc3339, E> {
+                    UnixMillis::parse_rfc3339(value)
+                        .map(Rfc3339)
+                        .map_err(E::custom)
+                }
+
+                fn visit_i64<E: de::Error>(self, value: i64) -> Result<Rfc3339, E> {
+                    Ok(Rfc3339(UnixMillis(value)))
+                }
+
+                fn visit_u64<E: de::Error>(self, value: u64) -> Result<Rfc3339, E> {
+                    i64::try_from(value)
+                        .map(|millis| Rfc3339(UnixMillis(millis)))
+                        .map_err(|_| E::custom("epoch milliseconds out of range"))
+                }
+            }
+
+            deserializer.deserialize_any(Visitor)
+        }
+    }
+
+    /// Serializes `value` as an RFC 3339 string.
+    pub fn serialize<S: Serializer>(value: &UnixMillis, serializer: S) -> Result<S::Ok, S::Error> {
+        Rfc3339(*value).serialize(serializer)
+    }
+
+    /// Deserializes an RFC 3339 string or epoch milliseconds.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UnixMillis, D::Error> {
+        Rfc3339::deserialize(deserializer).map(|value| value.0)
+    }
+
+    /// The same encoding for `Option<UnixMillis>` fields.
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        use super::{Rfc3339, UnixMillis};
+
+        /// Serializes `value` as an RFC 3339 string or `null`.
+        pub fn serialize<S: Serializer>(
+            value: &Option<UnixMillis>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value.map(Rfc3339).serialize(serializer)
+        }
+
+        /// Deserializes an optional RFC 3339 string or epoch milliseconds.
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<UnixMillis>, D::Error> {
+            Option::<Rfc3339>::deserialize(deserializer).map(|value| value.map(|value| value.0))
+        }
+    }
+}
+
+/// Percentage in `0.0..=100.0`, used for rollout progress and discounts.
+///
+/// Stored as integer basis points (`12.5%` is `1250`), so documents holding a percentage stay
+/// canonically encodable and hashable. Human-readable formats such as JSON use the decimal form;
+/// binary formats such as CBOR carry the basis points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percent(u16);
+
+impl Percent {
+    /// Zero percent.
+    pub const ZERO: Self = Self(0);
+    /// One hundred percent.
+    pub const HUNDRED: Self = Self(BASIS_POINTS);
+
+    /// Validates that `value` lies within `0.0..=100.0`, rounding to the nearest basis point.
+    pub fn new(value: f64) -> GResult<Self> {
+        to_basis_points("percentage", value, 100.0).map(Self)
+    }
+
+    /// Validates that `basis_points` is at most `10_000`.
+    pub fn from_basis_points(basis_points: u16) -> GResult<Self> {
+        check_basis_points("percentage", basis_points).map(Self)
+    }
+
+    /// Returns the percentage (for example `12.5`).
+    pub fn get(self) -> f64 {
+        f64::from(self.0) / 100.0
+    }
+
+    /// Returns the percentage in basis points (for example `1250`).
+    pub const fn basis_points(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the equivalent ratio (`50%` becomes `0.5`).
+    pub const fn as_ratio(self) -> Ratio {
+        Ratio(self.0)
+    }
+
+    /// Applies the percentage to `amount`, rounding down.
+    pub fn of(self, amount: u64) -> u64 {
+        let scaled = u128::from(amount) * u128::from(self.0) / u128::from(BASIS_POINTS);
+        u64::try_from(scaled).unwrap_or(u64::MAX)
+    }
+}
+
+/// Ratio in `0.0..=1.0`, used for sampling.
+///
+/// Stored as integer basis points (`0.25` is `2500`) with the same encoding rules as [`Percent`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ratio(u16);
+
+impl Ratio {
+    /// Zero (nothing).
+    pub const ZERO: Self = Self(0);
+    /// One (everything).
+    pub const ONE: Self = Self(BASIS_POINTS);
+
+    /// Validates that `value` lies within `0.0..=1.0`, rounding to the nearest basis point.
+    pub fn new(value: f64) -> GResult<Self> {
+        to_basis_points("ratio", value, 1.0).map(Self)
+    }
+
+    /// Validates that `basis_points` is at most `10_000`.
+    pub fn from_basis_points(basis_points: u16) -> GResult<Self> {
+        check_basis_points("ratio", basis_points).map(Self)
+    }
+
+    /// Returns the ratio (for example `0.25`).
+    pub fn get(self) -> f64 {
+        f64::from(self.0) / f64::from(BASIS_POINTS)
+    }
+
+    /// Returns the ratio in basis points (for example `2500`).
+    pub const fn basis_points(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the equivalent percentage (`0.5` becomes `50%`).
+    pub const fn as_percent(self) -> Percent {
+        Percent(self.0)
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.get())
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
+
+impl TryFrom<f64> for Percent {
+    type Error = GreenticError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<f64> for Ratio {
+    type Error = GreenticError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Percent> for f64 {
+    fn from(value: Percent) -> Self {
+        value.get()
+    }
+}
+
+impl From<Ratio> for f64 {
+    fn from(value: Ratio) -> Self {
+        value.get()
+    }
+}
+
+const BASIS_POINTS: u16 = 10_000;
+
+fn to_basis_points(kind: &str, value: f64, max: f64) -> GResult<u16> {
+    if (0.0..=max).contains(&value) {
+        Ok((value / max * f64::from(BASIS_POINTS)).round() as u16)
+    } else {
+        Err(GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!("invalid {kind} '{value}': expected a number between 0 and {max}"),
+        ))
+    }
+}
+
+fn check_basis_points(kind: &str, basis_points: u16) -> GResult<u16> {
+    if basis_points <= BASIS_POINTS {
+        Ok(basis_points)
+    } else {
+        Err(GreenticError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "invalid {kind} '{basis_points}' basis points: expected at most {BASIS_POINTS}"
+            ),
+        ))
+    }
+}
+
+/// Splits a leading decimal number (`123`, `1.5`) from the remainder of the string.
+fn split_number(value: &str) -> (&str, &str) {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+        .unwrap_or(value.len());
+    value.split_at(end)
+}
+
+/// Multiplies a decimal number string by `multiplier`, truncating fractional units.
+fn scale(number: &str, multiplier: u64) -> Option<u64> {
+    let number: String = number.chars().filter(|c| *c != '_').collect();
+    let (whole, fraction) = match number.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (number.as_str(), ""),
+    };
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let mut value = whole.checked_mul(multiplier)?;
+    if !fraction.is_empty() {
+        let digits = u32::try_from(fraction.len())
+            .ok()
+            .filter(|len| *len <= 18)?;
+        let fraction: u64 = fraction.parse().ok()?;
+        let scaled = u128::from(fraction) * u128::from(multiplier) / 10u128.pow(digits);
+        value = value.checked_add(u64::try_from(scaled).ok()?)?;
+    }
+    Some(value)
+}
+
+fn invalid_quantity(kind: &str, value: &str, reason: &str) -> GreenticError {
+    GreenticError::new(
+        ErrorCode::InvalidInput,
+        format!("invalid {kind} '{value}': {reason}"),
+    )
+}
+
+#[cfg(feature = "serde")]
+struct QuantityVisitor<T> {
+    expecting: &'static str,
+    from_number: fn(u64) -> T,
+    from_str: fn(&str) -> GResult<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> de::Visitor<'_> for QuantityVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        Ok((self.from_number)(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        u64::try_from(value)
+            .map(self.from_number)
+            .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<T, E> {
+        if value >= 0.0 && value.fract() == 0.0 && value <= u64::MAX as f64 {
+            Ok((self.from_number)(value as u64))
+        } else {
+            Err(E::invalid_value(de::Unexpected::Float(value), &self))
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        (self.from_str)(value).map_err(|err| E::custom(err.message))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ByteSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(QuantityVisitor {
+            expecting: "a byte count or a size string such as \"512MiB\"",
+            from_number: ByteSize::from_bytes,
+            from_str: ByteSize::parse,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for DurationMs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for DurationMs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(QuantityVisitor {
+            expecting: "a millisecond count or a duration string such as \"30s\"",
+            from_number: DurationMs::from_millis,
+            from_str: DurationMs::parse,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Percent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_f64(self.get())
+        } else {
+            serializer.serialize_u16(self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Percent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_any(BasisPointsVisitor {
+            expecting: "a percentage between 0 and 100",
+            human_readable,
+            from_decimal: Percent::new,
+            from_basis_points: Percent::from_basis_points,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Ratio {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_f64(self.get())
+        } else {
+            serializer.serialize_u16(self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Ratio {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_any(BasisPointsVisitor {
+            expecting: "a ratio between 0 and 1",
+            human_readable,
+            from_decimal: Ratio::new,
+            from_basis_points: Ratio::from_basis_points,
+        })
+    }
+}
+
+/// Reads decimal numbers, and integer basis points from binary formats.
+///
+/// Binary formats also accept floats so CBOR written before the basis-point encoding still loads.
+#[cfg(feature = "serde")]
+struct BasisPointsVisitor<T> {
+    expecting: &'static str,
+    human_readable: bool,
+    from_decimal: fn(f64) -> GResult<T>,
+    from_basis_points: fn(u16) -> GResult<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> de::Visitor<'_> for BasisPointsVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        if self.human_readable {
+            return self.visit_f64(value as f64);
+        }
+        let basis_points = u16::try_from(value)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self.expecting))?;
+        (self.from_basis_points)(basis_points).map_err(|err| E::custom(err.message))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        match u64::try_from(value) {
+            Ok(value) => self.visit_u64(value),
+            Err(_) => Err(E::invalid_value(
+                de::Unexpected::Signed(value),
+                &self.expecting,
+            )),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<T, E> {
+        (self.from_decimal)(value).map_err(|err| E::custom(err.message))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for Percent {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("Percent")
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Percentage between 0 and 100",
+            "type": "number",
+            "minimum": 0,
+            "maximum": 100
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for Ratio {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("Ratio")
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Ratio between 0.0 and 1.0",
+            "type": "number",
+            "minimum": 0,
+            "maximum": 1
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for ByteSize {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("ByteSize")
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Size in bytes, or a size string with a unit suffix (for example `512MiB`)",
+            "anyOf": [
+                { "type": "integer", "minimum": 0 },
+                { "type": "string", "pattern": "^\\s*[0-9_]*\\.?[0-9_]+\\s*([kKmMgGtT][iI]?)?[bB]?\\s*$" }
+            ]
+        })
+    }
+}
+
+/// Schema stand-in for [`ByteSize`] fields serialized through the mebibyte adapter.
+#[cfg(feature = "schemars")]
+pub(crate) struct MebibyteSize;
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for MebibyteSize {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("MebibyteSize")
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Size in mebibytes, or a size string with a unit suffix (for example `512MiB`)",
+            "anyOf": [
+                { "type": "integer", "minimum": 0 },
+                { "type": "string", "pattern": "^\\s*[0-9_]*\\.?[0-9_]+\\s*([kKmMgGtT][iI]?)?[bB]?\\s*$" }
+            ]
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl JsonSchema for DurationMs {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("DurationMs")
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Duration in milliseconds, or a duration string such as `30s` or `1m30s`",
+            "anyOf": [
+                { "type": "integer", "minimum": 0 },
+                { "type": "string", "minLength": 1 }
+            ]
+        })
+    }
+}
+
+/// Serde adapter for legacy fields that stored sizes as whole mebibytes.
+///
+/// Bare numbers are read as MiB and sizes are written back as MiB when they divide evenly, so
+/// existing documents keep their shape; other sizes are written as unit strings.
+#[cfg(feature = "serde")]
+pub(crate) mod mebibytes {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &ByteSize,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if value.0 % MIB == 0 {
+            serializer.serialize_u64(value.0 / MIB)
+        } else {
+            serializer.collect_str(value)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ByteSize, D::Error> {
+        deserializer.deserialize_any(QuantityVisitor {
+            expecting: "a mebibyte count or a size string such as \"512MiB\"",
+            from_number: ByteSize::from_mib,
+            from_str: ByteSize::parse,
+        })
+    }
+
+    /// Optional variant of the adapter.
+    pub(crate) mod option {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer>(
+            value: &Option<ByteSize>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => super::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<ByteSize>, D::Error> {
+            struct Wrapper(ByteSize);
+
+            impl<'de> Deserialize<'de> for Wrapper {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    super::deserialize(deserializer).map(Wrapper)
+                }
+            }
+
+            Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|wrapper| wrapper.0))
+        }
+    }
+}
@@ -9,6 +9,8 @@ use semver::Version;
 use serde_json::Value;
 
 use crate::pack::extensions::component_sources::{ComponentSourcesV1, EXT_COMPONENT_SOURCES_V1};
+#[cfg(feature = "serde")]
+use crate::pack::extensions::pack_tests::EXT_PACK_TESTS_V1;
 use crate::pack_manifest::ExtensionInline;
 use crate::{PackId, PackManifest};
 
@@ -339,9 +341,63 @@ pub fn validate_pack_manifest_core(manifest: &PackManifest) -> Vec<Diagnostic> {
                     }
                 }
             }
+
+            if let Some(capabilities) = &node.capabilities_override {
+                let component = manifest.components.iter().find(|component| {
+                    node.component.pack_alias.is_none() && component.id == node.component.id
+                });
+                let Some(component) = component else {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_NODE_CAPABILITIES_UNVERIFIED",
+                        "Node capability override cannot be checked because its component is not declared in this pack.",
+                        Some(format!(
+                            "flows.{}.nodes.{}.capabilities_override",
+                            entry.id.as_str(),
+                            node_id.as_str()
+                        )),
+                        Some(
+                            "Declare the component in manifest.components or remove the override."
+                                .to_owned(),
+                        ),
+                    ));
+                    continue;
+                };
+                let report = capabilities.check_against(&component.capabilities);
+                for mismatch in report.mismatches {
+                    diagnostics.push(core_diagnostic(
+                        Severity::Error,
+                        "PACK_NODE_CAPABILITIES_EXCEED_COMPONENT",
+                        "Node capability override grants more than the component declares.",
+                        Some(format!(
+                            "flows.{}.nodes.{}.capabilities_override.{}",
+                            entry.id.as_str(),
+                            node_id.as_str(),
+                            mismatch.path
+                        )),
+                        Some(
+                            "Restrict the override to a subset of the component capabilities."
+                                .to_owned(),
+                        ),
+                    ));
+                }
+            }
         }
     }
 
+    #[cfg(feature = "serde")]
+    match manifest.get_pack_tests_v1() {
+        Ok(Some(tests)) => diagnostics.extend(tests.validate(manifest)),
+        Ok(None) => {}
+        Err(err) => diagnostics.push(core_diagnostic(
+            Severity::Error,
+            "PACK_TESTS_EXTENSION_INVALID",
+            &format!("Pack tests extension could not be decoded: {err}"),
+            Some(format!("extensions.{EXT_PACK_TESTS_V1}")),
+            None,
+        )),
+    }
+
     diagnostics
 }
 
@@ -372,7 +428,7 @@ fn declared_component_keys(manifest: &PackManifest) -> HashSet<String> {
     declared
 }
 
-fn core_diagnostic(
+pub(crate) fn core_diagnostic(
     severity: Severity,
     code: &str,
     message: &str,
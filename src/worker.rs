@@ -1,4 +1,6 @@
 //! Generic worker envelope shared across runner and messaging components.
+//!
+//! The types in this module form the single-shot v1 protocol; [`v2`] adds streaming frames.
 
 use alloc::{string::String, vec::Vec};
 
@@ -7,7 +9,9 @@ use schemars::JsonSchema;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::TenantCtx;
+use crate::{BlobRef, RetryPolicy, TenantCtx};
+
+pub mod v2;
 
 /// Request payload for invoking a worker.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -40,6 +44,12 @@ pub struct WorkerRequest {
     pub thread_id: Option<String>,
     /// JSON-encoded payload forwarded to the worker; the ABI treats this as opaque.
     pub payload_json: String,
+    /// Payload stored externally when too large to inline; `payload_json` is then empty.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub payload_blob: Option<BlobRef>,
     /// UTC timestamp for when the request was created (ISO8601).
     pub timestamp_utc: String,
 }
@@ -53,6 +63,12 @@ pub struct WorkerMessage {
     pub kind: String,
     /// JSON-encoded message payload; workers and callers negotiate its shape.
     pub payload_json: String,
+    /// Payload stored externally when too large to inline; `payload_json` is then empty.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub payload_blob: Option<BlobRef>,
 }
 
 /// Response envelope returned by worker executions.
@@ -90,6 +106,12 @@ pub struct WorkerResponse {
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
     pub messages: Vec<WorkerMessage>,
+    /// Retry policy the caller should apply when the worker reports a transient failure.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub retry_policy: Option<RetryPolicy>,
     /// UTC timestamp for when the response was produced (ISO8601).
     pub timestamp_utc: String,
 }
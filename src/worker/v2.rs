@@ -0,0 +1,255 @@
+//! Streaming worker protocol (v2).
+//!
+//! A v2 exchange is a stream of [`WorkerFrame`]s sharing a `stream_id`. The caller opens it with
+//! a [`WorkerFrameBody::Start`] frame carrying a v1 [`WorkerRequest`]; the worker answers with
+//! any number of partial results, progress updates, and heartbeats, and closes the stream with
+//! exactly one terminal frame: [`WorkerFrameBody::Complete`], [`WorkerFrameBody::Failed`], or
+//! [`WorkerFrameBody::CancelAcknowledged`] after the caller sent [`WorkerFrameBody::Cancel`].
+//! Cancellation is cooperative: the worker may still complete or fail after a cancel request.
+//!
+//! v1 envelopes convert into start and completion frames, and [`into_v1_response`] folds a
+//! finished stream back into a single-shot [`WorkerResponse`] for callers that do not stream.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorCode, Percent, WorkerMessage, WorkerRequest, WorkerResponse};
+
+/// Protocol version advertised by v2 workers.
+pub const PROTOCOL_VERSION: &str = "2.0";
+
+/// One frame of a v2 worker stream.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct WorkerFrame {
+    /// Identifier shared by every frame of the stream.
+    pub stream_id: String,
+    /// Position of the frame within the stream, strictly increasing from zero.
+    pub seq: u64,
+    /// UTC timestamp for when the frame was produced (ISO8601).
+    pub timestamp_utc: String,
+    /// Frame payload.
+    pub body: WorkerFrameBody,
+}
+
+/// Payload of a [`WorkerFrame`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub enum WorkerFrameBody {
+    /// Opens the stream with the invocation request.
+    Start(WorkerRequest),
+    /// Streamed partial result.
+    Partial(WorkerMessage),
+    /// Progress update for long-running operations.
+    Progress {
+        /// Completed share of the work, when the worker can estimate it.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        percent: Option<Percent>,
+        /// Human-readable status line.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        detail: Option<String>,
+    },
+    /// Liveness signal sent while no other frames are due.
+    Heartbeat,
+    /// Caller asks the worker to stop.
+    Cancel {
+        /// Optional reason shown in logs.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        reason: Option<String>,
+    },
+    /// Worker stopped in response to [`WorkerFrameBody::Cancel`].
+    CancelAcknowledged,
+    /// Worker finished; messages already streamed as partials are not repeated.
+    Complete(WorkerResponse),
+    /// Worker failed.
+    Failed {
+        /// Canonical error code.
+        code: ErrorCode,
+        /// Human-readable description.
+        message: String,
+    },
+}
+
+impl WorkerFrameBody {
+    /// Returns `true` for frames that close the stream.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Complete(_) | Self::Failed { .. } | Self::CancelAcknowledged
+        )
+    }
+}
+
+impl From<WorkerRequest> for WorkerFrameBody {
+    fn from(request: WorkerRequest) -> Self {
+        Self::Start(request)
+    }
+}
+
+impl From<WorkerResponse> for WorkerFrameBody {
+    fn from(response: WorkerResponse) -> Self {
+        Self::Complete(response)
+    }
+}
+
+impl WorkerFrame {
+    /// Creates a frame.
+    pub fn new(
+        stream_id: impl Into<String>,
+        seq: u64,
+        timestamp_utc: impl Into<String>,
+        body: impl Into<WorkerFrameBody>,
+    ) -> Self {
+        Self {
+            stream_id: stream_id.into(),
+            seq,
+            timestamp_utc: timestamp_utc.into(),
+            body: body.into(),
+        }
+    }
+
+    /// Opens a stream from a v1 request, reusing its timestamp.
+    pub fn start(stream_id: impl Into<String>, request: WorkerRequest) -> Self {
+        let timestamp_utc = request.timestamp_utc.clone();
+        Self::new(stream_id, 0, timestamp_utc, request)
+    }
+
+    /// Closes a stream with a v1 response, reusing its timestamp.
+    pub fn complete(stream_id: impl Into<String>, seq: u64, response: WorkerResponse) -> Self {
+        let timestamp_utc = response.timestamp_utc.clone();
+        Self::new(stream_id, seq, timestamp_utc, response)
+    }
+
+    /// Returns `true` when the frame closes the stream.
+    pub fn is_terminal(&self) -> bool {
+        self.body.is_terminal()
+    }
+}
+
+/// Protocol violation found by [`validate_stream`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum WorkerStreamError {
+    /// The stream has no frames.
+    #[error("worker stream is empty")]
+    Empty,
+    /// The first frame is not a start frame.
+    #[error("worker stream must open with a start frame")]
+    MissingStart,
+    /// A frame belongs to another stream.
+    #[error("frame {seq} belongs to stream `{found}`, expected `{expected}`")]
+    StreamMismatch {
+        /// Stream id of the first frame.
+        expected: String,
+        /// Stream id of the offending frame.
+        found: String,
+        /// Sequence number of the offending frame.
+        seq: u64,
+    },
+    /// Sequence numbers do not strictly increase.
+    #[error("frame {seq} does not follow frame {previous}")]
+    OutOfOrder {
+        /// Sequence number of the previous frame.
+        previous: u64,
+        /// Sequence number of the offending frame.
+        seq: u64,
+    },
+    /// A second start frame appeared.
+    #[error("frame {seq} restarts an open stream")]
+    DuplicateStart {
+        /// Sequence number of the offending frame.
+        seq: u64,
+    },
+    /// A cancellation was acknowledged without being requested.
+    #[error("frame {seq} acknowledges a cancellation that was never requested")]
+    UnrequestedCancelAck {
+        /// Sequence number of the offending frame.
+        seq: u64,
+    },
+    /// A frame follows the terminal frame.
+    #[error("frame {seq} follows the terminal frame")]
+    AfterTerminal {
+        /// Sequence number of the offending frame.
+        seq: u64,
+    },
+}
+
+/// Checks that `frames` form a well-ordered stream.
+///
+/// The stream may still be open; only frames received so far are checked.
+pub fn validate_stream(frames: &[WorkerFrame]) -> Result<(), WorkerStreamError> {
+    let Some((first, rest)) = frames.split_first() else {
+        return Err(WorkerStreamError::Empty);
+    };
+    if !matches!(first.body, WorkerFrameBody::Start(_)) {
+        return Err(WorkerStreamError::MissingStart);
+    }
+    let mut previous = first;
+    let mut cancel_requested = false;
+    for frame in rest {
+        if frame.stream_id != first.stream_id {
+            return Err(WorkerStreamError::StreamMismatch {
+                expected: first.stream_id.clone(),
+                found: frame.stream_id.clone(),
+                seq: frame.seq,
+            });
+        }
+        if frame.seq <= previous.seq {
+            return Err(WorkerStreamError::OutOfOrder {
+                previous: previous.seq,
+                seq: frame.seq,
+            });
+        }
+        if previous.is_terminal() {
+            return Err(WorkerStreamError::AfterTerminal { seq: frame.seq });
+        }
+        match frame.body {
+            WorkerFrameBody::Start(_) => {
+                return Err(WorkerStreamError::DuplicateStart { seq: frame.seq });
+            }
+            WorkerFrameBody::Cancel { .. } => cancel_requested = true,
+            WorkerFrameBody::CancelAcknowledged if !cancel_requested => {
+                return Err(WorkerStreamError::UnrequestedCancelAck { seq: frame.seq });
+            }
+            _ => {}
+        }
+        previous = frame;
+    }
+    Ok(())
+}
+
+/// Folds a completed stream into a v1 response.
+///
+/// Partial messages are placed before the messages of the completion frame. Returns `None` when
+/// the stream has no [`WorkerFrameBody::Complete`] frame.
+pub fn into_v1_response(frames: impl IntoIterator<Item = WorkerFrame>) -> Option<WorkerResponse> {
+    let mut partials: Vec<WorkerMessage> = Vec::new();
+    for frame in frames {
+        match frame.body {
+            WorkerFrameBody::Partial(message) => partials.push(message),
+            WorkerFrameBody::Complete(mut response) => {
+                partials.append(&mut response.messages);
+                response.messages = partials;
+                return Some(response);
+            }
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use greentic_types::{
+    Alert, AlertSeverity, AlertState, AlertSubject, ErrorCode, FindingSeverity, RolloutState,
+    RolloutStatus, TenantCtx, UnixMillis,
+};
+use serde_json::json;
+
+fn firing_alert() -> Alert {
+    let rollout = RolloutStatus {
+        environment_ref: "env-prod".parse().unwrap(),
+        desired_state_version: Some(7),
+        state: RolloutState::Failed,
+        bundle_id: None,
+        deferred_by: None,
+        progress: None,
+        message: Some("health check failed".into()),
+        metadata: BTreeMap::new(),
+    };
+    let mut alert = Alert::new(
+        "alert-1".parse().unwrap(),
+        TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap()),
+        "distributor",
+        AlertSeverity::Error,
+        "Rollout failed",
+    )
+    .with_subject(&rollout)
+    .with_subject(AlertSubject::Scan {
+        scan_id: "scan-9".parse().unwrap(),
+    });
+    alert.fingerprint = Some("rollout:env-prod".into());
+    alert.labels.insert("team".into(), "platform".into());
+    alert
+}
+
+#[test]
+fn alert_roundtrip() {
+    let alert = firing_alert();
+    let value = serde_json::to_value(&alert).unwrap();
+    assert_eq!(value["state"], "firing");
+    assert_eq!(value["severity"], "error");
+    assert_eq!(
+        value["related"][0],
+        json!({"kind": "rollout", "environment_ref": "env-prod", "desired_state_version": 7})
+    );
+    assert_eq!(
+        value["related"][1],
+        json!({"kind": "scan", "scan_id": "scan-9"})
+    );
+    let back: Alert = serde_json::from_value(value).unwrap();
+    assert_eq!(back, alert);
+}
+
+#[test]
+fn alert_lifecycle() {
+    let mut alert = firing_alert();
+    assert!(alert.is_open());
+    alert
+        .acknowledge(Some("oncall-1".parse().unwrap()))
+        .unwrap();
+    alert
+        .acknowledge(Some("oncall-2".parse().unwrap()))
+        .unwrap();
+    assert_eq!(alert.state, AlertState::Acknowledged);
+    assert_eq!(alert.acknowledged_by.as_ref().unwrap().as_str(), "oncall-1");
+
+    alert.resolve();
+    assert!(!alert.is_open());
+    let err = alert.acknowledge(None).unwrap_err();
+    assert_eq!(err.code, ErrorCode::Conflict);
+
+    alert.resolved_at_utc = Some(UnixMillis::from_millis(2_000));
+    let json = serde_json::to_value(&alert).unwrap();
+    assert_eq!(json["resolved_at_utc"], "1970-01-01T00:00:02Z");
+
+    alert.refire();
+    assert_eq!(alert.state, AlertState::Firing);
+    assert!(alert.acknowledged_by.is_none());
+    assert!(alert.resolved_at_utc.is_none());
+}
+
+#[test]
+fn finding_severity_maps_onto_alert_severity() {
+    assert_eq!(
+        AlertSeverity::from(FindingSeverity::Critical),
+        AlertSeverity::Critical
+    );
+    assert_eq!(
+        AlertSeverity::from(FindingSeverity::High),
+        AlertSeverity::Error
+    );
+    assert_eq!(
+        AlertSeverity::from(FindingSeverity::Medium),
+        AlertSeverity::Warning
+    );
+    assert_eq!(
+        AlertSeverity::from(FindingSeverity::Unknown),
+        AlertSeverity::Info
+    );
+    assert!(AlertSeverity::Critical > AlertSeverity::Warning);
+}
@@ -0,0 +1,88 @@
+use greentic_types::{
+    AttachmentChunker, DEFAULT_ATTACHMENT_CHUNK_SIZE, ErrorCode, reassemble_attachment,
+};
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|index| (index % 251) as u8).collect()
+}
+
+#[test]
+fn split_and_reassemble_roundtrip_out_of_order() {
+    let chunker = AttachmentChunker::new(4).unwrap();
+    let bytes = payload(10);
+    let mut chunks = chunker.split("transfer-1", &bytes).unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(
+        chunks
+            .iter()
+            .map(|chunk| chunk.bytes.len())
+            .collect::<Vec<_>>(),
+        [4, 4, 2]
+    );
+    assert!(chunks.iter().all(|chunk| chunk.total_size == 10));
+    assert_eq!(
+        chunks
+            .iter()
+            .map(|chunk| chunk.is_final)
+            .collect::<Vec<_>>(),
+        [false, false, true]
+    );
+
+    chunks.reverse();
+    assert_eq!(reassemble_attachment(chunks).unwrap(), bytes);
+}
+
+#[test]
+fn empty_payload_yields_single_final_chunk() {
+    let chunks = AttachmentChunker::default().split("empty", &[]).unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert!(chunks[0].is_final);
+    assert_eq!(reassemble_attachment(chunks).unwrap(), Vec::<u8>::new());
+    assert_eq!(
+        AttachmentChunker::default().chunk_size(),
+        DEFAULT_ATTACHMENT_CHUNK_SIZE
+    );
+    assert!(AttachmentChunker::new(0).is_err());
+}
+
+#[test]
+fn reassembly_rejects_broken_transfers() {
+    let chunker = AttachmentChunker::new(3).unwrap();
+    let chunks = chunker.split("transfer-2", &payload(9)).unwrap();
+
+    let mut missing = chunks.clone();
+    missing.remove(1);
+    let err = reassemble_attachment(missing).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert!(err.message.contains("missing chunk 1"), "{}", err.message);
+
+    let mut truncated = chunks.clone();
+    truncated.pop();
+    assert!(reassemble_attachment(truncated).is_err());
+
+    let mut tampered = chunks.clone();
+    tampered[0].bytes[0] ^= 0xff;
+    let err = reassemble_attachment(tampered).unwrap_err();
+    assert!(err.message.contains("digest"), "{}", err.message);
+
+    let mut mixed = chunks;
+    mixed[2].transfer_id = "other".into();
+    assert!(reassemble_attachment(mixed).is_err());
+    assert!(reassemble_attachment(Vec::new()).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn chunk_serializes_bytes_as_base64() {
+    let chunk = AttachmentChunker::new(8)
+        .unwrap()
+        .split("t", b"hi")
+        .unwrap()
+        .remove(0);
+    let json = serde_json::to_value(&chunk).unwrap();
+    assert_eq!(json["bytes"], "aGk=");
+    assert_eq!(json["final"], true);
+    assert_eq!(json["digest"]["algo"], "blake3");
+    let decoded: greentic_types::AttachmentChunk = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, chunk);
+}
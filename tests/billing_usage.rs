@@ -0,0 +1,158 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::{
+    MoneyError, MoneyMicro, PriceModel, SubscriptionId, SubscriptionUsage, UnixMillis,
+    UsageAggregate, UsageRecord,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+fn assert_roundtrip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug,
+{
+    let json = serde_json::to_string_pretty(value).expect("serialize");
+    let roundtrip: T = serde_json::from_str(&json).expect("deserialize");
+    assert_eq!(&roundtrip, value, "{json}");
+}
+
+fn eur(amount_micro: u64) -> MoneyMicro {
+    MoneyMicro::new(amount_micro, "EUR".parse().unwrap())
+}
+
+fn record(subscription: &str, unit: &str, quantity: u64, start: i64, key: &str) -> UsageRecord {
+    UsageRecord::new(
+        subscription.parse().unwrap(),
+        unit,
+        quantity,
+        UnixMillis::from_millis(start),
+        UnixMillis::from_millis(start + 60_000),
+        key,
+    )
+}
+
+#[test]
+fn usage_record_roundtrip_and_validation() {
+    let mut usage = record("sub-1", "build-minute", 12, 0, "runner-a:0");
+    usage
+        .metadata
+        .insert("runner".into(), serde_json::json!("runner-a"));
+    assert_roundtrip(&usage);
+    usage.validate().unwrap();
+
+    let mut inverted = usage.clone();
+    inverted.window_end = UnixMillis::from_millis(-1);
+    assert!(inverted.validate().is_err());
+    let mut unkeyed = usage;
+    unkeyed.idempotency_key.clear();
+    assert!(unkeyed.validate().is_err());
+}
+
+#[test]
+fn usage_aggregate_dedupes_and_prices_overage() {
+    let records = vec![
+        record("sub-1", "build-minute", 40, 60_000, "a:1"),
+        record("sub-1", "build-minute", 30, 0, "a:0"),
+        record("sub-1", "build-minute", 30, 0, "a:0"),
+        record("sub-1", "scan", 2, 0, "b:0"),
+        record("sub-2", "build-minute", 5, 0, "a:0"),
+    ];
+    let aggregates = UsageAggregate::from_records(&records);
+    assert_eq!(aggregates.len(), 3);
+
+    let builds = &aggregates[0];
+    let expected_id: SubscriptionId = "sub-1".parse().unwrap();
+    assert_eq!(builds.subscription_id, expected_id);
+    assert_eq!(builds.unit_label, "build-minute");
+    assert_eq!(builds.quantity, 70);
+    assert_eq!(builds.record_count, 2);
+    assert_eq!(builds.window_start, UnixMillis::from_millis(0));
+    assert_eq!(builds.window_end, UnixMillis::from_millis(120_000));
+    assert_eq!(aggregates[1].unit_label, "scan");
+    assert_eq!(aggregates[2].quantity, 5);
+    assert_roundtrip(builds);
+
+    let metered = PriceModel::Metered {
+        included_units: 50,
+        overage_rate: eur(1_000),
+        unit_label: "build-minute".into(),
+    };
+    assert_eq!(builds.overage_units(&metered), Some(20));
+    assert_eq!(builds.overage_cost(&metered), Some(eur(20_000)));
+    assert_eq!(aggregates[1].overage_units(&metered), None);
+    assert_eq!(builds.overage_units(&PriceModel::Free), None);
+}
+
+#[test]
+fn subscription_usage_snapshot_reports_overage() {
+    let records = vec![
+        record("sub-1", "build-minute", 40, 0, "a:0"),
+        record("sub-1", "build-minute", 30, 60_000, "a:1"),
+        record("sub-1", "scan", 2, 0, "b:0"),
+        record("sub-2", "build-minute", 500, 0, "a:0"),
+    ];
+    let aggregates = UsageAggregate::from_records(&records);
+    let metered = PriceModel::Metered {
+        included_units: 50,
+        overage_rate: eur(1_000),
+        unit_label: "build-minute".into(),
+    };
+    let mut usage = SubscriptionUsage::from_aggregates(
+        "sub-1".parse().unwrap(),
+        UnixMillis::from_millis(0),
+        UnixMillis::from_millis(86_400_000),
+        &aggregates,
+        &metered,
+    )
+    .unwrap();
+
+    assert_eq!(usage.consumed("build-minute"), 70);
+    assert_eq!(usage.units["build-minute"].overage, 20);
+    assert_eq!(usage.remaining("build-minute"), Some(0));
+    assert_eq!(usage.consumed("scan"), 2);
+    assert_eq!(usage.remaining("scan"), None);
+    assert_eq!(usage.overage_cost, Some(eur(20_000)));
+    assert!(usage.has_overage());
+    assert_roundtrip(&usage);
+
+    usage.add("build-minute", 5, &metered).unwrap();
+    assert_eq!(usage.units["build-minute"].overage, 25);
+    assert_eq!(usage.overage_cost, Some(eur(25_000)));
+
+    let metered_usd = PriceModel::Metered {
+        included_units: 50,
+        overage_rate: MoneyMicro::new(1_000, "USD".parse().unwrap()),
+        unit_label: "build-minute".into(),
+    };
+    assert!(matches!(
+        usage.add("build-minute", 5, &metered_usd),
+        Err(MoneyError::CurrencyMismatch { .. })
+    ));
+    assert_eq!(
+        usage.consumed("build-minute"),
+        75,
+        "rejected add leaves usage as is"
+    );
+    assert_eq!(usage.overage_cost, Some(eur(25_000)));
+
+    let upgraded = PriceModel::Metered {
+        included_units: 100,
+        overage_rate: eur(1_000),
+        unit_label: "build-minute".into(),
+    };
+    usage.add("build-minute", 1, &upgraded).unwrap();
+    assert_eq!(usage.units["build-minute"].overage, 0);
+    assert_eq!(usage.remaining("build-minute"), Some(24));
+    assert_eq!(usage.overage_cost, Some(eur(25_000)));
+
+    let free = SubscriptionUsage::from_aggregates(
+        "sub-1".parse().unwrap(),
+        UnixMillis::from_millis(0),
+        UnixMillis::from_millis(86_400_000),
+        &aggregates,
+        &PriceModel::Free,
+    )
+    .unwrap();
+    assert!(!free.has_overage());
+    assert_eq!(free.overage_cost, None);
+}
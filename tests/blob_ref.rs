@@ -0,0 +1,54 @@
+use greentic_types::{BlobRef, ErrorCode, HashAlgorithm, HashDigest, UnixMillis};
+
+fn blob(payload: &[u8]) -> BlobRef {
+    let digest = HashDigest::blake3(blake3::hash(payload).to_hex().as_str()).unwrap();
+    BlobRef::new(
+        "artifacts-eu".parse().unwrap(),
+        "runs/run-1/report.json",
+        payload.len() as u64,
+        digest,
+    )
+}
+
+#[test]
+fn verify_checks_size_and_digest() {
+    let payload = br#"{"passed":12}"#;
+    let reference = blob(payload);
+    reference.verify(payload).unwrap();
+
+    let err = reference.verify(br#"{"passed":13}"#).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert!(err.message.contains("digest"), "{}", err.message);
+    let err = reference.verify(b"{}").unwrap_err();
+    assert!(err.message.contains("expected 13"), "{}", err.message);
+
+    let sha = BlobRef {
+        digest: HashDigest::new(HashAlgorithm::Other("sha256".into()), "00ff").unwrap(),
+        ..reference
+    };
+    assert_eq!(
+        sha.verify(payload).unwrap_err().code,
+        ErrorCode::Unavailable
+    );
+}
+
+#[test]
+fn expiry_is_inclusive() {
+    let reference = blob(b"x").with_expires_at(UnixMillis::from_millis(5_000));
+    assert!(!reference.is_expired(UnixMillis::from_millis(4_999)));
+    assert!(reference.is_expired(UnixMillis::from_millis(5_000)));
+    assert!(!blob(b"x").is_expired(UnixMillis::from_millis(i64::MAX)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn blob_ref_serializes_compactly() {
+    let reference = blob(b"hello").with_content_type("text/plain");
+    let value = serde_json::to_value(&reference).unwrap();
+    assert_eq!(value["store"], "artifacts-eu");
+    assert_eq!(value["size_bytes"], 5);
+    assert_eq!(value["content_type"], "text/plain");
+    assert!(value.get("expires_at").is_none());
+    let decoded: BlobRef = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded, reference);
+}
@@ -0,0 +1,106 @@
+use greentic_types::{
+    CapabilityMismatch, CapabilityMismatchReason, ComponentCapabilities, EnvCapabilities,
+    FilesystemCapabilities, FilesystemMode, FilesystemMount, HostCapabilities, HttpCapabilities,
+    SecretKey, SecretRequirement, SecretsCapabilities, StateCapabilities, TelemetryCapabilities,
+    TelemetryScope, WasiCapabilities,
+};
+
+fn secret(key: &str) -> SecretRequirement {
+    let mut requirement = SecretRequirement::default();
+    requirement.key = SecretKey::new(key).unwrap();
+    requirement
+}
+
+fn required() -> ComponentCapabilities {
+    ComponentCapabilities {
+        wasi: WasiCapabilities {
+            filesystem: Some(FilesystemCapabilities {
+                mode: FilesystemMode::ReadOnly,
+                mounts: vec![FilesystemMount {
+                    name: "cache".into(),
+                    host_class: "cache".into(),
+                    guest_path: "/cache".into(),
+                }],
+            }),
+            env: Some(EnvCapabilities {
+                allow: vec!["HOME".into()],
+            }),
+            random: true,
+            clocks: false,
+        },
+        host: HostCapabilities {
+            secrets: Some(SecretsCapabilities {
+                required: vec![secret("api_token")],
+            }),
+            state: Some(StateCapabilities {
+                read: true,
+                write: true,
+            }),
+            http: Some(HttpCapabilities {
+                client: true,
+                server: false,
+            }),
+            telemetry: Some(TelemetryCapabilities {
+                scope: TelemetryScope::Pack,
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn identical_grant_satisfies_requirements() {
+    let caps = required();
+    assert!(caps.is_satisfied_by(&caps));
+    assert!(ComponentCapabilities::default().is_satisfied_by(&ComponentCapabilities::default()));
+
+    let mut broader = caps.clone();
+    broader.wasi.filesystem.as_mut().unwrap().mode = FilesystemMode::Sandbox;
+    broader.wasi.clocks = true;
+    broader.host.telemetry = Some(TelemetryCapabilities {
+        scope: TelemetryScope::Tenant,
+    });
+    assert!(caps.is_satisfied_by(&broader));
+}
+
+#[test]
+fn narrower_grant_reports_structured_mismatches() {
+    let mut granted = required();
+    granted.wasi.random = false;
+    granted.wasi.filesystem.as_mut().unwrap().mode = FilesystemMode::None;
+    granted.wasi.env = None;
+    granted.host.secrets = None;
+    granted.host.state.as_mut().unwrap().write = false;
+    granted.host.telemetry = Some(TelemetryCapabilities {
+        scope: TelemetryScope::Node,
+    });
+
+    let report = required().check_against(&granted);
+    assert!(!report.is_satisfied());
+    let paths: Vec<&str> = report.mismatches.iter().map(|m| m.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "wasi.random",
+            "wasi.filesystem.mode",
+            "wasi.env.HOME",
+            "host.secrets.api_token",
+            "host.state.write",
+            "host.telemetry.scope",
+        ]
+    );
+    assert_eq!(
+        report.mismatches[1],
+        CapabilityMismatch {
+            path: "wasi.filesystem.mode".into(),
+            reason: CapabilityMismatchReason::Insufficient {
+                required: "ReadOnly".into(),
+                granted: "None".into(),
+            },
+        }
+    );
+    assert_eq!(
+        report.mismatches[0].reason,
+        CapabilityMismatchReason::NotGranted
+    );
+}
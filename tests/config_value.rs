@@ -0,0 +1,93 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::{
+    ConfigRef, ConfigSegment, ConfigValue, ErrorCode, GreenticError, SecretKey, parse_config_string,
+};
+use serde_json::json;
+
+fn secret(key: &str) -> ConfigRef {
+    ConfigRef::Secret(SecretKey::new(key).unwrap())
+}
+
+#[test]
+fn parses_references_and_escapes() {
+    let segments =
+        parse_config_string("postgres://${env:DB_USER}:${secret:db/password}@h/$${x}$").unwrap();
+    assert_eq!(
+        segments,
+        [
+            ConfigSegment::Text("postgres://".into()),
+            ConfigSegment::Ref(ConfigRef::Env("DB_USER".into())),
+            ConfigSegment::Text(":".into()),
+            ConfigSegment::Ref(secret("db/password")),
+            ConfigSegment::Text("@h/${x}$".into()),
+        ]
+    );
+    assert_eq!(secret("db/password").to_string(), "${secret:db/password}");
+}
+
+#[test]
+fn rejects_malformed_references() {
+    for input in [
+        "${env:DB_USER",
+        "${vault:db}",
+        "${secret}",
+        "${env:1BAD}",
+        "${secret:../escape}",
+    ] {
+        let err = ConfigValue::parse(json!({"nested": [input]})).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput, "{input}");
+    }
+    let legacy: ConfigValue = serde_json::from_value(json!("${nope:x}")).unwrap();
+    assert_eq!(legacy.validate().unwrap_err().code, ErrorCode::InvalidInput);
+    assert!(ConfigValue::env("not valid").is_err());
+}
+
+#[test]
+fn resolves_nested_references() {
+    let value = ConfigValue::parse(json!({
+        "url": "https://${env:HOST}/api",
+        "auth": {"token": "${secret:api/token}"},
+        "retries": 3,
+        "hosts": ["${env:HOST}"]
+    }))
+    .unwrap();
+    assert_eq!(
+        value.references(),
+        [secret("api/token"), ConfigRef::Env("HOST".into())]
+    );
+
+    let resolver = |reference: &ConfigRef| match reference {
+        ConfigRef::Env(name) if name == "HOST" => Ok("example.test".to_owned()),
+        ConfigRef::Secret(_) => Ok("s3cr3t".to_owned()),
+        other => Err(GreenticError::new(
+            ErrorCode::NotFound,
+            format!("unknown {other}"),
+        )),
+    };
+    assert_eq!(
+        value.resolve(&resolver).unwrap(),
+        json!({
+            "url": "https://example.test/api",
+            "auth": {"token": "s3cr3t"},
+            "retries": 3,
+            "hosts": ["example.test"]
+        })
+    );
+
+    let missing = ConfigValue::env("MISSING").unwrap();
+    assert_eq!(
+        missing.resolve(&resolver).unwrap_err().code,
+        ErrorCode::NotFound
+    );
+    assert!(!ConfigValue::parse(json!(1)).unwrap().has_references());
+}
+
+#[test]
+fn config_value_serializes_as_plain_json() {
+    let value = ConfigValue::secret(SecretKey::new("db/password").unwrap());
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json, json!("${secret:db/password}"));
+    let decoded: ConfigValue = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, value);
+}
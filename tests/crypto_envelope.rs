@@ -0,0 +1,104 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::{
+    EnvelopeSigner, EnvelopeVerifier, ErrorCode, GResult, SignatureAlgorithm, SignedWorkerRequest,
+    SigningKeyRef, TenantCtx, WorkerRequest,
+};
+use sha2::{Digest, Sha256};
+
+/// Keyed-hash stand-in for a real signature scheme.
+struct TestKey {
+    key: &'static str,
+    secret: &'static [u8],
+}
+
+impl TestKey {
+    fn mac(&self, message: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.secret);
+        hasher.update(message);
+        hasher.finalize().to_vec()
+    }
+}
+
+impl EnvelopeSigner for TestKey {
+    fn algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::Other("test-mac".into())
+    }
+
+    fn key(&self) -> SigningKeyRef {
+        self.key.parse().unwrap()
+    }
+
+    fn sign(&self, message: &[u8]) -> GResult<Vec<u8>> {
+        Ok(self.mac(message))
+    }
+}
+
+impl EnvelopeVerifier for TestKey {
+    fn verify(
+        &self,
+        _algorithm: &SignatureAlgorithm,
+        key: &SigningKeyRef,
+        message: &[u8],
+        signature: &[u8],
+    ) -> GResult<bool> {
+        Ok(key.as_str() == self.key && self.mac(message) == signature)
+    }
+}
+
+fn request() -> WorkerRequest {
+    WorkerRequest {
+        version: "1.0".into(),
+        tenant: TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap()),
+        worker_id: "greentic-test-worker".into(),
+        correlation_id: None,
+        session_id: None,
+        thread_id: None,
+        payload_json: r#"{"input":"value"}"#.into(),
+        payload_blob: None,
+        timestamp_utc: "2025-01-01T00:00:00Z".into(),
+    }
+}
+
+const KEY: TestKey = TestKey {
+    key: "runner-key",
+    secret: b"shared-secret",
+};
+
+#[test]
+fn sealed_envelope_roundtrips_and_opens() {
+    let envelope = SignedWorkerRequest::seal(&request(), &KEY).unwrap();
+    let json = serde_json::to_value(&envelope).unwrap();
+    assert!(json["payload"].is_string());
+    assert!(json["signature"].is_string());
+    assert_eq!(json["key"], "runner-key");
+
+    let decoded: SignedWorkerRequest = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, envelope);
+    assert_eq!(decoded.open(&KEY).unwrap(), request());
+}
+
+#[test]
+fn tampering_is_rejected() {
+    let envelope = SignedWorkerRequest::seal(&request(), &KEY).unwrap();
+
+    let mut payload = envelope.clone();
+    payload.payload = serde_json::to_vec(&WorkerRequest {
+        worker_id: "other-worker".into(),
+        ..request()
+    })
+    .unwrap();
+    let err = payload.open(&KEY).unwrap_err();
+    assert_eq!(err.code, ErrorCode::Unauthenticated);
+
+    let mut algorithm = envelope.clone();
+    algorithm.algorithm = SignatureAlgorithm::Ed25519;
+    assert!(algorithm.verify(&KEY).is_err());
+
+    let other = TestKey {
+        key: "runner-key",
+        secret: b"different",
+    };
+    assert!(envelope.verify(&other).is_err());
+}
@@ -0,0 +1,44 @@
+use greentic_types::{ErrorCode, IdPolicy, define_id};
+
+define_id!(OrderId, "Identifier of a customer order.");
+define_id!(
+    SkuCode,
+    "Lowercase stock keeping unit code.",
+    IdPolicy::IDENTIFIER
+        .lowercase()
+        .with_extra_chars("-")
+        .with_max_len(8)
+);
+
+#[test]
+fn policy_is_enforced() {
+    let order = OrderId::new("order_42.v1").expect("valid id");
+    assert_eq!(order.to_string(), "order_42.v1");
+    assert_eq!(OrderId::new("").unwrap_err().code, ErrorCode::InvalidInput);
+    assert!(OrderId::new("order 42").is_err());
+
+    assert!(SkuCode::new("ab-12").is_ok());
+    assert!(SkuCode::new("AB-12").is_err());
+    assert!(SkuCode::new("ab_12").is_err());
+    let err = SkuCode::new("abcdefghi").unwrap_err();
+    assert!(err.message.contains("SkuCode"), "{}", err.message);
+    assert_eq!(SkuCode::POLICY.max_len, Some(8));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_rejects_invalid_ids() {
+    let sku: SkuCode = serde_json::from_str("\"ab-1\"").expect("deserialize");
+    assert_eq!(serde_json::to_string(&sku).unwrap(), "\"ab-1\"");
+    assert!(serde_json::from_str::<SkuCode>("\"AB-1\"").is_err());
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn schema_carries_policy() {
+    let schema = serde_json::to_value(schemars::schema_for!(SkuCode)).unwrap();
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["maxLength"], 8);
+    assert_eq!(schema["pattern"], "^[a-z0-9\\-]*$");
+    assert_eq!(schema["description"], "Lowercase stock keeping unit code.");
+}
@@ -1,8 +1,9 @@
 #![cfg(feature = "serde")]
 
 use greentic_types::{
-    ArtifactLocation, CacheInfo, ComponentDigest, ComponentStatus, DistributorEnvironmentId,
-    ResolveComponentRequest, ResolveComponentResponse, SignatureSummary, TenantCtx, TenantId,
+    ArtifactLocation, ArtifactMirror, ByteSize, CacheInfo, ComponentDigest, ComponentStatus,
+    CompressionCodec, DistributorEnvironmentId, ResolveComponentRequest, ResolveComponentResponse,
+    SignatureSummary, TenantCtx, TenantId, TransferHints,
 };
 use serde_json::json;
 
@@ -20,6 +21,7 @@ fn resolve_component_request_roundtrip() {
         pack_id: "pack.alpha".into(),
         component_id: "component.beta".into(),
         version: "1.2.3".into(),
+        accept_encodings: vec![CompressionCodec::Gzip, CompressionCodec::Zstd],
         extra: json!({"hint": "warm"}),
     };
 
@@ -38,6 +40,15 @@ fn resolve_component_response_roundtrip() {
         artifact: ArtifactLocation::FilePath {
             path: "/tmp/component.wasm".into(),
         },
+        transfer: Some(TransferHints {
+            compression: vec![CompressionCodec::Zstd],
+            chunk_size: Some(ByteSize::from_mib(8)),
+            resumable: true,
+            mirrors: vec![ArtifactMirror {
+                url: "https://mirror.example/component.wasm".into(),
+                priority: 1,
+            }],
+        }),
         signature: SignatureSummary {
             verified: true,
             signer: "sig-key-1".into(),
@@ -73,3 +84,48 @@ fn component_digest_sha256_like_check() {
         assert!(!digest.is_sha256_like(), "digest {bad} should be rejected");
     }
 }
+
+#[test]
+fn transfer_hints_negotiate_codec_and_order_mirrors() {
+    let hints: TransferHints = serde_json::from_value(json!({
+        "compression": ["brotli", "zstd", "gzip"],
+        "resumable": true,
+        "mirrors": [
+            {"url": "https://b.example/c.wasm", "priority": 20},
+            {"url": "https://a.example/c.wasm", "priority": 10},
+            {"url": "https://c.example/c.wasm"}
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(
+        hints.negotiate(&[CompressionCodec::Gzip, CompressionCodec::Zstd]),
+        CompressionCodec::Zstd
+    );
+    assert_eq!(hints.negotiate(&[]), CompressionCodec::Identity);
+
+    let order: Vec<&str> = hints
+        .mirrors_by_priority()
+        .into_iter()
+        .map(|mirror| mirror.url.as_str())
+        .collect();
+    assert_eq!(
+        order,
+        [
+            "https://c.example/c.wasm",
+            "https://a.example/c.wasm",
+            "https://b.example/c.wasm"
+        ]
+    );
+
+    let legacy: ResolveComponentRequest = serde_json::from_value(json!({
+        "tenant": sample_ctx(),
+        "environment_id": "env-1",
+        "pack_id": "pack.alpha",
+        "component_id": "component.beta",
+        "version": "1.2.3",
+        "extra": null
+    }))
+    .unwrap();
+    assert!(legacy.accept_encodings.is_empty());
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::{
+    EncryptedPayload, EncryptionAlgorithm, EncryptionKeyRef, ErrorCode, GResult, GreenticError,
+    PayloadDecryptor, PayloadEncryptor,
+};
+
+/// Toy cipher: XOR with the nonce, followed by a one-byte "tag" over the AAD.
+struct XorCipher;
+
+fn tag(aad: &[u8]) -> u8 {
+    aad.iter().fold(0u8, |acc, byte| acc.wrapping_add(*byte))
+}
+
+impl PayloadEncryptor for XorCipher {
+    fn algorithm(&self) -> EncryptionAlgorithm {
+        EncryptionAlgorithm::ChaCha20Poly1305
+    }
+
+    fn key(&self) -> EncryptionKeyRef {
+        EncryptionKeyRef::new("sessions.v1").unwrap()
+    }
+
+    fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> GResult<(Vec<u8>, Vec<u8>)> {
+        let nonce: Vec<u8> = (1..=12).collect();
+        let mut ciphertext: Vec<u8> = plaintext
+            .iter()
+            .zip(nonce.iter().cycle())
+            .map(|(byte, pad)| byte ^ pad)
+            .collect();
+        ciphertext.push(tag(aad));
+        Ok((nonce, ciphertext))
+    }
+}
+
+impl PayloadDecryptor for XorCipher {
+    fn decrypt(
+        &self,
+        _algorithm: &EncryptionAlgorithm,
+        _key: &EncryptionKeyRef,
+        nonce: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> GResult<Vec<u8>> {
+        let (body, expected) = ciphertext.split_at(ciphertext.len() - 1);
+        if expected[0] != tag(aad) {
+            return Err(GreenticError::new(
+                ErrorCode::Unauthenticated,
+                "authentication tag mismatch",
+            ));
+        }
+        Ok(body
+            .iter()
+            .zip(nonce.iter().cycle())
+            .map(|(byte, pad)| byte ^ pad)
+            .collect())
+    }
+}
+
+#[test]
+fn encrypted_payload_roundtrips_and_checks_aad() {
+    let aad = b"tenant-1/session-42";
+    let sealed = EncryptedPayload::seal(b"snapshot bytes", aad, &XorCipher).unwrap();
+    assert_eq!(sealed.key.as_str(), "sessions.v1");
+    assert!(sealed.aad_digest.is_some());
+
+    let json = serde_json::to_value(&sealed).unwrap();
+    assert_eq!(json["algorithm"], "chacha20_poly1305");
+    assert_eq!(json["nonce"], "AQIDBAUGBwgJCgsM");
+    let parsed: EncryptedPayload = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, sealed);
+
+    assert_eq!(parsed.open(aad, &XorCipher).unwrap(), b"snapshot bytes");
+    let err = parsed.open(b"tenant-2/session-42", &XorCipher).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+
+    let mut truncated = parsed.clone();
+    truncated.nonce.pop();
+    assert!(truncated.open(aad, &XorCipher).is_err());
+
+    let no_aad = EncryptedPayload::seal(b"artifact", b"", &XorCipher).unwrap();
+    assert!(no_aad.aad_digest.is_none());
+    assert_eq!(no_aad.open(b"", &XorCipher).unwrap(), b"artifact");
+}
@@ -1,7 +1,10 @@
 #![cfg(feature = "serde")]
 
 use chrono::{TimeZone, Utc};
-use greentic_types::{EventEnvelope, EventId, EventMetadata, TenantCtx};
+use greentic_types::{
+    ByteSize, DurationMs, EnvelopeLimitError, ErrorCode, EventBatch, EventEnvelope, EventId,
+    EventMetadata, GreenticError, HashedPayload, Limits, OrderingKind, TenantCtx,
+};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::json;
@@ -29,6 +32,7 @@ fn minimal_event_envelope_roundtrip() {
         time: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
         correlation_id: None,
         payload: json!({"status": "ok"}),
+        hashed_payload: None,
         metadata: EventMetadata::new(),
     };
 
@@ -54,8 +58,126 @@ fn full_event_envelope_roundtrip_preserves_metadata() {
         time: Utc.with_ymd_and_hms(2025, 5, 6, 7, 8, 9).unwrap(),
         correlation_id: Some("corr-9".into()),
         payload: json!({"status": "failed", "attempt": 2}),
+        hashed_payload: Some(HashedPayload::new(b"build log".to_vec())),
         metadata,
     };
 
     assert_roundtrip(&envelope);
 }
+
+fn batch_event(id: &str, subject: &str) -> EventEnvelope {
+    EventEnvelope {
+        id: EventId::new(id).unwrap(),
+        topic: "greentic.events.test".into(),
+        r#type: "com.greentic.events.test.v1".into(),
+        source: "urn:greentic:test".into(),
+        tenant: TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap()),
+        subject: Some(subject.into()),
+        time: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        correlation_id: None,
+        payload: json!({}),
+        hashed_payload: None,
+        metadata: EventMetadata::new(),
+    }
+}
+
+fn event_ids(batch: &EventBatch) -> Vec<&str> {
+    batch.events.iter().map(|event| event.id.as_str()).collect()
+}
+
+#[test]
+fn event_batch_roundtrip_and_split() {
+    let deadline = Utc.with_ymd_and_hms(2024, 1, 2, 3, 5, 0).unwrap();
+    let mut batch = EventBatch::new(EventId::new("batch-1").unwrap(), OrderingKind::Global)
+        .with_partition_key("orders")
+        .with_ack_deadline(deadline);
+    for id in ["e1", "e2", "e3", "e4", "e5"] {
+        batch.push(batch_event(id, "a"));
+    }
+    assert_roundtrip(&batch);
+    assert!(batch.is_ack_overdue(deadline + chrono::Duration::seconds(1)));
+
+    let chunks = batch.clone().split(2).unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[2].id.as_str(), "batch-1.2");
+    assert_eq!(event_ids(&chunks[0]), ["e1", "e2"]);
+    assert!(
+        chunks
+            .iter()
+            .all(|chunk| chunk.partition_key.as_deref() == Some("orders"))
+    );
+    assert!(batch.clone().split(0).is_err());
+
+    let merged = EventBatch::merge(EventId::new("batch-1").unwrap(), chunks).unwrap();
+    assert_eq!(merged, batch);
+}
+
+#[test]
+fn event_batch_partition_respects_ordering() {
+    let mut batch = EventBatch::new(EventId::new("batch-2").unwrap(), OrderingKind::PerKey);
+    for (id, subject) in [("e1", "a"), ("e2", "b"), ("e3", "a"), ("e4", "b")] {
+        batch.push(batch_event(id, subject));
+    }
+    let parts = batch
+        .clone()
+        .partition_by(|event| event.subject.clone().unwrap_or_default())
+        .unwrap();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].partition_key.as_deref(), Some("a"));
+    assert_eq!(event_ids(&parts[0]), ["e1", "e3"]);
+    assert_eq!(event_ids(&parts[1]), ["e2", "e4"]);
+
+    let merged = EventBatch::merge(EventId::new("merged").unwrap(), parts).unwrap();
+    assert_eq!(merged.partition_key, None);
+    assert_eq!(merged.len(), 4);
+
+    let mut global = batch.clone();
+    global.ordering = OrderingKind::Global;
+    assert!(global.clone().partition_by(|_| String::new()).is_err());
+    let other = global.clone().with_partition_key("other");
+    assert!(EventBatch::merge(EventId::new("m").unwrap(), vec![global, other]).is_err());
+    assert!(
+        EventBatch::merge(
+            EventId::new("m").unwrap(),
+            vec![batch.clone(), {
+                let mut none = batch;
+                none.ordering = OrderingKind::None;
+                none
+            }]
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn event_envelope_check_limits_rejects_oversized_payloads() {
+    let mut event = batch_event("evt-big", "repo:a");
+    event.payload = json!({"status": "ok"});
+    event.hashed_payload = Some(HashedPayload::new(vec![0u8; 100]));
+    assert_eq!(event.compute_payload_size(), ByteSize::from_bytes(15 + 100));
+
+    let limits = Limits::new(ByteSize::from_mib(64), DurationMs::from_secs(5));
+    assert!(event.check_limits(&limits).is_ok(), "no ceiling configured");
+    assert!(
+        event
+            .check_limits(
+                &limits
+                    .clone()
+                    .with_max_payload_bytes(ByteSize::from_bytes(115))
+            )
+            .is_ok()
+    );
+
+    let err = event
+        .check_limits(&limits.with_max_payload_bytes(ByteSize::from_bytes(114)))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        EnvelopeLimitError::PayloadTooLarge {
+            size: ByteSize::from_bytes(115),
+            limit: ByteSize::from_bytes(114),
+        }
+    );
+    let err: GreenticError = err.into();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+}
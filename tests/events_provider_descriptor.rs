@@ -1,7 +1,9 @@
 #![cfg(feature = "serde")]
 
 use greentic_types::{
-    EventProviderDescriptor, EventProviderKind, OrderingKind, ReliabilityKind, TransportKind,
+    AuthUserRefV1, DurationMs, EventProviderDescriptor, EventProviderKind, OrderingKind,
+    ReliabilityKind, SecretKey, TenantCtx, TransportKind, UnixMillis, WebhookExpiryPolicy,
+    WebhookSubscription,
 };
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -36,3 +38,58 @@ fn descriptor_with_tags_roundtrip() {
 
     assert_roundtrip(&descriptor);
 }
+
+#[test]
+fn webhook_subscription_converts_to_v1_dtos() {
+    let mut tenant = TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap());
+    tenant = tenant.with_team(Some("team-a".parse().unwrap()));
+    let subscription = WebhookSubscription::new(
+        "hook-1".parse().unwrap(),
+        tenant,
+        "graph",
+        "/me/mailFolders('inbox')/messages",
+        "https://hooks.example.com/graph".parse().unwrap(),
+    )
+    .with_change_type("created")
+    .with_secret(SecretKey::new("graph/client-state").unwrap())
+    .with_expiry(
+        WebhookExpiryPolicy::Sliding {
+            lifetime: DurationMs::from_secs(90 * 60 + 1),
+        },
+        DurationMs::from_mins(10),
+    );
+    assert_roundtrip(&subscription);
+
+    let user = AuthUserRefV1 {
+        user_id: "user-1".into(),
+        token_key: "token-1".into(),
+        ..AuthUserRefV1::default()
+    };
+    let ensure = subscription.to_ensure_in(Some("s3cret".into()), user.clone());
+    assert_eq!(ensure.v, 1);
+    assert_eq!(ensure.provider, "graph");
+    assert_eq!(ensure.tenant_hint.as_deref(), Some("tenant-1"));
+    assert_eq!(ensure.team_hint.as_deref(), Some("team-a"));
+    assert_eq!(ensure.binding_id.as_deref(), Some("hook-1"));
+    assert_eq!(ensure.change_types, vec!["created".to_string()]);
+    assert_eq!(ensure.notification_url, "https://hooks.example.com/graph");
+    assert_eq!(ensure.expiration_minutes, Some(91));
+    assert_eq!(ensure.expiration_target_unix_ms, None);
+    assert_eq!(ensure.client_state.as_deref(), Some("s3cret"));
+
+    let fixed = subscription.clone().with_expiry(
+        WebhookExpiryPolicy::Fixed {
+            at: UnixMillis::from_millis(1_700_000_000_000),
+        },
+        DurationMs::from_mins(10),
+    );
+    let renew = fixed.to_renew_in("provider-sub-9", user);
+    assert_eq!(renew.subscription_id, "provider-sub-9");
+    assert_eq!(renew.expiration_minutes, None);
+    assert_eq!(renew.expiration_target_unix_ms, Some(1_700_000_000_000));
+
+    let expires_at = UnixMillis::from_millis(3_600_000);
+    assert!(!subscription.renewal_due(expires_at, UnixMillis::from_millis(2_999_999)));
+    assert!(subscription.renewal_due(expires_at, UnixMillis::from_millis(3_000_000)));
+    assert!(subscription.renewal_due(expires_at, UnixMillis::from_millis(4_000_000)));
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use greentic_types::{
+    ComponentCapabilities, ComponentManifest, ComponentOperation, ComponentProfiles, Flow,
+    FlowComponentRef, FlowKind, FlowMetadata, InputMapping, Node, OutputMapping, ResourceHints,
+    Routing, Severity, TelemetryHints, validate_flow_bindings,
+};
+use indexmap::IndexMap;
+use semver::Version;
+use serde_json::{Value, json};
+
+fn node(id: &str, component: &str, operation: Option<&str>, mapping: Value) -> Node {
+    Node {
+        id: id.parse().unwrap(),
+        component: FlowComponentRef {
+            id: component.parse().unwrap(),
+            pack_alias: None,
+            operation: operation.map(Into::into),
+        },
+        input: InputMapping { mapping },
+        output: OutputMapping {
+            mapping: Value::Null,
+        },
+        routing: Routing::End,
+        telemetry: TelemetryHints::default(),
+        capabilities_override: None,
+    }
+}
+
+fn flow(nodes: Vec<Node>) -> Flow {
+    let mut map: IndexMap<_, _, greentic_types::flow::FlowHasher> = IndexMap::default();
+    for node in nodes {
+        map.insert(node.id.clone(), node);
+    }
+    Flow {
+        schema_version: "flow-v1".into(),
+        id: "flow.bindings".parse().unwrap(),
+        kind: FlowKind::Messaging,
+        entrypoints: BTreeMap::new(),
+        nodes: map,
+        metadata: FlowMetadata::default(),
+    }
+}
+
+fn component(id: &str, operations: Vec<ComponentOperation>) -> ComponentManifest {
+    ComponentManifest {
+        id: id.parse().unwrap(),
+        version: Version::parse("1.0.0").unwrap(),
+        supports: vec![FlowKind::Messaging],
+        world: "test:component@1.0.0".into(),
+        profiles: ComponentProfiles::default(),
+        capabilities: ComponentCapabilities::default(),
+        configurators: None,
+        operations,
+        config_schema: None,
+        resources: ResourceHints::default(),
+        targets: Vec::new(),
+        dev_flows: BTreeMap::new(),
+    }
+}
+
+fn operation(name: &str, input_schema: Value) -> ComponentOperation {
+    ComponentOperation {
+        name: name.into(),
+        input_schema,
+        output_schema: Value::Null,
+    }
+}
+
+#[test]
+fn validate_flow_bindings_reports_operations_and_schema_mismatches() {
+    let send = operation(
+        "send",
+        json!({
+            "type": "object",
+            "required": ["text", "channel"],
+            "additionalProperties": false,
+            "properties": {
+                "text": {"type": "string"},
+                "channel": {"enum": ["email", "chat"]},
+                "retries": {"type": "integer"},
+                "target": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {"id": {"type": "string"}}
+                },
+                "tags": {"type": "array", "items": {"type": "string"}}
+            }
+        }),
+    );
+    let components = vec![component(
+        "component.notify",
+        vec![send, operation("ping", Value::Null)],
+    )];
+    let flow = flow(vec![
+        node(
+            "ok",
+            "component.notify",
+            Some("send"),
+            json!({"text": "{{input.text}}", "retries": 3, "tags": ["a"]}),
+        ),
+        node(
+            "bad",
+            "component.notify",
+            Some("send"),
+            json!({
+                "channel": "fax",
+                "retries": 1.5,
+                "target": {},
+                "tags": ["a", 2],
+                "extra": true
+            }),
+        ),
+        node("typo", "component.notify", Some("sned"), Value::Null),
+        node("implicit", "component.notify", None, json!({"text": 1})),
+        node("external", "component.other", Some("run"), Value::Null),
+    ]);
+
+    let diagnostics = validate_flow_bindings(&flow, &components);
+    let found: Vec<(&str, &str)> = diagnostics
+        .iter()
+        .map(|d| (d.code.as_str(), d.path.as_deref().unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (
+                "FLOW_BINDING_INPUT_SCHEMA_MISMATCH",
+                "nodes.bad.input.mapping.channel"
+            ),
+            (
+                "FLOW_BINDING_INPUT_SCHEMA_MISMATCH",
+                "nodes.bad.input.mapping.extra"
+            ),
+            (
+                "FLOW_BINDING_INPUT_SCHEMA_MISMATCH",
+                "nodes.bad.input.mapping.retries"
+            ),
+            (
+                "FLOW_BINDING_INPUT_SCHEMA_MISMATCH",
+                "nodes.bad.input.mapping.tags.1"
+            ),
+            (
+                "FLOW_BINDING_INPUT_SCHEMA_MISMATCH",
+                "nodes.bad.input.mapping.target"
+            ),
+            (
+                "FLOW_BINDING_OPERATION_MISSING",
+                "nodes.typo.component.operation"
+            ),
+            (
+                "FLOW_BINDING_COMPONENT_UNKNOWN",
+                "nodes.external.component.id"
+            ),
+        ]
+    );
+    assert!(
+        diagnostics[5]
+            .hint
+            .as_deref()
+            .unwrap()
+            .contains("send, ping")
+    );
+    assert_eq!(diagnostics[6].severity, Severity::Warn);
+}
+
+#[test]
+fn validate_flow_bindings_skips_nodes_without_mapping() {
+    let send = operation(
+        "send",
+        json!({"type": "object", "properties": {"text": {"type": "string"}}}),
+    );
+    let components = vec![component("component.notify", vec![send])];
+    let flow = flow(vec![node(
+        "passthrough",
+        "component.notify",
+        Some("send"),
+        Value::Null,
+    )]);
+
+    assert!(validate_flow_bindings(&flow, &components).is_empty());
+}
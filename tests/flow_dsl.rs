@@ -0,0 +1,144 @@
+#![cfg(feature = "flow-dsl")]
+
+use greentic_types::{ErrorCode, FlowKind, NodeId, Routing, flow_to_dsl, parse_flow_dsl};
+use serde_json::json;
+
+const SOURCE: &str = r#"
+# Support triage demo
+flow support-triage messaging
+title "Support triage"
+tags ["support", "demo"]
+entry default {"channel": "teams"}
+
+node classify = ai.classifier#classify -> [billing: billing, "needs human": "end", _: answer]
+  in {"text": "{{input.text}}"}
+node billing = shared/crm.lookup -> answer
+  out {"account": "$.account"}
+node answer = templates.reply -> reply
+node end = handoff.queue -> custom {"queue": "tier-2"}
+"#;
+
+fn id(value: &str) -> NodeId {
+    value.parse().unwrap()
+}
+
+#[test]
+fn parses_nodes_routing_and_metadata() {
+    let flow = parse_flow_dsl(SOURCE).unwrap();
+    assert_eq!(flow.id.as_str(), "support-triage");
+    assert_eq!(flow.kind, FlowKind::Messaging);
+    assert_eq!(flow.schema_version, "flow-v1");
+    assert_eq!(flow.metadata.title.as_deref(), Some("Support triage"));
+    assert!(flow.metadata.tags.contains("demo"));
+    assert_eq!(flow.entrypoints["default"], json!({"channel": "teams"}));
+
+    let order: Vec<_> = flow.nodes.keys().map(NodeId::as_str).collect();
+    assert_eq!(order, ["classify", "billing", "answer", "end"]);
+
+    let classify = &flow.nodes[&id("classify")];
+    assert_eq!(classify.component.id.as_str(), "ai.classifier");
+    assert_eq!(classify.component.operation.as_deref(), Some("classify"));
+    assert_eq!(classify.input.mapping, json!({"text": "{{input.text}}"}));
+    match &classify.routing {
+        Routing::Branch { on_status, default } => {
+            assert_eq!(on_status["billing"], id("billing"));
+            assert_eq!(on_status["needs human"], id("end"));
+            assert_eq!(default.as_ref(), Some(&id("answer")));
+        }
+        other => panic!("unexpected routing {other:?}"),
+    }
+
+    let billing = &flow.nodes[&id("billing")];
+    assert_eq!(billing.component.pack_alias.as_deref(), Some("shared"));
+    assert_eq!(
+        billing.routing,
+        Routing::Next {
+            node_id: id("answer")
+        }
+    );
+    assert_eq!(flow.nodes[&id("answer")].routing, Routing::Reply);
+    assert_eq!(
+        flow.nodes[&id("end")].routing,
+        Routing::Custom(json!({"queue": "tier-2"}))
+    );
+}
+
+#[test]
+fn serializer_round_trips() {
+    let flow = parse_flow_dsl(SOURCE).unwrap();
+    let text = flow_to_dsl(&flow).unwrap();
+    assert!(
+        text.contains(r#"node billing = shared/crm.lookup -> answer"#),
+        "{text}"
+    );
+    assert_eq!(parse_flow_dsl(&text).unwrap(), flow);
+
+    let mut next_to_keyword = flow.clone();
+    next_to_keyword.nodes[&id("answer")].routing = Routing::Next { node_id: id("end") };
+    let text = flow_to_dsl(&next_to_keyword).unwrap();
+    assert!(
+        text.contains(r#"node answer = templates.reply -> "end""#),
+        "{text}"
+    );
+    assert_eq!(parse_flow_dsl(&text).unwrap(), next_to_keyword);
+
+    let mut traced = flow;
+    traced.nodes[&id("answer")].telemetry.span_name = Some("reply".into());
+    let err = flow_to_dsl(&traced).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+}
+
+#[test]
+fn errors_carry_line_and_column_spans() {
+    let source = "flow demo messaging\nnode a = comp.a -> missing\nnode b = comp b -> end\n  in {\"broken\": }\nnode a = comp.c -> end\n";
+    let diagnostics = parse_flow_dsl(source).unwrap_err();
+    let summary: Vec<_> = diagnostics
+        .iter()
+        .map(|diag| (diag.code.as_str(), diag.path.as_deref().unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("FLOW_DSL_SYNTAX", "3:15"),
+            ("FLOW_DSL_JSON", "4:17"),
+            ("FLOW_DSL_DUPLICATE_NODE", "5:6"),
+            ("FLOW_DSL_UNKNOWN_NODE", "2:20"),
+        ]
+    );
+    assert_eq!(
+        diagnostics[3].data,
+        json!({"line": 2, "column": 20, "end_column": 27})
+    );
+
+    let flow = parse_flow_dsl("flow demo acme.batch\nnode a = comp.a -> end\n").unwrap();
+    assert_eq!(flow.kind, FlowKind::custom("acme.batch").unwrap());
+    let diagnostics = parse_flow_dsl("flow demo batch\nnode a = comp.a -> end\n").unwrap_err();
+    let summary: Vec<_> = diagnostics
+        .iter()
+        .map(|diag| (diag.code.as_str(), diag.path.as_deref().unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("FLOW_DSL_MISSING_HEADER", "1:1"),
+            ("FLOW_DSL_SYNTAX", "1:11")
+        ]
+    );
+
+    let diagnostics = parse_flow_dsl("node a = comp.a -> end\n  in {\"x\": }").unwrap_err();
+    let codes: Vec<_> = diagnostics.iter().map(|diag| diag.code.as_str()).collect();
+    assert_eq!(codes, ["FLOW_DSL_MISSING_HEADER", "FLOW_DSL_JSON"]);
+    assert_eq!(diagnostics[1].path.as_deref(), Some("2:12"));
+}
+
+#[test]
+fn empty_alias_and_operation_segments_are_rejected() {
+    for (component, message) in [("/comp.a", "pack alias"), ("comp.a#", "operation")] {
+        let source = format!("flow demo messaging\nnode a = {component} -> end\n");
+        let diagnostics = parse_flow_dsl(&source).unwrap_err();
+        assert_eq!(diagnostics.len(), 1, "{component}");
+        assert_eq!(diagnostics[0].code, "FLOW_DSL_SYNTAX");
+        assert_eq!(diagnostics[0].path.as_deref(), Some("2:10"));
+        assert!(diagnostics[0].message.contains(message), "{component}");
+    }
+}
@@ -1,9 +1,9 @@
 use std::collections::BTreeMap;
 
 use greentic_types::{
-    ComponentCapabilities, ComponentManifest, ComponentOperation, ComponentProfiles, Flow,
-    FlowComponentRef, FlowId, FlowKind, FlowMetadata, InputMapping, Node, OutputMapping,
-    ResourceHints, Routing, TelemetryHints,
+    CUSTOM_FLOW_KIND_MAX_LEN, ComponentCapabilities, ComponentManifest, ComponentOperation,
+    ComponentProfiles, Flow, FlowComponentRef, FlowId, FlowKind, FlowKindError, FlowMetadata,
+    InputMapping, Node, OutputMapping, Ratio, ResourceHints, Routing, TelemetryHints,
 };
 use indexmap::IndexMap;
 use semver::Version;
@@ -28,6 +28,7 @@ fn flow_ingress_respects_insertion_order() {
                 node_id: "second".parse().unwrap(),
             },
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
     nodes.insert(
@@ -43,6 +44,7 @@ fn flow_ingress_respects_insertion_order() {
             },
             routing: Routing::End,
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
 
@@ -78,6 +80,7 @@ fn flow_json_roundtrips_with_routing_variants() {
                 default: Some("end".parse().unwrap()),
             },
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
     nodes.insert(
@@ -93,6 +96,7 @@ fn flow_json_roundtrips_with_routing_variants() {
             },
             routing: Routing::Reply,
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
     nodes.insert(
@@ -108,6 +112,7 @@ fn flow_json_roundtrips_with_routing_variants() {
             },
             routing: Routing::End,
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
 
@@ -125,6 +130,69 @@ fn flow_json_roundtrips_with_routing_variants() {
     assert_eq!(decoded, flow);
 }
 
+#[test]
+fn node_telemetry_layers_over_flow_defaults() {
+    let mut nodes: IndexMap<_, _, greentic_types::flow::FlowHasher> = IndexMap::default();
+    nodes.insert(
+        "noisy".parse().unwrap(),
+        Node {
+            id: "noisy".parse().unwrap(),
+            component: component_ref("component.noisy"),
+            input: InputMapping {
+                mapping: Value::Null,
+            },
+            output: OutputMapping {
+                mapping: Value::Null,
+            },
+            routing: Routing::End,
+            telemetry: TelemetryHints {
+                span_name: Some("noisy.poll".into()),
+                attributes: BTreeMap::from([("node".to_string(), "noisy".to_string())]),
+                sample_ratio: Some(Ratio::new(0.01).unwrap()),
+                ..TelemetryHints::default()
+            },
+            capabilities_override: None,
+        },
+    );
+
+    let flow = Flow {
+        schema_version: "flow-v1".into(),
+        id: "flow.noisy".parse().unwrap(),
+        kind: FlowKind::Event,
+        entrypoints: BTreeMap::new(),
+        nodes,
+        metadata: FlowMetadata {
+            telemetry: Some(TelemetryHints {
+                span_name: Some("flow.noisy".into()),
+                attributes: BTreeMap::from([
+                    ("flow".to_string(), "noisy".to_string()),
+                    ("secret".to_string(), "hidden".to_string()),
+                ]),
+                sample_ratio: Some(Ratio::new(0.1).unwrap()),
+                attribute_allowlist: vec!["flow".into(), "node".into()],
+                ..TelemetryHints::default()
+            }),
+            ..FlowMetadata::default()
+        },
+    };
+
+    let hints = flow
+        .node_telemetry(&"noisy".parse().unwrap())
+        .expect("node exists");
+    assert_eq!(hints.span_name.as_deref(), Some("noisy.poll"));
+    assert_eq!(hints.sample_ratio.map(Ratio::get), Some(0.01));
+    assert_eq!(hints.sample_ratio.map(Ratio::basis_points), Some(100));
+    assert_eq!(hints.attributes.len(), 3);
+    let exported = hints.exported_attributes();
+    assert_eq!(exported.len(), 2);
+    assert!(!exported.contains_key("secret"));
+    assert!(flow.node_telemetry(&"missing".parse().unwrap()).is_none());
+
+    let encoded = serde_json::to_string(&flow).expect("serialize");
+    let decoded: Flow = serde_json::from_str(&encoded).expect("deserialize");
+    assert_eq!(decoded, flow);
+}
+
 #[test]
 fn component_manifest_defaults_extend() {
     let manifest = ComponentManifest {
@@ -145,9 +213,13 @@ fn component_manifest_defaults_extend() {
         }],
         config_schema: None,
         resources: ResourceHints::default(),
+        targets: Vec::new(),
         dev_flows: BTreeMap::new(),
     };
 
+    assert!(manifest.supports_kind(FlowKind::Messaging));
+    assert!(!manifest.accepts_kind(&FlowKind::custom("acme.batch").unwrap()));
+
     let default = manifest.select_profile(None).expect("default");
     assert_eq!(default, Some("default"));
 
@@ -163,6 +235,56 @@ fn component_manifest_defaults_extend() {
     ));
 }
 
+#[test]
+fn custom_flow_kinds_are_namespaced_and_compatible() {
+    let batch = FlowKind::custom("acme.batch").expect("custom kind");
+    assert_eq!(batch.namespace(), Some("acme"));
+    assert!(!batch.is_builtin());
+    assert_eq!("job".parse::<FlowKind>().unwrap(), FlowKind::Job);
+    assert_eq!("acme.batch".parse::<FlowKind>().unwrap(), batch);
+
+    for invalid in [
+        "batch",
+        "Acme.batch",
+        "acme..batch",
+        "acme.9lives",
+        ".batch",
+    ] {
+        assert!(
+            matches!(FlowKind::parse(invalid), Err(FlowKindError::Invalid(_))),
+            "{invalid}"
+        );
+    }
+    assert_eq!(
+        FlowKind::custom("greentic.workflow"),
+        Err(FlowKindError::ReservedNamespace("greentic.workflow".into()))
+    );
+    let longest = format!("acme.{}", "x".repeat(CUSTOM_FLOW_KIND_MAX_LEN - 5));
+    assert_eq!(
+        FlowKind::custom(longest.as_str()).unwrap().as_str(),
+        longest
+    );
+    assert!(matches!(
+        FlowKind::custom(format!("{longest}x")),
+        Err(FlowKindError::TooLong(_))
+    ));
+
+    let nightly = FlowKind::custom("acme.batch.nightly").unwrap();
+    assert!(batch.accepts(&nightly));
+    assert!(!nightly.accepts(&batch));
+    assert!(!batch.accepts(&FlowKind::custom("acme.batchx").unwrap()));
+    assert!(!FlowKind::Job.accepts(&batch));
+
+    let json = serde_json::to_value(vec![FlowKind::ComponentConfig, nightly]).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!(["component_config", "acme.batch.nightly"])
+    );
+    let back: Vec<FlowKind> = serde_json::from_value(json).unwrap();
+    assert_eq!(back[1], nightly);
+    assert!(serde_json::from_value::<FlowKind>(serde_json::json!("unknown")).is_err());
+}
+
 fn component_ref(id: &str) -> FlowComponentRef {
     FlowComponentRef {
         id: id.parse().unwrap(),
@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use greentic_types::{
+    ByteSize, ComponentId, DurationMs, Flow, FlowComponentRef, FlowKind, FlowMetadata,
+    FlowMetricsThresholds, InputMapping, Limits, Node, NodeId, OutputMapping, ResourceHints,
+    Routing, Severity, TelemetryHints, estimate_flow_budget,
+};
+use indexmap::IndexMap;
+use serde_json::{Value, json};
+
+fn id(value: &str) -> NodeId {
+    value.parse().unwrap()
+}
+
+fn node(name: &str, component: &str, routing: Routing) -> Node {
+    Node {
+        id: id(name),
+        component: FlowComponentRef {
+            id: component.parse().unwrap(),
+            pack_alias: None,
+            operation: None,
+        },
+        input: InputMapping {
+            mapping: Value::Null,
+        },
+        output: OutputMapping {
+            mapping: Value::Null,
+        },
+        routing,
+        telemetry: TelemetryHints::default(),
+        capabilities_override: None,
+    }
+}
+
+fn flow(nodes: Vec<Node>) -> Flow {
+    let mut map: IndexMap<_, _, greentic_types::flow::FlowHasher> = IndexMap::default();
+    for node in nodes {
+        map.insert(node.id.clone(), node);
+    }
+    Flow {
+        schema_version: "flow-v1".into(),
+        id: "flow.metrics".parse().unwrap(),
+        kind: FlowKind::Messaging,
+        entrypoints: BTreeMap::new(),
+        nodes: map,
+        metadata: FlowMetadata::default(),
+    }
+}
+
+fn sample() -> Flow {
+    flow(vec![
+        node(
+            "triage",
+            "router",
+            Routing::Branch {
+                on_status: BTreeMap::from([
+                    ("billing".into(), id("billing")),
+                    ("support".into(), id("support")),
+                    ("fallback".into(), id("reply")),
+                ]),
+                default: Some(id("reply")),
+            },
+        ),
+        node(
+            "billing",
+            "lookup",
+            Routing::Next {
+                node_id: id("reply"),
+            },
+        ),
+        node(
+            "support",
+            "lookup",
+            Routing::Next {
+                node_id: id("triage"),
+            },
+        ),
+        node("reply", "templates", Routing::Reply),
+        node("orphan", "lookup", Routing::End),
+    ])
+}
+
+#[test]
+fn metrics_measure_routing_graph() {
+    let metrics = sample().metrics();
+    assert_eq!(metrics.node_count, 5);
+    assert_eq!(metrics.max_depth, 2);
+    assert_eq!(metrics.max_branching_factor, 3);
+    assert_eq!(metrics.component_fan_in[&"lookup".parse().unwrap()], 3);
+    assert_eq!(metrics.max_component_fan_in(), 3);
+    assert_eq!(metrics.unreachable, vec![id("orphan")]);
+
+    let chain = flow(vec![
+        node("a", "step", Routing::Next { node_id: id("b") }),
+        node("b", "step", Routing::Next { node_id: id("c") }),
+        node("c", "step", Routing::Custom(json!({"queue": "x"}))),
+    ]);
+    assert_eq!(chain.metrics().max_depth, 3);
+    assert_eq!(flow(Vec::new()).metrics().max_depth, 0);
+}
+
+#[test]
+fn metrics_warn_above_thresholds() {
+    let flow = sample();
+    let metrics = flow.metrics();
+    assert!(
+        metrics
+            .check(&flow.id, &FlowMetricsThresholds::default())
+            .is_empty()
+    );
+
+    let strict = FlowMetricsThresholds {
+        max_nodes: 4,
+        max_depth: 2,
+        max_branching_factor: 2,
+        max_component_fan_in: 2,
+    };
+    let diagnostics = metrics.check(&flow.id, &strict);
+    let codes: Vec<_> = diagnostics.iter().map(|diag| diag.code.as_str()).collect();
+    assert_eq!(
+        codes,
+        [
+            "FLOW_METRICS_NODE_COUNT_EXCEEDED",
+            "FLOW_METRICS_BRANCHING_EXCEEDED",
+            "FLOW_METRICS_COMPONENT_FAN_IN_EXCEEDED",
+        ]
+    );
+    assert!(
+        diagnostics
+            .iter()
+            .all(|diag| diag.severity == Severity::Warn)
+    );
+    assert_eq!(
+        diagnostics[2].path.as_deref(),
+        Some("flows.flow.metrics.components.lookup")
+    );
+    assert_eq!(diagnostics[0].data, json!({"value": 5, "threshold": 4}));
+}
+
+fn hints(memory_mib: u64, latency_ms: u64, fuel: u64) -> ResourceHints {
+    ResourceHints {
+        memory: Some(ByteSize::from_mib(memory_mib)),
+        average_latency: Some(DurationMs::from_millis(latency_ms)),
+        fuel: Some(fuel),
+        ..ResourceHints::default()
+    }
+}
+
+#[test]
+fn budget_follows_worst_branch() {
+    let routed = sample();
+    let components: BTreeMap<ComponentId, ResourceHints> = BTreeMap::from([
+        ("router".parse().unwrap(), hints(16, 5, 100)),
+        ("lookup".parse().unwrap(), hints(64, 200, 10)),
+    ]);
+    let budget = estimate_flow_budget(&routed, &components);
+    // triage -> billing -> reply is the slowest acyclic path; support loops back to triage.
+    assert_eq!(budget.memory, ByteSize::from_mib(80));
+    assert_eq!(budget.wall_time, DurationMs::from_millis(205));
+    assert_eq!(budget.fuel, 110);
+    assert_eq!(
+        budget.critical_path,
+        vec![id("triage"), id("billing"), id("reply")]
+    );
+    assert_eq!(budget.missing_hints, vec![id("reply")]);
+    assert!(budget.cyclic);
+    assert!(!budget.is_exact());
+
+    let json = serde_json::to_value(&budget).unwrap();
+    assert_eq!(json["memory_bytes"], 80 * 1024 * 1024);
+    assert_eq!(json["wall_time_ms"], 205);
+
+    let roomy = Limits::new(ByteSize::from_mib(128), DurationMs::from_secs(1));
+    let codes: Vec<_> = budget
+        .check(&routed.id, &roomy)
+        .into_iter()
+        .map(|diag| (diag.code, diag.severity))
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            ("FLOW_BUDGET_MISSING_HINTS".to_string(), Severity::Warn),
+            ("FLOW_BUDGET_CYCLIC".to_string(), Severity::Warn),
+        ]
+    );
+
+    let mut tight = Limits::new(ByteSize::from_mib(64), DurationMs::from_millis(100));
+    tight.fuel = Some(50);
+    let diagnostics = budget.check(&routed.id, &tight);
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .filter(|diag| diag.severity == Severity::Error)
+        .map(|diag| diag.code.as_str())
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            "FLOW_BUDGET_MEMORY_EXCEEDED",
+            "FLOW_BUDGET_WALL_TIME_EXCEEDED",
+            "FLOW_BUDGET_FUEL_EXCEEDED",
+        ]
+    );
+    assert_eq!(diagnostics[2].data, json!({"estimate": 110, "limit": 50}));
+    assert_eq!(budget.check(&routed.id, &Limits::default()).len(), 2);
+
+    let empty = estimate_flow_budget(&flow(Vec::new()), &components);
+    assert!(empty.is_exact());
+    assert_eq!(empty.memory, ByteSize::ZERO);
+}
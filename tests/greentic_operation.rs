@@ -0,0 +1,49 @@
+#![cfg(feature = "macros")]
+
+use greentic_types::{GResult, greentic_operation};
+use schemars::JsonSchema;
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Greeting {
+    name: String,
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Reply {
+    text: String,
+}
+
+#[greentic_operation(name = "greet")]
+fn handle(input: &Greeting) -> GResult<Reply> {
+    Ok(Reply {
+        text: format!("hello {}", input.name),
+    })
+}
+
+struct Component;
+
+#[allow(dead_code)]
+impl Component {
+    #[greentic_operation]
+    async fn ping(&self) {}
+}
+
+#[test]
+fn operation_contract_follows_signature() {
+    let operation = handle_operation();
+    assert_eq!(operation.name, "greet");
+    assert_eq!(operation.input_schema["title"], "Greeting");
+    assert_eq!(operation.input_schema["required"][0], "name");
+    assert_eq!(operation.output_schema["title"], "Reply");
+    assert_eq!(
+        handle(&Greeting { name: "ada".into() }).unwrap().text,
+        "hello ada"
+    );
+
+    let ping = Component::ping_operation();
+    assert_eq!(ping.name, "ping");
+    assert_eq!(ping.input_schema["type"], "null");
+    assert_eq!(ping.output_schema, ping.input_schema);
+}
@@ -0,0 +1,65 @@
+use greentic_types::{ErrorCode, HashAlgorithm, HashDigest, HashedPayload, PayloadCompression};
+
+#[test]
+fn verify_detects_tampering() {
+    let payload = HashedPayload::new(b"hello".to_vec());
+    assert_eq!(payload.digest, HashDigest::blake3_of(b"hello"));
+    payload.verify().unwrap();
+
+    let mut tampered = payload.clone();
+    tampered.bytes[0] = b'j';
+    assert_eq!(tampered.verify().unwrap_err().code, ErrorCode::InvalidInput);
+
+    let foreign = HashedPayload {
+        digest: HashDigest::new(HashAlgorithm::Other("sha256".into()), "00ff").unwrap(),
+        ..payload
+    };
+    assert_eq!(foreign.verify().unwrap_err().code, ErrorCode::Unavailable);
+}
+
+#[test]
+fn same_content_compares_digest_and_compression() {
+    let plain = HashedPayload::new(b"body".to_vec());
+    let copy = HashedPayload {
+        digest: HashDigest::blake3(plain.digest.hex.to_uppercase()).unwrap(),
+        ..plain.clone()
+    };
+    assert!(plain.same_content(&copy));
+    copy.verify().unwrap();
+
+    let gzip = HashedPayload::compressed(b"body".to_vec(), PayloadCompression::Gzip);
+    assert!(!plain.same_content(&gzip));
+    assert!(!plain.same_content(&HashedPayload::new(b"other".to_vec())));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serializes_bytes_as_base64() {
+    let payload = HashedPayload::compressed(b"hi".to_vec(), PayloadCompression::Zstd);
+    let value = serde_json::to_value(&payload).unwrap();
+    assert_eq!(value["bytes"], "aGk=");
+    assert_eq!(value["compression"], "zstd");
+    assert_eq!(value["digest"]["algo"], "blake3");
+    let decoded: HashedPayload = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded, payload);
+
+    let plain = serde_json::to_value(HashedPayload::new(Vec::new())).unwrap();
+    assert!(plain.get("compression").is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_rejects_digest_mismatch() {
+    let mut value = serde_json::to_value(HashedPayload::new(b"hi".to_vec())).unwrap();
+    value["bytes"] = "aG8=".into();
+    let err = serde_json::from_value::<HashedPayload>(value).unwrap_err();
+    assert!(err.to_string().contains("digest mismatch"), "{err}");
+
+    let foreign = HashedPayload {
+        digest: HashDigest::new(HashAlgorithm::Other("sha256".into()), "00ff").unwrap(),
+        ..HashedPayload::new(b"hi".to_vec())
+    };
+    let decoded: HashedPayload =
+        serde_json::from_value(serde_json::to_value(&foreign).unwrap()).unwrap();
+    assert_eq!(decoded, foreign);
+}
@@ -0,0 +1,131 @@
+use greentic_types::{
+    DurationMs, ErrorCode, MaintenanceAction, MaintenanceRecurrence, MaintenanceWindow, UnixMillis,
+    Weekday,
+};
+
+fn friday_night_freeze() -> MaintenanceWindow {
+    let mut window = MaintenanceWindow::new(
+        "weekend-freeze".parse().unwrap(),
+        MaintenanceRecurrence::Weekly {
+            days: vec![Weekday::Friday],
+            start_minute: 22 * 60,
+        },
+        "Europe/Berlin",
+        DurationMs::from_mins(60 * 10),
+    );
+    window.environments.push("env-prod".parse().unwrap());
+    window.suppressed_actions.push(MaintenanceAction::Rollout);
+    window
+}
+
+#[test]
+fn weekly_window_wraps_past_midnight() {
+    let window = friday_night_freeze();
+    window.validate().expect("valid window");
+
+    assert!(!window.is_active_local(Weekday::Friday, 21 * 60 + 59));
+    assert!(window.is_active_local(Weekday::Friday, 22 * 60));
+    assert!(window.is_active_local(Weekday::Saturday, 7 * 60 + 59));
+    assert!(!window.is_active_local(Weekday::Saturday, 8 * 60));
+    assert!(!window.is_active_local(Weekday::Thursday, 23 * 60));
+}
+
+#[test]
+fn blocks_only_matching_environment_and_action() {
+    let window = friday_night_freeze();
+    let prod = "env-prod".parse().unwrap();
+    let staging = "env-staging".parse().unwrap();
+    let late_friday = (Weekday::Friday, 23 * 60);
+
+    assert!(window.blocks(
+        &prod,
+        &MaintenanceAction::Rollout,
+        late_friday.0,
+        late_friday.1
+    ));
+    assert!(!window.blocks(
+        &staging,
+        &MaintenanceAction::Rollout,
+        late_friday.0,
+        late_friday.1
+    ));
+    assert!(!window.blocks(
+        &prod,
+        &MaintenanceAction::Scale,
+        late_friday.0,
+        late_friday.1
+    ));
+}
+
+#[test]
+fn validate_rejects_malformed_windows() {
+    let mut window = friday_night_freeze();
+    window.recurrence = MaintenanceRecurrence::Weekly {
+        days: Vec::new(),
+        start_minute: 0,
+    };
+    assert_eq!(window.validate().unwrap_err().code, ErrorCode::InvalidInput);
+
+    window.recurrence = MaintenanceRecurrence::Daily { start_minute: 1440 };
+    assert!(window.validate().is_err());
+
+    window.recurrence = MaintenanceRecurrence::Daily { start_minute: 0 };
+    window.duration = DurationMs::ZERO;
+    assert!(window.validate().is_err());
+}
+
+#[test]
+fn once_window_accepts_unix_millis_anchor() {
+    let mut window = MaintenanceWindow::new(
+        "cutover".parse().unwrap(),
+        MaintenanceRecurrence::Once,
+        "UTC",
+        DurationMs::from_mins(30),
+    );
+    assert!(window.validate().is_err());
+    window.starts_at_utc = Some(UnixMillis::from_millis(1_000_000));
+    window.validate().expect("valid once window");
+
+    assert!(!window.is_once_active_at(UnixMillis::from_millis(999_999)));
+    assert!(window.is_once_active_at(UnixMillis::from_millis(1_000_000)));
+    assert!(window.is_once_active_at(UnixMillis::from_millis(2_799_999)));
+    assert!(!window.is_once_active_at(UnixMillis::from_millis(2_800_000)));
+    assert!(!friday_night_freeze().is_once_active_at(UnixMillis::from_millis(1_000_000)));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn once_window_uses_utc_anchor() {
+    use time::macros::{datetime, offset};
+
+    let mut window = MaintenanceWindow::new(
+        "year-end".parse().unwrap(),
+        MaintenanceRecurrence::Once,
+        "UTC",
+        DurationMs::from_mins(60 * 24),
+    );
+    assert!(window.validate().is_err());
+    window.starts_at_utc = Some(datetime!(2025-12-31 00:00 UTC).into());
+    window.validate().expect("valid once window");
+
+    assert!(window.is_active_at(datetime!(2025-12-31 12:00 UTC), offset!(UTC)));
+    assert!(!window.is_active_at(datetime!(2026-01-01 00:00 UTC), offset!(UTC)));
+    assert!(!window.is_active_local(Weekday::Wednesday, 12 * 60));
+
+    let recurring = friday_night_freeze();
+    // 2025-01-03 is a Friday; 21:30 UTC is 22:30 in Berlin.
+    assert!(recurring.is_active_at(datetime!(2025-01-03 21:30 UTC), offset!(+1)));
+    assert!(!recurring.is_active_at(datetime!(2025-01-03 21:30 UTC), offset!(UTC)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn maintenance_window_roundtrips() {
+    let window = friday_night_freeze();
+    let value = serde_json::to_value(&window).unwrap();
+    assert_eq!(value["recurrence"]["kind"], "weekly");
+    assert_eq!(value["recurrence"]["days"][0], "friday");
+    assert_eq!(value["suppressed_actions"][0], "rollout");
+    let back: MaintenanceWindow = serde_json::from_value(value).unwrap();
+    assert_eq!(back, window);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use greentic_types::{LocaleTag, MessageTemplate};
+
+fn tag(value: &str) -> LocaleTag {
+    value.parse().unwrap()
+}
+
+fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn greeting() -> MessageTemplate {
+    MessageTemplate::new(
+        "greeting",
+        Some("Hello {name}, you have {count} messages.".into()),
+    )
+    .with_translation(tag("de"), "Hallo {name}, du hast {count} Nachrichten.")
+    .with_translation(tag("fr-CA"), "Bonjour {name} {{VIP}}")
+    .with_default("count", "0")
+}
+
+#[test]
+fn template_renders_through_locale_fallback_chain() {
+    let template = greeting();
+    let report = template.render_report(&tag("de-CH"), &vars(&[("name", "Ada")]));
+    assert_eq!(report.text, "Hallo Ada, du hast 0 Nachrichten.");
+    assert_eq!(report.locale, Some(tag("de")));
+    assert!(report.diagnostics.is_empty());
+
+    assert_eq!(
+        template.render(&tag("fr-CA"), &vars(&[("name", "Ada")])),
+        "Bonjour Ada {VIP}"
+    );
+    let fallback = template.render_report(&tag("ja"), &vars(&[("name", "Ada"), ("count", "3")]));
+    assert_eq!(fallback.text, "Hello Ada, you have 3 messages.");
+    assert_eq!(fallback.locale, None);
+
+    let json = serde_json::to_value(&template).unwrap();
+    assert_eq!(json["key"], "greeting");
+    assert_eq!(json["defaults"]["count"], "0");
+    let parsed: MessageTemplate = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, template);
+}
+
+#[test]
+fn template_reports_missing_variables_and_texts() {
+    let report = greeting().render_report(&tag("en"), &BTreeMap::new());
+    assert_eq!(report.text, "Hello {name}, you have 0 messages.");
+    let codes: Vec<_> = report
+        .diagnostics
+        .iter()
+        .map(|diag| diag.code.as_str())
+        .collect();
+    assert_eq!(codes, ["TEMPLATE_MISSING_VARIABLE"]);
+    assert_eq!(report.diagnostics[0].path.as_deref(), Some("greeting.name"));
+
+    let bare = MessageTemplate::new("promo.banner", None);
+    let report = bare.render_report(&tag("en"), &BTreeMap::new());
+    assert_eq!(report.text, "promo.banner");
+    assert_eq!(report.diagnostics[0].code, "TEMPLATE_MISSING_TEXT");
+
+    let broken = MessageTemplate::new("broken", Some("50% off { today".into()));
+    let report = broken.render_report(&tag("en"), &BTreeMap::new());
+    assert_eq!(report.text, "50% off { today");
+    assert_eq!(report.diagnostics[0].code, "TEMPLATE_UNBALANCED_BRACE");
+}
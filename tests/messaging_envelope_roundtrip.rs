@@ -1,7 +1,9 @@
 #![cfg(feature = "serde")]
 
 use greentic_types::{
-    Actor, Attachment, ChannelMessageEnvelope, Destination, MessageMetadata, TenantCtx,
+    Actor, Attachment, ChannelId, ChannelMessageEnvelope, ConversationBinding, ConversationId,
+    DeliveryOptions, DeliveryPriority, Destination, DurationMs, ErrorCode, FlowId, MessageMetadata,
+    PeerId, SessionKey, TenantCtx, UnixMillis,
 };
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -37,6 +39,7 @@ fn text_only_message_roundtrip() {
         text: Some("hello world".into()),
         attachments: Vec::new(),
         metadata: MessageMetadata::new(),
+        delivery_options: None,
     };
 
     assert_roundtrip(&envelope);
@@ -52,9 +55,11 @@ fn message_with_attachments_and_metadata_roundtrip() {
     metadata.insert("adapter".into(), "test-adapter".into());
     let attachments = vec![Attachment {
         mime_type: "image/png".into(),
-        url: "https://example.test/image.png".into(),
+        url: "https://example.test/image.png".parse().unwrap(),
         name: Some("diagram.png".into()),
         size_bytes: Some(1_024),
+        transfer_id: None,
+        blob: None,
     }];
     let envelope = ChannelMessageEnvelope {
         id: "msg-attachment".into(),
@@ -74,7 +79,175 @@ fn message_with_attachments_and_metadata_roundtrip() {
         text: Some("see attachment".into()),
         attachments,
         metadata,
+        delivery_options: None,
     };
 
     assert_roundtrip(&envelope);
 }
+
+#[test]
+fn builder_enforces_required_fields() {
+    let ctx = TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap());
+    let envelope = ChannelMessageEnvelope::builder()
+        .id("msg-1")
+        .tenant(ctx.clone())
+        .channel("slack")
+        .session_id(ConversationId::new("T01:C42"))
+        .from("U123", Some("user".into()))
+        .to("C42", Some("channel".into()))
+        .text("hello")
+        .metadata("adapter", "slack")
+        .build()
+        .unwrap();
+    assert_eq!(envelope.channel, ChannelId::new("slack"));
+    assert_eq!(envelope.from.as_ref().unwrap().id, "U123");
+    assert_eq!(envelope.to[0].id, PeerId::from("C42"));
+    assert_roundtrip(&envelope);
+
+    for (builder, field) in [
+        (
+            ChannelMessageEnvelope::builder()
+                .tenant(ctx.clone())
+                .channel("slack")
+                .session_id("s"),
+            "id",
+        ),
+        (
+            ChannelMessageEnvelope::builder()
+                .id("m")
+                .channel("slack")
+                .session_id("s"),
+            "tenant",
+        ),
+        (
+            ChannelMessageEnvelope::builder()
+                .id("m")
+                .tenant(ctx.clone())
+                .session_id("s"),
+            "channel",
+        ),
+        (
+            ChannelMessageEnvelope::builder()
+                .id("m")
+                .tenant(ctx.clone())
+                .channel("slack")
+                .session_id(""),
+            "session_id",
+        ),
+    ] {
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert!(err.message.contains(field), "{}", err.message);
+    }
+}
+
+#[test]
+fn typed_identifiers_keep_string_wire_format() {
+    let ctx = TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap());
+    let json = serde_json::json!({
+        "id": "msg-1",
+        "tenant": serde_json::to_value(&ctx).unwrap(),
+        "channel": "teams",
+        "session_id": "19:meeting_abc@thread.v2",
+        "from": {"id": "29:1a-user"},
+        "to": [{"id": "+15551234567", "kind": "phone"}]
+    });
+    let envelope: ChannelMessageEnvelope = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(envelope.session_id.as_str(), "19:meeting_abc@thread.v2");
+    assert_eq!(envelope.to[0].id.to_string(), "+15551234567");
+    let back = serde_json::to_value(&envelope).unwrap();
+    assert_eq!(back["channel"], json["channel"]);
+    assert_eq!(back["session_id"], json["session_id"]);
+    assert_eq!(back["from"]["id"], json["from"]["id"]);
+    assert_eq!(back["to"], json["to"]);
+}
+
+#[test]
+fn conversation_binding_roundtrip_and_lookup() {
+    let ctx = TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap());
+    let created = UnixMillis::from_millis(1_700_000_000_000);
+    let binding = ConversationBinding::new(
+        &ctx,
+        "telegram",
+        "chat-42",
+        SessionKey::derive(&ctx, "telegram", "user-7"),
+        FlowId::new("support").unwrap(),
+        created,
+    )
+    .with_thread("topic-3")
+    .with_ttl(DurationMs::from_secs(60));
+    assert_roundtrip(&binding);
+
+    assert_eq!(
+        binding.lookup_key(),
+        "prod:tenant-1:telegram:chat-42#topic-3"
+    );
+    assert_eq!(
+        binding.lookup_key(),
+        ConversationBinding::key_for(
+            &ctx.env,
+            &ctx.tenant_id,
+            &ChannelId::new("telegram"),
+            &ConversationId::new("chat-42"),
+            Some("topic-3"),
+        )
+    );
+    assert!(!binding.is_expired(UnixMillis::from_millis(1_700_000_059_999)));
+    assert!(binding.is_expired(UnixMillis::from_millis(1_700_000_060_000)));
+
+    let json = serde_json::to_value(&binding).unwrap();
+    assert_eq!(json["created_at"], "2023-11-14T22:13:20Z");
+    assert_eq!(json["conversation"], "chat-42");
+}
+
+#[test]
+fn delivery_options_schedule_and_expire() {
+    let options = DeliveryOptions::default()
+        .with_send_after(UnixMillis::from_millis(10_000))
+        .with_expire_after(DurationMs::from_millis(5_000))
+        .with_priority(DeliveryPriority::High);
+    let queued_at = UnixMillis::from_millis(1_000);
+
+    assert!(!options.is_due(UnixMillis::from_millis(9_999)));
+    assert!(options.is_due(UnixMillis::from_millis(10_000)));
+    assert_eq!(
+        options.expires_at(queued_at),
+        Some(UnixMillis::from_millis(15_000))
+    );
+    assert!(!options.is_expired(queued_at, UnixMillis::from_millis(14_999)));
+    assert!(options.is_expired(queued_at, UnixMillis::from_millis(15_000)));
+
+    let immediate = DeliveryOptions::default().with_expire_after(DurationMs::from_millis(500));
+    assert!(immediate.is_due(UnixMillis::EPOCH));
+    assert_eq!(
+        immediate.expires_at(queued_at),
+        Some(UnixMillis::from_millis(1_500))
+    );
+    assert_eq!(DeliveryOptions::default().expires_at(queued_at), None);
+
+    let envelope = ChannelMessageEnvelope::builder()
+        .id("msg-scheduled")
+        .tenant(TenantCtx::new(
+            "prod".parse().unwrap(),
+            "tenant-1".parse().unwrap(),
+        ))
+        .channel("generic-channel")
+        .session_id("thread-1")
+        .text("reminder")
+        .delivery_options(options)
+        .build()
+        .unwrap();
+    let value = serde_json::to_value(&envelope).unwrap();
+    assert_eq!(
+        value["delivery_options"],
+        serde_json::json!({
+            "send_after": "1970-01-01T00:00:10Z",
+            "expire_after": 5_000,
+            "priority": "high"
+        })
+    );
+    assert_roundtrip(&envelope);
+
+    let plain = serde_json::to_value(DeliveryOptions::default()).unwrap();
+    assert_eq!(plain, serde_json::json!({ "priority": "normal" }));
+}
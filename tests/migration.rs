@@ -0,0 +1,82 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::migration::{
+    COMPONENT_QA_SCHEMA, ComponentQaV0_5ToV0_6, MIGRATIONS, Migrate, MigrationNoteKind,
+    migrate_component_qa_v0_5_to_v0_6, migration_path, upgrade_json,
+};
+use greentic_types::schemas::component::v0_5_0::LegacyComponentQaSpec;
+use greentic_types::schemas::component::v0_6_0::{ComponentQaSpec, QaMode};
+use serde_json::json;
+
+fn legacy_fixture() -> LegacyComponentQaSpec {
+    serde_json::from_str(include_str!("../fixtures/legacy/component_v0_5_0_qa.json"))
+        .expect("legacy fixture")
+}
+
+#[test]
+fn component_qa_migration_matches_trait_impl() {
+    let legacy = legacy_fixture();
+    let (spec, report) = migrate_component_qa_v0_5_to_v0_6(QaMode::Setup, &legacy);
+    let (via_trait, _) = ComponentQaV0_5ToV0_6 {
+        mode: QaMode::Setup,
+    }
+    .migrate(&legacy);
+
+    assert_eq!(spec, via_trait);
+    assert_eq!(spec.mode, QaMode::Setup);
+    assert_eq!(spec.questions.len(), legacy.questions.len());
+    assert_eq!(report.schema, COMPONENT_QA_SCHEMA);
+    assert_eq!(report.from_version, "0.5.0");
+    assert_eq!(report.to_version, "0.6.0");
+    assert!(report.is_lossy());
+}
+
+#[test]
+fn component_qa_migration_reports_dropped_choices() {
+    let legacy: LegacyComponentQaSpec = serde_json::from_value(json!({
+        "title": "Setup",
+        "description": null,
+        "questions": [{
+            "id": "token",
+            "label": "Token",
+            "help": null,
+            "kind": "text",
+            "required": true,
+            "default": null,
+            "choices": [{"value": "a", "label": "A"}]
+        }]
+    }))
+    .expect("legacy");
+
+    let (_, report) = migrate_component_qa_v0_5_to_v0_6(QaMode::Default, &legacy);
+    let dropped = report
+        .notes
+        .iter()
+        .find(|note| note.kind == MigrationNoteKind::Dropped)
+        .expect("dropped note");
+    assert_eq!(dropped.path, "questions.token.choices");
+}
+
+#[test]
+fn registry_upgrades_json_documents() {
+    assert!(!MIGRATIONS.is_empty());
+    assert_eq!(
+        migration_path(COMPONENT_QA_SCHEMA, "0.5.0", "0.6.0").map(|path| path.len()),
+        Some(1)
+    );
+    assert_eq!(
+        migration_path(COMPONENT_QA_SCHEMA, "0.6.0", "0.6.0").map(|path| path.len()),
+        Some(0)
+    );
+    assert!(migration_path(COMPONENT_QA_SCHEMA, "0.4.0", "0.6.0").is_none());
+
+    let document = serde_json::to_value(legacy_fixture()).expect("legacy json");
+    let (upgraded, report) =
+        upgrade_json(COMPONENT_QA_SCHEMA, "0.5.0", "0.6.0", &document).expect("upgrade");
+    let spec: ComponentQaSpec = serde_json::from_value(upgraded).expect("v0.6 spec");
+    assert_eq!(spec.mode, QaMode::Default);
+    assert!(report.notes.iter().any(|note| note.path == "mode"));
+
+    let err = upgrade_json("pack.qa", "0.5.0", "0.6.0", &document).expect_err("no path");
+    assert_eq!(err.code, greentic_types::ErrorCode::NotFound);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::{
+    DigestFrequency, NotificationCategory, NotificationChannel, NotificationPreferences,
+    NotificationRule, QuietHours, TenantCtx,
+};
+
+fn preferences() -> NotificationPreferences {
+    let mut preferences = NotificationPreferences::new(TenantCtx::new(
+        "prod".parse().unwrap(),
+        "tenant-1".parse().unwrap(),
+    ))
+    .for_user("user-1".parse().unwrap());
+    preferences.set_rule(
+        NotificationCategory::RunFailure,
+        NotificationRule {
+            channels: vec![NotificationChannel::Email, NotificationChannel::Chat],
+            frequency: DigestFrequency::Immediate,
+            quiet_hours: Some(QuietHours::new((22, 0), (7, 0), "Europe/Berlin").unwrap()),
+        },
+    );
+    preferences.set_rule(
+        NotificationCategory::Billing,
+        NotificationRule {
+            channels: vec![NotificationChannel::Email],
+            frequency: DigestFrequency::Weekly,
+            quiet_hours: None,
+        },
+    );
+    preferences
+}
+
+#[test]
+fn notification_preferences_roundtrip() {
+    let preferences = preferences();
+    preferences.validate().unwrap();
+    let json = serde_json::to_value(&preferences).unwrap();
+    assert_eq!(json["categories"][0]["category"], "run_failure");
+    let back: NotificationPreferences = serde_json::from_value(json).unwrap();
+    assert_eq!(back, preferences);
+
+    let minimal: NotificationPreferences = serde_json::from_value(serde_json::json!({
+        "tenant": serde_json::to_value(&preferences.tenant).unwrap(),
+    }))
+    .unwrap();
+    assert_eq!(minimal.defaults, NotificationRule::default());
+}
+
+#[test]
+fn rules_resolve_per_category() {
+    let mut preferences = preferences();
+    let failure = preferences.rule_for(&NotificationCategory::RunFailure);
+    assert!(failure.delivers_now(12 * 60));
+    assert!(!failure.delivers_now(23 * 60));
+    assert!(!failure.delivers_now(6 * 60 + 59));
+    assert!(failure.delivers_now(7 * 60));
+
+    assert!(
+        !preferences
+            .rule_for(&NotificationCategory::Billing)
+            .delivers_now(12 * 60)
+    );
+    assert_eq!(
+        preferences.rule_for(&NotificationCategory::Rollout),
+        &NotificationRule::default()
+    );
+
+    preferences.set_rule(
+        NotificationCategory::Billing,
+        NotificationRule {
+            frequency: DigestFrequency::Muted,
+            ..NotificationRule::default()
+        },
+    );
+    assert_eq!(preferences.categories.len(), 2);
+    assert_eq!(
+        preferences
+            .rule_for(&NotificationCategory::Billing)
+            .frequency,
+        DigestFrequency::Muted
+    );
+}
+
+#[test]
+fn validation_rejects_bad_windows_and_duplicates() {
+    assert!(QuietHours::new((24, 0), (7, 0), "UTC").is_err());
+    assert!(QuietHours::new((22, 0), (7, 0), " ").is_err());
+
+    let mut preferences = preferences();
+    preferences
+        .categories
+        .push(preferences.categories[0].clone());
+    assert!(preferences.validate().is_err());
+
+    let mut preferences = self::preferences();
+    preferences.defaults.quiet_hours = Some(QuietHours {
+        start_minute: 60,
+        end_minute: 2000,
+        timezone: "UTC".into(),
+    });
+    assert!(preferences.validate().is_err());
+
+    let daytime = QuietHours::new((9, 0), (17, 30), "UTC").unwrap();
+    assert!(daytime.contains(9 * 60));
+    assert!(!daytime.contains(17 * 60 + 30));
+}
@@ -0,0 +1,63 @@
+use greentic_types::{ErrorCode, NodeError, NodeResult, Outcome};
+
+#[test]
+fn combinators_thread_done_values() {
+    let done: Outcome<u32> = Outcome::Done(2);
+    let chained = done
+        .map(|value| value * 10)
+        .and_then(|value| Outcome::Done(value + 1));
+    assert_eq!(chained, Outcome::Done(21));
+    assert_eq!(chained.as_ref().done(), Some(&21));
+    assert_eq!(chained.into_result().unwrap(), Some(21));
+
+    let failed = Outcome::<u32>::Done(1)
+        .and_then(|_| Outcome::<u32>::error(ErrorCode::Conflict, "taken"))
+        .map(|value| value + 1);
+    assert!(failed.is_error());
+    let err = failed.into_result().unwrap_err();
+    assert_eq!(err.code, ErrorCode::Conflict);
+    assert_eq!(err.message, "taken");
+}
+
+#[test]
+fn pending_helpers_expose_reason_and_inputs() {
+    let pending = Outcome::<u32>::pending_with_inputs(&["approval", "payment"]);
+    assert!(pending.is_pending());
+    assert_eq!(
+        pending.pending_reason(),
+        Some("waiting for input: approval, payment")
+    );
+    assert_eq!(
+        pending.expected_input(),
+        Some(&["approval".to_owned(), "payment".to_owned()][..])
+    );
+
+    let mapped = pending.and_then(|value| Outcome::Done(value.to_string()));
+    assert_eq!(
+        mapped.pending_reason(),
+        Some("waiting for input: approval, payment")
+    );
+    assert_eq!(mapped.into_result().unwrap(), None);
+
+    let plain = Outcome::<()>::pending("awaiting webhook");
+    assert_eq!(plain.expected_input(), None);
+    assert_eq!(Outcome::<()>::Done(()).pending_reason(), None);
+}
+
+#[test]
+fn node_results_convert_to_outcomes() {
+    let ok: NodeResult<&str> = Ok("hi");
+    assert_eq!(Outcome::from(ok), Outcome::Done("hi"));
+
+    let known: NodeResult<()> = Err(NodeError::new("rate_limited", "slow down"));
+    assert_eq!(
+        Outcome::from(known),
+        Outcome::error(ErrorCode::RateLimited, "slow down")
+    );
+
+    let custom: NodeResult<()> = Err(NodeError::new("weather.api_down", "no data"));
+    assert_eq!(
+        Outcome::from(custom),
+        Outcome::error(ErrorCode::Unknown, "weather.api_down: no data")
+    );
+}
@@ -0,0 +1,251 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use greentic_types::{
+    ComponentCapabilities, ComponentCapability, ComponentManifest, ComponentOperation,
+    ComponentProfiles, Diagnostic, Flow, FlowComponentRef, FlowId, FlowKind, FlowMetadata,
+    InputMapping, LintConfig, LintLevel, LintRegistry, LintRule, Node, OutputMapping,
+    PackFlowEntry, PackKind, PackManifest, PackSignatures, ResourceHints, Routing, Severity,
+    TelemetryHints, lint_pack_manifest,
+};
+use indexmap::IndexMap;
+use semver::Version;
+use serde_json::Value;
+
+fn flow(id: &str, component: &str) -> PackFlowEntry {
+    let mut nodes: IndexMap<_, _, greentic_types::flow::FlowHasher> = IndexMap::default();
+    nodes.insert(
+        "start".parse().unwrap(),
+        Node {
+            id: "start".parse().unwrap(),
+            component: FlowComponentRef {
+                id: component.parse().unwrap(),
+                pack_alias: None,
+                operation: Some("handle".into()),
+            },
+            input: InputMapping {
+                mapping: Value::Null,
+            },
+            output: OutputMapping {
+                mapping: Value::Null,
+            },
+            routing: Routing::End,
+            telemetry: TelemetryHints::default(),
+            capabilities_override: None,
+        },
+    );
+    PackFlowEntry {
+        id: FlowId::new(id).unwrap(),
+        kind: FlowKind::Messaging,
+        flow: Flow {
+            schema_version: "flow-v1".into(),
+            id: FlowId::new(id).unwrap(),
+            kind: FlowKind::Messaging,
+            entrypoints: BTreeMap::from([("default".into(), Value::Null)]),
+            nodes,
+            metadata: FlowMetadata {
+                description: Some("Handles inbound chat.".into()),
+                telemetry: Some(TelemetryHints::default()),
+                ..FlowMetadata::default()
+            },
+        },
+        tags: Vec::new(),
+        entrypoints: vec!["default".into()],
+    }
+}
+
+fn component(id: &str) -> ComponentManifest {
+    ComponentManifest {
+        id: id.parse().unwrap(),
+        version: Version::parse("1.0.0").unwrap(),
+        supports: vec![FlowKind::Messaging],
+        world: "test:world@1.0.0".into(),
+        profiles: ComponentProfiles::default(),
+        capabilities: ComponentCapabilities::default(),
+        configurators: None,
+        operations: vec![ComponentOperation {
+            name: "handle".into(),
+            input_schema: Value::Null,
+            output_schema: Value::Null,
+        }],
+        config_schema: None,
+        resources: ResourceHints::default(),
+        targets: Vec::new(),
+        dev_flows: BTreeMap::new(),
+    }
+}
+
+fn manifest() -> PackManifest {
+    PackManifest {
+        schema_version: "pack-v1".into(),
+        pack_id: "dev.local.lint".parse().unwrap(),
+        name: None,
+        version: Version::parse("0.1.0").unwrap(),
+        kind: PackKind::Application,
+        publisher: "tests".into(),
+        components: vec![component("component.chat")],
+        flows: vec![flow("main", "component.chat")],
+        dependencies: Vec::new(),
+        capabilities: vec![ComponentCapability {
+            name: "messaging".into(),
+            description: Some("Chat surface.".into()),
+        }],
+        secret_requirements: Vec::new(),
+        signatures: PackSignatures::default(),
+        bootstrap: None,
+        extensions: None,
+    }
+}
+
+fn codes(diagnostics: &[Diagnostic]) -> Vec<(&str, Severity, &str)> {
+    diagnostics
+        .iter()
+        .map(|diag| {
+            (
+                diag.code.as_str(),
+                diag.severity,
+                diag.path.as_deref().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn clean_manifest_has_no_lints() {
+    let report = lint_pack_manifest(&manifest(), &LintConfig::default());
+    assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+    assert_eq!(report.pack_id, Some("dev.local.lint".parse().unwrap()));
+}
+
+#[test]
+fn built_in_rules_flag_manifest_smells() {
+    let mut manifest = manifest();
+    manifest.flows[0].flow.metadata = FlowMetadata::default();
+    manifest.capabilities[0].description = None;
+    let mut idle = component("component.idle");
+    idle.operations.clear();
+    idle.capabilities.wasi.random = true;
+    manifest.components.push(idle);
+
+    let report = lint_pack_manifest(&manifest, &LintConfig::default());
+    assert_eq!(
+        codes(&report.diagnostics),
+        vec![
+            (
+                "LINT_MISSING_DESCRIPTION",
+                Severity::Warn,
+                "flows.main.metadata.description"
+            ),
+            (
+                "LINT_MISSING_DESCRIPTION",
+                Severity::Warn,
+                "capabilities.0.description"
+            ),
+            (
+                "LINT_UNUSED_CAPABILITIES",
+                Severity::Warn,
+                "components.component.idle.capabilities"
+            ),
+            (
+                "LINT_COMPONENT_WITHOUT_OPERATIONS",
+                Severity::Warn,
+                "components.component.idle.operations"
+            ),
+            (
+                "LINT_FLOW_WITHOUT_TELEMETRY",
+                Severity::Info,
+                "flows.main.metadata.telemetry"
+            ),
+        ]
+    );
+    assert_eq!(report.diagnostics[0].data["rule"], "missing-description");
+
+    let config: LintConfig = serde_json::from_value(serde_json::json!({
+        "rules": {
+            "missing-description": "off",
+            "component-without-operations": "error"
+        }
+    }))
+    .unwrap();
+    assert_eq!(
+        config.rules.get("component-without-operations"),
+        Some(&LintLevel::Error)
+    );
+    let report = lint_pack_manifest(&manifest, &config);
+    assert!(report.has_errors());
+    assert_eq!(report.counts().warn, 1);
+    assert_eq!(report.counts().info, 1);
+    assert!(
+        report
+            .diagnostics
+            .iter()
+            .all(|diag| diag.code != "LINT_MISSING_DESCRIPTION")
+    );
+}
+
+struct PublisherRule;
+
+impl LintRule for PublisherRule {
+    fn id(&self) -> &'static str {
+        "publisher-domain"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warn
+    }
+
+    fn check(&self, manifest: &PackManifest) -> Vec<Diagnostic> {
+        if manifest.publisher.contains('.') {
+            return Vec::new();
+        }
+        vec![Diagnostic {
+            severity: Severity::Warn,
+            code: "LINT_PUBLISHER_DOMAIN".into(),
+            message: "Publisher should be a domain.".into(),
+            path: Some("publisher".into()),
+            hint: None,
+            data: Value::Null,
+        }]
+    }
+}
+
+#[test]
+fn custom_rules_join_the_registry() {
+    let mut registry = LintRegistry::default();
+    registry.register(Box::new(PublisherRule));
+    assert_eq!(registry.rule_ids().last(), Some(&"publisher-domain"));
+
+    let report = registry.lint(&manifest(), &LintConfig::default());
+    assert_eq!(
+        codes(&report.diagnostics),
+        vec![("LINT_PUBLISHER_DOMAIN", Severity::Warn, "publisher")]
+    );
+
+    let config = LintConfig::default().with_rule("publisher-domain", LintLevel::Off);
+    assert!(registry.lint(&manifest(), &config).diagnostics.is_empty());
+}
+
+#[test]
+fn unknown_rule_ids_in_config_are_reported() {
+    let config = LintConfig::default()
+        .with_rule("missing-descriptoin", LintLevel::Off)
+        .with_rule("flow-without-telemetry", LintLevel::Error);
+    let report = lint_pack_manifest(&manifest(), &config);
+    assert_eq!(
+        codes(&report.diagnostics),
+        vec![(
+            "LINT_UNKNOWN_RULE",
+            Severity::Warn,
+            "rules.missing-descriptoin"
+        )]
+    );
+    assert_eq!(report.diagnostics[0].data["rule"], "missing-descriptoin");
+    assert!(
+        report.diagnostics[0]
+            .hint
+            .as_deref()
+            .unwrap()
+            .contains("missing-description")
+    );
+}
@@ -3,11 +3,13 @@
 use std::collections::BTreeMap;
 
 use greentic_types::{
-    BootstrapSpec, ComponentCapabilities, ComponentCapability, ComponentManifest,
-    ComponentOperation, ComponentProfiles, DeploymentPlan, Flow, FlowComponentRef, FlowId,
-    FlowKind, FlowMetadata, InputMapping, Node, OutputMapping, PackDependency, PackFlowEntry,
-    PackId, PackKind, PackManifest, PackSignatures, ResourceHints, Routing, SecretFormat,
-    SecretRequirement, SecretScope, TelemetryHints, decode_pack_manifest, encode_pack_manifest,
+    BootstrapSpec, ByteSize, Capabilities, ComponentCapabilities, ComponentCapability,
+    ComponentManifest, ComponentOperation, ComponentProfiles, ComponentTarget,
+    DEFAULT_COMPONENT_TARGET_TRIPLE, DeploymentPlan, Flow, FlowComponentRef, FlowId, FlowKind,
+    FlowMetadata, HashAlgorithm, InputMapping, Node, OutputMapping, PackDependency, PackFlowEntry,
+    PackId, PackKind, PackManifest, PackManifestOverlay, PackSignatures, Platform, Ratio,
+    ResourceHints, Routing, SecretFormat, SecretRequirement, SecretScope, StableHash,
+    TelemetryHints, decode_pack_manifest, encode_pack_manifest,
 };
 use indexmap::IndexMap;
 use semver::Version;
@@ -35,6 +37,7 @@ fn sample_flow() -> Flow {
                 default: Some("end".parse().unwrap()),
             },
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
     nodes.insert(
@@ -54,6 +57,7 @@ fn sample_flow() -> Flow {
             },
             routing: Routing::Reply,
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
     nodes.insert(
@@ -73,6 +77,7 @@ fn sample_flow() -> Flow {
             },
             routing: Routing::End,
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
 
@@ -105,6 +110,7 @@ fn sample_component(id: &str, supports: Vec<FlowKind>) -> ComponentManifest {
         }],
         config_schema: None,
         resources: ResourceHints::default(),
+        targets: Vec::new(),
         dev_flows: BTreeMap::new(),
     }
 }
@@ -221,6 +227,75 @@ fn pack_manifest_cbor_encoding_is_deterministic() {
     assert_eq!(first, second);
 }
 
+#[test]
+fn content_digests_are_stable_and_detect_drift() {
+    let manifest = sample_pack_manifest();
+    let digest = manifest.content_digest().unwrap();
+    assert_eq!(digest.algo, HashAlgorithm::Blake3);
+    assert_eq!(digest.hex.len(), 64);
+    assert_eq!(roundtrip_json(&manifest).content_digest().unwrap(), digest);
+
+    let mut drifted = manifest.clone();
+    drifted.version = Version::new(9, 9, 9);
+    assert_ne!(drifted.content_digest().unwrap(), digest);
+
+    let flow = sample_flow();
+    let flow_digest = flow.content_digest().unwrap();
+    assert_eq!(sample_flow().content_digest().unwrap(), flow_digest);
+    assert_ne!(flow_digest, digest);
+
+    let mut sampled = flow;
+    sampled.metadata.telemetry = Some(TelemetryHints {
+        sample_ratio: Some(Ratio::new(0.5).unwrap()),
+        ..TelemetryHints::default()
+    });
+    assert_ne!(sampled.content_digest().unwrap(), flow_digest);
+
+    let mut weighted = sample_flow();
+    let start = weighted.nodes.get_index_mut(0).unwrap().1;
+    start.input.mapping = serde_json::json!({ "weight": 0.75, "threshold": 1e300 });
+    weighted.metadata.extra = serde_json::json!({ "score": 2.5 });
+    let weighted_digest = weighted.content_digest().unwrap();
+    assert_eq!(
+        roundtrip_json(&weighted).content_digest().unwrap(),
+        weighted_digest
+    );
+    weighted.metadata.extra["score"] = serde_json::json!(3.5);
+    assert_ne!(weighted.content_digest().unwrap(), weighted_digest);
+}
+
+#[test]
+fn stable_hash_matches_content_digest_and_ignores_transport() {
+    let manifest = sample_pack_manifest();
+    assert_eq!(
+        manifest.stable_hash().unwrap(),
+        manifest.content_digest().unwrap()
+    );
+    let flow = sample_flow();
+    assert_eq!(flow.stable_hash().unwrap(), flow.content_digest().unwrap());
+
+    let mut reordered = sample_flow();
+    reordered.nodes.swap_indices(0, 1);
+    assert_ne!(reordered.ingress().unwrap().0, flow.ingress().unwrap().0);
+    assert_ne!(
+        reordered.stable_hash().unwrap(),
+        flow.stable_hash().unwrap()
+    );
+    let mut reordered_manifest = manifest.clone();
+    reordered_manifest.flows[0].flow = reordered;
+    assert_ne!(
+        reordered_manifest.stable_hash().unwrap(),
+        manifest.stable_hash().unwrap()
+    );
+
+    let mut caps = Capabilities::default();
+    caps.http = Some(greentic_types::HttpCaps::new());
+    caps.secrets = Some(greentic_types::SecretsCaps::new());
+    let digest = caps.stable_hash().unwrap();
+    assert_eq!(roundtrip_json(&caps).stable_hash().unwrap(), digest);
+    assert_ne!(Capabilities::default().stable_hash().unwrap(), digest);
+}
+
 #[test]
 fn pack_manifest_yaml_roundtrip_without_bootstrap() {
     let manifest: PackManifest = serde_yaml_bw::from_str(include_str!(
@@ -364,6 +439,54 @@ fn component_manifest_deserializes_without_dev_flows() {
     assert!(decoded.dev_flows.is_empty());
 }
 
+#[test]
+fn component_manifest_targets_default_and_select_by_platform() {
+    let mut manifest = sample_component("component.multi", vec![FlowKind::Messaging]);
+    let legacy: ComponentManifest =
+        serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).expect("deserialize");
+    let defaults = legacy.effective_targets();
+    assert_eq!(defaults.len(), 1);
+    assert_eq!(defaults[0].triple, DEFAULT_COMPONENT_TARGET_TRIPLE);
+    assert_eq!(defaults[0].world.as_deref(), Some(manifest.world.as_str()));
+    assert_eq!(
+        legacy.select_target(&Platform::Lambda),
+        Some(legacy.default_target())
+    );
+
+    let wasm = ComponentTarget::new("wasm32-wasip2");
+    let mut native = ComponentTarget::new("x86_64-unknown-linux-gnu");
+    native.image = Some("acme.multi-native".parse().unwrap());
+    native.platforms = vec![Platform::K8s, Platform::Nomad];
+    native.resources = Some(ResourceHints {
+        cpu_millis: Some(500),
+        ..ResourceHints::default()
+    });
+    manifest.targets = vec![wasm, native];
+
+    let selected = manifest
+        .select_target(&Platform::K8s)
+        .expect("native target");
+    assert!(!selected.is_wasm());
+    assert_eq!(selected.world.as_deref(), Some(manifest.world.as_str()));
+    assert_eq!(selected.resources.unwrap().cpu_millis, Some(500));
+    let selected = manifest
+        .select_target(&Platform::CfWorkers)
+        .expect("wasm target");
+    assert!(selected.is_wasm());
+    assert_eq!(selected.resources.as_ref(), Some(&manifest.resources));
+
+    manifest.targets.remove(0);
+    assert_eq!(manifest.select_target(&Platform::CfWorkers), None);
+
+    let json = serde_json::to_value(&manifest).unwrap();
+    assert_eq!(
+        json["targets"][0]["platforms"],
+        serde_json::json!(["K8s", "Nomad"])
+    );
+    let decoded: ComponentManifest = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, manifest);
+}
+
 #[test]
 fn component_manifest_with_dev_flows_roundtrips() {
     let manifest_json = serde_json::json!({
@@ -411,3 +534,66 @@ fn component_manifest_with_dev_flows_roundtrips() {
     let roundtrip = roundtrip_json(&decoded);
     assert_eq!(roundtrip.dev_flows.len(), 1);
 }
+
+#[test]
+fn pack_manifest_overlay_merges_tenant_overrides() {
+    let mut manifest = sample_pack_manifest();
+    manifest.components[1].resources.cpu_millis = Some(250);
+
+    let mut replacement = sample_secret_requirement();
+    replacement.required = false;
+    let mut extra = SecretRequirement::default();
+    extra.key = "TENANT_WEBHOOK_SECRET".into();
+
+    let hints = ResourceHints {
+        memory: Some(ByteSize::from_mib(512)),
+        ..ResourceHints::default()
+    };
+    let overlay = PackManifestOverlay {
+        disabled_flows: vec![FlowId::new("demo.flow").unwrap()],
+        secret_requirements: vec![replacement, extra],
+        removed_secret_requirements: Vec::new(),
+        component_resources: BTreeMap::from([("component.handler".parse().unwrap(), hints)]),
+    };
+    let overlay = roundtrip_json(&overlay);
+
+    let report = manifest.apply_overlay(&overlay);
+    assert!(report.diagnostics.is_empty(), "{:?}", report.diagnostics);
+    assert!(manifest.flows.is_empty());
+    assert_eq!(manifest.secret_requirements.len(), 2);
+    assert!(!manifest.secret_requirements[0].required);
+    assert_eq!(
+        manifest.secret_requirements[1].key.as_str(),
+        "TENANT_WEBHOOK_SECRET"
+    );
+    let resources = &manifest.components[1].resources;
+    assert_eq!(resources.cpu_millis, Some(250));
+    assert_eq!(resources.memory, Some(ByteSize::from_mib(512)));
+}
+
+#[test]
+fn pack_manifest_overlay_rejects_unknown_references() {
+    let mut manifest = sample_pack_manifest();
+    let original = manifest.clone();
+    let overlay = PackManifestOverlay {
+        disabled_flows: vec![FlowId::new("missing.flow").unwrap()],
+        removed_secret_requirements: vec!["TEST_API_KEY".into()],
+        component_resources: BTreeMap::from([(
+            "component.ghost".parse().unwrap(),
+            ResourceHints::default(),
+        )]),
+        ..PackManifestOverlay::default()
+    };
+
+    let report = manifest.apply_overlay(&overlay);
+    assert!(report.has_errors());
+    let codes: Vec<_> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(
+        codes,
+        vec![
+            "PACK_OVERLAY_UNKNOWN_FLOW",
+            "PACK_OVERLAY_UNKNOWN_COMPONENT"
+        ]
+    );
+    assert_eq!(manifest, original);
+}
@@ -0,0 +1,136 @@
+use greentic_types::{
+    AvailablePack, PackDependency, PackId, PackKind, PackManifest, PackRef, PackSignatures,
+    ResolutionError, resolve_dependencies,
+};
+use semver::Version;
+
+fn id(value: &str) -> PackId {
+    value.parse().unwrap()
+}
+
+fn dep(pack: &str, req: &str) -> PackDependency {
+    PackDependency {
+        alias: pack.into(),
+        pack_id: id(pack),
+        version_req: req.parse().unwrap(),
+        required_capabilities: Vec::new(),
+    }
+}
+
+fn pack(pack: &str, version: &str, deps: &[(&str, &str)]) -> AvailablePack {
+    let pack_ref = PackRef::new(
+        format!("oci://registry.local/{pack}:{version}"),
+        Version::parse(version).unwrap(),
+        format!("sha256:{pack}-{version}"),
+    );
+    deps.iter().fold(
+        AvailablePack::new(id(pack), pack_ref),
+        |candidate, (name, req)| candidate.with_dependency(dep(name, req)),
+    )
+}
+
+fn root(deps: &[(&str, &str)]) -> PackManifest {
+    PackManifest {
+        schema_version: "pack-v1".into(),
+        pack_id: id("app"),
+        name: None,
+        version: Version::parse("1.0.0").unwrap(),
+        kind: PackKind::Application,
+        publisher: "tests".into(),
+        components: Vec::new(),
+        flows: Vec::new(),
+        dependencies: deps.iter().map(|(name, req)| dep(name, req)).collect(),
+        capabilities: Vec::new(),
+        secret_requirements: Vec::new(),
+        signatures: PackSignatures {
+            signatures: Vec::new(),
+        },
+        bootstrap: None,
+        extensions: None,
+    }
+}
+
+fn order(plan: &greentic_types::ResolutionPlan) -> Vec<String> {
+    plan.install_order
+        .iter()
+        .map(|resolved| format!("{}@{}", resolved.pack_id, resolved.pack_ref.version))
+        .collect()
+}
+
+#[test]
+fn resolves_transitive_dependencies_in_install_order() {
+    let available = vec![
+        pack("http", "1.0.0", &[("core", "^1")]),
+        pack("http", "1.4.0", &[("core", "^1.2")]),
+        pack("http", "2.0.0", &[]),
+        pack("core", "1.1.0", &[]),
+        pack("core", "1.3.0", &[]),
+        pack("auth", "0.3.0", &[("core", ">=1.0, <2")]),
+    ];
+    let plan = resolve_dependencies(&root(&[("http", "^1"), ("auth", "0.3")]), &available)
+        .expect("resolvable");
+
+    assert_eq!(plan.root, id("app"));
+    assert_eq!(order(&plan), ["core@1.3.0", "auth@0.3.0", "http@1.4.0"]);
+    let core = plan.get(&id("core")).unwrap();
+    assert_eq!(core.required_by, vec![id("auth"), id("http")]);
+
+    // Resolution is independent of the order candidates are listed in.
+    let mut reversed = available.clone();
+    reversed.reverse();
+    let again = resolve_dependencies(&root(&[("http", "^1"), ("auth", "0.3")]), &reversed)
+        .expect("resolvable");
+    assert_eq!(again, plan);
+}
+
+#[test]
+fn reports_missing_and_conflicting_requirements() {
+    let available = vec![
+        pack("http", "1.0.0", &[("core", "^2")]),
+        pack("core", "1.0.0", &[]),
+        pack("core", "2.0.0", &[]),
+    ];
+
+    let err = resolve_dependencies(&root(&[("db", "^1")]), &available).unwrap_err();
+    assert_eq!(
+        err,
+        ResolutionError::Missing {
+            pack_id: id("db"),
+            version_req: "^1".parse().unwrap(),
+            required_by: id("app"),
+        }
+    );
+
+    let err =
+        resolve_dependencies(&root(&[("http", "^1"), ("core", "^1")]), &available).unwrap_err();
+    match err {
+        ResolutionError::Conflict {
+            pack_id,
+            requirements,
+        } => {
+            assert_eq!(pack_id, id("core"));
+            assert_eq!(requirements.len(), 2);
+        }
+        other => panic!("expected conflict, got {other:?}"),
+    }
+}
+
+#[test]
+fn detects_cycles() {
+    let available = vec![
+        pack("a", "1.0.0", &[("b", "*")]),
+        pack("b", "1.0.0", &[("a", "*")]),
+    ];
+    let err = resolve_dependencies(&root(&[("a", "^1")]), &available).unwrap_err();
+    assert_eq!(
+        err,
+        ResolutionError::Cycle {
+            path: vec![id("a"), id("b"), id("a")],
+        }
+    );
+    assert_eq!(err.to_string(), "dependency cycle: a -> b -> a");
+
+    let back_to_root = vec![pack("a", "1.0.0", &[("app", "*")])];
+    let err = resolve_dependencies(&root(&[("a", "^1")]), &back_to_root).unwrap_err();
+    assert!(matches!(err, ResolutionError::Cycle { .. }), "{err}");
+}
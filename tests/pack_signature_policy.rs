@@ -0,0 +1,108 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::{
+    ErrorCode, GResult, GreenticError, PackSignatures, Signature, SignatureAlgorithm,
+    SignaturePolicy, SignatureVerifier, SignerRole, verify_signature_policy,
+};
+
+const DIGEST: &[u8] = b"sha256:pack";
+
+/// Toy scheme: a signature is the key id followed by the signed digest.
+struct Concat;
+
+impl SignatureVerifier for Concat {
+    fn verify(&self, signature: &Signature) -> GResult<bool> {
+        if signature.key_id == "revoked" {
+            return Err(GreenticError::new(ErrorCode::NotFound, "key revoked"));
+        }
+        Ok(signature.signature == [signature.key_id.as_bytes(), DIGEST].concat())
+    }
+}
+
+fn sign(key_id: &str) -> Signature {
+    Signature::new(
+        key_id,
+        SignatureAlgorithm::Ed25519,
+        [key_id.as_bytes(), DIGEST].concat(),
+    )
+}
+
+fn policy() -> SignaturePolicy {
+    SignaturePolicy::threshold(
+        2,
+        [
+            ("acme".to_string(), SignerRole::Publisher),
+            ("store".to_string(), SignerRole::Store),
+            ("secops".to_string(), SignerRole::Security),
+            ("revoked".to_string(), SignerRole::Security),
+        ],
+    )
+    .unwrap()
+    .require_role(SignerRole::Store)
+}
+
+#[test]
+fn signature_policy_roundtrips() {
+    let json = serde_json::to_value(policy()).unwrap();
+    assert_eq!(json["required_roles"], serde_json::json!(["store"]));
+    assert_eq!(json["keys"]["secops"], "security");
+    let parsed: SignaturePolicy = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, policy());
+}
+
+#[test]
+fn signature_policy_requires_threshold_and_roles() {
+    let policy = policy();
+    let bundle = PackSignatures {
+        signatures: vec![sign("acme"), sign("stranger"), sign("store"), sign("acme")],
+    };
+    let signers = bundle.verify_policy(&policy, &Concat).unwrap();
+    assert_eq!(signers.into_iter().collect::<Vec<_>>(), ["acme", "store"]);
+
+    let err = verify_signature_policy(&[sign("acme"), sign("acme")], &policy, &Concat).unwrap_err();
+    assert_eq!(err.code, ErrorCode::Unauthenticated);
+    assert_eq!(err.fields["valid"], 1);
+
+    let err =
+        verify_signature_policy(&[sign("acme"), sign("secops")], &policy, &Concat).unwrap_err();
+    assert_eq!(err.fields["role"], "store");
+
+    let mut forged = sign("secops");
+    forged.signature.push(0);
+    let err = verify_signature_policy(&[sign("acme"), sign("store"), forged], &policy, &Concat)
+        .unwrap_err();
+    assert_eq!(err.fields["key_id"], "secops");
+
+    let err = verify_signature_policy(&[sign("revoked")], &policy, &Concat).unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotFound);
+}
+
+#[test]
+fn signature_policy_rejects_unusable_thresholds() {
+    let keys = || [("acme".to_string(), SignerRole::Publisher)];
+    let err = SignaturePolicy::threshold(0, keys()).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert_eq!(err.fields["threshold"], 0);
+    let err = SignaturePolicy::threshold(2, keys()).unwrap_err();
+    assert_eq!(err.fields["keys"], 1);
+
+    for json in [
+        serde_json::json!({ "threshold": 0, "keys": { "acme": "publisher" } }),
+        serde_json::json!({ "threshold": 2, "keys": { "acme": "publisher" } }),
+        serde_json::json!({ "threshold": 1 }),
+    ] {
+        assert!(serde_json::from_value::<SignaturePolicy>(json).is_err());
+    }
+
+    let mut open = policy();
+    open.threshold = 0;
+    open.required_roles.clear();
+    let err = verify_signature_policy(&[], &open, &Concat).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+
+    let mut unreachable = policy();
+    unreachable.threshold = 5;
+    let signatures = [sign("acme"), sign("store"), sign("secops")];
+    let err = verify_signature_policy(&signatures, &unreachable, &Concat).unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+}
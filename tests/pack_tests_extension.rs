@@ -0,0 +1,291 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use greentic_types::{
+    ComponentId, DurationMs, EXT_PACK_TESTS_V1, ErrorCode, ExpectedOutcome, Flow, FlowId, FlowKind,
+    FlowMetadata, MockComponentSpec, MockLatency, MockOperation, MockResponse, NodeId, NodeStatus,
+    OutputMatcher, PackFlowEntry, PackKind, PackManifest, PackSignatures, PackTestSpec,
+    PackTestsV1, RunStatus, TestFixture, TranscriptAssertion, validate_pack_manifest_core,
+};
+use indexmap::IndexMap;
+use semver::Version;
+use serde_json::json;
+
+fn manifest() -> PackManifest {
+    let flow = Flow {
+        schema_version: "flow-v1".into(),
+        id: FlowId::new("main").unwrap(),
+        kind: FlowKind::Messaging,
+        entrypoints: BTreeMap::new(),
+        nodes: IndexMap::default(),
+        metadata: FlowMetadata::default(),
+    };
+    PackManifest {
+        schema_version: "pack-v1".into(),
+        pack_id: "dev.local.tests".parse().unwrap(),
+        name: None,
+        version: Version::parse("0.1.0").unwrap(),
+        kind: PackKind::Application,
+        publisher: "tests".into(),
+        components: Vec::new(),
+        flows: vec![PackFlowEntry {
+            id: FlowId::new("main").unwrap(),
+            kind: FlowKind::Messaging,
+            flow,
+            tags: Vec::new(),
+            entrypoints: Vec::new(),
+        }],
+        dependencies: Vec::new(),
+        capabilities: Vec::new(),
+        secret_requirements: Vec::new(),
+        signatures: PackSignatures {
+            signatures: Vec::new(),
+        },
+        bootstrap: None,
+        extensions: None,
+    }
+}
+
+fn greeting_test() -> PackTestSpec {
+    let mut test = PackTestSpec::new("greets-user", FlowId::new("main").unwrap());
+    test.input = json!({"text": "hi"});
+    test.fixtures.push(TestFixture {
+        name: "profile".into(),
+        path: "tests/fixtures/profile.json".into(),
+        content_type: Some("application/json".into()),
+    });
+    test.expected.output = Some(json!({"text": "hello"}));
+    test.mock_capabilities.push("http".into());
+    test.timeout = Some(DurationMs::from_secs(5));
+    test
+}
+
+#[test]
+fn pack_tests_extension_roundtrips_through_manifest() {
+    let mut manifest = manifest();
+    assert!(manifest.get_pack_tests_v1().unwrap().is_none());
+
+    let tests = PackTestsV1::new(vec![greeting_test()]);
+    manifest.set_pack_tests_v1(tests.clone()).unwrap();
+    assert!(
+        manifest
+            .extensions
+            .as_ref()
+            .unwrap()
+            .contains_key(EXT_PACK_TESTS_V1)
+    );
+    assert_eq!(manifest.get_pack_tests_v1().unwrap(), Some(tests));
+    assert!(validate_pack_manifest_core(&manifest).is_empty());
+
+    let decoded: PackTestSpec =
+        serde_json::from_value(json!({"name": "smoke", "flow_id": "main"})).unwrap();
+    assert_eq!(decoded.expected, ExpectedOutcome::default());
+    assert_eq!(decoded.expected.status, RunStatus::Success);
+}
+
+#[test]
+fn pack_tests_validation_reports_problems() {
+    let mut duplicate = greeting_test();
+    duplicate.flow_id = FlowId::new("missing").unwrap();
+    duplicate.fixtures[0].path = "../secrets.json".into();
+    duplicate.mock_capabilities.push(" ".into());
+
+    let tests = PackTestsV1::new(vec![greeting_test(), duplicate]);
+    let codes: Vec<_> = tests
+        .validate(&manifest())
+        .into_iter()
+        .map(|diag| diag.code)
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "PACK_TEST_NAME_DUPLICATE",
+            "PACK_TEST_FLOW_MISSING",
+            "PACK_TEST_FIXTURE_PATH_INVALID",
+            "PACK_TEST_MOCK_CAPABILITY_EMPTY",
+        ]
+    );
+
+    let mut manifest = manifest();
+    manifest.set_pack_tests_v1(tests).unwrap();
+    let core = validate_pack_manifest_core(&manifest);
+    assert_eq!(core.len(), 4);
+}
+
+#[test]
+fn transcript_assertions_match_node_output() {
+    let output = json!({"reply": {"text": "hello"}, "items": [{"id": 1}, {"id": 1}]});
+
+    let mut assertion = TranscriptAssertion::node_ok(NodeId::new("reply").unwrap());
+    assert_eq!(assertion.evaluate(NodeStatus::Ok, &output), Some(true));
+    assert_eq!(assertion.evaluate(NodeStatus::Error, &output), Some(false));
+
+    assertion.output = Some(OutputMatcher::JsonPath {
+        path: "$.reply.text".into(),
+        equals: Some(json!("hello")),
+    });
+    assert_eq!(assertion.evaluate(NodeStatus::Ok, &output), Some(true));
+
+    let wildcard = OutputMatcher::JsonPath {
+        path: "$.items[*].id".into(),
+        equals: Some(json!(1)),
+    };
+    assert_eq!(wildcard.matches(&output), Some(true));
+    let absent = OutputMatcher::JsonPath {
+        path: "$.reply.missing".into(),
+        equals: None,
+    };
+    assert_eq!(absent.matches(&output), Some(false));
+    let exact = OutputMatcher::Exact {
+        value: json!({"text": "hello"}),
+    };
+    assert_eq!(exact.matches(&output["reply"]), Some(true));
+    let regex = OutputMatcher::Regex {
+        pattern: "^hel+o$".into(),
+        path: Some("$.reply.text".into()),
+    };
+    assert_eq!(regex.matches(&output), None);
+
+    let encoded = serde_json::to_value(&regex).unwrap();
+    assert_eq!(
+        encoded,
+        json!({"kind": "regex", "pattern": "^hel+o$", "path": "$.reply.text"})
+    );
+}
+
+#[test]
+fn transcript_assertion_validation_reports_problems() {
+    let mut manifest = manifest();
+    let node_id = NodeId::new("reply").unwrap();
+    let node: greentic_types::Node = serde_json::from_value(json!({
+        "id": "reply",
+        "component": {"id": "reply.component"},
+        "input": {"mapping": null},
+        "output": {"mapping": null},
+        "routing": "end"
+    }))
+    .unwrap();
+    manifest.flows[0].flow.nodes.insert(node_id.clone(), node);
+
+    let mut test = greeting_test();
+    test.assertions.push(TranscriptAssertion {
+        node_id: node_id.clone(),
+        status: Some(NodeStatus::Ok),
+        output: Some(OutputMatcher::JsonPath {
+            path: "$..text".into(),
+            equals: None,
+        }),
+    });
+    test.assertions.push(TranscriptAssertion::node_ok(
+        NodeId::new("unknown").unwrap(),
+    ));
+    test.assertions.push(TranscriptAssertion {
+        node_id,
+        status: None,
+        output: Some(OutputMatcher::Regex {
+            pattern: String::new(),
+            path: None,
+        }),
+    });
+
+    let codes: Vec<_> = PackTestsV1::new(vec![test])
+        .validate(&manifest)
+        .into_iter()
+        .map(|diag| (diag.code, diag.path.unwrap_or_default()))
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            (
+                "PACK_TEST_ASSERTION_MATCHER_INVALID".to_owned(),
+                "tests.greets-user.assertions.0.output".to_owned()
+            ),
+            (
+                "PACK_TEST_ASSERTION_NODE_MISSING".to_owned(),
+                "tests.greets-user.assertions.1.node_id".to_owned()
+            ),
+            (
+                "PACK_TEST_ASSERTION_MATCHER_INVALID".to_owned(),
+                "tests.greets-user.assertions.2.output".to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unsupported_schema_version_is_rejected() {
+    let mut tests = PackTestsV1::new(Vec::new());
+    tests.schema_version = 2;
+    assert!(manifest().set_pack_tests_v1(tests.clone()).is_err());
+    assert!(PackTestsV1::from_extension_value(&serde_json::to_value(&tests).unwrap()).is_err());
+}
+
+#[test]
+fn component_mocks_answer_and_validate() {
+    let component: ComponentId = "weather.api".parse().unwrap();
+    let mut mock = MockComponentSpec::new(component.clone())
+        .with_output("forecast", json!({"temp": 21}))
+        .with_latency(MockLatency {
+            base: DurationMs::from_millis(100),
+            jitter: Some(DurationMs::from_millis(50)),
+        });
+    mock.operations.push(MockOperation {
+        operation: "alerts".into(),
+        responses: vec![
+            MockResponse::Error {
+                code: ErrorCode::Unavailable,
+                message: "upstream down".into(),
+            },
+            MockResponse::Output { value: json!([]) },
+        ],
+        latency: Some(MockLatency::fixed(DurationMs::ZERO)),
+    });
+
+    assert_eq!(
+        mock.response_for("forecast", 5),
+        Some(&MockResponse::Output {
+            value: json!({"temp": 21})
+        })
+    );
+    assert!(matches!(
+        mock.response_for("alerts", 0),
+        Some(MockResponse::Error { .. })
+    ));
+    assert_eq!(
+        mock.response_for("alerts", 9),
+        Some(&MockResponse::Output { value: json!([]) })
+    );
+    assert_eq!(mock.response_for("unknown", 0), None);
+    assert_eq!(
+        mock.latency_for("forecast").unwrap().delay(175),
+        DurationMs::from_millis(122)
+    );
+    assert_eq!(
+        mock.latency_for("alerts").unwrap().delay(175),
+        DurationMs::ZERO
+    );
+    mock.validate().unwrap();
+
+    let mut broken = MockComponentSpec::new(component);
+    broken.operations.push(MockOperation {
+        operation: "forecast".into(),
+        responses: Vec::new(),
+        latency: None,
+    });
+    let mut test = greeting_test();
+    test.mocks = vec![mock.clone(), mock, broken];
+    let codes: Vec<_> = PackTestsV1::new(vec![test])
+        .validate(&manifest())
+        .into_iter()
+        .map(|diag| diag.code)
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "PACK_TEST_MOCK_DUPLICATE",
+            "PACK_TEST_MOCK_DUPLICATE",
+            "PACK_TEST_MOCK_INVALID"
+        ]
+    );
+}
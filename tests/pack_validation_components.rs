@@ -9,9 +9,9 @@ use greentic_types::pack::extensions::component_sources::{
 use greentic_types::pack_manifest::{ExtensionInline, ExtensionRef};
 use greentic_types::{
     ComponentCapabilities, ComponentManifest, ComponentOperation, ComponentProfiles, Flow,
-    FlowComponentRef, FlowId, FlowKind, FlowMetadata, InputMapping, Node, OutputMapping,
-    PackFlowEntry, PackId, PackKind, PackManifest, PackSignatures, ResourceHints, Routing,
-    TelemetryHints, validate_pack_manifest_core,
+    FlowComponentRef, FlowId, FlowKind, FlowMetadata, InputMapping, Node, NodeId, OutputMapping,
+    PackFlowEntry, PackId, PackKind, PackManifest, PackSignatures, Ratio, ResourceHints, Routing,
+    Severity, StateCapabilities, TelemetryHints, validate_pack_manifest_core,
 };
 use indexmap::IndexMap;
 use semver::Version;
@@ -36,6 +36,7 @@ fn flow_with_component(component_id: &str) -> PackFlowEntry {
             },
             routing: Routing::End,
             telemetry: TelemetryHints::default(),
+            capabilities_override: None,
         },
     );
 
@@ -97,6 +98,7 @@ fn sample_component(id: &str) -> ComponentManifest {
         }],
         config_schema: None,
         resources: ResourceHints::default(),
+        targets: Vec::new(),
         dev_flows: BTreeMap::new(),
     }
 }
@@ -185,3 +187,71 @@ fn flow_component_resolves_via_manifest_components() {
         "explicit components should not warn"
     );
 }
+
+#[test]
+fn flow_telemetry_sample_ratio_out_of_range_is_rejected() {
+    let err = serde_json::from_value::<TelemetryHints>(serde_json::json!({ "sample_ratio": 1.5 }))
+        .expect_err("sample ratios above 1.0 should be rejected");
+    assert!(err.to_string().contains("invalid ratio"), "{err}");
+    assert!(Ratio::new(-0.1).is_err());
+}
+
+#[test]
+fn node_capability_override_must_narrow_component_capabilities() {
+    let mut component = sample_component("explicit");
+    component.capabilities.wasi.clocks = true;
+    component.capabilities.host.state = Some(StateCapabilities {
+        read: true,
+        write: true,
+    });
+    let mut manifest = base_manifest();
+    manifest.components = vec![component.clone()];
+    let mut entry = flow_with_component("explicit");
+    let start: NodeId = "start".parse().unwrap();
+    let node = entry.flow.nodes.get_mut(&start).unwrap();
+    let mut narrowed = ComponentCapabilities::default();
+    narrowed.host.state = Some(StateCapabilities {
+        read: true,
+        write: false,
+    });
+    node.capabilities_override = Some(narrowed.clone());
+    assert_eq!(node.effective_capabilities(&component), &narrowed);
+    manifest.flows = vec![entry.clone()];
+
+    let diagnostics = validate_pack_manifest_core(&manifest);
+    assert!(
+        diagnostics
+            .iter()
+            .all(|diag| diag.code != "PACK_NODE_CAPABILITIES_EXCEED_COMPONENT"),
+        "narrower overrides are allowed"
+    );
+
+    let node = entry.flow.nodes.get_mut(&start).unwrap();
+    let mut broadened = narrowed;
+    broadened.wasi.random = true;
+    node.capabilities_override = Some(broadened);
+    manifest.flows = vec![entry];
+
+    let diagnostics = validate_pack_manifest_core(&manifest);
+    let paths: Vec<_> = diagnostics
+        .iter()
+        .filter(|diag| diag.code == "PACK_NODE_CAPABILITIES_EXCEED_COMPONENT")
+        .filter_map(|diag| diag.path.as_deref())
+        .collect();
+    assert_eq!(
+        paths,
+        ["flows.main.nodes.start.capabilities_override.wasi.random"]
+    );
+
+    let mut aliased = flow_with_component("explicit");
+    let node = aliased.flow.nodes.get_mut(&start).unwrap();
+    node.component.pack_alias = Some("vendor".into());
+    node.capabilities_override = Some(ComponentCapabilities::default());
+    manifest.flows = vec![aliased];
+    let diagnostics = validate_pack_manifest_core(&manifest);
+    assert!(diagnostics.iter().any(|diag| {
+        diag.code == "PACK_NODE_CAPABILITIES_UNVERIFIED"
+            && diag.severity == Severity::Error
+            && diag.path.as_deref() == Some("flows.main.nodes.start.capabilities_override")
+    }));
+}
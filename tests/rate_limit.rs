@@ -0,0 +1,105 @@
+use greentic_types::{
+    DurationMs, ErrorCode, RateLimitPolicy, RateLimitScope, RateLimitState, TenantCtx, UnixMillis,
+};
+
+fn at(millis: i64) -> UnixMillis {
+    UnixMillis::from_millis(millis)
+}
+
+#[test]
+fn bucket_allows_burst_then_refills() {
+    let policy =
+        RateLimitPolicy::new(RateLimitScope::Tenant, 10, DurationMs::from_secs(1)).with_burst(3);
+    policy.validate().unwrap();
+    let mut state = RateLimitState::full(&policy, at(0));
+
+    let remaining: Vec<_> = (0..3)
+        .map(|_| state.check(&policy, at(0)))
+        .map(|decision| {
+            assert!(decision.allowed);
+            decision.remaining
+        })
+        .collect();
+    assert_eq!(remaining, vec![2, 1, 0]);
+
+    let denied = state.check(&policy, at(0));
+    assert!(!denied.allowed);
+    assert_eq!(denied.limit, 3);
+    assert_eq!(denied.retry_after, Some(DurationMs::from_millis(100)));
+    assert_eq!(
+        state.check(&policy, at(50)).retry_after,
+        Some(DurationMs::from_millis(50))
+    );
+    let err = denied.into_result().unwrap_err();
+    assert_eq!(err.code, ErrorCode::RateLimited);
+
+    let refilled = state.check(&policy, at(250));
+    assert!(refilled.allowed);
+    assert_eq!(refilled.remaining, 1);
+    assert_eq!(state.updated_at, at(200));
+
+    let idle = state.check(&policy, at(10_000));
+    assert_eq!(idle.remaining, 2);
+    assert_eq!(state.updated_at, at(10_000));
+}
+
+#[test]
+fn policy_validation_and_scope_keys() {
+    let window = DurationMs::from_secs(60);
+    assert!(
+        RateLimitPolicy::new(RateLimitScope::User, 0, window)
+            .validate()
+            .is_err()
+    );
+    assert!(
+        RateLimitPolicy::new(RateLimitScope::User, 5, DurationMs::ZERO)
+            .validate()
+            .is_err()
+    );
+    assert!(
+        RateLimitPolicy::new(RateLimitScope::User, 5, window)
+            .with_burst(0)
+            .validate()
+            .is_err()
+    );
+
+    let ctx = TenantCtx::new("prod".parse().unwrap(), "acme".parse().unwrap())
+        .with_team(Some("ops".parse().unwrap()));
+    let key = |scope| RateLimitPolicy::new(scope, 5, window).scope_key(&ctx);
+    assert_eq!(key(RateLimitScope::Tenant).as_deref(), Some("prod:acme"));
+    assert_eq!(
+        key(RateLimitScope::Team).as_deref(),
+        Some("prod:acme:team=ops")
+    );
+    assert_eq!(key(RateLimitScope::User), None);
+    assert_eq!(key(RateLimitScope::Session), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rate_limit_types_roundtrip() {
+    let policy =
+        RateLimitPolicy::new(RateLimitScope::Session, 30, DurationMs::from_secs(60)).with_burst(5);
+    let value = serde_json::to_value(policy).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "scope": "session",
+            "max_requests": 30,
+            "window": 60_000,
+            "burst": 5
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<RateLimitPolicy>(value).unwrap(),
+        policy
+    );
+
+    let mut state = RateLimitState::full(&policy, at(1_000));
+    let decision = state.check(&policy, at(1_000));
+    let json = serde_json::to_string(&decision).unwrap();
+    assert_eq!(json, r#"{"allowed":true,"limit":5,"remaining":4}"#);
+    let stored: RateLimitState =
+        serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+    assert_eq!(stored, state);
+}
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use greentic_types::{
+    DEFAULT_REDACTION_PLACEHOLDER, ErrorCode, RedactionKey, RedactionPath, RedactionSegment,
+    RedactionStrategy, apply_redactions, apply_redactions_with_keys,
+};
+use serde_json::{Value, json};
+
+fn hash_strategy(key_ref: &str) -> RedactionStrategy {
+    RedactionStrategy::Hash {
+        key_ref: key_ref.into(),
+    }
+}
+
+fn keys(entries: &[(&str, RedactionKey)]) -> BTreeMap<String, RedactionKey> {
+    entries
+        .iter()
+        .map(|(name, key)| ((*name).to_owned(), key.clone()))
+        .collect()
+}
+
+fn paths(exprs: &[&str]) -> Vec<RedactionPath> {
+    exprs
+        .iter()
+        .map(|expr| RedactionPath::parse(expr).unwrap())
+        .collect()
+}
+
+fn sample() -> Value {
+    json!({
+        "user": {
+            "name": "Ada",
+            "password": "hunter2",
+            "cards": [
+                {"number": "4242", "cvc": "123"},
+                {"number": "5555", "cvc": "456"}
+            ]
+        },
+        "tokens": ["a", "b", "c"],
+        "headers": {"authorization": "Bearer x", "cookie": "y"}
+    })
+}
+
+#[test]
+fn parses_supported_segments() {
+    let path = RedactionPath::parse("$.user['full name'][\"x\\\"y\"][0][-1][*].*").unwrap();
+    assert_eq!(
+        path.segments().unwrap(),
+        vec![
+            RedactionSegment::Key("user".into()),
+            RedactionSegment::Key("full name".into()),
+            RedactionSegment::Key("x\"y".into()),
+            RedactionSegment::Index(0),
+            RedactionSegment::Index(-1),
+            RedactionSegment::Wildcard,
+            RedactionSegment::Wildcard,
+        ]
+    );
+    assert!(
+        RedactionPath::parse("$")
+            .unwrap()
+            .segments()
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn rejects_unsupported_syntax() {
+    for expr in [
+        "$..password",
+        "$.items[?(@.secret)]",
+        "$.items[0:2]",
+        "$.items[0,1]",
+        "$.a[",
+        "$.a['b",
+        "$.",
+        "$x",
+    ] {
+        let mut value = sample();
+        let err =
+            apply_redactions(&mut value, &paths(&[expr]), RedactionStrategy::Drop).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput, "{expr}");
+        assert_eq!(value, sample(), "{expr} must leave the document untouched");
+    }
+}
+
+#[test]
+fn placeholder_replaces_matches() {
+    let mut value = sample();
+    let count = apply_redactions(
+        &mut value,
+        &paths(&["$.user.password", "$.user.cards[*].cvc", "$.missing.field"]),
+        RedactionStrategy::default(),
+    )
+    .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(value["user"]["password"], DEFAULT_REDACTION_PLACEHOLDER);
+    assert_eq!(
+        value["user"]["cards"][0]["cvc"],
+        DEFAULT_REDACTION_PLACEHOLDER
+    );
+    assert_eq!(
+        value["user"]["cards"][1]["cvc"],
+        DEFAULT_REDACTION_PLACEHOLDER
+    );
+    assert_eq!(value["user"]["cards"][1]["number"], "5555");
+    assert_eq!(value["user"]["name"], "Ada");
+}
+
+#[test]
+fn wildcard_and_negative_index() {
+    let mut value = sample();
+    let count = apply_redactions(
+        &mut value,
+        &paths(&["$.headers.*", "$.tokens[-1]", "$.tokens[7]"]),
+        RedactionStrategy::Placeholder("***".into()),
+    )
+    .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(
+        value["headers"],
+        json!({"authorization": "***", "cookie": "***"})
+    );
+    assert_eq!(value["tokens"], json!(["a", "b", "***"]));
+}
+
+#[test]
+fn hash_is_stable_and_correlatable() {
+    let mut value = json!({"a": {"email": "x@example.com"}, "b": {"email": "x@example.com"}});
+    let keys = keys(&[("tenant-a", RedactionKey::derive(b"tenant-a"))]);
+    let strategy = hash_strategy("tenant-a");
+    apply_redactions_with_keys(&mut value, &paths(&["$[*].email"]), strategy.clone(), &keys)
+        .unwrap();
+    let hashed = value["a"]["email"].as_str().unwrap();
+    assert!(hashed.starts_with("blake3-keyed:"));
+    assert_eq!(hashed.len(), "blake3-keyed:".len() + 64);
+    assert_eq!(value["a"]["email"], value["b"]["email"]);
+
+    let mut other = json!({"email": "y@example.com"});
+    apply_redactions_with_keys(&mut other, &paths(&["$.email"]), strategy, &keys).unwrap();
+    assert_ne!(other["email"], value["a"]["email"]);
+}
+
+#[test]
+fn hash_depends_on_key() {
+    let redact_with = |key: RedactionKey| {
+        let mut value = json!({"email": "x@example.com"});
+        apply_redactions_with_keys(
+            &mut value,
+            &paths(&["$.email"]),
+            hash_strategy("k"),
+            &keys(&[("k", key)]),
+        )
+        .unwrap();
+        value
+    };
+    assert_ne!(
+        redact_with(RedactionKey::new([1; 32])),
+        redact_with(RedactionKey::new([2; 32]))
+    );
+    assert_eq!(
+        format!("{:?}", RedactionKey::new([7; 32])),
+        "RedactionKey(..)"
+    );
+}
+
+#[test]
+fn hash_strategy_serializes_only_the_key_reference() {
+    let strategy = hash_strategy("tenant-a/redaction");
+    let encoded = serde_json::to_value(&strategy).unwrap();
+    assert_eq!(encoded, json!({"hash": {"key_ref": "tenant-a/redaction"}}));
+    let decoded: RedactionStrategy = serde_json::from_value(encoded).unwrap();
+    assert_eq!(decoded, strategy);
+}
+
+#[test]
+fn hash_without_its_key_fails_untouched() {
+    let original = json!({"email": "x@example.com"});
+    let mut value = original.clone();
+    let err = apply_redactions(&mut value, &paths(&["$.email"]), hash_strategy("k")).unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotFound);
+
+    let other = keys(&[("other", RedactionKey::new([1; 32]))]);
+    let err =
+        apply_redactions_with_keys(&mut value, &paths(&["$.email"]), hash_strategy("k"), &other)
+            .unwrap_err();
+    assert_eq!(err.code, ErrorCode::NotFound);
+    assert_eq!(value, original);
+}
+
+#[test]
+fn drop_removes_members_and_elements() {
+    let mut value = sample();
+    let count = apply_redactions(
+        &mut value,
+        &paths(&["$.user.password", "$.user.cards[*].cvc", "$.tokens[0]"]),
+        RedactionStrategy::Drop,
+    )
+    .unwrap();
+    assert_eq!(count, 4);
+    assert!(value["user"].get("password").is_none());
+    assert_eq!(
+        value["user"]["cards"],
+        json!([{"number": "4242"}, {"number": "5555"}])
+    );
+    assert_eq!(value["tokens"], json!(["b", "c"]));
+
+    let count = apply_redactions(
+        &mut value,
+        &paths(&["$.headers[*]"]),
+        RedactionStrategy::Drop,
+    )
+    .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(value["headers"], json!({}));
+}
+
+#[test]
+fn drop_paths_apply_in_order() {
+    let mut value = json!({"items": [1, 2, 3]});
+    apply_redactions(
+        &mut value,
+        &paths(&["$.items[0]", "$.items[0]"]),
+        RedactionStrategy::Drop,
+    )
+    .unwrap();
+    assert_eq!(value["items"], json!([3]));
+}
+
+#[test]
+fn root_path_and_type_mismatches() {
+    let mut value = json!({"a": [1, 2]});
+    assert_eq!(
+        apply_redactions(
+            &mut value,
+            &paths(&["$.a.b", "$.a[0].c", "$['a'][5]"]),
+            RedactionStrategy::Drop
+        )
+        .unwrap(),
+        0
+    );
+    assert_eq!(value, json!({"a": [1, 2]}));
+
+    let count = apply_redactions(&mut value, &paths(&["$"]), RedactionStrategy::default()).unwrap();
+    assert_eq!(count, 1);
+    assert_eq!(value, json!(DEFAULT_REDACTION_PLACEHOLDER));
+}
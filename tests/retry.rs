@@ -0,0 +1,139 @@
+use greentic_types::{BackoffKind, DurationMs, ErrorCode, NodeError, RetryPolicy};
+
+#[test]
+fn exponential_delay_doubles_and_caps() {
+    let policy = RetryPolicy::exponential(
+        6,
+        DurationMs::from_millis(100),
+        DurationMs::from_millis(500),
+    );
+    let delays: Vec<_> = (1..=6)
+        .map(|attempt| policy.next_delay(attempt).map(|delay| delay.as_millis()))
+        .collect();
+    assert_eq!(
+        delays,
+        vec![Some(100), Some(200), Some(400), Some(500), Some(500), None]
+    );
+    assert_eq!(policy.next_delay(0), None);
+}
+
+#[test]
+fn fixed_delay_is_constant_and_large_attempts_saturate() {
+    let fixed = RetryPolicy::fixed(3, DurationMs::from_secs(1));
+    assert_eq!(fixed.next_delay(2), Some(DurationMs::from_secs(1)));
+
+    let uncapped = RetryPolicy {
+        max_delay: None,
+        ..RetryPolicy::exponential(u32::MAX, DurationMs::from_millis(1), DurationMs::ZERO)
+    };
+    assert_eq!(
+        uncapped.next_delay(200),
+        Some(DurationMs::from_millis(u64::MAX))
+    );
+}
+
+#[test]
+fn jitter_stays_within_exponential_window() {
+    let policy =
+        RetryPolicy::exponential(5, DurationMs::from_millis(100), DurationMs::from_secs(10))
+            .with_backoff(BackoffKind::Jitter);
+    assert_eq!(policy.next_delay(3), Some(DurationMs::from_millis(400)));
+    for entropy in [0, 1, 399, 400, 401, u64::MAX] {
+        let delay = policy.jittered_delay(3, entropy).expect("delay");
+        assert!(delay.as_millis() <= 400, "{delay}");
+    }
+    assert_eq!(
+        policy.jittered_delay(3, 401),
+        Some(DurationMs::from_millis(0))
+    );
+}
+
+#[test]
+fn retry_on_filters_codes() {
+    let policy = RetryPolicy::fixed(3, DurationMs::from_millis(10))
+        .with_retry_on([ErrorCode::Timeout, ErrorCode::Unavailable]);
+    assert!(policy.should_retry(ErrorCode::Timeout, 1));
+    assert!(!policy.should_retry(ErrorCode::InvalidInput, 1));
+    assert!(!policy.should_retry(ErrorCode::Timeout, 3));
+}
+
+#[test]
+fn node_error_prefers_policy_over_backoff() {
+    let plain = NodeError::new("E", "boom").with_retry(Some(DurationMs::from_millis(250)));
+    assert_eq!(
+        plain.next_retry_delay(ErrorCode::Internal, 1),
+        Some(DurationMs::from_millis(250))
+    );
+
+    let err = NodeError::new("E", "boom")
+        .with_retry_policy(RetryPolicy::fixed(2, DurationMs::from_millis(50)));
+    assert!(err.retryable);
+    assert_eq!(
+        err.next_retry_delay(ErrorCode::Internal, 1),
+        Some(DurationMs::from_millis(50))
+    );
+    assert_eq!(err.next_retry_delay(ErrorCode::Internal, 2), None);
+    assert_eq!(err.to_chain().retry_policy, err.retry_policy);
+
+    assert_eq!(
+        NodeError::new("E", "fatal").next_retry_delay(ErrorCode::Internal, 1),
+        None
+    );
+}
+
+#[test]
+fn node_error_retry_delay_honors_retry_on() {
+    let err = NodeError::new("E", "slow").with_retry_policy(
+        RetryPolicy::fixed(3, DurationMs::from_millis(20)).with_retry_on([ErrorCode::Timeout]),
+    );
+    assert_eq!(
+        err.next_retry_delay(ErrorCode::Timeout, 1),
+        Some(DurationMs::from_millis(20))
+    );
+    assert_eq!(err.next_retry_delay(ErrorCode::InvalidInput, 1), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn retry_policy_serializes_with_defaults() {
+    let policy: RetryPolicy =
+        serde_json::from_str(r#"{"max_attempts":3,"initial_delay":"2s"}"#).expect("deserialize");
+    assert_eq!(policy, RetryPolicy::fixed(3, DurationMs::from_secs(2)));
+
+    let json = serde_json::to_value(
+        RetryPolicy::fixed(3, DurationMs::from_millis(5)).with_retry_on([ErrorCode::RateLimited]),
+    )
+    .expect("serialize");
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "max_attempts": 3,
+            "backoff": "fixed",
+            "initial_delay": 5,
+            "retry_on": ["rate_limited"],
+        })
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn node_error_chain_backoff_accepts_duration_strings() {
+    let chain: greentic_types::NodeErrorChain = serde_json::from_value(serde_json::json!({
+        "code": "E",
+        "message": "boom",
+        "retryable": true,
+        "backoff_ms": "1m30s"
+    }))
+    .unwrap();
+    assert_eq!(chain.backoff_ms, Some(DurationMs::from_secs(90)));
+    assert_eq!(serde_json::to_value(&chain).unwrap()["backoff_ms"], 90_000);
+    assert!(
+        serde_json::from_value::<greentic_types::NodeErrorChain>(serde_json::json!({
+            "code": "E",
+            "message": "boom",
+            "retryable": true,
+            "backoff_ms": "soon"
+        }))
+        .is_err()
+    );
+}
@@ -0,0 +1,34 @@
+use greentic_types::{NodeStatus, RunStatus};
+
+#[test]
+fn run_status_classification() {
+    assert!(!RunStatus::Pending.is_terminal());
+    assert!(!RunStatus::Running.is_terminal());
+    assert!(RunStatus::Success.is_terminal());
+    assert!(RunStatus::PartialFailure.is_terminal());
+    assert!(RunStatus::Failure.is_terminal());
+
+    assert!(RunStatus::Failure.is_retryable());
+    assert!(!RunStatus::PartialFailure.is_retryable());
+    assert!(!RunStatus::Success.is_retryable());
+    assert!(!RunStatus::Running.is_retryable());
+}
+
+#[test]
+fn run_status_transitions() {
+    assert!(RunStatus::Pending.can_transition_to(RunStatus::Running));
+    assert!(RunStatus::Pending.can_transition_to(RunStatus::Failure));
+    assert!(!RunStatus::Pending.can_transition_to(RunStatus::Success));
+    assert!(RunStatus::Running.can_transition_to(RunStatus::PartialFailure));
+    assert!(!RunStatus::Running.can_transition_to(RunStatus::Pending));
+    assert!(RunStatus::Failure.can_transition_to(RunStatus::Pending));
+    assert!(!RunStatus::Success.can_transition_to(RunStatus::Pending));
+    assert!(!RunStatus::Success.can_transition_to(RunStatus::Running));
+}
+
+#[test]
+fn node_status_failure() {
+    assert!(NodeStatus::Error.is_failure());
+    assert!(!NodeStatus::Ok.is_failure());
+    assert!(!NodeStatus::Skipped.is_failure());
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "schemars")]
 
-use greentic_types::{Outcome, PackRef, SpanContext, TenantCtx};
+use greentic_types::{Outcome, PackRef, ResourceHints, SpanContext, TenantCtx};
 use schemars::{JsonSchema, schema_for};
 use serde_json::Value;
 
@@ -58,3 +58,66 @@ fn outcome_schema_enumerates_variants() {
         .unwrap_or_default();
     assert!(variants >= 3, "Outcome schema should declare variants");
 }
+
+#[cfg(feature = "schema")]
+#[test]
+fn embedded_schemas_resolve_by_id_and_slug() {
+    use greentic_types::schema::{embedded, embedded_schema};
+    use greentic_types::{HashDigest, ids};
+
+    let by_id = embedded_schema(ids::PACK_MANIFEST).expect("pack manifest schema");
+    let by_slug = embedded_schema("pack-manifest").expect("pack manifest slug");
+    assert!(std::ptr::eq(by_id, by_slug));
+    assert_eq!(by_id.slug(), "pack-manifest");
+    assert_eq!(by_id.digest(), HashDigest::blake3_of(by_id.json.as_bytes()));
+    assert_eq!(by_id.etag(), format!("\"{}\"", by_id.digest_hex));
+    assert!(embedded_schema("no-such-schema").is_none());
+
+    for schema in embedded() {
+        let value: Value = serde_json::from_str(schema.json).expect("schema is JSON");
+        assert_eq!(value.get("$id").and_then(Value::as_str), Some(schema.id));
+    }
+}
+
+#[cfg(all(feature = "schema", feature = "otel-keys"))]
+#[test]
+fn embedded_schemas_match_generated() {
+    use greentic_types::schema::embedded;
+
+    let out_dir = std::env::temp_dir().join(format!("greentic-schemas-{}", std::process::id()));
+    greentic_types::write_all_schemas(&out_dir).expect("write schemas");
+    let mut generated = std::fs::read_dir(&out_dir)
+        .expect("read schemas")
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    generated.sort();
+    let mut committed = embedded()
+        .iter()
+        .map(|schema| schema.file_name.to_owned())
+        .collect::<Vec<_>>();
+    committed.sort();
+    assert_eq!(committed, generated);
+
+    for schema in embedded() {
+        let fresh = std::fs::read_to_string(out_dir.join(schema.file_name)).unwrap();
+        assert!(
+            fresh == schema.json,
+            "schemas/v1/{} is stale; run `cargo run --bin export-schemas --all-features -- schemas/v1`",
+            schema.file_name
+        );
+    }
+    std::fs::remove_dir_all(&out_dir).ok();
+}
+
+#[test]
+fn resource_hints_memory_is_described_in_mebibytes() {
+    let value = schema_value::<ResourceHints>();
+    let description = value
+        .pointer("/$defs/MebibyteSize/description")
+        .and_then(Value::as_str)
+        .expect("MebibyteSize definition");
+    assert!(
+        description.starts_with("Size in mebibytes"),
+        "{description}"
+    );
+}
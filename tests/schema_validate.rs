@@ -0,0 +1,51 @@
+#![cfg(feature = "schema-validate")]
+
+use greentic_types::schema::validate_value;
+use greentic_types::{Limits, ids};
+use serde_json::{Value, json};
+
+#[test]
+fn valid_documents_pass_by_id_and_slug() {
+    let limits = serde_json::to_value(Limits::default()).unwrap();
+    validate_value(ids::LIMITS, &limits).unwrap();
+    validate_value("limits", &limits).unwrap();
+}
+
+#[test]
+fn invalid_documents_report_violations() {
+    let document = json!({"memory_mb": 256, "wall_time_ms": 1000, "files": "many"});
+    let violations = validate_value(ids::LIMITS, &document).unwrap_err();
+    assert_eq!(violations.len(), 1, "{violations:?}");
+    assert_eq!(violations[0].instance_path, "/files");
+
+    let missing = validate_value(ids::LIMITS, &json!({})).unwrap_err();
+    assert!(!missing.is_empty());
+
+    let unknown = validate_value("no-such-schema", &Value::Null).unwrap_err();
+    assert_eq!(unknown.len(), 1);
+    assert!(unknown[0].message.contains("unknown schema"));
+}
+
+#[test]
+fn every_exported_schema_compiles() {
+    let dir = std::env::temp_dir().join(format!("greentic-schema-validate-{}", std::process::id()));
+    greentic_types::write_all_schemas(&dir).unwrap();
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        let slug = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".schema.json"))
+            .unwrap()
+            .to_owned();
+        if let Err(violations) = validate_value(&slug, &Value::Null) {
+            assert!(
+                violations
+                    .iter()
+                    .all(|violation| !violation.message.contains("failed to compile")),
+                "{slug}: {violations:?}"
+            );
+        }
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
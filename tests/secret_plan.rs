@@ -0,0 +1,145 @@
+use greentic_types::{
+    AvailableSecret, SecretFormat, SecretKey, SecretRequirement, SecretResolutionStatus,
+    SecretScope, SecretSource, plan_secrets,
+};
+
+fn scope(tenant: &str, team: Option<&str>) -> SecretScope {
+    SecretScope {
+        env: "prod".into(),
+        tenant: tenant.into(),
+        team: team.map(Into::into),
+    }
+}
+
+fn requirement(key: &str, required: bool, scope: Option<SecretScope>) -> SecretRequirement {
+    let mut requirement = SecretRequirement::default();
+    requirement.key = SecretKey::new(key).unwrap();
+    requirement.required = required;
+    requirement.scope = scope;
+    requirement
+}
+
+fn vault(key: &str, scope: Option<SecretScope>) -> AvailableSecret {
+    let mut secret = AvailableSecret::new(
+        SecretKey::new(key).unwrap(),
+        SecretSource::Vault {
+            path: format!("kv/{key}"),
+        },
+    );
+    secret.scope = scope;
+    secret
+}
+
+#[test]
+fn plan_reports_resolved_missing_and_forbidden_secrets() {
+    let mut api = requirement("api_key", true, Some(scope("acme", Some("ops"))));
+    api.format = Some(SecretFormat::Json);
+    let requirements = [
+        api,
+        requirement("db_password", true, Some(scope("acme", None))),
+        requirement("webhook_token", false, None),
+        requirement("db_password", false, None),
+    ];
+    let mut tenant_wide = vault("api_key", Some(scope("acme", None)));
+    tenant_wide.format = Some(SecretFormat::Text);
+    let available = [
+        vault("db_password", Some(scope("globex", None))),
+        tenant_wide,
+        vault("api_key", None),
+    ];
+
+    let plan = plan_secrets(&requirements, &available);
+    assert_eq!(plan.entries.len(), 4);
+
+    let api = plan.get(&SecretKey::new("api_key").unwrap()).unwrap();
+    assert_eq!(api.status, SecretResolutionStatus::Resolved);
+    assert_eq!(
+        api.source,
+        Some(SecretSource::Vault {
+            path: "kv/api_key".into()
+        })
+    );
+
+    let db = plan.get(&SecretKey::new("db_password").unwrap()).unwrap();
+    assert_eq!(db.status, SecretResolutionStatus::Forbidden);
+    assert!(db.requirement.required);
+    let unscoped_db = plan
+        .get_in(&SecretKey::new("db_password").unwrap(), None)
+        .unwrap();
+    assert_eq!(unscoped_db.status, SecretResolutionStatus::Resolved);
+
+    let webhook = plan.get(&SecretKey::new("webhook_token").unwrap()).unwrap();
+    assert_eq!(webhook.status, SecretResolutionStatus::Missing);
+
+    assert!(!plan.is_complete());
+    assert_eq!(plan.unresolved_required().count(), 1);
+    let codes: Vec<_> = plan
+        .diagnostics
+        .iter()
+        .map(|diag| diag.code.as_str())
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "SECRET_FORMAT_MISMATCH",
+            "SECRET_FORBIDDEN",
+            "SECRET_MISSING"
+        ]
+    );
+}
+
+#[test]
+fn requirements_merge_per_key_and_scope() {
+    let ops = scope("acme", Some("ops"));
+    let dev = scope("acme", Some("dev"));
+    let requirements = [
+        requirement("token", false, Some(ops.clone())),
+        requirement("token", true, Some(dev.clone())),
+        requirement("token", true, Some(ops.clone())),
+    ];
+    let plan = plan_secrets(&requirements, &[vault("token", Some(ops.clone()))]);
+    assert_eq!(plan.entries.len(), 2);
+
+    let key = SecretKey::new("token").unwrap();
+    let for_ops = plan.get_in(&key, Some(&ops)).unwrap();
+    assert!(
+        for_ops.requirement.required,
+        "merged requirement stays mandatory"
+    );
+    assert_eq!(for_ops.status, SecretResolutionStatus::Resolved);
+    let for_dev = plan.get_in(&key, Some(&dev)).unwrap();
+    assert_eq!(for_dev.status, SecretResolutionStatus::Forbidden);
+    assert!(!plan.is_complete());
+}
+
+#[test]
+fn team_scoped_secrets_are_not_visible_to_other_teams() {
+    let secret = vault("token", Some(scope("acme", Some("ops"))));
+    assert!(secret.visible_in(Some(&scope("acme", Some("ops")))));
+    assert!(!secret.visible_in(Some(&scope("acme", Some("dev")))));
+    assert!(!secret.visible_in(Some(&scope("acme", None))));
+    assert!(secret.visible_in(None));
+
+    let plan = plan_secrets(&[requirement("token", true, None)], &[secret]);
+    assert!(plan.is_complete());
+    assert!(plan.diagnostics.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn plan_roundtrips_through_json() {
+    let plan = plan_secrets(
+        &[requirement("token", true, None)],
+        &[AvailableSecret::new(
+            SecretKey::new("token").unwrap(),
+            SecretSource::ApiKey {
+                reference: "provider:token".parse().unwrap(),
+            },
+        )],
+    );
+    let json = serde_json::to_value(&plan).unwrap();
+    assert_eq!(json["entries"][0]["status"], "resolved");
+    assert_eq!(json["entries"][0]["source"]["kind"], "api_key");
+    let decoded: greentic_types::SecretResolutionPlan = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, plan);
+}
@@ -1,15 +1,15 @@
 #![cfg(feature = "serde")]
 
 use greentic_types::{
-    AllowList, Capabilities, ComponentId, ErrorCode, FsCaps, GitProviderRef, GreenticError,
-    HashDigest, HttpCaps, Impersonation, InvocationDeadline, KvCaps, Limits, NetCaps,
-    NetworkPolicy, NodeFailure, NodeId, NodeStatus, NodeSummary, Outcome, PackId, PackRef,
-    PolicyDecision, PolicyDecisionStatus, RedactionPath, RunStatus, ScannerRef, SecretRequirement,
-    SecretsCaps, SemverReq, SessionCursor, SessionKey, Signature, SignatureAlgorithm, SpanContext,
-    StateKey, StatePath, TelemetrySpec, TenantContext, TenantCtx, TenantIdentity, ToolsCaps,
-    TranscriptOffset,
+    AllowList, ByteSize, Capabilities, ComponentId, DurationMs, ErrorCategory, ErrorCode, FsCaps,
+    GitProviderRef, GreenticError, HashDigest, HttpCaps, Impersonation, InvocationDeadline, KvCaps,
+    Limits, NetCaps, NetworkPolicy, NodeFailure, NodeId, NodeStatus, NodeSummary,
+    ObligationCapabilities, Outcome, PackId, PackRef, PolicyDecision, PolicyDecisionStatus,
+    PolicyObligation, RedactionPath, RunStatus, ScannerRef, SecretRequirement, SecretsCaps,
+    SemverReq, SessionCursor, SessionKey, Signature, SignatureAlgorithm, SpanContext, StateKey,
+    StatePath, TelemetrySpec, TenantContext, TenantCtx, TenantIdentity, ToolsCaps,
+    TranscriptOffset, UnixMillis, UrlRef,
 };
-#[cfg(feature = "time")]
 use greentic_types::{FlowId, RunResult};
 use semver::Version;
 use serde::Serialize;
@@ -18,7 +18,7 @@ use std::collections::BTreeMap;
 use std::str::FromStr;
 
 #[cfg(feature = "time")]
-use time::{Duration, OffsetDateTime};
+use time::OffsetDateTime;
 
 fn assert_roundtrip<T>(value: &T)
 where
@@ -50,6 +50,42 @@ fn tenant_ctx_roundtrip() {
     assert_roundtrip(&identity);
 }
 
+#[test]
+fn impersonation_event_tracks_break_glass_session() {
+    let ctx = TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap())
+        .with_user(Some("user-9".parse().unwrap()));
+    assert!(
+        ctx.impersonation_event(UnixMillis::from_millis(0))
+            .is_none()
+    );
+
+    let mut ctx = ctx.with_impersonation(Some(Impersonation {
+        actor_id: "support-ops".parse().unwrap(),
+        reason: Some("break-glass".into()),
+    }));
+    ctx.correlation_id = Some("corr-7".into());
+    let mut event = ctx
+        .impersonation_event(UnixMillis::from_millis(1_000))
+        .expect("impersonation is set");
+    assert_eq!(event.actor_id.as_str(), "support-ops");
+    assert_eq!(event.target_tenant.as_str(), "tenant-1");
+    assert_eq!(
+        event.target_user.as_ref().map(|u| u.as_str()),
+        Some("user-9")
+    );
+    assert_eq!(event.correlation_id.as_deref(), Some("corr-7"));
+    assert!(event.is_active());
+    assert_eq!(event.duration(), None);
+
+    event.record_action();
+    event.record_action();
+    event.end(UnixMillis::from_millis(61_000));
+    assert!(!event.is_active());
+    assert_eq!(event.actions, 2);
+    assert_eq!(event.duration(), Some(DurationMs::from_millis(60_000)));
+    assert_roundtrip(&event);
+}
+
 #[test]
 fn session_types_roundtrip() {
     let key = SessionKey::from("sess-123");
@@ -61,6 +97,35 @@ fn session_types_roundtrip() {
     assert_roundtrip(&cursor);
 }
 
+#[test]
+fn session_cursor_history_is_bounded() {
+    let mut cursor = SessionCursor::new("start");
+    for (step, node) in ["a", "b", "c"].into_iter().enumerate() {
+        cursor.advance_to(node, UnixMillis::from_millis(step as i64 * 10), 2);
+    }
+
+    assert_eq!(cursor.node_pointer, "c");
+    let hops: Vec<_> = cursor
+        .history
+        .iter()
+        .map(|hop| {
+            (
+                hop.node_id.as_str(),
+                hop.entered_at.as_millis(),
+                hop.left_at,
+            )
+        })
+        .collect();
+    assert_eq!(
+        hops,
+        vec![
+            ("b", 10, Some(UnixMillis::from_millis(20))),
+            ("c", 20, None),
+        ]
+    );
+    assert_roundtrip(&cursor);
+}
+
 #[test]
 fn state_types_roundtrip() {
     let key = StateKey::from("state::demo");
@@ -110,6 +175,7 @@ fn policy_roundtrip() {
         reasons: vec!["matched allow list".into()],
         allow: Some(true),
         reason: Some("matched allow list".into()),
+        obligations: Vec::new(),
     };
 
     assert_roundtrip(&policy);
@@ -126,6 +192,78 @@ fn policy_roundtrip() {
     assert_eq!(decoded.reasons, vec!["denied by policy".to_string()]);
 }
 
+#[test]
+fn policy_decision_obligations_roundtrip_and_satisfiability() {
+    let decision = PolicyDecision {
+        status: PolicyDecisionStatus::Allow,
+        reasons: vec!["support access".into()],
+        allow: None,
+        reason: None,
+        obligations: vec![
+            PolicyObligation::Redact {
+                paths: vec![RedactionPath::parse("$.customer.email").unwrap()],
+            },
+            PolicyObligation::Ttl {
+                duration: DurationMs::from_millis(60_000),
+            },
+            PolicyObligation::Ttl {
+                duration: DurationMs::from_millis(30_000),
+            },
+            PolicyObligation::RequireMfa,
+            PolicyObligation::Custom {
+                name: "watermark".into(),
+                params: serde_json::json!({"text": "internal"}),
+            },
+        ],
+    };
+    assert_roundtrip(&decision);
+    let value = serde_json::to_value(&decision).unwrap();
+    assert_eq!(value["obligations"][0]["kind"], "redact");
+    assert_eq!(
+        value["obligations"][3],
+        serde_json::json!({"kind": "require_mfa"})
+    );
+
+    assert!(decision.is_conditional());
+    assert_eq!(decision.ttl(), Some(DurationMs::from_millis(30_000)));
+    assert_eq!(
+        decision
+            .redaction_paths()
+            .map(RedactionPath::as_str)
+            .collect::<Vec<_>>(),
+        ["$.customer.email"]
+    );
+
+    let mut host = ObligationCapabilities {
+        redact: true,
+        ttl: true,
+        require_mfa: false,
+        custom: vec!["watermark".into()],
+    };
+    assert_eq!(
+        decision.unsatisfied_obligations(&host),
+        [&PolicyObligation::RequireMfa]
+    );
+    assert_eq!(decision.effective_status(&host), PolicyDecisionStatus::Deny);
+    host.require_mfa = true;
+    assert!(decision.is_satisfiable_by(&host));
+    assert_eq!(
+        decision.effective_status(&host),
+        PolicyDecisionStatus::Allow
+    );
+
+    let legacy: PolicyDecision =
+        serde_json::from_str(r#"{"status": "allow", "reasons": []}"#).unwrap();
+    assert!(legacy.obligations.is_empty());
+    assert!(!legacy.is_conditional());
+    assert!(
+        serde_json::to_value(&legacy)
+            .unwrap()
+            .get("obligations")
+            .is_none()
+    );
+}
+
 #[test]
 fn pack_signature_roundtrip() {
     let reference = PackRef::new(
@@ -152,7 +290,10 @@ fn span_context_roundtrip() {
     {
         let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).expect("timestamp");
         span = span.started(now).finished(now);
+        assert_eq!(span.start, Some(now.into()));
     }
+    span = span.finished_at(UnixMillis::from_millis(1_700_000_000_250));
+    assert_eq!(span.end, Some(UnixMillis::from_millis(1_700_000_000_250)));
 
     assert_roundtrip(&span);
 }
@@ -164,6 +305,76 @@ fn greentic_error_roundtrip() {
     let deser: GreenticError = serde_json::from_str(&json).expect("deserialize");
     assert_eq!(deser.code, err.code);
     assert_eq!(deser.message, err.message);
+    assert!(!json.contains("context"), "{json}");
+}
+
+#[test]
+fn error_codes_map_to_categories_and_statuses() {
+    assert_eq!(
+        ErrorCode::InvalidInput.category(),
+        ErrorCategory::Validation
+    );
+    assert_eq!(ErrorCode::PermissionDenied.category(), ErrorCategory::Auth);
+    assert_eq!(ErrorCode::RateLimited.category(), ErrorCategory::Capacity);
+    assert!(ErrorCode::RateLimited.is_retryable());
+    assert!(ErrorCode::Timeout.is_retryable());
+    assert!(!ErrorCode::Conflict.is_retryable());
+    assert!(!ErrorCode::Internal.is_retryable());
+    assert!(GreenticError::new(ErrorCode::Unavailable, "down").is_retryable());
+
+    assert_eq!(ErrorCode::NotFound.http_status(), 404);
+    assert_eq!(ErrorCode::Unauthenticated.grpc_status(), 16);
+    assert_eq!(ErrorCode::from_http_status(200), None);
+    assert_eq!(ErrorCode::from_http_status(410), Some(ErrorCode::NotFound));
+    assert_eq!(ErrorCode::from_http_status(418), Some(ErrorCode::Unknown));
+    for code in ErrorCode::ALL {
+        let status = code.http_status();
+        assert!((400..600).contains(&status), "{code:?}");
+        if code != ErrorCode::Unknown {
+            assert_eq!(ErrorCode::from_http_status(status), Some(code), "{code:?}");
+        }
+    }
+
+    assert_eq!(
+        serde_json::to_string(&ErrorCode::RateLimited).unwrap(),
+        "\"rate_limited\""
+    );
+    assert_eq!(
+        serde_json::to_string(&ErrorCategory::Transient).unwrap(),
+        "\"transient\""
+    );
+}
+
+#[test]
+fn greentic_error_context_survives_serde() {
+    use greentic_types::ResultExt;
+
+    let result: Result<(), GreenticError> =
+        Err(GreenticError::new(ErrorCode::NotFound, "pack missing").with_field("pack", "demo"));
+    let err = result
+        .context("loading manifest")
+        .with_context(|| format!("deploying to {}", "prod"))
+        .map_err(|err| err.with_field("attempt", 2))
+        .expect_err("error");
+    assert_eq!(err.code, ErrorCode::NotFound);
+    assert_eq!(err.message, "pack missing");
+    assert_eq!(
+        err.display_chain(),
+        "deploying to prod: loading manifest: pack missing"
+    );
+
+    let json = serde_json::to_value(&err).expect("serialize");
+    assert_eq!(
+        json["context"],
+        serde_json::json!(["loading manifest", "deploying to prod"])
+    );
+    assert_eq!(
+        json["fields"],
+        serde_json::json!({"attempt": 2, "pack": "demo"})
+    );
+    let deser: GreenticError = serde_json::from_value(json).expect("deserialize");
+    assert_eq!(deser.context, err.context);
+    assert_eq!(deser.fields, err.fields);
 }
 
 #[test]
@@ -200,6 +411,35 @@ fn redaction_path_validates() {
     assert_roundtrip(&path);
 }
 
+#[test]
+fn url_ref_validates() {
+    let url = UrlRef::parse("https://cdn.greentic.ai/logo.svg?v=2").expect("valid url");
+    assert_eq!(url.scheme(), "https");
+    assert!(url.is_https());
+    assert_roundtrip(&url);
+    assert_eq!(
+        serde_json::to_string(&url).unwrap(),
+        "\"https://cdn.greentic.ai/logo.svg?v=2\""
+    );
+
+    let plain = UrlRef::parse("http://localhost:8080/hook").expect("valid http url");
+    assert!(plain.require_https().is_err());
+    assert!(UrlRef::parse_https("http://localhost:8080/hook").is_err());
+    assert!(UrlRef::parse("mailto:support@greentic.ai").is_ok());
+
+    for invalid in [
+        "",
+        "cdn.greentic.ai/logo.svg",
+        "https://cdn.greentic.ai/my logo.svg",
+        "https:",
+        "https:///path",
+        "1http://host",
+    ] {
+        assert!(UrlRef::parse(invalid).is_err(), "{invalid}");
+    }
+    assert!(serde_json::from_str::<UrlRef>("\"not a url\"").is_err());
+}
+
 #[test]
 fn hash_digest_roundtrip() {
     let digest = HashDigest::blake3("deadbeef").expect("valid hex");
@@ -270,7 +510,7 @@ fn capabilities_roundtrip() {
         ports: vec![443],
         protocols: vec![greentic_types::Protocol::Https],
     });
-    http.max_body_bytes = Some(1_048_576);
+    http.max_body_bytes = Some(ByteSize::from_mib(1));
     caps.http = Some(http);
 
     let mut secrets = SecretsCaps::new();
@@ -301,7 +541,7 @@ fn capabilities_roundtrip() {
     tools.allowed.push("summarize".into());
     caps.tools = Some(tools);
 
-    let mut limits = Limits::new(256, 15_000);
+    let mut limits = Limits::new(ByteSize::from_mib(256), DurationMs::from_secs(15));
     limits.files = Some(32);
     limits.fuel = Some(10_000);
 
@@ -315,11 +555,10 @@ fn capabilities_roundtrip() {
     assert!(!caps.is_empty());
 }
 
-#[cfg(feature = "time")]
 #[test]
 fn run_result_roundtrip() {
-    let start = OffsetDateTime::from_unix_timestamp(1_700_000_000).expect("timestamp");
-    let finish = start + Duration::seconds(2);
+    let start = UnixMillis::from_millis(1_700_000_000_000);
+    let finish = UnixMillis::from_millis(1_700_000_002_250);
     let summary = NodeSummary {
         node_id: NodeId::from_str("node.entry").unwrap(),
         component: ComponentId::from_str("qa.process").unwrap(),
@@ -345,8 +584,12 @@ fn run_result_roundtrip() {
         node_summaries: vec![summary],
         failures: vec![failure],
         artifacts_dir: Some("/tmp/run-artifacts".into()),
+        artifacts: Vec::new(),
     };
 
     assert_roundtrip(&result);
-    assert!(result.duration_ms() >= 2000);
+    assert_eq!(result.duration_ms(), 2_250);
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(json["started_at_utc"], "2023-11-14T22:13:20Z");
+    assert_eq!(json["finished_at_utc"], "2023-11-14T22:13:22.250Z");
 }
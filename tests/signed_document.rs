@@ -0,0 +1,94 @@
+#![cfg(all(feature = "serde", feature = "std"))]
+
+use std::collections::BTreeMap;
+
+use greentic_types::{
+    DesiredState, EnvelopeSigner, EnvelopeVerifier, ErrorCode, GResult, GreenticError,
+    SignatureAlgorithm, Signed, SignedDesiredState, SigningKeyRef, TenantCtx,
+};
+
+struct KeyedHash(&'static str);
+
+fn mac(key: &str, message: &[u8]) -> Vec<u8> {
+    blake3::keyed_hash(blake3::hash(key.as_bytes()).as_bytes(), message)
+        .as_bytes()
+        .to_vec()
+}
+
+impl EnvelopeSigner for KeyedHash {
+    fn algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::Other("blake3-keyed".into())
+    }
+
+    fn key(&self) -> SigningKeyRef {
+        SigningKeyRef::new(self.0).unwrap()
+    }
+
+    fn sign(&self, message: &[u8]) -> GResult<Vec<u8>> {
+        Ok(mac(self.0, message))
+    }
+}
+
+struct KnownKeys(&'static [&'static str]);
+
+impl EnvelopeVerifier for KnownKeys {
+    fn verify(
+        &self,
+        _algorithm: &SignatureAlgorithm,
+        key: &SigningKeyRef,
+        message: &[u8],
+        signature: &[u8],
+    ) -> GResult<bool> {
+        let key = key.as_str();
+        if !self.0.contains(&key) {
+            return Err(GreenticError::new(
+                ErrorCode::Unauthenticated,
+                format!("unknown key {key}"),
+            ));
+        }
+        Ok(mac(key, message) == signature)
+    }
+}
+
+fn sample_state() -> DesiredState {
+    DesiredState {
+        tenant: TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap()),
+        environment_ref: "env-1".parse().unwrap(),
+        entries: Vec::new(),
+        event_subscriptions: Vec::new(),
+        version: 3,
+        metadata: BTreeMap::from([("owner".to_string(), "ops".into())]),
+    }
+}
+
+#[test]
+fn signed_document_roundtrips_and_verifies_every_signature() {
+    let state = sample_state();
+    let mut signed: SignedDesiredState = Signed::seal(&state, &KeyedHash("release-key")).unwrap();
+    signed.sign(&KeyedHash("ops-key")).unwrap();
+    assert_eq!(
+        signed
+            .key_refs()
+            .map(SigningKeyRef::as_str)
+            .collect::<Vec<_>>(),
+        ["release-key", "ops-key"]
+    );
+
+    let json = serde_json::to_string(&signed).unwrap();
+    let parsed: SignedDesiredState = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, signed);
+
+    let verifier = KnownKeys(&["release-key", "ops-key"]);
+    assert_eq!(parsed.open(&verifier).unwrap(), state);
+    assert!(parsed.verify(&KnownKeys(&["release-key"])).is_err());
+
+    let mut tampered = parsed.clone();
+    let mut other = sample_state();
+    other.version = 4;
+    tampered.payload = Signed::new(&other).unwrap().payload;
+    let err = tampered.verify(&verifier).unwrap_err();
+    assert_eq!(err.code, ErrorCode::Unauthenticated);
+
+    let unsigned = Signed::new(&state).unwrap();
+    assert!(unsigned.verify(&verifier).is_err());
+}
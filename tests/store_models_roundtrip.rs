@@ -1,17 +1,24 @@
 #![cfg(feature = "serde")]
 
 use greentic_types::{
-    ArtifactSelector, BundleSpec, CapabilityMap, Collection, ConnectionKind, DesiredState,
-    DesiredStateExportSpec, DesiredSubscriptionEntry, Environment, LayoutSection,
-    LayoutSectionKind, PlanLimits, PriceModel, ProductOverride, StoreFront, StorePlan,
-    StoreProduct, StoreProductKind, Subscription, SubscriptionStatus, Theme, VersionStrategy,
+    ApprovalDecision, ApprovalRecord, ApprovalStatus, ApprovalVerdict, ArtifactRef,
+    ArtifactSelector, BillingAccount, BundleIntegrity, BundleMismatch, BundleSpec, CapabilityMap,
+    CapabilityMapExt, CatalogFilter, Collection, CompatibilityIssue, ConfigOverrideChange,
+    ConnectionKind, CostConfidence, CostEstimate, CursorStrategy, DeadLetterTarget, DesiredState,
+    DesiredStateChangeRequest, DesiredStateExportSpec, DesiredSubscriptionEntry, Environment,
+    EventFilter, EventSubscriptionSpec, HashDigest, LayoutSection, LayoutSectionKind,
+    LifecycleState, MergeStrategy, MoneyMicro, Page, PageRequest, PaymentMethod, PaymentMethodKind,
+    PlanFeature, PlanFeatureMatrix, PlanFeatureValue, PlanLimits, PriceModel, ProductCompatibility,
+    ProductOverride, SemverReq, StableHash, StoreFront, StorePlan, StoreProduct, StoreProductKind,
+    Subscription, SubscriptionStatus, Theme, UnixMillis, VersionStrategy, diff_desired_state,
 };
+use semver::Version;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::collections::BTreeMap;
 
-fn map(value: serde_json::Value) -> BTreeMap<String, serde_json::Value> {
+fn map<T: DeserializeOwned>(value: serde_json::Value) -> BTreeMap<String, T> {
     serde_json::from_value(value).expect("object to map")
 }
 
@@ -33,7 +40,7 @@ fn storefront_and_theme_roundtrip() {
         background_color: "#ffffff".into(),
         text_color: "#000000".into(),
         font_family: "CustomSans".into(),
-        logo_url: Some("https://example.test/logo.png".into()),
+        logo_url: Some("https://example.test/logo.png".parse().unwrap()),
         favicon_url: None,
         hero_image_url: None,
         hero_title: Some("Welcome".into()),
@@ -82,10 +89,24 @@ fn storefront_and_theme_roundtrip() {
         collections,
         overrides,
         worker_id: Some("storefront-worker".into()),
+        default_locale: Some("en".parse().unwrap()),
+        locales: vec!["en".parse().unwrap(), "de".parse().unwrap()],
         metadata: map(json!({"brand": "greentic"})),
     };
 
     assert_roundtrip(&storefront);
+    assert_eq!(
+        storefront
+            .resolve_locale(Some(&"de-AT".parse().unwrap()))
+            .map(|tag| tag.as_str()),
+        Some("de")
+    );
+    assert_eq!(
+        storefront
+            .resolve_locale(Some(&"fr".parse().unwrap()))
+            .map(|tag| tag.as_str()),
+        Some("en")
+    );
 }
 
 #[test]
@@ -110,8 +131,28 @@ fn store_product_and_subscription_roundtrip() {
         },
         default_plan_id: Some("plan-free".parse().unwrap()),
         is_free: true,
+        compatibility: Some(ProductCompatibility {
+            runner_versions: Some(SemverReq::parse(">=0.4, <0.6").unwrap()),
+            required_host_capabilities: BTreeMap::from([(
+                "http".to_string(),
+                vec!["client".to_string()],
+            )]),
+            conflicts_with: vec!["prod-legacy-scanner".parse().unwrap()],
+        }),
+        lifecycle: LifecycleState::Deprecated,
+        deprecated_at: Some(UnixMillis::from_millis(1_700_000_000_000)),
+        sunset_at: Some(UnixMillis::from_millis(1_710_000_000_000)),
+        replacement_id: Some("prod-2".parse().unwrap()),
         metadata: map(json!({"ui_icon": "shield"})),
     };
+    let environment: CapabilityMap = BTreeMap::from([("scan".into(), vec!["sast".into()])]);
+    assert_eq!(
+        product.capability_gaps(&environment).missing,
+        BTreeMap::from([
+            ("http".to_string(), vec!["client".to_string()]),
+            ("scan".to_string(), vec!["deps".to_string()]),
+        ])
+    );
 
     let plan = StorePlan {
         id: "plan-free".parse().unwrap(),
@@ -125,6 +166,14 @@ fn store_product_and_subscription_roundtrip() {
             metadata: map(json!({"note": "beta"})),
         },
         tags: vec!["free".into()],
+        features: vec![
+            PlanFeature::new("sso", "Single sign-on", PlanFeatureValue::Included(false)),
+            PlanFeature::new("seats", "Seats", PlanFeatureValue::Quantity(3)),
+        ],
+        lifecycle: LifecycleState::Ga,
+        deprecated_at: None,
+        sunset_at: None,
+        replacement_id: None,
         metadata: map(json!({})),
     };
 
@@ -139,12 +188,98 @@ fn store_product_and_subscription_roundtrip() {
         environment_ref: Some("env-1".parse().unwrap()),
         distributor_ref: Some("dist-1".parse().unwrap()),
         status: SubscriptionStatus::Active,
+        billing_account_id: Some("ba-1".parse().unwrap()),
+        discount: Some(greentic_types::Percent::new(12.5).unwrap()),
         metadata: map(json!({"priority": "high"})),
     };
 
     assert_roundtrip(&product);
     assert_roundtrip(&plan);
     assert_roundtrip(&subscription);
+
+    #[cfg(feature = "otel-keys")]
+    {
+        use greentic_types::{OtlpKeys, TelemetryAttributes};
+        let attributes = TelemetryAttributes::from(&subscription);
+        assert_eq!(attributes.get(OtlpKeys::TENANT_ID), Some("tenant-1"));
+        assert_eq!(
+            attributes.get(OtlpKeys::STORE_SUBSCRIPTION_ID),
+            Some("sub-1")
+        );
+        assert_eq!(
+            attributes.get(OtlpKeys::STORE_PLAN_ID),
+            Some(plan.id.as_str())
+        );
+    }
+}
+
+#[test]
+fn desired_entry_applies_config_overrides() {
+    let mut entry = DesiredSubscriptionEntry {
+        selector: ArtifactSelector::Component("component.scan".parse().unwrap()),
+        version_strategy: VersionStrategy::Latest,
+        plan_id: None,
+        config_overrides: map(json!({
+            "rules": {"$append": ["secrets"]},
+            "limits": {"timeout_ms": 500},
+            "legacy": null,
+        })),
+        config_merge: MergeStrategy::Strategic,
+        policy_tags: Vec::new(),
+        metadata: BTreeMap::new(),
+    };
+    let base = json!({"rules": ["sast"], "limits": {"timeout_ms": 100, "depth": 3}, "legacy": 1});
+
+    let mut strategic = base.clone();
+    assert!(entry.apply_config_overrides(&mut strategic).is_empty());
+    assert_eq!(
+        strategic,
+        json!({"rules": ["sast", "secrets"], "limits": {"timeout_ms": 500, "depth": 3}})
+    );
+
+    entry.config_merge = MergeStrategy::MergePatch;
+    let mut merge_patch = base;
+    assert!(entry.apply_config_overrides(&mut merge_patch).is_empty());
+    assert_eq!(merge_patch["rules"], json!({"$append": ["secrets"]}));
+
+    let override_doc = entry.config_override();
+    assert_eq!(
+        serde_json::to_value(&override_doc).unwrap(),
+        json!({"patch": {"legacy": null, "limits": {"timeout_ms": 500}, "rules": {"$append": ["secrets"]}}})
+    );
+    assert_roundtrip(&override_doc);
+}
+
+#[test]
+fn legacy_config_overrides_deserialize_as_literals() {
+    let entry = DesiredSubscriptionEntry {
+        selector: ArtifactSelector::Component("component.scan".parse().unwrap()),
+        version_strategy: VersionStrategy::Latest,
+        plan_id: None,
+        config_overrides: BTreeMap::new(),
+        config_merge: MergeStrategy::MergePatch,
+        policy_tags: Vec::new(),
+        metadata: BTreeMap::new(),
+    };
+    let mut stored = serde_json::to_value(&entry).unwrap();
+    stored["config_overrides"] = json!({
+        "home": "${HOME}/cache",
+        "password": "${secret:db/password}",
+        "template": "Hello ${name",
+    });
+
+    let entry: DesiredSubscriptionEntry = serde_json::from_value(stored.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&entry).unwrap(), stored);
+    assert!(entry.config_overrides["home"].validate().is_err());
+    assert!(!entry.config_overrides["home"].has_references());
+    assert!(entry.config_overrides["password"].has_references());
+
+    let resolved = entry.config_overrides["template"]
+        .resolve(&|_: &_| -> Result<String, greentic_types::GreenticError> {
+            panic!("literal strings have no references")
+        })
+        .unwrap();
+    assert_eq!(resolved, json!("Hello ${name"));
 }
 
 #[test]
@@ -152,7 +287,9 @@ fn desired_state_and_bundle_roundtrip() {
     let desired_entry = DesiredSubscriptionEntry {
         selector: ArtifactSelector::Component("component.scan".parse().unwrap()),
         version_strategy: VersionStrategy::Latest,
+        plan_id: Some("plan-1".parse().unwrap()),
         config_overrides: map(json!({"setting": true})),
+        config_merge: MergeStrategy::Strategic,
         policy_tags: vec!["strict".into()],
         metadata: map(json!({})),
     };
@@ -164,6 +301,18 @@ fn desired_state_and_bundle_roundtrip() {
         ),
         environment_ref: "env-1".parse().unwrap(),
         entries: vec![desired_entry],
+        event_subscriptions: vec![
+            EventSubscriptionSpec::new("repo-builds", "nats-main")
+                .with_filter(EventFilter::topics(["greentic.repo.*"]))
+                .with_consumer_group("builders")
+                .with_cursor(CursorStrategy::Timestamp {
+                    at: UnixMillis::from_millis(1_700_000_000_000),
+                })
+                .with_dead_letter(DeadLetterTarget {
+                    topic: "greentic.dlq.builds".into(),
+                    max_deliveries: Some(5),
+                }),
+        ],
         version: 1,
         metadata: map(json!({"channel": "stable"})),
     };
@@ -175,6 +324,7 @@ fn desired_state_and_bundle_roundtrip() {
         desired_state_version: desired_state.version,
         artifact_refs: vec!["artifact-1".parse().unwrap()],
         metadata_refs: vec!["meta-1".parse().unwrap()],
+        integrity: None,
         additional_metadata: map(json!({"signed": true})),
     };
 
@@ -190,12 +340,317 @@ fn desired_state_and_bundle_roundtrip() {
     assert_roundtrip(&desired_state);
     assert_roundtrip(&bundle);
     assert_roundtrip(&export_spec);
+
+    let digest = desired_state.stable_hash().unwrap();
+    let mut bumped = desired_state.clone();
+    bumped.version += 1;
+    assert_eq!(desired_state.clone().stable_hash().unwrap(), digest);
+    assert_ne!(bumped.stable_hash().unwrap(), digest);
+}
+
+#[test]
+fn product_compatibility_reports_blocking_issues() {
+    let compatibility = ProductCompatibility {
+        runner_versions: Some(SemverReq::parse("^0.5").unwrap()),
+        required_host_capabilities: BTreeMap::from([(
+            "messaging".to_string(),
+            vec!["inbound".to_string(), "outbound".to_string()],
+        )]),
+        conflicts_with: vec!["prod-old".parse().unwrap()],
+    };
+    let host: CapabilityMap =
+        BTreeMap::from([("messaging".to_string(), vec!["inbound".to_string()])]);
+
+    assert!(compatibility.supports_runner(&Version::new(0, 5, 3)));
+    let issues = compatibility.check(
+        &Version::new(0, 4, 0),
+        &host,
+        &["prod-old".parse().unwrap(), "prod-other".parse().unwrap()],
+    );
+    assert_eq!(
+        issues,
+        vec![
+            CompatibilityIssue::UnsupportedRunner {
+                required: SemverReq::parse("^0.5").unwrap(),
+                actual: "0.4.0".into(),
+            },
+            CompatibilityIssue::MissingHostCapability {
+                group: "messaging".into(),
+                capability: "outbound".into(),
+            },
+            CompatibilityIssue::ConflictingProduct {
+                product: "prod-old".parse().unwrap(),
+            },
+        ]
+    );
+    assert!(
+        ProductCompatibility::default()
+            .check(&Version::new(0, 1, 0), &CapabilityMap::new(), &[])
+            .is_empty()
+    );
+}
+
+#[test]
+fn capability_map_reports_gaps() {
+    let required: CapabilityMap = BTreeMap::from([
+        (
+            "scan".to_string(),
+            vec!["sast".to_string(), "deps".to_string()],
+        ),
+        ("storage".to_string(), Vec::new()),
+        ("messaging".to_string(), vec!["inbound".to_string()]),
+    ]);
+    let environment: CapabilityMap = BTreeMap::from([
+        ("scan".to_string(), vec!["sast".to_string()]),
+        ("messaging".to_string(), vec!["inbound".to_string()]),
+    ]);
+
+    let report = required.gaps(&environment);
+    assert!(!CapabilityMap::satisfies(&required, &environment));
+    assert_eq!(
+        report.missing,
+        BTreeMap::from([
+            ("scan".to_string(), vec!["deps".to_string()]),
+            ("storage".to_string(), Vec::new()),
+        ])
+    );
+    assert_eq!(
+        report.issues(),
+        vec![
+            CompatibilityIssue::MissingHostCapability {
+                group: "scan".into(),
+                capability: "deps".into(),
+            },
+            CompatibilityIssue::MissingHostCapability {
+                group: "storage".into(),
+                capability: "*".into(),
+            },
+        ]
+    );
+    assert_roundtrip(&report);
+
+    let mut environment = environment;
+    environment.insert("storage".into(), vec!["blob".into()]);
+    environment.get_mut("scan").unwrap().push("deps".into());
+    assert!(required.satisfies(&environment));
+    assert!(required.gaps(&environment).is_satisfied());
+    assert!(CapabilityMap::new().satisfies(&CapabilityMap::new()));
+}
+
+#[test]
+fn desired_state_change_request_requires_sign_off() {
+    let decision = |user: &str, verdict| ApprovalDecision {
+        approver: user.parse().unwrap(),
+        verdict,
+        decided_at_utc: UnixMillis::parse_rfc3339("2024-05-01T10:00:00Z").unwrap(),
+        comment: None,
+    };
+    let proposed = DesiredState {
+        tenant: greentic_types::TenantCtx::new(
+            "prod".parse().unwrap(),
+            "tenant-1".parse().unwrap(),
+        ),
+        environment_ref: "env-1".parse().unwrap(),
+        entries: Vec::new(),
+        event_subscriptions: Vec::new(),
+        version: 8,
+        metadata: map(json!({})),
+    };
+    let mut request = DesiredStateChangeRequest {
+        id: "cr-1".parse().unwrap(),
+        base_version: 7,
+        proposed,
+        approval: ApprovalRecord::new(
+            "alice".parse().unwrap(),
+            vec!["bob".parse().unwrap(), "carol".parse().unwrap()],
+            2,
+        )
+        .with_expiry(UnixMillis::parse_rfc3339("2024-05-02T00:00:00Z").unwrap()),
+        created_at_utc: UnixMillis::parse_rfc3339("2024-05-01T09:00:00Z").unwrap(),
+        metadata: map(json!({"ticket": "CHG-42"})),
+    };
+
+    let err = request
+        .approval
+        .record(decision("alice", ApprovalVerdict::Approve))
+        .unwrap_err();
+    assert_eq!(err.code, greentic_types::ErrorCode::PermissionDenied);
+    assert!(
+        request
+            .approval
+            .record(decision("mallory", ApprovalVerdict::Approve))
+            .is_err()
+    );
+
+    assert_eq!(
+        request
+            .approval
+            .record(decision("bob", ApprovalVerdict::Approve))
+            .unwrap(),
+        ApprovalStatus::Pending
+    );
+    assert!(
+        request
+            .approval
+            .record(decision("bob", ApprovalVerdict::Approve))
+            .is_err()
+    );
+    assert!(!request.is_ready_to_push(7));
+    assert_eq!(
+        request
+            .approval
+            .record(decision("carol", ApprovalVerdict::Approve))
+            .unwrap(),
+        ApprovalStatus::Approved
+    );
+    assert!(request.is_ready_to_push(7));
+    assert!(!request.is_ready_to_push(8));
+    assert_roundtrip(&request);
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["created_at_utc"], "2024-05-01T09:00:00Z");
+    assert_eq!(json["approval"]["expires_at_utc"], "2024-05-02T00:00:00Z");
+    assert_eq!(
+        json["approval"]["decisions"][0]["decided_at_utc"],
+        "2024-05-01T10:00:00Z"
+    );
+
+    let mut rejected = ApprovalRecord::new("alice".parse().unwrap(), Vec::new(), 1);
+    rejected
+        .record(decision("dave", ApprovalVerdict::Reject))
+        .unwrap();
+    assert_eq!(rejected.status, ApprovalStatus::Rejected);
+    rejected.expire();
+    assert_eq!(rejected.status, ApprovalStatus::Rejected);
+}
+
+#[test]
+fn desired_state_diff_reports_entry_and_override_changes() {
+    let entry = |selector: &str, overrides: serde_json::Value| DesiredSubscriptionEntry {
+        selector: ArtifactSelector::Component(selector.parse().unwrap()),
+        version_strategy: VersionStrategy::Latest,
+        plan_id: None,
+        config_overrides: map(overrides),
+        config_merge: MergeStrategy::default(),
+        policy_tags: Vec::new(),
+        metadata: map(json!({})),
+    };
+    let state = |entries: Vec<DesiredSubscriptionEntry>, version: u64| DesiredState {
+        tenant: greentic_types::TenantCtx::new(
+            "prod".parse().unwrap(),
+            "tenant-1".parse().unwrap(),
+        ),
+        environment_ref: "env-1".parse().unwrap(),
+        entries,
+        event_subscriptions: Vec::new(),
+        version,
+        metadata: map(json!({})),
+    };
+
+    let old = state(
+        vec![
+            entry("component.keep", json!({"a": 1})),
+            entry("component.change", json!({"a": 1, "b": 2})),
+            entry("component.drop", json!({})),
+        ],
+        1,
+    );
+    let new = state(
+        vec![
+            entry("component.keep", json!({"a": 1})),
+            entry("component.change", json!({"a": 5, "c": 3})),
+            entry("component.new", json!({})),
+        ],
+        2,
+    );
+
+    let diff = diff_desired_state(&old, &new);
+    assert_eq!(diff.added, vec![new.entries[2].clone()]);
+    assert_eq!(diff.removed, vec![old.entries[2].clone()]);
+    assert_eq!(diff.changed.len(), 1);
+    let change = &diff.changed[0];
+    assert_eq!(change.selector(), &new.entries[1].selector);
+    assert!(!change.version_strategy_changed());
+    assert_eq!(
+        change.config_overrides.get("a"),
+        Some(&ConfigOverrideChange::Changed {
+            old: json!(1),
+            new: json!(5)
+        })
+    );
+    assert_eq!(
+        change.config_overrides.get("b"),
+        Some(&ConfigOverrideChange::Removed { value: json!(2) })
+    );
+    assert_eq!(
+        change.config_overrides.get("c"),
+        Some(&ConfigOverrideChange::Added { value: json!(3) })
+    );
+    assert_roundtrip(&diff);
+
+    assert!(diff_desired_state(&new, &new).is_empty());
+}
+
+#[test]
+fn desired_state_diff_reconciles_event_subscriptions() {
+    let state = |event_subscriptions: Vec<EventSubscriptionSpec>| DesiredState {
+        tenant: greentic_types::TenantCtx::new(
+            "prod".parse().unwrap(),
+            "tenant-1".parse().unwrap(),
+        ),
+        environment_ref: "env-1".parse().unwrap(),
+        entries: Vec::new(),
+        event_subscriptions,
+        version: 1,
+        metadata: map(json!({})),
+    };
+    let builds = EventSubscriptionSpec::new("builds", "nats-main")
+        .with_filter(EventFilter::topics(["greentic.repo.build.*"]));
+    let old = state(vec![
+        builds.clone(),
+        EventSubscriptionSpec::new("audit", "nats-main"),
+    ]);
+    let new = state(vec![
+        builds.clone().with_cursor(CursorStrategy::Earliest),
+        EventSubscriptionSpec::new("alerts", "kafka-ops"),
+    ]);
+
+    let diff = diff_desired_state(&old, &new);
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+    assert_eq!(
+        diff.added_event_subscriptions,
+        vec![new.event_subscriptions[1].clone()]
+    );
+    assert_eq!(
+        diff.removed_event_subscriptions,
+        vec![old.event_subscriptions[1].clone()]
+    );
+    assert_eq!(diff.changed_event_subscriptions.len(), 1);
+    assert_eq!(diff.changed_event_subscriptions[0].old, builds);
+    assert_roundtrip(&diff);
+    assert!(diff_desired_state(&new, &new).is_empty());
+
+    assert!(builds.filter.matches_topic("greentic.repo.build.status"));
+    assert!(!builds.filter.matches_topic("greentic.repo.scan.status"));
+    assert_eq!(builds.effective_consumer_group(), "builds");
+    builds.validate().unwrap();
+    let looping = builds.with_dead_letter(DeadLetterTarget {
+        topic: "greentic.repo.build.dlq".into(),
+        max_deliveries: None,
+    });
+    assert!(looping.validate().is_err());
 }
 
 #[test]
 fn distribution_bundle_spec_roundtrip() {
     let tenant =
         greentic_types::TenantCtx::new("prod".parse().unwrap(), "tenant-2".parse().unwrap());
+    let integrity = BundleIntegrity::from_artifacts([
+        ("artifact-runner".parse().unwrap(), b"runner".as_slice()),
+        (
+            "artifact-components".parse().unwrap(),
+            b"components".as_slice(),
+        ),
+    ]);
     let bundle = BundleSpec {
         bundle_id: "bundle-dist-1".parse().unwrap(),
         tenant,
@@ -206,6 +661,7 @@ fn distribution_bundle_spec_roundtrip() {
             "artifact-components".parse().unwrap(),
         ],
         metadata_refs: vec!["sbom-1".parse().unwrap(), "attestation-1".parse().unwrap()],
+        integrity: Some(integrity),
         additional_metadata: map(json!({
             "pack_kind": "distribution-bundle",
             "notes": "offline rollout pack"
@@ -215,6 +671,74 @@ fn distribution_bundle_spec_roundtrip() {
     assert_roundtrip(&bundle);
 }
 
+#[test]
+fn bundle_integrity_reports_structured_mismatches() {
+    let runner: ArtifactRef = "artifact-runner".parse().unwrap();
+    let components: ArtifactRef = "artifact-components".parse().unwrap();
+    let integrity = BundleIntegrity::from_artifacts([
+        (runner.clone(), b"runner".as_slice()),
+        (components.clone(), b"components".as_slice()),
+    ]);
+    assert_eq!(integrity.total_size_bytes, 16);
+    assert_eq!(integrity.artifacts[0].artifact, components);
+    let reordered = BundleIntegrity::from_artifacts([
+        (components.clone(), b"components".as_slice()),
+        (runner.clone(), b"runner".as_slice()),
+    ]);
+    assert_eq!(reordered.merkle_root, integrity.merkle_root);
+
+    let intact = [
+        (runner.clone(), b"runner".as_slice()),
+        (components.clone(), b"components".as_slice()),
+    ];
+    assert!(
+        integrity
+            .verify_bundle(intact.clone().into_iter())
+            .is_empty()
+    );
+
+    let extra: ArtifactRef = "artifact-extra".parse().unwrap();
+    let tampered = [
+        (runner.clone(), b"RUNNER".as_slice()),
+        (extra.clone(), b"x".as_slice()),
+    ];
+    let mismatches = integrity.verify_bundle(tampered.into_iter());
+    assert_eq!(mismatches.len(), 3);
+    assert!(matches!(
+        &mismatches[0],
+        BundleMismatch::Digest { artifact, .. } if *artifact == runner
+    ));
+    assert_eq!(
+        mismatches[1],
+        BundleMismatch::Unexpected { artifact: extra }
+    );
+    assert_eq!(
+        mismatches[2],
+        BundleMismatch::Missing {
+            artifact: components.clone()
+        }
+    );
+
+    let mut edited = integrity.clone();
+    edited.artifacts[1].size_bytes = 7;
+    let mismatches = edited.verify_bundle(intact.clone().into_iter());
+    assert!(matches!(mismatches[0], BundleMismatch::TotalSize { .. }));
+    assert!(mismatches.contains(&BundleMismatch::Size {
+        artifact: runner,
+        expected: 7,
+        actual: 6,
+    }));
+
+    let mut swapped = integrity;
+    swapped.artifacts[0].digest = HashDigest::blake3_of(b"other");
+    let mismatches = swapped.verify_bundle(intact.clone().into_iter());
+    assert!(matches!(mismatches[0], BundleMismatch::MerkleRoot { .. }));
+    assert_eq!(
+        serde_json::to_value(&mismatches[0]).unwrap()["kind"],
+        "merkle_root"
+    );
+}
+
 #[test]
 fn environment_roundtrip() {
     let env = Environment {
@@ -226,6 +750,10 @@ fn environment_roundtrip() {
         distributor_ref: "dist-1".parse().unwrap(),
         name: "Primary".into(),
         connection_kind: ConnectionKind::Online,
+        residency: Some(greentic_types::RegionConstraint::countries([
+            "de".parse().unwrap(),
+            "fr".parse().unwrap(),
+        ])),
         labels: BTreeMap::from([("region".into(), "eu-west".into())]),
         metadata: map(json!({"notes": "primary"})),
     };
@@ -240,6 +768,8 @@ fn rollout_status_roundtrip() {
         desired_state_version: Some(2),
         state: greentic_types::RolloutState::InProgress,
         bundle_id: Some("bundle-1".parse().unwrap()),
+        deferred_by: None,
+        progress: Some(greentic_types::Percent::new(40.0).unwrap()),
         message: Some("deploying".into()),
         metadata: map(json!({"wave": 1})),
     };
@@ -266,3 +796,353 @@ fn version_strategy_compat() {
     let fixed: VersionStrategy = serde_json::from_str(fixed_json).expect("fixed");
     assert!(matches!(fixed, VersionStrategy::Fixed { version } if version == "1.2.3"));
 }
+
+fn catalog_product(id: &str, kind: StoreProductKind, tags: &[&str], is_free: bool) -> StoreProduct {
+    StoreProduct {
+        id: id.parse().unwrap(),
+        kind,
+        name: id.into(),
+        slug: id.into(),
+        description: String::new(),
+        source_repo: "repo-1".parse().unwrap(),
+        component_ref: None,
+        pack_ref: None,
+        category: None,
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        capabilities: BTreeMap::from([("http".to_string(), vec!["client".to_string()])]),
+        version_strategy: VersionStrategy::Latest,
+        default_plan_id: None,
+        is_free,
+        compatibility: None,
+        lifecycle: LifecycleState::Ga,
+        deprecated_at: None,
+        sunset_at: None,
+        replacement_id: None,
+        metadata: BTreeMap::new(),
+    }
+}
+
+#[test]
+fn catalog_pagination_and_filtering() {
+    let products = vec![
+        catalog_product("p1", StoreProductKind::Component, &["security"], true),
+        catalog_product("p2", StoreProductKind::Pack, &["security"], false),
+        catalog_product("p3", StoreProductKind::Component, &["ai"], true),
+        catalog_product("p4", StoreProductKind::Component, &["security", "ai"], true),
+    ];
+
+    let filter = CatalogFilter {
+        kind: Some(StoreProductKind::Component),
+        tags: vec!["security".into()],
+        capability: Some("http:client".into()),
+        free_only: true,
+    };
+    assert_roundtrip(&filter);
+    let matching: Vec<_> = products
+        .iter()
+        .filter(|product| filter.matches_product(product))
+        .map(|product| product.id.to_string())
+        .collect();
+    assert_eq!(matching, ["p1", "p4"]);
+    assert!(CatalogFilter::default().is_empty());
+    assert!(
+        !CatalogFilter {
+            capability: Some("http:server".into()),
+            ..Default::default()
+        }
+        .matches_product(&products[0])
+    );
+
+    let request = PageRequest::first(3);
+    let first = Page::from_slice(&products, &request).unwrap();
+    assert_eq!(first.items.len(), 3);
+    assert_eq!(first.total, Some(4));
+    assert!(first.has_more());
+    assert_roundtrip(&first);
+
+    let next = request.next(&first).unwrap();
+    let second = Page::from_slice(&products, &next).unwrap();
+    assert_eq!(second.items.len(), 1);
+    assert!(request.next(&second).is_none());
+
+    assert!(
+        Page::from_slice(
+            &products,
+            &PageRequest {
+                cursor: Some("bogus".into()),
+                limit: None,
+            }
+        )
+        .is_err()
+    );
+    assert_eq!(
+        PageRequest::default().effective_limit(),
+        PageRequest::DEFAULT_LIMIT
+    );
+    assert_eq!(PageRequest::first(0).effective_limit(), 1);
+    assert_eq!(
+        first.map(|product| product.id.to_string()).items,
+        ["p1", "p2", "p3"]
+    );
+}
+
+fn billing_account() -> BillingAccount {
+    BillingAccount {
+        id: "ba-1".parse().unwrap(),
+        tenant_ctx: greentic_types::TenantCtx::new(
+            "prod".parse().unwrap(),
+            "tenant-1".parse().unwrap(),
+        ),
+        billing_email: "billing@example.com".into(),
+        tax_id: Some("DE123456789".into()),
+        country: Some("DE".parse().unwrap()),
+        region: Some("DE-BY".parse().unwrap()),
+        payment_methods: vec![
+            PaymentMethod {
+                reference: "pm_1NvXk2".parse().unwrap(),
+                provider: "stripe".into(),
+                kind: PaymentMethodKind::Card,
+                display_hint: Some("Visa •••• 4242".into()),
+                is_default: false,
+            },
+            PaymentMethod {
+                reference: "pm_2Bank".parse().unwrap(),
+                provider: "stripe".into(),
+                kind: PaymentMethodKind::BankAccount,
+                display_hint: None,
+                is_default: true,
+            },
+        ],
+        metadata: map(json!({})),
+    }
+}
+
+#[test]
+fn billing_account_roundtrip_and_validation() {
+    let account = billing_account();
+    assert_roundtrip(&account);
+    account.validate().unwrap();
+    assert_eq!(
+        account.default_payment_method().unwrap().reference.as_str(),
+        "pm_2Bank"
+    );
+
+    let mut bad_email = account.clone();
+    bad_email.billing_email = "billing".into();
+    assert!(bad_email.validate().is_err());
+
+    let mut two_defaults = account.clone();
+    two_defaults.payment_methods[0].is_default = true;
+    assert!(two_defaults.validate().is_err());
+
+    let mut foreign_region = account.clone();
+    foreign_region.region = Some("US-CA".parse().unwrap());
+    assert!(foreign_region.validate().is_err());
+
+    let mut raw_card = account.clone();
+    raw_card.payment_methods[0].display_hint = Some("4242 4242 4242 4242".into());
+    let err = raw_card.validate().unwrap_err();
+    assert_eq!(err.code, greentic_types::ErrorCode::InvalidInput);
+    raw_card.payment_methods[0].display_hint = Some("Card 4000-0566-5566-5556 on file".into());
+    assert!(raw_card.validate().is_err());
+    for hint in [
+        "4242 4242 4242 4243",
+        "Invoice 123456789012",
+        "Order 12345678901234567890",
+    ] {
+        raw_card.payment_methods[0].display_hint = Some(hint.into());
+        raw_card
+            .validate()
+            .unwrap_or_else(|err| panic!("{hint}: {err}"));
+    }
+
+    let mut single = account;
+    single.payment_methods.truncate(1);
+    assert_eq!(
+        single.default_payment_method().unwrap().reference.as_str(),
+        "pm_1NvXk2"
+    );
+}
+
+fn eur(amount_micro: u64) -> MoneyMicro {
+    MoneyMicro::new(amount_micro, "EUR".parse().unwrap())
+}
+
+fn priced_plan(id: &str, price_model: PriceModel) -> StorePlan {
+    StorePlan {
+        id: id.parse().unwrap(),
+        name: id.into(),
+        description: String::new(),
+        price_model,
+        limits: PlanLimits::default(),
+        tags: Vec::new(),
+        features: Vec::new(),
+        lifecycle: LifecycleState::Ga,
+        deprecated_at: None,
+        sunset_at: None,
+        replacement_id: None,
+        metadata: map(json!({})),
+    }
+}
+
+#[test]
+fn cost_estimate_prices_desired_state() {
+    let plans = vec![
+        priced_plan("free", PriceModel::Free),
+        priced_plan(
+            "flat",
+            PriceModel::Flat {
+                price: eur(20_000_000),
+                period_days: 30,
+            },
+        ),
+        priced_plan(
+            "yearly",
+            PriceModel::Flat {
+                price: eur(365_000_000),
+                period_days: 365,
+            },
+        ),
+        priced_plan(
+            "metered",
+            PriceModel::Metered {
+                included_units: 100,
+                overage_rate: eur(10_000),
+                unit_label: "build-minute".into(),
+            },
+        ),
+    ];
+    let entry = |component: &str, plan: Option<&str>| DesiredSubscriptionEntry {
+        selector: ArtifactSelector::Component(component.parse().unwrap()),
+        version_strategy: VersionStrategy::Latest,
+        plan_id: plan.map(|plan| plan.parse().unwrap()),
+        config_overrides: BTreeMap::new(),
+        config_merge: MergeStrategy::default(),
+        policy_tags: Vec::new(),
+        metadata: BTreeMap::new(),
+    };
+    let mut state = DesiredState {
+        tenant: greentic_types::TenantCtx::new(
+            "prod".parse().unwrap(),
+            "tenant-1".parse().unwrap(),
+        ),
+        environment_ref: "env-1".parse().unwrap(),
+        entries: vec![
+            entry("component.free", Some("free")),
+            entry("component.flat", Some("flat")),
+            entry("component.yearly", Some("yearly")),
+            entry("component.build", Some("metered")),
+        ],
+        event_subscriptions: Vec::new(),
+        version: 3,
+        metadata: BTreeMap::new(),
+    };
+    let usage = BTreeMap::from([("build-minute".to_string(), 250u64)]);
+
+    let baseline = CostEstimate::compute(&state, &plans[..2], eur(0).currency, &usage);
+    let estimate = CostEstimate::compute(&state, &plans, eur(0).currency, &usage);
+    assert_roundtrip(&estimate);
+    assert_eq!(estimate.desired_state_version, 3);
+    assert_eq!(estimate.entries[0].monthly, Some(eur(0)));
+    assert_eq!(estimate.entries[0].confidence, CostConfidence::Exact);
+    assert_eq!(estimate.entries[1].monthly, Some(eur(20_000_000)));
+    assert_eq!(estimate.entries[2].monthly, Some(eur(30_000_000)));
+    assert_eq!(estimate.entries[2].confidence, CostConfidence::Estimated);
+    let metered = estimate.entries[3].metered.as_ref().unwrap();
+    assert_eq!(metered.overage_units, 150);
+    assert_eq!(estimate.entries[3].monthly, Some(eur(1_500_000)));
+    assert_eq!(estimate.monthly_total, eur(51_500_000));
+    assert_eq!(estimate.monthly_total.to_string(), "51.50 EUR");
+    assert_eq!(estimate.confidence, CostConfidence::Estimated);
+
+    assert_eq!(baseline.confidence, CostConfidence::Unknown);
+    assert_eq!(baseline.monthly_total, eur(20_000_000));
+    assert_eq!(estimate.delta_micro(&baseline), Some(31_500_000));
+
+    state.entries.push(entry("component.unpriced", None));
+    let estimate = CostEstimate::compute(&state, &plans, eur(0).currency, &BTreeMap::new());
+    assert_eq!(estimate.confidence, CostConfidence::Unknown);
+    assert_eq!(estimate.entries[3].monthly, None);
+    assert!(estimate.entries[4].note.is_some());
+    assert_eq!(estimate.monthly_total, eur(50_000_000));
+
+    let usd = CostEstimate::compute(&state, &plans, "USD".parse().unwrap(), &usage);
+    assert_eq!(usd.entries[1].monthly, None);
+    assert_eq!(
+        usd.entries[1].note.as_deref(),
+        Some("plan `flat` is priced in EUR")
+    );
+    assert_eq!(usd.delta_micro(&baseline), None);
+}
+
+#[test]
+fn lifecycle_follows_deprecation_and_sunset_dates() {
+    let mut plan = priced_plan("team", PriceModel::Free);
+    plan.deprecated_at = Some(UnixMillis::from_millis(1_000));
+    plan.sunset_at = Some(UnixMillis::from_millis(2_000));
+    plan.replacement_id = Some("team-v2".parse().unwrap());
+
+    let at = UnixMillis::from_millis;
+    assert_eq!(plan.lifecycle_at(at(999)), LifecycleState::Ga);
+    assert!(plan.is_purchasable(at(999)));
+    assert_eq!(plan.lifecycle_at(at(1_000)), LifecycleState::Deprecated);
+    assert!(!plan.is_purchasable(at(1_000)));
+    assert_eq!(plan.lifecycle_at(at(2_000)), LifecycleState::Retired);
+
+    let mut product = catalog_product("p-preview", StoreProductKind::Pack, &[], true);
+    product.lifecycle = LifecycleState::Preview;
+    assert!(product.is_purchasable(at(0)));
+    product.lifecycle = LifecycleState::Retired;
+    product.sunset_at = Some(at(5_000));
+    assert_eq!(product.lifecycle_at(at(0)), LifecycleState::Retired);
+
+    let value = serde_json::to_value(&plan).unwrap();
+    assert_eq!(value["lifecycle"], "ga");
+    assert_eq!(value["replacement_id"], "team-v2");
+    let legacy = json!({
+        "id": "old",
+        "name": "Old",
+        "description": "",
+        "price_model": "free",
+    });
+    let legacy: StorePlan = serde_json::from_value(legacy).unwrap();
+    assert_eq!(legacy.lifecycle, LifecycleState::Ga);
+    assert!(legacy.is_purchasable(at(i64::MAX)));
+}
+
+#[test]
+fn plan_feature_matrix_compares_plans() {
+    let mut free = priced_plan("free", PriceModel::Free);
+    free.features = vec![
+        PlanFeature::new("seats", "Seats", PlanFeatureValue::Quantity(3)),
+        PlanFeature::new("sso", "Single sign-on", PlanFeatureValue::Included(false)),
+    ];
+    let mut team = priced_plan("team", PriceModel::Free);
+    team.features = vec![
+        PlanFeature::new("seats", "Seats", PlanFeatureValue::Unlimited),
+        PlanFeature::new("sso", "Single sign-on", PlanFeatureValue::Included(true)),
+        PlanFeature::new("audit", "Audit log", PlanFeatureValue::Included(true)),
+    ];
+
+    assert!(team.covers_features_of(&free));
+    assert!(!free.covers_features_of(&team));
+    assert!(free.grants("seats", &PlanFeatureValue::Quantity(2)));
+    assert!(!free.grants("seats", &PlanFeatureValue::Quantity(4)));
+    assert!(!free.grants("audit", &PlanFeatureValue::Included(true)));
+    assert!(!PlanFeatureValue::Included(true).covers(&PlanFeatureValue::Quantity(1)));
+
+    let matrix = PlanFeatureMatrix::from_plans(&[free, team]);
+    assert_eq!(
+        matrix
+            .rows
+            .iter()
+            .map(|row| row.key.as_str())
+            .collect::<Vec<_>>(),
+        vec!["seats", "sso", "audit"]
+    );
+    assert_eq!(
+        matrix.rows[2].values,
+        vec![None, Some(PlanFeatureValue::Included(true))]
+    );
+    assert_roundtrip(&matrix);
+}
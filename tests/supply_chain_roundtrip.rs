@@ -1,15 +1,16 @@
-#![cfg(all(feature = "serde", feature = "time"))]
+#![cfg(feature = "serde")]
 
 use greentic_types::{
-    AttestationStatement, BuildPlan, BuildStatus, BuildStatusKind, MetadataRecord, PredicateType,
-    RegistryRef, RepoContext, ScanKind, ScanRequest, ScanResult, ScanStatusKind, SignRequest,
-    StoreContext, StoreRef, VerifyRequest, VerifyResult,
+    AttestationBundle, AttestationStatement, BuildLogChunk, BuildLogCursor, BuildLogStream,
+    BuildPlan, BuildStatus, BuildStatusKind, DurationMs, FindingSeverity, MetadataRecord,
+    PipelineRunStatus, PipelineStage, PipelineVerdict, PolicyDecision, PolicyDecisionStatus,
+    PolicyEvaluation, PredicateType, ProvenanceChain, ProvenanceIssue, ProvenanceStage,
+    RegistryRef, RepoContext, ScanKind, ScanRequest, ScanResult, ScanStatusKind, SeveritySummary,
+    SignRequest, StoreContext, StoreRef, UnixMillis, VerifyRequest, VerifyResult,
 };
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::json;
-use time::OffsetDateTime;
-use time::macros::datetime;
 
 fn assert_roundtrip<T>(value: &T)
 where
@@ -42,8 +43,8 @@ fn build_plan_and_status_roundtrip() {
     let status = BuildStatus {
         build_id: plan.build_id.clone(),
         status: BuildStatusKind::Succeeded,
-        started_at_utc: Some(datetime!(2025-01-02 03:04:05 UTC)),
-        finished_at_utc: Some(datetime!(2025-01-02 03:14:05 UTC)),
+        started_at_utc: Some(UnixMillis::from_millis(1_735_787_045_000)),
+        finished_at_utc: Some(UnixMillis::from_millis(1_735_787_645_000)),
         artifacts: plan.outputs.clone(),
         logs_ref: Some("logs://build-1".into()),
         log_refs: vec!["log-1".parse().unwrap()],
@@ -51,6 +52,17 @@ fn build_plan_and_status_roundtrip() {
     };
 
     assert_roundtrip(&status);
+
+    let json = serde_json::to_value(&status).unwrap();
+    assert_eq!(json["started_at_utc"], "2025-01-02T03:04:05Z");
+    assert_eq!(json["finished_at_utc"], "2025-01-02T03:14:05Z");
+    assert_eq!(
+        status
+            .finished_at_utc
+            .unwrap()
+            .saturating_duration_since(status.started_at_utc.unwrap()),
+        DurationMs::from_mins(10)
+    );
 }
 
 #[test]
@@ -73,11 +85,65 @@ fn scan_request_and_result_roundtrip() {
         status: ScanStatusKind::Succeeded,
         sbom: Some("sbom-1".parse().unwrap()),
         findings: json!({"vulns": [{"id": "CVE-1234"}]}),
-        started_at_utc: Some(OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap()),
-        finished_at_utc: Some(OffsetDateTime::from_unix_timestamp(1_700_000_500).unwrap()),
+        started_at_utc: Some(UnixMillis::from_millis(1_700_000_000_000)),
+        finished_at_utc: Some(UnixMillis::from_millis(1_700_000_500_000)),
+        summary: None,
+    };
+
+    assert_roundtrip(&result);
+}
+
+#[test]
+fn scan_result_severity_summary() {
+    let mut result = ScanResult {
+        scan_id: "scan-2".parse().unwrap(),
+        component: "component.repo".parse().unwrap(),
+        kind: ScanKind::Dependencies,
+        status: ScanStatusKind::Succeeded,
+        sbom: None,
+        findings: json!({
+            "Results": [
+                {"Target": "Cargo.lock", "Vulnerabilities": [
+                    {"VulnerabilityID": "CVE-1", "Severity": "CRITICAL"},
+                    {"VulnerabilityID": "CVE-2", "Severity": "high"},
+                    {"VulnerabilityID": "CVE-3", "Severity": "Moderate"}
+                ]},
+                {"Target": "image", "Vulnerabilities": [
+                    {"VulnerabilityID": "CVE-4", "severity": "negligible"},
+                    {"VulnerabilityID": "CVE-5", "severity": "odd"}
+                ]}
+            ]
+        }),
+        started_at_utc: None,
+        finished_at_utc: None,
+        summary: None,
     };
 
+    let summary = result.severity_summary();
+    assert_eq!(
+        summary,
+        SeveritySummary {
+            critical: 1,
+            high: 1,
+            medium: 1,
+            low: 0,
+            info: 1,
+            unknown: 1,
+            worst: Some(FindingSeverity::Critical),
+        }
+    );
+    assert_eq!(summary.total(), 5);
+    assert!(summary.exceeds(FindingSeverity::High));
+    assert_eq!(summary.count(FindingSeverity::Medium), 1);
+
+    result.persist_severity_summary();
+    result.findings = json!([]);
+    assert_eq!(result.severity_summary(), summary);
     assert_roundtrip(&result);
+
+    let empty = SeveritySummary::from_findings(&json!({"vulns": []}));
+    assert_eq!(empty.worst, None);
+    assert!(!empty.exceeds(FindingSeverity::Unknown));
 }
 
 #[test]
@@ -149,3 +215,302 @@ fn context_wrappers_roundtrip() {
     assert_roundtrip(&repo_ctx);
     assert_roundtrip(&store_ctx);
 }
+
+fn statement(id: &str, predicate_type: PredicateType) -> AttestationStatement {
+    AttestationStatement {
+        attestation_id: None,
+        attestation: id.parse().unwrap(),
+        predicate_type,
+        statement: format!("stmt-{id}").parse().unwrap(),
+        registry: None,
+        store: None,
+        metadata: json!({}),
+    }
+}
+
+#[test]
+fn attestation_bundle_digest_and_verification() {
+    let sbom = PredicateType::Custom("sbom".into());
+    let mut bundle = AttestationBundle::new("artifact-1".parse().unwrap());
+    bundle.push(statement("att-1", PredicateType::Slsa));
+    bundle.push(statement("att-2", sbom.clone()));
+    assert!(bundle.verify_digest().is_err());
+
+    let digest = bundle.seal().to_owned();
+    assert!(digest.starts_with("sha256:"));
+    assert_roundtrip(&bundle);
+    bundle.verify(&[PredicateType::Slsa, sbom.clone()]).unwrap();
+
+    let mut reordered = bundle.clone();
+    reordered.statements.reverse();
+    reordered.verify_digest().unwrap();
+
+    assert_eq!(
+        bundle.missing_predicates(&[PredicateType::Vulnerability, sbom]),
+        vec![PredicateType::Vulnerability]
+    );
+    assert!(bundle.verify(&[PredicateType::Vulnerability]).is_err());
+
+    let mut tampered = bundle.clone();
+    tampered.statements[0].statement = "stmt-other".parse().unwrap();
+    assert!(tampered.verify_digest().is_err());
+
+    let tamper = |edit: &dyn Fn(&mut AttestationBundle)| {
+        let mut copy = bundle.clone();
+        edit(&mut copy);
+        assert!(copy.verify_digest().is_err(), "{copy:?}");
+    };
+    tamper(&|b| b.statements[0].attestation_id = Some("id-9".parse().unwrap()));
+    tamper(&|b| b.statements[0].registry = Some("registry-9".parse().unwrap()));
+    tamper(&|b| b.statements[0].store = Some("store-9".parse().unwrap()));
+    tamper(&|b| b.statements[0].metadata = json!({"signed": false}));
+    tamper(&|b| b.metadata = json!({"source": "mirror"}));
+
+    let mut keys_reordered = bundle.clone();
+    keys_reordered.metadata = json!({"b": 1, "a": [true, null]});
+    keys_reordered.seal();
+    let mut same = keys_reordered.clone();
+    same.metadata = json!({"a": [true, null], "b": 1});
+    same.verify_digest().unwrap();
+}
+
+fn log_chunk(sequence: u64, bytes: &[u8], last: bool) -> BuildLogChunk {
+    BuildLogChunk {
+        log: "log-1".parse().unwrap(),
+        sequence,
+        stream: BuildLogStream::Stdout,
+        bytes: bytes.to_vec(),
+        emitted_at_utc: Some(UnixMillis::from_millis(1_735_787_045_000)),
+        last,
+    }
+}
+
+#[test]
+fn build_log_chunks_roundtrip_and_resume() {
+    let chunk = log_chunk(0, b"compiling\n\xff", false);
+    assert_roundtrip(&chunk);
+    let json = serde_json::to_value(&chunk).unwrap();
+    assert!(json["bytes"].is_string());
+    assert_eq!(json["emitted_at_utc"], "2025-01-02T03:04:05Z");
+    assert_eq!(chunk.text_lossy(), "compiling\n\u{fffd}");
+
+    let mut cursor = BuildLogCursor::new("log-1".parse().unwrap());
+    assert!(cursor.accept(&chunk).unwrap());
+    assert!(!cursor.accept(&chunk).unwrap());
+    assert!(cursor.accept(&log_chunk(2, b"", false)).is_err());
+    assert!(cursor.accept(&log_chunk(1, b"done\n", true)).unwrap());
+    assert!(cursor.complete);
+    assert_eq!(cursor.next_sequence, 2);
+    assert!(cursor.accept(&log_chunk(2, b"", false)).is_err());
+
+    let mut other = log_chunk(2, b"", false);
+    other.log = "log-2".parse().unwrap();
+    assert!(
+        BuildLogCursor::new("log-1".parse().unwrap())
+            .accept(&other)
+            .is_err()
+    );
+}
+
+fn build(status: BuildStatusKind) -> BuildStatus {
+    BuildStatus {
+        build_id: "build-1".parse().unwrap(),
+        status,
+        started_at_utc: None,
+        finished_at_utc: None,
+        artifacts: Vec::new(),
+        logs_ref: None,
+        log_refs: Vec::new(),
+        metadata: json!({}),
+    }
+}
+
+fn scan(status: ScanStatusKind, findings: serde_json::Value) -> ScanResult {
+    ScanResult {
+        scan_id: "scan-1".parse().unwrap(),
+        component: "component.repo".parse().unwrap(),
+        kind: ScanKind::Dependencies,
+        status,
+        sbom: None,
+        findings,
+        started_at_utc: None,
+        finished_at_utc: None,
+        summary: None,
+    }
+}
+
+#[test]
+fn pipeline_run_status_aggregates_stages() {
+    let mut run = PipelineRunStatus::new("run-1".parse().unwrap())
+        .with_blocking_severity(FindingSeverity::High);
+    assert_eq!(run.verdict, PipelineVerdict::Pending);
+
+    assert_eq!(
+        run.record_build(build(BuildStatusKind::Running)),
+        PipelineVerdict::Running
+    );
+    assert_eq!(
+        run.record_build(build(BuildStatusKind::Succeeded)),
+        PipelineVerdict::Running
+    );
+    assert_eq!(
+        run.stage_verdict(PipelineStage::Policy),
+        PipelineVerdict::Pending
+    );
+    assert_eq!(
+        run.record_scan(scan(ScanStatusKind::Pending, json!([]))),
+        PipelineVerdict::Running
+    );
+    assert_eq!(
+        run.record_scan(scan(
+            ScanStatusKind::Succeeded,
+            json!([{"severity": "CRITICAL"}])
+        )),
+        PipelineVerdict::Blocked
+    );
+    assert_eq!(run.scans.len(), 1);
+    assert_eq!(
+        run.stage_verdict(PipelineStage::Scan),
+        PipelineVerdict::Blocked
+    );
+    assert_eq!(
+        run.record_scan(scan(
+            ScanStatusKind::Succeeded,
+            json!([{"severity": "low"}])
+        )),
+        PipelineVerdict::Running
+    );
+
+    run.record_signature("sig-1".parse().unwrap());
+    assert_eq!(
+        run.record_verification(VerifyResult {
+            signature: "sig-1".parse().unwrap(),
+            valid: false,
+            message: None,
+            metadata: json!({}),
+        }),
+        PipelineVerdict::Failed
+    );
+    assert!(run.verdict.is_terminal());
+    run.verifications[0].valid = true;
+    run = run.refresh();
+    assert_eq!(run.verdict, PipelineVerdict::Running);
+
+    let deny = PolicyEvaluation {
+        policy: "policy-1".parse().unwrap(),
+        decision: PolicyDecision {
+            status: PolicyDecisionStatus::Deny,
+            reasons: vec!["unsigned base image".into()],
+            allow: None,
+            reason: None,
+            obligations: Vec::new(),
+        },
+    };
+    assert_eq!(run.record_policy(deny), PipelineVerdict::Blocked);
+    assert_roundtrip(&run);
+
+    run.record_build(build(BuildStatusKind::Cancelled));
+    assert_eq!(run.verdict, PipelineVerdict::Cancelled);
+}
+
+#[test]
+fn pipeline_run_only_skips_declared_stages() {
+    let mut run = PipelineRunStatus::new("run-2".parse().unwrap())
+        .with_skippable_stage(PipelineStage::Sign)
+        .with_skippable_stage(PipelineStage::Verify)
+        .with_skippable_stage(PipelineStage::Build);
+    assert!(!run.skippable_stages.contains(&PipelineStage::Build));
+    assert_eq!(
+        run.record_build(build(BuildStatusKind::Succeeded)),
+        PipelineVerdict::Running
+    );
+    assert_eq!(
+        run.record_scan(scan(ScanStatusKind::Succeeded, json!([]))),
+        PipelineVerdict::Running
+    );
+    assert_eq!(
+        run.stage_verdict(PipelineStage::Sign),
+        PipelineVerdict::Skipped
+    );
+    let allow = PolicyEvaluation {
+        policy: "policy-1".parse().unwrap(),
+        decision: PolicyDecision {
+            status: PolicyDecisionStatus::Allow,
+            reasons: Vec::new(),
+            allow: None,
+            reason: None,
+            obligations: Vec::new(),
+        },
+    };
+    assert_eq!(run.record_policy(allow), PipelineVerdict::Passed);
+    assert_roundtrip(&run);
+}
+
+#[test]
+fn provenance_chain_reports_missing_and_mismatched_stages() {
+    let mut chain = ProvenanceChain::new(
+        "component.repo".parse().unwrap(),
+        "version-1".parse().unwrap(),
+    );
+    assert_eq!(chain.completeness().missing, ProvenanceStage::ALL.to_vec());
+
+    chain
+        .attestations
+        .push(statement("att-1", PredicateType::Slsa));
+    assert_eq!(
+        chain.verify_links(),
+        vec![ProvenanceIssue::MissingPredecessor {
+            stage: ProvenanceStage::Attest,
+            requires: ProvenanceStage::Verify,
+        }]
+    );
+
+    let mut built = build(BuildStatusKind::Succeeded);
+    built.artifacts = vec!["artifact-1".parse().unwrap()];
+    chain.build = Some(built);
+    chain.scans.push(scan(ScanStatusKind::Succeeded, json!([])));
+    let mut foreign = scan(ScanStatusKind::Failed, json!([]));
+    foreign.scan_id = "scan-2".parse().unwrap();
+    foreign.component = "component.other".parse().unwrap();
+    chain.scans.push(foreign);
+    let sign = |artifact: &str| SignRequest {
+        signing_key: "key-1".parse().unwrap(),
+        artifact: artifact.parse().unwrap(),
+        payload: json!({}),
+        metadata: json!({}),
+    };
+    chain.signatures = vec![sign("artifact-1"), sign("artifact-9")];
+    chain.verifications.push(VerifyResult {
+        signature: "sig-1".parse().unwrap(),
+        valid: false,
+        message: None,
+        metadata: json!({}),
+    });
+
+    assert!(chain.completeness().is_complete());
+    assert_eq!(
+        chain.verify_links(),
+        vec![
+            ProvenanceIssue::ScanComponentMismatch {
+                scan_id: "scan-2".parse().unwrap(),
+                component: "component.other".parse().unwrap(),
+            },
+            ProvenanceIssue::ScanNotSucceeded {
+                scan_id: "scan-2".parse().unwrap(),
+                status: ScanStatusKind::Failed,
+            },
+            ProvenanceIssue::SignedArtifactNotBuilt {
+                artifact: "artifact-9".parse().unwrap(),
+            },
+            ProvenanceIssue::VerificationFailed {
+                signature: "sig-1".parse().unwrap(),
+            },
+        ]
+    );
+    assert_roundtrip(&chain);
+
+    chain.scans.truncate(1);
+    chain.signatures.truncate(1);
+    chain.verifications[0].valid = true;
+    assert!(chain.verify_links().is_empty());
+}
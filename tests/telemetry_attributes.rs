@@ -0,0 +1,113 @@
+#![cfg(feature = "otel-keys")]
+
+use std::collections::BTreeMap;
+
+use greentic_types::{ErrorCode, OtlpKeys, RunStatus, SpanContext, TelemetryAttributes, TenantCtx};
+
+#[test]
+fn builder_sets_known_and_extra_keys() {
+    let attributes = TelemetryAttributes::new()
+        .service_name("greentic-runner")
+        .pack_id("dev.local.weather")
+        .run_status(RunStatus::PartialFailure)
+        .extra("acme.queue.depth", "3")
+        .unwrap();
+
+    let map: BTreeMap<String, String> = attributes.clone().into();
+    assert_eq!(map.len(), 4);
+    assert_eq!(
+        attributes.get(OtlpKeys::RUN_STATUS),
+        Some("partial_failure")
+    );
+    assert_eq!(attributes.get("acme.queue.depth"), Some("3"));
+    assert_eq!(
+        attributes.iter().next(),
+        Some(("acme.queue.depth", "3")),
+        "attributes iterate in key order"
+    );
+}
+
+#[test]
+fn try_insert_rejects_unknown_keys() {
+    let mut attributes = TelemetryAttributes::new();
+    attributes
+        .try_insert(OtlpKeys::CAPABILITY, "http")
+        .expect("known key");
+    let err = attributes
+        .try_insert("greentic.flow_id", "main")
+        .expect_err("typo must be rejected");
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert_eq!(attributes.len(), 1);
+    assert!(OtlpKeys::ALL.iter().all(|key| OtlpKeys::is_known(key)));
+}
+
+#[test]
+fn extra_rejects_unknown_reserved_keys() {
+    let err = TelemetryAttributes::new()
+        .extra("greentic.flow_id", "main")
+        .expect_err("reserved namespace");
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    let attributes = TelemetryAttributes::new()
+        .extra(OtlpKeys::FLOW_ID, "main")
+        .unwrap();
+    assert_eq!(attributes.get(OtlpKeys::FLOW_ID), Some("main"));
+}
+
+#[test]
+fn attributes_derive_from_contexts() {
+    let ctx = TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap())
+        .with_team(Some("team-a".parse().unwrap()))
+        .with_session("sess-1")
+        .with_flow("flow-main");
+    let attributes = TelemetryAttributes::from(&ctx);
+    assert_eq!(attributes.get(OtlpKeys::TENANT_ID), Some("tenant-1"));
+    assert_eq!(attributes.get(OtlpKeys::TEAM_ID), Some("team-a"));
+    assert_eq!(attributes.get(OtlpKeys::SESSION_ID), Some("sess-1"));
+    assert_eq!(attributes.get(OtlpKeys::FLOW_ID), Some("flow-main"));
+    assert_eq!(attributes.get(OtlpKeys::USER_ID), None);
+
+    let span = SpanContext::new("tenant-2".parse().unwrap(), "flow-alpha", "runtime-core")
+        .with_node("node-7");
+    let attributes = TelemetryAttributes::from(&span).merge(TelemetryAttributes::from(&ctx));
+    assert_eq!(attributes.get(OtlpKeys::TENANT_ID), Some("tenant-1"));
+    assert_eq!(attributes.get(OtlpKeys::NODE_ID), Some("node-7"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn attributes_serialize_as_flat_map() {
+    let attributes = TelemetryAttributes::new()
+        .tenant_id("tenant-1")
+        .flow_id("main");
+    let json = serde_json::to_value(&attributes).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"greentic.flow.id": "main", "greentic.tenant.id": "tenant-1"})
+    );
+    let decoded: TelemetryAttributes = serde_json::from_value(json).unwrap();
+    assert_eq!(decoded, attributes);
+
+    let err = serde_json::from_value::<TelemetryAttributes>(
+        serde_json::json!({"greentic.flow_id": "main", "acme.queue.depth": "3"}),
+    )
+    .expect_err("reserved namespace");
+    assert!(err.to_string().contains("greentic.flow_id"));
+}
+
+#[test]
+fn store_and_supply_chain_keys_are_canonical() {
+    let attributes = TelemetryAttributes::new()
+        .store_product_id("prod-1")
+        .store_plan_id("plan-pro")
+        .store_subscription_id("sub-9")
+        .build_ref("build-42")
+        .scan_ref("scan-7")
+        .attestation_id("att-3");
+    assert_eq!(attributes.len(), 6);
+    assert_eq!(attributes.get("greentic.store.product.id"), Some("prod-1"));
+    assert_eq!(
+        attributes.get("greentic.supply_chain.attestation.id"),
+        Some("att-3")
+    );
+    assert!(attributes.iter().all(|(key, _)| OtlpKeys::is_known(key)));
+}
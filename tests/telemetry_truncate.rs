@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+
+use greentic_types::telemetry::truncate::{
+    truncate_attributes, truncate_error_detail, truncate_str,
+};
+use greentic_types::{ErrorDetail, NodeError};
+
+#[test]
+fn truncation_is_bounded_and_deterministic() {
+    let long = "é".repeat(500);
+    let short = truncate_str(&long, 64);
+    assert!(short.len() <= 64);
+    assert!(short.starts_with('é'));
+    assert!(short.contains("bytes blake3:"));
+    assert_eq!(short, truncate_str(&long, 64));
+    assert_ne!(short, truncate_str(&"e".repeat(1000), 64));
+    assert_eq!(truncate_str("tiny", 64), "tiny");
+    assert_eq!(truncate_str(&long, 5), "éé");
+
+    let mut attributes = BTreeMap::from([
+        ("payload".to_owned(), "x".repeat(4096)),
+        ("tenant".to_owned(), "acme".to_owned()),
+    ]);
+    assert_eq!(truncate_attributes(&mut attributes, 128), 1);
+    assert!(attributes["payload"].len() <= 128);
+    assert_eq!(attributes["tenant"], "acme");
+}
+
+#[test]
+fn error_details_are_trimmed() {
+    let mut error = NodeError::new("E_BIG", "x".repeat(300)).with_detail_text("y".repeat(300));
+    assert!(truncate_error_detail(&mut error, 100));
+    assert!(error.message.len() <= 100);
+    let Some(ErrorDetail::Text(text)) = &error.details else {
+        panic!("text detail expected");
+    };
+    assert!(text.len() <= 100);
+    assert!(!truncate_error_detail(&mut error, 100));
+
+    let mut error = NodeError::new("E_BIN", "boom").with_detail_binary(vec![0; 1024]);
+    assert!(truncate_error_detail(&mut error, 100));
+    let Some(ErrorDetail::Text(text)) = &error.details else {
+        panic!("binary detail should become a note");
+    };
+    assert!(text.starts_with("[binary detail]") && text.contains("+1024 bytes"));
+}
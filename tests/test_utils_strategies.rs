@@ -0,0 +1,58 @@
+#![cfg(all(feature = "test-utils", feature = "serde"))]
+
+use greentic_types::test_utils::{flow_kind, identifier, pack_manifest};
+use greentic_types::{
+    ChannelMessageEnvelope, Flow, FlowId, PackManifest, Severity, TenantCtx, TenantId,
+    decode_pack_manifest, encode_pack_manifest, validate_pack_manifest_core,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn identifiers_parse_as_ids(raw in identifier()) {
+        prop_assert!(TenantId::new(&raw).is_ok());
+        prop_assert!(FlowId::new(&raw).is_ok());
+    }
+
+    #[test]
+    fn flow_kinds_are_builtin(kind in flow_kind()) {
+        prop_assert!(kind.is_builtin());
+    }
+
+    #[test]
+    fn tenant_ctx_json_roundtrip(ctx in any::<TenantCtx>()) {
+        let json = serde_json::to_string(&ctx).unwrap();
+        let decoded: TenantCtx = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(decoded, ctx);
+    }
+
+    #[test]
+    fn envelope_json_roundtrip(envelope in any::<ChannelMessageEnvelope>()) {
+        let json = serde_json::to_string(&envelope).unwrap();
+        let decoded: ChannelMessageEnvelope = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(decoded, envelope);
+    }
+
+    #[test]
+    fn flow_routes_stay_inside_the_flow(flow in any::<Flow>()) {
+        let metrics = flow.metrics();
+        prop_assert!(metrics.unreachable.is_empty());
+        prop_assert_eq!(metrics.max_depth, flow.nodes.len());
+        let json = serde_json::to_string(&flow).unwrap();
+        let decoded: Flow = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(decoded, flow);
+    }
+
+    #[test]
+    fn pack_manifests_validate_and_roundtrip(manifest in pack_manifest()) {
+        let diagnostics = validate_pack_manifest_core(&manifest);
+        prop_assert!(
+            diagnostics.iter().all(|diag| diag.severity != Severity::Error),
+            "{:?}",
+            diagnostics
+        );
+        let bytes = encode_pack_manifest(&manifest).unwrap();
+        let decoded: PackManifest = decode_pack_manifest(&bytes).unwrap();
+        prop_assert_eq!(decoded, manifest);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, HashMap};
+
+use greentic_types::{
+    ErrorCode, MessageHeaders, SessionKey, SpanContext, SpanLinkKind, TRACE_STATE_MAX_MEMBERS,
+    TraceContextError, TraceParent, TraceState,
+};
+
+const HEADER: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+#[test]
+fn traceparent_parses_and_formats() {
+    let parent: TraceParent = HEADER.parse().unwrap();
+    assert_eq!(parent.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+    assert_eq!(parent.span_id(), "00f067aa0ba902b7");
+    assert!(parent.is_sampled());
+    assert_eq!(parent.to_string(), HEADER);
+
+    let future =
+        TraceParent::parse("cc-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-extra")
+            .unwrap();
+    assert!(!future.is_sampled());
+    assert!(future.as_str().starts_with("00-"));
+
+    for bad in [
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+        "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+        "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+    ] {
+        assert!(
+            matches!(
+                TraceParent::parse(bad),
+                Err(TraceContextError::InvalidTraceParent { .. })
+            ),
+            "{bad}"
+        );
+    }
+}
+
+#[test]
+fn tracestate_parses_updates_and_bounds_members() {
+    let mut state = TraceState::parse("rojo=00f067aa0ba902b7, ,congo=t61rcWkgMzE").unwrap();
+    assert_eq!(state.get("congo"), Some("t61rcWkgMzE"));
+    assert_eq!(state.to_string(), "rojo=00f067aa0ba902b7,congo=t61rcWkgMzE");
+
+    state.insert("congo", "updated").unwrap();
+    assert_eq!(state.as_str(), "congo=updated,rojo=00f067aa0ba902b7");
+    assert!(state.insert("Bad", "x").is_err());
+
+    assert!(TraceState::parse("a=1,a=2").is_err());
+    assert!(TraceState::parse("novalue").is_err());
+    let crowded: Vec<String> = (0..=TRACE_STATE_MAX_MEMBERS)
+        .map(|i| format!("k{i}=v"))
+        .collect();
+    assert!(TraceState::parse(&crowded.join(",")).is_err());
+
+    let mut full = TraceState::parse(&crowded[..TRACE_STATE_MAX_MEMBERS].join(",")).unwrap();
+    full.insert("vendor@tenant", "x").unwrap();
+    assert_eq!(full.entries().count(), TRACE_STATE_MAX_MEMBERS);
+    assert_eq!(full.get("k31"), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn span_context_roundtrips_trace_and_links() {
+    let parent: TraceParent = HEADER.parse().unwrap();
+    let retried =
+        TraceParent::new("0af7651916cd43dd8448eb211c80319c", "b7ad6b7169203331", 0).unwrap();
+    let span = SpanContext::new("tenant-1".parse().unwrap(), "flow-1", "runtime")
+        .with_trace(parent, Some(TraceState::parse("rojo=1").unwrap()))
+        .with_link(&retried, SpanLinkKind::RetriedFrom);
+
+    let json = serde_json::to_value(&span).unwrap();
+    assert_eq!(json["trace_parent"], HEADER);
+    assert_eq!(json["trace_state"], "rojo=1");
+    assert_eq!(json["links"][0]["kind"], "retried_from");
+    let back: SpanContext = serde_json::from_value(json).unwrap();
+    assert_eq!(back, span);
+
+    let mut broken = serde_json::to_value(&span).unwrap();
+    broken["trace_parent"] = "nope".into();
+    assert!(serde_json::from_value::<SpanContext>(broken).is_err());
+}
+
+#[test]
+fn span_context_roundtrips_through_message_headers() {
+    let span = SpanContext::new("tenant-1".parse().unwrap(), "flow-main", "nats-publisher")
+        .with_node("node-2")
+        .with_session(SessionKey::new("sess-9"))
+        .with_trace(
+            HEADER.parse().unwrap(),
+            Some("vendor=abc,other=1".parse().unwrap()),
+        );
+    let mut headers = BTreeMap::new();
+    span.to_message_headers(&mut headers);
+    assert_eq!(headers.get_header("traceparent"), Some(HEADER));
+    assert_eq!(headers.get_header("greentic-tenant-id"), Some("tenant-1"));
+
+    let restored = SpanContext::from_message_headers(&headers, "nats-consumer")
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored.provider, "nats-consumer");
+    assert_eq!(restored.node_id.as_deref(), Some("node-2"));
+    assert_eq!(restored.trace_parent, span.trace_parent);
+    assert_eq!(restored.trace_state, span.trace_state);
+
+    let mut kafka: HashMap<String, String> = HashMap::new();
+    assert!(
+        SpanContext::from_message_headers(&kafka, "kafka")
+            .unwrap()
+            .is_none()
+    );
+    SpanContext::new("tenant-1".parse().unwrap(), "flow-main", "kafka")
+        .to_message_headers(&mut kafka);
+    assert_eq!(kafka.len(), 2);
+    kafka.set_header("greentic-tenant-id", "bad tenant".into());
+    let err = SpanContext::from_message_headers(&kafka, "kafka").unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+}
+
+#[test]
+fn malformed_trace_headers_are_ignored() {
+    let mut headers = BTreeMap::new();
+    SpanContext::new("tenant-1".parse().unwrap(), "flow-main", "nats")
+        .to_message_headers(&mut headers);
+    headers.set_header("traceparent", "garbage".into());
+    headers.set_header("tracestate", "vendor=abc".into());
+    let restored = SpanContext::from_message_headers(&headers, "nats")
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored.trace_parent, None);
+    assert_eq!(restored.trace_state, None);
+
+    headers.set_header("traceparent", HEADER.into());
+    headers.set_header("tracestate", "not a tracestate".into());
+    let restored = SpanContext::from_message_headers(&headers, "nats")
+        .unwrap()
+        .unwrap();
+    assert_eq!(restored.trace_parent, Some(HEADER.parse().unwrap()));
+    assert_eq!(restored.trace_state, None);
+}
+
+#[test]
+fn trace_context_is_read_without_greentic_headers() {
+    let mut foreign: HashMap<String, String> = HashMap::new();
+    foreign.set_header("traceparent", HEADER.into());
+    foreign.set_header("tracestate", "vendor=abc".into());
+    assert!(
+        SpanContext::from_message_headers(&foreign, "kafka")
+            .unwrap()
+            .is_none()
+    );
+    let (parent, state) = SpanContext::trace_from_message_headers(&foreign).unwrap();
+    assert_eq!(parent.as_str(), HEADER);
+    assert_eq!(state.unwrap().get("vendor"), Some("abc"));
+
+    foreign.set_header("traceparent", "garbage".into());
+    assert!(SpanContext::trace_from_message_headers(&foreign).is_none());
+}
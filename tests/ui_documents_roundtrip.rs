@@ -1,10 +1,13 @@
 #![cfg(feature = "serde")]
 
 use greentic_types::{
-    DefaultPipeline, DidContext, DidService, DistributorTarget, EnabledPacks,
-    IdentityProviderOption, RepoAuth, RepoConfigFeatures, RepoSkin, RepoSkinLayout, RepoSkinLinks,
-    RepoSkinTheme, RepoTenantConfig, RepoWorkerPanel, StoreTarget, TenantDidDocument,
-    VerificationMethod,
+    BootstrapAdmin, ByteSize, ConnectionKind, DefaultPipeline, DidContext, DidDocumentError,
+    DidService, DistributorTarget, EnabledPacks, Environment, FeatureFlag, FeatureFlags, FlagValue,
+    IdentityProviderOption, MultibaseBase, MultibaseKey, Percent, PublicJwk, PublicKeyMaterial,
+    QuotaResource, QuotaUsage, QuotaViolation, RepoAuth, RepoConfigFeatures, RepoSkin,
+    RepoSkinLayout, RepoSkinLinks, RepoSkinTheme, RepoTenantConfig, RepoWorkerPanel, StoreTarget,
+    TenantBootstrap, TenantCtx, TenantDidDocument, TenantQuota, UnixMillis, VerificationMethod,
+    rollout_bucket,
 };
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -27,9 +30,9 @@ fn repo_skin_roundtrip() {
         tenant_name: Some("Nutanix".into()),
         product_name: Some("Greentic Repo".into()),
         theme: RepoSkinTheme {
-            logo_url: "https://cdn.greentic.ai/logo.svg".into(),
-            favicon_url: Some("https://cdn.greentic.ai/favicon.ico".into()),
-            hero_image_url: Some("https://cdn.greentic.ai/hero.png".into()),
+            logo_url: "https://cdn.greentic.ai/logo.svg".parse().unwrap(),
+            favicon_url: Some("https://cdn.greentic.ai/favicon.ico".parse().unwrap()),
+            hero_image_url: Some("https://cdn.greentic.ai/hero.png".parse().unwrap()),
             primary_color: "#00cc88".into(),
             accent_color: "#ff9900".into(),
             background_color: Some("#0b1021".into()),
@@ -58,9 +61,9 @@ fn repo_skin_roundtrip() {
             position: Some("right".into()),
         }),
         links: Some(RepoSkinLinks {
-            docs_url: Some("https://docs.greentic.ai".into()),
-            support_url: Some("https://support.greentic.ai".into()),
-            status_url: Some("https://status.greentic.ai".into()),
+            docs_url: Some("https://docs.greentic.ai".parse().unwrap()),
+            support_url: Some("https://support.greentic.ai".parse().unwrap()),
+            status_url: Some("https://status.greentic.ai".parse().unwrap()),
         }),
     };
 
@@ -77,7 +80,7 @@ fn repo_auth_roundtrip() {
             icon: Some("github".into()),
             button_style: Some("dark".into()),
             order: Some(1),
-            login_url: "https://auth.greentic.ai/github".into(),
+            login_url: "https://auth.greentic.ai/github".parse().unwrap(),
             description: Some("Sign in with GitHub".into()),
             recommended: Some(true),
         },
@@ -88,7 +91,7 @@ fn repo_auth_roundtrip() {
             icon: Some("azure".into()),
             button_style: None,
             order: Some(2),
-            login_url: "https://auth.greentic.ai/azure".into(),
+            login_url: "https://auth.greentic.ai/azure".parse().unwrap(),
             description: None,
             recommended: None,
         },
@@ -135,13 +138,13 @@ fn repo_tenant_config_roundtrip() {
         stores: Some(vec![StoreTarget {
             id: "primary-store".into(),
             label: "Primary Store".into(),
-            url: "https://store.greentic.ai".into(),
+            url: "https://store.greentic.ai".parse().unwrap(),
             description: Some("Public artifact store".into()),
         }]),
         distributors: Some(vec![DistributorTarget {
             id: "edge-distributor".into(),
             label: "Edge Distributor".into(),
-            url: "https://distributor.greentic.ai".into(),
+            url: "https://distributor.greentic.ai".parse().unwrap(),
             description: Some("Edge locations".into()),
         }]),
         features: Some(RepoConfigFeatures {
@@ -171,7 +174,7 @@ fn tenant_did_document_roundtrip() {
         service: vec![DidService {
             id: "#repo-api".into(),
             r#type: "RepoApi".into(),
-            service_endpoint: "https://repo.greentic.ai/api".into(),
+            service_endpoint: "https://repo.greentic.ai/api".parse().unwrap(),
         }],
     };
 
@@ -193,3 +196,239 @@ fn tenant_did_document_roundtrip() {
     );
     assert_roundtrip(&doc_multi);
 }
+
+#[test]
+fn tenant_did_document_resolves_keys() {
+    let did = "did:web:repos.did.greentic.ai%3A8443:tenants:tenant-1";
+    let mut doc = TenantDidDocument {
+        raw_context: None,
+        id: did.into(),
+        verification_method: Some(vec![
+            VerificationMethod {
+                id: "#key-1".into(),
+                r#type: "JsonWebKey2020".into(),
+                controller: did.into(),
+                public_key_jwk: Some(json!({"kty": "OKP", "crv": "Ed25519", "x": "abc"})),
+                public_key_multibase: None,
+            },
+            VerificationMethod {
+                id: format!("{did}#key-2"),
+                r#type: "Multikey".into(),
+                controller: did.into(),
+                public_key_jwk: None,
+                public_key_multibase: Some("z6MkhaXgBZD".into()),
+            },
+        ]),
+        authentication: Some(vec![format!("{did}#key-1"), "#key-2".into()]),
+        service: Vec::new(),
+    };
+
+    assert_eq!(
+        doc.verification_method("#key-2").map(|m| m.r#type.as_str()),
+        Some("Multikey")
+    );
+    let auth = doc.authentication_methods().unwrap();
+    assert_eq!(auth.len(), 2);
+    assert_eq!(
+        auth[0].public_key().unwrap(),
+        PublicKeyMaterial::Jwk(PublicJwk {
+            kty: "OKP".into(),
+            crv: Some("Ed25519".into()),
+            x: Some("abc".into()),
+            y: None,
+            n: None,
+            e: None,
+            kid: None,
+        })
+    );
+    assert_eq!(
+        auth[1].public_key().unwrap(),
+        PublicKeyMaterial::Multibase(MultibaseKey {
+            base: MultibaseBase::Base58Btc,
+            encoded: "6MkhaXgBZD".into(),
+        })
+    );
+
+    assert_eq!(
+        doc.did_web_host().as_deref(),
+        Some("repos.did.greentic.ai:8443")
+    );
+    doc.ensure_host("REPOS.did.greentic.ai:8443").unwrap();
+    assert_eq!(
+        doc.ensure_host("evil.example"),
+        Err(DidDocumentError::HostMismatch {
+            expected: "evil.example".into(),
+            actual: "repos.did.greentic.ai:8443".into(),
+        })
+    );
+    assert!(doc.validate().diagnostics.is_empty());
+
+    doc.authentication = Some(vec!["#missing".into()]);
+    if let Some(methods) = doc.verification_method.as_mut() {
+        methods[0].public_key_jwk = Some(json!({"kty": "OKP", "x": "abc", "d": "secret"}));
+    }
+    doc.id = "did:key:z6Mk".into();
+    assert!(matches!(
+        doc.authentication_methods(),
+        Err(DidDocumentError::UnresolvedReference { .. })
+    ));
+    let codes: Vec<_> = doc
+        .validate()
+        .diagnostics
+        .into_iter()
+        .map(|diag| diag.code)
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "DID_NOT_DID_WEB",
+            "DID_VERIFICATION_METHOD_INVALID_KEY",
+            "DID_AUTHENTICATION_UNRESOLVED"
+        ]
+    );
+}
+
+#[test]
+fn tenant_bootstrap_roundtrip_and_validation() {
+    let tenant = TenantCtx::new("prod".parse().unwrap(), "tenant-1".parse().unwrap());
+    let mut bootstrap = TenantBootstrap {
+        tenant: tenant.clone(),
+        config: RepoTenantConfig {
+            tenant_id: "tenant-1".into(),
+            enabled_tabs: vec!["dashboard".into()],
+            enabled_packs: EnabledPacks::default(),
+            default_pipeline: None,
+            stores: None,
+            distributors: None,
+            features: None,
+            page_handlers: None,
+        },
+        default_packs: vec!["greentic.core".parse().unwrap()],
+        default_environment: Environment::new(
+            "env-prod".parse().unwrap(),
+            tenant,
+            "dist-1".parse().unwrap(),
+            ConnectionKind::Online,
+            "Production",
+        ),
+        admin: BootstrapAdmin {
+            user_id: "admin-1".parse().unwrap(),
+            email: "admin@example.com".into(),
+            display_name: Some("Admin".into()),
+        },
+        metadata: BTreeMap::new(),
+    };
+    assert_roundtrip(&bootstrap);
+    assert!(bootstrap.validate().diagnostics.is_empty());
+
+    bootstrap.config.tenant_id = "tenant-2".into();
+    bootstrap.default_environment.tenant =
+        TenantCtx::new("dev".parse().unwrap(), "tenant-1".parse().unwrap());
+    bootstrap.admin.email = "admin@localhost".into();
+    bootstrap
+        .default_packs
+        .push("greentic.core".parse().unwrap());
+    let report = bootstrap.validate();
+    assert!(report.has_errors());
+    let codes: Vec<&str> = report
+        .diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.code.as_str())
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "TENANT_BOOTSTRAP_CONFIG_TENANT_MISMATCH",
+            "TENANT_BOOTSTRAP_ENVIRONMENT_TENANT_MISMATCH",
+            "TENANT_BOOTSTRAP_INVALID_ADMIN_EMAIL",
+            "TENANT_BOOTSTRAP_DUPLICATE_PACK",
+        ]
+    );
+}
+
+#[test]
+fn tenant_quota_roundtrip_and_check() {
+    let quota = TenantQuota {
+        max_flows: Some(10),
+        max_sessions: None,
+        max_monthly_runs: Some(1_000),
+        max_storage: Some(ByteSize::from_mib(512)),
+        metadata: BTreeMap::new(),
+    };
+    assert_roundtrip(&quota);
+    let usage = QuotaUsage {
+        flows: 10,
+        sessions: 5_000,
+        monthly_runs: 1_001,
+        storage: ByteSize::from_mib(513),
+    };
+    assert_roundtrip(&usage);
+
+    assert_eq!(
+        quota.check(&usage),
+        vec![
+            QuotaViolation {
+                resource: QuotaResource::MonthlyRuns,
+                limit: 1_000,
+                used: 1_001,
+            },
+            QuotaViolation {
+                resource: QuotaResource::Storage,
+                limit: 512 * 1024 * 1024,
+                used: 513 * 1024 * 1024,
+            },
+        ]
+    );
+    assert!(TenantQuota::default().check(&usage).is_empty());
+}
+
+#[test]
+fn feature_flags_roundtrip_and_rollout() {
+    let mut flags = FeatureFlags::default();
+    flags
+        .flags
+        .insert("new-editor".into(), FeatureFlag::new(true));
+    flags.flags.insert(
+        "checkout".into(),
+        FeatureFlag::new("variant-b")
+            .with_rollout(Percent::new(25.0).unwrap())
+            .with_expiry(UnixMillis::from_millis(2_000)),
+    );
+    flags
+        .flags
+        .insert("disabled".into(), FeatureFlag::new(false));
+    assert_roundtrip(&flags);
+
+    let parsed: FeatureFlags = serde_json::from_value(json!({
+        "flags": {
+            "limit": { "value": 3.5 },
+            "arm": { "value": "a", "rollout": 100.0 }
+        }
+    }))
+    .unwrap();
+    assert_eq!(parsed.flags["limit"].value, FlagValue::Number(3.5));
+    assert_eq!(parsed.flags["arm"].value, FlagValue::String("a".into()));
+
+    let now = UnixMillis::from_millis(1_000);
+    assert!(flags.is_enabled("new-editor", "tenant-a", now));
+    assert!(!flags.is_enabled("disabled", "tenant-a", now));
+    assert!(!flags.is_enabled("unknown", "tenant-a", now));
+
+    let keys: Vec<String> = (0..1_000).map(|i| format!("user-{i}")).collect();
+    let enabled = keys
+        .iter()
+        .filter(|key| flags.is_enabled("checkout", key, now))
+        .count();
+    assert!((200..300).contains(&enabled), "{enabled}");
+    for key in &keys {
+        assert_eq!(
+            flags.is_enabled("checkout", key, now),
+            rollout_bucket("checkout", key) < 2_500
+        );
+        assert!(!flags.is_enabled("checkout", key, UnixMillis::from_millis(2_000)));
+    }
+    assert_eq!(
+        rollout_bucket("checkout", "user-1"),
+        rollout_bucket("checkout", "user-1")
+    );
+}
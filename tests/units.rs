@@ -0,0 +1,226 @@
+#![cfg(feature = "serde")]
+
+use greentic_types::{
+    ByteSize, DurationMs, ErrorCode, HttpCaps, Limits, Percent, Ratio, ResourceHints, UnixMillis,
+};
+use serde_json::json;
+
+#[test]
+fn byte_size_parses_decimal_and_binary_units() {
+    assert_eq!("2048".parse::<ByteSize>().unwrap().as_bytes(), 2048);
+    assert_eq!(
+        "512MiB".parse::<ByteSize>().unwrap(),
+        ByteSize::from_mib(512)
+    );
+    assert_eq!(
+        "1.5 GiB".parse::<ByteSize>().unwrap(),
+        ByteSize::from_mib(1536)
+    );
+    assert_eq!("10kb".parse::<ByteSize>().unwrap().as_bytes(), 10_000);
+    assert_eq!(ByteSize::from_mib(512).to_string(), "512MiB");
+    assert_eq!(ByteSize::from_bytes(1500).to_string(), "1500B");
+
+    let err = "12 parsecs".parse::<ByteSize>().unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert!("-1MiB".parse::<ByteSize>().is_err());
+}
+
+#[test]
+fn duration_parses_units_and_compound_values() {
+    assert_eq!("250".parse::<DurationMs>().unwrap().as_millis(), 250);
+    assert_eq!(
+        "30s".parse::<DurationMs>().unwrap(),
+        DurationMs::from_secs(30)
+    );
+    assert_eq!(
+        "1m30s".parse::<DurationMs>().unwrap(),
+        DurationMs::from_secs(90)
+    );
+    assert_eq!(
+        "1.5h".parse::<DurationMs>().unwrap(),
+        DurationMs::from_mins(90)
+    );
+    assert_eq!(DurationMs::from_secs(90).to_string(), "90s");
+    assert_eq!(DurationMs::from_millis(1500).to_string(), "1500ms");
+
+    assert!("".parse::<DurationMs>().is_err());
+    assert!("5 fortnights".parse::<DurationMs>().is_err());
+    assert!("10 20s".parse::<DurationMs>().is_err());
+}
+
+#[test]
+fn quantities_accept_numbers_and_strings() {
+    let size: ByteSize = serde_json::from_value(json!("4KiB")).unwrap();
+    assert_eq!(size.as_bytes(), 4096);
+    let size: ByteSize = serde_json::from_value(json!(4096)).unwrap();
+    assert_eq!(serde_json::to_value(size).unwrap(), json!(4096));
+
+    let duration: DurationMs = serde_json::from_value(json!("2s")).unwrap();
+    assert_eq!(serde_json::to_value(duration).unwrap(), json!(2000));
+    assert!(serde_json::from_value::<DurationMs>(json!(-5)).is_err());
+}
+
+#[test]
+fn unix_millis_arithmetic_and_wire_format() {
+    let start = UnixMillis::from_millis(1_700_000_000_000);
+    let end = start.checked_add(DurationMs::from_secs(90)).unwrap();
+    assert_eq!(end.as_millis(), 1_700_000_090_000);
+    assert_eq!(
+        end.saturating_duration_since(start),
+        DurationMs::from_secs(90)
+    );
+    assert_eq!(start.saturating_duration_since(end), DurationMs::ZERO);
+    assert!(
+        UnixMillis::from_millis(i64::MAX)
+            .checked_add(DurationMs::from_millis(1))
+            .is_none()
+    );
+
+    assert_eq!(
+        serde_json::to_value(end).unwrap(),
+        json!(1_700_000_090_000i64)
+    );
+    let before_epoch: UnixMillis = serde_json::from_value(json!(-1)).unwrap();
+    assert!(before_epoch < UnixMillis::EPOCH);
+}
+
+#[test]
+fn unix_millis_rfc3339_roundtrip() {
+    let cases = [
+        (0, "1970-01-01T00:00:00Z"),
+        (-1, "1969-12-31T23:59:59.999Z"),
+        (1_700_000_000_250, "2023-11-14T22:13:20.250Z"),
+        (951_782_400_000, "2000-02-29T00:00:00Z"),
+    ];
+    for (millis, text) in cases {
+        let value = UnixMillis::from_millis(millis);
+        assert_eq!(value.to_rfc3339().as_deref(), Some(text));
+        assert_eq!(UnixMillis::parse_rfc3339(text).unwrap(), value);
+    }
+    assert_eq!(
+        UnixMillis::parse_rfc3339("2023-11-14T23:13:20.2509+01:00").unwrap(),
+        UnixMillis::from_millis(1_700_000_000_250)
+    );
+    for invalid in [
+        "2023-02-29T00:00:00Z",
+        "2023-11-14T24:00:00Z",
+        "2023-11-14T22:13:20",
+        "2023-11-14T22:13:20.Z",
+        "2023-11-14",
+    ] {
+        assert!(UnixMillis::parse_rfc3339(invalid).is_err(), "{invalid}");
+    }
+    assert_eq!(UnixMillis::from_millis(i64::MAX).to_rfc3339(), None);
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn unix_millis_converts_offset_date_time() {
+    let instant = time::macros::datetime!(1969-12-31 23:59:59.9995 UTC);
+    let millis = UnixMillis::from(instant);
+    assert_eq!(millis.as_millis(), -1);
+    assert_eq!(
+        millis.to_offset_date_time().unwrap(),
+        time::macros::datetime!(1969-12-31 23:59:59.999 UTC)
+    );
+}
+
+#[test]
+fn limits_keep_legacy_numeric_wire_format() {
+    let limits: Limits = serde_json::from_value(json!({
+        "memory_mb": 256,
+        "wall_time_ms": 15000
+    }))
+    .unwrap();
+    assert_eq!(limits.memory, ByteSize::from_mib(256));
+    assert_eq!(limits.wall_time, DurationMs::from_secs(15));
+    assert_eq!(
+        serde_json::to_value(&limits).unwrap(),
+        json!({ "memory_mb": 256, "wall_time_ms": 15000 })
+    );
+
+    let limits: Limits = serde_json::from_value(json!({
+        "memory_mb": "1GiB",
+        "wall_time_ms": "1m"
+    }))
+    .unwrap();
+    assert_eq!(limits.memory, ByteSize::from_gib(1));
+    assert_eq!(limits.wall_time, DurationMs::from_mins(1));
+    assert_eq!(
+        serde_json::to_value(&limits).unwrap()["memory_mb"],
+        json!(1024)
+    );
+
+    let odd = Limits::new(ByteSize::from_bytes(1500), DurationMs::ZERO);
+    let value = serde_json::to_value(&odd).unwrap();
+    assert_eq!(value["memory_mb"], json!("1500B"));
+    assert_eq!(serde_json::from_value::<Limits>(value).unwrap(), odd);
+}
+
+#[test]
+fn resource_hints_and_http_caps_accept_unit_strings() {
+    let hints: ResourceHints = serde_json::from_value(json!({
+        "cpu_millis": 500,
+        "memory_mb": "128MiB",
+        "average_latency_ms": "1.5s"
+    }))
+    .unwrap();
+    assert_eq!(hints.memory, Some(ByteSize::from_mib(128)));
+    assert_eq!(hints.average_latency, Some(DurationMs::from_millis(1500)));
+    assert_eq!(
+        serde_json::to_value(&hints).unwrap(),
+        json!({ "cpu_millis": 500, "memory_mb": 128, "average_latency_ms": 1500 })
+    );
+    assert_eq!(
+        serde_json::to_value(ResourceHints::default()).unwrap(),
+        json!({})
+    );
+
+    let http: HttpCaps = serde_json::from_value(json!({ "max_body_bytes": "1MiB" })).unwrap();
+    assert_eq!(http.max_body_bytes, Some(ByteSize::from_mib(1)));
+}
+
+#[test]
+fn percent_and_ratio_enforce_bounds() {
+    let percent = Percent::new(12.5).unwrap();
+    assert_eq!(percent.of(1_000), 125);
+    assert_eq!(percent.as_ratio(), Ratio::new(0.125).unwrap());
+    assert_eq!(
+        Ratio::new(0.5).unwrap().as_percent(),
+        Percent::new(50.0).unwrap()
+    );
+    assert_eq!(Percent::HUNDRED.of(u64::MAX), u64::MAX);
+    assert_eq!(percent.to_string(), "12.5%");
+
+    for invalid in [-1.0, 100.5, f64::NAN] {
+        assert_eq!(
+            Percent::new(invalid).unwrap_err().code,
+            ErrorCode::InvalidInput
+        );
+    }
+    assert!(Ratio::new(1.01).is_err());
+    assert!(Ratio::new(f64::INFINITY).is_err());
+
+    assert_eq!(serde_json::to_value(percent).unwrap(), json!(12.5));
+    assert_eq!(
+        serde_json::from_value::<Ratio>(json!(1)).unwrap(),
+        Ratio::ONE
+    );
+    assert!(serde_json::from_value::<Percent>(json!(101)).is_err());
+    assert!(serde_json::from_value::<Ratio>(json!(-0.2)).is_err());
+}
+
+#[test]
+fn percent_and_ratio_encode_as_basis_points_in_cbor() {
+    let percent = Percent::new(12.5).unwrap();
+    assert_eq!(percent.basis_points(), 1250);
+    assert_eq!(Ratio::new(0.01).unwrap().basis_points(), 100);
+    assert!(Percent::from_basis_points(10_001).is_err());
+
+    let bytes = greentic_types::cbor::canonical::to_canonical_cbor(&percent).unwrap();
+    assert_eq!(bytes, [0x19, 0x04, 0xE2]);
+    let decoded: Percent = greentic_types::cbor::canonical::from_cbor(&bytes).unwrap();
+    assert_eq!(decoded, percent);
+    let legacy: Ratio = greentic_types::cbor::canonical::from_cbor(&[0xF9, 0x38, 0x00]).unwrap();
+    assert_eq!(legacy, Ratio::new(0.5).unwrap());
+}
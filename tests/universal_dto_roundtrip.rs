@@ -1,10 +1,12 @@
 #![cfg(feature = "serde")]
 
 use greentic_types::{
-    AuthUserRefV1, ChannelMessageEnvelope, EncodeInV1, Header, HttpInV1, HttpOutV1,
-    ProviderPayloadV1, RenderPlanInV1, RenderPlanOutV1, SendPayloadInV1, SendPayloadResultV1,
-    SubscriptionDeleteInV1, SubscriptionDeleteOutV1, SubscriptionEnsureInV1,
-    SubscriptionEnsureOutV1, SubscriptionRenewInV1, SubscriptionRenewOutV1, TenantCtx,
+    AuthUserRefV1, BroadcastAudienceV1, BroadcastRecipientResultV1, BroadcastRecipientV1,
+    BroadcastSendInV1, BroadcastSendResultV1, ChannelMessageEnvelope, EncodeInV1, ErrorCode,
+    Header, HttpInV1, HttpOutV1, ProviderErrorKind, ProviderPayloadV1, RenderPlanInV1,
+    RenderPlanOutV1, SendPayloadInV1, SendPayloadResultV1, SubscriptionDeleteInV1,
+    SubscriptionDeleteOutV1, SubscriptionEnsureInV1, SubscriptionEnsureOutV1,
+    SubscriptionRenewInV1, SubscriptionRenewOutV1, TenantCtx,
 };
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -35,6 +37,7 @@ fn sample_envelope() -> ChannelMessageEnvelope {
         text: Some("hello".into()),
         attachments: Vec::new(),
         metadata: BTreeMap::new(),
+        delivery_options: None,
     }
 }
 
@@ -112,16 +115,101 @@ fn send_payload_dtos_roundtrip() {
             display_name: Some("User".into()),
         }),
         payload,
+        delivery_options: None,
     };
     let send_result = SendPayloadResultV1 {
         ok: true,
         message: Some("accepted".into()),
         retryable: false,
+        error_kind: None,
     };
+    let rate_limited = SendPayloadResultV1::failure(ProviderErrorKind::RateLimited, "slow down");
+    assert!(rate_limited.retryable);
+    assert_eq!(
+        rate_limited.error_kind.unwrap().error_code(),
+        ErrorCode::RateLimited
+    );
+    let rejected = SendPayloadResultV1::failure(ProviderErrorKind::PayloadTooLarge, "too big");
+    assert!(!rejected.retryable);
+    assert_eq!(
+        serde_json::to_value(&rejected).unwrap()["error_kind"],
+        "payload_too_large"
+    );
+    assert!(SendPayloadResultV1::success().ok);
 
     assert_roundtrip(&encode);
     assert_roundtrip(&send);
     assert_roundtrip(&send_result);
+    assert_roundtrip(&rate_limited);
+}
+
+#[test]
+fn broadcast_dtos_roundtrip() {
+    let payload = ProviderPayloadV1 {
+        content_type: "text/plain".into(),
+        body_b64: "SGVsbG8ge3tuYW1lfX0=".into(),
+        metadata: BTreeMap::new(),
+    };
+    let listed = BroadcastSendInV1 {
+        provider_type: "sms".into(),
+        tenant_id: Some("tenant-1".into()),
+        auth_user: None,
+        payload: payload.clone(),
+        audience: BroadcastAudienceV1::Recipients {
+            recipients: vec![
+                BroadcastRecipientV1::new("+15550001").with_variable("name", json!("Ada")),
+                BroadcastRecipientV1::new("+15550002").with_variable("name", json!("Linus")),
+            ],
+        },
+        throttle_per_second: Some(20),
+        delivery_options: None,
+    };
+    let segment = BroadcastSendInV1 {
+        provider_type: "email".into(),
+        tenant_id: None,
+        auth_user: None,
+        payload,
+        audience: BroadcastAudienceV1::Selector {
+            selector: "segment:trial-users".into(),
+            variables: sample_metadata(),
+        },
+        throttle_per_second: None,
+        delivery_options: None,
+    };
+    assert_roundtrip(&listed);
+    assert_roundtrip(&segment);
+    let value = serde_json::to_value(&segment).unwrap();
+    assert_eq!(value["audience"]["kind"], "selector");
+    assert!(value.get("throttle_per_second").is_none());
+
+    let result = BroadcastSendResultV1 {
+        results: vec![
+            BroadcastRecipientResultV1 {
+                recipient: "+15550001".into(),
+                result: SendPayloadResultV1::success(),
+            },
+            BroadcastRecipientResultV1 {
+                recipient: "+15550002".into(),
+                result: SendPayloadResultV1::failure(ProviderErrorKind::RateLimited, "slow down"),
+            },
+            BroadcastRecipientResultV1 {
+                recipient: "+15550003".into(),
+                result: SendPayloadResultV1::failure(
+                    ProviderErrorKind::RecipientNotFound,
+                    "unknown number",
+                ),
+            },
+        ],
+    };
+    assert_roundtrip(&result);
+    assert!(!result.all_ok());
+    assert_eq!(result.sent_count(), 1);
+    assert_eq!(result.failed().count(), 2);
+    let retry: Vec<_> = result
+        .retryable()
+        .map(|entry| entry.recipient.as_str())
+        .collect();
+    assert_eq!(retry, ["+15550002"]);
 }
 
 #[test]
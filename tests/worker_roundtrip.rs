@@ -28,6 +28,7 @@ fn worker_request_roundtrips() {
         session_id: Some("sess-1".into()),
         thread_id: Some("thread-9".into()),
         payload_json: r#"{"input":"value"}"#.into(),
+        payload_blob: None,
         timestamp_utc: "2025-01-01T00:00:00Z".into(),
     };
 
@@ -41,10 +42,12 @@ fn worker_response_with_messages_roundtrips() {
         WorkerMessage {
             kind: "text".into(),
             payload_json: r#"{"text":"hello"}"#.into(),
+            payload_blob: None,
         },
         WorkerMessage {
             kind: "card".into(),
             payload_json: r#"{"title":"Card"}"#.into(),
+            payload_blob: None,
         },
     ];
     let response = WorkerResponse {
@@ -55,8 +58,118 @@ fn worker_response_with_messages_roundtrips() {
         session_id: None,
         thread_id: Some("thread-1".into()),
         messages,
+        retry_policy: None,
         timestamp_utc: "2025-01-01T00:01:00Z".into(),
     };
 
     assert_roundtrip(&response);
 }
+
+#[test]
+fn worker_v2_stream_validates_and_folds_into_v1() {
+    use greentic_types::worker::v2::{
+        WorkerFrame, WorkerFrameBody, WorkerStreamError, into_v1_response, validate_stream,
+    };
+    use greentic_types::{ErrorCode, Percent};
+
+    let ctx = TenantCtx::new("prod".parse().unwrap(), "tenant-stream".parse().unwrap());
+    let message = |text: &str| WorkerMessage {
+        kind: "text".into(),
+        payload_json: format!(r#"{{"text":"{text}"}}"#),
+        payload_blob: None,
+    };
+    let request = WorkerRequest {
+        version: "1.0".into(),
+        tenant: ctx.clone(),
+        worker_id: "greentic-test-worker".into(),
+        correlation_id: Some("corr-1".into()),
+        session_id: None,
+        thread_id: None,
+        payload_json: "{}".into(),
+        payload_blob: None,
+        timestamp_utc: "2025-01-01T00:00:00Z".into(),
+    };
+    let response = WorkerResponse {
+        version: "1.0".into(),
+        tenant: ctx,
+        worker_id: "greentic-test-worker".into(),
+        correlation_id: Some("corr-1".into()),
+        session_id: None,
+        thread_id: None,
+        messages: vec![message("done")],
+        retry_policy: None,
+        timestamp_utc: "2025-01-01T00:00:05Z".into(),
+    };
+    let at = "2025-01-01T00:00:01Z";
+    let frames = vec![
+        WorkerFrame::start("stream-1", request),
+        WorkerFrame::new(
+            "stream-1",
+            1,
+            at,
+            WorkerFrameBody::Progress {
+                percent: Some(Percent::new(50.0).unwrap()),
+                detail: Some("halfway".into()),
+            },
+        ),
+        WorkerFrame::new("stream-1", 2, at, WorkerFrameBody::Partial(message("a"))),
+        WorkerFrame::new("stream-1", 3, at, WorkerFrameBody::Heartbeat),
+        WorkerFrame::complete("stream-1", 4, response),
+    ];
+    for frame in &frames {
+        assert_roundtrip(frame);
+    }
+    assert_eq!(
+        serde_json::to_value(&frames[3]).unwrap()["body"],
+        serde_json::json!({"type": "heartbeat"})
+    );
+    validate_stream(&frames).unwrap();
+    assert!(frames[4].is_terminal());
+
+    let folded = into_v1_response(frames.clone()).unwrap();
+    assert_eq!(folded.messages, vec![message("a"), message("done")]);
+    assert!(into_v1_response(frames[..4].to_vec()).is_none());
+
+    let mut cancelled = frames[..3].to_vec();
+    cancelled.push(WorkerFrame::new(
+        "stream-1",
+        3,
+        at,
+        WorkerFrameBody::CancelAcknowledged,
+    ));
+    assert_eq!(
+        validate_stream(&cancelled),
+        Err(WorkerStreamError::UnrequestedCancelAck { seq: 3 })
+    );
+    cancelled.insert(
+        3,
+        WorkerFrame::new("stream-1", 3, at, WorkerFrameBody::Cancel { reason: None }),
+    );
+    cancelled[4].seq = 4;
+    validate_stream(&cancelled).unwrap();
+
+    let mut after_end = cancelled;
+    after_end.push(WorkerFrame::new(
+        "stream-1",
+        5,
+        at,
+        WorkerFrameBody::Failed {
+            code: ErrorCode::Internal,
+            message: "late".into(),
+        },
+    ));
+    assert_eq!(
+        validate_stream(&after_end),
+        Err(WorkerStreamError::AfterTerminal { seq: 5 })
+    );
+    assert_eq!(
+        validate_stream(&frames[1..]),
+        Err(WorkerStreamError::MissingStart)
+    );
+    let mut reordered = frames.clone();
+    reordered.swap(1, 2);
+    assert!(matches!(
+        validate_stream(&reordered),
+        Err(WorkerStreamError::OutOfOrder { .. })
+    ));
+}